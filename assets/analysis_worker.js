@@ -0,0 +1,20 @@
+// Runs workflow layout and analysis off the main thread.
+//
+// The first message is the URL of the app's wasm-bindgen glue; every later
+// message is a JSON analysis request, answered with one JSON message per
+// result (see src/analysis_worker).
+let handler = null;
+
+self.onmessage = (event) => {
+  if (handler === null) {
+    handler = import(event.data).then(async (glue) => {
+      await glue.default();
+      return glue.oya_analysis_worker_handle;
+    });
+    // Surface load failures as a worker error so the page falls back to
+    // running jobs itself.
+    handler.catch((error) => setTimeout(() => { throw error; }));
+    return;
+  }
+  handler.then((handle) => handle(event.data, (reply) => self.postMessage(reply)));
+};
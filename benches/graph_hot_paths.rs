@@ -0,0 +1,107 @@
+//! Benchmarks for the editor's hot paths on large synthetic workflows.
+//!
+//! Run with `cargo bench --features testing --bench graph_hot_paths`. Save a
+//! baseline with `-- --save-baseline main` and compare a branch against it
+//! with `-- --baseline main`; changes beyond the 5% noise threshold are
+//! reported as regressions. The coarse budgets in
+//! `graph::testing::synthetic` run with the unit tests.
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use oya_frontend::graph::testing::synthetic::{deep_config, large_workflow};
+use oya_frontend::ui::edges::edge_paths;
+use oya_frontend::ui::editor_interactions::nodes_in_rect;
+use serde_json::json;
+
+const GRAPH_SIZES: [usize; 3] = [1_000, 5_000, 10_000];
+
+fn bench_prepare_run(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prepare_run");
+    group.sample_size(20);
+    for size in GRAPH_SIZES {
+        let workflow = large_workflow(size);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &workflow,
+            |b, workflow| {
+                b.iter_batched_ref(
+                    || workflow.clone(),
+                    |workflow| black_box(workflow.prepare_run()),
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_resolve_expressions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resolve_expressions");
+    let mut workflow = large_workflow(1_000);
+    if let Some(node) = workflow.nodes.last_mut() {
+        node.last_output = Some(json!({ "value": 42 }));
+    }
+    let name = workflow
+        .nodes
+        .last()
+        .map(|node| node.name.clone())
+        .unwrap_or_default();
+    for depth in [10, 50, 100] {
+        let config = deep_config(depth, &name);
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &config, |b, config| {
+            b.iter(|| black_box(workflow.resolve_expressions(config)));
+        });
+    }
+    group.finish();
+}
+
+fn bench_marquee(c: &mut Criterion) {
+    let mut group = c.benchmark_group("marquee_hit_test");
+    for size in GRAPH_SIZES {
+        let workflow = large_workflow(size);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &workflow,
+            |b, workflow| {
+                b.iter(|| black_box(nodes_in_rect(&workflow.nodes, (0.0, 0.0, 5_000.0, 1_500.0))));
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_edge_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("edge_paths");
+    group.sample_size(20);
+    for size in GRAPH_SIZES {
+        let workflow = large_workflow(size);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &workflow,
+            |b, workflow| {
+                b.iter(|| black_box(edge_paths(&workflow.connections, &workflow.nodes)));
+            },
+        );
+    }
+    group.finish();
+}
+
+fn config() -> Criterion {
+    Criterion::default()
+        .noise_threshold(0.05)
+        .measurement_time(Duration::from_secs(5))
+}
+
+criterion_group! {
+    name = benches;
+    config = config();
+    targets = bench_prepare_run, bench_resolve_expressions, bench_marquee, bench_edge_paths
+}
+criterion_main!(benches);
@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+mod schema;
+
+pub use schema::{
+    FeedbackEnvelope, FeedbackSchemaError, AGENT_FEEDBACK_SCHEMA, AGENT_FEEDBACK_SCHEMA_VERSION,
+};
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, clap::ValueEnum)]
 pub enum FailureCategory {
     #[serde(rename = "spec")]
@@ -21,13 +27,72 @@ pub struct FeedbackRequest {
     pub failure_context: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AgentFeedback {
     pub message: String,
     pub category: String,
     pub priority: String,
     pub hints: Vec<String>,
     pub spec_reference: String,
+    /// Concrete next steps, most specific first.
+    #[serde(default)]
+    pub actions: Vec<RemediationAction>,
+}
+
+/// What an orchestrating agent is asked to do. New kinds may be added in
+/// minor schema versions, so consumers should skip kinds they don't know.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemediationKind {
+    AddEdgeCase,
+    HandleDependencyError,
+    AddObservableOutcome,
+    ReturnStatus,
+    EnforceInvariant,
+    ValidateInput,
+    FixIntegration,
+    ReviewBehavior,
+}
+
+impl RemediationKind {
+    pub const ALL: [Self; 8] = [
+        Self::AddEdgeCase,
+        Self::HandleDependencyError,
+        Self::AddObservableOutcome,
+        Self::ReturnStatus,
+        Self::EnforceInvariant,
+        Self::ValidateInput,
+        Self::FixIntegration,
+        Self::ReviewBehavior,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemediationAction {
+    pub kind: RemediationKind,
+    pub description: String,
+    /// What the action applies to: a dependency, a status code, a
+    /// behavior.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+impl RemediationAction {
+    fn new(kind: RemediationKind, description: impl Into<String>) -> Self {
+        Self {
+            kind,
+            description: description.into(),
+            target: None,
+        }
+    }
+
+    fn targeting(kind: RemediationKind, target: &str, description: impl Into<String>) -> Self {
+        Self {
+            kind,
+            description: description.into(),
+            target: Some(target.to_string()),
+        }
+    }
 }
 
 pub struct FeedbackGenerator {
@@ -134,9 +199,70 @@ impl FeedbackGenerator {
             priority,
             hints: template.hints,
             spec_reference: request.spec_ref.clone(),
+            actions: Self::remediation_actions(request),
         }
     }
 
+    /// Actions read from the failure context first, then the category's
+    /// standing actions.
+    fn remediation_actions(request: &FeedbackRequest) -> Vec<RemediationAction> {
+        use RemediationKind as Kind;
+
+        let context = request.failure_context.as_str();
+        let mut actions = Vec::new();
+        if let Some(dependency) = word_after(context, "dependency ") {
+            actions.push(RemediationAction::targeting(
+                Kind::AddEdgeCase,
+                dependency,
+                format!("Add an edge case for dependency {dependency} failing"),
+            ));
+        }
+        if context.contains("404") {
+            actions.push(RemediationAction::targeting(
+                Kind::ReturnStatus,
+                "404",
+                "Return 404 on missing resource",
+            ));
+        }
+        if context.contains("500") {
+            actions.push(RemediationAction::targeting(
+                Kind::HandleDependencyError,
+                "500",
+                "Handle the error instead of returning 500",
+            ));
+        }
+
+        actions.extend(match request.failure_category {
+            FailureCategory::Spec => vec![
+                RemediationAction::new(
+                    Kind::HandleDependencyError,
+                    "Specify error handling for every dependency",
+                ),
+                RemediationAction::new(
+                    Kind::AddObservableOutcome,
+                    "Add observable outcomes to every behavior",
+                ),
+            ],
+            FailureCategory::Validation => vec![RemediationAction::targeting(
+                Kind::ReviewBehavior,
+                &request.spec_ref,
+                "Check the failed behavior against its acceptance criteria",
+            )],
+            FailureCategory::Security => vec![
+                RemediationAction::new(
+                    Kind::EnforceInvariant,
+                    "Enforce the spec's security invariants",
+                ),
+                RemediationAction::new(Kind::ValidateInput, "Validate and reject malformed input"),
+            ],
+            FailureCategory::Integration => vec![RemediationAction::new(
+                Kind::FixIntegration,
+                "Match the external service's API contract",
+            )],
+        });
+        actions
+    }
+
     fn category_to_key(category: FailureCategory) -> String {
         match category {
             FailureCategory::Spec => "spec-quality".to_string(),
@@ -160,6 +286,16 @@ impl FeedbackGenerator {
     }
 }
 
+/// The word following `marker` in `text`, without trailing punctuation.
+fn word_after<'a>(text: &'a str, marker: &str) -> Option<&'a str> {
+    let (_, rest) = text.split_once(marker)?;
+    let word = rest
+        .split_whitespace()
+        .next()?
+        .trim_end_matches(|c: char| !c.is_alphanumeric());
+    (!word.is_empty()).then_some(word)
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -281,4 +417,77 @@ mod tests {
         assert!(!feedback.spec_reference.is_empty());
         assert_eq!(feedback.spec_reference, "spec-001");
     }
+
+    #[test]
+    fn feedback_generator_derives_remediation_actions_from_context() {
+        let generator = FeedbackGenerator::new();
+
+        let request = FeedbackRequest {
+            failure_category: FailureCategory::Validation,
+            spec_ref: "spec-001".to_string(),
+            iteration: 2,
+            failure_context: "GET /orders/9 returned 404 after dependency payments, timed out"
+                .to_string(),
+        };
+
+        let feedback = generator.generate(&request);
+
+        let actions = feedback
+            .actions
+            .iter()
+            .map(|action| (action.kind, action.target.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            actions,
+            vec![
+                (RemediationKind::AddEdgeCase, Some("payments")),
+                (RemediationKind::ReturnStatus, Some("404")),
+                (RemediationKind::ReviewBehavior, Some("spec-001")),
+            ]
+        );
+    }
+
+    #[test]
+    fn feedback_envelope_round_trips_and_rejects_other_major_versions() {
+        let generator = FeedbackGenerator::new();
+        let feedback = generator.generate(&FeedbackRequest {
+            failure_category: FailureCategory::Security,
+            spec_ref: "spec-003".to_string(),
+            iteration: 1,
+            failure_context: "Security issue".to_string(),
+        });
+
+        let json = FeedbackEnvelope::new(vec![feedback]).to_json().unwrap();
+        let envelope = FeedbackEnvelope::from_json(&json).unwrap();
+        let newer_minor = json.replace("\"1.0.0\"", "\"1.4.0\"");
+        let next_major = json.replace("\"1.0.0\"", "\"2.0.0\"");
+
+        assert_eq!(envelope.schema_version, AGENT_FEEDBACK_SCHEMA_VERSION);
+        assert_eq!(
+            envelope.feedback[0].actions[0].kind,
+            RemediationKind::EnforceInvariant
+        );
+        assert!(json.contains("\"kind\": \"enforce_invariant\""));
+        assert!(FeedbackEnvelope::from_json(&newer_minor).is_ok());
+        assert!(matches!(
+            FeedbackEnvelope::from_json(&next_major),
+            Err(FeedbackSchemaError::IncompatibleVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn agent_feedback_schema_lists_every_action_kind() {
+        let schema: serde_json::Value = serde_yaml::from_str(AGENT_FEEDBACK_SCHEMA).unwrap();
+
+        let kinds = schema
+            .pointer("/definitions/action/properties/kind/enum")
+            .and_then(serde_json::Value::as_array)
+            .unwrap();
+        let expected = RemediationKind::ALL
+            .iter()
+            .map(|kind| serde_json::to_value(kind).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(kinds, &expected);
+    }
 }
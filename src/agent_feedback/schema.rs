@@ -0,0 +1,66 @@
+//! Versioned JSON envelope for [`AgentFeedback`], described by
+//! `specs/schema/agent-feedback.schema.yaml`.
+//!
+//! Minor versions only add optional fields or action kinds; a reader
+//! accepts any document with the same major version.
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::AgentFeedback;
+
+pub const AGENT_FEEDBACK_SCHEMA_VERSION: &str = "1.0.0";
+
+/// The JSON Schema, as YAML, that [`FeedbackEnvelope`] documents follow.
+pub const AGENT_FEEDBACK_SCHEMA: &str =
+    include_str!("../../specs/schema/agent-feedback.schema.yaml");
+
+#[derive(Debug, Error)]
+pub enum FeedbackSchemaError {
+    #[error("Invalid feedback document: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Unsupported feedback schema version {found}; this reader supports {supported}")]
+    IncompatibleVersion { found: String, supported: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackEnvelope {
+    pub schema_version: String,
+    pub feedback: Vec<AgentFeedback>,
+}
+
+impl FeedbackEnvelope {
+    #[must_use]
+    pub fn new(feedback: Vec<AgentFeedback>) -> Self {
+        Self {
+            schema_version: AGENT_FEEDBACK_SCHEMA_VERSION.to_string(),
+            feedback,
+        }
+    }
+
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Parse a document written by this or a compatible version.
+    ///
+    /// # Errors
+    /// Returns an error if the document is not valid JSON for the envelope
+    /// or its major version differs from this reader's.
+    pub fn from_json(json: &str) -> Result<Self, FeedbackSchemaError> {
+        let envelope: Self = serde_json::from_str(json)?;
+        if major(&envelope.schema_version) != major(AGENT_FEEDBACK_SCHEMA_VERSION) {
+            return Err(FeedbackSchemaError::IncompatibleVersion {
+                found: envelope.schema_version,
+                supported: AGENT_FEEDBACK_SCHEMA_VERSION.to_string(),
+            });
+        }
+        Ok(envelope)
+    }
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or_default()
+}
@@ -0,0 +1,180 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! The browser side of the analysis worker.
+//!
+//! The worker is `assets/analysis_worker.js`, a module worker that loads
+//! this same wasm build and calls [`oya_analysis_worker_handle`] for each
+//! request. Where workers are unavailable, or the worker fails, jobs run on
+//! the main thread after yielding to the event loop, so results still
+//! arrive through the same callback.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dioxus::prelude::{asset, manganis, Asset};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{MessageEvent, Worker, WorkerOptions, WorkerType};
+
+use super::{handle_message, run_job, AnalysisMessage, AnalysisRequest};
+
+const WORKER_SCRIPT: Asset = asset!("/assets/analysis_worker.js");
+
+/// Entry point the worker script calls with a JSON request; `post` receives
+/// each JSON reply.
+#[wasm_bindgen]
+pub fn oya_analysis_worker_handle(request: &str, post: &js_sys::Function) {
+    handle_message(request, |reply| {
+        let _ = post.call1(&JsValue::NULL, &JsValue::from_str(&reply));
+    });
+}
+
+type Listener = Rc<dyn Fn(AnalysisMessage)>;
+
+struct Connection {
+    worker: Worker,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+/// Posts analysis requests to the worker and hands every reply to one
+/// listener.
+pub struct AnalysisWorker {
+    connection: Option<Connection>,
+    failed: Rc<Cell<bool>>,
+    /// Requests without a final reply, replayed inline if the worker fails.
+    in_flight: Rc<RefCell<HashMap<u64, AnalysisRequest>>>,
+    listener: Listener,
+}
+
+impl AnalysisWorker {
+    /// Starts the worker. Falls back to running jobs inline when it cannot
+    /// be started.
+    pub fn spawn(listener: impl Fn(AnalysisMessage) + 'static) -> Self {
+        let listener: Listener = Rc::new(listener);
+        let failed = Rc::new(Cell::new(false));
+        let in_flight = Rc::new(RefCell::new(HashMap::new()));
+        let connection = connect(&listener, &failed, &in_flight);
+        Self {
+            connection,
+            failed,
+            in_flight,
+            listener,
+        }
+    }
+
+    pub fn post(&self, request: AnalysisRequest) {
+        let worker = self
+            .connection
+            .as_ref()
+            .filter(|_| !self.failed.get())
+            .map(|connection| &connection.worker);
+        let Some(worker) = worker else {
+            run_inline(request, Rc::clone(&self.listener));
+            return;
+        };
+        let Ok(json) = serde_json::to_string(&request) else {
+            return;
+        };
+        self.in_flight.borrow_mut().insert(request.id, request);
+        if worker.post_message(&JsValue::from_str(&json)).is_err() {
+            fail_over(&self.failed, &self.in_flight, &self.listener);
+        }
+    }
+}
+
+fn connect(
+    listener: &Listener,
+    failed: &Rc<Cell<bool>>,
+    in_flight: &Rc<RefCell<HashMap<u64, AnalysisRequest>>>,
+) -> Option<Connection> {
+    let glue = glue_script_url()?;
+    let options = WorkerOptions::new();
+    options.set_type(WorkerType::Module);
+    let worker = Worker::new_with_options(&WORKER_SCRIPT.to_string(), &options).ok()?;
+
+    let on_message = {
+        let (listener, in_flight) = (Rc::clone(listener), Rc::clone(in_flight));
+        Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(message) = event
+                .data()
+                .as_string()
+                .and_then(|json| serde_json::from_str::<AnalysisMessage>(&json).ok())
+            else {
+                return;
+            };
+            if message.update.is_final() {
+                in_flight.borrow_mut().remove(&message.id);
+            }
+            listener(message);
+        })
+    };
+    let on_error = {
+        let (listener, failed, in_flight) =
+            (Rc::clone(listener), Rc::clone(failed), Rc::clone(in_flight));
+        Closure::<dyn FnMut(web_sys::Event)>::new(move |_event: web_sys::Event| {
+            fail_over(&failed, &in_flight, &listener);
+        })
+    };
+    worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    worker.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+    // The first message tells the worker where to load the wasm from.
+    worker.post_message(&JsValue::from_str(&glue)).ok()?;
+    Some(Connection {
+        worker,
+        _on_message: on_message,
+        _on_error: on_error,
+    })
+}
+
+/// Stops using the worker and reruns whatever it had not finished.
+fn fail_over(
+    failed: &Cell<bool>,
+    in_flight: &RefCell<HashMap<u64, AnalysisRequest>>,
+    listener: &Listener,
+) {
+    failed.set(true);
+    let pending: Vec<AnalysisRequest> = in_flight.borrow_mut().drain().map(|(_, r)| r).collect();
+    for request in pending {
+        run_inline(request, Rc::clone(listener));
+    }
+}
+
+fn run_inline(request: AnalysisRequest, listener: Listener) {
+    wasm_bindgen_futures::spawn_local(async move {
+        // Let the current frame paint before blocking on the job.
+        gloo_timers::future::TimeoutFuture::new(0).await;
+        let id = request.id;
+        run_job(request.job, |update| {
+            listener(AnalysisMessage { id, update })
+        });
+    });
+}
+
+/// URL of the app's wasm-bindgen JS glue, which the worker imports. Its name
+/// is hashed by the bundler, so it is found among the page's loaded
+/// resources.
+fn glue_script_url() -> Option<String> {
+    let performance = web_sys::window()?.performance()?;
+    performance
+        .get_entries_by_type("resource")
+        .iter()
+        .filter_map(|entry| entry.dyn_into::<web_sys::PerformanceEntry>().ok())
+        .map(|entry| entry.name())
+        .find(|name| {
+            let path = name.split(['?', '#']).next().unwrap_or_default();
+            path.contains("/wasm/") && path.ends_with(".js")
+        })
+}
@@ -0,0 +1,287 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! Layout and analysis jobs that can run off the main thread.
+//!
+//! DAG layout, compilation and extension suggestions take long enough on
+//! big graphs to drop canvas frames. The editor sends them as
+//! [`AnalysisRequest`]s to a web worker, which runs [`run_job`] and streams
+//! each result back as soon as it is ready, so diagnostics show up before
+//! suggestions are done. Requests and messages are JSON so the same code
+//! runs on either side of `postMessage`, and natively in tests.
+
+#[cfg(target_arch = "wasm32")]
+pub mod bridge;
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::flow_extender::{suggest_extensions, FlowExtension};
+use crate::graph::compile::{compile_workflow, CompileReport, SeverityGate};
+use crate::graph::{Node, NodeId, Workflow};
+
+/// Graphs smaller than this are analysed inline; posting them to a worker
+/// costs more than it saves.
+pub const OFFLOAD_NODE_THRESHOLD: usize = 150;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Layout,
+    Analyze,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalysisJob {
+    /// Auto-layout; answers with node positions.
+    Layout { workflow: Workflow },
+    /// Compile under `gate`, then suggest extensions.
+    Analyze {
+        workflow: Workflow,
+        gate: SeverityGate,
+    },
+}
+
+impl AnalysisJob {
+    #[must_use]
+    pub const fn kind(&self) -> JobKind {
+        match self {
+            Self::Layout { .. } => JobKind::Layout,
+            Self::Analyze { .. } => JobKind::Analyze,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisRequest {
+    pub id: u64,
+    pub job: AnalysisJob,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NodePosition {
+    pub id: NodeId,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// One streamed result. Every job ends with `Done` or `Failed`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "update", content = "data", rename_all = "snake_case")]
+pub enum AnalysisUpdate {
+    Positions(Vec<NodePosition>),
+    Compiled(CompileReport),
+    Suggestions(Vec<FlowExtension>),
+    Done,
+    Failed(String),
+}
+
+impl AnalysisUpdate {
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(self, Self::Done | Self::Failed(_))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisMessage {
+    /// The request this answers; 0 when the request could not be read.
+    pub id: u64,
+    pub update: AnalysisUpdate,
+}
+
+/// Runs `job`, handing each result to `emit` as soon as it is computed.
+pub fn run_job(job: AnalysisJob, mut emit: impl FnMut(AnalysisUpdate)) {
+    match job {
+        AnalysisJob::Layout { mut workflow } => {
+            workflow.nodes.iter_mut().for_each(Node::rehydrate);
+            workflow.apply_layout();
+            emit(AnalysisUpdate::Positions(
+                workflow
+                    .nodes
+                    .iter()
+                    .map(|node| NodePosition {
+                        id: node.id,
+                        x: node.x,
+                        y: node.y,
+                    })
+                    .collect(),
+            ));
+        }
+        AnalysisJob::Analyze { mut workflow, gate } => {
+            workflow.nodes.iter_mut().for_each(Node::rehydrate);
+            emit(AnalysisUpdate::Compiled(compile_workflow(&workflow, gate)));
+            emit(AnalysisUpdate::Suggestions(suggest_extensions(&workflow)));
+        }
+    }
+    emit(AnalysisUpdate::Done);
+}
+
+/// The worker side of the bridge: reads a JSON request and replies with
+/// one JSON [`AnalysisMessage`] per result.
+pub fn handle_message(request_json: &str, mut reply: impl FnMut(String)) {
+    let mut send = |message: &AnalysisMessage| {
+        if let Ok(json) = serde_json::to_string(message) {
+            reply(json);
+        }
+    };
+    match serde_json::from_str::<AnalysisRequest>(request_json) {
+        Ok(request) => run_job(request.job, |update| {
+            send(&AnalysisMessage {
+                id: request.id,
+                update,
+            });
+        }),
+        Err(error) => send(&AnalysisMessage {
+            id: 0,
+            update: AnalysisUpdate::Failed(format!("Unreadable analysis request: {error}")),
+        }),
+    }
+}
+
+/// Numbers requests and drops results that a newer job of the same kind
+/// has superseded, e.g. diagnostics for a graph that has since changed.
+#[derive(Debug, Clone, Default)]
+pub struct JobTracker {
+    next_id: u64,
+    running: HashMap<JobKind, u64>,
+}
+
+impl JobTracker {
+    pub fn start(&mut self, job: AnalysisJob) -> AnalysisRequest {
+        self.next_id += 1;
+        self.running.insert(job.kind(), self.next_id);
+        AnalysisRequest {
+            id: self.next_id,
+            job,
+        }
+    }
+
+    /// Whether `message` answers a current job; a final message also
+    /// finishes that job.
+    pub fn accept(&mut self, message: &AnalysisMessage) -> bool {
+        let Some(kind) = self
+            .running
+            .iter()
+            .find_map(|(kind, id)| (*id == message.id).then_some(*kind))
+        else {
+            return false;
+        };
+        if message.update.is_final() {
+            self.running.remove(&kind);
+        }
+        true
+    }
+
+    #[must_use]
+    pub fn is_running(&self, kind: JobKind) -> bool {
+        self.running.contains_key(&kind)
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::synthetic::large_workflow;
+
+    fn round_trip(request: &AnalysisRequest) -> Vec<AnalysisMessage> {
+        let mut replies = Vec::new();
+        handle_message(&serde_json::to_string(request).unwrap(), |json| {
+            replies.push(serde_json::from_str(&json).unwrap());
+        });
+        replies
+    }
+
+    #[test]
+    fn given_analyze_request_when_handled_then_results_stream_in_order_and_match_inline() {
+        let workflow = large_workflow(40);
+        let mut tracker = JobTracker::default();
+        let request = tracker.start(AnalysisJob::Analyze {
+            workflow: workflow.clone(),
+            gate: SeverityGate::default(),
+        });
+
+        let replies = round_trip(&request);
+
+        assert_eq!(replies.len(), 3);
+        assert!(replies.iter().all(|message| tracker.accept(message)));
+        assert!(!tracker.is_running(JobKind::Analyze));
+        assert_eq!(
+            replies[0].update,
+            AnalysisUpdate::Compiled(compile_workflow(&workflow, SeverityGate::default()))
+        );
+        assert_eq!(
+            replies[1].update,
+            AnalysisUpdate::Suggestions(suggest_extensions(&workflow))
+        );
+        assert_eq!(replies[2].update, AnalysisUpdate::Done);
+    }
+
+    #[test]
+    fn given_layout_request_when_handled_then_positions_match_inline_layout() {
+        let workflow = large_workflow(30);
+        let mut expected = workflow.clone();
+        expected.apply_layout();
+
+        let replies = round_trip(&AnalysisRequest {
+            id: 7,
+            job: AnalysisJob::Layout { workflow },
+        });
+
+        let AnalysisUpdate::Positions(positions) = &replies[0].update else {
+            panic!("layout should answer with positions");
+        };
+        assert_eq!(positions.len(), expected.nodes.len());
+        for (position, node) in positions.iter().zip(&expected.nodes) {
+            assert_eq!(
+                (position.id, position.x, position.y),
+                (node.id, node.x, node.y)
+            );
+        }
+        assert!(replies.iter().all(|message| message.id == 7));
+    }
+
+    #[test]
+    fn given_superseded_job_when_its_results_arrive_then_they_are_dropped() {
+        let workflow = large_workflow(5);
+        let mut tracker = JobTracker::default();
+        let stale = tracker.start(AnalysisJob::Analyze {
+            workflow: workflow.clone(),
+            gate: SeverityGate::default(),
+        });
+        let layout = tracker.start(AnalysisJob::Layout {
+            workflow: workflow.clone(),
+        });
+        let current = tracker.start(AnalysisJob::Analyze {
+            workflow,
+            gate: SeverityGate::default(),
+        });
+
+        let done = |id| AnalysisMessage {
+            id,
+            update: AnalysisUpdate::Done,
+        };
+        assert!(!tracker.accept(&done(stale.id)));
+        assert!(tracker.is_running(JobKind::Layout));
+        assert!(tracker.accept(&done(current.id)));
+        assert!(tracker.accept(&done(layout.id)));
+        assert!(!tracker.is_running(JobKind::Analyze));
+
+        let mut replies = Vec::new();
+        handle_message("{not json", |json| replies.push(json));
+        let failed: AnalysisMessage = serde_json::from_str(&replies[0]).unwrap();
+        assert_eq!(failed.id, 0);
+        assert!(matches!(failed.update, AnalysisUpdate::Failed(_)));
+    }
+}
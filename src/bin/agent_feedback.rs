@@ -1,7 +1,9 @@
 #[cfg(not(target_arch = "wasm32"))]
 use clap::{Parser, Subcommand};
 #[cfg(not(target_arch = "wasm32"))]
-use oya_frontend::agent_feedback::{FailureCategory, FeedbackGenerator, FeedbackRequest};
+use oya_frontend::agent_feedback::{
+    FailureCategory, FeedbackEnvelope, FeedbackGenerator, FeedbackRequest,
+};
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::PathBuf;
 
@@ -25,6 +27,9 @@ enum Commands {
         spec_ref: Option<String>,
         #[arg(long)]
         iteration: u32,
+        /// Print the versioned JSON document instead of the message
+        #[arg(long)]
+        json: bool,
     },
 
     #[command(about = "Generate feedback from validation results")]
@@ -33,6 +38,9 @@ enum Commands {
         spec_id: String,
         #[arg(long)]
         validation_results_path: PathBuf,
+        /// Print the versioned JSON document instead of text
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -46,6 +54,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             category,
             spec_ref,
             iteration,
+            json,
         } => {
             let request = FeedbackRequest {
                 failure_category: category,
@@ -58,12 +67,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             };
 
             let feedback = generator.generate(&request);
-            println!("{}", feedback.message);
+            if json {
+                println!("{}", FeedbackEnvelope::new(vec![feedback]).to_json()?);
+            } else {
+                println!("{}", feedback.message);
+            }
         }
 
         Commands::Batch {
             spec_id,
             validation_results_path,
+            json,
         } => {
             let spec_content = std::fs::read_to_string(&validation_results_path)?;
             let validation: serde_json::Value = serde_json::from_str(&spec_content)?;
@@ -88,6 +102,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
 
             let feedback_batch = generator.generate_batch(&requests);
+            if json {
+                println!("{}", FeedbackEnvelope::new(feedback_batch).to_json()?);
+                return Ok(());
+            }
             for feedback in &feedback_batch {
                 println!("--- FEEDBACK: {} ---", feedback.category);
                 println!("Priority: {}", feedback.priority);
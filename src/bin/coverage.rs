@@ -1,9 +1,11 @@
 #[cfg(not(target_arch = "wasm32"))]
 use clap::Parser;
 #[cfg(not(target_arch = "wasm32"))]
-use oya_frontend::coverage::{CoverageAnalyzer, CoverageReport};
+use oya_frontend::coverage::{CoverageAnalyzer, CoverageReport, CoverageThresholds, ReportFormat};
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::str::FromStr;
 
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Parser)]
@@ -18,30 +20,57 @@ struct Args {
 
     #[arg(short = 'f', long, default_value = "text")]
     format: String,
+
+    /// Write the rendered report to this file instead of stdout.
+    #[arg(short = 'o', long)]
+    output: Option<PathBuf>,
+
+    /// Fail if overall behavior coverage is below this percentage.
+    #[arg(long)]
+    min_overall: Option<f64>,
+
+    /// Fail if any spec's behavior coverage is below this percentage.
+    #[arg(long)]
+    min_spec: Option<f64>,
+
+    /// Fail if edge-case coverage is below this percentage.
+    #[arg(long)]
+    min_edge_cases: Option<f64>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
+    let thresholds = CoverageThresholds {
+        overall: args.min_overall,
+        per_spec: args.min_spec,
+        edge_cases: args.min_edge_cases,
+    };
     let analyzer = CoverageAnalyzer::new(&args.specs_dir, &args.scenarios_dir);
-    let report = analyzer.analyze()?;
+    let outcome = analyzer.analyze_with_policy(&thresholds)?;
 
-    match args.format.as_str() {
-        "json" => {
-            let json = serde_json::to_string_pretty(&report)?;
-            println!("{json}");
-        }
-        "text" => {
-            println!("Analyzing scenario coverage...");
-            print_text_report(&report);
-        }
-        _ => {
+    if args.format == "text" {
+        println!("Analyzing scenario coverage...");
+        print_text_report(&outcome.report);
+    } else {
+        let Ok(format) = ReportFormat::from_str(&args.format) else {
             eprintln!("Unsupported format: {}", args.format);
-            return Err("Use 'json' or 'text'".into());
+            return Err("Use 'json', 'html', 'markdown' or 'text'".into());
+        };
+        match &args.output {
+            Some(path) => outcome.report.write_to(format, path)?,
+            None => print!("{}", outcome.report.render(format)?),
         }
     }
 
+    if !outcome.passed() {
+        for violation in &outcome.violations {
+            eprintln!("Coverage threshold violated: {violation}");
+        }
+        return Err(format!("{} coverage threshold(s) not met", outcome.violations.len()).into());
+    }
+
     Ok(())
 }
 
@@ -74,6 +103,34 @@ fn print_text_report(report: &CoverageReport) {
         }
     }
 
+    let issues = &report.reference_issues;
+    if !issues.is_empty() {
+        println!("  Reference Issues:");
+        for stale in &issues.unknown_behavior_refs {
+            println!(
+                "    - {}: unknown behavior '{}' (spec {})",
+                stale.scenario.path.display(),
+                stale.reference,
+                stale.spec_id
+            );
+        }
+        for stale in &issues.unknown_edge_case_refs {
+            println!(
+                "    - {}: unknown edge case '{}' (spec {})",
+                stale.scenario.path.display(),
+                stale.reference,
+                stale.spec_id
+            );
+        }
+        for orphan in &issues.orphan_scenarios {
+            println!(
+                "    - {}: unknown spec '{}'",
+                orphan.scenario.path.display(),
+                orphan.spec_ref
+            );
+        }
+    }
+
     println!(
         "\n  Totals: {} behaviors, {} edge cases",
         report.total_behaviors, report.total_edge_cases
@@ -5,6 +5,8 @@ use oya_frontend::flow_extender::{apply_extension, preview_extension, suggest_ex
 #[cfg(not(target_arch = "wasm32"))]
 use oya_frontend::graph::Workflow;
 #[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::metrics::{AuditAction, AuditEntry, MetricsStore};
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::PathBuf;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -40,6 +42,9 @@ enum Command {
         /// Optional output path. Defaults to in-place write.
         #[arg(long)]
         output: Option<PathBuf>,
+        /// Directory holding `quality-metrics/`, where the change is audited.
+        #[arg(long, default_value = ".")]
+        metrics_dir: PathBuf,
     },
 }
 
@@ -96,6 +101,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             workflow_path,
             extension_key,
             output,
+            metrics_dir,
         } => {
             let mut workflow = parse_workflow(&workflow_path)?;
             let applied = apply_extension(&mut workflow, &extension_key).map_err(|message| {
@@ -104,6 +110,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             let target_path = output.as_ref().unwrap_or(&workflow_path);
             write_workflow(target_path, &workflow)?;
+            MetricsStore::new(&metrics_dir).record_audit(
+                AuditEntry::new(
+                    AuditAction::ExtensionApplied,
+                    &target_path.display().to_string(),
+                )
+                .with_detail(applied.key.clone()),
+            )?;
 
             println!(
                 "Applied {} and created {} node(s). Saved to {}",
@@ -0,0 +1,133 @@
+#[cfg(not(target_arch = "wasm32"))]
+use clap::Parser;
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::flow_extender::archetype::SpecArchetype;
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::linter::SystemDependency;
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::wizard::{BehaviorDraft, FeatureDraft, FeatureWizard, WorkspaceManifest};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{self, BufRead, Write};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::str::FromStr;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Parser)]
+#[command(name = "new-feature")]
+#[command(about = "Create a spec, workflow, scenarios and twins for a new feature")]
+struct Args {
+    /// Workspace root holding the manifest, specs and workflows.
+    #[arg(short, long, default_value = ".")]
+    root: PathBuf,
+
+    /// Base URL twin endpoints are derived from.
+    #[arg(long)]
+    twin_base_url: Option<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn ask(input: &mut impl BufRead, prompt: &str) -> io::Result<String> {
+    print!("{prompt}: ");
+    io::stdout().flush()?;
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+/// Ask repeatedly until an empty answer, splitting each on the first `:`.
+#[cfg(not(target_arch = "wasm32"))]
+fn ask_pairs(input: &mut impl BufRead, prompt: &str) -> io::Result<Vec<(String, String)>> {
+    let mut pairs = Vec::new();
+    loop {
+        let answer = ask(input, prompt)?;
+        if answer.is_empty() {
+            return Ok(pairs);
+        }
+        match answer.split_once(':') {
+            Some((key, value)) => pairs.push((key.trim().to_string(), value.trim().to_string())),
+            None => println!("  expected `id: description`"),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn ask_draft(input: &mut impl BufRead) -> Result<FeatureDraft, Box<dyn std::error::Error>> {
+    println!("Step 1/5: spec");
+    let id = ask(input, "Feature id (kebab-case)")?;
+    let author = ask(input, "Author")?;
+    let problem_statement = ask(input, "Problem statement")?;
+
+    println!("Step 2/5: workflow");
+    let names = SpecArchetype::all().map(SpecArchetype::as_str).join(", ");
+    let archetype = SpecArchetype::from_str(&ask(input, &format!("Archetype ({names})"))?)?;
+
+    println!("Step 3/5: behaviors (blank line to finish)");
+    let behaviors = ask_pairs(input, "Behavior `id: description`")?
+        .into_iter()
+        .map(|(id, description)| BehaviorDraft { id, description })
+        .collect();
+
+    println!("Step 4/5: dependencies (blank line to finish; append `[twin]` if one exists)");
+    let dependencies = ask_pairs(input, "Dependency `service: purpose`")?
+        .into_iter()
+        .map(|(service, purpose)| {
+            let twin_available = purpose.ends_with("[twin]");
+            SystemDependency {
+                service,
+                purpose: purpose.trim_end_matches("[twin]").trim().to_string(),
+                twin_available,
+            }
+        })
+        .collect();
+
+    Ok(FeatureDraft {
+        id,
+        author,
+        problem_statement,
+        archetype,
+        behaviors,
+        dependencies,
+    })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let mut manifest = WorkspaceManifest::load(&args.root)?;
+
+    let draft = ask_draft(&mut io::stdin().lock())?;
+    let mut wizard = FeatureWizard::new(draft)?;
+    if let Some(base_url) = &args.twin_base_url {
+        wizard = wizard.with_twin_base_url(base_url);
+    }
+    let created = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let bundle = wizard.build(&manifest, &created)?;
+
+    println!("Step 5/5: register");
+    let entry = bundle.write(&args.root, &mut manifest)?;
+
+    println!("Created feature {}:", entry.id);
+    println!("  spec      {}", entry.spec.display());
+    println!(
+        "  workflow  {} ({} nodes)",
+        entry.workflow.display(),
+        bundle.workflow.nodes.len()
+    );
+    for path in &entry.scenarios {
+        println!("  scenario  {}", path.display());
+    }
+    println!("  universe  {}", entry.universe);
+    for preset in &bundle.recommended_presets {
+        println!("Recommended preset: {} — {}", preset.key, preset.title);
+    }
+    for service in &bundle.untwinned {
+        println!("Warning: {service} has no twin; scenarios will call the real service.");
+    }
+
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
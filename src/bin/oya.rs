@@ -0,0 +1,534 @@
+#[cfg(not(target_arch = "wasm32"))]
+use clap::{Parser, Subcommand};
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::coverage::{CoverageAnalyzer, ReportFormat};
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::headless;
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::linter::{LintConfig, SpecLinter};
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::metrics::{AuditAction, AuditEntry, AuditQuery, MetricsStore};
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::scenario_runner::{
+    run_validation_with, scaffold_scenarios, RunOptions, ScenarioFilter, ValidationReportFormat,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::twin_client::TwinInspectionClient;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::str::FromStr;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Parser)]
+#[command(name = "oya")]
+#[command(about = "Lint specs, measure coverage, run scenarios and report metrics")]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+    /// OTLP/HTTP collector to export traces to, e.g. `http://localhost:4318`;
+    /// defaults to `OTEL_EXPORTER_OTLP_ENDPOINT`
+    #[cfg(feature = "otel")]
+    #[arg(long, global = true)]
+    otlp_endpoint: Option<String>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Subcommand)]
+enum Commands {
+    /// Lint a specification
+    Lint {
+        spec_path: PathBuf,
+        #[arg(long, default_value = "specs/linter/rules.yaml")]
+        rules_path: PathBuf,
+        /// Lint configuration; defaults to the nearest `.oya-lint.yaml`
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Measure how well scenarios cover spec behaviors
+    Coverage {
+        #[arg(short = 's', long, default_value = "specs")]
+        specs_dir: PathBuf,
+        #[arg(short = 'c', long, default_value = "../scenarios-vault")]
+        scenarios_dir: PathBuf,
+        /// json, html or markdown
+        #[arg(short, long, default_value = "markdown", value_parser = ReportFormat::from_str)]
+        format: ReportFormat,
+    },
+    /// Work with holdout scenarios
+    Scenarios {
+        #[command(subcommand)]
+        command: ScenarioCommands,
+    },
+    /// Inspect a running digital twin
+    Twin {
+        #[command(subcommand)]
+        command: TwinCommands,
+    },
+    /// Report recorded quality gate metrics
+    Metrics {
+        #[command(subcommand)]
+        command: MetricsCommands,
+    },
+    /// Serve the headless workflow execution API (JSON-RPC over HTTP)
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8091")]
+        addr: String,
+        /// Directory holding `quality-metrics/`, where loads and runs are audited
+        #[arg(long, default_value = ".")]
+        metrics_dir: PathBuf,
+        /// Bearer token callers must send; defaults to `OYA_API_TOKEN`, or a
+        /// fresh random token printed at startup
+        #[arg(long)]
+        token: Option<String>,
+        /// Browser origin allowed to call the API, e.g. `http://localhost:8081`
+        #[arg(long = "allow-origin")]
+        allowed_origins: Vec<String>,
+    },
+    /// Serve the http-handler nodes of workflows as local endpoints
+    Webhooks {
+        /// Workflow JSON files
+        #[arg(required = true)]
+        workflow_paths: Vec<PathBuf>,
+        #[arg(long, default_value = "127.0.0.1:8092")]
+        addr: String,
+    },
+    /// Relay live collaboration between editors over WebSockets
+    #[cfg(feature = "collab")]
+    CollabRelay {
+        #[arg(long, default_value = "127.0.0.1:8093")]
+        listen: String,
+        /// Workflow JSON every room starts from; without it the first
+        /// editor to join a room shares theirs
+        #[arg(long)]
+        workflow: Option<PathBuf>,
+    },
+    /// Consume the topics bound to a workflow's kafka-handler nodes
+    #[cfg(feature = "kafka")]
+    Kafka {
+        workflow_path: PathBuf,
+        /// Stop after this many messages per handler
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Directory holding `quality-metrics/`, where runs are audited
+        #[arg(long, default_value = ".")]
+        metrics_dir: PathBuf,
+    },
+    /// Run a workflow on the schedules of its cron-trigger nodes
+    Schedule {
+        /// Workflow JSON; run history is written back after every run
+        workflow_path: PathBuf,
+        /// Wait for the next fire time, run once and exit
+        #[arg(long)]
+        once: bool,
+        /// Directory holding `quality-metrics/`, where runs are audited
+        #[arg(long, default_value = ".")]
+        metrics_dir: PathBuf,
+    },
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Subcommand)]
+enum ScenarioCommands {
+    /// Run a directory of scenarios against an application
+    Run {
+        scenarios_path: PathBuf,
+        #[arg(long, default_value = "http://localhost:8081")]
+        app_endpoint: String,
+        /// Digital twin as `name=endpoint`, for `${twins.<name>.endpoint}`
+        #[arg(long = "twin", value_parser = parse_twin)]
+        twins: Vec<(String, String)>,
+        #[arg(long, default_value = "1")]
+        concurrency: usize,
+        /// Only run scenarios carrying this tag (repeatable)
+        #[arg(long = "tag")]
+        include_tags: Vec<String>,
+        /// json, junit or html
+        #[arg(short, long, default_value = "json", value_parser = ValidationReportFormat::from_str)]
+        format: ValidationReportFormat,
+    },
+    /// Write skeleton scenarios for every behavior of a spec
+    Scaffold {
+        spec_path: PathBuf,
+        #[arg(short, long)]
+        out_dir: PathBuf,
+    },
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Subcommand)]
+enum TwinCommands {
+    /// List a twin's collections
+    Collections { endpoint: String },
+    /// List the records of one collection
+    Records {
+        endpoint: String,
+        collection: String,
+    },
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Subcommand)]
+enum MetricsCommands {
+    /// Print the metrics report
+    Report {
+        /// Directory holding `quality-metrics/`
+        #[arg(long, default_value = ".")]
+        dir: PathBuf,
+        /// json, text or openmetrics
+        #[arg(short, long, default_value = "text")]
+        format: String,
+    },
+    /// Print the audit log of runs, imports, deployments and extensions
+    Audit {
+        /// Directory holding `quality-metrics/`
+        #[arg(long, default_value = ".")]
+        dir: PathBuf,
+        #[arg(long)]
+        workflow: Option<String>,
+        #[arg(long)]
+        actor: Option<String>,
+        /// run, import, deployment or extension_applied
+        #[arg(long, value_parser = AuditAction::from_str)]
+        action: Option<AuditAction>,
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::parse();
+    #[cfg(feature = "otel")]
+    let _telemetry = cli
+        .otlp_endpoint
+        .as_deref()
+        .map(headless::OtlpConfig::new)
+        .or_else(headless::OtlpConfig::from_env)
+        .map(|config| headless::init_telemetry(&config))
+        .transpose()?;
+    match cli.command {
+        Commands::Lint {
+            spec_path,
+            rules_path,
+            config,
+            json,
+        } => {
+            let config = match config {
+                Some(path) => Some(LintConfig::load(&path)?),
+                None => LintConfig::discover(spec_path.parent().unwrap_or(&spec_path))?,
+            };
+            let linter = SpecLinter::new(&rules_path)?.with_config(config.unwrap_or_default())?;
+            let report = linter.lint(&spec_path)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                println!(
+                    "{} v{}: {}/100 {}",
+                    report.spec_id,
+                    report.spec_version,
+                    report.overall_score,
+                    if report.passed { "passed" } else { "failed" }
+                );
+            }
+            if !report.passed {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Coverage {
+            specs_dir,
+            scenarios_dir,
+            format,
+        } => {
+            let report = CoverageAnalyzer::new(&specs_dir, &scenarios_dir).analyze()?;
+            print!("{}", report.render(format)?);
+        }
+
+        Commands::Scenarios {
+            command:
+                ScenarioCommands::Run {
+                    scenarios_path,
+                    app_endpoint,
+                    twins,
+                    concurrency,
+                    include_tags,
+                    format,
+                },
+        } => {
+            let options = RunOptions {
+                concurrency,
+                filter: ScenarioFilter {
+                    include_tags,
+                    ..ScenarioFilter::default()
+                },
+                ..RunOptions::default()
+            };
+            let report = run_validation_with(
+                &scenarios_path,
+                &app_endpoint,
+                twins
+                    .into_iter()
+                    .collect::<std::collections::HashMap<_, _>>(),
+                options,
+            )
+            .await?;
+            print!("{}", report.render(format)?);
+            if report.failed_scenarios > 0 {
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Scenarios {
+            command: ScenarioCommands::Scaffold { spec_path, out_dir },
+        } => {
+            for path in scaffold_scenarios(&spec_path, &out_dir)? {
+                println!("{}", path.display());
+            }
+        }
+
+        Commands::Twin { command } => match command {
+            TwinCommands::Collections { endpoint } => {
+                for collection in TwinInspectionClient::new(&endpoint)
+                    .list_collections()
+                    .await?
+                {
+                    println!("{collection}");
+                }
+            }
+            TwinCommands::Records {
+                endpoint,
+                collection,
+            } => {
+                let records = TwinInspectionClient::new(&endpoint)
+                    .list_records(&collection)
+                    .await?;
+                println!("{}", serde_json::to_string_pretty(&records)?);
+            }
+        },
+
+        Commands::Metrics {
+            command: MetricsCommands::Report { dir, format },
+        } => {
+            println!("{}", MetricsStore::new(&dir).export_report(&format)?);
+        }
+        Commands::Metrics {
+            command:
+                MetricsCommands::Audit {
+                    dir,
+                    workflow,
+                    actor,
+                    action,
+                    json,
+                },
+        } => {
+            let entries = MetricsStore::new(&dir).audit_log(&AuditQuery {
+                workflow,
+                actor,
+                action,
+                ..AuditQuery::default()
+            })?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+            for entry in &entries {
+                let outcome = match entry.success {
+                    Some(true) => " ok",
+                    Some(false) => " failed",
+                    None => "",
+                };
+                println!(
+                    "{} {} {} {}{}{outcome}",
+                    entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                    entry.actor,
+                    entry.action,
+                    entry.workflow,
+                    entry
+                        .detail
+                        .as_deref()
+                        .map_or_else(String::new, |detail| format!(" ({detail})")),
+                );
+            }
+        }
+
+        Commands::Serve {
+            addr,
+            metrics_dir,
+            token,
+            allowed_origins,
+        } => {
+            let token = match token.or_else(|| std::env::var("OYA_API_TOKEN").ok()) {
+                Some(token) => token,
+                None => {
+                    let token = uuid::Uuid::new_v4().simple().to_string();
+                    println!("API token: {token}");
+                    token
+                }
+            };
+            let access = allowed_origins.into_iter().fold(
+                headless::ApiAccess::new(token),
+                headless::ApiAccess::with_allowed_origin,
+            );
+            let listener = tokio::net::TcpListener::bind(&addr).await?;
+            println!(
+                "Execution API listening on http://{}/rpc",
+                listener.local_addr()?
+            );
+            let host = headless::ExecutionHost::new()
+                .with_audit(std::sync::Arc::new(MetricsStore::new(&metrics_dir)));
+            headless::serve(listener, host, access).await?;
+        }
+        Commands::Webhooks {
+            workflow_paths,
+            addr,
+        } => {
+            let workflows = workflow_paths
+                .iter()
+                .map(|path| {
+                    let json = std::fs::read_to_string(path)?;
+                    Ok(serde_json::from_str(&json)?)
+                })
+                .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+            let runtime = headless::WebhookRuntime::new(workflows)?;
+            let listener = tokio::net::TcpListener::bind(&addr).await?;
+            let local = listener.local_addr()?;
+            for (method, path) in runtime.routes() {
+                println!("{method} http://{local}{path}");
+            }
+            headless::serve_webhooks(listener, runtime).await?;
+        }
+        #[cfg(feature = "collab")]
+        Commands::CollabRelay { listen, workflow } => {
+            let base = match workflow {
+                Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)?,
+                None => oya_frontend::graph::Workflow::new(),
+            };
+            let listener = tokio::net::TcpListener::bind(&listen).await?;
+            println!(
+                "Collaboration relay listening on ws://{}/<room>",
+                listener.local_addr()?
+            );
+            oya_frontend::graph::collab::serve_relay(listener, base).await?;
+        }
+        #[cfg(feature = "kafka")]
+        Commands::Kafka {
+            workflow_path,
+            limit,
+            metrics_dir,
+        } => {
+            let store = std::sync::Arc::new(MetricsStore::new(&metrics_dir));
+            let audited = workflow_path.display().to_string();
+            let workflow: oya_frontend::graph::Workflow =
+                serde_json::from_str(&std::fs::read_to_string(&workflow_path)?)?;
+            let bindings = headless::KafkaBinding::from_workflow(&workflow)?;
+            if bindings.is_empty() {
+                return Err(headless::KafkaError::NoBindings.into());
+            }
+            let mut consumers = tokio::task::JoinSet::new();
+            for binding in bindings {
+                let workflow = workflow.clone();
+                let store = std::sync::Arc::clone(&store);
+                let audited = audited.clone();
+                let mut source = headless::BrokerSource::connect(&binding)?;
+                println!(
+                    "Consuming {} from {} as {}",
+                    binding.topic, binding.brokers, binding.group_id
+                );
+                consumers.spawn(async move {
+                    headless::kafka::consume(
+                        &workflow,
+                        &binding,
+                        &mut source,
+                        limit,
+                        |message, run| {
+                            let success = run.history.last().is_some_and(|run| run.success);
+                            let outcome = if success {
+                                "committed"
+                            } else {
+                                "failed, not committed"
+                            };
+                            println!(
+                                "{}[{}]@{} {outcome}",
+                                message.topic, message.partition, message.offset
+                            );
+                            if let Some(run) = run.history.last() {
+                                audit_run(&store, &audited, run);
+                            }
+                        },
+                    )
+                    .await
+                });
+            }
+            while let Some(consumed) = consumers.join_next().await {
+                consumed??;
+            }
+        }
+        Commands::Schedule {
+            workflow_path,
+            once,
+            metrics_dir,
+        } => {
+            let store = MetricsStore::new(&metrics_dir);
+            let audited = workflow_path.display().to_string();
+            let workflow = serde_json::from_str(&std::fs::read_to_string(&workflow_path)?)?;
+            let mut scheduler = headless::Scheduler::new(workflow)?;
+            if let Some((due, _)) = scheduler.next_fire(chrono::Utc::now()) {
+                println!("Next run at {}", due.format("%Y-%m-%d %H:%M UTC"));
+            }
+            let mut write_error = None;
+            scheduler
+                .run(once, |workflow| {
+                    let Some(run) = workflow.history.last() else {
+                        return;
+                    };
+                    let outcome = if run.success { "succeeded" } else { "failed" };
+                    println!(
+                        "{} run {} {outcome}",
+                        run.timestamp.format("%Y-%m-%d %H:%M UTC"),
+                        run.id
+                    );
+                    audit_run(&store, &audited, run);
+                    if let Err(error) = serde_json::to_string_pretty(workflow)
+                        .map_err(std::io::Error::from)
+                        .and_then(|json| std::fs::write(&workflow_path, json))
+                    {
+                        eprintln!("Failed to save {}: {error}", workflow_path.display());
+                        write_error = Some(error);
+                    }
+                })
+                .await?;
+            if let Some(error) = write_error {
+                return Err(error.into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Audit failures are reported but never stop the runtime.
+#[cfg(not(target_arch = "wasm32"))]
+fn audit_run(store: &MetricsStore, workflow: &str, run: &oya_frontend::graph::RunRecord) {
+    let entry = AuditEntry::new(AuditAction::Run, workflow)
+        .with_detail(run.id.to_string())
+        .with_success(run.success);
+    if let Err(error) = store.record_audit(entry) {
+        eprintln!("Failed to audit run {}: {error}", run.id);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_twin(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .filter(|(name, endpoint)| !name.is_empty() && !endpoint.is_empty())
+        .map(|(name, endpoint)| (name.to_string(), endpoint.to_string()))
+        .ok_or_else(|| format!("expected name=endpoint, got '{value}'"))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -1,13 +1,20 @@
 #[cfg(not(target_arch = "wasm32"))]
 use clap::{Parser, Subcommand};
 #[cfg(not(target_arch = "wasm32"))]
-use oya_frontend::feedback::sanitize_results;
+use oya_frontend::feedback::{sanitize_results, EscalationPolicy};
 #[cfg(not(target_arch = "wasm32"))]
-use oya_frontend::linter::{LintReport, SpecLinter};
+use oya_frontend::linter::{LintConfig, LintReport, SpecLinter};
 #[cfg(not(target_arch = "wasm32"))]
-use oya_frontend::scenario_runner::{run_validation, ValidationReport};
+use oya_frontend::metrics::MetricsStore;
+#[cfg(not(target_arch = "wasm32"))]
+use oya_frontend::scenario_runner::{
+    run_validation_with, RunOptions, RunnerConfig, ScenarioFilter, ValidationReport,
+    ValidationReportFormat,
+};
 #[cfg(not(target_arch = "wasm32"))]
 use std::path::PathBuf;
+#[cfg(not(target_arch = "wasm32"))]
+use std::str::FromStr;
 
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Parser)]
@@ -20,6 +27,8 @@ struct Cli {
 
 #[cfg(not(target_arch = "wasm32"))]
 #[derive(Subcommand)]
+// Parsed once per process; boxing the larger variant buys nothing.
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// Validate a specification
     LintSpec {
@@ -28,6 +37,9 @@ enum Commands {
         /// Path to linter rules
         #[arg(long, default_value = "specs/linter/rules.yaml")]
         rules_path: PathBuf,
+        /// Lint configuration; defaults to the nearest `.oya-lint.yaml`
+        #[arg(long)]
+        config: Option<PathBuf>,
     },
     /// Run holdout scenarios
     Validate {
@@ -39,6 +51,52 @@ enum Commands {
         /// Feedback level (1-5)
         #[arg(long, default_value = "3")]
         level: u8,
+        /// Quality gate session whose history picks the feedback level,
+        /// overriding `--level`
+        #[arg(long)]
+        session: Option<String>,
+        /// Scenarios to run at once
+        #[arg(long, default_value = "1")]
+        concurrency: usize,
+        /// Fail scenarios still running after this many seconds
+        #[arg(long)]
+        timeout_secs: Option<u64>,
+        /// Digital twin as `name=endpoint`, for `${twins.<name>.endpoint}`
+        #[arg(long = "twin", value_parser = parse_twin)]
+        twins: Vec<(String, String)>,
+        /// Record request/response transcripts in the report
+        #[arg(long)]
+        transcripts: bool,
+        /// Also trust this PEM CA certificate, e.g. for TLS twins (repeatable)
+        #[arg(long = "ca-cert")]
+        ca_certs: Vec<PathBuf>,
+        /// Send requests through this proxy URL
+        #[arg(long)]
+        proxy: Option<String>,
+        /// Resend requests this many times when connecting fails
+        #[arg(long, default_value = "0")]
+        connect_retries: u32,
+        /// Also write the report to this file
+        #[arg(long)]
+        report: Option<PathBuf>,
+        /// Only run scenarios in this category (repeatable)
+        #[arg(long = "category")]
+        categories: Vec<String>,
+        /// Only run scenarios with this priority (repeatable)
+        #[arg(long = "priority")]
+        priorities: Vec<String>,
+        /// Only run scenarios carrying this tag (repeatable)
+        #[arg(long = "tag")]
+        include_tags: Vec<String>,
+        /// Skip scenarios carrying this tag (repeatable)
+        #[arg(long = "exclude-tag")]
+        exclude_tags: Vec<String>,
+        /// Only run scenarios whose id matches this glob
+        #[arg(long = "id")]
+        id_glob: Option<String>,
+        /// Format of `--report`: junit, html or json
+        #[arg(long, default_value = "junit", value_parser = ValidationReportFormat::from_str)]
+        report_format: ValidationReportFormat,
     },
 }
 
@@ -51,9 +109,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::LintSpec {
             spec_path,
             rules_path,
+            config,
         } => {
             println!("🔍 Linting spec: {}", spec_path.display());
-            let linter = SpecLinter::new(&rules_path)?;
+            let config = match config {
+                Some(path) => Some(LintConfig::load(&path)?),
+                None => LintConfig::discover(spec_path.parent().unwrap_or(&spec_path))?,
+            };
+            let linter = SpecLinter::new(&rules_path)?.with_config(config.unwrap_or_default())?;
             let report = linter.lint(&spec_path)?;
             print_report(&report);
             if report.passed {
@@ -69,18 +132,68 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             scenarios_path,
             app_endpoint,
             level,
+            session,
+            concurrency,
+            timeout_secs,
+            twins,
+            report,
+            report_format,
+            transcripts,
+            ca_certs,
+            proxy,
+            connect_retries,
+            categories,
+            priorities,
+            include_tags,
+            exclude_tags,
+            id_glob,
         } => {
             println!("🎭 Running holdout scenarios...");
-            let twins = std::collections::HashMap::new();
-            let results = run_validation(&scenarios_path, &app_endpoint, twins).await?;
+            let twins = twins
+                .into_iter()
+                .collect::<std::collections::HashMap<_, _>>();
+            let options = RunOptions {
+                concurrency,
+                timeout: timeout_secs.map(std::time::Duration::from_secs),
+                filter: ScenarioFilter {
+                    categories,
+                    priorities,
+                    include_tags,
+                    exclude_tags,
+                    id_glob,
+                },
+                transcripts,
+                runner: runner_config(ca_certs, proxy, connect_retries),
+            };
+            let results =
+                run_validation_with(&scenarios_path, &app_endpoint, twins, options).await?;
             print_validation_results(&results);
+            if let Some(path) = &report {
+                results.write_to(report_format, path)?;
+                println!("Report written to {}", path.display());
+            }
 
             if results.failed_scenarios == 0 {
                 println!("\n✅ VALIDATION PASSED");
                 Ok(())
             } else {
-                let feedback = sanitize_results(&results.results, 1, level);
+                let feedback = match &session {
+                    Some(session_id) => EscalationPolicy::default().sanitize(
+                        &MetricsStore::new(&PathBuf::from(".")),
+                        session_id,
+                        &results.results,
+                        1,
+                    ),
+                    None => sanitize_results(&results.results, 1, level),
+                };
                 println!("\n❌ VALIDATION FAILED: {}", feedback.summary);
+                if let Some(decision) = &feedback.level {
+                    println!(
+                        "Feedback level: {} ({})",
+                        decision.level.name(),
+                        decision.reason
+                    );
+                }
                 std::process::exit(1);
             }
         }
@@ -96,6 +209,13 @@ fn print_report(report: &LintReport) {
     for (cat, score) in &report.categories {
         println!("  - {}: {} ({})", cat, score.score, score.details);
     }
+    if let Some(source) = report
+        .effective_config
+        .as_ref()
+        .and_then(|config| config.source.as_ref())
+    {
+        println!("Config: {}", source.display());
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -107,6 +227,34 @@ fn print_validation_results(results: &ValidationReport) {
         results.passed_scenarios,
         results.failed_scenarios
     );
+    if let Some(filter) = &results.filter {
+        println!("Filter: {filter} | Skipped: {}", results.skipped_scenarios);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_twin(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .filter(|(name, endpoint)| !name.is_empty() && !endpoint.is_empty())
+        .map(|(name, endpoint)| (name.to_string(), endpoint.to_string()))
+        .ok_or_else(|| format!("expected name=endpoint, got '{value}'"))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn runner_config(
+    ca_certs: Vec<PathBuf>,
+    proxy: Option<String>,
+    connect_retries: u32,
+) -> RunnerConfig {
+    let config = ca_certs
+        .into_iter()
+        .fold(RunnerConfig::default(), RunnerConfig::with_ca_certificate)
+        .with_connect_retries(connect_retries);
+    match proxy {
+        Some(proxy) => config.with_proxy(proxy),
+        None => config,
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -1,10 +1,17 @@
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::thread;
 use thiserror::Error;
 
+mod policy;
+mod render;
+
+pub use policy::{CoverageThresholds, PolicyOutcome, ThresholdKind, ThresholdViolation};
+pub use render::ReportFormat;
+
 #[derive(Debug, Error)]
 pub enum CoverageError {
     #[error("Failed to read file at {path}: {source}")]
@@ -33,6 +40,18 @@ pub enum CoverageError {
     DuplicateEdgeCaseId { path: PathBuf, id: String },
     #[error("Malformed scenario reference at {path}: {detail}")]
     MalformedReference { path: PathBuf, detail: String },
+    #[error("Failed to render {} report: {source}", format.as_str())]
+    Render {
+        format: ReportFormat,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Failed to write report to {path}: {source}")]
+    WriteFile {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +64,57 @@ pub struct SpecCoverage {
     pub coverage_percentage: f64,
     pub missing_behaviors: Vec<String>,
     pub missing_edge_cases: Vec<String>,
+    /// Covered behavior id → scenarios that assert it.
+    #[serde(default)]
+    pub behavior_to_scenarios: BTreeMap<String, Vec<ScenarioRef>>,
+}
+
+/// A scenario file that references a spec behavior.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ScenarioRef {
+    pub id: String,
+    /// Path relative to the scenarios directory when possible.
+    pub path: PathBuf,
+}
+
+/// A scenario reference to an id its spec does not define.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StaleReference {
+    pub spec_id: String,
+    pub reference: String,
+    pub scenario: ScenarioRef,
+}
+
+/// A scenario whose `spec_ref` matches no spec.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OrphanScenario {
+    pub spec_ref: String,
+    pub scenario: ScenarioRef,
+}
+
+/// References that coverage would otherwise ignore silently.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceIssues {
+    #[serde(default)]
+    pub unknown_behavior_refs: Vec<StaleReference>,
+    #[serde(default)]
+    pub unknown_edge_case_refs: Vec<StaleReference>,
+    #[serde(default)]
+    pub orphan_scenarios: Vec<OrphanScenario>,
+}
+
+impl ReferenceIssues {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.unknown_behavior_refs.len()
+            + self.unknown_edge_case_refs.len()
+            + self.orphan_scenarios.len()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +126,8 @@ pub struct CoverageReport {
     pub covered_behaviors: usize,
     pub covered_edge_cases: usize,
     pub common_gaps: Vec<String>,
+    #[serde(default)]
+    pub reference_issues: ReferenceIssues,
 }
 
 pub struct CoverageAnalyzer {
@@ -78,13 +150,25 @@ impl CoverageAnalyzer {
     /// Returns an error if finding files or reading content fails.
     pub fn analyze(&self) -> Result<CoverageReport, CoverageError> {
         let mut spec_coverage = Vec::new();
+        let mut reference_issues = ReferenceIssues::default();
+        let scenarios = self.index_scenarios()?;
 
         for spec_file in self.find_spec_files()? {
-            if let Some(coverage) = self.analyze_spec(&spec_file)? {
+            if let Some(coverage) =
+                self.analyze_spec(&spec_file, &scenarios, &mut reference_issues)?
+            {
                 spec_coverage.push(coverage);
             }
         }
 
+        let known_specs = spec_coverage
+            .iter()
+            .map(|spec| Self::normalize_spec_ref(&spec.spec_id))
+            .collect::<HashSet<_>>();
+        reference_issues.orphan_scenarios = self.find_orphan_scenarios(&scenarios, &known_specs);
+        reference_issues.unknown_behavior_refs.sort();
+        reference_issues.unknown_edge_case_refs.sort();
+
         let (total_behaviors, covered_behaviors) = if spec_coverage.is_empty() {
             (0, 0)
         } else {
@@ -125,48 +209,16 @@ impl CoverageAnalyzer {
             covered_behaviors,
             covered_edge_cases,
             common_gaps,
+            reference_issues,
         })
     }
 
     fn find_spec_files(&self) -> Result<Vec<PathBuf>, CoverageError> {
-        let mut specs = Self::collect_yaml_files(&self.specs_dir)?;
+        let mut specs = collect_yaml_files(&self.specs_dir)?;
         specs.sort();
         Ok(specs)
     }
 
-    fn collect_yaml_files(root: &Path) -> Result<Vec<PathBuf>, CoverageError> {
-        let mut files = Vec::new();
-        if !root.exists() {
-            return Ok(files);
-        }
-
-        let mut stack = vec![root.to_path_buf()];
-        while let Some(dir) = stack.pop() {
-            for entry in fs::read_dir(&dir).map_err(|source| CoverageError::ReadDir {
-                path: dir.clone(),
-                source,
-            })? {
-                let path = entry
-                    .map_err(|source| CoverageError::ReadDir {
-                        path: dir.clone(),
-                        source,
-                    })?
-                    .path();
-                if path.is_dir() {
-                    stack.push(path);
-                } else if path
-                    .extension()
-                    .and_then(std::ffi::OsStr::to_str)
-                    .is_some_and(|ext| ext == "yaml" || ext == "yml")
-                {
-                    files.push(path);
-                }
-            }
-        }
-
-        Ok(files)
-    }
-
     fn normalize_spec_ref(value: &str) -> String {
         let normalized = value.trim().replace('\\', "/");
         let name = normalized
@@ -184,7 +236,12 @@ impl CoverageAnalyzer {
     }
 
     #[allow(clippy::too_many_lines)]
-    fn analyze_spec(&self, spec_path: &Path) -> Result<Option<SpecCoverage>, CoverageError> {
+    fn analyze_spec(
+        &self,
+        spec_path: &Path,
+        scenarios: &ScenarioIndex,
+        reference_issues: &mut ReferenceIssues,
+    ) -> Result<Option<SpecCoverage>, CoverageError> {
         let spec_path_buf = spec_path.to_path_buf();
         let spec_content =
             fs::read_to_string(spec_path).map_err(|source| CoverageError::ReadFile {
@@ -303,8 +360,18 @@ impl CoverageAnalyzer {
 
         let mut scenario_behavior_ids: HashSet<String> = HashSet::new();
         let mut scenario_edge_case_ids: HashSet<String> = HashSet::new();
-
-        for (scenario, scenario_path) in self.find_scenarios_for_spec(&spec_id)? {
+        let mut behavior_to_scenarios: BTreeMap<String, Vec<ScenarioRef>> = BTreeMap::new();
+
+        let spec_scenarios = scenarios
+            .get(&Self::normalize_spec_ref(&spec_id))
+            .map_or(&[][..], Vec::as_slice);
+        for IndexedScenario {
+            yaml: scenario,
+            path: scenario_path,
+            ..
+        } in spec_scenarios
+        {
+            let scenario_ref = self.scenario_ref(scenario, scenario_path);
             let steps = scenario
                 .get("steps")
                 .and_then(serde_yaml::Value::as_sequence)
@@ -338,6 +405,21 @@ impl CoverageAnalyzer {
                                     })?;
 
                                 scenario_behavior_ids.insert(behavior_ref.to_string());
+                                if behavior_ids.contains(behavior_ref) {
+                                    let refs = behavior_to_scenarios
+                                        .entry(behavior_ref.to_string())
+                                        .or_default();
+                                    if !refs.contains(&scenario_ref) {
+                                        refs.push(scenario_ref.clone());
+                                    }
+                                } else {
+                                    push_stale(
+                                        &mut reference_issues.unknown_behavior_refs,
+                                        &spec_id,
+                                        behavior_ref,
+                                        &scenario_ref,
+                                    );
+                                }
                             }
 
                             if let Some(edge_case_ref_value) = assertion.get("edge_case_ref") {
@@ -352,6 +434,14 @@ impl CoverageAnalyzer {
                                     })?;
 
                                 scenario_edge_case_ids.insert(edge_case_ref.to_string());
+                                if !edge_case_ids.contains(edge_case_ref) {
+                                    push_stale(
+                                        &mut reference_issues.unknown_edge_case_refs,
+                                        &spec_id,
+                                        edge_case_ref,
+                                        &scenario_ref,
+                                    );
+                                }
                             }
                         }
                     }
@@ -374,6 +464,10 @@ impl CoverageAnalyzer {
             .collect();
         missing_edge_cases.sort();
 
+        for refs in behavior_to_scenarios.values_mut() {
+            refs.sort();
+        }
+
         Ok(Some(SpecCoverage {
             spec_id,
             total_behaviors: behavior_ids.len(),
@@ -390,38 +484,174 @@ impl CoverageAnalyzer {
             },
             missing_behaviors,
             missing_edge_cases,
+            behavior_to_scenarios,
         }))
     }
 
-    fn find_scenarios_for_spec(
+    fn scenario_ref(&self, scenario: &Value, path: &Path) -> ScenarioRef {
+        let id = scenario
+            .get("scenario")
+            .and_then(|inner| inner.get("id"))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .or_else(|| {
+                path.file_stem()
+                    .and_then(std::ffi::OsStr::to_str)
+                    .map(str::to_string)
+            })
+            .unwrap_or_default();
+
+        ScenarioRef {
+            id,
+            path: path
+                .strip_prefix(&self.scenarios_dir)
+                .map_or_else(|_| path.to_path_buf(), Path::to_path_buf),
+        }
+    }
+
+    /// Reads and parses every scenario once, in parallel, grouped by
+    /// normalized `spec_ref`. Scenarios without a `spec_ref` are dropped.
+    fn index_scenarios(&self) -> Result<ScenarioIndex, CoverageError> {
+        let mut paths = collect_yaml_files(&self.scenarios_dir)?;
+        paths.sort();
+
+        let workers = thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        let chunk_size = paths.len().div_ceil(workers).max(1);
+        let parsed = thread::scope(|scope| {
+            let handles = paths
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|path| parse_yaml_file(path).map(|yaml| (path.clone(), yaml)))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    // Dropping a panicked chunk would undercount coverage.
+                    handle
+                        .join()
+                        .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+
+        let mut index = ScenarioIndex::new();
+        for (path, yaml) in parsed {
+            let Some(spec_ref) = yaml
+                .get("scenario")
+                .and_then(|scenario| scenario.get("spec_ref"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+            else {
+                continue;
+            };
+            index
+                .entry(Self::normalize_spec_ref(&spec_ref))
+                .or_default()
+                .push(IndexedScenario {
+                    spec_ref,
+                    yaml,
+                    path,
+                });
+        }
+        Ok(index)
+    }
+
+    fn find_orphan_scenarios(
         &self,
-        spec_id: &str,
-    ) -> Result<Vec<(Value, PathBuf)>, CoverageError> {
-        let mut scenarios = Vec::new();
-        let normalized_spec_id = Self::normalize_spec_ref(spec_id);
-
-        for path in Self::collect_yaml_files(&self.scenarios_dir)? {
-            let content = fs::read_to_string(&path).map_err(|source| CoverageError::ReadFile {
-                path: path.clone(),
-                source,
-            })?;
-            let yaml = serde_yaml::from_str::<Value>(&content).map_err(|source| {
-                CoverageError::MalformedYaml {
-                    path: path.clone(),
-                    source,
-                }
-            })?;
+        scenarios: &ScenarioIndex,
+        known_specs: &HashSet<String>,
+    ) -> Vec<OrphanScenario> {
+        let mut orphans = scenarios
+            .iter()
+            .filter(|(spec, _)| !known_specs.contains(*spec))
+            .flat_map(|(_, scenarios)| scenarios)
+            .map(|scenario| OrphanScenario {
+                spec_ref: scenario.spec_ref.clone(),
+                scenario: self.scenario_ref(&scenario.yaml, &scenario.path),
+            })
+            .collect::<Vec<_>>();
+        orphans.sort();
+        orphans
+    }
+}
 
-            if let Some(scenario) = yaml.get("scenario") {
-                if let Some(ref_str) = scenario.get("spec_ref").and_then(Value::as_str) {
-                    let normalized_ref = Self::normalize_spec_ref(ref_str);
-                    if normalized_ref == normalized_spec_id {
-                        scenarios.push((yaml, path.clone()));
-                    }
-                }
+/// A parsed scenario file and the `spec_ref` it was filed under.
+struct IndexedScenario {
+    spec_ref: String,
+    yaml: Value,
+    path: PathBuf,
+}
+
+/// Normalized spec ref → its scenarios, in path order.
+type ScenarioIndex = HashMap<String, Vec<IndexedScenario>>;
+
+fn parse_yaml_file(path: &Path) -> Result<Value, CoverageError> {
+    let content = fs::read_to_string(path).map_err(|source| CoverageError::ReadFile {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_yaml::from_str(&content).map_err(|source| CoverageError::MalformedYaml {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Every `.yaml`/`.yml` file below `root`, recursively. A missing root
+/// yields no files.
+pub(crate) fn collect_yaml_files(root: &Path) -> Result<Vec<PathBuf>, CoverageError> {
+    let mut files = Vec::new();
+    if !root.exists() {
+        return Ok(files);
+    }
+
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in fs::read_dir(&dir).map_err(|source| CoverageError::ReadDir {
+            path: dir.clone(),
+            source,
+        })? {
+            let path = entry
+                .map_err(|source| CoverageError::ReadDir {
+                    path: dir.clone(),
+                    source,
+                })?
+                .path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path
+                .extension()
+                .and_then(std::ffi::OsStr::to_str)
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+            {
+                files.push(path);
             }
         }
-        Ok(scenarios)
+    }
+
+    Ok(files)
+}
+
+fn push_stale(
+    stale: &mut Vec<StaleReference>,
+    spec_id: &str,
+    reference: &str,
+    scenario: &ScenarioRef,
+) {
+    let entry = StaleReference {
+        spec_id: spec_id.to_string(),
+        reference: reference.to_string(),
+        scenario: scenario.clone(),
+    };
+    if !stale.contains(&entry) {
+        stale.push(entry);
     }
 }
 
@@ -508,6 +738,56 @@ scenario:
         Ok(())
     }
 
+    #[test]
+    fn given_scenarios_referencing_behavior_when_analyzing_then_they_are_attributed(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let root = temp_dir("attribution")?;
+        let specs = root.join("specs");
+        let scenarios = root.join("scenarios");
+        fs::create_dir_all(&specs)?;
+        fs::create_dir_all(scenarios.join("nested"))?;
+
+        write_file(&specs.join("spec.yaml"), spec_with_edge_cases())?;
+        write_file(
+            &scenarios.join("named.yaml"),
+            r"
+scenario:
+  id: scenario-named
+  spec_ref: spec-coverage
+  steps:
+    - assertions:
+        - behavior_ref: behavior-1
+    - assertions:
+        - behavior_ref: behavior-1
+        - behavior_ref: behavior-unknown
+",
+        )?;
+        write_file(
+            &scenarios.join("nested").join("anonymous.yaml"),
+            &scenario_with_refs("spec-coverage"),
+        )?;
+
+        let report = CoverageAnalyzer::new(&specs, &scenarios).analyze()?;
+        let attribution = &report.specs[0].behavior_to_scenarios;
+
+        assert_eq!(attribution.len(), 1);
+        assert_eq!(
+            attribution["behavior-1"],
+            vec![
+                ScenarioRef {
+                    id: "anonymous".to_string(),
+                    path: Path::new("nested").join("anonymous.yaml"),
+                },
+                ScenarioRef {
+                    id: "scenario-named".to_string(),
+                    path: PathBuf::from("named.yaml"),
+                },
+            ]
+        );
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
     #[test]
     fn given_matching_edge_case_ref_when_analyzing_then_edge_case_is_counted_as_covered(
     ) -> Result<(), Box<dyn std::error::Error>> {
@@ -733,4 +1013,120 @@ specification:
         fs::remove_dir_all(root)?;
         Ok(())
     }
+
+    #[test]
+    fn given_stale_refs_when_analyzing_then_unknown_ids_are_reported_once(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let root = temp_dir("stale-ref")?;
+        let specs = root.join("specs");
+        let scenarios = root.join("scenarios");
+        fs::create_dir_all(&specs)?;
+        fs::create_dir_all(&scenarios)?;
+
+        write_file(&specs.join("spec.yaml"), spec_with_edge_cases())?;
+        write_file(
+            &scenarios.join("stale.yaml"),
+            r"
+scenario:
+  id: stale-scenario
+  spec_ref: spec-coverage
+  steps:
+    - assertions:
+        - behavior_ref: behavior-1
+        - behavior_ref: renamed-behavior
+        - behavior_ref: renamed-behavior
+          edge_case_ref: removed-edge
+",
+        )?;
+
+        let report = CoverageAnalyzer::new(&specs, &scenarios).analyze()?;
+        let issues = &report.reference_issues;
+
+        assert_eq!(report.specs[0].covered_behaviors, 1);
+        assert_eq!(issues.unknown_behavior_refs.len(), 1);
+        assert_eq!(
+            issues.unknown_behavior_refs[0].reference,
+            "renamed-behavior"
+        );
+        assert_eq!(issues.unknown_behavior_refs[0].spec_id, "spec-coverage");
+        assert_eq!(
+            issues.unknown_behavior_refs[0].scenario.path,
+            PathBuf::from("stale.yaml")
+        );
+        assert_eq!(issues.unknown_edge_case_refs.len(), 1);
+        assert_eq!(issues.unknown_edge_case_refs[0].reference, "removed-edge");
+        assert!(issues.orphan_scenarios.is_empty());
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn given_scenario_for_missing_spec_when_analyzing_then_it_is_reported_as_orphan(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let root = temp_dir("orphan")?;
+        let specs = root.join("specs");
+        let scenarios = root.join("scenarios");
+        fs::create_dir_all(&specs)?;
+        fs::create_dir_all(&scenarios)?;
+
+        write_file(&specs.join("spec.yaml"), spec_with_edge_cases())?;
+        write_file(
+            &scenarios.join("covered.yaml"),
+            &scenario_with_refs("specs/spec-coverage.yaml"),
+        )?;
+        write_file(
+            &scenarios.join("orphan.yaml"),
+            &scenario_with_refs("retired-spec"),
+        )?;
+
+        let report = CoverageAnalyzer::new(&specs, &scenarios).analyze()?;
+        let orphans = &report.reference_issues.orphan_scenarios;
+
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].spec_ref, "retired-spec");
+        assert_eq!(orphans[0].scenario.id, "orphan");
+        assert_eq!(report.reference_issues.len(), 1);
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn given_many_scenarios_across_specs_when_analyzing_then_each_is_attributed_once(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let root = temp_dir("index")?;
+        let specs = root.join("specs");
+        let scenarios = root.join("scenarios");
+        fs::create_dir_all(&specs)?;
+        fs::create_dir_all(scenarios.join("deep").join("er"))?;
+
+        write_file(&specs.join("spec.yaml"), spec_with_edge_cases())?;
+        write_file(
+            &specs.join("other.yaml"),
+            &spec_with_edge_cases().replace("id: spec-coverage", "id: spec-other"),
+        )?;
+        for index in 0..60 {
+            let (dir, spec_ref) = match index % 3 {
+                0 => (scenarios.clone(), "spec-coverage"),
+                1 => (scenarios.join("deep"), "specs/spec-other.yaml"),
+                _ => (scenarios.join("deep").join("er"), "retired"),
+            };
+            write_file(
+                &dir.join(format!("scenario-{index:02}.yaml")),
+                &scenario_with_refs(spec_ref),
+            )?;
+        }
+
+        let report = CoverageAnalyzer::new(&specs, &scenarios).analyze()?;
+
+        assert_eq!(report.specs.len(), 2);
+        for spec in &report.specs {
+            assert_eq!(spec.covered_behaviors, 1);
+            assert_eq!(spec.behavior_to_scenarios["behavior-1"].len(), 20);
+        }
+        let orphans = &report.reference_issues.orphan_scenarios;
+        assert_eq!(orphans.len(), 20);
+        assert!(orphans.windows(2).all(|pair| pair[0] <= pair[1]));
+        fs::remove_dir_all(root)?;
+        Ok(())
+    }
 }
@@ -0,0 +1,246 @@
+//! Coverage thresholds for gating merges on scenario coverage.
+
+use super::{CoverageAnalyzer, CoverageError, CoverageReport};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Minimum coverage percentages. Unset thresholds are not enforced.
+///
+/// Specs with no behaviors, and reports with no edge cases, have nothing to
+/// cover and never violate a threshold.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageThresholds {
+    #[serde(default)]
+    pub overall: Option<f64>,
+    #[serde(default)]
+    pub per_spec: Option<f64>,
+    #[serde(default)]
+    pub edge_cases: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThresholdKind {
+    Overall,
+    PerSpec,
+    EdgeCases,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdViolation {
+    pub kind: ThresholdKind,
+    /// Set for per-spec violations.
+    pub spec_id: Option<String>,
+    pub required: f64,
+    pub actual: f64,
+}
+
+impl fmt::Display for ThresholdViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let subject = match (&self.kind, &self.spec_id) {
+            (ThresholdKind::PerSpec, Some(spec_id)) => format!("spec {spec_id}"),
+            (ThresholdKind::PerSpec, None) => "spec".to_string(),
+            (ThresholdKind::Overall, _) => "overall behavior coverage".to_string(),
+            (ThresholdKind::EdgeCases, _) => "edge-case coverage".to_string(),
+        };
+        write!(
+            f,
+            "{subject} is {:.1}%, below the required {:.1}%",
+            self.actual, self.required
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PolicyOutcome {
+    pub report: CoverageReport,
+    pub violations: Vec<ThresholdViolation>,
+}
+
+impl PolicyOutcome {
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl CoverageThresholds {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.overall.is_none() && self.per_spec.is_none() && self.edge_cases.is_none()
+    }
+
+    /// Check a report against these thresholds.
+    #[must_use]
+    pub fn evaluate(&self, report: &CoverageReport) -> Vec<ThresholdViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(required) = self.overall {
+            if report.total_behaviors > 0 && report.overall_coverage < required {
+                violations.push(ThresholdViolation {
+                    kind: ThresholdKind::Overall,
+                    spec_id: None,
+                    required,
+                    actual: report.overall_coverage,
+                });
+            }
+        }
+
+        if let Some(required) = self.per_spec {
+            violations.extend(
+                report
+                    .specs
+                    .iter()
+                    .filter(|spec| spec.total_behaviors > 0 && spec.coverage_percentage < required)
+                    .map(|spec| ThresholdViolation {
+                        kind: ThresholdKind::PerSpec,
+                        spec_id: Some(spec.spec_id.clone()),
+                        required,
+                        actual: spec.coverage_percentage,
+                    }),
+            );
+        }
+
+        if let Some(required) = self.edge_cases {
+            if report.total_edge_cases > 0 {
+                #[allow(clippy::cast_precision_loss)]
+                let actual =
+                    report.covered_edge_cases as f64 / report.total_edge_cases as f64 * 100.0;
+                if actual < required {
+                    violations.push(ThresholdViolation {
+                        kind: ThresholdKind::EdgeCases,
+                        spec_id: None,
+                        required,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+impl CoverageAnalyzer {
+    /// Analyze coverage and evaluate it against `thresholds`.
+    ///
+    /// # Errors
+    /// Returns an error if the analysis itself fails; threshold violations
+    /// are reported in the outcome, not as errors.
+    pub fn analyze_with_policy(
+        &self,
+        thresholds: &CoverageThresholds,
+    ) -> Result<PolicyOutcome, CoverageError> {
+        let report = self.analyze()?;
+        let violations = thresholds.evaluate(&report);
+        Ok(PolicyOutcome { report, violations })
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{CoverageThresholds, ThresholdKind};
+    use crate::coverage::{CoverageReport, ReferenceIssues, SpecCoverage};
+    use std::collections::BTreeMap;
+
+    fn spec(spec_id: &str, total: usize, covered: usize) -> SpecCoverage {
+        #[allow(clippy::cast_precision_loss)]
+        let coverage_percentage = if total == 0 {
+            0.0
+        } else {
+            covered as f64 / total as f64 * 100.0
+        };
+        SpecCoverage {
+            spec_id: spec_id.to_string(),
+            total_behaviors: total,
+            covered_behaviors: covered,
+            total_edge_cases: 0,
+            covered_edge_cases: 0,
+            coverage_percentage,
+            missing_behaviors: Vec::new(),
+            missing_edge_cases: Vec::new(),
+            behavior_to_scenarios: BTreeMap::new(),
+        }
+    }
+
+    fn report(specs: Vec<SpecCoverage>, edge_cases: (usize, usize)) -> CoverageReport {
+        let total_behaviors: usize = specs.iter().map(|spec| spec.total_behaviors).sum();
+        let covered_behaviors: usize = specs.iter().map(|spec| spec.covered_behaviors).sum();
+        #[allow(clippy::cast_precision_loss)]
+        let overall_coverage = if total_behaviors == 0 {
+            0.0
+        } else {
+            covered_behaviors as f64 / total_behaviors as f64 * 100.0
+        };
+        CoverageReport {
+            specs,
+            overall_coverage,
+            total_behaviors,
+            total_edge_cases: edge_cases.0,
+            covered_behaviors,
+            covered_edge_cases: edge_cases.1,
+            common_gaps: Vec::new(),
+            reference_issues: ReferenceIssues::default(),
+        }
+    }
+
+    #[test]
+    fn given_report_below_every_threshold_when_evaluating_then_each_violation_is_listed() {
+        let thresholds = CoverageThresholds {
+            overall: Some(80.0),
+            per_spec: Some(60.0),
+            edge_cases: Some(50.0),
+        };
+        let report = report(vec![spec("spec-a", 4, 4), spec("spec-b", 4, 2)], (4, 1));
+
+        let violations = thresholds.evaluate(&report);
+
+        let kinds = violations.iter().map(|v| v.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                ThresholdKind::Overall,
+                ThresholdKind::PerSpec,
+                ThresholdKind::EdgeCases
+            ]
+        );
+        assert_eq!(violations[0].actual, 75.0);
+        assert_eq!(violations[1].spec_id.as_deref(), Some("spec-b"));
+        assert_eq!(violations[2].actual, 25.0);
+        assert_eq!(
+            violations[1].to_string(),
+            "spec spec-b is 50.0%, below the required 60.0%"
+        );
+    }
+
+    #[test]
+    fn given_thresholds_met_exactly_when_evaluating_then_report_passes() {
+        let thresholds = CoverageThresholds {
+            overall: Some(50.0),
+            per_spec: Some(50.0),
+            edge_cases: Some(50.0),
+        };
+        let report = report(vec![spec("spec-a", 2, 1)], (2, 1));
+
+        assert!(thresholds.evaluate(&report).is_empty());
+    }
+
+    #[test]
+    fn given_nothing_to_cover_when_evaluating_then_thresholds_are_not_violated() {
+        let thresholds = CoverageThresholds {
+            overall: Some(100.0),
+            per_spec: Some(100.0),
+            edge_cases: Some(100.0),
+        };
+        let report = report(vec![spec("spec-empty", 0, 0)], (0, 0));
+
+        assert!(thresholds.evaluate(&report).is_empty());
+        assert!(CoverageThresholds::default().is_empty());
+    }
+}
@@ -0,0 +1,295 @@
+//! Renderers that turn a [`CoverageReport`] into shareable documents.
+
+use super::{CoverageError, CoverageReport, SpecCoverage, StaleReference};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    /// A single page with inline styles and no external assets.
+    Html,
+    /// GitHub-flavoured Markdown, sized for a pull request comment.
+    Markdown,
+}
+
+impl ReportFormat {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Html => "html",
+            Self::Markdown => "markdown",
+        }
+    }
+
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Html => "html",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(Self::Json),
+            "html" => Ok(Self::Html),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(format!("Unknown report format: {value}")),
+        }
+    }
+}
+
+impl CoverageReport {
+    /// Render the report in the given format.
+    ///
+    /// # Errors
+    /// Returns an error if JSON serialization fails.
+    pub fn render(&self, format: ReportFormat) -> Result<String, CoverageError> {
+        match format {
+            ReportFormat::Json => serde_json::to_string_pretty(self)
+                .map(|json| json + "\n")
+                .map_err(|source| CoverageError::Render { format, source }),
+            ReportFormat::Html => Ok(render_html(self)),
+            ReportFormat::Markdown => Ok(render_markdown(self)),
+        }
+    }
+
+    /// Render the report and write it to `path`.
+    ///
+    /// # Errors
+    /// Returns an error if rendering or writing the file fails.
+    pub fn write_to(&self, format: ReportFormat, path: &Path) -> Result<(), CoverageError> {
+        let rendered = self.render(format)?;
+        fs::write(path, rendered).map_err(|source| CoverageError::WriteFile {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+fn has_gaps(spec: &SpecCoverage) -> bool {
+    !spec.missing_behaviors.is_empty() || !spec.missing_edge_cases.is_empty()
+}
+
+fn render_markdown(report: &CoverageReport) -> String {
+    let mut out = String::from("## Scenario Coverage\n\n");
+    let _ = writeln!(
+        out,
+        "**Overall: {:.1}%** — {}/{} behaviors, {}/{} edge cases\n",
+        report.overall_coverage,
+        report.covered_behaviors,
+        report.total_behaviors,
+        report.covered_edge_cases,
+        report.total_edge_cases
+    );
+
+    if report.specs.is_empty() {
+        out.push_str("_No specs found._\n");
+        return out;
+    }
+
+    out.push_str("| Spec | Coverage | Behaviors | Edge cases | Status |\n");
+    out.push_str("| --- | ---: | ---: | ---: | :---: |\n");
+    for spec in &report.specs {
+        let _ = writeln!(
+            out,
+            "| `{}` | {:.1}% | {}/{} | {}/{} | {} |",
+            spec.spec_id,
+            spec.coverage_percentage,
+            spec.covered_behaviors,
+            spec.total_behaviors,
+            spec.covered_edge_cases,
+            spec.total_edge_cases,
+            if has_gaps(spec) { "⚠️" } else { "✅" }
+        );
+    }
+
+    let gaps = report
+        .specs
+        .iter()
+        .filter(|spec| has_gaps(spec))
+        .collect::<Vec<_>>();
+    if !gaps.is_empty() {
+        out.push_str("\n### Gaps\n");
+        for spec in gaps {
+            let _ = writeln!(
+                out,
+                "\n<details><summary><code>{}</code></summary>\n",
+                escape_html(&spec.spec_id)
+            );
+            for behavior in &spec.missing_behaviors {
+                let _ = writeln!(out, "- behavior `{behavior}`");
+            }
+            for edge_case in &spec.missing_edge_cases {
+                let _ = writeln!(out, "- edge case `{edge_case}`");
+            }
+            out.push_str("\n</details>\n");
+        }
+    }
+
+    if !report.common_gaps.is_empty() {
+        out.push_str("\n### Common gaps\n\n");
+        for gap in &report.common_gaps {
+            let _ = writeln!(out, "- `{gap}`");
+        }
+    }
+
+    if !report.reference_issues.is_empty() {
+        out.push_str("\n### Reference issues\n\n");
+        for (kind, stale) in stale_rows(report) {
+            let _ = writeln!(
+                out,
+                "- unknown {kind} `{}` in `{}` ({}), spec `{}`",
+                stale.reference,
+                stale.scenario.id,
+                stale.scenario.path.display(),
+                stale.spec_id
+            );
+        }
+        for orphan in &report.reference_issues.orphan_scenarios {
+            let _ = writeln!(
+                out,
+                "- scenario `{}` ({}) references unknown spec `{}`",
+                orphan.scenario.id,
+                orphan.scenario.path.display(),
+                orphan.spec_ref
+            );
+        }
+    }
+
+    out
+}
+
+fn stale_rows(report: &CoverageReport) -> impl Iterator<Item = (&'static str, &StaleReference)> {
+    let issues = &report.reference_issues;
+    issues
+        .unknown_behavior_refs
+        .iter()
+        .map(|stale| ("behavior", stale))
+        .chain(
+            issues
+                .unknown_edge_case_refs
+                .iter()
+                .map(|stale| ("edge case", stale)),
+        )
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#0f172a}\
+table{border-collapse:collapse;width:100%;margin-bottom:1.5rem}\
+th,td{border:1px solid #e2e8f0;padding:.4rem .6rem;text-align:left}\
+th{background:#f8fafc}td.num{text-align:right;font-variant-numeric:tabular-nums}\
+tr.gap{background:#fef2f2}ul.missing li{color:#b91c1c}\
+.summary{font-size:1.1rem;margin-bottom:1rem}";
+
+fn render_html(report: &CoverageReport) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    out.push_str("<meta charset=\"utf-8\">\n<title>Scenario Coverage</title>\n");
+    let _ = writeln!(out, "<style>{HTML_STYLE}</style>");
+    out.push_str("</head>\n<body>\n<h1>Scenario Coverage</h1>\n");
+    let _ = writeln!(
+        out,
+        "<p class=\"summary\">Overall <strong>{:.1}%</strong> &middot; {}/{} behaviors &middot; {}/{} edge cases</p>",
+        report.overall_coverage,
+        report.covered_behaviors,
+        report.total_behaviors,
+        report.covered_edge_cases,
+        report.total_edge_cases
+    );
+
+    out.push_str("<table>\n<thead><tr><th>Spec</th><th>Coverage</th><th>Behaviors</th><th>Edge cases</th></tr></thead>\n<tbody>\n");
+    for spec in &report.specs {
+        let _ = writeln!(
+            out,
+            "<tr{}><td>{}</td><td class=\"num\">{:.1}%</td><td class=\"num\">{}/{}</td><td class=\"num\">{}/{}</td></tr>",
+            if has_gaps(spec) { " class=\"gap\"" } else { "" },
+            escape_html(&spec.spec_id),
+            spec.coverage_percentage,
+            spec.covered_behaviors,
+            spec.total_behaviors,
+            spec.covered_edge_cases,
+            spec.total_edge_cases
+        );
+    }
+    out.push_str("</tbody>\n</table>\n");
+
+    for spec in report.specs.iter().filter(|spec| has_gaps(spec)) {
+        let _ = writeln!(out, "<h2>{}</h2>", escape_html(&spec.spec_id));
+        out.push_str(
+            "<table>\n<thead><tr><th>Kind</th><th>Missing id</th></tr></thead>\n<tbody>\n",
+        );
+        let rows = spec
+            .missing_behaviors
+            .iter()
+            .map(|id| ("behavior", id))
+            .chain(spec.missing_edge_cases.iter().map(|id| ("edge case", id)));
+        for (kind, id) in rows {
+            let _ = writeln!(
+                out,
+                "<tr class=\"gap\"><td>{kind}</td><td><code>{}</code></td></tr>",
+                escape_html(id)
+            );
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    if !report.common_gaps.is_empty() {
+        out.push_str("<h2>Common gaps</h2>\n<ul class=\"missing\">\n");
+        for gap in &report.common_gaps {
+            let _ = writeln!(out, "<li><code>{}</code></li>", escape_html(gap));
+        }
+        out.push_str("</ul>\n");
+    }
+
+    if !report.reference_issues.is_empty() {
+        out.push_str("<h2>Reference issues</h2>\n");
+        out.push_str("<table>\n<thead><tr><th>Kind</th><th>Reference</th><th>Scenario</th><th>Spec</th></tr></thead>\n<tbody>\n");
+        for (kind, stale) in stale_rows(report) {
+            let _ = writeln!(
+                out,
+                "<tr class=\"gap\"><td>unknown {kind}</td><td><code>{}</code></td><td>{} <code>{}</code></td><td>{}</td></tr>",
+                escape_html(&stale.reference),
+                escape_html(&stale.scenario.id),
+                escape_html(&stale.scenario.path.display().to_string()),
+                escape_html(&stale.spec_id)
+            );
+        }
+        for orphan in &report.reference_issues.orphan_scenarios {
+            let _ = writeln!(
+                out,
+                "<tr class=\"gap\"><td>unknown spec</td><td><code>{}</code></td><td>{} <code>{}</code></td><td>&mdash;</td></tr>",
+                escape_html(&orphan.spec_ref),
+                escape_html(&orphan.scenario.id),
+                escape_html(&orphan.scenario.path.display().to_string())
+            );
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .chars()
+        .fold(String::with_capacity(value.len()), |mut out, ch| {
+            match ch {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                '\'' => out.push_str("&#39;"),
+                _ => out.push(ch),
+            }
+            out
+        })
+}
@@ -1,7 +1,14 @@
-use crate::metrics::{MetricsStore, SessionStatus};
+use crate::coverage::CoverageAnalyzer;
+use crate::metrics::{HealthWeights, MetricsStore, RetentionPolicy, SessionStatus};
 use clap::{Parser, Subcommand};
 use std::path::{Path, PathBuf};
 
+pub mod server;
+pub mod workflows;
+
+pub use server::{DashboardConfig, DashboardSnapshot, SpecStatus, TwinStatus};
+pub use workflows::{workflow_inventory, LastRun, LinkTargets, PendingSuggestion, WorkflowEntry};
+
 #[derive(Parser)]
 #[command(name = "quality-dashboard")]
 #[command(about = "View quality gate metrics")]
@@ -28,6 +35,62 @@ pub enum Commands {
         #[arg(short, long)]
         output: Option<PathBuf>,
     },
+
+    #[command(about = "Export one session's timeline")]
+    Session {
+        session_id: String,
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    #[command(about = "Prune old metrics into daily rollups")]
+    Compact {
+        /// Most finished sessions to keep.
+        #[arg(long)]
+        max_sessions: Option<usize>,
+        /// Drop raw records older than this many days.
+        #[arg(long)]
+        max_age_days: Option<i64>,
+    },
+
+    #[command(about = "Generate the weekly workspace health digest")]
+    Digest {
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        #[arg(long, default_value = "specs")]
+        specs_dir: PathBuf,
+        #[arg(long, default_value = "../scenarios-vault")]
+        scenarios_dir: PathBuf,
+        /// Append the week's sessions to the markdown digest.
+        #[arg(long)]
+        sessions: bool,
+    },
+
+    #[command(about = "Serve the live web dashboard")]
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:8090")]
+        addr: String,
+        #[arg(long, default_value = "specs")]
+        specs_dir: PathBuf,
+        #[arg(long, default_value = "../scenarios-vault")]
+        scenarios_dir: PathBuf,
+        /// Digital twin to probe, as `name=endpoint` (repeatable).
+        #[arg(long = "twin", value_parser = parse_twin)]
+        twins: Vec<(String, String)>,
+        /// Directory of workflows saved from the editor.
+        #[arg(long, default_value = "workflows")]
+        workflows_dir: PathBuf,
+        /// Editor base URL that workflow deep links open.
+        #[arg(long)]
+        editor_url: Option<String>,
+        /// Seconds between live updates.
+        #[arg(long, default_value = "5")]
+        refresh_secs: u64,
+    },
 }
 
 /// Run the dashboard application.
@@ -53,11 +116,104 @@ pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
                 .unwrap_or_else(|| PathBuf::from("metrics-report.txt"));
             export_metrics(&metrics_store, &format, &output_path)?;
         }
+
+        Commands::Session {
+            session_id,
+            format,
+            output,
+        } => {
+            let rendered = metrics_store.export_session(&session_id, &format)?;
+            match output {
+                Some(path) => std::fs::write(path, rendered)?,
+                None => println!("{rendered}"),
+            }
+        }
+
+        Commands::Compact {
+            max_sessions,
+            max_age_days,
+        } => {
+            let policy = RetentionPolicy {
+                max_sessions,
+                max_age: max_age_days.map(chrono::Duration::days),
+            };
+            let report = metrics_store.compact(&policy, chrono::Utc::now())?;
+            println!(
+                "✅ Removed {} spec validations, {} scenario validations and {} sessions ({} daily rollups kept)",
+                report.spec_validations_removed,
+                report.scenario_validations_removed,
+                report.sessions_removed,
+                report.rollups
+            );
+        }
+
+        Commands::Digest {
+            format,
+            output,
+            specs_dir,
+            scenarios_dir,
+            sessions,
+        } => {
+            let coverage = CoverageAnalyzer::new(&specs_dir, &scenarios_dir).analyze()?;
+            let now = chrono::Utc::now();
+            let digest =
+                metrics_store.weekly_digest(now, Some(&coverage), 0, &HealthWeights::default());
+            let rendered = match format.as_str() {
+                "json" => digest.to_json()?,
+                "markdown" | "md" if sessions => format!(
+                    "{}\n{}",
+                    digest.to_markdown(),
+                    metrics_store.weekly_sessions_digest(now)
+                ),
+                "markdown" | "md" => digest.to_markdown(),
+                _ => return Err("Unsupported format. Use 'json' or 'markdown'".into()),
+            };
+            match output {
+                Some(path) => std::fs::write(path, rendered)?,
+                None => println!("{rendered}"),
+            }
+        }
+
+        Commands::Serve {
+            addr,
+            specs_dir,
+            scenarios_dir,
+            twins,
+            workflows_dir,
+            editor_url,
+            refresh_secs,
+        } => {
+            let config = DashboardConfig {
+                specs_dir,
+                scenarios_dir,
+                twins,
+                workflows_dir,
+                editor_url,
+                refresh: std::time::Duration::from_secs(refresh_secs),
+                ..DashboardConfig::default()
+            };
+            tokio::runtime::Runtime::new()?.block_on(async {
+                let listener = tokio::net::TcpListener::bind(&addr).await?;
+                println!(
+                    "📊 Dashboard listening on http://{}",
+                    listener.local_addr()?
+                );
+                server::serve(listener, config).await
+            })?;
+        }
     }
 
     Ok(())
 }
 
+fn parse_twin(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .filter(|(name, endpoint)| !name.is_empty() && !endpoint.is_empty())
+        .map(|(name, endpoint)| (name.to_string(), endpoint.to_string()))
+        .ok_or_else(|| format!("expected name=endpoint, got '{value}'"))
+}
+
 pub fn print_summary(summary: &crate::metrics::MetricsSummary) {
     println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("  QUALITY GATE SUMMARY");
@@ -138,6 +294,7 @@ pub fn export_metrics(
     let report = match format {
         "json" => serde_json::to_string_pretty(&summary)?,
         "text" => format_text_summary(&summary),
+        "openmetrics" => store.to_openmetrics(),
         _ => return Err("Unsupported format".into()),
     };
 
@@ -0,0 +1,460 @@
+//! Live quality-gate dashboard over HTTP: a JSON API, a single HTML page and
+//! a server-sent-event stream that pushes a fresh snapshot every refresh.
+//!
+//! Every snapshot reopens the metrics store, so runs recorded by other
+//! processes show up without restarting the server.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::coverage::{CoverageAnalyzer, CoverageReport};
+use crate::http::{read_request, respond, start_event_stream};
+use crate::metrics::{MetricsStore, MetricsSummary, SessionQuery, SessionTimeline};
+use crate::twin_client::TwinInspectionClient;
+
+use super::workflows::{saved_workflow_json, workflow_inventory, LinkTargets, WorkflowEntry};
+
+const INDEX_HTML: &str = include_str!("index.html");
+
+#[derive(Debug, Clone)]
+pub struct DashboardConfig {
+    /// Directory holding `quality-metrics/`.
+    pub metrics_dir: PathBuf,
+    pub specs_dir: PathBuf,
+    pub scenarios_dir: PathBuf,
+    /// Twins to probe, as `(name, endpoint)`.
+    pub twins: Vec<(String, String)>,
+    /// Interval between server-sent events.
+    pub refresh: Duration,
+    /// Most recent sessions to include in a snapshot.
+    pub session_limit: usize,
+    /// Directory of workflows saved from the editor.
+    pub workflows_dir: PathBuf,
+    /// Editor base URL for workflow deep links; without one, workflows are
+    /// listed unlinked.
+    pub editor_url: Option<String>,
+    /// URL the editor reaches this dashboard at. [`serve`] fills it in from
+    /// the listener address when unset.
+    pub public_url: Option<String>,
+}
+
+impl Default for DashboardConfig {
+    fn default() -> Self {
+        Self {
+            metrics_dir: PathBuf::from("."),
+            specs_dir: PathBuf::from("specs"),
+            scenarios_dir: PathBuf::from("../scenarios-vault"),
+            twins: Vec::new(),
+            refresh: Duration::from_secs(5),
+            session_limit: 20,
+            workflows_dir: PathBuf::from("workflows"),
+            editor_url: None,
+            public_url: None,
+        }
+    }
+}
+
+/// Latest known quality signals of one spec.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpecStatus {
+    pub spec_id: String,
+    pub lint_score: Option<u32>,
+    pub lint_passed: Option<bool>,
+    pub linted_at: Option<DateTime<Utc>>,
+    /// Behaviors and edge cases covered by scenarios, as a percentage.
+    pub coverage_percentage: Option<f64>,
+    /// Pass rate of the latest scenario run, as a percentage.
+    pub scenario_pass_rate: Option<f64>,
+    pub scenarios_run_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TwinStatus {
+    pub name: String,
+    pub endpoint: String,
+    pub reachable: bool,
+    pub collections: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardSnapshot {
+    pub generated_at: DateTime<Utc>,
+    pub summary: MetricsSummary,
+    pub specs: Vec<SpecStatus>,
+    pub twins: Vec<TwinStatus>,
+    pub workflows: Vec<WorkflowEntry>,
+    /// Most recent sessions first.
+    pub sessions: Vec<SessionTimeline>,
+    /// Why coverage is missing from `specs`, when it could not be analyzed.
+    pub coverage_error: Option<String>,
+}
+
+/// One row per spec seen in the metrics or the coverage report, sorted by
+/// spec id.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn spec_statuses(store: &MetricsStore, coverage: Option<&CoverageReport>) -> Vec<SpecStatus> {
+    let mut statuses = BTreeMap::new();
+    if let Ok(data) = store.data.read() {
+        for validation in &data.spec_validations {
+            let status = status_for(&mut statuses, validation.spec_id.as_str());
+            if status
+                .linted_at
+                .is_none_or(|linted_at| linted_at <= validation.timestamp)
+            {
+                status.lint_score = Some(validation.overall_score);
+                status.lint_passed = Some(validation.passed);
+                status.linted_at = Some(validation.timestamp);
+            }
+        }
+        for validation in &data.scenario_validations {
+            let status = status_for(&mut statuses, validation.spec_id.as_str());
+            if status
+                .scenarios_run_at
+                .is_none_or(|run_at| run_at <= validation.timestamp)
+            {
+                status.scenario_pass_rate = (validation.total_scenarios > 0).then(|| {
+                    validation.passed_scenarios as f64 / validation.total_scenarios as f64 * 100.0
+                });
+                status.scenarios_run_at = Some(validation.timestamp);
+            }
+        }
+    }
+    for spec in coverage
+        .map(|report| report.specs.as_slice())
+        .unwrap_or_default()
+    {
+        status_for(&mut statuses, &spec.spec_id).coverage_percentage =
+            Some(spec.coverage_percentage);
+    }
+    statuses.into_values().collect()
+}
+
+fn status_for<'a>(
+    statuses: &'a mut BTreeMap<String, SpecStatus>,
+    spec_id: &str,
+) -> &'a mut SpecStatus {
+    statuses
+        .entry(spec_id.to_string())
+        .or_insert_with(|| SpecStatus {
+            spec_id: spec_id.to_string(),
+            lint_score: None,
+            lint_passed: None,
+            linted_at: None,
+            coverage_percentage: None,
+            scenario_pass_rate: None,
+            scenarios_run_at: None,
+        })
+}
+
+/// Gather a snapshot from the metrics store, the spec and scenario
+/// directories and the configured twins.
+pub async fn snapshot(config: &DashboardConfig) -> DashboardSnapshot {
+    let coverage = CoverageAnalyzer::new(&config.specs_dir, &config.scenarios_dir).analyze();
+    let (summary, specs, sessions) = {
+        let store = MetricsStore::new(&config.metrics_dir);
+        let mut sessions = store.sessions(&SessionQuery::default()).unwrap_or_default();
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.started_at));
+        sessions.truncate(config.session_limit);
+        (
+            store.get_summary(),
+            spec_statuses(&store, coverage.as_ref().ok()),
+            sessions.iter().map(SessionTimeline::new).collect(),
+        )
+    };
+
+    let mut twins = Vec::with_capacity(config.twins.len());
+    for (name, endpoint) in &config.twins {
+        let result = TwinInspectionClient::new(endpoint).list_collections().await;
+        twins.push(TwinStatus {
+            name: name.clone(),
+            endpoint: endpoint.clone(),
+            reachable: result.is_ok(),
+            error: result.as_ref().err().map(ToString::to_string),
+            collections: result.unwrap_or_default(),
+        });
+    }
+
+    let links = config
+        .editor_url
+        .as_deref()
+        .zip(config.public_url.as_deref())
+        .map(|(editor_url, dashboard_url)| LinkTargets {
+            editor_url,
+            dashboard_url,
+        });
+
+    DashboardSnapshot {
+        generated_at: Utc::now(),
+        summary,
+        specs,
+        twins,
+        workflows: workflow_inventory(&config.workflows_dir, links),
+        sessions,
+        coverage_error: coverage.err().map(|error| error.to_string()),
+    }
+}
+
+/// Serve the dashboard on `listener` until accepting a connection fails.
+///
+/// Routes: `/` (HTML), `/api/status` (full snapshot), `/api/specs`,
+/// `/api/sessions`, `/api/twins`, `/api/workflows`, `/api/workflows/<id>`
+/// (the saved workflow, for editor deep links) and `/events`
+/// (server-sent events).
+///
+/// # Errors
+/// Returns an error if the listener stops accepting connections.
+pub async fn serve(listener: TcpListener, mut config: DashboardConfig) -> std::io::Result<()> {
+    if config.public_url.is_none() {
+        config.public_url = Some(format!("http://{}", listener.local_addr()?));
+    }
+    let config = Arc::new(config);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let config = Arc::clone(&config);
+        tokio::spawn(async move {
+            // A client hanging up mid-response is not the server's problem.
+            let _ = handle(stream, &config).await;
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream, config: &DashboardConfig) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+    // Only the configured editor may read the dashboard from another origin,
+    // to open deep-linked workflows.
+    let cors = request
+        .header("origin")
+        .filter(|origin| Some(*origin) == config.editor_url.as_deref().and_then(origin_of))
+        .map(|origin| vec![("Access-Control-Allow-Origin", origin), ("Vary", "Origin")])
+        .unwrap_or_default();
+    if request.method != "GET" {
+        return respond(
+            &mut stream,
+            "405 Method Not Allowed",
+            &cors,
+            "text/plain",
+            "GET only",
+        )
+        .await;
+    }
+
+    let path = request.path.as_str();
+    match path {
+        "/" | "/index.html" => {
+            respond(
+                &mut stream,
+                "200 OK",
+                &cors,
+                "text/html; charset=utf-8",
+                INDEX_HTML,
+            )
+            .await
+        }
+        "/events" => stream_events(&mut stream, &cors, config).await,
+        "/api/status" | "/api/specs" | "/api/sessions" | "/api/twins" | "/api/workflows" => {
+            let snapshot = snapshot(config).await;
+            let body = match path {
+                "/api/specs" => serde_json::to_string(&snapshot.specs),
+                "/api/sessions" => serde_json::to_string(&snapshot.sessions),
+                "/api/twins" => serde_json::to_string(&snapshot.twins),
+                "/api/workflows" => serde_json::to_string(&snapshot.workflows),
+                _ => serde_json::to_string(&snapshot),
+            }
+            .map_err(std::io::Error::other)?;
+            respond(&mut stream, "200 OK", &cors, "application/json", &body).await
+        }
+        _ => match path
+            .strip_prefix("/api/workflows/")
+            .and_then(crate::ui::deep_link::decode)
+            .and_then(|id| saved_workflow_json(&config.workflows_dir, &id))
+        {
+            Some(workflow) => {
+                respond(&mut stream, "200 OK", &cors, "application/json", &workflow).await
+            }
+            None => {
+                respond(
+                    &mut stream,
+                    "404 Not Found",
+                    &cors,
+                    "text/plain",
+                    "Not found",
+                )
+                .await
+            }
+        },
+    }
+}
+
+/// `scheme://host[:port]` of `url`.
+fn origin_of(url: &str) -> Option<&str> {
+    let authority = url.find("://")? + 3;
+    let end = url[authority..]
+        .find(['/', '?', '#'])
+        .map_or(url.len(), |end| authority + end);
+    Some(&url[..end])
+}
+
+/// Send a `status` event carrying the snapshot now and after every refresh,
+/// until the client disconnects.
+async fn stream_events(
+    stream: &mut TcpStream,
+    cors: &[(&str, &str)],
+    config: &DashboardConfig,
+) -> std::io::Result<()> {
+    start_event_stream(stream, cors).await?;
+    loop {
+        let data = serde_json::to_string(&snapshot(config).await).map_err(std::io::Error::other)?;
+        stream
+            .write_all(format!("event: status\ndata: {data}\n\n").as_bytes())
+            .await?;
+        stream.flush().await?;
+        tokio::time::sleep(config.refresh).await;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::metrics::ScenarioValidationMetrics;
+    use tokio::io::AsyncReadExt;
+
+    fn scenario_run(spec: &str, total: usize, passed: usize) -> ScenarioValidationMetrics {
+        serde_json::from_value(serde_json::json!({
+            "timestamp": Utc::now(),
+            "spec_id": spec,
+            "total_scenarios": total,
+            "passed_scenarios": passed,
+            "failed_scenarios": total - passed,
+            "category_breakdown": {},
+            "duration_ms": 10,
+        }))
+        .unwrap()
+    }
+
+    async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[test]
+    fn given_scenario_runs_when_building_spec_statuses_then_latest_run_sets_pass_rate() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MetricsStore::new(dir.path());
+        store
+            .record_scenario_validation(scenario_run("checkout", 4, 1))
+            .unwrap();
+        store
+            .record_scenario_validation(scenario_run("checkout", 4, 3))
+            .unwrap();
+        store
+            .record_scenario_validation(scenario_run("billing", 0, 0))
+            .unwrap();
+
+        let statuses = spec_statuses(&store, None);
+
+        let ids = statuses
+            .iter()
+            .map(|status| status.spec_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["billing", "checkout"]);
+        assert_eq!(statuses[0].scenario_pass_rate, None);
+        assert_eq!(statuses[1].scenario_pass_rate, Some(75.0));
+        assert_eq!(statuses[1].lint_score, None);
+    }
+
+    #[tokio::test]
+    async fn given_running_dashboard_when_requesting_routes_then_json_html_and_events_are_served() {
+        let dir = tempfile::tempdir().unwrap();
+        MetricsStore::new(dir.path())
+            .record_scenario_validation(scenario_run("checkout", 2, 2))
+            .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(
+            listener,
+            DashboardConfig {
+                metrics_dir: dir.path().to_path_buf(),
+                specs_dir: dir.path().join("specs"),
+                scenarios_dir: dir.path().join("scenarios"),
+                ..DashboardConfig::default()
+            },
+        ));
+
+        let specs = get(addr, "/api/specs").await;
+        assert!(specs.starts_with("HTTP/1.1 200 OK"), "{specs}");
+        let body = specs.split("\r\n\r\n").nth(1).unwrap();
+        let specs: Vec<SpecStatus> = serde_json::from_str(body).unwrap();
+        assert_eq!(specs[0].spec_id, "checkout");
+        assert_eq!(specs[0].scenario_pass_rate, Some(100.0));
+
+        assert!(get(addr, "/").await.contains("text/html"));
+        assert!(get(addr, "/missing").await.starts_with("HTTP/1.1 404"));
+
+        let mut events = TcpStream::connect(addr).await.unwrap();
+        events
+            .write_all(b"GET /events HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut received = String::new();
+        let mut buffer = [0_u8; 4096];
+        while !received.contains("\n\n") || !received.contains("data: ") {
+            let read = events.read(&mut buffer).await.unwrap();
+            assert!(read > 0, "stream closed early: {received}");
+            received.push_str(&String::from_utf8_lossy(&buffer[..read]));
+        }
+        assert!(received.contains("text/event-stream"));
+        assert!(received.contains("event: status\ndata: {"));
+    }
+
+    #[tokio::test]
+    async fn given_editor_url_when_requesting_from_origins_then_only_the_editor_is_allowed() {
+        let dir = tempfile::tempdir().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(
+            listener,
+            DashboardConfig {
+                metrics_dir: dir.path().to_path_buf(),
+                specs_dir: dir.path().join("specs"),
+                scenarios_dir: dir.path().join("scenarios"),
+                editor_url: Some("http://localhost:8081/editor".to_string()),
+                ..DashboardConfig::default()
+            },
+        ));
+        let from = |origin: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET /missing HTTP/1.1\r\nOrigin: {origin}\r\n\r\n").as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        assert!(from("http://localhost:8081")
+            .await
+            .contains("Access-Control-Allow-Origin: http://localhost:8081\r\n"));
+        assert!(!from("https://evil.example")
+            .await
+            .contains("Access-Control-Allow-Origin"));
+    }
+}
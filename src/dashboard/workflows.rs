@@ -0,0 +1,186 @@
+//! Inventory of the workflows saved from the canvas editor: one JSON file
+//! per workflow in a workspace directory.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::flow_extender::{suggest_extensions, ExtensionPriority};
+use crate::graph::Workflow;
+use crate::ui::deep_link::editor_link;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastRun {
+    pub timestamp: DateTime<Utc>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSuggestion {
+    pub key: String,
+    pub title: String,
+    pub priority: ExtensionPriority,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowEntry {
+    /// File stem, which also names the workflow in `/api/workflows/<id>`.
+    pub id: String,
+    pub nodes: usize,
+    pub edges: usize,
+    pub runs: usize,
+    pub last_run: Option<LastRun>,
+    /// Flow-extender suggestions not yet applied or dismissed.
+    pub pending_suggestions: Vec<PendingSuggestion>,
+    /// Opens the editor on this workflow, when an editor URL is configured.
+    pub editor_link: Option<String>,
+}
+
+/// Where inventory deep links point: the editor, and the dashboard URL the
+/// editor fetches the workflow from.
+#[derive(Debug, Clone, Copy)]
+pub struct LinkTargets<'a> {
+    pub editor_url: &'a str,
+    pub dashboard_url: &'a str,
+}
+
+impl WorkflowEntry {
+    #[must_use]
+    pub fn new(id: &str, workflow: &Workflow, links: Option<LinkTargets<'_>>) -> Self {
+        Self {
+            id: id.to_string(),
+            nodes: workflow.nodes.len(),
+            edges: workflow.connections.len(),
+            runs: workflow.history.len(),
+            last_run: workflow
+                .history
+                .iter()
+                .max_by_key(|run| run.timestamp)
+                .map(|run| LastRun {
+                    timestamp: run.timestamp,
+                    success: run.success,
+                }),
+            pending_suggestions: suggest_extensions(workflow)
+                .into_iter()
+                .map(|extension| PendingSuggestion {
+                    key: extension.key,
+                    title: extension.title,
+                    priority: extension.priority,
+                })
+                .collect(),
+            editor_link: links.map(|links| {
+                editor_link(
+                    links.editor_url,
+                    &format!(
+                        "{}/api/workflows/{id}",
+                        links.dashboard_url.trim_end_matches('/')
+                    ),
+                )
+            }),
+        }
+    }
+}
+
+/// Saved workflows in `dir`, sorted by id. Files that are not workflow JSON
+/// are skipped; a missing directory is an empty workspace.
+#[must_use]
+pub fn workflow_inventory(dir: &Path, links: Option<LinkTargets<'_>>) -> Vec<WorkflowEntry> {
+    let mut entries = saved_workflows(dir)
+        .filter_map(|(id, path)| {
+            let workflow = read_workflow(&path)?;
+            Some(WorkflowEntry::new(&id, &workflow, links))
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|left, right| left.id.cmp(&right.id));
+    entries
+}
+
+/// The saved workflow with file stem `id`, as stored.
+#[must_use]
+pub fn saved_workflow_json(dir: &Path, id: &str) -> Option<String> {
+    // Look the id up rather than joining it onto `dir`, so a request can
+    // only reach files the inventory lists.
+    let (_, path) = saved_workflows(dir).find(|(stem, _)| stem == id)?;
+    read_workflow(&path)?;
+    std::fs::read_to_string(path).ok()
+}
+
+fn saved_workflows(dir: &Path) -> impl Iterator<Item = (String, PathBuf)> {
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?.to_string();
+            Some((stem, path))
+        })
+}
+
+fn read_workflow(path: &Path) -> Option<Workflow> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    fn saved(dir: &Path, id: &str, workflow: &Workflow) {
+        std::fs::write(
+            dir.join(format!("{id}.json")),
+            serde_json::to_string(workflow).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn given_saved_workflows_when_listing_inventory_then_counts_runs_and_links_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut signup = Workflow::new();
+        let first = signup.add_node("http-handler", 0.0, 0.0);
+        let second = signup.add_node("run", 0.0, 100.0);
+        let main = crate::graph::PortName("main".to_string());
+        signup.add_connection(first, second, &main, &main).unwrap();
+        signup.history.push(crate::graph::RunRecord {
+            id: uuid::Uuid::new_v4(),
+            timestamp: Utc::now(),
+            results: std::collections::HashMap::new(),
+            success: false,
+            restate_invocation_id: None,
+            durations: std::collections::HashMap::new(),
+        });
+        saved(dir.path(), "signup", &signup);
+        saved(dir.path(), "empty", &Workflow::new());
+        std::fs::write(dir.path().join("notes.json"), "{\"not\": \"a workflow\"}").unwrap();
+
+        let inventory = workflow_inventory(
+            dir.path(),
+            Some(LinkTargets {
+                editor_url: "http://localhost:8080",
+                dashboard_url: "http://127.0.0.1:8090/",
+            }),
+        );
+
+        let ids = inventory
+            .iter()
+            .map(|entry| entry.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["empty", "signup"]);
+        let entry = &inventory[1];
+        assert_eq!((entry.nodes, entry.edges, entry.runs), (2, 1, 1));
+        assert_eq!(entry.last_run.as_ref().map(|run| run.success), Some(false));
+        assert_eq!(
+            entry.editor_link.as_deref(),
+            Some("http://localhost:8080/?workflow=http%3A%2F%2F127.0.0.1%3A8090%2Fapi%2Fworkflows%2Fsignup")
+        );
+        assert!(saved_workflow_json(dir.path(), "signup").is_some());
+        assert!(saved_workflow_json(dir.path(), "notes").is_none());
+        assert!(saved_workflow_json(dir.path(), "../signup").is_none());
+    }
+}
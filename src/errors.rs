@@ -18,6 +18,9 @@ pub enum WorkflowError {
 
     #[error("Cannot connect node to itself")]
     SelfConnection,
+
+    #[error("Editing is not allowed for this role")]
+    ReadOnly,
 }
 
 pub type WorkflowResult<T> = Result<T, WorkflowError>;
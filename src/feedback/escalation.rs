@@ -0,0 +1,211 @@
+//! Choosing the feedback level for the next iteration from a session's
+//! history: start low, and reveal more once an agent keeps failing the same
+//! way.
+
+use serde::{Deserialize, Serialize};
+
+use super::{FeedbackLevel, FeedbackSanitizer, SanitizedFeedback};
+use crate::metrics::{MetricsStore, QualityGateSession};
+use crate::scenario_runner::ScenarioResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscalationPolicy {
+    pub start_level: FeedbackLevel,
+    pub escalated_level: FeedbackLevel,
+    /// Consecutive failed iterations of one category that trigger
+    /// escalation.
+    pub after_failures: usize,
+}
+
+impl Default for EscalationPolicy {
+    fn default() -> Self {
+        Self {
+            start_level: FeedbackLevel::CATEGORICAL,
+            escalated_level: FeedbackLevel::DIAGNOSTIC,
+            after_failures: 3,
+        }
+    }
+}
+
+/// The level chosen for an iteration and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelDecision {
+    pub level: FeedbackLevel,
+    pub reason: String,
+}
+
+impl EscalationPolicy {
+    /// Level for the iteration after the session's last one. Once a session
+    /// has been given the escalated level it keeps it.
+    #[must_use]
+    pub fn decide(&self, session: &QualityGateSession) -> LevelDecision {
+        let escalated = self.escalated_level.value();
+        if let Some(earlier) = session
+            .iterations
+            .iter()
+            .find(|iteration| iteration.feedback_level.value() >= escalated)
+        {
+            return LevelDecision {
+                level: self.escalated_level,
+                reason: format!("Escalated since iteration {}", earlier.iteration.value()),
+            };
+        }
+
+        let Some(category) = session
+            .iterations
+            .last()
+            .filter(|iteration| !iteration.overall_passed)
+            .and_then(|iteration| iteration.failure_category.as_ref())
+            .map(ToString::to_string)
+        else {
+            return LevelDecision {
+                level: self.start_level,
+                reason: "No failed iterations yet".to_string(),
+            };
+        };
+        let streak = session
+            .iterations
+            .iter()
+            .rev()
+            .take_while(|iteration| {
+                !iteration.overall_passed
+                    && iteration
+                        .failure_category
+                        .as_ref()
+                        .is_some_and(|name| name.to_string() == category)
+            })
+            .count();
+
+        if streak >= self.after_failures {
+            LevelDecision {
+                level: self.escalated_level,
+                reason: format!("{streak} consecutive failed iterations in category {category}"),
+            }
+        } else {
+            LevelDecision {
+                level: self.start_level,
+                reason: format!(
+                    "{streak} of {} consecutive failures in category {category} before escalating",
+                    self.after_failures
+                ),
+            }
+        }
+    }
+
+    /// [`decide`](Self::decide) for a session recorded in `store`; a session
+    /// the store does not know starts at the start level.
+    #[must_use]
+    pub fn decide_for(&self, store: &MetricsStore, session_id: &str) -> LevelDecision {
+        store.get_session(session_id).map_or_else(
+            || LevelDecision {
+                level: self.start_level,
+                reason: format!("No history for session {session_id}"),
+            },
+            |session| self.decide(&session),
+        )
+    }
+
+    /// Sanitize `raw_results` at the level this policy picks for the
+    /// session, recording the decision in the feedback.
+    #[must_use]
+    pub fn sanitize(
+        &self,
+        store: &MetricsStore,
+        session_id: &str,
+        raw_results: &[ScenarioResult],
+        iteration: u32,
+    ) -> SanitizedFeedback {
+        let decision = self.decide_for(store, session_id);
+        let mut feedback =
+            FeedbackSanitizer::new(decision.level.value()).sanitize(raw_results, iteration);
+        feedback.level = Some(decision);
+        feedback
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    fn failed_iteration(
+        number: u32,
+        category: &str,
+        level: u8,
+    ) -> crate::metrics::QualityGateIteration {
+        serde_json::from_value(serde_json::json!({
+            "iteration": number,
+            "timestamp": chrono::Utc::now(),
+            "spec_passed": true,
+            "spec_score": 90,
+            "scenarios_passed": false,
+            "scenarios_total": 4,
+            "scenarios_passed_count": 2,
+            "overall_passed": false,
+            "failure_category": category,
+            "feedback_level": level,
+            "duration_ms": 10,
+        }))
+        .unwrap()
+    }
+
+    fn store_with(iterations: &[(&str, u8)]) -> (tempfile::TempDir, MetricsStore, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MetricsStore::new(dir.path());
+        let session_id = store.start_session("checkout", "1.0.0").unwrap();
+        for (number, (category, level)) in (1..).zip(iterations) {
+            store
+                .record_iteration(&session_id, failed_iteration(number, category, *level))
+                .unwrap();
+        }
+        (dir, store, session_id)
+    }
+
+    #[test]
+    fn given_repeated_failures_in_one_category_when_deciding_then_level_escalates() {
+        let policy = EscalationPolicy::default();
+        let (_dir, store, session_id) =
+            store_with(&[("Timeout", 2), ("Timeout", 2), ("Timeout", 2)]);
+
+        let decision = policy.decide_for(&store, &session_id);
+
+        assert_eq!(decision.level, FeedbackLevel::DIAGNOSTIC);
+        assert_eq!(
+            decision.reason,
+            "3 consecutive failed iterations in category Timeout"
+        );
+        let feedback = policy.sanitize(&store, &session_id, &[], 4);
+        assert_eq!(feedback.level, Some(decision));
+    }
+
+    #[test]
+    fn given_changing_categories_when_deciding_then_level_stays_at_start() {
+        let policy = EscalationPolicy::default();
+        let (_dir, store, session_id) =
+            store_with(&[("Timeout", 2), ("Timeout", 2), ("Server Error", 2)]);
+
+        let decision = policy.decide_for(&store, &session_id);
+
+        assert_eq!(decision.level, FeedbackLevel::CATEGORICAL);
+        assert_eq!(
+            decision.reason,
+            "1 of 3 consecutive failures in category Server Error before escalating"
+        );
+        assert_eq!(
+            policy.decide_for(&store, "unknown").level,
+            FeedbackLevel::CATEGORICAL
+        );
+    }
+
+    #[test]
+    fn given_session_already_escalated_when_category_changes_then_level_is_kept() {
+        let policy = EscalationPolicy::default();
+        let (_dir, store, session_id) =
+            store_with(&[("Timeout", 2), ("Timeout", 4), ("Server Error", 4)]);
+
+        let decision = policy.decide_for(&store, &session_id);
+
+        assert_eq!(decision.level, FeedbackLevel::DIAGNOSTIC);
+        assert_eq!(decision.reason, "Escalated since iteration 2");
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+mod escalation;
+
+pub use escalation::{EscalationPolicy, LevelDecision};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct FeedbackLevel(u8);
 
@@ -193,6 +197,39 @@ pub struct SanitizedFailure {
     pub description: String,
     pub hint: String,
     pub spec_text: String,
+    /// The raw failure as far as the level reveals it; `None` below the
+    /// diagnostic level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<FailureDetails>,
+}
+
+/// What a failed scenario did, taken from its step results. Each field is
+/// filled only when the level's config allows it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailureDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_step: Option<String>,
+    /// Every step that ran, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<StepOutline>,
+    /// Failed assertions of the failed step, expected against actual.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assertions: Vec<super::scenario_runner::AssertionFailure>,
+    /// Status codes the failed step received.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_codes: Vec<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepOutline {
+    pub step_id: String,
+    pub passed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,10 +240,12 @@ pub struct SanitizedFeedback {
     pub total_count: usize,
     pub failures: Vec<SanitizedFailure>,
     pub summary: String,
+    /// The level an escalation policy chose for this iteration, and why.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<LevelDecision>,
 }
 
 pub struct FeedbackSanitizer {
-    #[allow(dead_code)]
     config: FeedbackConfig,
 }
 
@@ -231,7 +270,7 @@ impl FeedbackSanitizer {
         let failures: Vec<SanitizedFailure> = raw_results
             .iter()
             .filter(|r| !r.passed)
-            .map(Self::sanitize_failure)
+            .map(|result| self.sanitize_failure(result))
             .collect();
 
         let summary = format!("{failed_count} of {total_count} behavioral tests failed");
@@ -243,10 +282,69 @@ impl FeedbackSanitizer {
             total_count,
             failures,
             summary,
+            level: None,
+        }
+    }
+
+    /// The results with step transcripts cut down to what this level may
+    /// reveal: whole exchanges when bodies are allowed, method, URL and
+    /// status when status codes are, and no transcript otherwise.
+    #[must_use]
+    pub fn redact_transcripts(
+        &self,
+        raw_results: &[super::scenario_runner::ScenarioResult],
+    ) -> Vec<super::scenario_runner::ScenarioResult> {
+        raw_results
+            .iter()
+            .map(|result| super::scenario_runner::ScenarioResult {
+                steps: result
+                    .steps
+                    .iter()
+                    .map(|step| super::scenario_runner::StepResult {
+                        transcript: step
+                            .transcript
+                            .as_ref()
+                            .filter(|_| self.config.includes_status_codes)
+                            .map(|transcript| {
+                                transcript
+                                    .iter()
+                                    .map(|exchange| self.redact_exchange(exchange))
+                                    .collect()
+                            }),
+                        ..step.clone()
+                    })
+                    .collect(),
+                ..result.clone()
+            })
+            .collect()
+    }
+
+    fn redact_exchange(
+        &self,
+        exchange: &super::scenario_runner::HttpExchange,
+    ) -> super::scenario_runner::HttpExchange {
+        let config = &self.config;
+        let mut exchange = exchange.clone();
+        if !config.includes_bodies {
+            exchange.request.headers.clear();
+            exchange.request.body = None;
         }
+        if let Some(response) = &mut exchange.response {
+            if !config.includes_bodies {
+                response.headers.clear();
+                response.body = None;
+            }
+            if !config.includes_timing {
+                response.latency_ms = 0;
+            }
+        }
+        exchange
     }
 
-    fn sanitize_failure(result: &super::scenario_runner::ScenarioResult) -> SanitizedFailure {
+    fn sanitize_failure(
+        &self,
+        result: &super::scenario_runner::ScenarioResult,
+    ) -> SanitizedFailure {
         let category = Self::categorize_failure(result);
         let description = Self::sanitize_description(&category);
         let hint = Self::generate_hint(&category);
@@ -258,7 +356,69 @@ impl FeedbackSanitizer {
             description,
             hint,
             spec_text,
+            details: self.failure_details(result),
+        }
+    }
+
+    fn failure_details(
+        &self,
+        result: &super::scenario_runner::ScenarioResult,
+    ) -> Option<FailureDetails> {
+        let config = &self.config;
+        let failed_step = result.steps.iter().find(|step| !step.passed);
+        let mut status_codes = failed_step
+            .into_iter()
+            .flat_map(|step| step.transcript.iter().flatten())
+            .filter_map(|exchange| exchange.response.as_ref().map(|response| response.status))
+            .collect::<Vec<_>>();
+        if status_codes.is_empty() {
+            status_codes = failed_step
+                .into_iter()
+                .flat_map(|step| &step.failed_assertions)
+                .filter(|assertion| assertion.assertion_type == "status")
+                .filter_map(|assertion| assertion.actual.as_ref()?.as_u64())
+                .filter_map(|status| u16::try_from(status).ok())
+                .collect();
         }
+
+        let details = FailureDetails {
+            scenario_id: config
+                .includes_scenario_ids
+                .then(|| result.scenario_id.clone()),
+            failed_step: failed_step
+                .filter(|_| config.includes_step_sequences)
+                .map(|step| step.step_id.clone()),
+            steps: if config.includes_step_sequences {
+                result
+                    .steps
+                    .iter()
+                    .map(|step| StepOutline {
+                        step_id: step.step_id.clone(),
+                        passed: step.passed,
+                        duration_ms: config.includes_timing.then_some(step.duration_ms),
+                        error: step
+                            .error
+                            .clone()
+                            .filter(|_| config.includes_exact_assertions),
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            },
+            assertions: if config.includes_exact_assertions {
+                failed_step
+                    .map(|step| step.failed_assertions.clone())
+                    .unwrap_or_default()
+            } else {
+                Vec::new()
+            },
+            status_codes: if config.includes_status_codes {
+                status_codes
+            } else {
+                Vec::new()
+            },
+        };
+        (details != FailureDetails::default()).then_some(details)
     }
 
     fn categorize_failure(result: &super::scenario_runner::ScenarioResult) -> FailureCategoryName {
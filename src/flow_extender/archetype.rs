@@ -0,0 +1,369 @@
+//! Spec archetypes and the workspace manifest that links them to extension
+//! presets and scaffold templates.
+//!
+//! A spec may declare an archetype such as `webhook-service`. Workflows
+//! created from that spec start from the archetype's scaffold and get its
+//! presets recommended. The mapping lives in [`ArchetypeManifest`], which
+//! ships with built-in defaults and can be overridden per workspace.
+
+use super::{ExtensionPreset, ExtensionPresetKey, ExtensionPresetRegistry};
+use crate::graph::workflow_node::WorkflowNode;
+use crate::graph::{NodeId, Workflow};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+const SCAFFOLD_ORIGIN_X: f32 = 120.0;
+const SCAFFOLD_ORIGIN_Y: f32 = 160.0;
+const SCAFFOLD_STEP_X: f32 = 240.0;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpecArchetype {
+    WebhookService,
+    ApprovalWorkflow,
+    Saga,
+}
+
+impl SpecArchetype {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::WebhookService => "webhook-service",
+            Self::ApprovalWorkflow => "approval-workflow",
+            Self::Saga => "saga",
+        }
+    }
+
+    #[must_use]
+    pub const fn all() -> [Self; 3] {
+        [Self::WebhookService, Self::ApprovalWorkflow, Self::Saga]
+    }
+
+    const fn default_presets(self) -> &'static [ExtensionPresetKey] {
+        match self {
+            Self::WebhookService => &[ExtensionPresetKey::Webhook],
+            Self::ApprovalWorkflow => &[ExtensionPresetKey::Approval],
+            Self::Saga => &[ExtensionPresetKey::RetrySaga],
+        }
+    }
+
+    const fn default_scaffold(self) -> &'static [&'static str] {
+        match self {
+            Self::WebhookService => &["http-handler", "run", "set-state"],
+            Self::ApprovalWorkflow => &["workflow-submit", "run", "awakeable", "run"],
+            Self::Saga => &["workflow-submit", "run", "run", "set-state"],
+        }
+    }
+}
+
+impl FromStr for SpecArchetype {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "webhook-service" => Ok(Self::WebhookService),
+            "approval-workflow" => Ok(Self::ApprovalWorkflow),
+            "saga" => Ok(Self::Saga),
+            _ => Err(format!("Unknown spec archetype: {value}")),
+        }
+    }
+}
+
+/// What a workspace recommends for one archetype.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchetypeBinding {
+    pub archetype: SpecArchetype,
+    /// Built-in or custom preset keys, in recommendation order.
+    #[serde(default)]
+    pub preset_keys: Vec<String>,
+    /// Node types laid out left to right and chained `out` → `in`.
+    #[serde(default)]
+    pub scaffold: Vec<String>,
+}
+
+impl ArchetypeBinding {
+    fn builtin(archetype: SpecArchetype) -> Self {
+        Self {
+            archetype,
+            preset_keys: archetype
+                .default_presets()
+                .iter()
+                .map(|key| key.as_str().to_string())
+                .collect(),
+            scaffold: archetype
+                .default_scaffold()
+                .iter()
+                .map(|node_type| (*node_type).to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Archetype mapping stored in the workspace manifest.
+///
+/// Only overrides are serialized; archetypes without a binding fall back to
+/// the built-in mapping.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ArchetypeManifest {
+    #[serde(default)]
+    bindings: Vec<ArchetypeBinding>,
+}
+
+/// A workflow scaffolded from a spec, plus the presets to offer on it.
+#[derive(Debug, Clone)]
+pub struct ArchetypeScaffold {
+    pub archetype: SpecArchetype,
+    pub workflow: Workflow,
+    pub recommended_presets: Vec<ExtensionPreset>,
+}
+
+impl ArchetypeManifest {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The effective binding: a workspace override if present, else built-in.
+    #[must_use]
+    pub fn binding(&self, archetype: SpecArchetype) -> ArchetypeBinding {
+        self.bindings
+            .iter()
+            .find(|binding| binding.archetype == archetype)
+            .cloned()
+            .unwrap_or_else(|| ArchetypeBinding::builtin(archetype))
+    }
+
+    #[must_use]
+    pub fn is_overridden(&self, archetype: SpecArchetype) -> bool {
+        self.bindings
+            .iter()
+            .any(|binding| binding.archetype == archetype)
+    }
+
+    /// Override the binding for an archetype.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if a preset key is unknown to `registry` or a
+    /// scaffold entry is not a known node type.
+    pub fn bind(
+        &mut self,
+        registry: &ExtensionPresetRegistry,
+        binding: ArchetypeBinding,
+    ) -> Result<(), String> {
+        for key in &binding.preset_keys {
+            registry.get(key)?;
+        }
+        if let Some(node_type) = binding
+            .scaffold
+            .iter()
+            .find(|node_type| WorkflowNode::from_str(node_type).is_err())
+        {
+            return Err(format!("Unknown scaffold node type: {node_type}"));
+        }
+
+        match self
+            .bindings
+            .iter_mut()
+            .find(|item| item.archetype == binding.archetype)
+        {
+            Some(existing) => *existing = binding,
+            None => self.bindings.push(binding),
+        }
+        Ok(())
+    }
+
+    /// Drop a workspace override, restoring the built-in binding.
+    pub fn reset(&mut self, archetype: SpecArchetype) -> bool {
+        let before = self.bindings.len();
+        self.bindings
+            .retain(|binding| binding.archetype != archetype);
+        self.bindings.len() != before
+    }
+
+    /// Presets recommended for an archetype, in binding order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if a bound preset no longer exists in `registry`.
+    pub fn recommended_presets(
+        &self,
+        registry: &ExtensionPresetRegistry,
+        archetype: SpecArchetype,
+    ) -> Result<Vec<ExtensionPreset>, String> {
+        self.binding(archetype)
+            .preset_keys
+            .iter()
+            .map(|key| registry.get(key))
+            .collect()
+    }
+
+    /// Build a new workflow from the archetype's scaffold template.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if a scaffold step cannot be connected to the next.
+    pub fn scaffold_workflow(&self, archetype: SpecArchetype) -> Result<Workflow, String> {
+        let binding = self.binding(archetype);
+        let mut workflow = Workflow::new();
+        let mut previous: Option<NodeId> = None;
+
+        for (index, node_type) in binding.scaffold.iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let x = SCAFFOLD_STEP_X.mul_add(index as f32, SCAFFOLD_ORIGIN_X);
+            let id = workflow.add_node(node_type, x, SCAFFOLD_ORIGIN_Y);
+            if let Some(source) = previous {
+                workflow
+                    .add_connection_checked(source, id, &"out".into(), &"in".into())
+                    .map_err(|err| format!("Scaffold step {node_type} cannot be wired: {err}"))?;
+            }
+            previous = Some(id);
+        }
+
+        Ok(workflow)
+    }
+
+    /// Scaffold a workflow for a spec archetype and collect its presets.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if the scaffold cannot be built or a bound preset
+    /// is missing from `registry`.
+    pub fn scaffold(
+        &self,
+        registry: &ExtensionPresetRegistry,
+        archetype: SpecArchetype,
+    ) -> Result<ArchetypeScaffold, String> {
+        Ok(ArchetypeScaffold {
+            archetype,
+            workflow: self.scaffold_workflow(archetype)?,
+            recommended_presets: self.recommended_presets(registry, archetype)?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::{ArchetypeBinding, ArchetypeManifest, SpecArchetype};
+    use crate::flow_extender::{ExtensionPreset, ExtensionPresetRegistry};
+    use std::str::FromStr;
+
+    #[test]
+    fn given_builtin_manifest_when_scaffolding_every_archetype_then_steps_are_chained() {
+        let manifest = ArchetypeManifest::new();
+        let registry = ExtensionPresetRegistry::new();
+
+        for archetype in SpecArchetype::all() {
+            let scaffold = manifest.scaffold(&registry, archetype).unwrap();
+            let steps = manifest.binding(archetype).scaffold.len();
+
+            assert_eq!(scaffold.workflow.nodes.len(), steps);
+            assert_eq!(scaffold.workflow.connections.len(), steps - 1);
+            assert!(!scaffold.recommended_presets.is_empty());
+        }
+    }
+
+    #[test]
+    fn given_webhook_service_when_recommending_then_webhook_preset_is_offered() {
+        let presets = ArchetypeManifest::new()
+            .recommended_presets(
+                &ExtensionPresetRegistry::new(),
+                SpecArchetype::WebhookService,
+            )
+            .unwrap();
+
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].key, "webhook");
+    }
+
+    #[test]
+    fn given_custom_preset_binding_when_recommending_then_override_wins_until_reset() {
+        let mut registry = ExtensionPresetRegistry::new();
+        registry
+            .define(ExtensionPreset {
+                key: "team-saga".to_string(),
+                title: "Team saga".to_string(),
+                description: String::new(),
+                extension_keys: vec!["add-compensation-branch".to_string()],
+            })
+            .unwrap();
+        let mut manifest = ArchetypeManifest::new();
+        manifest
+            .bind(
+                &registry,
+                ArchetypeBinding {
+                    archetype: SpecArchetype::Saga,
+                    preset_keys: vec!["team-saga".to_string(), "retry-saga".to_string()],
+                    scaffold: vec!["workflow-submit".to_string(), "run".to_string()],
+                },
+            )
+            .unwrap();
+
+        let keys = manifest
+            .recommended_presets(&registry, SpecArchetype::Saga)
+            .unwrap()
+            .into_iter()
+            .map(|preset| preset.key)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec!["team-saga".to_string(), "retry-saga".to_string()]
+        );
+        assert_eq!(
+            manifest
+                .scaffold_workflow(SpecArchetype::Saga)
+                .unwrap()
+                .nodes
+                .len(),
+            2
+        );
+
+        assert!(manifest.reset(SpecArchetype::Saga));
+        assert!(!manifest.is_overridden(SpecArchetype::Saga));
+    }
+
+    #[test]
+    fn given_unknown_preset_or_node_type_when_binding_then_manifest_is_unchanged() {
+        let registry = ExtensionPresetRegistry::new();
+        let mut manifest = ArchetypeManifest::new();
+
+        let unknown_preset = manifest.bind(
+            &registry,
+            ArchetypeBinding {
+                archetype: SpecArchetype::Saga,
+                preset_keys: vec!["missing".to_string()],
+                scaffold: Vec::new(),
+            },
+        );
+        let unknown_node = manifest.bind(
+            &registry,
+            ArchetypeBinding {
+                archetype: SpecArchetype::Saga,
+                preset_keys: Vec::new(),
+                scaffold: vec!["teleport".to_string()],
+            },
+        );
+
+        assert!(unknown_preset.is_err());
+        assert!(unknown_node.is_err());
+        assert_eq!(manifest, ArchetypeManifest::new());
+    }
+
+    #[test]
+    fn given_manifest_json_when_round_tripping_then_overrides_are_preserved() {
+        let json = r#"{"bindings":[{"archetype":"approval-workflow","preset_keys":["approval"]}]}"#;
+
+        let manifest = serde_json::from_str::<ArchetypeManifest>(json).unwrap();
+
+        assert!(manifest.is_overridden(SpecArchetype::ApprovalWorkflow));
+        assert!(manifest
+            .binding(SpecArchetype::ApprovalWorkflow)
+            .scaffold
+            .is_empty());
+        assert_eq!(
+            SpecArchetype::from_str(SpecArchetype::ApprovalWorkflow.as_str()),
+            Ok(SpecArchetype::ApprovalWorkflow)
+        );
+    }
+}
@@ -0,0 +1,448 @@
+//! Acceptance tracking for applied extensions and the score calibration
+//! learned from it.
+//!
+//! Every applied extension ends up kept as generated, modified by hand, or
+//! reverted. [`ExtensionAcceptanceLog`] records the latest outcome per patch
+//! fingerprint, and rules whose patches tend to survive get a small boost to
+//! their confidence score while frequently reverted rules are damped.
+
+use super::{
+    analyze_suggestions, extension_fingerprint_of, extension_key_of, is_pristine_extension_node,
+    revert_extension, score_breakdown_for, ExtensionKey, ExtensionSuggestionAnalysis,
+    RevertedExtension, ScoreBreakdown, ScoreFactor,
+};
+use crate::graph::Workflow;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Largest amount calibration can add to or remove from a score.
+const CALIBRATION_WEIGHT: f32 = 0.15;
+/// Pseudo-observations at a neutral rate, so a handful of outcomes cannot
+/// swing a score to the extremes.
+const PRIOR_SAMPLES: f32 = 4.0;
+const NEUTRAL_RATE: f32 = 0.5;
+
+thread_local! {
+    static ACTIVE_LOG: RefCell<ExtensionAcceptanceLog> = RefCell::default();
+}
+
+/// Make `log` the calibration applied by the default scoring path
+/// ([`super::suggest_extensions_with_analysis`],
+/// [`super::explain_extension_score`], and compound plans) on this thread.
+///
+/// The browser app runs on one thread and installs its persisted log here
+/// whenever it changes.
+pub fn set_active_log(log: ExtensionAcceptanceLog) {
+    ACTIVE_LOG.with(|active| *active.borrow_mut() = log);
+}
+
+/// Runs `f` with the calibration installed by [`set_active_log`], which is
+/// empty until one is installed.
+pub(super) fn with_active_log<T>(f: impl FnOnce(&ExtensionAcceptanceLog) -> T) -> T {
+    ACTIVE_LOG.with(|active| f(&active.borrow()))
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExtensionOutcome {
+    /// Every generated node is still exactly as applied.
+    Kept,
+    /// At least one generated node was renamed, reconfigured, or rewired.
+    Modified,
+    Reverted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExtensionOutcomeRecord {
+    pub key: String,
+    pub fingerprint: String,
+    pub outcome: ExtensionOutcome,
+}
+
+/// Outcome counts for one rule.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AcceptanceStats {
+    pub key: String,
+    pub kept: usize,
+    pub modified: usize,
+    pub reverted: usize,
+}
+
+impl AcceptanceStats {
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.kept + self.modified + self.reverted
+    }
+
+    /// Share of outcomes that survived, counting a modification as half.
+    /// `None` until at least one outcome is recorded.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn acceptance_rate(&self) -> Option<f32> {
+        (self.total() > 0).then(|| self.accepted_weight() / self.total() as f32)
+    }
+
+    /// Score adjustment for this rule, or `None` without any outcomes.
+    ///
+    /// Unlike count factors, `contribution` is not `count * weight`: it is
+    /// the rate's distance from neutral, smoothed towards neutral for small
+    /// samples, scaled into `[-weight, weight]`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calibration_factor(&self) -> Option<ScoreFactor> {
+        if self.total() == 0 {
+            return None;
+        }
+        let smoothed = NEUTRAL_RATE.mul_add(PRIOR_SAMPLES, self.accepted_weight())
+            / (self.total() as f32 + PRIOR_SAMPLES);
+        Some(ScoreFactor {
+            key: "acceptance-calibration".to_string(),
+            count: self.total(),
+            weight: CALIBRATION_WEIGHT,
+            contribution: CALIBRATION_WEIGHT * (smoothed - NEUTRAL_RATE) / NEUTRAL_RATE,
+        })
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn accepted_weight(&self) -> f32 {
+        (self.modified as f32).mul_add(0.5, self.kept as f32)
+    }
+}
+
+/// Latest outcome of every applied extension, keyed by patch fingerprint.
+///
+/// Plain data like the preset registry, so it persists as-is alongside the
+/// workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExtensionAcceptanceLog {
+    #[serde(default)]
+    records: Vec<ExtensionOutcomeRecord>,
+}
+
+impl ExtensionAcceptanceLog {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn records(&self) -> &[ExtensionOutcomeRecord] {
+        &self.records
+    }
+
+    /// Record an outcome, replacing any earlier outcome for the fingerprint.
+    /// Returns true if the log changed.
+    pub fn record(&mut self, record: ExtensionOutcomeRecord) -> bool {
+        match self
+            .records
+            .iter_mut()
+            .find(|item| item.fingerprint == record.fingerprint)
+        {
+            Some(existing) if *existing == record => false,
+            Some(existing) => {
+                *existing = record;
+                true
+            }
+            None => {
+                self.records.push(record);
+                true
+            }
+        }
+    }
+
+    /// Record whether each extension applied in `workflow` is kept or
+    /// modified. Returns how many records changed.
+    pub fn observe(&mut self, workflow: &Workflow) -> usize {
+        workflow
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                extension_key_of(node)
+                    .zip(extension_fingerprint_of(node))
+                    .map(|(key, fingerprint)| {
+                        (
+                            (key, fingerprint),
+                            is_pristine_extension_node(workflow, node),
+                        )
+                    })
+            })
+            .into_group_map()
+            .into_iter()
+            .sorted_by(|(left, _), (right, _)| left.cmp(right))
+            .map(|((key, fingerprint), pristine)| ExtensionOutcomeRecord {
+                key: key.to_string(),
+                fingerprint: fingerprint.to_string(),
+                outcome: if pristine.into_iter().all(|value| value) {
+                    ExtensionOutcome::Kept
+                } else {
+                    ExtensionOutcome::Modified
+                },
+            })
+            .filter(|record| self.record(record.clone()))
+            .count()
+    }
+
+    /// Revert an extension like [`revert_extension`] and record every
+    /// fingerprint it removed nodes for as reverted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if the key is invalid.
+    pub fn revert(
+        &mut self,
+        workflow: &mut Workflow,
+        key: &str,
+    ) -> Result<RevertedExtension, String> {
+        let stamps = workflow
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                extension_key_of(node)
+                    .zip(extension_fingerprint_of(node))
+                    .map(|(key, fingerprint)| (node.id, (key.to_string(), fingerprint.to_string())))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let reverted = revert_extension(workflow, key)?;
+        reverted
+            .removed_nodes
+            .iter()
+            .filter_map(|node_id| stamps.get(node_id))
+            .unique()
+            .for_each(|(key, fingerprint)| {
+                self.record(ExtensionOutcomeRecord {
+                    key: key.clone(),
+                    fingerprint: fingerprint.clone(),
+                    outcome: ExtensionOutcome::Reverted,
+                });
+            });
+        Ok(reverted)
+    }
+
+    /// Per-rule acceptance statistics, ordered by rule key.
+    #[must_use]
+    pub fn stats(&self) -> Vec<AcceptanceStats> {
+        self.records
+            .iter()
+            .map(|record| record.key.as_str())
+            .unique()
+            .sorted()
+            .map(|key| self.stats_for(key))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn stats_for(&self, key: &str) -> AcceptanceStats {
+        self.records.iter().filter(|record| record.key == key).fold(
+            AcceptanceStats {
+                key: key.to_string(),
+                ..AcceptanceStats::default()
+            },
+            |mut stats, record| {
+                match record.outcome {
+                    ExtensionOutcome::Kept => stats.kept += 1,
+                    ExtensionOutcome::Modified => stats.modified += 1,
+                    ExtensionOutcome::Reverted => stats.reverted += 1,
+                }
+                stats
+            },
+        )
+    }
+
+    /// Add the learned calibration factor for `key` to a score breakdown.
+    #[must_use]
+    pub fn calibrate(&self, key: &str, breakdown: ScoreBreakdown) -> ScoreBreakdown {
+        match self.stats_for(key).calibration_factor() {
+            Some(factor) => {
+                let mut factors = breakdown.factors;
+                factors.push(factor);
+                ScoreBreakdown::new(breakdown.base, breakdown.cap, breakdown.applicable, factors)
+            }
+            None => breakdown,
+        }
+    }
+
+    /// Like [`super::explain_extension_score`], with calibration applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if the key is invalid.
+    pub fn explain_score(&self, workflow: &Workflow, key: &str) -> Result<ScoreBreakdown, String> {
+        let parsed_key = ExtensionKey::from_str(key)?;
+        Ok(self.calibrate(key, score_breakdown_for(parsed_key, workflow)))
+    }
+
+    /// Like [`super::suggest_extensions_with_analysis`], calibrated with
+    /// this log instead of the active one.
+    #[must_use]
+    pub fn suggest(&self, workflow: &Workflow) -> Vec<ExtensionSuggestionAnalysis> {
+        analyze_suggestions(workflow, self)
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{
+        set_active_log, AcceptanceStats, ExtensionAcceptanceLog, ExtensionOutcome,
+        ExtensionOutcomeRecord,
+    };
+    use crate::flow_extender::{
+        apply_extension, explain_extension_score, suggest_extensions_with_analysis,
+    };
+    use crate::graph::Workflow;
+
+    fn workflow_with_timeout_guard() -> Workflow {
+        let mut workflow = Workflow::new();
+        workflow.add_node("http-handler", 0.0, 0.0);
+        workflow.add_node("run", 100.0, 100.0);
+        apply_extension(&mut workflow, "add-timeout-guard").unwrap();
+        workflow
+    }
+
+    fn record(key: &str, fingerprint: &str, outcome: ExtensionOutcome) -> ExtensionOutcomeRecord {
+        ExtensionOutcomeRecord {
+            key: key.to_string(),
+            fingerprint: fingerprint.to_string(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn given_untouched_extension_when_observing_twice_then_it_is_kept_once() {
+        let workflow = workflow_with_timeout_guard();
+        let mut log = ExtensionAcceptanceLog::new();
+
+        assert_eq!(log.observe(&workflow), 1);
+        assert_eq!(log.observe(&workflow), 0);
+
+        let stats = log.stats_for("add-timeout-guard");
+        assert_eq!(stats.kept, 1);
+        assert_eq!(stats.total(), 1);
+    }
+
+    #[test]
+    fn given_renamed_extension_node_when_observing_then_outcome_is_modified() {
+        let mut workflow = workflow_with_timeout_guard();
+        let mut log = ExtensionAcceptanceLog::new();
+        log.observe(&workflow);
+        let node = workflow
+            .nodes
+            .iter_mut()
+            .find(|node| node.node_type == "timeout")
+            .unwrap();
+        node.name = "Renamed guard".to_string();
+
+        assert_eq!(log.observe(&workflow), 1);
+        assert_eq!(log.records()[0].outcome, ExtensionOutcome::Modified);
+    }
+
+    #[test]
+    fn given_reverted_extension_when_recording_then_stats_report_revert() {
+        let mut workflow = workflow_with_timeout_guard();
+        let mut log = ExtensionAcceptanceLog::new();
+        log.observe(&workflow);
+
+        let reverted = log.revert(&mut workflow, "add-timeout-guard").unwrap();
+
+        assert!(!reverted.removed_nodes.is_empty());
+        assert_eq!(
+            log.stats(),
+            vec![AcceptanceStats {
+                key: "add-timeout-guard".to_string(),
+                kept: 0,
+                modified: 0,
+                reverted: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn given_acceptance_history_when_explaining_score_then_calibration_shifts_it() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 100.0, 100.0);
+        let raw = explain_extension_score(&workflow, "add-durable-checkpoint").unwrap();
+        let mut accepted = ExtensionAcceptanceLog::new();
+        let mut rejected = ExtensionAcceptanceLog::new();
+        for index in 0..6 {
+            let fingerprint = format!("add-durable-checkpoint::{index}");
+            accepted.record(record(
+                "add-durable-checkpoint",
+                &fingerprint,
+                ExtensionOutcome::Kept,
+            ));
+            rejected.record(record(
+                "add-durable-checkpoint",
+                &fingerprint,
+                ExtensionOutcome::Reverted,
+            ));
+        }
+
+        let boosted = accepted
+            .explain_score(&workflow, "add-durable-checkpoint")
+            .unwrap();
+        let damped = rejected
+            .explain_score(&workflow, "add-durable-checkpoint")
+            .unwrap();
+
+        assert!(boosted.score > raw.score);
+        assert!(damped.score < raw.score);
+        assert!(boosted.score <= boosted.cap);
+        assert_eq!(
+            boosted.factors.last().map(|factor| factor.key.as_str()),
+            Some("acceptance-calibration")
+        );
+    }
+
+    #[test]
+    fn given_active_log_when_suggesting_by_default_then_scores_are_calibrated() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("http-handler", 0.0, 0.0);
+        workflow.add_node("http-call", 100.0, 100.0);
+        let raw = suggest_extensions_with_analysis(&workflow);
+        let key = raw.first().map(|analysis| analysis.key.clone()).unwrap();
+        let score_of = |analyses: &[crate::flow_extender::ExtensionSuggestionAnalysis]| {
+            analyses
+                .iter()
+                .find(|analysis| analysis.key == key)
+                .map(|analysis| analysis.score)
+                .unwrap()
+        };
+        let mut rejected = ExtensionAcceptanceLog::new();
+        for index in 0..6 {
+            rejected.record(record(
+                &key,
+                &format!("{key}::{index}"),
+                ExtensionOutcome::Reverted,
+            ));
+        }
+
+        set_active_log(rejected.clone());
+        let damped = score_of(&suggest_extensions_with_analysis(&workflow));
+        let explained = explain_extension_score(&workflow, &key).unwrap();
+        set_active_log(ExtensionAcceptanceLog::new());
+
+        assert!(damped < score_of(&raw));
+        assert_eq!(damped, score_of(&rejected.suggest(&workflow)));
+        assert_eq!(explained.score, damped);
+    }
+
+    #[test]
+    fn given_no_history_when_suggesting_then_scores_match_uncalibrated_analysis() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 100.0, 100.0);
+
+        let calibrated = ExtensionAcceptanceLog::new().suggest(&workflow);
+
+        assert_eq!(calibrated, suggest_extensions_with_analysis(&workflow));
+        assert_eq!(AcceptanceStats::default().acceptance_rate(), None);
+    }
+}
@@ -1,9 +1,15 @@
+pub mod archetype;
+pub mod calibration;
 pub mod preview_calc;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod twin;
 
+use crate::graph::events::{self, WorkflowEvent};
 use crate::graph::workflow_node::WorkflowNode;
-use crate::graph::{Node, NodeCategory, NodeId, PortName, Workflow};
+use crate::graph::{graph_ops, Connection, Node, NodeCategory, NodeId, PortName, Workflow};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -23,6 +29,11 @@ pub enum ExtensionKey {
     AddDurableCheckpoint,
     AddCompensationBranch,
     AddSignalResolution,
+    RemoveDuplicateTimeoutGuard,
+    RewireCompensationBranch,
+    ReorderEarlyStateWrite,
+    AddDeadLetterBranch,
+    AddOverlapGuard,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -109,6 +120,11 @@ impl ExtensionKey {
             Self::AddDurableCheckpoint => "add-durable-checkpoint",
             Self::AddCompensationBranch => "add-compensation-branch",
             Self::AddSignalResolution => "add-signal-resolution",
+            Self::RemoveDuplicateTimeoutGuard => "remove-duplicate-timeout-guard",
+            Self::RewireCompensationBranch => "rewire-compensation-branch",
+            Self::ReorderEarlyStateWrite => "reorder-early-state-write",
+            Self::AddDeadLetterBranch => "add-dead-letter-branch",
+            Self::AddOverlapGuard => "add-overlap-guard",
         }
     }
 }
@@ -124,6 +140,11 @@ impl FromStr for ExtensionKey {
             "add-durable-checkpoint" => Ok(Self::AddDurableCheckpoint),
             "add-compensation-branch" => Ok(Self::AddCompensationBranch),
             "add-signal-resolution" => Ok(Self::AddSignalResolution),
+            "remove-duplicate-timeout-guard" => Ok(Self::RemoveDuplicateTimeoutGuard),
+            "rewire-compensation-branch" => Ok(Self::RewireCompensationBranch),
+            "reorder-early-state-write" => Ok(Self::ReorderEarlyStateWrite),
+            "add-dead-letter-branch" => Ok(Self::AddDeadLetterBranch),
+            "add-overlap-guard" => Ok(Self::AddOverlapGuard),
             _ => Err(format!("Unknown extension key: {value}")),
         }
     }
@@ -149,6 +170,23 @@ pub struct FlowExtension {
 pub struct AppliedExtension {
     pub key: String,
     pub created_nodes: Vec<NodeId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_nodes: Vec<NodeId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RevertedExtension {
+    pub key: String,
+    pub removed_nodes: Vec<NodeId>,
+    pub retained_nodes: Vec<NodeId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExtensionSuppression {
+    pub key: String,
+    pub fingerprint: String,
+    /// Whether the suppressed fingerprint still matches the current plan.
+    pub active: bool,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -182,10 +220,68 @@ pub struct ExtensionSemantics {
     pub provides: Vec<RestateCapability>,
 }
 
+/// One input to a confidence score: how many times a signal was seen and
+/// how much each occurrence adds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScoreFactor {
+    pub key: String,
+    pub count: usize,
+    pub weight: f32,
+    pub contribution: f32,
+}
+
+/// How a confidence score was derived.
+///
+/// `score` is `base` plus every factor contribution, clamped to `cap`, or
+/// zero when the rule does not apply to the workflow at all.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ScoreBreakdown {
+    pub base: f32,
+    pub cap: f32,
+    pub applicable: bool,
+    pub factors: Vec<ScoreFactor>,
+    pub score: f32,
+}
+
+impl ScoreBreakdown {
+    fn new(base: f32, cap: f32, applicable: bool, factors: Vec<ScoreFactor>) -> Self {
+        let score = if applicable {
+            factors
+                .iter()
+                .map(|factor| factor.contribution)
+                .fold(base, |total, contribution| total + contribution)
+                .min(cap)
+        } else {
+            0.0
+        };
+        Self {
+            base,
+            cap,
+            applicable,
+            factors,
+            score,
+        }
+    }
+
+    /// Returns true when the cap cut off part of the factor contributions.
+    #[must_use]
+    pub fn is_capped(&self) -> bool {
+        self.applicable
+            && self
+                .factors
+                .iter()
+                .map(|factor| factor.contribution)
+                .fold(self.base, |total, contribution| total + contribution)
+                > self.cap
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ExtensionSuggestionAnalysis {
     pub key: String,
     pub score: f32,
+    #[serde(default)]
+    pub score_breakdown: ScoreBreakdown,
     pub rationale_class: RationaleClass,
     pub fingerprint: String,
     pub dependencies: Vec<String>,
@@ -256,11 +352,65 @@ pub struct CompoundExtensionPlan {
     pub steps: Vec<CompoundPlanStep>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AppliedCompoundPlan {
+    pub applied: Vec<AppliedExtension>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompoundApplyFailureKind {
+    /// The plan carried conflicts and was never started.
+    Conflict,
+    /// The step no longer matches the workflow it is applied to.
+    Drift,
+    /// Applying the step returned an error.
+    ApplyFailed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompoundApplyFailure {
+    pub key: String,
+    pub kind: CompoundApplyFailureKind,
+    pub reason: String,
+}
+
+/// Why [`apply_compound_plan`] rolled the workflow back.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CompoundApplyError {
+    pub failures: Vec<CompoundApplyFailure>,
+    /// Keys that had been applied before the failure and were rolled back.
+    pub rolled_back: Vec<String>,
+}
+
+impl std::fmt::Display for CompoundApplyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let details = self
+            .failures
+            .iter()
+            .map(|failure| format!("{}: {}", failure.key, failure.reason))
+            .join(" | ");
+        write!(
+            f,
+            "Compound plan failed with {} error(s): {details}",
+            self.failures.len()
+        )
+    }
+}
+
+impl std::error::Error for CompoundApplyError {}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ExtensionPatchPreview {
     pub key: String,
     pub nodes: Vec<PreviewNode>,
     pub connections: Vec<PreviewConnection>,
+    /// Existing nodes the patch deletes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_nodes: Vec<NodeId>,
+    /// Existing connections the patch deletes or replaces.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_connections: Vec<PreviewConnection>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -305,6 +455,11 @@ struct RulePlan {
 struct PatchPlan {
     nodes: Vec<PatchNode>,
     connections: Vec<PatchConnection>,
+    removed_nodes: Vec<NodeId>,
+    removed_connections: Vec<Connection>,
+    /// Existing nodes the patch is about but does not wire to, so that a
+    /// dismissal only covers suggestions about the same nodes.
+    anchors: Vec<NodeId>,
 }
 
 #[derive(Clone)]
@@ -318,8 +473,8 @@ struct PatchNode {
 struct PatchConnection {
     source: PatchEndpoint,
     target: PatchEndpoint,
-    source_port: &'static str,
-    target_port: &'static str,
+    source_port: String,
+    target_port: String,
 }
 
 #[derive(Clone, Copy)]
@@ -337,20 +492,31 @@ pub fn suggest_extensions(workflow: &Workflow) -> Vec<FlowExtension> {
                 if !key_is_compatible_with_workflow(workflow, rule.key) {
                     return None;
                 }
-                (rule.plan)(workflow).map(|rule_plan| FlowExtension {
-                    key: rule.key.as_str().to_string(),
-                    title: rule.title.to_string(),
-                    rationale: rule_plan.rationale,
-                    priority: rule.priority,
-                    contract: rule.contract,
-                })
+                (rule.plan)(workflow)
+                    .filter(|rule_plan| !is_suppressed(workflow, rule.key, &rule_plan.patch))
+                    .map(|rule_plan| FlowExtension {
+                        key: rule.key.as_str().to_string(),
+                        title: rule.title.to_string(),
+                        rationale: rule_plan.rationale,
+                        priority: rule.priority,
+                        contract: rule.contract,
+                    })
             })
             .collect(),
     )
 }
 
+/// Suggestions with confidence scores, calibrated by the acceptance log
+/// installed with [`calibration::set_active_log`].
 #[must_use]
 pub fn suggest_extensions_with_analysis(workflow: &Workflow) -> Vec<ExtensionSuggestionAnalysis> {
+    calibration::with_active_log(|log| analyze_suggestions(workflow, log))
+}
+
+fn analyze_suggestions(
+    workflow: &Workflow,
+    log: &calibration::ExtensionAcceptanceLog,
+) -> Vec<ExtensionSuggestionAnalysis> {
     hide_isolated_reliability_analysis(
         rules()
             .into_iter()
@@ -358,22 +524,46 @@ pub fn suggest_extensions_with_analysis(workflow: &Workflow) -> Vec<ExtensionSug
                 if !key_is_compatible_with_workflow(workflow, rule.key) {
                     return None;
                 }
-                (rule.plan)(workflow).map(|plan| ExtensionSuggestionAnalysis {
-                    key: rule.key.as_str().to_string(),
-                    score: confidence_score_for(rule.key, workflow),
-                    rationale_class: rationale_class_for(rule.key),
-                    fingerprint: extension_fingerprint(rule.key, &plan.patch),
-                    dependencies: extension_dependencies(rule.key)
-                        .iter()
-                        .map(|dependency| dependency.as_str().to_string())
-                        .collect(),
-                    semantics: extension_semantics(rule.key),
-                })
+                (rule.plan)(workflow)
+                    .filter(|plan| !is_suppressed(workflow, rule.key, &plan.patch))
+                    .map(|plan| {
+                        let score_breakdown = log
+                            .calibrate(rule.key.as_str(), score_breakdown_for(rule.key, workflow));
+                        ExtensionSuggestionAnalysis {
+                            key: rule.key.as_str().to_string(),
+                            score: score_breakdown.score,
+                            score_breakdown,
+                            rationale_class: rationale_class_for(rule.key),
+                            fingerprint: extension_fingerprint(rule.key, &plan.patch),
+                            dependencies: extension_dependencies(rule.key)
+                                .iter()
+                                .map(|dependency| dependency.as_str().to_string())
+                                .collect(),
+                            semantics: extension_semantics(rule.key),
+                        }
+                    })
             })
             .collect(),
     )
 }
 
+/// Explain how an extension's confidence score is derived for `workflow`.
+///
+/// Works for any workflow, including an edited copy, so callers can ask what
+/// the score would become after a hypothetical change. Includes the active
+/// acceptance calibration, like [`suggest_extensions_with_analysis`].
+///
+/// # Errors
+///
+/// Returns `String` if the key is invalid.
+pub fn explain_extension_score(workflow: &Workflow, key: &str) -> Result<ScoreBreakdown, String> {
+    let parsed_key = ExtensionKey::from_str(key)?;
+    let breakdown = score_breakdown_for(parsed_key, workflow);
+    Ok(calibration::with_active_log(|log| {
+        log.calibrate(key, breakdown)
+    }))
+}
+
 /// Preview an extension without applying it.
 ///
 /// # Errors
@@ -396,27 +586,170 @@ pub fn preview_extension(
 /// Returns `String` if the key is invalid or application fails.
 pub fn apply_extension(workflow: &mut Workflow, key: &str) -> Result<AppliedExtension, String> {
     let parsed_key = ExtensionKey::from_str(key)?;
-    if parsed_key == ExtensionKey::AddReliabilityBundle {
-        return apply_reliability_bundle(workflow, key);
-    }
+    let applied = if parsed_key == ExtensionKey::AddReliabilityBundle {
+        apply_reliability_bundle(workflow, key)
+    } else {
+        apply_planned_extension(workflow, parsed_key, key)
+    };
+
+    events::emit(&WorkflowEvent::ExtensionApplied {
+        key: applied.key.clone(),
+        created_nodes: applied.created_nodes.clone(),
+    });
+    Ok(applied)
+}
 
-    let created_nodes = plan_for_key(workflow, parsed_key)
+fn apply_planned_extension(
+    workflow: &mut Workflow,
+    parsed_key: ExtensionKey,
+    key: &str,
+) -> AppliedExtension {
+    let (created_nodes, removed_nodes) = plan_for_key(workflow, parsed_key)
         .map(|plan| {
             let fingerprint = extension_fingerprint(parsed_key, &plan.patch);
             if has_extension_fingerprint(workflow, &fingerprint) {
-                Vec::new()
+                (Vec::new(), Vec::new())
             } else {
                 execute_patch(workflow, parsed_key, &fingerprint, &plan.patch)
             }
         })
         .unwrap_or_default();
 
-    Ok(AppliedExtension {
+    AppliedExtension {
         key: key.to_string(),
         created_nodes,
+        removed_nodes,
+    }
+}
+
+/// Revert a previously applied extension.
+///
+/// Removes every node stamped with the extension's fingerprint metadata,
+/// together with its connections. Nodes that were renamed, reconfigured, or
+/// rewired after the extension was applied are retained untouched.
+///
+/// # Errors
+///
+/// Returns `String` if the key is invalid.
+pub fn revert_extension(workflow: &mut Workflow, key: &str) -> Result<RevertedExtension, String> {
+    let parsed_key = ExtensionKey::from_str(key)?;
+    let reverted_keys = if parsed_key == ExtensionKey::AddReliabilityBundle {
+        reliability_bundle_members().to_vec()
+    } else {
+        vec![parsed_key]
+    };
+
+    let (removed_nodes, retained_nodes): (Vec<NodeId>, Vec<NodeId>) = workflow
+        .nodes
+        .iter()
+        .filter(|node| {
+            extension_key_of(node)
+                .is_some_and(|stamped| reverted_keys.iter().any(|key| key.as_str() == stamped))
+        })
+        .map(|node| (node.id, is_pristine_extension_node(workflow, node)))
+        .fold(
+            (Vec::new(), Vec::new()),
+            |(mut removed, mut retained), (node_id, pristine)| {
+                if pristine {
+                    removed.push(node_id);
+                } else {
+                    retained.push(node_id);
+                }
+                (removed, retained)
+            },
+        );
+
+    removed_nodes
+        .iter()
+        .for_each(|node_id| workflow.remove_node(*node_id));
+
+    Ok(RevertedExtension {
+        key: key.to_string(),
+        removed_nodes,
+        retained_nodes,
     })
 }
 
+/// Dismiss a suggestion so it is no longer returned for this workflow.
+///
+/// The suppression is keyed by the suggestion's patch fingerprint, so the
+/// suggestion reappears once the workflow changes enough to alter its plan.
+/// Returns the suppressed fingerprint, or `None` if the rule does not apply.
+///
+/// # Errors
+///
+/// Returns `String` if the key is invalid.
+pub fn dismiss_extension(workflow: &mut Workflow, key: &str) -> Result<Option<String>, String> {
+    let parsed_key = ExtensionKey::from_str(key)?;
+    let fingerprint = plan_for_key(workflow, parsed_key)
+        .map(|plan| extension_fingerprint(parsed_key, &plan.patch));
+
+    if let Some(value) = fingerprint.as_ref() {
+        if !workflow.suppressed_extensions.contains(value) {
+            workflow.suppressed_extensions.push(value.clone());
+        }
+    }
+
+    Ok(fingerprint)
+}
+
+/// List dismissed suggestions recorded on the workflow.
+#[must_use]
+pub fn list_suppressions(workflow: &Workflow) -> Vec<ExtensionSuppression> {
+    workflow
+        .suppressed_extensions
+        .iter()
+        .map(|fingerprint| {
+            let key = fingerprint
+                .split("::")
+                .next()
+                .map(str::to_string)
+                .unwrap_or_default();
+            let active = ExtensionKey::from_str(&key)
+                .ok()
+                .and_then(|parsed_key| {
+                    plan_for_key(workflow, parsed_key)
+                        .map(|plan| extension_fingerprint(parsed_key, &plan.patch))
+                })
+                .is_some_and(|current| &current == fingerprint);
+            ExtensionSuppression {
+                key,
+                fingerprint: fingerprint.clone(),
+                active,
+            }
+        })
+        .collect()
+}
+
+/// Remove every suppression recorded for `key`, returning how many were removed.
+pub fn clear_suppression(workflow: &mut Workflow, key: &str) -> usize {
+    let prefix = format!("{key}::");
+    let before = workflow.suppressed_extensions.len();
+    workflow
+        .suppressed_extensions
+        .retain(|fingerprint| !fingerprint.starts_with(&prefix));
+    before - workflow.suppressed_extensions.len()
+}
+
+/// Remove every suppression, returning how many were removed.
+pub fn clear_suppressions(workflow: &mut Workflow) -> usize {
+    let removed = workflow.suppressed_extensions.len();
+    workflow.suppressed_extensions.clear();
+    removed
+}
+
+/// List extension keys that currently have stamped nodes in the workflow.
+#[must_use]
+pub fn applied_extension_keys(workflow: &Workflow) -> Vec<String> {
+    workflow
+        .nodes
+        .iter()
+        .filter_map(extension_key_of)
+        .unique()
+        .map(str::to_string)
+        .collect()
+}
+
 /// Detect conflicts between extensions.
 ///
 /// # Errors
@@ -579,15 +912,106 @@ pub fn generate_compound_plan(
     })
 }
 
-#[must_use]
-pub fn extension_presets() -> Vec<ExtensionPreset> {
-    [
-        ExtensionPresetKey::Webhook,
-        ExtensionPresetKey::Approval,
-        ExtensionPresetKey::RetrySaga,
-    ]
-    .iter()
-    .map(|preset_key| ExtensionPreset {
+/// Apply every step of a compound plan as a single transaction.
+///
+/// Each step is re-previewed against the workflow as it stands before it is
+/// applied; a step whose patch no longer matches the plan counts as drift.
+/// On the first failure the workflow is restored to its pre-apply state.
+///
+/// # Errors
+///
+/// Returns [`CompoundApplyError`] if the plan has conflicts or any step
+/// drifts or fails to apply.
+pub fn apply_compound_plan(
+    workflow: &mut Workflow,
+    plan: &CompoundExtensionPlan,
+) -> Result<AppliedCompoundPlan, CompoundApplyError> {
+    if !plan.conflicts.is_empty() {
+        return Err(CompoundApplyError {
+            failures: plan
+                .conflicts
+                .iter()
+                .map(|conflict| CompoundApplyFailure {
+                    key: conflict.right_key.clone(),
+                    kind: CompoundApplyFailureKind::Conflict,
+                    reason: conflict.reason.clone(),
+                })
+                .collect(),
+            rolled_back: Vec::new(),
+        });
+    }
+
+    let snapshot = workflow.clone();
+    let mut applied = Vec::with_capacity(plan.steps.len());
+
+    for step in &plan.steps {
+        match apply_compound_step(workflow, step) {
+            Ok(result) => applied.push(result),
+            Err(failure) => {
+                *workflow = snapshot;
+                return Err(CompoundApplyError {
+                    failures: vec![failure],
+                    rolled_back: applied.into_iter().map(|result| result.key).collect(),
+                });
+            }
+        }
+    }
+
+    Ok(AppliedCompoundPlan { applied })
+}
+
+fn apply_compound_step(
+    workflow: &mut Workflow,
+    step: &CompoundPlanStep,
+) -> Result<AppliedExtension, CompoundApplyFailure> {
+    let failure = |kind, reason: String| CompoundApplyFailure {
+        key: step.key.clone(),
+        kind,
+        reason,
+    };
+
+    let current = preview_extension(workflow, &step.key)
+        .map_err(|err| failure(CompoundApplyFailureKind::ApplyFailed, err))?;
+    match current {
+        Some(preview) if preview_shape_matches(&preview, &step.preview) => {}
+        Some(_) => {
+            return Err(failure(
+                CompoundApplyFailureKind::Drift,
+                "Patch differs from the planned preview.".to_string(),
+            ))
+        }
+        None => {
+            return Err(failure(
+                CompoundApplyFailureKind::Drift,
+                "Extension no longer applies to the workflow.".to_string(),
+            ))
+        }
+    }
+
+    let result = apply_extension(workflow, &step.key)
+        .map_err(|err| failure(CompoundApplyFailureKind::ApplyFailed, err))?;
+    if result.created_nodes.is_empty() && !step.preview.nodes.is_empty() {
+        return Err(failure(
+            CompoundApplyFailureKind::Drift,
+            "Extension is already applied.".to_string(),
+        ));
+    }
+    Ok(result)
+}
+
+/// Compares previews by what they add and remove.
+///
+/// Endpoints are left out: ones created by earlier steps get fresh ids on
+/// every apply, so they never match the simulated plan.
+fn preview_shape_matches(actual: &ExtensionPatchPreview, planned: &ExtensionPatchPreview) -> bool {
+    actual.nodes == planned.nodes
+        && actual.connections.len() == planned.connections.len()
+        && actual.removed_nodes.len() == planned.removed_nodes.len()
+        && actual.removed_connections.len() == planned.removed_connections.len()
+}
+
+fn builtin_preset(preset_key: ExtensionPresetKey) -> ExtensionPreset {
+    ExtensionPreset {
         key: preset_key.as_str().to_string(),
         title: preset_key.title().to_string(),
         description: preset_key.description().to_string(),
@@ -596,7 +1020,18 @@ pub fn extension_presets() -> Vec<ExtensionPreset> {
             .iter()
             .map(|key| key.as_str().to_string())
             .collect(),
-    })
+    }
+}
+
+#[must_use]
+pub fn extension_presets() -> Vec<ExtensionPreset> {
+    [
+        ExtensionPresetKey::Webhook,
+        ExtensionPresetKey::Approval,
+        ExtensionPresetKey::RetrySaga,
+    ]
+    .into_iter()
+    .map(builtin_preset)
     .collect()
 }
 
@@ -609,8 +1044,15 @@ pub fn resolve_extension_preset(
     workflow: &Workflow,
     preset_key: &str,
 ) -> Result<ResolvedExtensionPreset, String> {
-    let parsed_preset = ExtensionPresetKey::from_str(preset_key)?;
-    let expanded_keys = expand_keys_with_dependencies(parsed_preset.extension_keys());
+    ExtensionPresetRegistry::default().resolve(workflow, preset_key)
+}
+
+fn resolve_preset(
+    workflow: &Workflow,
+    preset: ExtensionPreset,
+) -> Result<ResolvedExtensionPreset, String> {
+    let parsed_keys = parse_unique_keys(&preset.extension_keys)?;
+    let expanded_keys = expand_keys_with_dependencies(&parsed_keys);
     let ordered_keys = order_keys_with_dependencies(&expanded_keys)?
         .into_iter()
         .map(|key| key.as_str().to_string())
@@ -618,21 +1060,125 @@ pub fn resolve_extension_preset(
     let conflicts = detect_extension_conflicts(workflow, &ordered_keys)?;
 
     Ok(ResolvedExtensionPreset {
-        preset: ExtensionPreset {
-            key: parsed_preset.as_str().to_string(),
-            title: parsed_preset.title().to_string(),
-            description: parsed_preset.description().to_string(),
-            extension_keys: parsed_preset
-                .extension_keys()
-                .iter()
-                .map(|key| key.as_str().to_string())
-                .collect(),
-        },
+        preset,
         ordered_keys,
         conflicts,
     })
 }
 
+/// Built-in presets plus user-defined ones.
+///
+/// Custom presets are plain data, so the registry serializes as-is into
+/// workspace storage. Preset keys are unique across both sets.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ExtensionPresetRegistry {
+    #[serde(default)]
+    custom: Vec<ExtensionPreset>,
+}
+
+impl ExtensionPresetRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn custom_presets(&self) -> &[ExtensionPreset] {
+        &self.custom
+    }
+
+    /// Built-in presets first, then custom presets in definition order.
+    #[must_use]
+    pub fn presets(&self) -> Vec<ExtensionPreset> {
+        extension_presets()
+            .into_iter()
+            .chain(self.custom.iter().cloned())
+            .collect()
+    }
+
+    #[must_use]
+    pub fn is_custom(&self, key: &str) -> bool {
+        self.custom.iter().any(|preset| preset.key == key)
+    }
+
+    /// Look up a built-in or custom preset by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if no preset has this key.
+    pub fn get(&self, key: &str) -> Result<ExtensionPreset, String> {
+        if let Ok(preset_key) = ExtensionPresetKey::from_str(key) {
+            return Ok(builtin_preset(preset_key));
+        }
+        self.custom
+            .iter()
+            .find(|preset| preset.key == key)
+            .cloned()
+            .ok_or_else(|| format!("Unknown extension preset key: {key}"))
+    }
+
+    /// Add a custom preset, replacing any custom preset with the same key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if the key is not kebab-case or collides with a
+    /// built-in preset, the title is blank, or the extension keys are empty,
+    /// unknown, or cannot be ordered by their dependencies.
+    pub fn define(&mut self, preset: ExtensionPreset) -> Result<(), String> {
+        let is_kebab = !preset.key.is_empty()
+            && !preset.key.starts_with('-')
+            && !preset.key.ends_with('-')
+            && preset
+                .key
+                .chars()
+                .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-');
+        if !is_kebab {
+            return Err(format!("Preset key must be kebab-case: '{}'", preset.key));
+        }
+        if ExtensionPresetKey::from_str(&preset.key).is_ok() {
+            return Err(format!(
+                "Preset key '{}' is reserved by a built-in preset",
+                preset.key
+            ));
+        }
+        if preset.title.trim().is_empty() {
+            return Err("Preset title must not be empty".to_string());
+        }
+        if preset.extension_keys.is_empty() {
+            return Err("Preset must list at least one extension key".to_string());
+        }
+        let parsed_keys = parse_unique_keys(&preset.extension_keys)?;
+        order_keys_with_dependencies(&expand_keys_with_dependencies(&parsed_keys))?;
+
+        match self.custom.iter_mut().find(|item| item.key == preset.key) {
+            Some(existing) => *existing = preset,
+            None => self.custom.push(preset),
+        }
+        Ok(())
+    }
+
+    /// Remove a custom preset. Built-in presets cannot be removed.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let before = self.custom.len();
+        self.custom.retain(|preset| preset.key != key);
+        self.custom.len() != before
+    }
+
+    /// Resolve a built-in or custom preset, expanding dependencies and
+    /// detecting conflicts exactly like [`resolve_extension_preset`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if the preset key is unknown or its keys are invalid.
+    pub fn resolve(
+        &self,
+        workflow: &Workflow,
+        preset_key: &str,
+    ) -> Result<ResolvedExtensionPreset, String> {
+        resolve_preset(workflow, self.get(preset_key)?)
+    }
+}
+
 fn rules() -> Vec<RuleDefinition> {
     vec![
         RuleDefinition {
@@ -729,34 +1275,135 @@ fn rules() -> Vec<RuleDefinition> {
             },
             plan: plan_missing_signal_resolution,
         },
-    ]
-}
-
-fn plan_missing_entry(workflow: &Workflow) -> Option<RulePlan> {
-    (!workflow
-        .nodes
-        .iter()
-        .any(|node| node.category == NodeCategory::Entry))
-    .then(|| RulePlan {
-        rationale:
-            "Workflow has no entry node. Add an HTTP trigger so execution has a clear start."
-                .to_string(),
-        patch: PatchPlan {
-            nodes: vec![PatchNode {
-                node_type: "http-handler",
-                x: 120.0,
-                y: 100.0,
-            }],
-            connections: Vec::new(),
+        RuleDefinition {
+            key: ExtensionKey::RemoveDuplicateTimeoutGuard,
+            title: "Remove duplicate timeout guard",
+            priority: ExtensionPriority::Medium,
+            contract: RuleContract {
+                preconditions: vec![
+                    "A timeout guard is chained after, or runs alongside, another guard on the same step."
+                        .to_string(),
+                ],
+                postconditions: vec![
+                    "The redundant guard is removed and its successor is rewired to the kept guard."
+                        .to_string(),
+                ],
+                invariants: vec!["The guarded step keeps exactly one timeout guard.".to_string()],
+            },
+            plan: plan_duplicate_timeout_guard,
         },
-    })
-}
-
-fn plan_missing_timeout_guard(workflow: &Workflow) -> Option<RulePlan> {
-    let has_durable = workflow
-        .nodes
-        .iter()
-        .any(|node| node.category == NodeCategory::Durable);
+        RuleDefinition {
+            key: ExtensionKey::RewireCompensationBranch,
+            title: "Route compensation through a condition",
+            priority: ExtensionPriority::Medium,
+            contract: RuleContract {
+                preconditions: vec![
+                    "A compensate node is wired directly off a non-condition node.".to_string(),
+                ],
+                postconditions: vec![
+                    "The compensate node hangs off the false branch of a new condition node."
+                        .to_string(),
+                ],
+                invariants: vec!["The compensate node itself is kept.".to_string()],
+            },
+            plan: plan_misplaced_compensation,
+        },
+        RuleDefinition {
+            key: ExtensionKey::ReorderEarlyStateWrite,
+            title: "Move state write after durable step",
+            priority: ExtensionPriority::High,
+            contract: RuleContract {
+                preconditions: vec![
+                    "A set-state node runs before any durable step on its path.".to_string(),
+                ],
+                postconditions: vec![
+                    "The set-state node is bypassed and reattached after the first downstream durable step."
+                        .to_string(),
+                ],
+                invariants: vec!["No nodes are removed.".to_string()],
+            },
+            plan: plan_early_state_write,
+        },
+        RuleDefinition {
+            key: ExtensionKey::AddDeadLetterBranch,
+            title: "Add dead-letter branch",
+            priority: ExtensionPriority::Medium,
+            contract: RuleContract {
+                preconditions: vec![
+                    "A kafka-handler has no downstream failure path (compensation or condition false branch)."
+                        .to_string(),
+                ],
+                postconditions: vec![
+                    "A condition's false branch forwards failed records to a dead-letter send-message node."
+                        .to_string(),
+                ],
+                invariants: vec!["Existing handler wiring remains intact.".to_string()],
+            },
+            plan: plan_missing_dead_letter,
+        },
+        RuleDefinition {
+            key: ExtensionKey::AddOverlapGuard,
+            title: "Add overlap guard",
+            priority: ExtensionPriority::High,
+            contract: RuleContract {
+                preconditions: vec![
+                    "A cron-trigger schedules durable work with no get-state dedupe check."
+                        .to_string(),
+                ],
+                postconditions: vec![
+                    "A get-state check runs before the first durable step.".to_string(),
+                ],
+                invariants: vec!["No nodes are removed.".to_string()],
+            },
+            plan: plan_missing_overlap_guard,
+        },
+    ]
+}
+
+fn plan_missing_entry(workflow: &Workflow) -> Option<RulePlan> {
+    (!workflow
+        .nodes
+        .iter()
+        .any(|node| node.category == NodeCategory::Entry))
+    .then(|| RulePlan {
+        rationale:
+            "Workflow has no entry node. Add an HTTP trigger so execution has a clear start."
+                .to_string(),
+        patch: PatchPlan {
+            nodes: vec![PatchNode {
+                node_type: "http-handler",
+                x: 120.0,
+                y: 100.0,
+            }],
+            connections: Vec::new(),
+            removed_nodes: Vec::new(),
+            removed_connections: Vec::new(),
+            anchors: root_nodes(workflow),
+        },
+    })
+}
+
+/// Nodes nothing flows into, where a new entry would lead, in id order.
+fn root_nodes(workflow: &Workflow) -> Vec<NodeId> {
+    workflow
+        .nodes
+        .iter()
+        .map(|node| node.id)
+        .filter(|id| {
+            !workflow
+                .connections
+                .iter()
+                .any(|connection| connection.target == *id)
+        })
+        .sorted_by_key(ToString::to_string)
+        .collect()
+}
+
+fn plan_missing_timeout_guard(workflow: &Workflow) -> Option<RulePlan> {
+    let has_durable = workflow
+        .nodes
+        .iter()
+        .any(|node| node.category == NodeCategory::Durable);
     let has_timeout = workflow
         .nodes
         .iter()
@@ -776,9 +1423,12 @@ fn plan_missing_timeout_guard(workflow: &Workflow) -> Option<RulePlan> {
             connections: vec![PatchConnection {
                 source: PatchEndpoint::Existing(anchor.id),
                 target: PatchEndpoint::Proposed(0),
-                source_port: "out",
-                target_port: "in",
+                source_port: "out".to_string(),
+                target_port: "in".to_string(),
             }],
+            removed_nodes: Vec::new(),
+            removed_connections: Vec::new(),
+            anchors: Vec::new(),
         },
     })
 }
@@ -806,6 +1456,9 @@ fn plan_reliability_bundle(workflow: &Workflow) -> Option<RulePlan> {
     let mut patch = PatchPlan {
         nodes: Vec::new(),
         connections: Vec::new(),
+        removed_nodes: Vec::new(),
+        removed_connections: Vec::new(),
+        anchors: Vec::new(),
     };
     let mut labels = Vec::new();
 
@@ -847,9 +1500,12 @@ fn plan_missing_checkpoint(workflow: &Workflow) -> Option<RulePlan> {
             connections: vec![PatchConnection {
                 source: PatchEndpoint::Existing(anchor.id),
                 target: PatchEndpoint::Proposed(0),
-                source_port: "out",
-                target_port: "in",
+                source_port: "out".to_string(),
+                target_port: "in".to_string(),
             }],
+            removed_nodes: Vec::new(),
+            removed_connections: Vec::new(),
+            anchors: Vec::new(),
         },
     })
 }
@@ -876,9 +1532,12 @@ fn plan_unbalanced_condition(workflow: &Workflow) -> Option<RulePlan> {
             connections: vec![PatchConnection {
                 source: PatchEndpoint::Existing(condition_node.id),
                 target: PatchEndpoint::Proposed(0),
-                source_port: "false",
-                target_port: "in",
+                source_port: "false".to_string(),
+                target_port: "in".to_string(),
             }],
+            removed_nodes: Vec::new(),
+            removed_connections: Vec::new(),
+            anchors: Vec::new(),
         },
     })
 }
@@ -903,9 +1562,304 @@ fn plan_missing_signal_resolution(workflow: &Workflow) -> Option<RulePlan> {
             connections: vec![PatchConnection {
                 source: PatchEndpoint::Existing(wait_node.id),
                 target: PatchEndpoint::Proposed(0),
-                source_port: "out",
-                target_port: "in",
+                source_port: "out".to_string(),
+                target_port: "in".to_string(),
+            }],
+            removed_nodes: Vec::new(),
+            removed_connections: Vec::new(),
+            anchors: Vec::new(),
+        },
+    })
+}
+
+fn plan_duplicate_timeout_guard(workflow: &Workflow) -> Option<RulePlan> {
+    let (kept, redundant) = find_redundant_timeout_guard(workflow)?;
+    let removed_connections = workflow
+        .connections
+        .iter()
+        .filter(|connection| connection.source == redundant.id || connection.target == redundant.id)
+        .cloned()
+        .collect::<Vec<_>>();
+    let kept_keeps_successor = workflow
+        .connections
+        .iter()
+        .any(|connection| connection.source == kept.id && connection.target != redundant.id);
+    let rewire = workflow
+        .connections
+        .iter()
+        .find(|connection| connection.source == redundant.id && connection.target != kept.id)
+        .filter(|_| !kept_keeps_successor)
+        .map(|connection| PatchConnection {
+            source: PatchEndpoint::Existing(kept.id),
+            target: PatchEndpoint::Existing(connection.target),
+            source_port: connection.source_port.0.clone(),
+            target_port: connection.target_port.0.clone(),
+        });
+
+    Some(RulePlan {
+        rationale: format!(
+            "Timeout guard '{}' duplicates '{}'. Remove it and keep a single guard on the step.",
+            redundant.name, kept.name
+        ),
+        patch: PatchPlan {
+            nodes: Vec::new(),
+            connections: rewire.into_iter().collect(),
+            removed_nodes: vec![redundant.id],
+            removed_connections,
+            anchors: Vec::new(),
+        },
+    })
+}
+
+fn plan_misplaced_compensation(workflow: &Workflow) -> Option<RulePlan> {
+    let (source, compensate, connection) = workflow
+        .nodes
+        .iter()
+        .filter(|node| matches!(node.node, WorkflowNode::Compensate(_)))
+        .sorted_by(|left, right| {
+            left.y
+                .total_cmp(&right.y)
+                .then_with(|| left.x.total_cmp(&right.x))
+        })
+        .find_map(|compensate| {
+            workflow
+                .connections
+                .iter()
+                .filter(|connection| connection.target == compensate.id)
+                .find_map(|connection| {
+                    workflow
+                        .nodes
+                        .iter()
+                        .find(|node| node.id == connection.source)
+                        .filter(|node| !matches!(node.node, WorkflowNode::Condition(_)))
+                        .map(|source| (source.clone(), compensate.clone(), connection.clone()))
+                })
+        })?;
+
+    Some(RulePlan {
+        rationale: format!(
+            "Compensation '{}' is wired directly off '{}', so it runs on success too. Route it through a condition's false branch.",
+            compensate.name, source.name
+        ),
+        patch: PatchPlan {
+            nodes: vec![PatchNode {
+                node_type: "condition",
+                x: f32::midpoint(source.x, compensate.x),
+                y: source.y + 120.0,
+            }],
+            connections: vec![
+                PatchConnection {
+                    source: PatchEndpoint::Existing(source.id),
+                    target: PatchEndpoint::Proposed(0),
+                    source_port: connection.source_port.0.clone(),
+                    target_port: "in".to_string(),
+                },
+                PatchConnection {
+                    source: PatchEndpoint::Proposed(0),
+                    target: PatchEndpoint::Existing(compensate.id),
+                    source_port: "false".to_string(),
+                    target_port: connection.target_port.0.clone(),
+                },
+            ],
+            removed_nodes: Vec::new(),
+            removed_connections: vec![connection],
+            anchors: Vec::new(),
+        },
+    })
+}
+
+fn plan_early_state_write(workflow: &Workflow) -> Option<RulePlan> {
+    let node_ids = graph_ops::collect_node_ids(&workflow.nodes);
+    let outgoing = graph_ops::build_outgoing_adjacency(&workflow.connections, &node_ids);
+    let incoming = graph_ops::build_reverse_adjacency(&workflow.connections, &node_ids);
+    let is_durable = |node_id: &NodeId| {
+        workflow
+            .nodes
+            .iter()
+            .any(|node| node.id == *node_id && node.category == NodeCategory::Durable)
+    };
+
+    let (state_write, durable) = workflow
+        .nodes
+        .iter()
+        .filter(|node| matches!(node.node, WorkflowNode::SetState(_)))
+        .filter(|node| {
+            !graph_ops::find_reachable(&[node.id], &incoming)
+                .iter()
+                .any(is_durable)
+        })
+        .sorted_by(|left, right| {
+            left.y
+                .total_cmp(&right.y)
+                .then_with(|| left.x.total_cmp(&right.x))
+        })
+        .find_map(|node| {
+            nearest_downstream(node.id, &outgoing, is_durable)
+                .and_then(|durable_id| workflow.nodes.iter().find(|n| n.id == durable_id))
+                .map(|durable| (node.clone(), durable.clone()))
+        })?;
+
+    let removed_connections = workflow
+        .connections
+        .iter()
+        .filter(|connection| {
+            connection.source == state_write.id || connection.target == state_write.id
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let bypass = removed_connections
+        .iter()
+        .filter(|inbound| inbound.target == state_write.id)
+        .flat_map(|inbound| {
+            removed_connections
+                .iter()
+                .filter(|outbound| outbound.source == state_write.id)
+                .map(move |outbound| PatchConnection {
+                    source: PatchEndpoint::Existing(inbound.source),
+                    target: PatchEndpoint::Existing(outbound.target),
+                    source_port: inbound.source_port.0.clone(),
+                    target_port: outbound.target_port.0.clone(),
+                })
+        });
+    let reattach = PatchConnection {
+        source: PatchEndpoint::Existing(durable.id),
+        target: PatchEndpoint::Existing(state_write.id),
+        source_port: "out".to_string(),
+        target_port: "in".to_string(),
+    };
+
+    Some(RulePlan {
+        rationale: format!(
+            "State write '{}' runs before any durable step, so a retry replays it without a journaled result. Move it after '{}'.",
+            state_write.name, durable.name
+        ),
+        patch: PatchPlan {
+            nodes: Vec::new(),
+            connections: bypass.chain(std::iter::once(reattach)).collect(),
+            removed_nodes: Vec::new(),
+            removed_connections,
+            anchors: Vec::new(),
+        },
+    })
+}
+
+/// Breadth-first walk over successors of `start`, returning the closest node
+/// (fewest hops) that matches `predicate`.
+fn nearest_downstream(
+    start: NodeId,
+    outgoing: &HashMap<NodeId, Vec<NodeId>>,
+    predicate: impl Fn(&NodeId) -> bool,
+) -> Option<NodeId> {
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        for &next in outgoing.get(&current).into_iter().flatten() {
+            if !visited.insert(next) {
+                continue;
+            }
+            if predicate(&next) {
+                return Some(next);
+            }
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+fn plan_missing_dead_letter(workflow: &Workflow) -> Option<RulePlan> {
+    let node_ids = graph_ops::collect_node_ids(&workflow.nodes);
+    let outgoing = graph_ops::build_outgoing_adjacency(&workflow.connections, &node_ids);
+    let handler = first_node_by_type(workflow, |node| {
+        matches!(node.node, WorkflowNode::KafkaHandler(_))
+            && !has_failure_path(workflow, node.id, &outgoing)
+    })?;
+    let downstream = graph_ops::find_reachable(&[handler.id], &outgoing);
+    let anchor = first_node_by_type(workflow, |node| {
+        node.category == NodeCategory::Durable && downstream.contains(&node.id)
+    })
+    .unwrap_or_else(|| handler.clone());
+
+    Some(RulePlan {
+        rationale: format!(
+            "Kafka handler '{}' has no failure path, so a poison record is retried forever. Route failures to a dead-letter topic.",
+            handler.name
+        ),
+        patch: PatchPlan {
+            nodes: vec![
+                PatchNode {
+                    node_type: "condition",
+                    x: anchor.x + 220.0,
+                    y: anchor.y + 120.0,
+                },
+                PatchNode {
+                    node_type: "send-message",
+                    x: anchor.x + 440.0,
+                    y: anchor.y + 200.0,
+                },
+            ],
+            connections: vec![
+                PatchConnection {
+                    source: PatchEndpoint::Existing(anchor.id),
+                    target: PatchEndpoint::Proposed(0),
+                    source_port: "out".to_string(),
+                    target_port: "in".to_string(),
+                },
+                PatchConnection {
+                    source: PatchEndpoint::Proposed(0),
+                    target: PatchEndpoint::Proposed(1),
+                    source_port: "false".to_string(),
+                    target_port: "in".to_string(),
+                },
+            ],
+            removed_nodes: Vec::new(),
+            removed_connections: Vec::new(),
+            anchors: Vec::new(),
+        },
+    })
+}
+
+fn plan_missing_overlap_guard(workflow: &Workflow) -> Option<RulePlan> {
+    if !needs_overlap_guard(workflow) {
+        return None;
+    }
+    let trigger = first_node_by_type(workflow, |node| {
+        matches!(node.node, WorkflowNode::CronTrigger(_))
+    })?;
+    let first_step = first_node_by_type(workflow, |node| node.category == NodeCategory::Durable)?;
+    let removed_connections = workflow
+        .connections
+        .iter()
+        .filter(|connection| connection.target == first_step.id)
+        .cloned()
+        .collect::<Vec<_>>();
+    let rewired = removed_connections.iter().map(|inbound| PatchConnection {
+        source: PatchEndpoint::Existing(inbound.source),
+        target: PatchEndpoint::Proposed(0),
+        source_port: inbound.source_port.0.clone(),
+        target_port: "in".to_string(),
+    });
+    let guard = PatchConnection {
+        source: PatchEndpoint::Proposed(0),
+        target: PatchEndpoint::Existing(first_step.id),
+        source_port: "out".to_string(),
+        target_port: "in".to_string(),
+    };
+
+    Some(RulePlan {
+        rationale: format!(
+            "Cron trigger '{}' schedules durable work without checking for an in-flight run. Read a run marker with get-state before '{}'.",
+            trigger.name, first_step.name
+        ),
+        patch: PatchPlan {
+            nodes: vec![PatchNode {
+                node_type: "get-state",
+                x: first_step.x - 220.0,
+                y: first_step.y + 80.0,
             }],
+            connections: rewired.chain(std::iter::once(guard)).collect(),
+            removed_nodes: Vec::new(),
+            removed_connections,
+            anchors: Vec::new(),
         },
     })
 }
@@ -915,7 +1869,25 @@ fn execute_patch(
     key: ExtensionKey,
     fingerprint: &str,
     patch: &PatchPlan,
-) -> Vec<NodeId> {
+) -> (Vec<NodeId>, Vec<NodeId>) {
+    let removed_connection_ids = patch
+        .removed_connections
+        .iter()
+        .map(|connection| connection.id)
+        .collect::<HashSet<_>>();
+    workflow
+        .connections
+        .retain(|connection| !removed_connection_ids.contains(&connection.id));
+    let removed_nodes = patch
+        .removed_nodes
+        .iter()
+        .copied()
+        .filter(|node_id| workflow.nodes.iter().any(|node| node.id == *node_id))
+        .collect::<Vec<_>>();
+    removed_nodes
+        .iter()
+        .for_each(|node_id| workflow.remove_node(*node_id));
+
     let created_nodes = patch
         .nodes
         .iter()
@@ -931,29 +1903,31 @@ fn execute_patch(
             let _ = workflow.add_connection_checked(
                 source_id,
                 target_id,
-                &PortName::from(connection.source_port),
-                &PortName::from(connection.target_port),
+                &PortName::from(connection.source_port.as_str()),
+                &PortName::from(connection.target_port.as_str()),
             );
         }
     });
 
-    created_nodes
+    stamp_applied_signatures(workflow, &created_nodes);
+
+    (created_nodes, removed_nodes)
 }
 
-fn apply_reliability_bundle(
-    workflow: &mut Workflow,
-    key: &str,
-) -> Result<AppliedExtension, String> {
+fn apply_reliability_bundle(workflow: &mut Workflow, key: &str) -> AppliedExtension {
     let mut created_nodes = Vec::new();
+    let mut removed_nodes = Vec::new();
     for part in reliability_bundle_members() {
-        let applied = apply_extension(workflow, part.as_str())?;
+        let applied = apply_planned_extension(workflow, *part, part.as_str());
         created_nodes.extend(applied.created_nodes);
+        removed_nodes.extend(applied.removed_nodes);
     }
 
-    Ok(AppliedExtension {
+    AppliedExtension {
         key: key.to_string(),
         created_nodes,
-    })
+        removed_nodes,
+    }
 }
 
 fn annotate_extension_nodes(
@@ -986,16 +1960,105 @@ fn annotate_extension_nodes(
     }
 }
 
+fn is_suppressed(workflow: &Workflow, key: ExtensionKey, patch: &PatchPlan) -> bool {
+    !workflow.suppressed_extensions.is_empty()
+        && workflow
+            .suppressed_extensions
+            .contains(&extension_fingerprint(key, patch))
+}
+
+fn stamp_applied_signatures(workflow: &mut Workflow, node_ids: &[NodeId]) {
+    let signatures = node_ids
+        .iter()
+        .filter_map(|node_id| {
+            workflow
+                .nodes
+                .iter()
+                .find(|candidate| candidate.id == *node_id)
+                .map(|node| (*node_id, extension_node_signature(workflow, node)))
+        })
+        .collect::<Vec<_>>();
+
+    for (node_id, signature) in signatures {
+        if let Some(extension) = workflow
+            .nodes
+            .iter_mut()
+            .find(|candidate| candidate.id == node_id)
+            .and_then(|node| node.metadata.get_mut("flow_extender"))
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            extension.insert(
+                "applied_signature".to_string(),
+                serde_json::Value::String(signature),
+            );
+        }
+    }
+}
+
+fn extension_node_signature(workflow: &Workflow, node: &Node) -> String {
+    // `status` is run bookkeeping written into the config, not an edit.
+    let mut config = node.config.clone();
+    if let Some(fields) = config.as_object_mut() {
+        fields.remove("status");
+        if fields.is_empty() {
+            config = serde_json::Value::Null;
+        }
+    }
+    let mut wiring = workflow
+        .connections
+        .iter()
+        .filter(|connection| connection.source == node.id || connection.target == node.id)
+        .map(|connection| {
+            format!(
+                "{}:{}->{}:{}",
+                connection.source,
+                connection.source_port.0,
+                connection.target,
+                connection.target_port.0
+            )
+        })
+        .collect::<Vec<_>>();
+    wiring.sort();
+    format!(
+        "{}|{}|{}|{}",
+        node.name,
+        node.description,
+        config,
+        wiring.join(",")
+    )
+}
+
+fn extension_metadata(node: &Node) -> Option<&serde_json::Map<String, serde_json::Value>> {
+    node.metadata
+        .as_object()
+        .and_then(|meta| meta.get("flow_extender"))
+        .and_then(serde_json::Value::as_object)
+}
+
+fn extension_key_of(node: &Node) -> Option<&str> {
+    extension_metadata(node)
+        .and_then(|ext| ext.get("extension_key"))
+        .and_then(serde_json::Value::as_str)
+}
+
+fn is_pristine_extension_node(workflow: &Workflow, node: &Node) -> bool {
+    extension_metadata(node)
+        .and_then(|ext| ext.get("applied_signature"))
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|signature| signature == extension_node_signature(workflow, node))
+}
+
+fn extension_fingerprint_of(node: &Node) -> Option<&str> {
+    extension_metadata(node)
+        .and_then(|ext| ext.get("fingerprint"))
+        .and_then(serde_json::Value::as_str)
+}
+
 fn has_extension_fingerprint(workflow: &Workflow, fingerprint: &str) -> bool {
-    workflow.nodes.iter().any(|node| {
-        node.metadata
-            .as_object()
-            .and_then(|meta| meta.get("flow_extender"))
-            .and_then(serde_json::Value::as_object)
-            .and_then(|ext| ext.get("fingerprint"))
-            .and_then(serde_json::Value::as_str)
-            .is_some_and(|value| value == fingerprint)
-    })
+    workflow
+        .nodes
+        .iter()
+        .any(|node| extension_fingerprint_of(node).is_some_and(|value| value == fingerprint))
 }
 
 fn append_patch_plan(target: &mut PatchPlan, patch: &PatchPlan) {
@@ -1007,9 +2070,16 @@ fn append_patch_plan(target: &mut PatchPlan, patch: &PatchPlan) {
         .extend(patch.connections.iter().map(|connection| PatchConnection {
             source: remap_endpoint(connection.source, offset),
             target: remap_endpoint(connection.target, offset),
-            source_port: connection.source_port,
-            target_port: connection.target_port,
+            source_port: connection.source_port.clone(),
+            target_port: connection.target_port.clone(),
         }));
+    target
+        .removed_nodes
+        .extend(patch.removed_nodes.iter().copied());
+    target
+        .removed_connections
+        .extend(patch.removed_connections.iter().cloned());
+    target.anchors.extend(patch.anchors.iter().copied());
 }
 
 const fn remap_endpoint(endpoint: PatchEndpoint, offset: usize) -> PatchEndpoint {
@@ -1023,7 +2093,7 @@ fn extension_fingerprint(key: ExtensionKey, patch: &PatchPlan) -> String {
     let node_parts = patch
         .nodes
         .iter()
-        .map(|node| format!("{}@{:.2}:{:.2}", node.node_type, node.x, node.y))
+        .map(|node| node.node_type)
         .collect::<Vec<_>>()
         .join("|");
     let connection_parts = patch
@@ -1040,7 +2110,33 @@ fn extension_fingerprint(key: ExtensionKey, patch: &PatchPlan) -> String {
         })
         .collect::<Vec<_>>()
         .join("|");
-    format!("{}::{node_parts}::{connection_parts}", key.as_str())
+    let mut base = format!("{}::{node_parts}::{connection_parts}", key.as_str());
+    if !patch.anchors.is_empty() {
+        let anchor_parts = patch
+            .anchors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("|");
+        base = format!("{base}::@{anchor_parts}");
+    }
+    if patch.removed_nodes.is_empty() && patch.removed_connections.is_empty() {
+        return base;
+    }
+
+    let removed_node_parts = patch
+        .removed_nodes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("|");
+    let removed_connection_parts = patch
+        .removed_connections
+        .iter()
+        .map(|connection| connection.id.to_string())
+        .collect::<Vec<_>>()
+        .join("|");
+    format!("{base}::-{removed_node_parts}::-{removed_connection_parts}")
 }
 
 fn endpoint_signature(endpoint: PatchEndpoint) -> String {
@@ -1159,6 +2255,53 @@ fn extension_semantics(key: ExtensionKey) -> ExtensionSemantics {
             requires: vec![RestateCapability::PromiseResolution],
             provides: vec![RestateCapability::PromiseResolution],
         },
+        ExtensionKey::RemoveDuplicateTimeoutGuard => ExtensionSemantics {
+            compatible_service_kinds: vec![
+                RestateServiceKind::Handler,
+                RestateServiceKind::Actor,
+                RestateServiceKind::Workflow,
+            ],
+            requires: vec![RestateCapability::TimerGuard],
+            provides: vec![],
+        },
+        ExtensionKey::RewireCompensationBranch => ExtensionSemantics {
+            compatible_service_kinds: vec![
+                RestateServiceKind::Handler,
+                RestateServiceKind::Actor,
+                RestateServiceKind::Workflow,
+            ],
+            requires: vec![RestateCapability::Compensation],
+            provides: vec![RestateCapability::Compensation],
+        },
+        ExtensionKey::ReorderEarlyStateWrite => ExtensionSemantics {
+            compatible_service_kinds: vec![RestateServiceKind::Actor, RestateServiceKind::Workflow],
+            requires: vec![
+                RestateCapability::DurableExecution,
+                RestateCapability::StateStore,
+            ],
+            provides: vec![RestateCapability::StateStore],
+        },
+        ExtensionKey::AddDeadLetterBranch => ExtensionSemantics {
+            compatible_service_kinds: vec![
+                RestateServiceKind::Handler,
+                RestateServiceKind::Actor,
+                RestateServiceKind::Workflow,
+            ],
+            requires: vec![RestateCapability::EntryTrigger],
+            provides: vec![RestateCapability::Compensation],
+        },
+        ExtensionKey::AddOverlapGuard => ExtensionSemantics {
+            compatible_service_kinds: vec![
+                RestateServiceKind::Handler,
+                RestateServiceKind::Actor,
+                RestateServiceKind::Workflow,
+            ],
+            requires: vec![
+                RestateCapability::EntryTrigger,
+                RestateCapability::DurableExecution,
+            ],
+            provides: vec![RestateCapability::StateStore],
+        },
     }
 }
 
@@ -1238,39 +2381,64 @@ const fn priority_rank(key: ExtensionKey) -> u8 {
     match key {
         ExtensionKey::AddEntryTrigger
         | ExtensionKey::AddReliabilityBundle
-        | ExtensionKey::AddTimeoutGuard => 0,
+        | ExtensionKey::AddTimeoutGuard
+        | ExtensionKey::ReorderEarlyStateWrite
+        | ExtensionKey::AddOverlapGuard => 0,
         ExtensionKey::AddDurableCheckpoint
         | ExtensionKey::AddCompensationBranch
-        | ExtensionKey::AddSignalResolution => 1,
+        | ExtensionKey::AddSignalResolution
+        | ExtensionKey::RemoveDuplicateTimeoutGuard
+        | ExtensionKey::RewireCompensationBranch
+        | ExtensionKey::AddDeadLetterBranch => 1,
     }
 }
 
 const fn extension_dependencies(key: ExtensionKey) -> &'static [ExtensionKey] {
     match key {
-        ExtensionKey::AddEntryTrigger => &[],
+        ExtensionKey::AddEntryTrigger
+        | ExtensionKey::RemoveDuplicateTimeoutGuard
+        | ExtensionKey::RewireCompensationBranch
+        | ExtensionKey::ReorderEarlyStateWrite => &[],
         ExtensionKey::AddDurableCheckpoint => &[ExtensionKey::AddTimeoutGuard],
         ExtensionKey::AddReliabilityBundle
         | ExtensionKey::AddTimeoutGuard
         | ExtensionKey::AddCompensationBranch
-        | ExtensionKey::AddSignalResolution => &[ExtensionKey::AddEntryTrigger],
+        | ExtensionKey::AddSignalResolution
+        | ExtensionKey::AddDeadLetterBranch
+        | ExtensionKey::AddOverlapGuard => &[ExtensionKey::AddEntryTrigger],
     }
 }
 
-fn confidence_score_for(key: ExtensionKey, workflow: &Workflow) -> f32 {
+#[allow(clippy::cast_precision_loss)] // OK: small counts, no precision loss
+fn count_factor(key: &str, count: usize, weight: f32) -> ScoreFactor {
+    ScoreFactor {
+        key: key.to_string(),
+        count,
+        weight,
+        contribution: count as f32 * weight,
+    }
+}
+
+fn durable_node_count(workflow: &Workflow) -> usize {
+    workflow
+        .nodes
+        .iter()
+        .filter(|node| node.category == NodeCategory::Durable)
+        .count()
+}
+
+fn score_breakdown_for(key: ExtensionKey, workflow: &Workflow) -> ScoreBreakdown {
     match key {
-        ExtensionKey::AddEntryTrigger => {
-            if workflow
+        ExtensionKey::AddEntryTrigger => ScoreBreakdown::new(
+            0.98,
+            0.98,
+            workflow
                 .nodes
                 .iter()
-                .all(|node| node.category != NodeCategory::Entry)
-            {
-                0.98
-            } else {
-                0.0
-            }
-        }
+                .all(|node| node.category != NodeCategory::Entry),
+            Vec::new(),
+        ),
         ExtensionKey::AddReliabilityBundle => {
-            #[allow(clippy::cast_precision_loss)]
             let missing = [
                 plan_missing_timeout_guard(workflow).is_some(),
                 plan_missing_checkpoint(workflow).is_some(),
@@ -1278,33 +2446,35 @@ fn confidence_score_for(key: ExtensionKey, workflow: &Workflow) -> f32 {
             ]
             .into_iter()
             .filter(|value| *value)
-            .count() as f32; // OK: small count < 100, no precision loss
-            if missing == 0.0 {
-                0.0
-            } else {
-                (0.78 + missing * 0.06).min(0.97)
-            }
-        }
-        ExtensionKey::AddTimeoutGuard => {
-            #[allow(clippy::cast_precision_loss)]
-            let durable_count = workflow
-                .nodes
-                .iter()
-                .filter(|node| node.category == NodeCategory::Durable)
-                .count() as f32; // OK: small count, no precision loss
-            (0.75 + durable_count * 0.08).min(0.97)
-        }
-        ExtensionKey::AddDurableCheckpoint => {
-            #[allow(clippy::cast_precision_loss)]
-            let durable_count = workflow
-                .nodes
-                .iter()
-                .filter(|node| node.category == NodeCategory::Durable)
-                .count() as f32; // OK: small count, no precision loss
-            (0.70 + durable_count * 0.07).min(0.95)
+            .count();
+            ScoreBreakdown::new(
+                0.78,
+                0.97,
+                missing > 0,
+                vec![count_factor("missing-reliability-pieces", missing, 0.06)],
+            )
         }
+        ExtensionKey::AddTimeoutGuard => ScoreBreakdown::new(
+            0.75,
+            0.97,
+            true,
+            vec![count_factor(
+                "durable-nodes",
+                durable_node_count(workflow),
+                0.08,
+            )],
+        ),
+        ExtensionKey::AddDurableCheckpoint => ScoreBreakdown::new(
+            0.70,
+            0.95,
+            true,
+            vec![count_factor(
+                "durable-nodes",
+                durable_node_count(workflow),
+                0.07,
+            )],
+        ),
         ExtensionKey::AddCompensationBranch => {
-            #[allow(clippy::cast_precision_loss)]
             let missing = workflow
                 .nodes
                 .iter()
@@ -1312,18 +2482,77 @@ fn confidence_score_for(key: ExtensionKey, workflow: &Workflow) -> f32 {
                     matches!(node.node, WorkflowNode::Condition(_))
                         && missing_condition_branch(workflow, node.id)
                 })
-                .count() as f32; // OK: small count, no precision loss
-            (0.72 + missing * 0.09).min(0.96)
+                .count();
+            ScoreBreakdown::new(
+                0.72,
+                0.96,
+                true,
+                vec![count_factor("missing-branches", missing, 0.09)],
+            )
         }
         ExtensionKey::AddSignalResolution => {
-            #[allow(clippy::cast_precision_loss)]
             let waits = workflow
                 .nodes
                 .iter()
                 .filter(|node| is_signal_wait_anchor(workflow, node))
-                .count() as f32; // OK: small count, no precision loss
-            (0.74 + waits * 0.08).min(0.97)
+                .count();
+            ScoreBreakdown::new(
+                0.74,
+                0.97,
+                true,
+                vec![count_factor("unresolved-signal-waits", waits, 0.08)],
+            )
         }
+        ExtensionKey::RemoveDuplicateTimeoutGuard => ScoreBreakdown::new(
+            0.9,
+            0.9,
+            find_redundant_timeout_guard(workflow).is_some(),
+            Vec::new(),
+        ),
+        ExtensionKey::RewireCompensationBranch => ScoreBreakdown::new(
+            0.82,
+            0.82,
+            plan_misplaced_compensation(workflow).is_some(),
+            Vec::new(),
+        ),
+        ExtensionKey::ReorderEarlyStateWrite => ScoreBreakdown::new(
+            0.88,
+            0.88,
+            plan_early_state_write(workflow).is_some(),
+            Vec::new(),
+        ),
+        ExtensionKey::AddDeadLetterBranch => {
+            let node_ids = graph_ops::collect_node_ids(&workflow.nodes);
+            let outgoing = graph_ops::build_outgoing_adjacency(&workflow.connections, &node_ids);
+            let unprotected = workflow
+                .nodes
+                .iter()
+                .filter(|node| {
+                    matches!(node.node, WorkflowNode::KafkaHandler(_))
+                        && !has_failure_path(workflow, node.id, &outgoing)
+                })
+                .count();
+            ScoreBreakdown::new(
+                0.76,
+                0.94,
+                unprotected > 0,
+                vec![count_factor(
+                    "unprotected-kafka-handlers",
+                    unprotected,
+                    0.08,
+                )],
+            )
+        }
+        ExtensionKey::AddOverlapGuard => ScoreBreakdown::new(
+            0.8,
+            0.95,
+            needs_overlap_guard(workflow),
+            vec![count_factor(
+                "durable-nodes",
+                durable_node_count(workflow),
+                0.05,
+            )],
+        ),
     }
 }
 
@@ -1335,6 +2564,11 @@ const fn rationale_class_for(key: ExtensionKey) -> RationaleClass {
         ExtensionKey::AddDurableCheckpoint => RationaleClass::StateSafety,
         ExtensionKey::AddCompensationBranch => RationaleClass::FailureRecovery,
         ExtensionKey::AddSignalResolution => RationaleClass::AsyncCoordination,
+        ExtensionKey::RemoveDuplicateTimeoutGuard => RationaleClass::RuntimeSafety,
+        ExtensionKey::RewireCompensationBranch => RationaleClass::FailureRecovery,
+        ExtensionKey::ReorderEarlyStateWrite => RationaleClass::StateSafety,
+        ExtensionKey::AddDeadLetterBranch => RationaleClass::FailureRecovery,
+        ExtensionKey::AddOverlapGuard => RationaleClass::RuntimeSafety,
     }
 }
 
@@ -1406,9 +2640,13 @@ fn fallback_analysis(
     let fingerprint = plan_for_key(workflow, parsed_key)
         .map(|plan| extension_fingerprint(parsed_key, &plan.patch))
         .unwrap_or_default();
+    let score_breakdown = calibration::with_active_log(|log| {
+        log.calibrate(key, score_breakdown_for(parsed_key, workflow))
+    });
     ExtensionSuggestionAnalysis {
         key: key.to_string(),
-        score: confidence_score_for(parsed_key, workflow),
+        score: score_breakdown.score,
+        score_breakdown,
         rationale_class: rationale_class_for(parsed_key),
         fingerprint,
         dependencies: extension_dependencies(parsed_key)
@@ -1470,10 +2708,23 @@ fn preview_from_patch(key: String, patch: &PatchPlan) -> ExtensionPatchPreview {
         })
         .collect::<Vec<_>>();
 
+    let removed_connections = patch
+        .removed_connections
+        .iter()
+        .map(|connection| PreviewConnection {
+            source: PreviewEndpoint::Existing(connection.source),
+            target: PreviewEndpoint::Existing(connection.target),
+            source_port: connection.source_port.0.clone(),
+            target_port: connection.target_port.0.clone(),
+        })
+        .collect::<Vec<_>>();
+
     ExtensionPatchPreview {
         key,
         nodes,
         connections,
+        removed_nodes: patch.removed_nodes.clone(),
+        removed_connections,
     }
 }
 
@@ -1491,6 +2742,72 @@ fn resolve_patch_endpoint(endpoint: PatchEndpoint, created_nodes: &[NodeId]) ->
     }
 }
 
+fn find_redundant_timeout_guard(workflow: &Workflow) -> Option<(Node, Node)> {
+    let is_guard = |node: &Node| {
+        matches!(
+            node.node,
+            WorkflowNode::Timeout(_) | WorkflowNode::TimeoutGuard(_)
+        )
+    };
+    let node_by_id = |node_id: NodeId| workflow.nodes.iter().find(|node| node.id == node_id);
+    let ordered = workflow
+        .nodes
+        .iter()
+        .sorted_by(|left, right| {
+            left.y
+                .total_cmp(&right.y)
+                .then_with(|| left.x.total_cmp(&right.x))
+        })
+        .collect::<Vec<_>>();
+
+    ordered.iter().find_map(|anchor| {
+        // Guards on different ports (e.g. a condition's true and false
+        // branches) protect different paths, so only same-port siblings
+        // duplicate each other.
+        let guards_by_port = workflow
+            .connections
+            .iter()
+            .filter(|connection| connection.source == anchor.id)
+            .filter_map(|connection| {
+                node_by_id(connection.target)
+                    .filter(|node| is_guard(node))
+                    .map(|node| (connection.source_port.0.as_str(), node))
+            })
+            .unique_by(|(port, node)| (*port, node.id))
+            .into_group_map();
+        let siblings = guards_by_port
+            .into_iter()
+            .sorted_by_key(|(port, _)| *port)
+            .find_map(|(_, guards)| {
+                let guards = guards
+                    .into_iter()
+                    .sorted_by(|left, right| {
+                        left.y
+                            .total_cmp(&right.y)
+                            .then_with(|| left.x.total_cmp(&right.x))
+                    })
+                    .collect::<Vec<_>>();
+                match guards.as_slice() {
+                    [kept, redundant, ..] => Some(((*kept).clone(), (*redundant).clone())),
+                    _ => None,
+                }
+            });
+        siblings.or_else(|| {
+            let mut downstream = workflow
+                .connections
+                .iter()
+                .filter(|connection| connection.source == anchor.id)
+                .filter_map(|connection| node_by_id(connection.target))
+                .filter(|node| is_guard(node))
+                .unique_by(|node| node.id);
+            match (downstream.next(), downstream.next()) {
+                (Some(only), None) if is_guard(anchor) => Some(((*anchor).clone(), only.clone())),
+                _ => None,
+            }
+        })
+    })
+}
+
 fn missing_condition_branch(workflow: &Workflow, node_id: NodeId) -> bool {
     let has_true = workflow
         .connections
@@ -1499,9 +2816,38 @@ fn missing_condition_branch(workflow: &Workflow, node_id: NodeId) -> bool {
     let has_false = workflow
         .connections
         .iter()
-        .any(|connection| connection.source == node_id && connection.source_port.0 == "false");
+        .any(|connection| connection.source == node_id && connection.source_port.0 == "false");
+
+    !(has_true && has_false)
+}
+
+/// A failure path is a reachable compensate node or a wired condition false branch.
+fn has_failure_path(
+    workflow: &Workflow,
+    node_id: NodeId,
+    outgoing: &HashMap<NodeId, Vec<NodeId>>,
+) -> bool {
+    let downstream = graph_ops::find_reachable(&[node_id], outgoing);
+    workflow
+        .nodes
+        .iter()
+        .filter(|node| downstream.contains(&node.id))
+        .any(|node| match node.node {
+            WorkflowNode::Compensate(_) => true,
+            WorkflowNode::Condition(_) => workflow.connections.iter().any(|connection| {
+                connection.source == node.id && connection.source_port.0 == "false"
+            }),
+            _ => false,
+        })
+}
 
-    !(has_true && has_false)
+/// Cron ports carry events, so a trigger cannot be wired into flow-control
+/// steps; any durable work in a workflow with a cron trigger counts as fed.
+fn needs_overlap_guard(workflow: &Workflow) -> bool {
+    let has_node = |predicate: fn(&Node) -> bool| workflow.nodes.iter().any(predicate);
+    has_node(|node| matches!(node.node, WorkflowNode::CronTrigger(_)))
+        && has_node(|node| node.category == NodeCategory::Durable)
+        && !has_node(|node| matches!(node.node, WorkflowNode::GetState(_)))
 }
 
 fn is_signal_wait_anchor(workflow: &Workflow, node: &Node) -> bool {
@@ -1541,10 +2887,13 @@ where
 )]
 mod tests {
     use super::{
-        apply_extension, detect_extension_conflicts, extension_dependency_graph, extension_presets,
-        generate_compound_plan, preview_extension, resolve_extension_preset, suggest_extensions,
-        suggest_extensions_with_analysis, ConflictKind, ExtensionKey, PreviewEndpoint,
-        RationaleClass, RestateCapability, RestateServiceKind,
+        applied_extension_keys, apply_compound_plan, apply_extension, clear_suppression,
+        clear_suppressions, detect_extension_conflicts, dismiss_extension, explain_extension_score,
+        extension_dependency_graph, extension_presets, generate_compound_plan, list_suppressions,
+        preview_extension, resolve_extension_preset, revert_extension, suggest_extensions,
+        suggest_extensions_with_analysis, CompoundApplyFailureKind, ConflictKind, ExtensionKey,
+        ExtensionPreset, ExtensionPresetRegistry, PreviewEndpoint, RationaleClass,
+        RestateCapability, RestateServiceKind,
     };
     use crate::graph::{workflow_node::WorkflowNode, Workflow};
     use std::collections::HashSet;
@@ -1599,6 +2948,11 @@ mod tests {
             ExtensionKey::AddDurableCheckpoint,
             ExtensionKey::AddCompensationBranch,
             ExtensionKey::AddSignalResolution,
+            ExtensionKey::RemoveDuplicateTimeoutGuard,
+            ExtensionKey::RewireCompensationBranch,
+            ExtensionKey::ReorderEarlyStateWrite,
+            ExtensionKey::AddDeadLetterBranch,
+            ExtensionKey::AddOverlapGuard,
         ];
 
         let unique: HashSet<&'static str> = keys.iter().map(|key| key.as_str()).collect();
@@ -1664,6 +3018,64 @@ mod tests {
             .contains(&RestateCapability::EntryTrigger));
     }
 
+    #[test]
+    fn given_condition_missing_branch_when_analyzing_then_breakdown_explains_score() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("http-handler", 0.0, 0.0);
+        let condition = workflow.add_node("condition", 120.0, 0.0);
+        let run = workflow.add_node("run", 240.0, 0.0);
+        let _ = workflow.add_connection_checked(condition, run, &"true".into(), &"in".into());
+
+        let analyses = suggest_extensions_with_analysis(&workflow);
+
+        let bundle = analyses
+            .iter()
+            .find(|item| item.key == "add-reliability-bundle")
+            .expect("bundle should be suggested");
+        let breakdown = &bundle.score_breakdown;
+        assert!(breakdown.applicable);
+        assert_eq!(breakdown.score, bundle.score);
+        assert_eq!(breakdown.factors.len(), 1);
+        assert_eq!(breakdown.factors[0].key, "missing-reliability-pieces");
+        assert!(breakdown.factors[0].count > 0);
+        let total = breakdown.base + breakdown.factors[0].contribution;
+        assert!((bundle.score - total.min(breakdown.cap)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn given_more_durable_nodes_when_explaining_then_contribution_grows_until_capped() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 0.0, 0.0);
+        let single =
+            explain_extension_score(&workflow, "add-timeout-guard").expect("key should be valid");
+
+        for y in [80.0, 160.0, 240.0, 320.0] {
+            workflow.add_node("run", 0.0, y);
+        }
+        let many =
+            explain_extension_score(&workflow, "add-timeout-guard").expect("key should be valid");
+
+        assert_eq!(single.factors[0].key, "durable-nodes");
+        assert_eq!(single.factors[0].count, 1);
+        assert!(!single.is_capped());
+        assert_eq!(many.factors[0].count, 5);
+        assert!(many.is_capped());
+        assert_eq!(many.score, many.cap);
+        assert!(explain_extension_score(&workflow, "not-a-key").is_err());
+    }
+
+    #[test]
+    fn given_existing_entry_when_explaining_entry_trigger_then_breakdown_is_not_applicable() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("http-handler", 0.0, 0.0);
+
+        let breakdown =
+            explain_extension_score(&workflow, "add-entry-trigger").expect("key should be valid");
+
+        assert!(!breakdown.applicable);
+        assert_eq!(breakdown.score, 0.0);
+    }
+
     #[test]
     fn side_effecting_durable_when_suggesting_then_bundle_replaces_isolated_hints() {
         let mut workflow = Workflow::new();
@@ -1896,6 +3308,50 @@ mod tests {
         assert!(plan.conflicts.is_empty());
     }
 
+    #[test]
+    fn given_compound_plan_when_applying_transactionally_then_every_step_lands() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 100.0, 100.0);
+        let keys = vec![
+            "add-entry-trigger".to_string(),
+            "add-timeout-guard".to_string(),
+        ];
+        let plan = generate_compound_plan(&workflow, &keys).unwrap();
+
+        let applied = apply_compound_plan(&mut workflow, &plan).unwrap();
+
+        assert_eq!(applied.applied.len(), plan.steps.len());
+        assert_eq!(
+            applied_extension_keys(&workflow),
+            vec![
+                "add-entry-trigger".to_string(),
+                "add-timeout-guard".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn given_step_drifted_mid_plan_when_applying_then_workflow_is_restored() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 100.0, 100.0);
+        let keys = vec![
+            "add-entry-trigger".to_string(),
+            "add-timeout-guard".to_string(),
+        ];
+        let plan = generate_compound_plan(&workflow, &keys).unwrap();
+        apply_extension(&mut workflow, "add-timeout-guard").unwrap();
+        let before = workflow.clone();
+
+        let error = apply_compound_plan(&mut workflow, &plan).unwrap_err();
+
+        assert_eq!(error.failures.len(), 1);
+        assert_eq!(error.failures[0].key, "add-timeout-guard");
+        assert_eq!(error.failures[0].kind, CompoundApplyFailureKind::Drift);
+        assert_eq!(error.rolled_back, vec!["add-entry-trigger".to_string()]);
+        assert_eq!(workflow.nodes.len(), before.nodes.len());
+        assert_eq!(workflow.connections.len(), before.connections.len());
+    }
+
     #[test]
     fn given_retry_saga_preset_when_resolving_then_dependencies_expand_in_order() {
         let workflow = Workflow::new();
@@ -1960,4 +3416,512 @@ mod tests {
         assert!(presets.iter().any(|preset| preset.key == "approval"));
         assert!(presets.iter().any(|preset| preset.key == "retry-saga"));
     }
+
+    fn custom_preset(key: &str, extension_keys: &[&str]) -> ExtensionPreset {
+        ExtensionPreset {
+            key: key.to_string(),
+            title: "Signal Safety".to_string(),
+            description: "Team default for signal-driven flows.".to_string(),
+            extension_keys: extension_keys.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn given_custom_preset_when_resolving_then_dependencies_expand_like_builtins() {
+        let mut registry = ExtensionPresetRegistry::new();
+        registry
+            .define(custom_preset(
+                "signal-safety",
+                &["add-durable-checkpoint", "add-signal-resolution"],
+            ))
+            .unwrap();
+
+        let resolved = registry.resolve(&Workflow::new(), "signal-safety").unwrap();
+
+        assert_eq!(resolved.preset.title, "Signal Safety");
+        assert_eq!(
+            resolved.ordered_keys,
+            vec![
+                "add-entry-trigger".to_string(),
+                "add-timeout-guard".to_string(),
+                "add-durable-checkpoint".to_string(),
+                "add-signal-resolution".to_string(),
+            ]
+        );
+        assert_eq!(registry.presets().len(), 4);
+        assert!(registry.resolve(&Workflow::new(), "webhook").is_ok());
+    }
+
+    #[test]
+    fn given_invalid_custom_presets_when_defining_then_they_are_rejected() {
+        let mut registry = ExtensionPresetRegistry::new();
+
+        assert!(registry
+            .define(custom_preset("webhook", &["add-timeout-guard"]))
+            .is_err());
+        assert!(registry
+            .define(custom_preset("Not Kebab", &["add-timeout-guard"]))
+            .is_err());
+        assert!(registry.define(custom_preset("empty", &[])).is_err());
+        assert!(registry
+            .define(custom_preset("unknown", &["add-magic"]))
+            .is_err());
+        assert!(registry.custom_presets().is_empty());
+    }
+
+    #[test]
+    fn given_registry_when_redefining_removing_and_round_tripping_then_custom_state_is_kept() {
+        let mut registry = ExtensionPresetRegistry::new();
+        registry
+            .define(custom_preset("guarded", &["add-timeout-guard"]))
+            .unwrap();
+        registry
+            .define(custom_preset("guarded", &["add-durable-checkpoint"]))
+            .unwrap();
+
+        let json = serde_json::to_string(&registry).unwrap();
+        let restored = serde_json::from_str::<ExtensionPresetRegistry>(&json).unwrap();
+
+        assert_eq!(restored, registry);
+        assert_eq!(registry.custom_presets().len(), 1);
+        assert_eq!(
+            registry.custom_presets()[0].extension_keys,
+            vec!["add-durable-checkpoint".to_string()]
+        );
+        assert!(registry.is_custom("guarded"));
+        assert!(!registry.remove("webhook"));
+        assert!(registry.remove("guarded"));
+        assert!(registry.resolve(&Workflow::new(), "guarded").is_err());
+    }
+
+    #[test]
+    fn applied_extension_when_reverting_then_created_nodes_and_edges_are_removed() {
+        let mut workflow = Workflow::new();
+        let run = workflow.add_node("run", 20.0, 30.0);
+        let applied = apply_extension(&mut workflow, "add-timeout-guard").unwrap();
+        assert_eq!(applied.created_nodes.len(), 1);
+        assert_eq!(applied_extension_keys(&workflow), vec!["add-timeout-guard"]);
+
+        let reverted = revert_extension(&mut workflow, "add-timeout-guard").unwrap();
+
+        assert_eq!(reverted.removed_nodes, applied.created_nodes);
+        assert!(reverted.retained_nodes.is_empty());
+        assert!(applied_extension_keys(&workflow).is_empty());
+        assert_eq!(workflow.nodes.len(), 1);
+        assert!(workflow.nodes.iter().all(|node| node.id == run));
+        assert!(workflow.connections.is_empty());
+    }
+
+    #[tokio::test]
+    async fn applied_extension_when_workflow_has_run_then_revert_still_removes_it() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 20.0, 30.0);
+        let applied = apply_extension(&mut workflow, "add-timeout-guard").unwrap();
+
+        workflow.run().await;
+
+        assert!(workflow
+            .nodes
+            .iter()
+            .filter(|node| applied.created_nodes.contains(&node.id))
+            .all(|node| node.config.get("status").is_some()));
+        let reverted = revert_extension(&mut workflow, "add-timeout-guard").unwrap();
+        assert_eq!(reverted.removed_nodes, applied.created_nodes);
+        assert!(reverted.retained_nodes.is_empty());
+        assert_eq!(workflow.nodes.len(), 1);
+    }
+
+    #[test]
+    fn manually_modified_extension_node_when_reverting_then_node_is_retained() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 20.0, 30.0);
+        let applied = apply_extension(&mut workflow, "add-timeout-guard").unwrap();
+        let timeout_id = applied.created_nodes[0];
+        if let Some(node) = workflow.nodes.iter_mut().find(|node| node.id == timeout_id) {
+            node.name = "Renamed timeout".to_string();
+        }
+
+        let reverted = revert_extension(&mut workflow, "add-timeout-guard").unwrap();
+
+        assert!(reverted.removed_nodes.is_empty());
+        assert_eq!(reverted.retained_nodes, vec![timeout_id]);
+        assert!(workflow.nodes.iter().any(|node| node.id == timeout_id));
+    }
+
+    #[test]
+    fn rewired_extension_node_when_reverting_then_node_is_retained() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 20.0, 30.0);
+        let applied = apply_extension(&mut workflow, "add-timeout-guard").unwrap();
+        let timeout_id = applied.created_nodes[0];
+        let downstream = workflow.add_node("run", 400.0, 30.0);
+        let _ =
+            workflow.add_connection_checked(timeout_id, downstream, &"out".into(), &"in".into());
+
+        let reverted = revert_extension(&mut workflow, "add-timeout-guard").unwrap();
+
+        assert_eq!(reverted.retained_nodes, vec![timeout_id]);
+        assert!(workflow.nodes.iter().any(|node| node.id == timeout_id));
+    }
+
+    #[test]
+    fn applied_bundle_when_reverting_then_member_nodes_are_removed() {
+        let mut workflow = Workflow::new();
+        let condition = workflow.add_node("condition", 40.0, 40.0);
+        let run = workflow.add_node("run", 120.0, 40.0);
+        workflow.add_node("get-state", 20.0, 20.0);
+        let _ = workflow.add_connection_checked(condition, run, &"true".into(), &"in".into());
+        let node_count = workflow.nodes.len();
+        let applied = apply_extension(&mut workflow, "add-reliability-bundle").unwrap();
+        assert!(!applied.created_nodes.is_empty());
+
+        let reverted = revert_extension(&mut workflow, "add-reliability-bundle").unwrap();
+
+        assert_eq!(reverted.removed_nodes.len(), applied.created_nodes.len());
+        assert_eq!(workflow.nodes.len(), node_count);
+    }
+
+    #[test]
+    fn given_unknown_key_when_reverting_then_error_is_returned() {
+        let mut workflow = Workflow::new();
+
+        let result = revert_extension(&mut workflow, "not-a-valid-extension");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn dismissed_suggestion_when_suggesting_again_then_it_stays_hidden() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("http-call", 20.0, 30.0);
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .any(|item| item.key == "add-timeout-guard"));
+
+        let fingerprint = dismiss_extension(&mut workflow, "add-timeout-guard").unwrap();
+
+        assert!(fingerprint.is_some());
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .all(|item| item.key != "add-timeout-guard"));
+        assert!(suggest_extensions_with_analysis(&workflow)
+            .iter()
+            .all(|item| item.key != "add-timeout-guard"));
+    }
+
+    #[test]
+    fn dismissed_suggestion_when_anchor_node_moves_then_it_stays_hidden() {
+        let mut workflow = Workflow::new();
+        let call = workflow.add_node("http-call", 20.0, 30.0);
+        let _ = dismiss_extension(&mut workflow, "add-timeout-guard").unwrap();
+
+        if let Some(node) = workflow.nodes.iter_mut().find(|node| node.id == call) {
+            node.x = 480.0;
+            node.y = 260.0;
+        }
+
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .all(|item| item.key != "add-timeout-guard"));
+        assert!(list_suppressions(&workflow)
+            .iter()
+            .all(|suppression| suppression.active));
+    }
+
+    #[test]
+    fn dismissed_suggestion_when_workflow_changes_materially_then_it_returns() {
+        let mut workflow = Workflow::new();
+        let run = workflow.add_node("http-call", 20.0, 30.0);
+        let _ = dismiss_extension(&mut workflow, "add-timeout-guard").unwrap();
+        workflow.add_node("http-call", 10.0, 5.0);
+
+        let suggestions = suggest_extensions(&workflow);
+        let suppressions = list_suppressions(&workflow);
+
+        assert!(suggestions
+            .iter()
+            .any(|item| item.key == "add-timeout-guard"));
+        assert_eq!(suppressions.len(), 1);
+        assert_eq!(suppressions[0].key, "add-timeout-guard");
+        assert!(!suppressions[0].active);
+        assert!(workflow.nodes.iter().any(|node| node.id == run));
+    }
+
+    #[test]
+    fn dismissed_entry_suggestion_when_a_new_root_appears_then_it_returns() {
+        let mut workflow = Workflow::new();
+        let first = workflow.add_node("run", 20.0, 30.0);
+        let _ = dismiss_extension(&mut workflow, "add-entry-trigger").unwrap();
+        let downstream = workflow.add_node("run", 240.0, 30.0);
+        let _ = workflow.add_connection_checked(first, downstream, &"out".into(), &"in".into());
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .all(|item| item.key != "add-entry-trigger"));
+
+        workflow.add_node("run", 20.0, 300.0);
+
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .any(|item| item.key == "add-entry-trigger"));
+    }
+
+    #[test]
+    fn suppressions_when_cleared_then_suggestions_return() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("http-call", 20.0, 30.0);
+        let _ = dismiss_extension(&mut workflow, "add-timeout-guard").unwrap();
+        let _ = dismiss_extension(&mut workflow, "add-entry-trigger").unwrap();
+        assert!(list_suppressions(&workflow)
+            .iter()
+            .all(|suppression| suppression.active));
+
+        assert_eq!(clear_suppression(&mut workflow, "add-timeout-guard"), 1);
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .any(|item| item.key == "add-timeout-guard"));
+        assert_eq!(clear_suppressions(&mut workflow), 1);
+        assert!(list_suppressions(&workflow).is_empty());
+    }
+
+    #[test]
+    fn chained_timeout_guards_when_applying_fix_then_redundant_guard_is_removed_and_rewired() {
+        let mut workflow = Workflow::new();
+        let run = workflow.add_node("run", 0.0, 0.0);
+        let kept = workflow.add_node("timeout", 220.0, 0.0);
+        let redundant = workflow.add_node("timeout", 440.0, 0.0);
+        let call = workflow.add_node("http-call", 660.0, 0.0);
+        let _ = workflow.add_connection_checked(run, kept, &"out".into(), &"in".into());
+        let _ = workflow.add_connection_checked(kept, redundant, &"out".into(), &"in".into());
+        let _ = workflow.add_connection_checked(redundant, call, &"out".into(), &"in".into());
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .any(|item| item.key == "remove-duplicate-timeout-guard"));
+
+        let applied = apply_extension(&mut workflow, "remove-duplicate-timeout-guard").unwrap();
+
+        assert!(applied.created_nodes.is_empty());
+        assert_eq!(applied.removed_nodes, vec![redundant]);
+        assert!(workflow.nodes.iter().all(|node| node.id != redundant));
+        assert!(workflow
+            .connections
+            .iter()
+            .any(|connection| connection.source == kept && connection.target == call));
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .all(|item| item.key != "remove-duplicate-timeout-guard"));
+    }
+
+    #[test]
+    fn duplicate_timeout_guard_when_previewing_then_removals_are_listed() {
+        let mut workflow = Workflow::new();
+        let run = workflow.add_node("run", 0.0, 0.0);
+        let first = workflow.add_node("timeout", 220.0, 0.0);
+        let second = workflow.add_node("timeout", 220.0, 120.0);
+        let _ = workflow.add_connection_checked(run, first, &"out".into(), &"in".into());
+        let _ = workflow.add_connection_checked(run, second, &"out".into(), &"in".into());
+
+        let preview = preview_extension(&workflow, "remove-duplicate-timeout-guard")
+            .unwrap()
+            .unwrap();
+
+        assert!(preview.nodes.is_empty());
+        assert_eq!(preview.removed_nodes, vec![second]);
+        assert_eq!(preview.removed_connections.len(), 1);
+        assert_eq!(
+            preview.removed_connections[0].source,
+            PreviewEndpoint::Existing(run)
+        );
+    }
+
+    #[test]
+    fn timeout_guards_on_both_condition_branches_when_suggesting_then_none_is_redundant() {
+        let mut workflow = Workflow::new();
+        let check = workflow.add_node("condition", 0.0, 0.0);
+        let on_true = workflow.add_node("timeout", 220.0, 0.0);
+        let on_false = workflow.add_node("timeout", 220.0, 120.0);
+        let _ = workflow.add_connection_checked(check, on_true, &"true".into(), &"in".into());
+        let _ = workflow.add_connection_checked(check, on_false, &"false".into(), &"in".into());
+        assert_eq!(workflow.connections.len(), 2);
+
+        let preview = preview_extension(&workflow, "remove-duplicate-timeout-guard").unwrap();
+
+        assert!(preview.is_none());
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .all(|item| item.key != "remove-duplicate-timeout-guard"));
+    }
+
+    #[test]
+    fn compensation_off_non_condition_when_applying_fix_then_condition_is_inserted() {
+        let mut workflow = Workflow::new();
+        let run = workflow.add_node("run", 0.0, 0.0);
+        let compensate = workflow.add_node("compensate", 220.0, 0.0);
+        let _ = workflow.add_connection_checked(run, compensate, &"out".into(), &"in".into());
+
+        let applied = apply_extension(&mut workflow, "rewire-compensation-branch").unwrap();
+
+        assert_eq!(applied.created_nodes.len(), 1);
+        let condition = applied.created_nodes[0];
+        assert!(workflow
+            .nodes
+            .iter()
+            .any(|node| node.id == condition && matches!(node.node, WorkflowNode::Condition(_))));
+        assert!(workflow
+            .connections
+            .iter()
+            .all(|connection| !(connection.source == run && connection.target == compensate)));
+        assert!(workflow.connections.iter().any(|connection| {
+            connection.source == condition
+                && connection.target == compensate
+                && connection.source_port.0 == "false"
+        }));
+        assert!(workflow.nodes.iter().any(|node| node.id == compensate));
+    }
+
+    #[test]
+    fn state_write_before_durable_step_when_applying_fix_then_it_moves_after_the_step() {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let state = workflow.add_node("set-state", 220.0, 0.0);
+        let run = workflow.add_node("run", 440.0, 0.0);
+        let _ = workflow.add_connection_checked(entry, state, &"out".into(), &"in".into());
+        let _ = workflow.add_connection_checked(state, run, &"out".into(), &"in".into());
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .any(|item| item.key == "reorder-early-state-write"));
+
+        let applied = apply_extension(&mut workflow, "reorder-early-state-write").unwrap();
+
+        assert!(applied.created_nodes.is_empty());
+        assert!(applied.removed_nodes.is_empty());
+        assert_eq!(workflow.nodes.len(), 3);
+        assert!(workflow
+            .connections
+            .iter()
+            .any(|connection| connection.source == entry && connection.target == run));
+        assert!(workflow
+            .connections
+            .iter()
+            .any(|connection| connection.source == run && connection.target == state));
+        assert!(workflow
+            .connections
+            .iter()
+            .all(|connection| connection.source != state));
+    }
+
+    #[test]
+    fn state_write_before_durable_chain_when_previewing_then_it_reattaches_to_nearest_step() {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let state = workflow.add_node("set-state", 220.0, 0.0);
+        let near = workflow.add_node("run", 440.0, 200.0);
+        let far = workflow.add_node("run", 100.0, -100.0);
+        let _ = workflow.add_connection_checked(entry, state, &"out".into(), &"in".into());
+        let _ = workflow.add_connection_checked(state, near, &"out".into(), &"in".into());
+        let _ = workflow.add_connection_checked(near, far, &"out".into(), &"in".into());
+
+        let preview = preview_extension(&workflow, "reorder-early-state-write")
+            .unwrap()
+            .unwrap();
+
+        assert!(preview.connections.iter().any(|connection| {
+            connection.source == PreviewEndpoint::Existing(near)
+                && connection.target == PreviewEndpoint::Existing(state)
+        }));
+        assert!(preview
+            .connections
+            .iter()
+            .all(|connection| connection.source != PreviewEndpoint::Existing(far)));
+    }
+
+    #[test]
+    fn kafka_handler_without_failure_path_when_applying_then_dead_letter_branch_is_wired() {
+        let mut workflow = Workflow::new();
+        let handler = workflow.add_node("kafka-handler", 0.0, 0.0);
+        let run = workflow.add_node("run", 220.0, 0.0);
+        let _ = workflow.add_connection_checked(handler, run, &"out".into(), &"in".into());
+        let analysis = suggest_extensions_with_analysis(&workflow)
+            .into_iter()
+            .find(|item| item.key == "add-dead-letter-branch")
+            .unwrap();
+        assert_eq!(analysis.rationale_class, RationaleClass::FailureRecovery);
+        assert_eq!(analysis.dependencies, vec!["add-entry-trigger".to_string()]);
+        assert!(analysis.fingerprint.starts_with("add-dead-letter-branch::"));
+
+        let applied = apply_extension(&mut workflow, "add-dead-letter-branch").unwrap();
+
+        assert_eq!(applied.created_nodes.len(), 2);
+        let (condition, dead_letter) = (applied.created_nodes[0], applied.created_nodes[1]);
+        assert!(workflow
+            .connections
+            .iter()
+            .any(|connection| connection.source == run && connection.target == condition));
+        assert!(workflow.connections.iter().any(|connection| {
+            connection.source == condition
+                && connection.target == dead_letter
+                && connection.source_port.0 == "false"
+        }));
+        assert!(preview_extension(&workflow, "add-dead-letter-branch")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn cron_trigger_scheduling_durable_work_when_applying_then_get_state_guard_is_inserted() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("cron-trigger", 0.0, 0.0);
+        let run = workflow.add_node("run", 220.0, 0.0);
+        let persist = workflow.add_node("set-state", 440.0, 0.0);
+        let _ = workflow.add_connection_checked(run, persist, &"out".into(), &"in".into());
+        let preview = preview_extension(&workflow, "add-overlap-guard")
+            .unwrap()
+            .unwrap();
+        assert_eq!(preview.nodes[0].node_type, "get-state");
+
+        let applied = apply_extension(&mut workflow, "add-overlap-guard").unwrap();
+
+        let guard = applied.created_nodes[0];
+        assert!(workflow
+            .connections
+            .iter()
+            .any(|connection| connection.source == guard && connection.target == run));
+        assert!(explain_extension_score(&workflow, "add-overlap-guard")
+            .unwrap()
+            .score
+            .eq(&0.0));
+    }
+
+    #[test]
+    fn durable_step_with_upstream_when_guarding_overlap_then_inbound_edges_route_through_guard() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("cron-trigger", 0.0, 0.0);
+        let sleep = workflow.add_node("sleep", 220.0, 0.0);
+        let run = workflow.add_node("run", 440.0, 0.0);
+        let _ = workflow.add_connection_checked(sleep, run, &"out".into(), &"in".into());
+
+        let applied = apply_extension(&mut workflow, "add-overlap-guard").unwrap();
+
+        let guard = applied.created_nodes[0];
+        assert!(workflow
+            .connections
+            .iter()
+            .all(|connection| !(connection.source == sleep && connection.target == run)));
+        assert!(workflow
+            .connections
+            .iter()
+            .any(|connection| connection.source == sleep && connection.target == guard));
+    }
+
+    #[test]
+    fn cron_workflow_already_reading_state_when_suggesting_then_overlap_guard_is_skipped() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("cron-trigger", 0.0, 0.0);
+        let check = workflow.add_node("get-state", 220.0, 0.0);
+        let run = workflow.add_node("run", 440.0, 0.0);
+        let _ = workflow.add_connection_checked(check, run, &"out".into(), &"in".into());
+
+        assert!(suggest_extensions(&workflow)
+            .iter()
+            .all(|item| item.key != "add-overlap-guard"));
+    }
 }
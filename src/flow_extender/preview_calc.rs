@@ -6,7 +6,7 @@
 use crate::flow_extender::{
     ExtensionPatchPreview, PreviewConnection, PreviewEndpoint, PreviewNode,
 };
-use crate::graph::{Node, NodeId};
+use crate::graph::NodeIndex;
 use std::collections::HashMap;
 use std::fmt::Write;
 
@@ -41,7 +41,7 @@ pub fn compute_preview_nodes(patches: &[ExtensionPatchPreview]) -> Vec<PreviewNo
 /// strings with keys `"p{patch_idx}-e{edge_idx}"`.
 pub fn compute_preview_edges(
     patches: &[ExtensionPatchPreview],
-    existing_nodes: &HashMap<NodeId, Node>,
+    existing_nodes: &NodeIndex,
 ) -> Vec<PreviewEdgeEntry> {
     patches
         .iter()
@@ -57,7 +57,7 @@ pub fn compute_preview_edges(
                 .filter_map(move |(edge_idx, edge)| {
                     compute_single_edge(
                         patch_idx,
-                        edge_idx,
+                        &format!("e{edge_idx}"),
                         edge,
                         &existing_nodes,
                         &proposed_lookup,
@@ -67,6 +67,60 @@ pub fn compute_preview_edges(
         .collect()
 }
 
+/// Pure function: compute removal markers for existing nodes a patch deletes.
+///
+/// Produces `(key, node_type, x, y)` tuples at the existing node positions,
+/// with keys `"p{patch_idx}-rm-{node_id}"`. Unknown node IDs are skipped.
+pub fn compute_removed_preview_nodes(
+    patches: &[ExtensionPatchPreview],
+    existing_nodes: &NodeIndex,
+) -> Vec<PreviewNodeEntry> {
+    patches
+        .iter()
+        .enumerate()
+        .flat_map(|(patch_idx, patch)| {
+            patch.removed_nodes.iter().filter_map(move |node_id| {
+                existing_nodes.get(node_id).map(|node| {
+                    let mut key = String::with_capacity(48);
+                    let _ = write!(key, "p{patch_idx}-rm-{node_id}");
+                    (key, node.node_type.clone(), node.x, node.y)
+                })
+            })
+        })
+        .collect()
+}
+
+/// Pure function: compute paths for existing edges a patch deletes.
+///
+/// Uses the same geometry as [`compute_preview_edges`] with keys
+/// `"p{patch_idx}-r{edge_idx}"`.
+pub fn compute_removed_preview_edges(
+    patches: &[ExtensionPatchPreview],
+    existing_nodes: &NodeIndex,
+) -> Vec<PreviewEdgeEntry> {
+    let no_proposed = HashMap::new();
+    patches
+        .iter()
+        .enumerate()
+        .flat_map(|(patch_idx, patch)| {
+            patch
+                .removed_connections
+                .iter()
+                .enumerate()
+                .filter_map(|(edge_idx, edge)| {
+                    compute_single_edge(
+                        patch_idx,
+                        &format!("r{edge_idx}"),
+                        edge,
+                        existing_nodes,
+                        &no_proposed,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
 /// Build the proposed node position lookup for a given patch index.
 fn build_proposed_lookup(patch_idx: usize, nodes: &[PreviewNode]) -> HashMap<String, (f32, f32)> {
     nodes
@@ -82,16 +136,16 @@ fn build_proposed_lookup(patch_idx: usize, nodes: &[PreviewNode]) -> HashMap<Str
 /// Compute a single preview edge, resolving endpoints to pixel positions.
 fn compute_single_edge(
     patch_idx: usize,
-    edge_idx: usize,
+    edge_label: &str,
     edge: &PreviewConnection,
-    existing_nodes: &HashMap<NodeId, Node>,
+    existing_nodes: &NodeIndex,
     proposed_lookup: &HashMap<String, (f32, f32)>,
 ) -> Option<PreviewEdgeEntry> {
     let source = resolve_source_position(&edge.source, patch_idx, existing_nodes, proposed_lookup)?;
     let target = resolve_target_position(&edge.target, patch_idx, existing_nodes, proposed_lookup)?;
 
     let mut edge_key = String::with_capacity(32);
-    let _ = write!(edge_key, "p{patch_idx}-e{edge_idx}");
+    let _ = write!(edge_key, "p{patch_idx}-{edge_label}");
 
     let (sx, sy) = source;
     let (tx, ty) = target;
@@ -117,7 +171,7 @@ fn compute_single_edge(
 fn resolve_source_position(
     endpoint: &PreviewEndpoint,
     patch_idx: usize,
-    existing_nodes: &HashMap<NodeId, Node>,
+    existing_nodes: &NodeIndex,
     proposed_lookup: &HashMap<String, (f32, f32)>,
 ) -> Option<(f32, f32)> {
     match endpoint {
@@ -139,7 +193,7 @@ fn resolve_source_position(
 fn resolve_target_position(
     endpoint: &PreviewEndpoint,
     patch_idx: usize,
-    existing_nodes: &HashMap<NodeId, Node>,
+    existing_nodes: &NodeIndex,
     proposed_lookup: &HashMap<String, (f32, f32)>,
 ) -> Option<(f32, f32)> {
     match endpoint {
@@ -169,8 +223,7 @@ mod tests {
     use crate::flow_extender::{
         ExtensionPatchPreview, PreviewConnection, PreviewEndpoint, PreviewNode,
     };
-    use crate::graph::Node;
-    use std::collections::HashMap;
+    use crate::graph::{Node, NodeId};
 
     fn sample_node(id: NodeId, x: f32, y: f32) -> Node {
         Node {
@@ -192,7 +245,7 @@ mod tests {
     #[test]
     fn empty_patches_produce_no_preview_edges() {
         let patches: Vec<ExtensionPatchPreview> = vec![];
-        let existing = HashMap::new();
+        let existing = NodeIndex::default();
         let result = compute_preview_edges(&patches, &existing);
         assert!(result.is_empty());
     }
@@ -208,6 +261,8 @@ mod tests {
                 y: 200.0,
             }],
             connections: vec![],
+            removed_nodes: vec![],
+            removed_connections: vec![],
         }];
 
         let result = compute_preview_nodes(&patches);
@@ -230,6 +285,8 @@ mod tests {
                     y: 20.0,
                 }],
                 connections: vec![],
+                removed_nodes: vec![],
+                removed_connections: vec![],
             },
             ExtensionPatchPreview {
                 key: "ext-b".to_string(),
@@ -248,6 +305,8 @@ mod tests {
                     },
                 ],
                 connections: vec![],
+                removed_nodes: vec![],
+                removed_connections: vec![],
             },
         ];
 
@@ -261,8 +320,7 @@ mod tests {
     #[test]
     fn edge_between_existing_and_proposed_node_produces_one_edge() {
         let existing_id = NodeId::new();
-        let mut existing = HashMap::new();
-        existing.insert(existing_id, sample_node(existing_id, 0.0, 100.0));
+        let existing = NodeIndex::from_nodes(&[sample_node(existing_id, 0.0, 100.0)]);
 
         let patches = vec![ExtensionPatchPreview {
             key: "test-ext".to_string(),
@@ -278,6 +336,8 @@ mod tests {
                 source_port: "out".to_string(),
                 target_port: "in".to_string(),
             }],
+            removed_nodes: vec![],
+            removed_connections: vec![],
         }];
 
         let result = compute_preview_edges(&patches, &existing);
@@ -293,7 +353,7 @@ mod tests {
     #[test]
     fn edge_with_missing_existing_node_is_skipped() {
         let missing_id = NodeId::new();
-        let existing = HashMap::new(); // empty, so missing_id won't be found
+        let existing = NodeIndex::default(); // empty, so missing_id won't be found
 
         let patches = vec![ExtensionPatchPreview {
             key: "test-ext".to_string(),
@@ -309,6 +369,8 @@ mod tests {
                 source_port: "out".to_string(),
                 target_port: "in".to_string(),
             }],
+            removed_nodes: vec![],
+            removed_connections: vec![],
         }];
 
         let result = compute_preview_edges(&patches, &existing);
@@ -320,7 +382,7 @@ mod tests {
 
     #[test]
     fn edge_between_two_proposed_nodes() {
-        let existing = HashMap::new();
+        let existing = NodeIndex::default();
 
         let patches = vec![ExtensionPatchPreview {
             key: "test-ext".to_string(),
@@ -344,6 +406,8 @@ mod tests {
                 source_port: "out".to_string(),
                 target_port: "in".to_string(),
             }],
+            removed_nodes: vec![],
+            removed_connections: vec![],
         }];
 
         let result = compute_preview_edges(&patches, &existing);
@@ -361,8 +425,7 @@ mod tests {
     #[test]
     fn multiple_edges_in_single_patch_use_correct_indices() {
         let existing_id = NodeId::new();
-        let mut existing = HashMap::new();
-        existing.insert(existing_id, sample_node(existing_id, 0.0, 0.0));
+        let existing = NodeIndex::from_nodes(&[sample_node(existing_id, 0.0, 0.0)]);
 
         let patches = vec![ExtensionPatchPreview {
             key: "test-ext".to_string(),
@@ -394,6 +457,8 @@ mod tests {
                     target_port: "in".to_string(),
                 },
             ],
+            removed_nodes: vec![],
+            removed_connections: vec![],
         }];
 
         let result = compute_preview_edges(&patches, &existing);
@@ -401,4 +466,36 @@ mod tests {
         assert_eq!(result[0].0, "p0-e0");
         assert_eq!(result[1].0, "p0-e1");
     }
+
+    #[test]
+    fn removed_nodes_and_edges_produce_removal_markers() {
+        let source_id = NodeId::new();
+        let target_id = NodeId::new();
+        let existing = NodeIndex::from_nodes(&[
+            sample_node(source_id, 0.0, 0.0),
+            sample_node(target_id, 300.0, 0.0),
+        ]);
+
+        let patches = vec![ExtensionPatchPreview {
+            key: "remove-duplicate-timeout-guard".to_string(),
+            nodes: vec![],
+            connections: vec![],
+            removed_nodes: vec![target_id, NodeId::new()],
+            removed_connections: vec![PreviewConnection {
+                source: PreviewEndpoint::Existing(source_id),
+                target: PreviewEndpoint::Existing(target_id),
+                source_port: "out".to_string(),
+                target_port: "in".to_string(),
+            }],
+        }];
+
+        let nodes = compute_removed_preview_nodes(&patches, &existing);
+        let edges = compute_removed_preview_edges(&patches, &existing);
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].0, format!("p0-rm-{target_id}"));
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].0, "p0-r0");
+        assert!(compute_preview_edges(&patches, &existing).is_empty());
+    }
 }
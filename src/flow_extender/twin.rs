@@ -0,0 +1,324 @@
+//! Twin-aware suggestions for calls to external services.
+//!
+//! A spec lists its external services under `system_dependencies`. When a
+//! `service-call` or `http-call` node targets one that has a digital twin,
+//! this rule proposes a twin definition for it, binds the node to it, and
+//! adds a universe entry so scenarios can run against the twin.
+
+use crate::graph::workflow_node::WorkflowNode;
+use crate::graph::{Node, NodeId, Workflow};
+use crate::linter::SystemDependency;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const TWIN_CONFIG_KEY: &str = "twin";
+pub const DEFAULT_TWIN_BASE_URL: &str = "http://127.0.0.1:9900/twins";
+pub const DEFAULT_UNIVERSE: &str = "local";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TwinDefinition {
+    pub service: String,
+    pub purpose: String,
+    pub endpoint: String,
+}
+
+impl TwinDefinition {
+    #[must_use]
+    pub fn for_dependency(dependency: &SystemDependency, base_url: &str) -> Self {
+        Self {
+            service: dependency.service.clone(),
+            purpose: dependency.purpose.clone(),
+            endpoint: format!("{}/{}", base_url.trim_end_matches('/'), dependency.service),
+        }
+    }
+}
+
+/// A call node that can run against a twin instead of the real service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwinSuggestion {
+    pub node_id: NodeId,
+    pub node_name: String,
+    pub definition: TwinDefinition,
+    pub rationale: String,
+}
+
+/// Twins a scenario universe provides, keyed by service name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TwinUniverse {
+    pub name: String,
+    #[serde(default)]
+    pub twins: BTreeMap<String, TwinDefinition>,
+}
+
+impl TwinUniverse {
+    #[must_use]
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            twins: BTreeMap::new(),
+        }
+    }
+
+    /// Add a twin entry. Returns `false` if the service already had one.
+    pub fn add(&mut self, definition: TwinDefinition) -> bool {
+        if self.twins.contains_key(&definition.service) {
+            return false;
+        }
+        self.twins.insert(definition.service.clone(), definition);
+        true
+    }
+
+    /// Service → endpoint map in the shape the scenario runner expects.
+    #[must_use]
+    pub fn endpoints(&self) -> HashMap<String, String> {
+        self.twins
+            .iter()
+            .map(|(service, definition)| (service.clone(), definition.endpoint.clone()))
+            .collect()
+    }
+}
+
+/// Suggest twins for call nodes that target twin-capable dependencies.
+///
+/// Nodes already bound to a twin are skipped.
+#[must_use]
+pub fn suggest_twins(
+    workflow: &Workflow,
+    dependencies: &[SystemDependency],
+    base_url: &str,
+) -> Vec<TwinSuggestion> {
+    workflow
+        .nodes
+        .iter()
+        .filter(|node| bound_twin(node).is_none())
+        .filter_map(|node| {
+            let target = call_target(node)?;
+            let dependency = dependencies
+                .iter()
+                .filter(|dependency| dependency.twin_available)
+                .find(|dependency| targets_service(&target, &dependency.service))?;
+            Some(TwinSuggestion {
+                node_id: node.id,
+                node_name: node.name.clone(),
+                definition: TwinDefinition::for_dependency(dependency, base_url),
+                rationale: format!(
+                    "'{}' calls {}, which the spec declares with a twin; run it against the twin in tests.",
+                    node.name, dependency.service
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Bind the suggested node to its twin and register the twin in `universe`.
+///
+/// # Errors
+///
+/// Returns `String` if the node no longer exists, is bound to another twin,
+/// or has a config that is not a JSON object.
+pub fn bind_twin(
+    workflow: &mut Workflow,
+    universe: &mut TwinUniverse,
+    suggestion: &TwinSuggestion,
+) -> Result<(), String> {
+    let node = workflow
+        .nodes
+        .iter_mut()
+        .find(|node| node.id == suggestion.node_id)
+        .ok_or_else(|| format!("Node {} not found", suggestion.node_id))?;
+
+    match bound_twin(node) {
+        Some(service) if service == suggestion.definition.service => {}
+        Some(service) => {
+            return Err(format!(
+                "Node '{}' is already bound to twin {service}",
+                node.name
+            ))
+        }
+        None => {
+            let mut config = node.config.clone();
+            let Some(fields) = config.as_object_mut() else {
+                return Err(format!(
+                    "Node '{}' has a non-object config; cannot bind a twin",
+                    node.name
+                ));
+            };
+            fields.insert(
+                TWIN_CONFIG_KEY.to_string(),
+                serde_json::Value::String(suggestion.definition.service.clone()),
+            );
+            node.apply_config_update(&config);
+        }
+    }
+
+    universe.add(suggestion.definition.clone());
+    Ok(())
+}
+
+/// The twin service a node is bound to, if any.
+#[must_use]
+pub fn bound_twin(node: &Node) -> Option<&str> {
+    node.config
+        .get(TWIN_CONFIG_KEY)
+        .and_then(serde_json::Value::as_str)
+}
+
+fn call_target(node: &Node) -> Option<String> {
+    let field = match node.node {
+        WorkflowNode::ServiceCall(_) => "service",
+        WorkflowNode::HttpCall(_) => "url",
+        _ => return None,
+    };
+    node.config
+        .get(field)
+        .and_then(serde_json::Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+        .map(str::to_lowercase)
+}
+
+fn targets_service(target: &str, service: &str) -> bool {
+    let service = service.to_lowercase();
+    target == service
+        || target
+            .split(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '-' || ch == '_'))
+            .any(|segment| segment == service)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::{
+        bind_twin, bound_twin, suggest_twins, TwinUniverse, DEFAULT_TWIN_BASE_URL, DEFAULT_UNIVERSE,
+    };
+    use crate::graph::Workflow;
+    use crate::linter::SystemDependency;
+
+    fn dependency(service: &str, twin_available: bool) -> SystemDependency {
+        SystemDependency {
+            service: service.to_string(),
+            purpose: format!("{service} purpose"),
+            twin_available,
+        }
+    }
+
+    fn workflow_with_call(node_type: &str, field: &str, value: &str) -> Workflow {
+        let mut workflow = Workflow::new();
+        let id = workflow.add_node(node_type, 0.0, 0.0);
+        if let Some(node) = workflow.nodes.iter_mut().find(|node| node.id == id) {
+            node.config = serde_json::json!({ field: value });
+        }
+        workflow
+    }
+
+    #[test]
+    fn given_calls_to_twinned_services_when_suggesting_then_each_is_proposed() {
+        let mut workflow = workflow_with_call("service-call", "service", "Payments");
+        let http = workflow.add_node("http-request", 200.0, 0.0);
+        if let Some(node) = workflow.nodes.iter_mut().find(|node| node.id == http) {
+            node.config = serde_json::json!({ "url": "https://ledger.internal/v1/entries" });
+        }
+
+        let suggestions = suggest_twins(
+            &workflow,
+            &[dependency("payments", true), dependency("ledger", true)],
+            DEFAULT_TWIN_BASE_URL,
+        );
+
+        let services = suggestions
+            .iter()
+            .map(|suggestion| suggestion.definition.service.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(services, vec!["payments", "ledger"]);
+        assert_eq!(
+            suggestions[0].definition.endpoint,
+            "http://127.0.0.1:9900/twins/payments"
+        );
+    }
+
+    #[test]
+    fn given_dependency_without_twin_when_suggesting_then_nothing_is_proposed() {
+        let workflow = workflow_with_call("service-call", "service", "payments");
+
+        let suggestions = suggest_twins(
+            &workflow,
+            &[dependency("payments", false)],
+            DEFAULT_TWIN_BASE_URL,
+        );
+
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn given_url_that_only_contains_service_as_substring_when_suggesting_then_it_is_not_matched() {
+        let workflow = workflow_with_call("http-call", "url", "https://paymentsgateway.io/charge");
+
+        let suggestions = suggest_twins(
+            &workflow,
+            &[dependency("payments", true)],
+            DEFAULT_TWIN_BASE_URL,
+        );
+
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn given_suggestion_when_binding_then_node_and_universe_are_updated_once() {
+        let mut workflow = workflow_with_call("service-call", "service", "payments");
+        let dependencies = [dependency("payments", true)];
+        let suggestion = suggest_twins(&workflow, &dependencies, DEFAULT_TWIN_BASE_URL)
+            .pop()
+            .unwrap();
+        let mut universe = TwinUniverse::new(DEFAULT_UNIVERSE);
+
+        bind_twin(&mut workflow, &mut universe, &suggestion).unwrap();
+        bind_twin(&mut workflow, &mut universe, &suggestion).unwrap();
+
+        assert_eq!(bound_twin(&workflow.nodes[0]), Some("payments"));
+        assert_eq!(workflow.nodes[0].config["service"], "payments");
+        assert_eq!(universe.twins.len(), 1);
+        assert_eq!(
+            universe.endpoints().get("payments").map(String::as_str),
+            Some("http://127.0.0.1:9900/twins/payments")
+        );
+        assert!(suggest_twins(&workflow, &dependencies, DEFAULT_TWIN_BASE_URL).is_empty());
+    }
+
+    #[test]
+    fn given_node_bound_to_other_twin_when_binding_then_error_is_returned() {
+        let mut workflow = workflow_with_call("service-call", "service", "payments");
+        let suggestion = suggest_twins(
+            &workflow,
+            &[dependency("payments", true)],
+            DEFAULT_TWIN_BASE_URL,
+        )
+        .pop()
+        .unwrap();
+        workflow.nodes[0].config["twin"] = serde_json::json!("billing");
+        let mut universe = TwinUniverse::new(DEFAULT_UNIVERSE);
+
+        let result = bind_twin(&mut workflow, &mut universe, &suggestion);
+
+        assert!(result.is_err());
+        assert!(universe.twins.is_empty());
+    }
+
+    #[test]
+    fn given_non_object_config_when_binding_then_error_is_returned_and_config_kept() {
+        let mut workflow = workflow_with_call("service-call", "service", "payments");
+        let suggestion = suggest_twins(
+            &workflow,
+            &[dependency("payments", true)],
+            DEFAULT_TWIN_BASE_URL,
+        )
+        .pop()
+        .unwrap();
+        workflow.nodes[0].config = serde_json::json!(["payments"]);
+        let mut universe = TwinUniverse::new(DEFAULT_UNIVERSE);
+
+        let result = bind_twin(&mut workflow, &mut universe, &suggestion);
+
+        assert!(result.is_err());
+        assert_eq!(workflow.nodes[0].config, serde_json::json!(["payments"]));
+        assert!(universe.twins.is_empty());
+    }
+}
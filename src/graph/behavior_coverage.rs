@@ -0,0 +1,325 @@
+//! Which spec behaviors a canvas workflow represents.
+//!
+//! Nodes claim behaviors through a `behavior_refs` array in their config (a
+//! single `behavior_ref` string is accepted too, matching scenario files).
+//! Coverage compares those claims with the behaviors declared in a spec.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{Node, NodeId, Workflow};
+
+pub const BEHAVIOR_REFS_CONFIG_KEY: &str = "behavior_refs";
+pub const BEHAVIOR_REF_CONFIG_KEY: &str = "behavior_ref";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecBehavior {
+    pub id: String,
+    pub description: String,
+}
+
+/// The behavior list of one spec, as much as the editor needs of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecBehaviors {
+    pub spec_id: String,
+    pub behaviors: Vec<SpecBehavior>,
+}
+
+impl SpecBehaviors {
+    /// Read `specification.identity.id` and `specification.behaviors` from a
+    /// spec document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if the text is not YAML or lacks those fields.
+    pub fn from_yaml(text: &str) -> Result<Self, String> {
+        let yaml = serde_yaml::from_str::<serde_yaml::Value>(text)
+            .map_err(|err| format!("Invalid spec YAML: {err}"))?;
+        let specification = yaml
+            .get("specification")
+            .ok_or_else(|| "Missing `specification`".to_string())?;
+        let spec_id = specification
+            .get("identity")
+            .and_then(|identity| identity.get("id"))
+            .and_then(serde_yaml::Value::as_str)
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .ok_or_else(|| "Missing `specification.identity.id`".to_string())?
+            .to_string();
+        let behaviors = specification
+            .get("behaviors")
+            .and_then(serde_yaml::Value::as_sequence)
+            .ok_or_else(|| "`specification.behaviors` must be a list".to_string())?
+            .iter()
+            .map(|behavior| {
+                let id = behavior
+                    .get("id")
+                    .and_then(serde_yaml::Value::as_str)
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .ok_or_else(|| "Every behavior needs an `id`".to_string())?;
+                Ok(SpecBehavior {
+                    id: id.to_string(),
+                    description: behavior
+                        .get("description")
+                        .and_then(serde_yaml::Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self { spec_id, behaviors })
+    }
+}
+
+/// Behavior ids a node claims, in config order and without duplicates.
+#[must_use]
+pub fn node_behavior_refs(node: &Node) -> Vec<String> {
+    let listed = node
+        .config
+        .get(BEHAVIOR_REFS_CONFIG_KEY)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+    let single = node
+        .config
+        .get(BEHAVIOR_REF_CONFIG_KEY)
+        .and_then(Value::as_str);
+
+    listed
+        .chain(single)
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .fold(Vec::new(), |mut refs, id| {
+            if !refs.iter().any(|existing| existing == id) {
+                refs.push(id.to_string());
+            }
+            refs
+        })
+}
+
+fn write_behavior_refs(node: &mut Node, refs: &[String]) {
+    if !node.config.is_object() {
+        node.config = serde_json::json!({});
+    }
+    if let Some(config) = node.config.as_object_mut() {
+        config.remove(BEHAVIOR_REF_CONFIG_KEY);
+        if refs.is_empty() {
+            config.remove(BEHAVIOR_REFS_CONFIG_KEY);
+        } else {
+            config.insert(
+                BEHAVIOR_REFS_CONFIG_KEY.to_string(),
+                Value::Array(refs.iter().cloned().map(Value::String).collect()),
+            );
+        }
+    }
+}
+
+/// Add `behavior_id` to the node's claims. Returns `false` if already there.
+pub fn link_behavior(node: &mut Node, behavior_id: &str) -> bool {
+    let mut refs = node_behavior_refs(node);
+    if refs.iter().any(|existing| existing == behavior_id) {
+        return false;
+    }
+    refs.push(behavior_id.to_string());
+    write_behavior_refs(node, &refs);
+    true
+}
+
+/// Remove `behavior_id` from the node's claims. Returns `false` if absent.
+pub fn unlink_behavior(node: &mut Node, behavior_id: &str) -> bool {
+    let mut refs = node_behavior_refs(node);
+    let before = refs.len();
+    refs.retain(|existing| existing != behavior_id);
+    if refs.len() == before {
+        return false;
+    }
+    write_behavior_refs(node, &refs);
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoveredBehavior {
+    pub behavior: SpecBehavior,
+    pub nodes: Vec<NodeId>,
+}
+
+/// A node claiming a behavior the spec does not declare.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnknownBehaviorRef {
+    pub node_id: NodeId,
+    pub behavior_ref: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BehaviorCoverage {
+    /// In spec order.
+    pub covered: Vec<CoveredBehavior>,
+    /// In spec order; the editor's to-do list.
+    pub uncovered: Vec<SpecBehavior>,
+    pub unknown_refs: Vec<UnknownBehaviorRef>,
+}
+
+impl BehaviorCoverage {
+    #[must_use]
+    pub fn compute(workflow: &Workflow, spec: &SpecBehaviors) -> Self {
+        let mut claims: BTreeMap<&str, Vec<NodeId>> = BTreeMap::new();
+        let mut unknown_refs = Vec::new();
+        let node_refs = workflow
+            .nodes
+            .iter()
+            .map(|node| (node.id, node_behavior_refs(node)))
+            .collect::<Vec<_>>();
+
+        for (node_id, refs) in &node_refs {
+            for behavior_ref in refs {
+                if spec
+                    .behaviors
+                    .iter()
+                    .any(|behavior| &behavior.id == behavior_ref)
+                {
+                    claims.entry(behavior_ref).or_default().push(*node_id);
+                } else {
+                    unknown_refs.push(UnknownBehaviorRef {
+                        node_id: *node_id,
+                        behavior_ref: behavior_ref.clone(),
+                    });
+                }
+            }
+        }
+
+        let (covered, uncovered) = spec.behaviors.iter().fold(
+            (Vec::new(), Vec::new()),
+            |(mut covered, mut uncovered), behavior| {
+                match claims.get(behavior.id.as_str()) {
+                    Some(nodes) => covered.push(CoveredBehavior {
+                        behavior: behavior.clone(),
+                        nodes: nodes.clone(),
+                    }),
+                    None => uncovered.push(behavior.clone()),
+                }
+                (covered, uncovered)
+            },
+        );
+
+        Self {
+            covered,
+            uncovered,
+            unknown_refs,
+        }
+    }
+
+    #[must_use]
+    pub const fn total(&self) -> usize {
+        self.covered.len() + self.uncovered.len()
+    }
+
+    #[must_use]
+    pub fn percentage(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            #[allow(clippy::cast_precision_loss)]
+            total => self.covered.len() as f64 / total as f64 * 100.0,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{
+        link_behavior, node_behavior_refs, unlink_behavior, BehaviorCoverage, SpecBehaviors,
+        BEHAVIOR_REFS_CONFIG_KEY,
+    };
+    use crate::graph::Workflow;
+
+    const SPEC: &str = r"
+specification:
+  identity:
+    id: checkout
+  behaviors:
+    - id: place-order
+      description: A customer places an order.
+    - id: cancel-order
+      description: A customer cancels an order.
+    - id: refund-order
+";
+
+    #[test]
+    fn given_spec_yaml_when_parsing_then_behaviors_keep_spec_order() {
+        let spec = SpecBehaviors::from_yaml(SPEC).unwrap();
+
+        assert_eq!(spec.spec_id, "checkout");
+        let ids = spec
+            .behaviors
+            .iter()
+            .map(|behavior| behavior.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["place-order", "cancel-order", "refund-order"]);
+        assert!(spec.behaviors[2].description.is_empty());
+    }
+
+    #[test]
+    fn given_spec_without_behaviors_when_parsing_then_it_is_rejected() {
+        let result = SpecBehaviors::from_yaml("specification:\n  identity:\n    id: x\n");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn given_annotated_nodes_when_computing_then_uncovered_behaviors_form_the_todo_list() {
+        let spec = SpecBehaviors::from_yaml(SPEC).unwrap();
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let step = workflow.add_node("run", 200.0, 0.0);
+        workflow.nodes[0].config = serde_json::json!({ "behavior_ref": "place-order" });
+        assert!(link_behavior(&mut workflow.nodes[1], "place-order"));
+        assert!(link_behavior(&mut workflow.nodes[1], "ship-order"));
+
+        let coverage = BehaviorCoverage::compute(&workflow, &spec);
+
+        assert_eq!(coverage.covered.len(), 1);
+        assert_eq!(coverage.covered[0].nodes, vec![entry, step]);
+        let todo = coverage
+            .uncovered
+            .iter()
+            .map(|behavior| behavior.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(todo, vec!["cancel-order", "refund-order"]);
+        assert_eq!(coverage.unknown_refs.len(), 1);
+        assert_eq!(coverage.unknown_refs[0].behavior_ref, "ship-order");
+        assert!((coverage.percentage() - 100.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn given_linked_node_when_linking_again_and_unlinking_then_config_stays_consistent() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 0.0, 0.0);
+        let node = &mut workflow.nodes[0];
+        node.config = serde_json::json!({ "behavior_ref": "place-order", "name": "step" });
+
+        assert!(!link_behavior(node, "place-order"));
+        assert!(link_behavior(node, "cancel-order"));
+        assert_eq!(
+            node.config[BEHAVIOR_REFS_CONFIG_KEY],
+            serde_json::json!(["place-order", "cancel-order"])
+        );
+        assert!(node.config.get("behavior_ref").is_none());
+
+        assert!(unlink_behavior(node, "place-order"));
+        assert!(unlink_behavior(node, "cancel-order"));
+        assert!(!unlink_behavior(node, "cancel-order"));
+        assert!(node_behavior_refs(node).is_empty());
+        assert!(node.config.get(BEHAVIOR_REFS_CONFIG_KEY).is_none());
+        assert_eq!(node.config["name"], "step");
+    }
+}
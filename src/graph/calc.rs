@@ -127,15 +127,37 @@ pub fn find_safe_position(
     (current_x, current_y)
 }
 
+/// Grid that dragged nodes snap to when no canvas preference overrides it.
+pub const DEFAULT_SNAP_GRID: f32 = 10.0;
+
 #[must_use]
 pub fn update_node_position(current_x: f32, current_y: f32, dx: f32, dy: f32) -> (f32, f32) {
+    snap_position(current_x, current_y, dx, dy, Some(DEFAULT_SNAP_GRID))
+}
+
+/// Moves a position by a delta, rounding to `grid` when snapping is on.
+#[must_use]
+pub fn snap_position(
+    current_x: f32,
+    current_y: f32,
+    dx: f32,
+    dy: f32,
+    grid: Option<f32>,
+) -> (f32, f32) {
     // Safety check: if any value is NaN or infinite, don't update
     if !dx.is_finite() || !dy.is_finite() || !current_x.is_finite() || !current_y.is_finite() {
         return (current_x, current_y);
     }
 
-    let new_x = ((current_x + dx) / 10.0).round() * 10.0;
-    let new_y = ((current_y + dy) / 10.0).round() * 10.0;
+    let (new_x, new_y) = grid.filter(|size| size.is_finite() && *size > 0.0).map_or(
+        (current_x + dx, current_y + dy),
+        |size| {
+            (
+                ((current_x + dx) / size).round() * size,
+                ((current_y + dy) / size).round() * size,
+            )
+        },
+    );
 
     // Additional safety: clamp to reasonable bounds
     let new_x = new_x.clamp(-100_000.0, 100_000.0);
@@ -165,7 +187,8 @@ pub fn calculate_rect_size(rect: (f32, f32, f32, f32)) -> (f32, f32) {
 )]
 mod tests {
     use super::{
-        calculate_fit_view, calculate_pan_offset, calculate_zoom_delta, update_node_position,
+        calculate_fit_view, calculate_pan_offset, calculate_zoom_delta, snap_position,
+        update_node_position,
     };
 
     #[test]
@@ -182,6 +205,19 @@ mod tests {
         assert_eq!((x, y), (420.0, 240.0));
     }
 
+    #[test]
+    fn given_custom_or_disabled_grid_when_snapping_position_then_grid_is_honoured() {
+        assert_eq!(
+            snap_position(350.0, 170.0, 6.0, -4.0, Some(25.0)),
+            (350.0, 175.0)
+        );
+        assert_eq!(snap_position(350.0, 170.0, 6.0, -4.0, None), (356.0, 166.0));
+        assert_eq!(
+            snap_position(350.0, 170.0, 6.0, -4.0, Some(0.0)),
+            (356.0, 166.0)
+        );
+    }
+
     #[test]
     fn given_non_finite_zoom_inputs_when_calculating_zoom_delta_then_result_is_deterministic() {
         assert_eq!(calculate_zoom_delta(f32::NAN, 1.2), 1.2);
@@ -0,0 +1,635 @@
+//! Merge-safe collaborative editing of one workflow.
+//!
+//! Each replica turns its edits into [`CollabOp`]s stamped with a Lamport
+//! clock and broadcasts them instead of writing the whole workflow. Node
+//! positions, names and configs are last-writer-wins registers ordered by
+//! stamp, and removing a node or connection leaves a tombstone so a
+//! concurrent edit cannot bring it back. Replicas that have applied the same
+//! operations hold the same nodes and connections whatever order they
+//! arrived in, provided each peer's own operations arrive in the order it
+//! made them, as they do through a [`CollabSession`].
+
+mod presence;
+#[cfg(all(feature = "collab", not(target_arch = "wasm32")))]
+mod relay;
+mod session;
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::redaction::{RedactionPolicy, REDACTED};
+use super::{Connection, Node, NodeId, Workflow};
+
+pub use presence::{peer_color, Presence, PRESENCE_COLORS};
+#[cfg(all(feature = "collab", not(target_arch = "wasm32")))]
+pub use relay::serve_relay;
+pub use session::{CollabMessage, CollabSession, Outgoing, Recipients};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PeerId(pub String);
+
+impl PeerId {
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+}
+
+impl std::fmt::Display for PeerId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Lamport timestamp of an operation; concurrent stamps order by peer id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Stamp {
+    pub counter: u64,
+    pub peer: PeerId,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum CollabOp {
+    AddNode { node: Box<Node> },
+    RemoveNode { id: NodeId },
+    MoveNode { id: NodeId, x: f32, y: f32 },
+    RenameNode { id: NodeId, name: String },
+    SetConfig { id: NodeId, config: Value },
+    Connect { connection: Connection },
+    Disconnect { id: Uuid },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StampedOp {
+    pub stamp: Stamp,
+    pub op: CollabOp,
+}
+
+impl StampedOp {
+    /// This operation with secrets in node configs redacted, as it may be
+    /// sent to the relay. Peers receiving a redacted config keep their own
+    /// copy of each secret; see [`CollabDocument::apply`].
+    #[must_use]
+    pub fn redacted(&self) -> Self {
+        let policy = RedactionPolicy::default();
+        let op = match &self.op {
+            CollabOp::AddNode { node } => {
+                let mut node = node.clone();
+                node.config = policy.redact_config(&node.config);
+                CollabOp::AddNode { node }
+            }
+            CollabOp::SetConfig { id, config } => CollabOp::SetConfig {
+                id: *id,
+                config: policy.redact_config(config),
+            },
+            op => op.clone(),
+        };
+        Self {
+            stamp: self.stamp.clone(),
+            op,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Field {
+    Position,
+    Name,
+    Config,
+}
+
+/// One peer's replica of a shared workflow.
+#[derive(Debug, Clone)]
+pub struct CollabDocument {
+    peer: PeerId,
+    clock: u64,
+    workflow: Workflow,
+    /// Stamps of nodes and connections added through operations; those in
+    /// the base workflow have none and sort first.
+    node_stamps: HashMap<NodeId, Stamp>,
+    connection_stamps: HashMap<Uuid, Stamp>,
+    registers: HashMap<(NodeId, Field), Stamp>,
+    removed_nodes: HashSet<NodeId>,
+    removed_connections: HashSet<Uuid>,
+}
+
+impl CollabDocument {
+    /// A replica starting from `base`, which every peer must share.
+    #[must_use]
+    pub fn new(peer: PeerId, base: Workflow) -> Self {
+        Self {
+            peer,
+            clock: 0,
+            workflow: base,
+            node_stamps: HashMap::new(),
+            connection_stamps: HashMap::new(),
+            registers: HashMap::new(),
+            removed_nodes: HashSet::new(),
+            removed_connections: HashSet::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn peer(&self) -> &PeerId {
+        &self.peer
+    }
+
+    #[must_use]
+    pub const fn workflow(&self) -> &Workflow {
+        &self.workflow
+    }
+
+    /// Bring `target`, the editor's own copy, in line with the shared nodes
+    /// and connections. Nodes it already has keep their selection and run
+    /// state; everything else outside the shared fields is left alone.
+    pub fn sync_into(&self, target: &mut Workflow) {
+        let mut local = std::mem::take(&mut target.nodes)
+            .into_iter()
+            .map(|node| (node.id, node))
+            .collect::<HashMap<_, _>>();
+        target.nodes = self
+            .workflow
+            .nodes
+            .iter()
+            .map(|shared| {
+                let Some(mut node) = local.remove(&shared.id) else {
+                    return shared.clone();
+                };
+                node.x = shared.x;
+                node.y = shared.y;
+                node.name.clone_from(&shared.name);
+                if node.config != shared.config {
+                    node.apply_config_update(&shared.config);
+                }
+                node
+            })
+            .collect();
+        target.connections.clone_from(&self.workflow.connections);
+    }
+
+    /// Apply a local edit and return it stamped, ready to broadcast.
+    pub fn edit(&mut self, op: CollabOp) -> StampedOp {
+        self.clock += 1;
+        let stamped = StampedOp {
+            stamp: Stamp {
+                counter: self.clock,
+                peer: self.peer.clone(),
+            },
+            op,
+        };
+        self.integrate(&stamped);
+        stamped
+    }
+
+    /// Turn a whole-workflow edit into operations, apply them and return
+    /// them for broadcast.
+    pub fn edit_to(&mut self, edited: &Workflow) -> Vec<StampedOp> {
+        ops_between(&self.workflow, edited)
+            .into_iter()
+            .map(|op| self.edit(op))
+            .collect()
+    }
+
+    /// Apply an operation from another peer. Returns whether it changed the
+    /// workflow; stale, duplicate and tombstoned operations do not.
+    pub fn apply(&mut self, stamped: &StampedOp) -> bool {
+        self.clock = self.clock.max(stamped.stamp.counter);
+        self.integrate(stamped)
+    }
+
+    fn integrate(&mut self, stamped: &StampedOp) -> bool {
+        let stamp = &stamped.stamp;
+        match &stamped.op {
+            CollabOp::AddNode { node } => self.add_node(node, stamp),
+            CollabOp::RemoveNode { id } => {
+                let known = self.removed_nodes.insert(*id);
+                self.workflow.remove_node(*id);
+                known
+            }
+            CollabOp::MoveNode { id, x, y } => self.write(*id, Field::Position, stamp, |node| {
+                node.x = *x;
+                node.y = *y;
+            }),
+            CollabOp::RenameNode { id, name } => {
+                self.write(*id, Field::Name, stamp, |node| node.name.clone_from(name))
+            }
+            CollabOp::SetConfig { id, config } => self.write(*id, Field::Config, stamp, |node| {
+                let config = keep_secrets(&node.config, config);
+                node.apply_config_update(&config);
+            }),
+            CollabOp::Connect { connection } => self.connect(connection, stamp),
+            CollabOp::Disconnect { id } => {
+                self.removed_connections.insert(*id);
+                let before = self.workflow.connections.len();
+                self.workflow
+                    .connections
+                    .retain(|existing| existing.id != *id);
+                before != self.workflow.connections.len()
+            }
+        }
+    }
+
+    fn add_node(&mut self, node: &Node, stamp: &Stamp) -> bool {
+        if self.removed_nodes.contains(&node.id)
+            || self
+                .workflow
+                .nodes
+                .iter()
+                .any(|existing| existing.id == node.id)
+        {
+            return false;
+        }
+        let mut node = node.clone();
+        // The typed node is not serialized; rebuild it from the config.
+        let config = node.config.clone();
+        node.apply_config_update(&config);
+        // Added nodes follow the base nodes in stamp order.
+        let index = self
+            .workflow
+            .nodes
+            .iter()
+            .position(|existing| {
+                self.node_stamps
+                    .get(&existing.id)
+                    .is_some_and(|existing| existing > stamp)
+            })
+            .unwrap_or(self.workflow.nodes.len());
+        for field in [Field::Position, Field::Name, Field::Config] {
+            self.registers.insert((node.id, field), stamp.clone());
+        }
+        self.node_stamps.insert(node.id, stamp.clone());
+        self.workflow.nodes.insert(index, node);
+        true
+    }
+
+    fn connect(&mut self, connection: &Connection, stamp: &Stamp) -> bool {
+        if self.removed_connections.contains(&connection.id)
+            || self.removed_nodes.contains(&connection.source)
+            || self.removed_nodes.contains(&connection.target)
+            || self
+                .workflow
+                .connections
+                .iter()
+                .any(|existing| existing.id == connection.id)
+        {
+            return false;
+        }
+        // Two peers wiring the same ports concurrently keep the earlier edge.
+        if let Some(position) = self.workflow.connections.iter().position(|existing| {
+            existing.source == connection.source
+                && existing.target == connection.target
+                && existing.source_port == connection.source_port
+                && existing.target_port == connection.target_port
+        }) {
+            let existing = &self.workflow.connections[position];
+            if self
+                .connection_stamps
+                .get(&existing.id)
+                .is_none_or(|existing_stamp| existing_stamp < stamp)
+            {
+                return false;
+            }
+            self.workflow.connections.remove(position);
+        }
+        let index = self
+            .workflow
+            .connections
+            .iter()
+            .position(|existing| {
+                self.connection_stamps
+                    .get(&existing.id)
+                    .is_some_and(|existing| existing > stamp)
+            })
+            .unwrap_or(self.workflow.connections.len());
+        self.connection_stamps.insert(connection.id, stamp.clone());
+        self.workflow.connections.insert(index, connection.clone());
+        true
+    }
+
+    fn write(
+        &mut self,
+        id: NodeId,
+        field: Field,
+        stamp: &Stamp,
+        update: impl FnOnce(&mut Node),
+    ) -> bool {
+        if self
+            .registers
+            .get(&(id, field))
+            .is_some_and(|current| current >= stamp)
+        {
+            return false;
+        }
+        let Some(node) = self.workflow.nodes.iter_mut().find(|node| node.id == id) else {
+            return false;
+        };
+        update(node);
+        self.registers.insert((id, field), stamp.clone());
+        true
+    }
+}
+
+/// `incoming` with each redacted value replaced by the one in `current`,
+/// so a config edited by a peer that never saw a secret does not erase it.
+fn keep_secrets(current: &Value, incoming: &Value) -> Value {
+    match (current, incoming) {
+        (Value::Object(current), Value::Object(incoming)) => incoming
+            .iter()
+            .map(|(key, value)| {
+                let kept = current
+                    .get(key)
+                    .map_or_else(|| value.clone(), |local| keep_secrets(local, value));
+                (key.clone(), kept)
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        (current, Value::String(text)) if text == REDACTED => current.clone(),
+        (_, incoming) => incoming.clone(),
+    }
+}
+
+/// Operations that turn `old` into `new`, matching nodes and connections by
+/// id.
+#[must_use]
+pub fn ops_between(old: &Workflow, new: &Workflow) -> Vec<CollabOp> {
+    let old_nodes = old
+        .nodes
+        .iter()
+        .map(|node| (node.id, node))
+        .collect::<HashMap<_, _>>();
+    let new_ids = new.nodes.iter().map(|node| node.id).collect::<HashSet<_>>();
+    let old_connections = old
+        .connections
+        .iter()
+        .map(|connection| connection.id)
+        .collect::<HashSet<_>>();
+    let new_connections = new
+        .connections
+        .iter()
+        .map(|connection| connection.id)
+        .collect::<HashSet<_>>();
+
+    let mut ops = old
+        .connections
+        .iter()
+        .filter(|connection| !new_connections.contains(&connection.id))
+        .map(|connection| CollabOp::Disconnect { id: connection.id })
+        .collect::<Vec<_>>();
+    ops.extend(
+        old.nodes
+            .iter()
+            .filter(|node| !new_ids.contains(&node.id))
+            .map(|node| CollabOp::RemoveNode { id: node.id }),
+    );
+    for node in &new.nodes {
+        let Some(previous) = old_nodes.get(&node.id) else {
+            ops.push(CollabOp::AddNode {
+                node: Box::new(node.clone()),
+            });
+            continue;
+        };
+        #[allow(clippy::float_cmp)]
+        if previous.x != node.x || previous.y != node.y {
+            ops.push(CollabOp::MoveNode {
+                id: node.id,
+                x: node.x,
+                y: node.y,
+            });
+        }
+        if previous.name != node.name {
+            ops.push(CollabOp::RenameNode {
+                id: node.id,
+                name: node.name.clone(),
+            });
+        }
+        if previous.config != node.config {
+            ops.push(CollabOp::SetConfig {
+                id: node.id,
+                config: node.config.clone(),
+            });
+        }
+    }
+    ops.extend(
+        new.connections
+            .iter()
+            .filter(|connection| !old_connections.contains(&connection.id))
+            .map(|connection| CollabOp::Connect {
+                connection: connection.clone(),
+            }),
+    );
+    ops
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::PortName;
+
+    fn base() -> (Workflow, NodeId, NodeId) {
+        let mut workflow = Workflow::new();
+        let handler = workflow.add_node("http-handler", 0.0, 0.0);
+        let run = workflow.add_node("run", 0.0, 200.0);
+        (workflow, handler, run)
+    }
+
+    fn replicas(workflow: &Workflow) -> (CollabDocument, CollabDocument) {
+        (
+            CollabDocument::new(PeerId::new("alice"), workflow.clone()),
+            CollabDocument::new(PeerId::new("bob"), workflow.clone()),
+        )
+    }
+
+    fn position(document: &CollabDocument, id: NodeId) -> (f32, f32) {
+        let node = document
+            .workflow()
+            .nodes
+            .iter()
+            .find(|node| node.id == id)
+            .unwrap();
+        (node.x, node.y)
+    }
+
+    #[test]
+    fn given_concurrent_moves_when_exchanged_then_replicas_converge_on_the_later_stamp() {
+        let (workflow, handler, _) = base();
+        let (mut alice, mut bob) = replicas(&workflow);
+
+        let from_alice = alice.edit(CollabOp::MoveNode {
+            id: handler,
+            x: 10.0,
+            y: 10.0,
+        });
+        let from_bob = bob.edit(CollabOp::MoveNode {
+            id: handler,
+            x: 50.0,
+            y: 50.0,
+        });
+        assert!(alice.apply(&from_bob));
+        assert!(!bob.apply(&from_alice));
+
+        // Equal counters: "bob" > "alice", so bob's move wins everywhere.
+        assert_eq!(position(&alice, handler), (50.0, 50.0));
+        assert_eq!(position(&bob, handler), (50.0, 50.0));
+    }
+
+    #[test]
+    fn given_concurrent_adds_when_applied_in_different_orders_then_node_order_matches() {
+        let (workflow, handler, run) = base();
+        let (mut alice, mut bob) = replicas(&workflow);
+        let mut alice_edit = workflow.clone();
+        alice_edit.add_node("set-state", 300.0, 0.0);
+        let mut bob_edit = workflow;
+        bob_edit.add_node("send-message", 300.0, 200.0);
+        let main = PortName("main".to_string());
+        bob_edit.add_connection(handler, run, &main, &main).unwrap();
+
+        let from_alice = alice.edit_to(&alice_edit);
+        let from_bob = bob.edit_to(&bob_edit);
+        for op in &from_bob {
+            alice.apply(op);
+        }
+        for op in &from_alice {
+            bob.apply(op);
+        }
+
+        assert_eq!(alice.workflow().nodes, bob.workflow().nodes);
+        assert_eq!(alice.workflow().connections, bob.workflow().connections);
+        assert_eq!(alice.workflow().nodes.len(), 4);
+        assert_eq!(alice.workflow().connections.len(), 1);
+    }
+
+    #[test]
+    fn given_node_removed_while_edited_elsewhere_when_merged_then_node_stays_removed() {
+        let (workflow, handler, run) = base();
+        let (mut alice, mut bob) = replicas(&workflow);
+        let main = PortName("main".to_string());
+
+        let removal = alice.edit(CollabOp::RemoveNode { id: run });
+        let rename = bob.edit(CollabOp::RenameNode {
+            id: run,
+            name: "Charge card".to_string(),
+        });
+        let connect = bob.edit(CollabOp::Connect {
+            connection: Connection {
+                id: Uuid::new_v4(),
+                source: handler,
+                target: run,
+                source_port: main.clone(),
+                target_port: main,
+            },
+        });
+        assert!(!alice.apply(&rename));
+        assert!(!alice.apply(&connect));
+        assert!(bob.apply(&removal));
+        assert!(!bob.apply(&removal));
+
+        for document in [&alice, &bob] {
+            assert_eq!(document.workflow().nodes.len(), 1);
+            assert!(document.workflow().connections.is_empty());
+        }
+    }
+
+    #[test]
+    fn given_same_edge_wired_by_both_peers_when_merged_then_one_connection_is_kept() {
+        let (workflow, handler, run) = base();
+        let (mut alice, mut bob) = replicas(&workflow);
+        let main = PortName("main".to_string());
+        let edge = |id| CollabOp::Connect {
+            connection: Connection {
+                id,
+                source: handler,
+                target: run,
+                source_port: main.clone(),
+                target_port: main.clone(),
+            },
+        };
+
+        let from_alice = alice.edit(edge(Uuid::new_v4()));
+        let from_bob = bob.edit(edge(Uuid::new_v4()));
+        alice.apply(&from_bob);
+        bob.apply(&from_alice);
+
+        assert_eq!(alice.workflow().connections, bob.workflow().connections);
+        assert_eq!(alice.workflow().connections.len(), 1);
+    }
+
+    #[test]
+    fn given_op_when_round_tripped_through_json_then_it_applies_identically() {
+        let (workflow, handler, _) = base();
+        let (mut alice, mut bob) = replicas(&workflow);
+        let op = alice.edit(CollabOp::SetConfig {
+            id: handler,
+            config: serde_json::json!({"path": "/signup", "method": "POST"}),
+        });
+
+        let decoded: StampedOp =
+            serde_json::from_str(&serde_json::to_string(&op).unwrap()).unwrap();
+
+        assert!(bob.apply(&decoded));
+        assert_eq!(alice.workflow().nodes, bob.workflow().nodes);
+    }
+
+    #[test]
+    fn given_remote_edits_when_synced_into_editor_then_local_run_state_is_kept() {
+        let (workflow, handler, run) = base();
+        let (mut alice, mut bob) = replicas(&workflow);
+        let mut editor = workflow;
+        let local = editor
+            .nodes
+            .iter_mut()
+            .find(|node| node.id == handler)
+            .unwrap();
+        local.selected = true;
+        local.last_output = Some(serde_json::json!({"status": 200}));
+
+        let moved = alice.edit(CollabOp::MoveNode {
+            id: handler,
+            x: 120.0,
+            y: 40.0,
+        });
+        let removed = alice.edit(CollabOp::RemoveNode { id: run });
+        bob.apply(&moved);
+        bob.apply(&removed);
+        bob.sync_into(&mut editor);
+
+        assert_eq!(editor.nodes.len(), 1);
+        let synced = &editor.nodes[0];
+        assert_eq!((synced.x, synced.y), (120.0, 40.0));
+        assert!(synced.selected);
+        assert_eq!(synced.last_output, Some(serde_json::json!({"status": 200})));
+        assert!(bob.edit_to(&editor).is_empty());
+    }
+
+    #[test]
+    fn given_secret_config_when_sent_redacted_then_peers_never_see_it_and_keep_their_own() {
+        let (workflow, handler, _) = base();
+        let (mut alice, mut bob) = replicas(&workflow);
+        let with_key = alice.edit(CollabOp::SetConfig {
+            id: handler,
+            config: serde_json::json!({"path": "/signup", "api_key": "sk-live-1234"}),
+        });
+
+        let sent = serde_json::to_string(&with_key.redacted()).unwrap();
+        assert!(!sent.contains("sk-live-1234"));
+        bob.apply(&serde_json::from_str(&sent).unwrap());
+        let renamed_path = bob.edit(CollabOp::SetConfig {
+            id: handler,
+            config: serde_json::json!({"path": "/join", "api_key": REDACTED}),
+        });
+        alice.apply(&renamed_path.redacted());
+
+        let config = &alice.workflow().nodes[0].config;
+        assert_eq!(config["path"], "/join");
+        assert_eq!(config["api_key"], "sk-live-1234");
+    }
+}
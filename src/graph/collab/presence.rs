@@ -0,0 +1,48 @@
+//! Who else is editing, where their cursor is and what they have selected.
+
+use serde::{Deserialize, Serialize};
+
+use super::PeerId;
+use crate::graph::NodeId;
+
+/// Colors handed out to peers, distinct enough to tell selections apart.
+pub const PRESENCE_COLORS: [&str; 8] = [
+    "#e11d48", "#2563eb", "#16a34a", "#d97706", "#9333ea", "#0891b2", "#db2777", "#65a30d",
+];
+
+/// A peer's preferred color, stable across sessions.
+#[must_use]
+pub fn peer_color(peer: &PeerId) -> &'static str {
+    // FNV-1a, so the choice does not depend on the std hasher's seed.
+    let hash = peer
+        .0
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    #[allow(clippy::cast_possible_truncation)]
+    PRESENCE_COLORS[(hash % PRESENCE_COLORS.len() as u64) as usize]
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Presence {
+    pub peer: PeerId,
+    pub display_name: String,
+    pub color: String,
+    /// Canvas coordinates of the peer's pointer.
+    pub cursor: Option<(f32, f32)>,
+    pub selection: Vec<NodeId>,
+}
+
+impl Presence {
+    #[must_use]
+    pub fn new(peer: PeerId, display_name: impl Into<String>) -> Self {
+        Self {
+            color: peer_color(&peer).to_string(),
+            peer,
+            display_name: display_name.into(),
+            cursor: None,
+            selection: Vec::new(),
+        }
+    }
+}
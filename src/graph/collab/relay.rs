@@ -0,0 +1,198 @@
+//! WebSocket transport for [`CollabSession`]s. Each request path is a room
+//! with its own session, so `ws://host:port/signup` and
+//! `ws://host:port/billing` edit different workflows. Rooms live as long as
+//! the relay, so a peer that reconnects finds every operation so far.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+use super::{CollabMessage, CollabSession, Outgoing, PeerId, Recipients};
+use crate::graph::Workflow;
+
+struct Room {
+    session: CollabSession,
+    peers: HashMap<PeerId, UnboundedSender<Message>>,
+}
+
+type Rooms = Arc<Mutex<HashMap<String, Room>>>;
+
+/// Relay collaboration messages between the peers of each room. New rooms
+/// start from `base`; when it is empty, the first peer's seed is used.
+///
+/// # Errors
+/// Returns an error if accepting a connection fails.
+pub async fn serve_relay(listener: TcpListener, base: Workflow) -> std::io::Result<()> {
+    let rooms = Rooms::default();
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let (rooms, base) = (Arc::clone(&rooms), base.clone());
+        tokio::spawn(async move {
+            // A peer dropping mid-handshake is not the relay's problem.
+            let _ = handle(stream, &rooms, base).await;
+        });
+    }
+}
+
+// The handshake callback's error type is fixed by tungstenite.
+#[allow(clippy::result_large_err)]
+async fn handle(
+    stream: TcpStream,
+    rooms: &Rooms,
+    base: Workflow,
+) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+    let mut room_name = String::new();
+    let socket = tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response| {
+        room_name = request.uri().path().trim_matches('/').to_string();
+        Ok::<Response, _>(response)
+    })
+    .await?;
+    let (mut sink, mut stream) = socket.split();
+    let peer = PeerId::new(Uuid::new_v4().to_string());
+    let (sender, mut outbox) = unbounded_channel();
+    if let Ok(mut rooms) = rooms.lock() {
+        rooms
+            .entry(room_name.clone())
+            .or_insert_with(|| Room {
+                session: CollabSession::new(base),
+                peers: HashMap::new(),
+            })
+            .peers
+            .insert(peer.clone(), sender);
+    }
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outbox.recv().await {
+            if sink.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(message)) = stream.next().await {
+        let Message::Text(text) = message else {
+            if message.is_close() {
+                break;
+            }
+            continue;
+        };
+        let Ok(message) = CollabMessage::from_json(&text) else {
+            continue;
+        };
+        with_room(rooms, &room_name, |room| {
+            room.session.handle(&peer, message)
+        });
+    }
+
+    with_room(rooms, &room_name, |room| {
+        room.peers.remove(&peer);
+        room.session.leave(&peer)
+    });
+    writer.abort();
+    Ok(())
+}
+
+/// Run `update` on a room and deliver the messages it returns.
+fn with_room(rooms: &Rooms, name: &str, update: impl FnOnce(&mut Room) -> Vec<Outgoing>) {
+    let Ok(mut rooms) = rooms.lock() else {
+        return;
+    };
+    let Some(room) = rooms.get_mut(name) else {
+        return;
+    };
+    for outgoing in update(room) {
+        let Ok(json) = outgoing.message.to_json() else {
+            continue;
+        };
+        let message = Message::text(json);
+        room.peers
+            .iter()
+            .filter(|(peer, _)| match &outgoing.to {
+                Recipients::Peer(to) => *peer == to,
+                Recipients::AllExcept(from) => *peer != from,
+            })
+            .for_each(|(_, sender)| {
+                let _ = sender.send(message.clone());
+            });
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::collab::{CollabDocument, CollabOp};
+
+    type Client = tokio_tungstenite::WebSocketStream<
+        tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+    >;
+
+    async fn connect(url: &str, display_name: &str) -> Client {
+        let (mut client, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        let join = CollabMessage::Join {
+            display_name: display_name.to_string(),
+            seed: None,
+        };
+        client
+            .send(Message::text(join.to_json().unwrap()))
+            .await
+            .unwrap();
+        client
+    }
+
+    async fn receive(client: &mut Client) -> CollabMessage {
+        loop {
+            let message = client.next().await.unwrap().unwrap();
+            if let Message::Text(text) = message {
+                return CollabMessage::from_json(&text).unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn given_two_peers_in_a_room_when_one_edits_then_the_other_receives_the_op() {
+        let mut base = Workflow::new();
+        let node = base.add_node("run", 0.0, 0.0);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/signup", listener.local_addr().unwrap());
+        tokio::spawn(serve_relay(listener, base));
+
+        let mut alice = connect(&url, "Alice").await;
+        let CollabMessage::Welcome { peer, base, .. } = receive(&mut alice).await else {
+            panic!("expected a welcome");
+        };
+        let mut document = CollabDocument::new(peer, *base);
+        let mut bob = connect(&url, "Bob").await;
+        assert!(matches!(
+            receive(&mut bob).await,
+            CollabMessage::Welcome { .. }
+        ));
+        assert!(matches!(
+            receive(&mut alice).await,
+            CollabMessage::Presence { presence } if presence.display_name == "Bob"
+        ));
+
+        let op = document.edit(CollabOp::MoveNode {
+            id: node,
+            x: 40.0,
+            y: 80.0,
+        });
+        let message = CollabMessage::Op { op: op.clone() };
+        alice
+            .send(Message::text(message.to_json().unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(receive(&mut bob).await, CollabMessage::Op { op });
+
+        alice.close(None).await.unwrap();
+        assert!(matches!(
+            receive(&mut bob).await,
+            CollabMessage::Leave { peer } if peer == *document.peer()
+        ));
+    }
+}
@@ -0,0 +1,305 @@
+//! The relay side of a collaboration session, independent of the socket
+//! that carries it: feed it each peer's messages and send what it returns.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::presence::{peer_color, Presence, PRESENCE_COLORS};
+use super::{PeerId, StampedOp};
+use crate::graph::Workflow;
+
+/// Wire format between peers and the relay, one JSON object per message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CollabMessage {
+    /// Peer → relay, first message on a connection. The first peer into a
+    /// session that has no base yet may offer its own workflow as `seed`.
+    Join {
+        display_name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seed: Option<Box<Workflow>>,
+    },
+    /// Relay → joining peer: the id the relay knows it by, the shared base
+    /// and every operation so far, to replay into a fresh
+    /// [`CollabDocument`](super::CollabDocument).
+    Welcome {
+        peer: PeerId,
+        base: Box<Workflow>,
+        ops: Vec<StampedOp>,
+        peers: Vec<Presence>,
+        color: String,
+    },
+    Op {
+        op: StampedOp,
+    },
+    Presence {
+        presence: Presence,
+    },
+    Leave {
+        peer: PeerId,
+    },
+}
+
+impl CollabMessage {
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// # Errors
+    /// Returns an error if `json` is not a collaboration message.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recipients {
+    Peer(PeerId),
+    AllExcept(PeerId),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outgoing {
+    pub to: Recipients,
+    pub message: CollabMessage,
+}
+
+/// Relay state for one shared workflow. Operations are forwarded in the
+/// order received, which keeps every peer's operations in the order it
+/// made them.
+#[derive(Debug, Clone)]
+pub struct CollabSession {
+    base: Workflow,
+    log: Vec<StampedOp>,
+    peers: BTreeMap<PeerId, Presence>,
+}
+
+impl CollabSession {
+    #[must_use]
+    pub const fn new(base: Workflow) -> Self {
+        Self {
+            base,
+            log: Vec::new(),
+            peers: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn peers(&self) -> Vec<&Presence> {
+        self.peers.values().collect()
+    }
+
+    /// Handle one message from `from` and return what to send where.
+    pub fn handle(&mut self, from: &PeerId, message: CollabMessage) -> Vec<Outgoing> {
+        match message {
+            CollabMessage::Join { display_name, seed } => {
+                if let Some(seed) = seed.filter(|_| self.is_unseeded()) {
+                    self.base = *seed;
+                }
+                self.join(from, display_name)
+            }
+            CollabMessage::Op { op } => {
+                self.log.push(op.clone());
+                vec![broadcast(from, CollabMessage::Op { op })]
+            }
+            CollabMessage::Presence { presence } => {
+                let Some(current) = self.peers.get_mut(from) else {
+                    return Vec::new();
+                };
+                // Peers choose their cursor and selection; the relay keeps
+                // the identity and color it assigned.
+                current.cursor = presence.cursor;
+                current.selection = presence.selection;
+                vec![broadcast(
+                    from,
+                    CollabMessage::Presence {
+                        presence: current.clone(),
+                    },
+                )]
+            }
+            CollabMessage::Leave { .. } => self.leave(from),
+            CollabMessage::Welcome { .. } => Vec::new(),
+        }
+    }
+
+    /// Whether nobody has shaped the workflow yet: the base is empty and no
+    /// operation has been made.
+    const fn is_unseeded(&self) -> bool {
+        self.base.nodes.is_empty() && self.log.is_empty()
+    }
+
+    /// Drop a peer whose connection closed.
+    pub fn leave(&mut self, peer: &PeerId) -> Vec<Outgoing> {
+        if self.peers.remove(peer).is_none() {
+            return Vec::new();
+        }
+        vec![broadcast(peer, CollabMessage::Leave { peer: peer.clone() })]
+    }
+
+    fn join(&mut self, peer: &PeerId, display_name: String) -> Vec<Outgoing> {
+        let mut presence = Presence::new(peer.clone(), display_name);
+        presence.color = self.free_color(peer);
+        let others = self
+            .peers
+            .values()
+            .filter(|other| other.peer != *peer)
+            .cloned()
+            .collect();
+        self.peers.insert(peer.clone(), presence.clone());
+        vec![
+            Outgoing {
+                to: Recipients::Peer(peer.clone()),
+                message: CollabMessage::Welcome {
+                    peer: peer.clone(),
+                    base: Box::new(self.base.clone()),
+                    ops: self.log.clone(),
+                    peers: others,
+                    color: presence.color.clone(),
+                },
+            },
+            broadcast(peer, CollabMessage::Presence { presence }),
+        ]
+    }
+
+    /// The peer's preferred color unless someone else already has it.
+    fn free_color(&self, peer: &PeerId) -> String {
+        let taken = |color: &str| {
+            self.peers
+                .values()
+                .any(|other| other.peer != *peer && other.color == color)
+        };
+        let preferred = peer_color(peer);
+        std::iter::once(preferred)
+            .chain(PRESENCE_COLORS)
+            .find(|color| !taken(color))
+            .unwrap_or(preferred)
+            .to_string()
+    }
+}
+
+fn broadcast(from: &PeerId, message: CollabMessage) -> Outgoing {
+    Outgoing {
+        to: Recipients::AllExcept(from.clone()),
+        message,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::collab::{CollabDocument, CollabOp};
+
+    fn join(session: &mut CollabSession, peer: &PeerId) -> CollabDocument {
+        let replies = session.handle(
+            peer,
+            CollabMessage::from_json(r#"{"type": "join", "display_name": "Someone"}"#).unwrap(),
+        );
+        let CollabMessage::Welcome { base, ops, .. } = &replies[0].message else {
+            panic!("expected a welcome, got {replies:?}");
+        };
+        let mut document = CollabDocument::new(peer.clone(), (**base).clone());
+        for op in ops {
+            document.apply(op);
+        }
+        document
+    }
+
+    #[test]
+    fn given_edits_before_join_when_late_peer_joins_then_replay_matches_the_editor() {
+        let mut base = Workflow::new();
+        let node = base.add_node("run", 0.0, 0.0);
+        let mut session = CollabSession::new(base);
+        let alice = PeerId::new("alice");
+        let mut alice_document = join(&mut session, &alice);
+
+        let op = alice_document.edit(CollabOp::MoveNode {
+            id: node,
+            x: 40.0,
+            y: 80.0,
+        });
+        let forwarded = session.handle(&alice, CollabMessage::Op { op });
+        assert_eq!(forwarded[0].to, Recipients::AllExcept(alice.clone()));
+
+        let bob_document = join(&mut session, &PeerId::new("bob"));
+        assert_eq!(
+            bob_document.workflow().nodes,
+            alice_document.workflow().nodes
+        );
+    }
+
+    #[test]
+    fn given_peers_in_session_when_joining_then_colors_are_distinct_and_presence_is_relayed() {
+        let mut session = CollabSession::new(Workflow::new());
+        let peers = (0..PRESENCE_COLORS.len())
+            .map(|index| PeerId::new(format!("peer-{index}")))
+            .collect::<Vec<_>>();
+        for peer in &peers {
+            join(&mut session, peer);
+        }
+
+        let mut colors = session
+            .peers()
+            .iter()
+            .map(|presence| presence.color.clone())
+            .collect::<Vec<_>>();
+        colors.sort();
+        colors.dedup();
+        assert_eq!(colors.len(), PRESENCE_COLORS.len());
+
+        let mut moved = Presence::new(peers[0].clone(), "Ignored");
+        moved.cursor = Some((12.0, 24.0));
+        moved.color = "#000000".to_string();
+        let relayed = session.handle(&peers[0], CollabMessage::Presence { presence: moved });
+        let CollabMessage::Presence { presence } = &relayed[0].message else {
+            panic!("expected presence, got {relayed:?}");
+        };
+        assert_eq!(presence.cursor, Some((12.0, 24.0)));
+        assert_ne!(presence.color, "#000000");
+
+        let left = session.leave(&peers[0]);
+        assert_eq!(
+            left[0].message,
+            CollabMessage::Leave {
+                peer: peers[0].clone()
+            }
+        );
+        assert_eq!(session.peers().len(), PRESENCE_COLORS.len() - 1);
+    }
+
+    #[test]
+    fn given_seed_when_first_peer_joins_then_it_becomes_the_base_for_later_peers() {
+        let mut seed = Workflow::new();
+        seed.add_node("http-handler", 0.0, 0.0);
+        let mut session = CollabSession::new(Workflow::new());
+        let alice = PeerId::new("alice");
+        let replies = session.handle(
+            &alice,
+            CollabMessage::Join {
+                display_name: "Alice".to_string(),
+                seed: Some(Box::new(seed.clone())),
+            },
+        );
+        let CollabMessage::Welcome { peer, .. } = &replies[0].message else {
+            panic!("expected a welcome, got {replies:?}");
+        };
+        assert_eq!(*peer, alice);
+
+        let mut other = Workflow::new();
+        other.add_node("run", 0.0, 0.0);
+        let replies = session.handle(
+            &PeerId::new("bob"),
+            CollabMessage::Join {
+                display_name: "Bob".to_string(),
+                seed: Some(Box::new(other)),
+            },
+        );
+        let CollabMessage::Welcome { base, .. } = &replies[0].message else {
+            panic!("expected a welcome, got {replies:?}");
+        };
+        assert_eq!(base.nodes, seed.nodes);
+    }
+}
@@ -0,0 +1,294 @@
+//! Workflow compilation: every static analysis behind one step.
+//!
+//! `compile_workflow` runs the graph lint, structural grammar, and node config
+//! passes, tags each diagnostic with the pass that produced it, and applies a
+//! [`SeverityGate`] to decide whether run and export are allowed.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::contract::validate_contract;
+use super::validation::{
+    validate_entry_points, validate_grammar_constraints, validate_orphan_nodes,
+    validate_reachability, validate_unique_node_ids,
+};
+use super::validation_checks::config::validate_node_configs;
+use super::{ValidationIssue, ValidationResult, ValidationSeverity, Workflow};
+
+/// The analysis pass that produced a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DiagnosticSource {
+    GraphLint,
+    Structural,
+    Config,
+}
+
+impl fmt::Display for DiagnosticSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GraphLint => write!(f, "lint"),
+            Self::Structural => write!(f, "structure"),
+            Self::Config => write!(f, "config"),
+        }
+    }
+}
+
+/// Which severities stop a workflow from running or being exported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SeverityGate {
+    /// Errors block; warnings are reported but allowed.
+    #[default]
+    AllowWarnings,
+    /// Both errors and warnings block.
+    DenyWarnings,
+}
+
+impl SeverityGate {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::AllowWarnings => "allow-warnings",
+            Self::DenyWarnings => "deny-warnings",
+        }
+    }
+
+    #[must_use]
+    pub const fn blocks(self, severity: ValidationSeverity) -> bool {
+        match (self, severity) {
+            (_, ValidationSeverity::Error) | (Self::DenyWarnings, ValidationSeverity::Warning) => {
+                true
+            }
+            (Self::AllowWarnings, ValidationSeverity::Warning) => false,
+        }
+    }
+}
+
+impl FromStr for SeverityGate {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "allow-warnings" => Ok(Self::AllowWarnings),
+            "deny-warnings" => Ok(Self::DenyWarnings),
+            other => Err(format!("Unknown severity gate: {other}")),
+        }
+    }
+}
+
+/// A validation issue tagged with the pass that reported it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompileDiagnostic {
+    pub source: DiagnosticSource,
+    pub issue: ValidationIssue,
+}
+
+/// Compact summary of a compile report, suitable for a status indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileStatus {
+    Clean,
+    Warnings,
+    Blocked,
+}
+
+/// The result of compiling a workflow under a given gate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompileReport {
+    pub gate: SeverityGate,
+    pub diagnostics: Vec<CompileDiagnostic>,
+}
+
+impl CompileReport {
+    #[must_use]
+    pub fn error_count(&self) -> usize {
+        self.count(ValidationSeverity::Error)
+    }
+
+    #[must_use]
+    pub fn warning_count(&self) -> usize {
+        self.count(ValidationSeverity::Warning)
+    }
+
+    /// Returns the diagnostics that stop run and export under the current gate.
+    pub fn blocking(&self) -> impl Iterator<Item = &CompileDiagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| self.gate.blocks(diagnostic.issue.severity))
+    }
+
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        self.blocking().next().is_some()
+    }
+
+    #[must_use]
+    pub fn status(&self) -> CompileStatus {
+        if self.is_blocked() {
+            CompileStatus::Blocked
+        } else if self.diagnostics.is_empty() {
+            CompileStatus::Clean
+        } else {
+            CompileStatus::Warnings
+        }
+    }
+
+    /// Flattens the report into a `ValidationResult` for the validation panel.
+    #[must_use]
+    pub fn to_validation_result(&self) -> ValidationResult {
+        ValidationResult::from_issues(
+            self.diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.issue.clone())
+                .collect(),
+        )
+    }
+
+    fn count(&self, severity: ValidationSeverity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.issue.severity == severity)
+            .count()
+    }
+}
+
+type CompilePass = fn(&Workflow, &mut Vec<ValidationIssue>);
+
+fn lint_graph(workflow: &Workflow, issues: &mut Vec<ValidationIssue>) {
+    validate_entry_points(workflow, issues);
+    validate_reachability(workflow, issues);
+    validate_orphan_nodes(workflow, issues);
+    issues.extend(validate_unique_node_ids(workflow));
+    validate_contract(workflow, issues);
+}
+
+// Policy checks slot in here as another source once a policy engine exists.
+const PASSES: [(DiagnosticSource, CompilePass); 3] = [
+    (DiagnosticSource::GraphLint, lint_graph),
+    (DiagnosticSource::Structural, validate_grammar_constraints),
+    (DiagnosticSource::Config, validate_node_configs),
+];
+
+/// Runs every static analysis over `workflow` and gates the result.
+#[must_use]
+pub fn compile_workflow(workflow: &Workflow, gate: SeverityGate) -> CompileReport {
+    let diagnostics = PASSES
+        .iter()
+        .flat_map(|(source, pass)| {
+            let mut issues = Vec::new();
+            pass(workflow, &mut issues);
+            issues.into_iter().map(|issue| CompileDiagnostic {
+                source: *source,
+                issue,
+            })
+        })
+        .collect();
+
+    CompileReport { gate, diagnostics }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{compile_workflow, CompileStatus, DiagnosticSource, SeverityGate};
+    use crate::graph::{PortName, Workflow};
+    use serde_json::json;
+
+    fn main_port() -> PortName {
+        PortName::from("main")
+    }
+
+    fn configured_workflow() -> Workflow {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let run = workflow.add_node("run", 200.0, 0.0);
+        let _ = workflow.add_connection_checked(entry, run, &main_port(), &main_port());
+        workflow
+    }
+
+    #[test]
+    fn given_configured_workflow_when_compiling_then_status_is_clean() {
+        let report = compile_workflow(&configured_workflow(), SeverityGate::default());
+
+        assert_eq!(report.status(), CompileStatus::Clean);
+        assert!(!report.is_blocked());
+    }
+
+    #[test]
+    fn given_missing_entry_point_when_compiling_then_lint_error_blocks() {
+        let mut workflow = Workflow::new();
+        let _ = workflow.add_node("run", 0.0, 0.0);
+
+        let report = compile_workflow(&workflow, SeverityGate::AllowWarnings);
+
+        assert!(report.is_blocked());
+        assert!(report
+            .blocking()
+            .all(|diagnostic| diagnostic.source == DiagnosticSource::GraphLint));
+    }
+
+    #[test]
+    fn given_unconfigured_http_call_when_compiling_then_config_warning_only_blocks_when_denied() {
+        let mut workflow = configured_workflow();
+        let run = workflow.nodes[1].id;
+        let call = workflow.add_node("http-call", 400.0, 0.0);
+        let _ = workflow.add_connection_checked(run, call, &main_port(), &main_port());
+
+        let allowed = compile_workflow(&workflow, SeverityGate::AllowWarnings);
+        let denied = compile_workflow(&workflow, SeverityGate::DenyWarnings);
+
+        assert_eq!(allowed.status(), CompileStatus::Warnings);
+        assert!(allowed.diagnostics.iter().any(|diagnostic| {
+            diagnostic.source == DiagnosticSource::Config
+                && diagnostic.issue.node_id == Some(call)
+                && diagnostic.issue.message.contains("'url'")
+        }));
+        assert_eq!(denied.status(), CompileStatus::Blocked);
+    }
+
+    #[test]
+    fn given_non_object_config_when_compiling_then_config_error_is_reported() {
+        let mut workflow = configured_workflow();
+        workflow.nodes[1].config = json!("broken");
+
+        let report = compile_workflow(&workflow, SeverityGate::AllowWarnings);
+
+        assert!(report.is_blocked());
+        assert!(report
+            .blocking()
+            .any(|diagnostic| diagnostic.source == DiagnosticSource::Config));
+    }
+
+    #[test]
+    fn given_dangling_timeout_when_compiling_then_diagnostic_is_tagged_structural() {
+        let mut workflow = configured_workflow();
+        let run = workflow.nodes[1].id;
+        let timeout = workflow.add_node("timeout", 400.0, 0.0);
+        let _ = workflow.add_connection_checked(run, timeout, &main_port(), &main_port());
+
+        let report = compile_workflow(&workflow, SeverityGate::AllowWarnings);
+
+        assert!(report.diagnostics.iter().any(|diagnostic| {
+            diagnostic.source == DiagnosticSource::Structural
+                && diagnostic.issue.node_id == Some(timeout)
+        }));
+        assert_eq!(
+            report.to_validation_result().issues.len(),
+            report.diagnostics.len()
+        );
+    }
+
+    #[test]
+    fn given_gate_names_when_parsed_then_round_trip() {
+        for gate in [SeverityGate::AllowWarnings, SeverityGate::DenyWarnings] {
+            assert_eq!(gate.as_str().parse::<SeverityGate>(), Ok(gate));
+        }
+        assert!("strict".parse::<SeverityGate>().is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use uuid::Uuid;
 
+use crate::graph::events::{self, WorkflowEvent};
 use crate::graph::{Connection, NodeId, PortName, Workflow};
 
 use super::validators::{validate_connection, ValidationState};
@@ -76,9 +77,71 @@ impl Workflow {
             source_port,
             target_port,
         )?;
-        commit_connection(&mut self.connections, validation);
+        let connection_id = commit_connection(&mut self.connections, validation);
+        events::emit(&WorkflowEvent::ConnectionCreated {
+            connection_id,
+            source,
+            target,
+        });
         Ok(ConnectionResult::Created)
     }
+
+    /// Inserts `node` into an existing connection, replacing
+    /// `source → target` with `source → node → target`.
+    ///
+    /// The original source and target ports are kept on the outer ends;
+    /// `node` is wired through its `main` ports.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConnectionError::MissingConnection`] if no connection has
+    /// `connection_id`, or any error [`Workflow::add_connection_checked`]
+    /// reports for either new edge. The connections are left unchanged on
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use oya_frontend::graph::{Workflow, PortName};
+    /// let mut workflow = Workflow::new();
+    /// let source = workflow.add_node("http-handler", 0.0, 0.0);
+    /// let target = workflow.add_node("run", 400.0, 0.0);
+    /// let main = PortName("main".to_string());
+    /// let _ = workflow.add_connection_checked(source, target, &main, &main);
+    /// let middle = workflow.add_node("run", 200.0, 0.0);
+    ///
+    /// let edge = workflow.connections[0].id;
+    /// assert!(workflow.splice_node_into_connection(edge, middle).is_ok());
+    /// assert_eq!(workflow.connections.len(), 2);
+    /// ```
+    pub fn splice_node_into_connection(
+        &mut self,
+        connection_id: Uuid,
+        node: NodeId,
+    ) -> Result<ConnectionResult, ConnectionError> {
+        let Some(connection) = self
+            .connections
+            .iter()
+            .find(|connection| connection.id == connection_id)
+            .cloned()
+        else {
+            return Err(ConnectionError::MissingConnection(connection_id));
+        };
+
+        let original = self.connections.clone();
+        let main = PortName::from("main");
+        self.connections
+            .retain(|existing| existing.id != connection_id);
+        let spliced = self
+            .add_connection_checked(connection.source, node, &connection.source_port, &main)
+            .and_then(|_| {
+                self.add_connection_checked(node, connection.target, &main, &connection.target_port)
+            });
+        if spliced.is_err() {
+            self.connections = original;
+        }
+        spliced
+    }
 }
 
 /// Commits a validated connection to the graph.
@@ -86,12 +149,14 @@ impl Workflow {
 /// # Safety
 ///
 /// Only call this after `validate_connection` has succeeded.
-fn commit_connection(connections: &mut Vec<Connection>, validation: ValidationState) {
+fn commit_connection(connections: &mut Vec<Connection>, validation: ValidationState) -> Uuid {
+    let id = Uuid::new_v4();
     connections.push(Connection {
-        id: Uuid::new_v4(),
+        id,
         source: validation.source,
         target: validation.target,
         source_port: validation.source_port,
         target_port: validation.target_port,
     });
+    id
 }
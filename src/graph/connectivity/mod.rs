@@ -1,3 +1,4 @@
+use crate::graph::grammar::GrammarViolation;
 use crate::graph::graph_ops;
 use crate::graph::restate_types::{ParsePortTypeError, PortType};
 use crate::graph::{Connection, NodeId, Workflow};
@@ -32,6 +33,7 @@ pub enum ConnectionError {
     SelfConnection,
     MissingSourceNode(NodeId),
     MissingTargetNode(NodeId),
+    MissingConnection(uuid::Uuid),
     WouldCreateCycle,
     Duplicate,
     TypeMismatch {
@@ -39,6 +41,7 @@ pub enum ConnectionError {
         target_type: TargetPortType,
     },
     ParseError(ParsePortTypeError),
+    GrammarViolation(GrammarViolation),
 }
 
 impl std::fmt::Display for ConnectionError {
@@ -51,6 +54,9 @@ impl std::fmt::Display for ConnectionError {
             Self::MissingTargetNode(node_id) => {
                 write!(f, "Target node not found: {node_id}")
             }
+            Self::MissingConnection(connection_id) => {
+                write!(f, "Connection not found: {connection_id}")
+            }
             Self::WouldCreateCycle => write!(f, "Connection would create a cycle"),
             Self::Duplicate => write!(f, "Connection already exists"),
             Self::TypeMismatch {
@@ -61,6 +67,9 @@ impl std::fmt::Display for ConnectionError {
                 "Type mismatch: {source_type} is not compatible with {target_type}"
             ),
             Self::ParseError(err) => write!(f, "Parse error: {err}"),
+            Self::GrammarViolation(violation) => {
+                write!(f, "Structural constraint violated: {violation}")
+            }
         }
     }
 }
@@ -241,3 +241,49 @@ fn given_invalid_node_type_when_checking_port_compatibility_then_parse_error_is_
 
     assert!(matches!(result, Err(ConnectionError::ParseError(_))));
 }
+
+// ---------------------------------------------------------------------------
+// splice_node_into_connection
+// ---------------------------------------------------------------------------
+
+#[test]
+fn given_connection_when_splicing_node_then_it_is_routed_through_the_node() {
+    let mut workflow = Workflow::new();
+    let source = workflow.add_node("http-handler", 0.0, 0.0);
+    let target = workflow.add_node("run", 400.0, 0.0);
+    let middle = workflow.add_node("run", 200.0, 0.0);
+    let main = PortName("main".to_string());
+    let _ = workflow.add_connection_checked(source, target, &main, &main);
+    let edge = workflow.connections[0].id;
+
+    let result = workflow.splice_node_into_connection(edge, middle);
+
+    assert_eq!(result, Ok(ConnectionResult::Created));
+    let pairs: Vec<_> = workflow
+        .connections
+        .iter()
+        .map(|connection| (connection.source, connection.target))
+        .collect();
+    assert_eq!(pairs, vec![(source, middle), (middle, target)]);
+}
+
+#[test]
+fn given_invalid_splice_when_splicing_then_connections_are_unchanged() {
+    let mut workflow = Workflow::new();
+    let source = workflow.add_node("http-handler", 0.0, 0.0);
+    let target = workflow.add_node("run", 400.0, 0.0);
+    let main = PortName("main".to_string());
+    let _ = workflow.add_connection_checked(source, target, &main, &main);
+    let before = workflow.connections.clone();
+    let missing = Uuid::new_v4();
+
+    assert_eq!(
+        workflow.splice_node_into_connection(before[0].id, target),
+        Err(ConnectionError::SelfConnection)
+    );
+    assert_eq!(workflow.connections, before);
+    assert_eq!(
+        workflow.splice_node_into_connection(missing, target),
+        Err(ConnectionError::MissingConnection(missing))
+    );
+}
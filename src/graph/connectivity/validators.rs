@@ -1,6 +1,6 @@
-use crate::graph::graph_ops;
 use crate::graph::restate_types::{types_compatible, ParsePortTypeError, PortType};
 use crate::graph::workflow_node::WorkflowNode;
+use crate::graph::{grammar, graph_ops};
 use crate::graph::{Connection, Node, NodeId, PortName};
 
 use super::{ConnectionError, SourcePortType, TargetPortType};
@@ -24,6 +24,7 @@ pub(super) struct ValidationState {
 /// - Cycle would be created
 /// - Connection already exists
 /// - Port types incompatible
+/// - A node type's structural constraint would be violated
 pub(super) fn validate_connection(
     nodes: &[Node],
     connections: &[Connection],
@@ -37,6 +38,7 @@ pub(super) fn validate_connection(
     validate_no_cycle(connections, target, source)?;
     validate_no_duplicate(connections, source, target, source_port, target_port)?;
     check_port_type_compatibility(nodes, source, target)?;
+    check_grammar_constraints(nodes, connections, source, target)?;
 
     Ok(ValidationState {
         source,
@@ -150,6 +152,17 @@ pub(super) fn check_port_type_compatibility(
     Ok(())
 }
 
+fn check_grammar_constraints(
+    nodes: &[Node],
+    connections: &[Connection],
+    source: NodeId,
+    target: NodeId,
+) -> Result<(), ConnectionError> {
+    let (source_node, target_node) = find_source_and_target_nodes(nodes, source, target)?;
+    grammar::check_new_edge(source_node, target_node, connections)
+        .map_err(ConnectionError::GrammarViolation)
+}
+
 fn get_node_output_port_type(node: &Node) -> Result<PortType, ConnectionError> {
     node.node_type
         .parse::<WorkflowNode>()
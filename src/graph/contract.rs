@@ -0,0 +1,287 @@
+//! The workflow's external contract: the input payload it accepts and the
+//! node whose output is its result.
+//!
+//! The contract is saved with the workflow, checked by the graph lint, and
+//! used to type the handler signature generated for export.
+
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{NodeId, PortName, ValidationIssue, Workflow};
+
+/// Which node and port produce the workflow's final result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputMapping {
+    pub node_id: NodeId,
+    #[serde(default = "main_port")]
+    pub port: PortName,
+}
+
+fn main_port() -> PortName {
+    PortName::from("main")
+}
+
+impl OutputMapping {
+    #[must_use]
+    pub fn new(node_id: NodeId) -> Self {
+        Self {
+            node_id,
+            port: main_port(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowContract {
+    /// JSON Schema for the payload the entry node receives.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_schema: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<OutputMapping>,
+}
+
+impl WorkflowContract {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.input_schema.is_none() && self.output.is_none()
+    }
+}
+
+/// Warn when the declared contract cannot be honoured by the graph.
+pub fn validate_contract(workflow: &Workflow, issues: &mut Vec<ValidationIssue>) {
+    let contract = &workflow.contract;
+    if let Some(schema) = &contract.input_schema {
+        if !schema.is_object() {
+            issues.push(ValidationIssue::warning(
+                "Workflow input schema must be a JSON Schema object",
+            ));
+        }
+    }
+    if let Some(output) = &contract.output {
+        if !workflow.nodes.iter().any(|node| node.id == output.node_id) {
+            issues.push(ValidationIssue::warning(
+                "Workflow output node no longer exists; choose a new output in settings",
+            ));
+        }
+    }
+}
+
+/// TypeScript type for a JSON Schema, falling back to `unknown` for anything
+/// it cannot describe.
+#[must_use]
+pub fn schema_type(schema: &Value) -> String {
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        return union(options.iter().map(Value::to_string));
+    }
+    match schema.get("type").and_then(Value::as_str) {
+        Some("string") => "string".to_string(),
+        Some("number" | "integer") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("null") => "null".to_string(),
+        Some("array") => {
+            let items = schema
+                .get("items")
+                .map_or_else(|| "unknown".to_string(), schema_type);
+            format!("Array<{items}>")
+        }
+        Some("object") => object_type(schema),
+        _ if schema.get("properties").is_some() => object_type(schema),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn object_type(schema: &Value) -> String {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return "Record<string, unknown>".to_string();
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut out = String::from("{");
+    for (name, property) in properties {
+        let optional = if required.contains(&name.as_str()) {
+            ""
+        } else {
+            "?"
+        };
+        let _ = write!(
+            out,
+            " {}{optional}: {};",
+            property_name(name),
+            schema_type(property)
+        );
+    }
+    out.push_str(" }");
+    out
+}
+
+/// TypeScript type describing a sample value, used for the output when no
+/// schema is declared for it.
+#[must_use]
+pub fn sample_type(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(_) => "boolean".to_string(),
+        Value::Number(_) => "number".to_string(),
+        Value::String(_) => "string".to_string(),
+        Value::Array(items) => {
+            let item = items
+                .first()
+                .map_or_else(|| "unknown".to_string(), sample_type);
+            format!("Array<{item}>")
+        }
+        Value::Object(fields) => {
+            let mut out = String::from("{");
+            for (name, field) in fields {
+                let _ = write!(out, " {}: {};", property_name(name), sample_type(field));
+            }
+            out.push_str(" }");
+            out
+        }
+    }
+}
+
+fn property_name(name: &str) -> String {
+    let identifier = name.chars().enumerate().all(|(index, character)| {
+        character == '_'
+            || character == '$'
+            || character.is_ascii_alphabetic()
+            || (index > 0 && character.is_ascii_digit())
+    });
+    if identifier && !name.is_empty() {
+        name.to_string()
+    } else {
+        Value::String(name.to_string()).to_string()
+    }
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    let types: Vec<String> = types.collect();
+    if types.is_empty() {
+        "never".to_string()
+    } else {
+        types.join(" | ")
+    }
+}
+
+/// The result type of the workflow, read from the last output of its
+/// mapped node and port.
+#[must_use]
+pub fn output_type(workflow: &Workflow) -> String {
+    workflow
+        .contract
+        .output
+        .as_ref()
+        .and_then(|output| {
+            let node = workflow
+                .nodes
+                .iter()
+                .find(|node| node.id == output.node_id)?;
+            let sample = node.last_output.as_ref()?;
+            Some(if output.port.0 == "main" {
+                sample_type(sample)
+            } else {
+                sample
+                    .get(&output.port.0)
+                    .map_or_else(|| sample_type(sample), sample_type)
+            })
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// camelCase identifier for a workflow name, e.g. `Place order` -> `placeOrder`.
+#[must_use]
+pub fn handler_name(workflow_name: &str) -> String {
+    let mut name = String::new();
+    for word in workflow_name
+        .split(|character: char| !character.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let mut characters = word.chars();
+        if let Some(first) = characters.next() {
+            if name.is_empty() {
+                name.push(first.to_ascii_lowercase());
+            } else {
+                name.push(first.to_ascii_uppercase());
+            }
+            name.extend(characters);
+        }
+    }
+    match name.chars().next() {
+        None => "run".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("run{name}"),
+        Some(_) => name,
+    }
+}
+
+/// Restate handler signature typed by the workflow contract.
+#[must_use]
+pub fn handler_signature(workflow: &Workflow, name: &str) -> String {
+    let input = workflow
+        .contract
+        .input_schema
+        .as_ref()
+        .map_or_else(|| "unknown".to_string(), schema_type);
+    let output = if workflow.contract.output.is_some() {
+        output_type(workflow)
+    } else {
+        "void".to_string()
+    };
+    format!("async function {name}(ctx: restate.Context, input: {input}): Promise<{output}>")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::WorkflowBuilder;
+    use serde_json::json;
+
+    #[test]
+    fn given_contract_when_generating_signature_then_input_and_output_are_typed() {
+        let (mut workflow, ids) = WorkflowBuilder::new()
+            .node("entry", "http-handler")
+            .node("reply", "run")
+            .connect("entry", "reply")
+            .build_with_ids();
+        workflow.contract.input_schema = Some(json!({
+            "type": "object",
+            "properties": {
+                "order-id": { "type": "string" },
+                "items": { "type": "array", "items": { "type": "integer" } },
+                "mode": { "enum": ["fast", "slow"] }
+            },
+            "required": ["order-id"]
+        }));
+        workflow.contract.output = Some(OutputMapping::new(ids["reply"]));
+        workflow.nodes[1].last_output = Some(json!({ "ok": true, "total": 3 }));
+
+        assert_eq!(
+            handler_signature(&workflow, &handler_name("Place order!")),
+            "async function placeOrder(ctx: restate.Context, input: { items?: Array<number>; mode?: \"fast\" | \"slow\"; \"order-id\": string; }): Promise<{ ok: boolean; total: number; }>"
+        );
+    }
+
+    #[test]
+    fn given_missing_output_node_when_validating_then_warning_is_reported() {
+        let mut workflow = WorkflowBuilder::new().node("entry", "http-handler").build();
+        let mut issues = Vec::new();
+        validate_contract(&workflow, &mut issues);
+        assert!(issues.is_empty());
+
+        workflow.contract.output = Some(OutputMapping::new(NodeId::new()));
+        workflow.contract.input_schema = Some(json!("string"));
+        validate_contract(&workflow, &mut issues);
+
+        assert_eq!(issues.len(), 2);
+        assert!(issues[1].message.contains("output node"));
+        assert_eq!(
+            handler_signature(&workflow, &handler_name("")),
+            "async function run(ctx: restate.Context, input: unknown): Promise<unknown>"
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use super::contract::WorkflowContract;
+use super::events::{self, WorkflowEvent};
+use super::execution_runtime::cache::NodeCache;
 use super::execution_types::ExecutionConfig;
 use super::{can_transition, ExecutionState, Node, NodeId, RollbackAction, Viewport, Workflow};
 use crate::graph::{calc, workflow_node::WorkflowNode};
@@ -97,12 +100,16 @@ impl Workflow {
             current_step: 0,
             history: Vec::new(),
             execution_records: Vec::new(),
+            suppressed_extensions: Vec::new(),
             restate_ingress_url: "http://localhost:8080".to_owned(),
             current_memory_bytes: 0,
             execution_config: ExecutionConfig::default(),
             execution_failed: false,
             last_checkpoint_step: None,
             rollback_stack: Vec::new(),
+            node_cache: NodeCache::default(),
+            contract: WorkflowContract::default(),
+            frames: Vec::new(),
         }
     }
 
@@ -112,6 +119,11 @@ impl Workflow {
         self.last_checkpoint_step = Some(self.current_step);
     }
 
+    /// Forget every memoized node output, so the next run executes all nodes.
+    pub fn clear_node_cache(&mut self) {
+        self.node_cache.clear();
+    }
+
     /// Reset checkpoint state for a new execution.
     pub const fn reset_checkpoint(&mut self) {
         self.last_checkpoint_step = None;
@@ -161,6 +173,10 @@ impl Workflow {
         let mut node = Node::from_workflow_node(name, workflow_node, final_x, final_y);
         node.id = id;
         self.nodes.push(node);
+        events::emit(&WorkflowEvent::NodeAdded {
+            node_id: id,
+            node_type: node_type.to_string(),
+        });
         id
     }
 
@@ -174,8 +190,19 @@ impl Workflow {
     }
 
     pub fn update_node_position(&mut self, id: NodeId, dx: f32, dy: f32) {
+        self.update_node_position_on_grid(id, dx, dy, Some(calc::DEFAULT_SNAP_GRID));
+    }
+
+    /// Moves a node by a delta, snapping to `grid` unless it is `None`.
+    pub fn update_node_position_on_grid(
+        &mut self,
+        id: NodeId,
+        dx: f32,
+        dy: f32,
+        grid: Option<f32>,
+    ) {
         if let Some(node) = self.nodes.iter_mut().find(|n| n.id == id) {
-            let (new_x, new_y) = calc::update_node_position(node.x, node.y, dx, dy);
+            let (new_x, new_y) = calc::snap_position(node.x, node.y, dx, dy, grid);
             node.x = new_x;
             node.y = new_y;
         }
@@ -16,6 +16,9 @@ use crate::graph::{Connection, NodeCategory, NodeId};
 // Node
 // ===========================================================================
 
+/// `execution_data` key holding the node's last run duration.
+const DURATION_DATA_KEY: &str = "durationMs";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Node {
     pub id: NodeId,
@@ -129,6 +132,16 @@ impl Node {
         }
     }
 
+    /// Rebuild the typed node after deserializing, which keeps only
+    /// `node_type` and `config`. Unknown types keep the current typed node.
+    pub fn rehydrate(&mut self) {
+        if let Ok(typed) = self.node_type.parse::<WorkflowNode>() {
+            self.node = typed;
+        }
+        let config = self.config.clone();
+        self.apply_config_update(&config);
+    }
+
     #[must_use]
     pub fn from_workflow_node(name: String, node: WorkflowNode, x: f32, y: f32) -> Self {
         let category = node.category();
@@ -178,6 +191,29 @@ impl Node {
     pub const fn try_transition(&self, to: ExecutionState) -> Option<super::StateTransition> {
         super::try_transition(self.execution_state, to)
     }
+
+    /// Wall-clock time the node took in its last run, in milliseconds.
+    #[must_use]
+    pub fn duration_ms(&self) -> Option<u64> {
+        self.execution_data
+            .get(DURATION_DATA_KEY)
+            .and_then(Value::as_u64)
+    }
+
+    pub(crate) fn set_duration_ms(&mut self, duration_ms: Option<u64>) {
+        match (&mut self.execution_data, duration_ms) {
+            (Value::Object(data), Some(ms)) => {
+                data.insert(DURATION_DATA_KEY.to_string(), Value::from(ms));
+            }
+            (Value::Object(data), None) => {
+                data.remove(DURATION_DATA_KEY);
+            }
+            (data, Some(ms)) => {
+                *data = serde_json::json!({ DURATION_DATA_KEY: ms });
+            }
+            (_, None) => {}
+        }
+    }
 }
 
 impl Default for Node {
@@ -210,6 +246,9 @@ pub struct RunRecord {
     pub success: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub restate_invocation_id: Option<String>,
+    /// Wall-clock time each executed node took, in milliseconds.
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub durations: std::collections::HashMap<NodeId, u64>,
 }
 
 // ===========================================================================
@@ -226,6 +265,18 @@ pub struct Workflow {
     pub history: Vec<RunRecord>,
     #[serde(default)]
     pub execution_records: Vec<super::ExecutionRecord>,
+    /// Fingerprints of flow-extender suggestions dismissed for this workflow.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed_extensions: Vec<String>,
+    /// Declared input schema and result node for the workflow as a whole.
+    #[serde(
+        default,
+        skip_serializing_if = "super::contract::WorkflowContract::is_empty"
+    )]
+    pub contract: super::contract::WorkflowContract,
+    /// Background frames grouping nodes on the canvas, bottom to top.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frames: Vec<super::frames::Frame>,
     /// Base URL for Restate ingress (e.g., `<http://localhost:8080>`).
     /// Populated at runtime before `run()`; not part of the saved workflow definition.
     #[serde(default = "default_restate_ingress_url", skip_serializing)]
@@ -248,6 +299,9 @@ pub struct Workflow {
     /// Track rollback state for saga compensation.
     #[serde(skip, default)]
     pub rollback_stack: Vec<RollbackAction>,
+    /// Outputs reused by later runs while a node's inputs are unchanged.
+    #[serde(skip, default)]
+    pub node_cache: super::execution_runtime::cache::NodeCache,
 }
 
 /// Action to perform during saga rollback.
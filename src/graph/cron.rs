@@ -0,0 +1,327 @@
+//! Cron expressions for `cron-trigger` nodes.
+//!
+//! Schedules use the five standard fields — minute, hour, day of month,
+//! month, day of week — evaluated in UTC. Fields accept `*`, numbers,
+//! ranges (`1-5`), steps (`*/15`, `0-30/10`), comma lists and three-letter
+//! month or weekday names. The `@hourly`, `@daily`, `@weekly`, `@monthly`
+//! and `@yearly` shorthands are also understood. As in Vixie cron, when
+//! both day fields are restricted (neither starts with `*`) a day matching
+//! either one fires.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use thiserror::Error;
+
+use super::{Node, NodeId, Workflow};
+
+/// Config key holding a `cron-trigger` node's expression.
+pub const SCHEDULE_CONFIG_KEY: &str = "schedule";
+
+/// Upper bound on candidate minutes tried when searching for the next fire
+/// time, so impossible dates such as `0 0 30 2 *` terminate.
+const SEARCH_LIMIT: usize = 200_000;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CronError {
+    #[error("Expected 5 fields (minute hour day month weekday), found {0}")]
+    FieldCount(usize),
+    #[error("Invalid {field} field '{value}'")]
+    Field { field: &'static str, value: String },
+    #[error("Unknown shorthand '{0}'")]
+    Shorthand(String),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FieldSpec {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const MINUTE: FieldSpec = FieldSpec {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR: FieldSpec = FieldSpec {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY: FieldSpec = FieldSpec {
+    name: "day",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH: FieldSpec = FieldSpec {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &MONTH_NAMES,
+};
+// 7 is accepted as a second spelling of Sunday and folded onto 0.
+const WEEKDAY: FieldSpec = FieldSpec {
+    name: "weekday",
+    min: 0,
+    max: 7,
+    names: &WEEKDAY_NAMES,
+};
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl CronSchedule {
+    /// # Errors
+    /// Returns an error if the expression is not a valid five-field cron
+    /// expression or known shorthand.
+    pub fn parse(expression: &str) -> Result<Self, CronError> {
+        let expression = expression.trim();
+        let expanded = match expression {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other if other.starts_with('@') => {
+                return Err(CronError::Shorthand(other.to_string()));
+            }
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(CronError::FieldCount(fields.len()));
+        };
+        let mut weekdays = parse_field(weekday, WEEKDAY)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, MINUTE)?,
+            hours: parse_field(hour, HOUR)?,
+            days: parse_field(day, DAY)?,
+            months: parse_field(month, MONTH)?,
+            weekdays,
+            days_restricted: !day.starts_with('*'),
+            weekdays_restricted: !weekday.starts_with('*'),
+        })
+    }
+
+    /// Whether the schedule fires during the minute containing `time`.
+    #[must_use]
+    pub fn matches(&self, time: DateTime<Utc>) -> bool {
+        has(self.minutes, time.minute())
+            && has(self.hours, time.hour())
+            && has(self.months, time.month())
+            && self.matches_day(time)
+    }
+
+    fn matches_day(&self, time: DateTime<Utc>) -> bool {
+        let day = has(self.days, time.day());
+        let weekday = has(self.weekdays, time.weekday().num_days_from_sunday());
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            (true, false) => day,
+            (false, true) => weekday,
+            (false, false) => true,
+        }
+    }
+
+    /// The first fire time strictly after `after`, or `None` if the
+    /// schedule can never fire (e.g. February 30th).
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut candidate = start;
+        for _ in 0..SEARCH_LIMIT {
+            if !has(self.months, candidate.month()) {
+                candidate = first_of_next_month(candidate)?;
+            } else if !self.matches_day(candidate) {
+                candidate = start_of_day(candidate)? + Duration::days(1);
+            } else if !has(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+            } else if !has(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+            } else {
+                return Some(candidate);
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronError;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        Self::parse(expression)
+    }
+}
+
+const fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn start_of_day(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    time.with_hour(0)?.with_minute(0)
+}
+
+fn first_of_next_month(time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (year, month) = if time.month() == 12 {
+        (time.year() + 1, 1)
+    } else {
+        (time.year(), time.month() + 1)
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()
+}
+
+fn parse_field(text: &str, spec: FieldSpec) -> Result<u64, CronError> {
+    let invalid = || CronError::Field {
+        field: spec.name,
+        value: text.to_string(),
+    };
+    text.split(',').try_fold(0_u64, |mask, part| {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+        let (low, high) = match range {
+            "*" => (spec.min, spec.max),
+            _ => match range.split_once('-') {
+                Some((low, high)) => (
+                    parse_value(low, spec).ok_or_else(invalid)?,
+                    parse_value(high, spec).ok_or_else(invalid)?,
+                ),
+                // `5/15` means every 15 starting at 5.
+                None if part.contains('/') => {
+                    (parse_value(range, spec).ok_or_else(invalid)?, spec.max)
+                }
+                None => {
+                    let value = parse_value(range, spec).ok_or_else(invalid)?;
+                    (value, value)
+                }
+            },
+        };
+        if low > high {
+            return Err(invalid());
+        }
+        Ok((low..=high)
+            .step_by(step as usize)
+            .fold(mask, |mask, value| mask | (1 << value)))
+    })
+}
+
+fn parse_value(text: &str, spec: FieldSpec) -> Option<u32> {
+    let lower = text.to_ascii_lowercase();
+    let value = spec
+        .names
+        .iter()
+        .position(|name| *name == lower)
+        .and_then(|index| u32::try_from(index).ok())
+        .map(|index| index + spec.min)
+        .or_else(|| text.parse().ok())?;
+    (spec.min..=spec.max).contains(&value).then_some(value)
+}
+
+/// The expression configured on a `cron-trigger` node, if any.
+#[must_use]
+pub fn node_schedule(node: &Node) -> Option<&str> {
+    (node.node_type == "cron-trigger")
+        .then(|| node.config.get(SCHEDULE_CONFIG_KEY)?.as_str())
+        .flatten()
+        .map(str::trim)
+        .filter(|expression| !expression.is_empty())
+}
+
+impl Workflow {
+    /// Every `cron-trigger` node with a schedule, parsed.
+    #[must_use]
+    pub fn cron_triggers(&self) -> Vec<(NodeId, Result<CronSchedule, CronError>)> {
+        self.nodes
+            .iter()
+            .filter_map(|node| Some((node.id, CronSchedule::parse(node_schedule(node)?))))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn given_common_schedules_when_finding_next_fire_then_times_are_correct() {
+        let now = at("2026-10-17T10:07:30Z");
+        let next = |expression: &str| CronSchedule::parse(expression).unwrap().next_after(now);
+
+        assert_eq!(next("*/15 * * * *"), Some(at("2026-10-17T10:15:00Z")));
+        assert_eq!(next("@daily"), Some(at("2026-10-18T00:00:00Z")));
+        assert_eq!(next("30 9 * * mon-fri"), Some(at("2026-10-19T09:30:00Z")));
+        assert_eq!(next("0 0 1 jan *"), Some(at("2027-01-01T00:00:00Z")));
+        assert_eq!(next("0 12 * * 7"), Some(at("2026-10-18T12:00:00Z")));
+        assert_eq!(next("0 0 30 2 *"), None);
+    }
+
+    #[test]
+    fn given_both_day_fields_when_matching_then_either_day_fires() {
+        let schedule = CronSchedule::parse("0 0 1 * fri").unwrap();
+
+        assert_eq!(
+            schedule.next_after(at("2026-10-17T10:00:00Z")),
+            Some(at("2026-10-23T00:00:00Z"))
+        );
+        assert!(schedule.matches(at("2026-11-01T00:00:00Z")));
+    }
+
+    #[test]
+    fn given_malformed_expressions_when_parsing_then_errors_name_the_problem() {
+        assert_eq!(CronSchedule::parse("* * *"), Err(CronError::FieldCount(3)));
+        assert!(matches!(
+            CronSchedule::parse("61 * * * *"),
+            Err(CronError::Field {
+                field: "minute",
+                ..
+            })
+        ));
+        assert!(matches!(
+            CronSchedule::parse("*/0 * * * *"),
+            Err(CronError::Field { .. })
+        ));
+        assert!(matches!(
+            CronSchedule::parse("@fortnightly"),
+            Err(CronError::Shorthand(_))
+        ));
+    }
+}
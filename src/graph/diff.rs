@@ -0,0 +1,271 @@
+//! Structural diff between two workflow documents.
+//!
+//! Nodes are matched by id, so a document copied from a template keeps its
+//! correspondence with the template even after nodes are renamed or moved.
+//! Connections are matched by endpoints and ports; their own ids are ignored
+//! because re-wiring the same edge mints a fresh id.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Connection, Node, NodeId, PortName, Workflow};
+
+/// How a node or connection differs between the two documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiffStatus {
+    Unchanged,
+    Added,
+    Removed,
+    Modified,
+    /// Repositioned on the canvas without any other change.
+    Moved,
+}
+
+/// The structural differences from `old` to `new`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowDiff {
+    pub added_nodes: Vec<NodeId>,
+    pub removed_nodes: Vec<NodeId>,
+    /// Nodes present in both documents whose name, type, or config differ.
+    pub modified_nodes: Vec<NodeId>,
+    /// Nodes present in both documents that differ only in position.
+    #[serde(default)]
+    pub moved_nodes: Vec<NodeId>,
+    pub added_connections: Vec<Connection>,
+    pub removed_connections: Vec<Connection>,
+}
+
+type EdgeKey<'a> = (NodeId, NodeId, &'a PortName, &'a PortName);
+
+const fn edge_key(connection: &Connection) -> EdgeKey<'_> {
+    (
+        connection.source,
+        connection.target,
+        &connection.source_port,
+        &connection.target_port,
+    )
+}
+
+fn node_differs(old: &Node, new: &Node) -> bool {
+    old.name != new.name || old.node_type != new.node_type || old.config != new.config
+}
+
+#[allow(clippy::float_cmp)]
+fn node_moved(old: &Node, new: &Node) -> bool {
+    old.x != new.x || old.y != new.y
+}
+
+impl WorkflowDiff {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.modified_nodes.is_empty()
+            && self.moved_nodes.is_empty()
+            && self.added_connections.is_empty()
+            && self.removed_connections.is_empty()
+    }
+
+    /// Total number of changed nodes and connections.
+    #[must_use]
+    pub const fn change_count(&self) -> usize {
+        self.added_nodes.len()
+            + self.removed_nodes.len()
+            + self.modified_nodes.len()
+            + self.moved_nodes.len()
+            + self.added_connections.len()
+            + self.removed_connections.len()
+    }
+
+    #[must_use]
+    pub fn node_status(&self, id: NodeId) -> DiffStatus {
+        if self.added_nodes.contains(&id) {
+            DiffStatus::Added
+        } else if self.removed_nodes.contains(&id) {
+            DiffStatus::Removed
+        } else if self.modified_nodes.contains(&id) {
+            DiffStatus::Modified
+        } else if self.moved_nodes.contains(&id) {
+            DiffStatus::Moved
+        } else {
+            DiffStatus::Unchanged
+        }
+    }
+
+    #[must_use]
+    pub fn connection_status(&self, connection: &Connection) -> DiffStatus {
+        let key = edge_key(connection);
+        if self
+            .added_connections
+            .iter()
+            .any(|added| edge_key(added) == key)
+        {
+            DiffStatus::Added
+        } else if self
+            .removed_connections
+            .iter()
+            .any(|removed| edge_key(removed) == key)
+        {
+            DiffStatus::Removed
+        } else {
+            DiffStatus::Unchanged
+        }
+    }
+}
+
+/// Computes the structural diff from `old` to `new`.
+#[must_use]
+pub fn diff(old: &Workflow, new: &Workflow) -> WorkflowDiff {
+    let old_nodes = old
+        .nodes
+        .iter()
+        .map(|node| (node.id, node))
+        .collect::<HashMap<_, _>>();
+    let new_nodes = new
+        .nodes
+        .iter()
+        .map(|node| (node.id, node))
+        .collect::<HashMap<_, _>>();
+
+    let added_nodes = new
+        .nodes
+        .iter()
+        .filter(|node| !old_nodes.contains_key(&node.id))
+        .map(|node| node.id)
+        .collect();
+    let removed_nodes = old
+        .nodes
+        .iter()
+        .filter(|node| !new_nodes.contains_key(&node.id))
+        .map(|node| node.id)
+        .collect();
+    let modified_nodes = new
+        .nodes
+        .iter()
+        .filter(|node| {
+            old_nodes
+                .get(&node.id)
+                .is_some_and(|previous| node_differs(previous, node))
+        })
+        .map(|node| node.id)
+        .collect();
+    let moved_nodes = new
+        .nodes
+        .iter()
+        .filter(|node| {
+            old_nodes
+                .get(&node.id)
+                .is_some_and(|previous| !node_differs(previous, node) && node_moved(previous, node))
+        })
+        .map(|node| node.id)
+        .collect();
+
+    let old_edges = old.connections.iter().map(edge_key).collect::<HashSet<_>>();
+    let new_edges = new.connections.iter().map(edge_key).collect::<HashSet<_>>();
+    let added_connections = new
+        .connections
+        .iter()
+        .filter(|connection| !old_edges.contains(&edge_key(connection)))
+        .cloned()
+        .collect();
+    let removed_connections = old
+        .connections
+        .iter()
+        .filter(|connection| !new_edges.contains(&edge_key(connection)))
+        .cloned()
+        .collect();
+
+    WorkflowDiff {
+        added_nodes,
+        removed_nodes,
+        modified_nodes,
+        moved_nodes,
+        added_connections,
+        removed_connections,
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{diff, DiffStatus};
+    use crate::graph::{PortName, Workflow};
+    use serde_json::json;
+
+    fn main_port() -> PortName {
+        PortName::from("main")
+    }
+
+    #[test]
+    fn given_identical_documents_when_diffing_then_diff_is_empty() {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let run = workflow.add_node("run", 200.0, 0.0);
+        let _ = workflow.add_connection_checked(entry, run, &main_port(), &main_port());
+
+        let result = diff(&workflow, &workflow.clone());
+
+        assert!(result.is_empty());
+        assert_eq!(result.node_status(run), DiffStatus::Unchanged);
+    }
+
+    #[test]
+    fn given_customized_copy_when_diffing_then_changes_are_classified() {
+        let mut template = Workflow::new();
+        let entry = template.add_node("http-handler", 0.0, 0.0);
+        let run = template.add_node("run", 200.0, 0.0);
+        let legacy = template.add_node("set-state", 400.0, 0.0);
+        let _ = template.add_connection_checked(entry, run, &main_port(), &main_port());
+        let _ = template.add_connection_checked(run, legacy, &main_port(), &main_port());
+
+        let mut copy = template.clone();
+        copy.remove_node(legacy);
+        copy.nodes[1].config = json!({"durable_step_name": "charge"});
+        copy.nodes[1].x += 80.0;
+        let extra = copy.add_node("timeout", 400.0, 120.0);
+        let _ = copy.add_connection_checked(run, extra, &main_port(), &main_port());
+
+        let result = diff(&template, &copy);
+
+        assert_eq!(result.added_nodes, vec![extra]);
+        assert_eq!(result.removed_nodes, vec![legacy]);
+        assert_eq!(result.modified_nodes, vec![run]);
+        assert_eq!(result.added_connections.len(), 1);
+        assert_eq!(result.removed_connections.len(), 1);
+        assert_eq!(result.change_count(), 5);
+        assert_eq!(
+            result.connection_status(&result.added_connections[0]),
+            DiffStatus::Added
+        );
+        assert_eq!(
+            result.connection_status(&template.connections[0]),
+            DiffStatus::Unchanged
+        );
+    }
+
+    #[test]
+    fn given_repositioned_node_when_diffing_then_it_is_moved_not_modified() {
+        let mut before = Workflow::new();
+        let entry = before.add_node("http-handler", 0.0, 0.0);
+        let run = before.add_node("run", 200.0, 0.0);
+        let mut after = before.clone();
+        after.nodes[0].y += 40.0;
+        after.nodes[1].x += 40.0;
+        after.nodes[1].name = "Charge card".to_string();
+
+        let result = diff(&before, &after);
+
+        assert_eq!(result.moved_nodes, vec![entry]);
+        assert_eq!(result.modified_nodes, vec![run]);
+        assert_eq!(result.node_status(entry), DiffStatus::Moved);
+        assert_eq!(result.node_status(run), DiffStatus::Modified);
+        assert_eq!(result.change_count(), 2);
+    }
+}
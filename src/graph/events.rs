@@ -0,0 +1,164 @@
+//! Editor events for host applications.
+//!
+//! A host embedding the editor calls [`subscribe`] to hear about changes as
+//! they happen: nodes added, connections created, runs started and
+//! completed, extensions applied. The bus is process-wide, so one
+//! subscription sees every workflow. Handlers run synchronously on the
+//! thread that made the change and should hand slow work off elsewhere.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::NodeId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkflowEvent {
+    NodeAdded {
+        node_id: NodeId,
+        node_type: String,
+    },
+    ConnectionCreated {
+        connection_id: Uuid,
+        source: NodeId,
+        target: NodeId,
+    },
+    RunStarted {
+        node_count: usize,
+    },
+    RunCompleted {
+        run_id: Uuid,
+        success: bool,
+        duration_ms: i64,
+    },
+    ExtensionApplied {
+        key: String,
+        created_nodes: Vec<NodeId>,
+    },
+}
+
+type Handler = Arc<dyn Fn(&WorkflowEvent) + Send + Sync>;
+
+static HANDLERS: Mutex<Vec<(u64, Handler)>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A registered handler. Dropping it unsubscribes.
+#[must_use = "dropping a Subscription unsubscribes its handler"]
+pub struct Subscription {
+    id: Option<u64>,
+}
+
+impl Subscription {
+    /// Keeps the handler registered for the rest of the process.
+    pub fn detach(mut self) {
+        self.id = None;
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            handlers().retain(|(handler_id, _)| *handler_id != id);
+        }
+    }
+}
+
+/// Calls `handler` with every event emitted until the returned
+/// [`Subscription`] is dropped.
+pub fn subscribe(handler: impl Fn(&WorkflowEvent) + Send + Sync + 'static) -> Subscription {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    handlers().push((id, Arc::new(handler)));
+    Subscription { id: Some(id) }
+}
+
+/// Delivers `event` to every subscriber. Handlers are called outside the
+/// lock, so they may subscribe or emit in turn.
+pub(crate) fn emit(event: &WorkflowEvent) {
+    let snapshot: Vec<Handler> = handlers()
+        .iter()
+        .map(|(_, handler)| Arc::clone(handler))
+        .collect();
+    for handler in snapshot {
+        handler(event);
+    }
+}
+
+fn handlers() -> std::sync::MutexGuard<'static, Vec<(u64, Handler)>> {
+    HANDLERS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::{PortName, Workflow};
+
+    #[test]
+    fn given_subscriber_when_editing_then_node_and_connection_events_arrive_in_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let subscription = subscribe(move |event| sink.lock().unwrap().push(event.clone()));
+        let mut workflow = Workflow::new();
+        let source = workflow.add_node("http-handler", 0.0, 0.0);
+        let target = workflow.add_node("run", 200.0, 0.0);
+        let main = PortName::from("main");
+        workflow
+            .add_connection_checked(source, target, &main, &main)
+            .unwrap();
+        drop(subscription);
+
+        // Other tests share the bus, so keep only this workflow's events.
+        let ours: Vec<WorkflowEvent> = seen
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| match event {
+                WorkflowEvent::NodeAdded { node_id, .. } => [source, target].contains(node_id),
+                WorkflowEvent::ConnectionCreated { source: from, .. } => *from == source,
+                _ => false,
+            })
+            .cloned()
+            .collect();
+        assert_eq!(ours.len(), 3);
+        assert_eq!(
+            ours[1],
+            WorkflowEvent::NodeAdded {
+                node_id: target,
+                node_type: "run".to_string(),
+            }
+        );
+        assert!(matches!(
+            ours[2],
+            WorkflowEvent::ConnectionCreated { target: to, .. } if to == target
+        ));
+    }
+
+    #[test]
+    fn given_dropped_subscription_when_emitting_then_handler_is_not_called() {
+        let calls = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&calls);
+        // An unlikely node count keeps runs from other tests out of the tally.
+        let marker = WorkflowEvent::RunStarted {
+            node_count: usize::MAX,
+        };
+        let expected = marker.clone();
+        let subscription = subscribe(move |event| {
+            if *event == expected {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        emit(&marker);
+        drop(subscription);
+        emit(&marker);
+
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+    }
+}
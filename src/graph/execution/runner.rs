@@ -42,6 +42,7 @@ impl Workflow {
             node.last_output = None;
             node.skipped = false;
             node.error = None;
+            node.set_duration_ms(None);
             let _ = Self::set_node_pending_status(node);
         }
 
@@ -88,6 +88,7 @@ mod tests {
             results: std::collections::HashMap::new(),
             success: true,
             restate_invocation_id: None,
+            durations: std::collections::HashMap::new(),
         };
 
         let execution_record = from_run_record(&record);
@@ -109,6 +110,7 @@ mod tests {
             results,
             success: true,
             restate_invocation_id: None,
+            durations: std::collections::HashMap::new(),
         };
 
         let execution_record = from_run_record(&record);
@@ -126,6 +128,7 @@ mod tests {
             results: std::collections::HashMap::new(),
             success: false,
             restate_invocation_id: None,
+            durations: std::collections::HashMap::new(),
         };
 
         let execution_record = from_run_record(&record);
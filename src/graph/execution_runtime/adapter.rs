@@ -0,0 +1,538 @@
+//! Execution adapters for delegating node execution to external runners.
+//!
+//! By default every node runs through the built-in `execute_node_type`. An
+//! [`ExecutionAdapter`] lets a canvas run hand selected node types to an
+//! external process instead, so organizations can plug in their real step
+//! implementations. Two JSON-RPC 2.0 transports are provided:
+//! - [`HttpJsonRpcAdapter`] posts each request to an HTTP endpoint
+//! - `StdioJsonRpcAdapter` (native only) exchanges newline-delimited messages
+//!   with a long-lived child process
+//!
+//! Every request uses the `execute_node` method with a
+//! [`NodeExecutionRequest`] as params; the JSON-RPC `result` becomes the node
+//! output.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::graph::NodeId;
+
+/// JSON-RPC method invoked on external runners.
+pub const EXECUTE_NODE_METHOD: &str = "execute_node";
+
+/// How long a runner may take to answer one request.
+pub const DEFAULT_RUNNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Boxed future returned by [`ExecutionAdapter::execute`].
+///
+/// Native futures are `Send` so workflow runs stay spawnable; browser fetch
+/// futures are not, so the bound is dropped on wasm.
+#[cfg(not(target_arch = "wasm32"))]
+pub type AdapterFuture<'a> = Pin<Box<dyn Future<Output = Result<Value, AdapterError>> + Send + 'a>>;
+#[cfg(target_arch = "wasm32")]
+pub type AdapterFuture<'a> = Pin<Box<dyn Future<Output = Result<Value, AdapterError>> + 'a>>;
+
+/// Everything an external runner needs to execute one node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeExecutionRequest {
+    pub node_id: NodeId,
+    pub node_type: String,
+    /// Node config with expressions already resolved.
+    pub config: Value,
+    pub parent_outputs: Vec<Value>,
+    pub step: usize,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AdapterError {
+    #[error("Runner transport failed: {0}")]
+    Transport(String),
+
+    #[error("Invalid runner response: {0}")]
+    Protocol(String),
+
+    #[error("Runner error {code}: {message}")]
+    Remote { code: i64, message: String },
+
+    #[error("Runner did not answer within {}ms", .0.as_millis())]
+    Timeout(Duration),
+}
+
+/// Executes nodes on behalf of the workflow runtime.
+pub trait ExecutionAdapter: Send + Sync {
+    /// Whether this adapter executes `node_type`; other types use the built-in runtime.
+    fn handles(&self, _node_type: &str) -> bool {
+        true
+    }
+
+    /// Executes a node and returns its output.
+    fn execute<'a>(&'a self, request: &'a NodeExecutionRequest) -> AdapterFuture<'a>;
+}
+
+/// Builds a JSON-RPC 2.0 `execute_node` request.
+#[must_use]
+pub fn build_rpc_request(id: u64, request: &NodeExecutionRequest) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": EXECUTE_NODE_METHOD,
+        "params": request,
+    })
+}
+
+/// Extracts the result from a JSON-RPC 2.0 response.
+///
+/// # Errors
+///
+/// Returns `AdapterError::Remote` for JSON-RPC error objects and
+/// `AdapterError::Protocol` for malformed responses or mismatched IDs.
+pub fn parse_rpc_response(expected_id: u64, response: &Value) -> Result<Value, AdapterError> {
+    if response.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err(AdapterError::Protocol(
+            "missing jsonrpc version".to_string(),
+        ));
+    }
+    if response.get("id").and_then(Value::as_u64) != Some(expected_id) {
+        return Err(AdapterError::Protocol(format!(
+            "expected response id {expected_id}"
+        )));
+    }
+    if let Some(error) = response.get("error") {
+        return Err(AdapterError::Remote {
+            code: error.get("code").and_then(Value::as_i64).unwrap_or(-32000),
+            message: error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error")
+                .to_string(),
+        });
+    }
+    response
+        .get("result")
+        .cloned()
+        .ok_or_else(|| AdapterError::Protocol("missing result".to_string()))
+}
+
+fn restricted_to(node_types: Option<&HashSet<String>>, node_type: &str) -> bool {
+    node_types.is_none_or(|types| types.contains(node_type))
+}
+
+// ===========================================================================
+// HTTP Transport
+// ===========================================================================
+
+/// Sends JSON-RPC requests to an external runner over HTTP.
+#[derive(Debug)]
+pub struct HttpJsonRpcAdapter {
+    endpoint: String,
+    node_types: Option<HashSet<String>>,
+    client: reqwest::Client,
+    timeout: Duration,
+    next_id: AtomicU64,
+}
+
+impl HttpJsonRpcAdapter {
+    #[must_use]
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            node_types: None,
+            client: reqwest::Client::new(),
+            timeout: DEFAULT_RUNNER_TIMEOUT,
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Fails requests the runner has not answered after `timeout`.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Limits the adapter to the given node types.
+    #[must_use]
+    pub fn with_node_types<I, S>(mut self, node_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.node_types = Some(node_types.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl ExecutionAdapter for HttpJsonRpcAdapter {
+    fn handles(&self, node_type: &str) -> bool {
+        restricted_to(self.node_types.as_ref(), node_type)
+    }
+
+    fn execute<'a>(&'a self, request: &'a NodeExecutionRequest) -> AdapterFuture<'a> {
+        Box::pin(async move {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            let req = self
+                .client
+                .post(&self.endpoint)
+                .json(&build_rpc_request(id, request));
+
+            // Browser fetch API does not support per-request timeouts.
+            #[cfg(not(target_arch = "wasm32"))]
+            let req = req.timeout(self.timeout);
+
+            let response = req.send().await.map_err(|err| {
+                if err.is_timeout() {
+                    AdapterError::Timeout(self.timeout)
+                } else {
+                    AdapterError::Transport(err.to_string())
+                }
+            })?;
+            let body: Value = response
+                .json()
+                .await
+                .map_err(|err| AdapterError::Protocol(err.to_string()))?;
+            parse_rpc_response(id, &body)
+        })
+    }
+}
+
+// ===========================================================================
+// Stdio Transport
+// ===========================================================================
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use stdio::StdioJsonRpcAdapter;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod stdio {
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    use serde_json::Value;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+    use tokio::sync::Mutex;
+
+    use super::{
+        build_rpc_request, parse_rpc_response, restricted_to, AdapterError, AdapterFuture,
+        ExecutionAdapter, NodeExecutionRequest, DEFAULT_RUNNER_TIMEOUT,
+    };
+
+    struct Channel {
+        stdin: ChildStdin,
+        stdout: BufReader<ChildStdout>,
+    }
+
+    /// Exchanges newline-delimited JSON-RPC messages with a child process.
+    ///
+    /// The child is spawned once and killed when the adapter is dropped.
+    /// Requests are serialized, so the runner may answer them in order.
+    /// Lines that do not answer the current request, such as late replies
+    /// to a request that timed out, are skipped.
+    pub struct StdioJsonRpcAdapter {
+        _child: Child,
+        channel: Mutex<Channel>,
+        node_types: Option<HashSet<String>>,
+        timeout: Duration,
+        next_id: AtomicU64,
+    }
+
+    impl StdioJsonRpcAdapter {
+        /// Spawns `program` with `args` as the external runner.
+        ///
+        /// # Errors
+        ///
+        /// Returns `AdapterError::Transport` if the process cannot be started.
+        pub fn spawn<I, S>(program: &str, args: I) -> Result<Self, AdapterError>
+        where
+            I: IntoIterator<Item = S>,
+            S: AsRef<std::ffi::OsStr>,
+        {
+            let mut child = Command::new(program)
+                .args(args)
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|err| AdapterError::Transport(err.to_string()))?;
+            let stdin = child
+                .stdin
+                .take()
+                .ok_or_else(|| AdapterError::Transport("runner stdin unavailable".to_string()))?;
+            let stdout = child
+                .stdout
+                .take()
+                .ok_or_else(|| AdapterError::Transport("runner stdout unavailable".to_string()))?;
+
+            Ok(Self {
+                _child: child,
+                channel: Mutex::new(Channel {
+                    stdin,
+                    stdout: BufReader::new(stdout),
+                }),
+                node_types: None,
+                timeout: DEFAULT_RUNNER_TIMEOUT,
+                next_id: AtomicU64::new(1),
+            })
+        }
+
+        /// Fails requests the runner has not answered after `timeout`.
+        #[must_use]
+        pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = timeout;
+            self
+        }
+
+        /// Limits the adapter to the given node types.
+        #[must_use]
+        pub fn with_node_types<I, S>(mut self, node_types: I) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: Into<String>,
+        {
+            self.node_types = Some(node_types.into_iter().map(Into::into).collect());
+            self
+        }
+    }
+
+    impl ExecutionAdapter for StdioJsonRpcAdapter {
+        fn handles(&self, node_type: &str) -> bool {
+            restricted_to(self.node_types.as_ref(), node_type)
+        }
+
+        fn execute<'a>(&'a self, request: &'a NodeExecutionRequest) -> AdapterFuture<'a> {
+            Box::pin(async move {
+                let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+                let mut line = build_rpc_request(id, request).to_string();
+                line.push('\n');
+
+                let mut channel = self.channel.lock().await;
+                channel
+                    .stdin
+                    .write_all(line.as_bytes())
+                    .await
+                    .map_err(|err| AdapterError::Transport(err.to_string()))?;
+                channel
+                    .stdin
+                    .flush()
+                    .await
+                    .map_err(|err| AdapterError::Transport(err.to_string()))?;
+
+                let response =
+                    tokio::time::timeout(self.timeout, read_reply(&mut channel.stdout, id))
+                        .await
+                        .map_err(|_| AdapterError::Timeout(self.timeout))??;
+                drop(channel);
+                parse_rpc_response(id, &response)
+            })
+        }
+    }
+
+    /// Reads lines until one carries JSON-RPC id `id`.
+    async fn read_reply(
+        stdout: &mut BufReader<ChildStdout>,
+        id: u64,
+    ) -> Result<Value, AdapterError> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = stdout
+                .read_line(&mut line)
+                .await
+                .map_err(|err| AdapterError::Transport(err.to_string()))?;
+            if read == 0 {
+                return Err(AdapterError::Transport(
+                    "runner closed its output".to_string(),
+                ));
+            }
+            let reply: Value = serde_json::from_str(&line)
+                .map_err(|err| AdapterError::Protocol(err.to_string()))?;
+            if reply.get("id").and_then(Value::as_u64) == Some(id) {
+                return Ok(reply);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{
+        build_rpc_request, parse_rpc_response, AdapterError, AdapterFuture, ExecutionAdapter,
+        NodeExecutionRequest,
+    };
+    use crate::graph::{ExecutionState, NodeId, PortName, Workflow};
+    use serde_json::{json, Value};
+
+    struct EchoAdapter;
+
+    impl ExecutionAdapter for EchoAdapter {
+        fn handles(&self, node_type: &str) -> bool {
+            node_type == "run"
+        }
+
+        fn execute<'a>(&'a self, request: &'a NodeExecutionRequest) -> AdapterFuture<'a> {
+            Box::pin(async move { Ok(json!({ "runner": "echo", "node_type": request.node_type })) })
+        }
+    }
+
+    struct FailingAdapter;
+
+    impl ExecutionAdapter for FailingAdapter {
+        fn execute<'a>(&'a self, _request: &'a NodeExecutionRequest) -> AdapterFuture<'a> {
+            Box::pin(async move {
+                Err(AdapterError::Remote {
+                    code: 7,
+                    message: "boom".to_string(),
+                })
+            })
+        }
+    }
+
+    fn sample_request() -> NodeExecutionRequest {
+        NodeExecutionRequest {
+            node_id: NodeId::new(),
+            node_type: "run".to_string(),
+            config: json!({ "code": "return 1" }),
+            parent_outputs: vec![],
+            step: 0,
+        }
+    }
+
+    fn entry_and_run() -> (Workflow, NodeId, NodeId) {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let run = workflow.add_node("run", 200.0, 0.0);
+        let main = PortName::from("main");
+        let _ = workflow.add_connection_checked(entry, run, &main, &main);
+        (workflow, entry, run)
+    }
+
+    #[test]
+    fn given_request_when_building_rpc_then_envelope_carries_method_and_params() {
+        let request = sample_request();
+
+        let rpc = build_rpc_request(3, &request);
+
+        assert_eq!(rpc["jsonrpc"], "2.0");
+        assert_eq!(rpc["id"], 3);
+        assert_eq!(rpc["method"], "execute_node");
+        assert_eq!(rpc["params"]["node_type"], "run");
+    }
+
+    #[test]
+    fn given_result_response_when_parsing_then_result_is_returned() {
+        let response = json!({ "jsonrpc": "2.0", "id": 4, "result": { "ok": true } });
+
+        assert_eq!(parse_rpc_response(4, &response), Ok(json!({ "ok": true })));
+    }
+
+    #[test]
+    fn given_error_or_mismatched_response_when_parsing_then_error_is_returned() {
+        let remote = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": -32601, "message": "no such step" }
+        });
+        let mismatched = json!({ "jsonrpc": "2.0", "id": 2, "result": Value::Null });
+
+        assert_eq!(
+            parse_rpc_response(1, &remote),
+            Err(AdapterError::Remote {
+                code: -32601,
+                message: "no such step".to_string()
+            })
+        );
+        assert!(matches!(
+            parse_rpc_response(1, &mismatched),
+            Err(AdapterError::Protocol(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn given_adapter_for_run_nodes_when_running_then_only_run_nodes_are_delegated() {
+        let (mut workflow, entry, run) = entry_and_run();
+
+        workflow.run_with_adapter(&EchoAdapter).await;
+
+        let output = |id: NodeId| {
+            workflow
+                .nodes
+                .iter()
+                .find(|node| node.id == id)
+                .and_then(|node| node.last_output.clone())
+                .unwrap()
+        };
+        assert_eq!(output(run)["runner"], "echo");
+        assert_eq!(output(entry)["source"], "http-handler");
+    }
+
+    #[tokio::test]
+    async fn given_failing_adapter_when_running_then_node_is_marked_failed() {
+        let (mut workflow, entry, _) = entry_and_run();
+
+        workflow.run_with_adapter(&FailingAdapter).await;
+
+        let entry_node = workflow.nodes.iter().find(|node| node.id == entry).unwrap();
+        assert_eq!(entry_node.execution_state, ExecutionState::Failed);
+        assert_eq!(entry_node.error.as_deref(), Some("Runner error 7: boom"));
+        assert!(workflow.history.last().is_some_and(|run| !run.success));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn given_stdio_runner_when_executing_then_reply_line_is_parsed() {
+        let script =
+            r#"while read line; do echo '{"jsonrpc":"2.0","id":1,"result":{"ok":true}}'; done"#;
+        let adapter = super::StdioJsonRpcAdapter::spawn("sh", ["-c", script])
+            .unwrap()
+            .with_node_types(["run"]);
+
+        let output = adapter.execute(&sample_request()).await;
+
+        assert!(adapter.handles("run"));
+        assert!(!adapter.handles("http-handler"));
+        assert_eq!(output, Ok(json!({ "ok": true })));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn given_stdio_runner_with_stale_reply_when_executing_then_only_matching_id_is_used() {
+        let script = r#"while read line; do
+  echo '{"jsonrpc":"2.0","id":99,"result":{"stale":true}}'
+  echo '{"jsonrpc":"2.0","method":"log","params":{}}'
+  echo '{"jsonrpc":"2.0","id":1,"result":{"ok":true}}'
+done"#;
+        let adapter = super::StdioJsonRpcAdapter::spawn("sh", ["-c", script]).unwrap();
+
+        let output = adapter.execute(&sample_request()).await;
+
+        assert_eq!(output, Ok(json!({ "ok": true })));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn given_silent_stdio_runner_when_executing_then_it_times_out() {
+        let timeout = std::time::Duration::from_millis(50);
+        let adapter =
+            super::StdioJsonRpcAdapter::spawn("sh", ["-c", "while read line; do :; done"])
+                .unwrap()
+                .with_timeout(timeout);
+
+        let output = adapter.execute(&sample_request()).await;
+
+        assert_eq!(output, Err(AdapterError::Timeout(timeout)));
+    }
+}
@@ -0,0 +1,173 @@
+//! Memoized node outputs between runs.
+//!
+//! Each node's output is stored under a hash of its type, resolved config
+//! and parent outputs. When a later run reaches the node with the same
+//! hash, the stored output is reused instead of executing it again, so an
+//! unchanged HTTP call is not repeated. Nodes opt out with `"cache": false`
+//! in their config; failed outputs are never stored.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use serde_json::Value;
+
+use crate::graph::{Node, NodeId};
+
+/// Config key that disables caching for a node when set to `false`.
+pub const CACHE_CONFIG_KEY: &str = "cache";
+
+#[derive(Debug, Clone, PartialEq)]
+struct CacheEntry {
+    key: u64,
+    output: Value,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeCache {
+    entries: HashMap<NodeId, CacheEntry>,
+}
+
+impl NodeCache {
+    /// The stored output for `node_id`, if it was computed from `key`.
+    #[must_use]
+    pub fn get(&self, node_id: NodeId, key: u64) -> Option<&Value> {
+        self.entries
+            .get(&node_id)
+            .filter(|entry| entry.key == key)
+            .map(|entry| &entry.output)
+    }
+
+    /// Store a successful output; outputs carrying an `error` are skipped.
+    pub fn insert(&mut self, node_id: NodeId, key: u64, output: &Value) {
+        if output.get("error").is_some() {
+            self.entries.remove(&node_id);
+            return;
+        }
+        self.entries.insert(
+            node_id,
+            CacheEntry {
+                key,
+                output: output.clone(),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, node_id: NodeId) {
+        self.entries.remove(&node_id);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Whether a node's outputs may be reused.
+#[must_use]
+pub fn is_cacheable(node: &Node) -> bool {
+    node.config.get(CACHE_CONFIG_KEY).and_then(Value::as_bool) != Some(false)
+}
+
+/// Hash of everything a node's output depends on.
+#[must_use]
+pub fn cache_key(node_type: &str, resolved_config: &Value, parent_outputs: &[Value]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node_type.hash(&mut hasher);
+    // `status` is run bookkeeping written into the config, not an input.
+    let mut config = resolved_config.clone();
+    if let Some(config) = config.as_object_mut() {
+        config.remove("status");
+    }
+    // Object keys serialize in sorted order, so equal values hash equally.
+    config.to_string().hash(&mut hasher);
+    for output in parent_outputs {
+        output.to_string().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::WorkflowBuilder;
+    use crate::graph::Workflow;
+    use serde_json::json;
+
+    fn workflow_with_step(code: &str) -> Workflow {
+        WorkflowBuilder::new()
+            .node("entry", "http-handler")
+            .node("fetch", "run")
+            .config("fetch", &json!({ "code": code }))
+            .node("fresh", "run")
+            .config("fresh", &json!({ "cache": false }))
+            .connect("entry", "fetch")
+            .connect("fetch", "fresh")
+            .build()
+    }
+
+    #[test]
+    fn given_same_inputs_when_hashing_then_keys_match_and_differ_on_change() {
+        let key = cache_key("run", &json!({ "a": 1 }), &[json!({ "x": true })]);
+
+        assert_eq!(
+            key,
+            cache_key("run", &json!({ "a": 1 }), &[json!({ "x": true })])
+        );
+        assert_ne!(
+            key,
+            cache_key("run", &json!({ "a": 2 }), &[json!({ "x": true })])
+        );
+        assert_ne!(key, cache_key("run", &json!({ "a": 1 }), &[]));
+        assert_eq!(
+            key,
+            cache_key(
+                "run",
+                &json!({ "a": 1, "status": "running" }),
+                &[json!({ "x": true })]
+            )
+        );
+    }
+
+    #[test]
+    fn given_error_output_when_inserting_then_it_is_not_cached() {
+        let mut cache = NodeCache::default();
+        let node = NodeId::new();
+        cache.insert(node, 1, &json!({ "ok": true }));
+        cache.insert(node, 2, &json!({ "error": "boom" }));
+
+        assert!(cache.get(node, 1).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn given_second_run_when_nothing_changed_then_cacheable_outputs_are_reused() {
+        let mut workflow = workflow_with_step("first");
+        workflow.run().await;
+        assert_eq!(workflow.node_cache.len(), 2);
+
+        let fetch = workflow.nodes[1].id;
+        let marker = json!({ "marker": "from cache" });
+        let key = workflow.node_cache.entries[&fetch].key;
+        workflow.node_cache.insert(fetch, key, &marker);
+        workflow.run().await;
+        assert_eq!(workflow.nodes[1].last_output, Some(marker.clone()));
+
+        workflow.nodes[1].apply_config_update(&json!({ "code": "second" }));
+        workflow.run().await;
+        assert_ne!(workflow.nodes[1].last_output, Some(marker));
+
+        workflow.clear_node_cache();
+        assert!(workflow.node_cache.is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 //! Execution runtime implementations.
 
+pub mod adapter;
+pub mod cache;
 pub mod execution;
 pub mod service_calls;
 pub mod step_runner;
@@ -1,6 +1,9 @@
 //! Execution step runner.
 
-use crate::graph::{ExecutionState, NodeId, Workflow};
+use tracing::Instrument;
+
+use super::adapter::{ExecutionAdapter, NodeExecutionRequest};
+use crate::graph::{ExecutionState, Node, NodeId, Workflow};
 
 impl Workflow {
     // ===========================================================================
@@ -62,6 +65,40 @@ impl Workflow {
     // ===========================================================================
 
     pub async fn step(&mut self) -> bool {
+        self.step_inner(None, &mut |_| {}).await
+    }
+
+    /// Runs the next step, delegating node types the adapter handles.
+    ///
+    /// Adapter failures are recorded as node errors, exactly like a built-in
+    /// node that returns an `error` field.
+    pub async fn step_with_adapter(&mut self, adapter: &dyn ExecutionAdapter) -> bool {
+        self.step_inner(Some(adapter), &mut |_| {}).await
+    }
+
+    /// Records a finished node's output, marking it failed when the output
+    /// carries an `error`.
+    fn settle_node(&mut self, node_id: NodeId, output: serde_json::Value, duration_ms: u64) {
+        if let Some(n) = self.nodes.iter_mut().find(|n| n.id == node_id) {
+            n.set_duration_ms(Some(duration_ms));
+            if let Some(err) = output.get("error").and_then(serde_json::Value::as_str) {
+                n.error = Some(err.to_string());
+                let _ = Self::set_node_status(n, ExecutionState::Failed);
+            } else {
+                let _ = Self::set_node_status(n, ExecutionState::Completed);
+            }
+            n.executing = false;
+            n.last_output = Some(output);
+        }
+    }
+
+    /// Runs the next step. `on_progress` sees the workflow once the node is
+    /// marked running and again after it settles.
+    pub(super) async fn step_inner<F: FnMut(&Self)>(
+        &mut self,
+        adapter: Option<&dyn ExecutionAdapter>,
+        on_progress: &mut F,
+    ) -> bool {
         if self.current_step >= self.execution_queue.len() {
             self.nodes.iter_mut().for_each(|node| {
                 node.executing = false;
@@ -91,6 +128,8 @@ impl Workflow {
             node.executing = true;
             let _ = Self::set_node_status(node, ExecutionState::Running);
         }
+        on_progress(self);
+        let started = chrono::Utc::now();
 
         let parent_outputs: Vec<serde_json::Value> = self
             .connections
@@ -108,14 +147,42 @@ impl Workflow {
             let node_type = node.node_type.clone();
             let node_config_json = node.config.clone();
             let resolved_config = self.resolve_expressions(&node_config_json);
-            let output = self
-                .execute_node_type(&node_type, &resolved_config, &parent_outputs)
-                .await;
+            let cache_key = super::cache::is_cacheable(node)
+                .then(|| super::cache::cache_key(&node_type, &resolved_config, &parent_outputs));
+            let cached = cache_key.and_then(|key| self.node_cache.get(node_id, key).cloned());
+            let span = node_span(node, self.current_step, cached.is_some());
+            let output = match (
+                cached,
+                adapter.filter(|adapter| adapter.handles(&node_type)),
+            ) {
+                (Some(output), _) => output,
+                (None, Some(adapter)) => {
+                    let request = NodeExecutionRequest {
+                        node_id,
+                        node_type: node_type.clone(),
+                        config: resolved_config,
+                        parent_outputs,
+                        step: self.current_step,
+                    };
+                    adapter
+                        .execute(&request)
+                        .instrument(span.clone())
+                        .await
+                        .unwrap_or_else(|err| serde_json::json!({ "error": err.to_string() }))
+                }
+                (None, None) => {
+                    self.execute_node_type(&node_type, &resolved_config, &parent_outputs)
+                        .instrument(span.clone())
+                        .await
+                }
+            };
+            record_node_error(&span, &output);
 
             // Check memory limit after node execution
             if let Err(memory_error) = self.check_and_update_memory(&output) {
                 // Update node status to failed due to memory limit
                 if let Some(n) = self.nodes.iter_mut().find(|n| n.id == node_id) {
+                    n.set_duration_ms(Some(elapsed_ms(started)));
                     n.error = Some(memory_error.to_string());
                     let _ = Self::set_node_status(n, ExecutionState::Failed);
                     n.executing = false;
@@ -125,6 +192,7 @@ impl Workflow {
                 self.execution_failed = true;
                 // Continue to next step to maintain queue consistency
                 self.current_step += 1;
+                on_progress(self);
                 return true;
             }
 
@@ -132,19 +200,38 @@ impl Workflow {
                 self.execute_condition_and_skip_branches(node_id, &output);
             }
 
-            if let Some(n) = self.nodes.iter_mut().find(|n| n.id == node_id) {
-                if let Some(err) = output.get("error").and_then(serde_json::Value::as_str) {
-                    n.error = Some(err.to_string());
-                    let _ = Self::set_node_status(n, ExecutionState::Failed);
-                } else {
-                    let _ = Self::set_node_status(n, ExecutionState::Completed);
-                }
-                n.executing = false;
-                n.last_output = Some(output);
+            match cache_key {
+                Some(key) => self.node_cache.insert(node_id, key, &output),
+                None => self.node_cache.remove(node_id),
             }
+
+            self.settle_node(node_id, output, elapsed_ms(started));
         }
 
         self.current_step += 1;
+        on_progress(self);
         true
     }
 }
+
+fn node_span(node: &Node, step: usize, cached: bool) -> tracing::Span {
+    tracing::info_span!(
+        "workflow.node",
+        node.id = %node.id,
+        node.name = %node.name,
+        "node.type" = %node.node_type,
+        step,
+        cached,
+        error = tracing::field::Empty,
+    )
+}
+
+fn record_node_error(span: &tracing::Span, output: &serde_json::Value) {
+    if let Some(error) = output.get("error").and_then(serde_json::Value::as_str) {
+        span.record("error", error);
+    }
+}
+
+fn elapsed_ms(started: chrono::DateTime<chrono::Utc>) -> u64 {
+    u64::try_from((chrono::Utc::now() - started).num_milliseconds()).unwrap_or(0)
+}
@@ -1,5 +1,7 @@
 //! Workflow runner.
 
+use super::adapter::ExecutionAdapter;
+use crate::graph::events::{self, WorkflowEvent};
 use crate::graph::{ExecutionState, NodeCategory, RunRecord, Workflow};
 
 impl Workflow {
@@ -8,8 +10,41 @@ impl Workflow {
     // ===========================================================================
 
     pub async fn run(&mut self) {
+        self.run_inner(None, &mut |_| {}).await;
+    }
+
+    /// Runs the workflow, delegating node types the adapter handles.
+    pub async fn run_with_adapter(&mut self, adapter: &dyn ExecutionAdapter) {
+        self.run_inner(Some(adapter), &mut |_| {}).await;
+    }
+
+    /// Runs the workflow, calling `on_progress` whenever a node starts or
+    /// settles so callers can mirror statuses while the run is in flight.
+    pub async fn run_with_progress<F: FnMut(&Self)>(&mut self, mut on_progress: F) {
+        self.run_inner(None, &mut on_progress).await;
+    }
+
+    #[tracing::instrument(
+        name = "workflow.run",
+        skip_all,
+        fields(nodes = self.nodes.len(), run_id = tracing::field::Empty, success = tracing::field::Empty)
+    )]
+    async fn run_inner<F: FnMut(&Self)>(
+        &mut self,
+        adapter: Option<&dyn ExecutionAdapter>,
+        on_progress: &mut F,
+    ) {
+        // A finished run leaves its queue behind; drop it so the workflow
+        // can run again and reuse cached outputs.
+        if self.current_step >= self.execution_queue.len() {
+            self.execution_queue.clear();
+            self.current_step = 0;
+        }
         let _ = self.prepare_run();
         let start_time = chrono::Utc::now();
+        events::emit(&WorkflowEvent::RunStarted {
+            node_count: self.nodes.len(),
+        });
         let mut results = std::collections::HashMap::new();
 
         if self.nodes.is_empty()
@@ -18,20 +53,18 @@ impl Workflow {
                 .iter()
                 .any(|node| node.category == NodeCategory::Entry)
         {
-            self.history.push(RunRecord {
+            self.record_run(RunRecord {
                 id: uuid::Uuid::new_v4(),
                 timestamp: start_time,
                 results,
                 success: false,
                 restate_invocation_id: None,
+                durations: std::collections::HashMap::new(),
             });
-            if self.history.len() > 10 {
-                self.history.remove(0);
-            }
             return;
         }
 
-        while !self.execution_failed && self.step().await {
+        while !self.execution_failed && self.step_inner(adapter, on_progress).await {
             if let Some(id) = self
                 .execution_queue
                 .get(self.current_step.saturating_sub(1))
@@ -67,14 +100,36 @@ impl Workflow {
                     .map(str::to_string)
             });
 
-        self.history.push(RunRecord {
+        crate::graph::redaction::RedactionPolicy::default()
+            .redact_results(&self.nodes, &mut results);
+
+        let durations = self
+            .nodes
+            .iter()
+            .filter_map(|node| node.duration_ms().map(|ms| (node.id, ms)))
+            .collect();
+
+        self.record_run(RunRecord {
             id: uuid::Uuid::new_v4(),
             timestamp: start_time,
             results,
             success,
             restate_invocation_id,
+            durations,
         });
+    }
 
+    /// Appends `record` to the bounded history and announces the finished run.
+    fn record_run(&mut self, record: RunRecord) {
+        tracing::Span::current()
+            .record("run_id", tracing::field::display(record.id))
+            .record("success", record.success);
+        events::emit(&WorkflowEvent::RunCompleted {
+            run_id: record.id,
+            success: record.success,
+            duration_ms: (chrono::Utc::now() - record.timestamp).num_milliseconds(),
+        });
+        self.history.push(record);
         if self.history.len() > 10 {
             let _ = self.history.remove(0);
         }
@@ -0,0 +1,318 @@
+//! Best-effort migration of n8n and Node-RED flows into [`Workflow`]s.
+//!
+//! Node types with a close equivalent are mapped onto it; anything else
+//! becomes a generic `run` node. Either way the original type and settings
+//! are kept under `imported_from` in the node's config, so nothing from the
+//! source flow is lost.
+
+mod n8n;
+mod node_red;
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{Connection, Node, NodeId, PortName, Workflow, WorkflowNode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FlowSource {
+    N8n,
+    NodeRed,
+}
+
+impl FlowSource {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::N8n => "n8n",
+            Self::NodeRed => "Node-RED",
+        }
+    }
+}
+
+impl FromStr for FlowSource {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "n8n" => Ok(Self::N8n),
+            "node-red" | "nodered" => Ok(Self::NodeRed),
+            _ => Err(format!(
+                "Unknown flow source: {value}. Use 'n8n' or 'node-red'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum FlowImportError {
+    #[error("Invalid flow JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Not a {} flow: {reason}", tool.label())]
+    Format { tool: FlowSource, reason: String },
+    #[error("Unrecognized flow format; expected an n8n workflow or a Node-RED flow")]
+    Unrecognized,
+}
+
+/// A source node that had no equivalent and was imported as `run`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnmappedNode {
+    pub node_id: NodeId,
+    pub name: String,
+    pub original_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlowImport {
+    pub source: FlowSource,
+    pub workflow: Workflow,
+    pub unmapped: Vec<UnmappedNode>,
+    /// Source connections whose endpoints were not imported.
+    pub skipped_connections: usize,
+}
+
+/// Guess the source tool from the document's shape.
+#[must_use]
+pub fn detect_source(document: &Value) -> Option<FlowSource> {
+    if document.get("nodes").is_some_and(Value::is_array)
+        && document.get("connections").is_some_and(Value::is_object)
+    {
+        Some(FlowSource::N8n)
+    } else if document.as_array().is_some_and(|items| {
+        items
+            .iter()
+            .any(|item| item.get("wires").is_some() && item.get("type").is_some())
+    }) {
+        Some(FlowSource::NodeRed)
+    } else {
+        None
+    }
+}
+
+/// Import `json` from `source`, or from the detected source when `None`.
+///
+/// # Errors
+/// Returns an error if `json` is not valid JSON or not a flow of the
+/// expected tool.
+pub fn import_flow(json: &str, source: Option<FlowSource>) -> Result<FlowImport, FlowImportError> {
+    let document: Value = serde_json::from_str(json)?;
+    let source = source
+        .or_else(|| detect_source(&document))
+        .ok_or(FlowImportError::Unrecognized)?;
+    let flow = match source {
+        FlowSource::N8n => n8n::parse(&document)?,
+        FlowSource::NodeRed => node_red::parse(&document)?,
+    };
+    Ok(flow.build(source))
+}
+
+/// A source node translated to this editor's vocabulary.
+struct ForeignNode {
+    key: String,
+    name: String,
+    original_type: String,
+    /// Our node type, or `None` to import as `run`.
+    node_type: Option<&'static str>,
+    config: Value,
+    original: Value,
+    x: f32,
+    y: f32,
+}
+
+struct ForeignEdge {
+    source: String,
+    target: String,
+    source_port: String,
+}
+
+struct ForeignFlow {
+    nodes: Vec<ForeignNode>,
+    edges: Vec<ForeignEdge>,
+}
+
+impl ForeignFlow {
+    fn build(self, source: FlowSource) -> FlowImport {
+        let mut workflow = Workflow::new();
+        let mut unmapped = Vec::new();
+        let mut ids = std::collections::HashMap::new();
+
+        for foreign in self.nodes {
+            let node_type = foreign.node_type.unwrap_or("run");
+            let workflow_node = WorkflowNode::from_str(node_type).unwrap_or_default();
+            let mut node =
+                Node::from_workflow_node(foreign.name.clone(), workflow_node, foreign.x, foreign.y);
+            let mut config = match foreign.config {
+                Value::Object(map) => map,
+                _ => serde_json::Map::new(),
+            };
+            config.insert(
+                "imported_from".to_string(),
+                json!({
+                    "source": source,
+                    "type": foreign.original_type,
+                    "node": foreign.original,
+                }),
+            );
+            node.apply_config_update(&Value::Object(config));
+            if foreign.node_type.is_none() {
+                unmapped.push(UnmappedNode {
+                    node_id: node.id,
+                    name: foreign.name,
+                    original_type: foreign.original_type,
+                });
+            }
+            ids.insert(foreign.key, node.id);
+            workflow.nodes.push(node);
+        }
+
+        let mut skipped_connections = 0;
+        for edge in self.edges {
+            let (Some(&source), Some(&target)) = (ids.get(&edge.source), ids.get(&edge.target))
+            else {
+                skipped_connections += 1;
+                continue;
+            };
+            workflow.connections.push(Connection {
+                id: Uuid::new_v4(),
+                source,
+                target,
+                source_port: PortName::from(edge.source_port.as_str()),
+                target_port: PortName::from("main"),
+            });
+        }
+
+        FlowImport {
+            source,
+            workflow,
+            unmapped,
+            skipped_connections,
+        }
+    }
+}
+
+fn text(value: &Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn coordinate(value: Option<&Value>) -> f32 {
+    value.and_then(Value::as_f64).unwrap_or_default() as f32
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    const N8N_FLOW: &str = r#"{
+        "name": "Signup",
+        "nodes": [
+            {"name": "Webhook", "type": "n8n-nodes-base.webhook", "position": [0, 0],
+             "parameters": {"path": "signup", "httpMethod": "POST"}},
+            {"name": "Is adult", "type": "n8n-nodes-base.if", "position": [200, 0],
+             "parameters": {"conditions": {"number": [{"value1": "={{$json.age}}", "operation": "largerEqual", "value2": 18}]}}},
+            {"name": "Welcome mail", "type": "n8n-nodes-base.gmail", "position": [400, -100],
+             "parameters": {"subject": "Welcome"}},
+            {"name": "Wait", "type": "n8n-nodes-base.wait", "position": [400, 100],
+             "parameters": {"amount": 2, "unit": "minutes"}}
+        ],
+        "connections": {
+            "Webhook": {"main": [[{"node": "Is adult", "type": "main", "index": 0}]]},
+            "Is adult": {"main": [
+                [{"node": "Welcome mail", "type": "main", "index": 0}],
+                [{"node": "Wait", "type": "main", "index": 0}, {"node": "Gone", "type": "main", "index": 0}]
+            ]}
+        }
+    }"#;
+
+    const NODE_RED_FLOW: &str = r#"[
+        {"id": "tab1", "type": "tab", "label": "Flow 1"},
+        {"id": "a", "type": "http in", "z": "tab1", "name": "", "url": "/orders", "method": "post",
+         "x": 100, "y": 80, "wires": [["b"]]},
+        {"id": "b", "type": "function", "z": "tab1", "name": "Price", "func": "return msg;",
+         "x": 300, "y": 80, "wires": [["c"]]},
+        {"id": "c", "type": "http response", "z": "tab1", "name": "Reply", "statusCode": "201",
+         "x": 500, "y": 80, "wires": []},
+        {"id": "broker", "type": "mqtt-broker", "broker": "localhost"}
+    ]"#;
+
+    fn node<'a>(workflow: &'a Workflow, name: &str) -> &'a Node {
+        workflow
+            .nodes
+            .iter()
+            .find(|node| node.name == name)
+            .unwrap_or_else(|| panic!("no node named {name}"))
+    }
+
+    #[test]
+    fn given_n8n_workflow_when_importing_then_nodes_map_and_branches_keep_their_ports() {
+        let imported = import_flow(N8N_FLOW, None).unwrap();
+
+        assert_eq!(imported.source, FlowSource::N8n);
+        let workflow = &imported.workflow;
+        assert_eq!(node(workflow, "Webhook").node_type, "http-handler");
+        assert_eq!(node(workflow, "Webhook").config["path"], "/signup");
+        assert_eq!(node(workflow, "Wait").config["duration_ms"], 120_000);
+        assert_eq!(node(workflow, "Is adult").node_type, "condition");
+        assert_eq!(node(workflow, "Welcome mail").node_type, "run");
+        assert_eq!(
+            node(workflow, "Welcome mail").config["imported_from"]["node"]["parameters"]["subject"],
+            "Welcome"
+        );
+        assert_eq!(imported.unmapped.len(), 1);
+        assert_eq!(imported.unmapped[0].original_type, "n8n-nodes-base.gmail");
+        assert_eq!(imported.skipped_connections, 1);
+        let mut ports = workflow
+            .connections
+            .iter()
+            .map(|connection| connection.source_port.0.as_str())
+            .collect::<Vec<_>>();
+        ports.sort_unstable();
+        assert_eq!(ports, ["false", "main", "true"]);
+    }
+
+    #[test]
+    fn given_node_red_flow_when_importing_then_tabs_and_config_nodes_are_skipped() {
+        let imported = import_flow(NODE_RED_FLOW, None).unwrap();
+
+        assert_eq!(imported.source, FlowSource::NodeRed);
+        let workflow = &imported.workflow;
+        assert_eq!(workflow.nodes.len(), 3);
+        assert_eq!(workflow.connections.len(), 2);
+        let entry = node(workflow, "http in");
+        assert_eq!(entry.node_type, "http-handler");
+        assert_eq!(entry.config["method"], "POST");
+        assert_eq!((entry.x, entry.y), (100.0, 80.0));
+        assert_eq!(node(workflow, "Price").config["code"], "return msg;");
+        assert_eq!(node(workflow, "Reply").node_type, "run");
+        assert_eq!(
+            node(workflow, "Reply").config["imported_from"]["type"],
+            "http response"
+        );
+    }
+
+    #[test]
+    fn given_unrelated_json_when_importing_then_format_is_rejected() {
+        assert!(matches!(
+            import_flow(r#"{"hello": "world"}"#, None),
+            Err(FlowImportError::Unrecognized)
+        ));
+        assert!(matches!(
+            import_flow(r#"{"nodes": 3}"#, Some(FlowSource::N8n)),
+            Err(FlowImportError::Format { .. })
+        ));
+    }
+}
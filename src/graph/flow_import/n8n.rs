@@ -0,0 +1,141 @@
+//! n8n workflow exports: `{"nodes": [...], "connections": {...}}`, with
+//! connections keyed by node name.
+
+use serde_json::{json, Value};
+
+use super::{coordinate, text, FlowImportError, FlowSource, ForeignEdge, ForeignFlow, ForeignNode};
+
+pub(super) fn parse(document: &Value) -> Result<ForeignFlow, FlowImportError> {
+    let nodes = document
+        .get("nodes")
+        .and_then(Value::as_array)
+        .ok_or_else(|| format_error("missing 'nodes' array"))?;
+
+    let nodes = nodes
+        .iter()
+        .filter_map(|node| {
+            let name = text(node, "name")?;
+            let original_type = text(node, "type").unwrap_or_default();
+            let parameters = node.get("parameters").cloned().unwrap_or(Value::Null);
+            let (node_type, config) = map_node(&original_type, &parameters);
+            let position = node.get("position").and_then(Value::as_array);
+            Some(ForeignNode {
+                key: name.clone(),
+                name,
+                original_type,
+                node_type,
+                config,
+                original: node.clone(),
+                x: coordinate(position.and_then(|position| position.first())),
+                y: coordinate(position.and_then(|position| position.get(1))),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let conditions = nodes
+        .iter()
+        .filter(|node| node.node_type == Some("condition"))
+        .map(|node| node.key.as_str())
+        .collect::<Vec<_>>();
+    let mut edges = Vec::new();
+    for (source, outputs) in document
+        .get("connections")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+    {
+        let branches = outputs.get("main").and_then(Value::as_array);
+        for (index, branch) in branches.into_iter().flatten().enumerate() {
+            for target in branch.as_array().into_iter().flatten() {
+                let Some(target) = text(target, "node") else {
+                    continue;
+                };
+                edges.push(ForeignEdge {
+                    source: source.clone(),
+                    target,
+                    source_port: output_port(conditions.contains(&source.as_str()), index),
+                });
+            }
+        }
+    }
+
+    Ok(ForeignFlow { nodes, edges })
+}
+
+fn format_error(reason: &str) -> FlowImportError {
+    FlowImportError::Format {
+        tool: FlowSource::N8n,
+        reason: reason.to_string(),
+    }
+}
+
+/// An `If` node's first output is its true branch, the second its false one.
+fn output_port(is_condition: bool, index: usize) -> String {
+    match (is_condition, index) {
+        (true, 0) => "true".to_string(),
+        (true, 1) => "false".to_string(),
+        _ => "main".to_string(),
+    }
+}
+
+fn map_node(node_type: &str, parameters: &Value) -> (Option<&'static str>, Value) {
+    let kind = node_type.rsplit('.').next().unwrap_or(node_type);
+    match kind {
+        "webhook" => (
+            Some("http-handler"),
+            json!({
+                "path": text(parameters, "path").map(|path| format!("/{}", path.trim_start_matches('/'))),
+                "method": text(parameters, "httpMethod").unwrap_or_else(|| "GET".to_string()),
+            }),
+        ),
+        "httpRequest" => (Some("http-call"), json!({ "url": text(parameters, "url") })),
+        "scheduleTrigger" | "cron" => (
+            Some("cron-trigger"),
+            json!({ "schedule": cron_expression(parameters) }),
+        ),
+        "if" => (
+            Some("condition"),
+            json!({ "expression": parameters.get("conditions").map(Value::to_string) }),
+        ),
+        "switch" => (
+            Some("switch"),
+            json!({ "expression": text(parameters, "value1").or_else(|| text(parameters, "rules")) }),
+        ),
+        "wait" => (Some("sleep"), json!({ "duration_ms": wait_ms(parameters) })),
+        "code" | "function" | "functionItem" => (
+            Some("run"),
+            json!({
+                "code": text(parameters, "jsCode")
+                    .or_else(|| text(parameters, "functionCode"))
+                    .or_else(|| text(parameters, "pythonCode")),
+            }),
+        ),
+        "executeWorkflow" => (
+            Some("workflow-call"),
+            json!({ "workflow_name": text(parameters, "workflowId") }),
+        ),
+        "splitInBatches" => (Some("loop"), json!({})),
+        _ => (None, json!({})),
+    }
+}
+
+fn cron_expression(parameters: &Value) -> Option<String> {
+    text(parameters, "cronExpression").or_else(|| {
+        parameters
+            .pointer("/rule/interval/0/expression")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    })
+}
+
+fn wait_ms(parameters: &Value) -> Option<u64> {
+    let amount = parameters.get("amount").and_then(Value::as_f64)?;
+    let unit_ms = match parameters.get("unit").and_then(Value::as_str) {
+        Some("minutes") => 60_000.0,
+        Some("hours") => 3_600_000.0,
+        Some("days") => 86_400_000.0,
+        _ => 1_000.0,
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((amount * unit_ms) as u64)
+}
@@ -0,0 +1,97 @@
+//! Node-RED flow exports: a flat array of nodes wired by id, plus tab and
+//! configuration entries that have no position on the canvas.
+
+use serde_json::{json, Value};
+
+use super::{coordinate, text, FlowImportError, FlowSource, ForeignEdge, ForeignFlow, ForeignNode};
+
+pub(super) fn parse(document: &Value) -> Result<ForeignFlow, FlowImportError> {
+    let items = document.as_array().ok_or_else(|| FlowImportError::Format {
+        tool: FlowSource::NodeRed,
+        reason: "expected an array of nodes".to_string(),
+    })?;
+
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    // Tabs, subflow definitions and config nodes carry no wires.
+    for item in items.iter().filter(|item| item.get("wires").is_some()) {
+        let (Some(id), Some(original_type)) = (text(item, "id"), text(item, "type")) else {
+            continue;
+        };
+        for (index, outputs) in item["wires"].as_array().into_iter().flatten().enumerate() {
+            for target in outputs.as_array().into_iter().flatten() {
+                if let Some(target) = target.as_str() {
+                    edges.push(ForeignEdge {
+                        source: id.clone(),
+                        target: target.to_string(),
+                        source_port: output_port(&original_type, index),
+                    });
+                }
+            }
+        }
+        let (node_type, config) = map_node(&original_type, item);
+        nodes.push(ForeignNode {
+            key: id,
+            name: text(item, "name").unwrap_or_else(|| original_type.clone()),
+            original_type,
+            node_type,
+            config,
+            original: item.clone(),
+            x: coordinate(item.get("x")),
+            y: coordinate(item.get("y")),
+        });
+    }
+
+    Ok(ForeignFlow { nodes, edges })
+}
+
+/// Outputs beyond the first keep their index, since a switch can have many.
+fn output_port(node_type: &str, index: usize) -> String {
+    if node_type == "switch" && index > 0 {
+        format!("case-{index}")
+    } else {
+        "main".to_string()
+    }
+}
+
+fn map_node(node_type: &str, node: &Value) -> (Option<&'static str>, Value) {
+    match node_type {
+        "http in" => (
+            Some("http-handler"),
+            json!({
+                "path": text(node, "url"),
+                "method": text(node, "method").map(|method| method.to_uppercase()),
+            }),
+        ),
+        "http request" => (Some("http-call"), json!({ "url": text(node, "url") })),
+        "inject" if text(node, "crontab").is_some() => (
+            Some("cron-trigger"),
+            json!({ "schedule": text(node, "crontab") }),
+        ),
+        "function" => (Some("run"), json!({ "code": text(node, "func") })),
+        "switch" => (
+            Some("switch"),
+            json!({ "expression": text(node, "property") }),
+        ),
+        "delay" => (Some("sleep"), json!({ "duration_ms": delay_ms(node) })),
+        _ => (None, json!({})),
+    }
+}
+
+fn delay_ms(node: &Value) -> Option<u64> {
+    // Node-RED stores the delay as a string, e.g. `"timeout": "5"`.
+    let amount = node.get("timeout").and_then(|timeout| {
+        timeout
+            .as_f64()
+            .or_else(|| timeout.as_str().and_then(|text| text.parse().ok()))
+    })?;
+    let unit_ms = match node.get("timeoutUnits").and_then(Value::as_str) {
+        Some("milliseconds") => 1.0,
+        Some("minutes") => 60_000.0,
+        Some("hours") => 3_600_000.0,
+        Some("days") => 86_400_000.0,
+        _ => 1_000.0,
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((amount * unit_ms) as u64)
+}
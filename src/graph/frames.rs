@@ -0,0 +1,328 @@
+//! Canvas frames: titled background rectangles that group nodes spatially.
+//!
+//! Frames carry no execution meaning. A node belongs to a frame while its
+//! whole box lies inside the frame's rectangle, so membership follows the
+//! layout rather than being stored. Frames render beneath edges and nodes;
+//! among themselves, later frames in [`Workflow::frames`] draw on top.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Node, NodeId, Workflow};
+
+/// Node box size, matching `ui::constants`.
+const NODE_WIDTH: f32 = 220.0;
+const NODE_HEIGHT: f32 = 68.0;
+
+/// Height of the draggable title bar along the top edge.
+pub const FRAME_TITLE_HEIGHT: f32 = 28.0;
+/// Side of the square resize grip in the bottom-right corner.
+pub const FRAME_RESIZE_HANDLE: f32 = 14.0;
+pub const FRAME_MIN_WIDTH: f32 = 160.0;
+pub const FRAME_MIN_HEIGHT: f32 = 96.0;
+/// Space left around the nodes when framing a selection.
+const FRAME_PADDING: f32 = 32.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FrameId(pub Uuid);
+
+impl FrameId {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl Default for FrameId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for FrameId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    pub id: FrameId,
+    pub title: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// The part of a frame under a canvas point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameHit {
+    /// The bottom-right grip; dragging it resizes the frame.
+    Resize,
+    /// The title bar; dragging it moves the frame and its nodes.
+    Title,
+    /// The rest of the rectangle. It does not capture the pointer, so
+    /// marquee selection and panning still start on top of a frame.
+    Body,
+}
+
+impl Frame {
+    #[must_use]
+    pub fn new(title: impl Into<String>, x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            id: FrameId::new(),
+            title: title.into(),
+            x,
+            y,
+            width: width.max(FRAME_MIN_WIDTH),
+            height: height.max(FRAME_MIN_HEIGHT),
+        }
+    }
+
+    #[must_use]
+    pub fn contains_rect(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && x + width <= self.x + self.width
+            && y + height <= self.y + self.height
+    }
+
+    #[must_use]
+    pub fn contains_node(&self, node: &Node) -> bool {
+        self.contains_rect(node.x, node.y, NODE_WIDTH, NODE_HEIGHT)
+    }
+
+    #[must_use]
+    pub fn hit(&self, x: f32, y: f32) -> Option<FrameHit> {
+        let right = self.x + self.width;
+        let bottom = self.y + self.height;
+        if x < self.x || y < self.y || x > right || y > bottom {
+            return None;
+        }
+        if x >= right - FRAME_RESIZE_HANDLE && y >= bottom - FRAME_RESIZE_HANDLE {
+            Some(FrameHit::Resize)
+        } else if y <= self.y + FRAME_TITLE_HEIGHT {
+            Some(FrameHit::Title)
+        } else {
+            Some(FrameHit::Body)
+        }
+    }
+}
+
+impl Workflow {
+    /// Adds a frame and returns its id. The new frame draws on top of
+    /// existing frames.
+    pub fn add_frame(&mut self, title: &str, x: f32, y: f32, width: f32, height: f32) -> FrameId {
+        let frame = Frame::new(title, x, y, width, height);
+        let id = frame.id;
+        self.frames.push(frame);
+        id
+    }
+
+    /// Adds a frame wrapping the given nodes with some padding. Returns
+    /// `None` if none of the ids match a node.
+    pub fn frame_nodes(&mut self, title: &str, node_ids: &[NodeId]) -> Option<FrameId> {
+        let (min_x, min_y, max_x, max_y) = self
+            .nodes
+            .iter()
+            .filter(|node| node_ids.contains(&node.id))
+            .fold(None, |bounds: Option<(f32, f32, f32, f32)>, node| {
+                let (left, top, right, bottom) =
+                    (node.x, node.y, node.x + NODE_WIDTH, node.y + NODE_HEIGHT);
+                Some(
+                    bounds.map_or((left, top, right, bottom), |(x0, y0, x1, y1)| {
+                        (x0.min(left), y0.min(top), x1.max(right), y1.max(bottom))
+                    }),
+                )
+            })?;
+        let x = min_x - FRAME_PADDING;
+        let y = min_y - FRAME_PADDING - FRAME_TITLE_HEIGHT;
+        Some(self.add_frame(
+            title,
+            x,
+            y,
+            max_x + FRAME_PADDING - x,
+            max_y + FRAME_PADDING - y,
+        ))
+    }
+
+    pub fn remove_frame(&mut self, id: FrameId) {
+        self.frames.retain(|frame| frame.id != id);
+    }
+
+    pub fn rename_frame(&mut self, id: FrameId, title: &str) {
+        if let Some(frame) = self.frames.iter_mut().find(|frame| frame.id == id) {
+            frame.title = title.to_string();
+        }
+    }
+
+    /// Raises a frame above every other frame, keeping the frames nested
+    /// inside it on top of it.
+    pub fn bring_frame_to_front(&mut self, id: FrameId) {
+        let Some(frame) = self.frames.iter().find(|frame| frame.id == id).cloned() else {
+            return;
+        };
+        let (raised, mut frames): (Vec<Frame>, Vec<Frame>) = std::mem::take(&mut self.frames)
+            .into_iter()
+            .partition(|other| {
+                other.id == id || frame.contains_rect(other.x, other.y, other.width, other.height)
+            });
+        frames.extend(raised);
+        self.frames = frames;
+    }
+
+    /// Nodes lying wholly inside the frame.
+    #[must_use]
+    pub fn frame_members(&self, id: FrameId) -> Vec<NodeId> {
+        self.frames
+            .iter()
+            .find(|frame| frame.id == id)
+            .map(|frame| {
+                self.nodes
+                    .iter()
+                    .filter(|node| frame.contains_node(node))
+                    .map(|node| node.id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Moves a frame together with the nodes and frames nested inside it.
+    ///
+    /// Members are shifted by the exact delta rather than snapped, so they
+    /// keep their placement relative to the frame.
+    pub fn move_frame(&mut self, id: FrameId, dx: f32, dy: f32) {
+        if !dx.is_finite() || !dy.is_finite() {
+            return;
+        }
+        let Some(frame) = self.frames.iter().find(|frame| frame.id == id).cloned() else {
+            return;
+        };
+        self.nodes
+            .iter_mut()
+            .filter(|node| frame.contains_node(node))
+            .for_each(|node| {
+                node.x += dx;
+                node.y += dy;
+            });
+        self.frames
+            .iter_mut()
+            .filter(|other| {
+                other.id == id || frame.contains_rect(other.x, other.y, other.width, other.height)
+            })
+            .for_each(|other| {
+                other.x += dx;
+                other.y += dy;
+            });
+    }
+
+    /// Grows or shrinks a frame from its bottom-right corner. Nodes do not
+    /// move, so resizing changes which nodes the frame contains.
+    pub fn resize_frame(&mut self, id: FrameId, dw: f32, dh: f32) {
+        if !dw.is_finite() || !dh.is_finite() {
+            return;
+        }
+        if let Some(frame) = self.frames.iter_mut().find(|frame| frame.id == id) {
+            frame.width = (frame.width + dw).max(FRAME_MIN_WIDTH);
+            frame.height = (frame.height + dh).max(FRAME_MIN_HEIGHT);
+        }
+    }
+
+    /// The topmost frame under a canvas point and the part that was hit.
+    #[must_use]
+    pub fn frame_at(&self, x: f32, y: f32) -> Option<(FrameId, FrameHit)> {
+        self.frames
+            .iter()
+            .rev()
+            .find_map(|frame| frame.hit(x, y).map(|hit| (frame.id, hit)))
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_frame_when_moved_then_contained_nodes_and_nested_frames_follow() {
+        let mut workflow = Workflow::new();
+        let inside = workflow.add_node("run", 100.0, 100.0);
+        let outside = workflow.add_node("run", 900.0, 100.0);
+        let outer = workflow.add_frame("Checkout", 50.0, 50.0, 500.0, 300.0);
+        let inner = workflow.add_frame("Payment", 60.0, 60.0, 300.0, 200.0);
+
+        workflow.move_frame(outer, 15.0, -5.0);
+
+        let position = |id| {
+            workflow
+                .nodes
+                .iter()
+                .find(|node| node.id == id)
+                .map(|node| (node.x, node.y))
+        };
+        assert_eq!(position(inside), Some((115.0, 95.0)));
+        assert_eq!(position(outside), Some((900.0, 100.0)));
+        assert_eq!((workflow.frames[0].x, workflow.frames[0].y), (65.0, 45.0));
+        assert_eq!((workflow.frames[1].x, workflow.frames[1].y), (75.0, 55.0));
+        assert_eq!(workflow.frame_members(inner), vec![inside]);
+
+        workflow.bring_frame_to_front(outer);
+        let order: Vec<FrameId> = workflow.frames.iter().map(|frame| frame.id).collect();
+        assert_eq!(order, vec![outer, inner]);
+    }
+
+    #[test]
+    fn given_overlapping_frames_when_hit_testing_then_topmost_part_is_reported() {
+        let mut workflow = Workflow::new();
+        let back = workflow.add_frame("Back", 0.0, 0.0, 400.0, 400.0);
+        let front = workflow.add_frame("Front", 200.0, 200.0, 300.0, 300.0);
+
+        assert_eq!(workflow.frame_at(10.0, 10.0), Some((back, FrameHit::Title)));
+        assert_eq!(
+            workflow.frame_at(250.0, 210.0),
+            Some((front, FrameHit::Title))
+        );
+        assert_eq!(
+            workflow.frame_at(495.0, 495.0),
+            Some((front, FrameHit::Resize))
+        );
+        assert_eq!(workflow.frame_at(600.0, 600.0), None);
+
+        workflow.bring_frame_to_front(back);
+        assert_eq!(
+            workflow.frame_at(250.0, 300.0),
+            Some((back, FrameHit::Body))
+        );
+    }
+
+    #[test]
+    fn given_selected_nodes_when_framing_then_frame_wraps_them_and_round_trips() {
+        let mut workflow = Workflow::new();
+        let a = workflow.add_node("run", 100.0, 100.0);
+        let b = workflow.add_node("run", 400.0, 300.0);
+
+        let id = workflow.frame_nodes("Group", &[a, b]).unwrap();
+        workflow.resize_frame(id, -10_000.0, 0.0);
+        assert_eq!(workflow.frames[0].width, FRAME_MIN_WIDTH);
+        workflow.resize_frame(id, 10_000.0, 0.0);
+
+        let mut members = workflow.frame_members(id);
+        members.sort_by_key(|id| id.0);
+        let mut expected = vec![a, b];
+        expected.sort_by_key(|id| id.0);
+        assert_eq!(members, expected);
+        assert!(workflow.frame_nodes("Empty", &[NodeId::new()]).is_none());
+
+        let json = serde_json::to_string(&workflow).unwrap();
+        let restored: Workflow = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.frames, workflow.frames);
+    }
+}
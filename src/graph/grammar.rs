@@ -0,0 +1,267 @@
+//! Declarative structural constraints for workflow node types.
+//!
+//! Each `WorkflowNode` variant declares the structural rules it must satisfy
+//! via [`WorkflowNode::structural_constraints`]. The rules are enforced in two
+//! places:
+//! - at connection time, for rules a single new edge can violate
+//! - by the graph linter (`validate_workflow`), for rules that only hold once
+//!   the graph is complete
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::graph_ops;
+use super::workflow_node::WorkflowNode;
+use super::{Connection, Node, NodeId};
+
+/// A structural rule attached to a node type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuralConstraint {
+    /// The node may not be the target of any connection.
+    NoIncomingEdges,
+    /// The node must have exactly one outgoing connection.
+    ExactlyOneSuccessor,
+    /// The node must be downstream of at least one node of the listed types.
+    DownstreamOf(&'static [&'static str]),
+}
+
+impl fmt::Display for StructuralConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoIncomingEdges => write!(f, "cannot have incoming edges"),
+            Self::ExactlyOneSuccessor => write!(f, "must have exactly one guarded successor"),
+            Self::DownstreamOf(node_types) => {
+                write!(f, "must be downstream of {}", node_types.join(" or "))
+            }
+        }
+    }
+}
+
+/// A violated structural constraint on a specific node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrammarViolation {
+    pub node_id: NodeId,
+    pub node_type: String,
+    pub constraint: StructuralConstraint,
+}
+
+impl fmt::Display for GrammarViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' {}", self.node_type, self.constraint)
+    }
+}
+
+/// Returns the constraints declared for a persisted node.
+#[must_use]
+pub fn constraints_for(node: &Node) -> &'static [StructuralConstraint] {
+    node.node_type
+        .parse::<WorkflowNode>()
+        .map_or(&[], |workflow_node| workflow_node.structural_constraints())
+}
+
+/// Checks the constraints a single new edge could break.
+///
+/// Only rules that are monotonic in the edge set are checked here, so a graph
+/// can still be built incrementally in any order.
+///
+/// # Errors
+///
+/// Returns the first `GrammarViolation` the new edge would introduce.
+pub fn check_new_edge(
+    source: &Node,
+    target: &Node,
+    connections: &[Connection],
+) -> Result<(), GrammarViolation> {
+    if constraints_for(target).contains(&StructuralConstraint::NoIncomingEdges) {
+        return Err(GrammarViolation {
+            node_id: target.id,
+            node_type: target.node_type.clone(),
+            constraint: StructuralConstraint::NoIncomingEdges,
+        });
+    }
+
+    let has_successor = connections
+        .iter()
+        .any(|connection| connection.source == source.id);
+    if has_successor && constraints_for(source).contains(&StructuralConstraint::ExactlyOneSuccessor)
+    {
+        return Err(GrammarViolation {
+            node_id: source.id,
+            node_type: source.node_type.clone(),
+            constraint: StructuralConstraint::ExactlyOneSuccessor,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks every declared constraint against a complete graph.
+#[must_use]
+pub fn check_graph(nodes: &[Node], connections: &[Connection]) -> Vec<GrammarViolation> {
+    let node_ids = graph_ops::collect_node_ids(nodes);
+    let incoming = graph_ops::build_reverse_adjacency(connections, &node_ids);
+
+    nodes
+        .iter()
+        .flat_map(|node| {
+            constraints_for(node)
+                .iter()
+                .filter(|constraint| {
+                    !is_satisfied(**constraint, node, nodes, connections, &incoming)
+                })
+                .map(|constraint| GrammarViolation {
+                    node_id: node.id,
+                    node_type: node.node_type.clone(),
+                    constraint: *constraint,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn is_satisfied(
+    constraint: StructuralConstraint,
+    node: &Node,
+    nodes: &[Node],
+    connections: &[Connection],
+    incoming: &HashMap<NodeId, Vec<NodeId>>,
+) -> bool {
+    match constraint {
+        StructuralConstraint::NoIncomingEdges => connections
+            .iter()
+            .all(|connection| connection.target != node.id),
+        StructuralConstraint::ExactlyOneSuccessor => {
+            connections
+                .iter()
+                .filter(|connection| connection.source == node.id)
+                .count()
+                == 1
+        }
+        StructuralConstraint::DownstreamOf(node_types) => {
+            let ancestors = graph_ops::find_reachable(&[node.id], incoming);
+            nodes.iter().any(|candidate| {
+                candidate.id != node.id
+                    && ancestors.contains(&candidate.id)
+                    && node_types
+                        .iter()
+                        .any(|node_type| matches_node_type(candidate, node_type))
+            })
+        }
+    }
+}
+
+fn matches_node_type(node: &Node, node_type: &str) -> bool {
+    match (
+        node.node_type.parse::<WorkflowNode>(),
+        node_type.parse::<WorkflowNode>(),
+    ) {
+        (Ok(actual), Ok(expected)) => actual.to_string() == expected.to_string(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{check_graph, StructuralConstraint};
+    use crate::graph::{validate_workflow, GraphConnectionError, PortName, Workflow};
+
+    fn main_port() -> PortName {
+        PortName::from("main")
+    }
+
+    #[test]
+    fn given_entry_target_when_connecting_then_grammar_violation_is_returned() {
+        let mut workflow = Workflow::new();
+        let run = workflow.add_node("run", 0.0, 0.0);
+        let entry = workflow.add_node("http-handler", 200.0, 0.0);
+
+        let result = workflow.add_connection_checked(run, entry, &main_port(), &main_port());
+
+        assert!(matches!(
+            result,
+            Err(GraphConnectionError::GrammarViolation(violation))
+                if violation.constraint == StructuralConstraint::NoIncomingEdges
+        ));
+        assert!(workflow.connections.is_empty());
+    }
+
+    #[test]
+    fn given_timeout_with_successor_when_adding_second_successor_then_connection_is_rejected() {
+        let mut workflow = Workflow::new();
+        let timeout = workflow.add_node("timeout", 0.0, 0.0);
+        let first = workflow.add_node("run", 200.0, 0.0);
+        let second = workflow.add_node("run", 200.0, 200.0);
+        let created = workflow.add_connection_checked(timeout, first, &main_port(), &main_port());
+        assert!(created.is_ok());
+
+        let result = workflow.add_connection_checked(timeout, second, &main_port(), &main_port());
+
+        assert!(matches!(
+            result,
+            Err(GraphConnectionError::GrammarViolation(violation))
+                if violation.node_id == timeout
+        ));
+        assert_eq!(workflow.connections.len(), 1);
+    }
+
+    #[test]
+    fn given_resolve_promise_without_awakeable_when_checking_graph_then_violation_is_reported() {
+        let mut workflow = Workflow::new();
+        let run = workflow.add_node("run", 0.0, 0.0);
+        let resolve = workflow.add_node("resolve-promise", 200.0, 0.0);
+        let _ = workflow.add_connection_checked(run, resolve, &main_port(), &main_port());
+
+        let violations = check_graph(&workflow.nodes, &workflow.connections);
+
+        assert!(violations
+            .iter()
+            .any(|violation| violation.node_id == resolve
+                && matches!(violation.constraint, StructuralConstraint::DownstreamOf(_))));
+    }
+
+    #[test]
+    fn given_resolve_promise_downstream_of_awakeable_when_checking_graph_then_no_violation() {
+        let mut workflow = Workflow::new();
+        let awakeable = workflow.add_node("awakeable", 0.0, 0.0);
+        let run = workflow.add_node("run", 200.0, 0.0);
+        let resolve = workflow.add_node("resolve-promise", 400.0, 0.0);
+        let _ = workflow.add_connection_checked(awakeable, run, &main_port(), &main_port());
+        let _ = workflow.add_connection_checked(run, resolve, &main_port(), &main_port());
+
+        let violations = check_graph(&workflow.nodes, &workflow.connections);
+
+        assert!(violations
+            .iter()
+            .all(|violation| violation.node_id != resolve));
+    }
+
+    #[test]
+    fn given_dangling_timeout_when_validating_workflow_then_grammar_warning_is_reported() {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let timeout = workflow.add_node("timeout", 200.0, 0.0);
+        let _ = workflow.add_connection_checked(entry, timeout, &main_port(), &main_port());
+
+        let result = validate_workflow(&workflow);
+
+        assert!(result
+            .issues
+            .iter()
+            .any(|issue| issue.node_id == Some(timeout)
+                && issue.message.contains("exactly one guarded successor")));
+    }
+
+    #[test]
+    fn given_entry_constraint_when_displayed_then_message_names_rule() {
+        assert_eq!(
+            StructuralConstraint::NoIncomingEdges.to_string(),
+            "cannot have incoming edges"
+        );
+    }
+}
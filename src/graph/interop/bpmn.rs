@@ -0,0 +1,703 @@
+//! BPMN 2.0 XML export and import.
+//!
+//! Entry nodes become start events, conditions and switches exclusive
+//! gateways, durable steps service tasks, and a compensation step a
+//! compensation boundary event on the activity it follows. Every element
+//! also carries its node type and config in the `oya` namespace, so a
+//! workflow survives a round trip through a modeler unchanged; documents
+//! drawn elsewhere are mapped from their element types alone.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::xml::{self, escape, Element};
+use crate::graph::{Connection, Node, NodeId, PortName, Workflow, WorkflowNode};
+
+pub const BPMN_NAMESPACE: &str = "http://www.omg.org/spec/BPMN/20100524/MODEL";
+pub const OYA_NAMESPACE: &str = "https://oya.dev/schema/bpmn";
+
+#[derive(Debug, Error)]
+pub enum BpmnError {
+    #[error("Malformed BPMN XML: {0}")]
+    Xml(String),
+    #[error("Not a BPMN document: root element is <{0}>")]
+    NotBpmn(String),
+    #[error("BPMN document has no process")]
+    MissingProcess,
+}
+
+/// How a node type is drawn in BPMN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Start,
+    ExclusiveGateway,
+    ParallelGateway,
+    ServiceTask,
+    CallActivity,
+    Compensation,
+    TimerCatch,
+    MessageCatch,
+    SignalCatch,
+}
+
+impl Shape {
+    fn of(node_type: &str) -> Self {
+        match node_type {
+            "http-handler" | "kafka-handler" | "cron-trigger" | "workflow-submit" => Self::Start,
+            "condition" | "switch" => Self::ExclusiveGateway,
+            "parallel" => Self::ParallelGateway,
+            "workflow-call" => Self::CallActivity,
+            "compensate" => Self::Compensation,
+            "sleep" | "timeout" => Self::TimerCatch,
+            "awakeable" | "wait-for-webhook" | "durable-promise" => Self::MessageCatch,
+            "signal-handler" => Self::SignalCatch,
+            _ => Self::ServiceTask,
+        }
+    }
+
+    const fn is_activity(self) -> bool {
+        matches!(self, Self::ServiceTask | Self::CallActivity)
+    }
+
+    const fn size(self) -> (f32, f32) {
+        match self {
+            Self::ServiceTask | Self::CallActivity => (100.0, 80.0),
+            Self::ExclusiveGateway | Self::ParallelGateway => (50.0, 50.0),
+            _ => (36.0, 36.0),
+        }
+    }
+}
+
+/// Serialize `workflow` as a BPMN 2.0 document with diagram layout.
+/// Secrets in node configs are redacted before they reach `oya:config`.
+#[must_use]
+pub fn to_bpmn_xml(workflow: &Workflow) -> String {
+    let workflow = &workflow.redacted();
+    let shapes = workflow
+        .nodes
+        .iter()
+        .map(|node| (node.id, Shape::of(&node.node_type)))
+        .collect::<HashMap<_, _>>();
+    let attachments = compensation_attachments(workflow, &shapes);
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<bpmn:definitions xmlns:bpmn=\"{BPMN_NAMESPACE}\" \
+         xmlns:bpmndi=\"http://www.omg.org/spec/BPMN/20100524/DI\" \
+         xmlns:dc=\"http://www.omg.org/spec/DD/20100524/DC\" \
+         xmlns:di=\"http://www.omg.org/spec/DD/20100524/DI\" \
+         xmlns:oya=\"{OYA_NAMESPACE}\" id=\"Definitions_1\" \
+         targetNamespace=\"{OYA_NAMESPACE}\">"
+    );
+    out.push_str("  <bpmn:process id=\"Process_1\" isExecutable=\"true\">\n");
+
+    for node in &workflow.nodes {
+        let shape = shapes[&node.id];
+        write_node(&mut out, node, shape, attachments.get(&node.id));
+    }
+
+    for connection in &workflow.connections {
+        if attachments.get(&connection.target) == Some(&connection.source) {
+            continue;
+        }
+        let from_compensation = shapes.get(&connection.source) == Some(&Shape::Compensation)
+            && attachments.contains_key(&connection.source);
+        let port = connection.source_port.0.as_str();
+        let label = if port == "main" {
+            String::new()
+        } else {
+            attributes(&[("name", port)])
+        };
+        let _ = writeln!(
+            out,
+            "    <bpmn:{}{}{label}/>",
+            if from_compensation {
+                "association"
+            } else {
+                "sequenceFlow"
+            },
+            attributes(&[
+                ("id", &flow_id(connection.id)),
+                ("sourceRef", &element_id(connection.source)),
+                ("targetRef", &element_id(connection.target)),
+                ("oya:sourcePort", port),
+            ]),
+        );
+    }
+    out.push_str("  </bpmn:process>\n");
+
+    out.push_str("  <bpmndi:BPMNDiagram id=\"Diagram_1\">\n");
+    out.push_str("    <bpmndi:BPMNPlane id=\"Plane_1\" bpmnElement=\"Process_1\">\n");
+    for node in &workflow.nodes {
+        let (width, height) = shapes[&node.id].size();
+        let id = element_id(node.id);
+        let _ = writeln!(
+            out,
+            "      <bpmndi:BPMNShape id=\"{id}_di\" bpmnElement=\"{id}\">\
+             <dc:Bounds x=\"{}\" y=\"{}\" width=\"{width}\" height=\"{height}\"/>\
+             </bpmndi:BPMNShape>",
+            node.x, node.y
+        );
+    }
+    out.push_str("    </bpmndi:BPMNPlane>\n  </bpmndi:BPMNDiagram>\n</bpmn:definitions>\n");
+    out
+}
+
+/// Compensation nodes reached from exactly one activity are drawn as a
+/// boundary event on it; any other compensation becomes a throw event.
+fn compensation_attachments(
+    workflow: &Workflow,
+    shapes: &HashMap<NodeId, Shape>,
+) -> HashMap<NodeId, NodeId> {
+    workflow
+        .nodes
+        .iter()
+        .filter(|node| shapes[&node.id] == Shape::Compensation)
+        .filter_map(|node| {
+            let mut incoming = workflow
+                .connections
+                .iter()
+                .filter(|connection| connection.target == node.id);
+            match (incoming.next(), incoming.next()) {
+                (Some(connection), None)
+                    if shapes
+                        .get(&connection.source)
+                        .is_some_and(|shape| shape.is_activity()) =>
+                {
+                    Some((node.id, connection.source))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn write_node(out: &mut String, node: &Node, shape: Shape, attached_to: Option<&NodeId>) {
+    let element = match shape {
+        Shape::Start => "startEvent",
+        Shape::ExclusiveGateway => "exclusiveGateway",
+        Shape::ParallelGateway => "parallelGateway",
+        Shape::ServiceTask => "serviceTask",
+        Shape::CallActivity => "callActivity",
+        Shape::Compensation if attached_to.is_some() => "boundaryEvent",
+        Shape::Compensation => "intermediateThrowEvent",
+        Shape::TimerCatch | Shape::MessageCatch | Shape::SignalCatch => "intermediateCatchEvent",
+    };
+    let config = node.config.to_string();
+    let mut pairs = vec![
+        ("id", element_id(node.id)),
+        ("name", node.name.clone()),
+        ("oya:type", node.node_type.clone()),
+        ("oya:config", config),
+    ];
+    if let Some(attached_to) = attached_to {
+        pairs.push(("attachedToRef", element_id(*attached_to)));
+    }
+    if let Some(called) =
+        config_text(node, "workflow_name").filter(|_| shape == Shape::CallActivity)
+    {
+        pairs.push(("calledElement", called));
+    }
+    if let Some(step) = config_text(node, "durable_step_name") {
+        pairs.push(("implementation", step));
+    }
+    let pairs = pairs
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect::<Vec<_>>();
+
+    let definition = match (shape, node.node_type.as_str()) {
+        (Shape::Start, "cron-trigger") => Some(format!(
+            "<bpmn:timerEventDefinition><bpmn:timeCycle>{}</bpmn:timeCycle></bpmn:timerEventDefinition>",
+            escape(&config_text(node, "schedule").unwrap_or_default())
+        )),
+        (Shape::Start, "kafka-handler") | (Shape::MessageCatch, _) => {
+            Some("<bpmn:messageEventDefinition/>".to_string())
+        }
+        (Shape::TimerCatch, _) => Some(format!(
+            "<bpmn:timerEventDefinition><bpmn:timeDuration>{}</bpmn:timeDuration></bpmn:timerEventDefinition>",
+            iso_duration(
+                node.config
+                    .get("duration_ms")
+                    .or_else(|| node.config.get("timeout_ms"))
+                    .and_then(Value::as_u64)
+                    .unwrap_or_default()
+            )
+        )),
+        (Shape::SignalCatch, _) => Some("<bpmn:signalEventDefinition/>".to_string()),
+        (Shape::Compensation, _) => Some("<bpmn:compensateEventDefinition/>".to_string()),
+        _ => None,
+    };
+
+    let attributes = attributes(&pairs);
+    let _ = match definition {
+        Some(definition) => writeln!(
+            out,
+            "    <bpmn:{element}{attributes}>{definition}</bpmn:{element}>"
+        ),
+        None => writeln!(out, "    <bpmn:{element}{attributes}/>"),
+    };
+}
+
+fn attributes(pairs: &[(&str, &str)]) -> String {
+    pairs.iter().fold(String::new(), |mut out, (name, value)| {
+        let _ = write!(out, " {name}=\"{}\"", escape(value));
+        out
+    })
+}
+
+fn config_text(node: &Node, key: &str) -> Option<String> {
+    node.config
+        .get(key)
+        .and_then(Value::as_str)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+// XML ids may not start with a digit, so uuids get a prefix.
+fn element_id(id: NodeId) -> String {
+    format!("node_{}", id.0)
+}
+
+fn flow_id(id: Uuid) -> String {
+    format!("flow_{id}")
+}
+
+fn parse_id(id: &str, prefix: &str) -> Option<Uuid> {
+    id.strip_prefix(prefix)
+        .and_then(|uuid| Uuid::parse_str(uuid).ok())
+}
+
+/// Build a workflow from a BPMN document's first process.
+///
+/// End events have no counterpart and are dropped along with the flows
+/// into them, as are elements this editor cannot represent.
+///
+/// # Errors
+/// Returns an error if `xml` is malformed or contains no BPMN process.
+pub fn from_bpmn_xml(xml: &str) -> Result<Workflow, BpmnError> {
+    let root = xml::parse(xml).map_err(BpmnError::Xml)?;
+    if root.local_name() != "definitions" {
+        return Err(BpmnError::NotBpmn(root.name));
+    }
+    let process = root.find("process").ok_or(BpmnError::MissingProcess)?;
+    let oya = root
+        .attributes
+        .iter()
+        .find(|(_, value)| value == OYA_NAMESPACE)
+        .and_then(|(name, _)| name.strip_prefix("xmlns:"))
+        .unwrap_or("oya");
+    let extension = |element: &Element, name: &str| {
+        element
+            .attribute(&format!("{oya}:{name}"))
+            .map(str::to_string)
+    };
+
+    let positions = shape_positions(&root);
+    let names = attribute_by_id(process, "name");
+    let flows = process
+        .children
+        .iter()
+        .filter(|element| matches!(element.local_name(), "sequenceFlow" | "association"))
+        .collect::<Vec<_>>();
+    let defaults = attribute_by_id(process, "default");
+    let outgoing = |id: &str| {
+        flows
+            .iter()
+            .filter(|flow| flow.attribute("sourceRef") == Some(id))
+            .count()
+    };
+
+    let mut workflow = Workflow::new();
+    let mut ids = HashMap::new();
+    let mut attachments = Vec::new();
+    for element in &process.children {
+        let Some(id) = element.attribute("id") else {
+            continue;
+        };
+        let (node_type, config) = match extension(element, "type") {
+            Some(node_type) => {
+                let config = extension(element, "config")
+                    .and_then(|config| serde_json::from_str(&config).ok())
+                    .unwrap_or_else(|| json!({}));
+                (node_type, config)
+            }
+            None => match infer_node(element, outgoing(id), &names) {
+                Some((node_type, config)) => (node_type.to_string(), config),
+                None => continue,
+            },
+        };
+        let (x, y) = positions.get(id).copied().unwrap_or_default();
+        let workflow_node = WorkflowNode::from_str(&node_type).unwrap_or_default();
+        let name = names.get(id).copied().unwrap_or(id).to_string();
+        let mut node = Node::from_workflow_node(name, workflow_node, x, y);
+        if let Some(uuid) = parse_id(id, "node_") {
+            node.id = NodeId(uuid);
+        }
+        node.apply_config_update(&config);
+        if let Some(host) = element.attribute("attachedToRef") {
+            attachments.push((host.to_string(), node.id));
+        }
+        ids.insert(id.to_string(), node.id);
+        workflow.nodes.push(node);
+    }
+
+    for (host, node) in attachments {
+        if let Some(&source) = ids.get(&host) {
+            workflow
+                .connections
+                .push(connection(Uuid::new_v4(), source, node, "main"));
+        }
+    }
+    let mut branch = HashMap::<&str, usize>::new();
+    for flow in flows {
+        let (Some(source_ref), Some(target_ref)) =
+            (flow.attribute("sourceRef"), flow.attribute("targetRef"))
+        else {
+            continue;
+        };
+        let (Some(&source), Some(&target)) = (ids.get(source_ref), ids.get(target_ref)) else {
+            continue;
+        };
+        let index = branch.entry(source_ref).or_default();
+        let port = extension(flow, "sourcePort").unwrap_or_else(|| {
+            let is_condition = workflow
+                .nodes
+                .iter()
+                .any(|node| node.id == source && node.node_type == "condition");
+            let is_default = flow.attribute("id").is_some()
+                && flow.attribute("id") == defaults.get(source_ref).copied();
+            branch_port(is_condition, is_default, flow, *index)
+        });
+        *index += 1;
+        let id = flow
+            .attribute("id")
+            .and_then(|id| parse_id(id, "flow_"))
+            .unwrap_or_else(Uuid::new_v4);
+        workflow
+            .connections
+            .push(connection(id, source, target, &port));
+    }
+
+    Ok(workflow)
+}
+
+/// Diagram positions keyed by the id of the element each shape draws.
+fn shape_positions(root: &Element) -> HashMap<String, (f32, f32)> {
+    root.descendants()
+        .into_iter()
+        .filter(|element| element.local_name() == "BPMNShape")
+        .filter_map(|shape| {
+            let bounds = shape.find("Bounds")?;
+            let coordinate = |name| {
+                bounds
+                    .attribute(name)
+                    .and_then(|value| value.parse::<f32>().ok())
+                    .unwrap_or_default()
+            };
+            Some((
+                shape.attribute("bpmnElement")?.to_string(),
+                (coordinate("x"), coordinate("y")),
+            ))
+        })
+        .collect()
+}
+
+fn attribute_by_id<'a>(process: &'a Element, name: &str) -> HashMap<&'a str, &'a str> {
+    process
+        .children
+        .iter()
+        .filter_map(|element| Some((element.attribute("id")?, element.attribute(name)?)))
+        .collect()
+}
+
+/// Map an element drawn by another tool onto a node type and config.
+fn infer_node(
+    element: &Element,
+    outgoing: usize,
+    names: &HashMap<&str, &str>,
+) -> Option<(&'static str, Value)> {
+    let definition = element
+        .children
+        .iter()
+        .map(Element::local_name)
+        .find(|name| name.ends_with("EventDefinition"));
+    let name = element.attribute("name");
+    match (element.local_name(), definition) {
+        ("startEvent", Some("timerEventDefinition")) => Some((
+            "cron-trigger",
+            json!({ "schedule": timer_text(element, "timeCycle") }),
+        )),
+        ("startEvent", Some("messageEventDefinition")) => Some(("kafka-handler", json!({}))),
+        ("startEvent", _) => Some(("workflow-submit", json!({}))),
+        ("exclusiveGateway" | "inclusiveGateway", _) if outgoing > 2 => Some(("switch", json!({}))),
+        ("exclusiveGateway" | "inclusiveGateway", _) => Some(("condition", json!({}))),
+        ("parallelGateway", _) => Some(("parallel", json!({}))),
+        ("callActivity", _) => Some((
+            "workflow-call",
+            json!({ "workflow_name": element.attribute("calledElement") }),
+        )),
+        (
+            "task" | "serviceTask" | "scriptTask" | "userTask" | "sendTask" | "receiveTask"
+            | "manualTask" | "businessRuleTask" | "subProcess",
+            _,
+        ) => Some(("run", json!({ "durable_step_name": name }))),
+        ("boundaryEvent" | "intermediateThrowEvent", Some("compensateEventDefinition")) => Some((
+            "compensate",
+            json!({
+                "target_step": element
+                    .attribute("attachedToRef")
+                    .and_then(|host| names.get(host)),
+            }),
+        )),
+        ("boundaryEvent", Some("timerEventDefinition")) => Some((
+            "timeout",
+            json!({ "timeout_ms": timer_text(element, "timeDuration").and_then(|text| iso_duration_ms(&text)) }),
+        )),
+        ("intermediateCatchEvent", Some("timerEventDefinition")) => Some((
+            "sleep",
+            json!({ "duration_ms": timer_text(element, "timeDuration").and_then(|text| iso_duration_ms(&text)) }),
+        )),
+        ("intermediateCatchEvent", Some("messageEventDefinition")) => {
+            Some(("awakeable", json!({})))
+        }
+        ("intermediateCatchEvent", Some("signalEventDefinition")) => {
+            Some(("signal-handler", json!({ "signal_name": name })))
+        }
+        _ => None,
+    }
+}
+
+fn timer_text(element: &Element, name: &str) -> Option<String> {
+    element
+        .find(name)
+        .map(|timer| timer.text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// A condition's branches are named by modelers, or fall back to the
+/// default flow and then to document order.
+fn branch_port(is_condition: bool, is_default: bool, flow: &Element, index: usize) -> String {
+    if !is_condition {
+        return "main".to_string();
+    }
+    if is_default {
+        return "false".to_string();
+    }
+    let label = flow.attribute("name").map(str::to_lowercase);
+    match label.as_deref() {
+        Some("true" | "yes") => "true",
+        Some("false" | "no") => "false",
+        _ if index == 0 => "true",
+        _ => "false",
+    }
+    .to_string()
+}
+
+fn connection(id: Uuid, source: NodeId, target: NodeId, port: &str) -> Connection {
+    Connection {
+        id,
+        source,
+        target,
+        source_port: PortName::from(port),
+        target_port: PortName::from("main"),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn iso_duration(ms: u64) -> String {
+    format!("PT{}S", ms as f64 / 1000.0)
+}
+
+/// Milliseconds in an ISO 8601 duration such as `PT1H30M` or `P1DT2.5S`.
+fn iso_duration_ms(text: &str) -> Option<u64> {
+    let mut total = 0.0;
+    let mut number = String::new();
+    let mut in_time = false;
+    for character in text.strip_prefix('P')?.chars() {
+        let unit_ms = match (character, in_time) {
+            ('T', false) => {
+                in_time = true;
+                continue;
+            }
+            ('0'..='9' | '.', _) => {
+                number.push(character);
+                continue;
+            }
+            ('W', false) => 604_800_000.0,
+            ('D', false) => 86_400_000.0,
+            ('H', true) => 3_600_000.0,
+            ('M', true) => 60_000.0,
+            ('S', true) => 1_000.0,
+            _ => return None,
+        };
+        total += number.parse::<f64>().ok()? * unit_ms;
+        number.clear();
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(total as u64)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    fn node<'a>(workflow: &'a Workflow, node_type: &str) -> &'a Node {
+        workflow
+            .nodes
+            .iter()
+            .find(|node| node.node_type == node_type)
+            .unwrap_or_else(|| panic!("no {node_type} node"))
+    }
+
+    #[test]
+    fn given_workflow_when_exported_and_imported_then_it_round_trips() {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("cron-trigger", 0.0, 0.0);
+        let check = workflow.add_node("condition", 150.0, 0.0);
+        let charge = workflow.add_node("run", 300.0, -80.0);
+        let refund = workflow.add_node("compensate", 300.0, 80.0);
+        let wait = workflow.add_node("sleep", 450.0, 0.0);
+        workflow.nodes[0].apply_config_update(&json!({ "schedule": "0 * * * *" }));
+        workflow.nodes[2].name = "Charge <card> & \"notify\"".to_string();
+        workflow.nodes[4].apply_config_update(&json!({ "duration_ms": 1500 }));
+        for (source, target, port) in [
+            (entry, check, "main"),
+            (check, charge, "true"),
+            (check, wait, "false"),
+            (charge, refund, "main"),
+        ] {
+            workflow
+                .connections
+                .push(connection(Uuid::new_v4(), source, target, port));
+        }
+
+        let xml = to_bpmn_xml(&workflow);
+        assert!(xml.contains("<bpmn:startEvent"));
+        assert!(xml.contains("<bpmn:exclusiveGateway"));
+        assert!(xml.contains("<bpmn:serviceTask"));
+        assert!(xml.contains(&format!("attachedToRef=\"{}\"", element_id(charge))));
+        assert!(xml.contains("<bpmn:timeDuration>PT1.5S</bpmn:timeDuration>"));
+
+        let imported = from_bpmn_xml(&xml).unwrap();
+        assert_eq!(imported.nodes.len(), workflow.nodes.len());
+        for original in &workflow.nodes {
+            let copy = imported
+                .nodes
+                .iter()
+                .find(|node| node.id == original.id)
+                .unwrap();
+            assert_eq!(copy.name, original.name);
+            assert_eq!(copy.node_type, original.node_type);
+            assert_eq!(copy.config, original.config);
+            assert_eq!((copy.x, copy.y), (original.x, original.y));
+        }
+        let edges = |workflow: &Workflow| {
+            let mut edges = workflow
+                .connections
+                .iter()
+                .map(|c| (c.source, c.target, c.source_port.0.clone()))
+                .collect::<Vec<_>>();
+            edges.sort();
+            edges
+        };
+        assert_eq!(edges(&imported), edges(&workflow));
+    }
+
+    #[test]
+    fn given_modeler_document_when_importing_then_elements_map_by_type() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <bpmn2:definitions xmlns:bpmn2="http://www.omg.org/spec/BPMN/20100524/MODEL"
+            xmlns:bpmndi="http://www.omg.org/spec/BPMN/20100524/DI"
+            xmlns:dc="http://www.omg.org/spec/DD/20100524/DC" id="d1">
+          <bpmn2:process id="order" isExecutable="false">
+            <bpmn2:startEvent id="start" name="Order placed"/>
+            <bpmn2:exclusiveGateway id="paid" name="Paid?" default="to_wait"/>
+            <bpmn2:serviceTask id="ship" name="Ship order"/>
+            <bpmn2:boundaryEvent id="unship" name="Unship" attachedToRef="ship">
+              <bpmn2:compensateEventDefinition/>
+            </bpmn2:boundaryEvent>
+            <bpmn2:intermediateCatchEvent id="wait" name="Wait">
+              <bpmn2:timerEventDefinition><bpmn2:timeDuration>PT1H30M</bpmn2:timeDuration></bpmn2:timerEventDefinition>
+            </bpmn2:intermediateCatchEvent>
+            <bpmn2:endEvent id="end"/>
+            <bpmn2:sequenceFlow id="f1" sourceRef="start" targetRef="paid"/>
+            <bpmn2:sequenceFlow id="to_wait" sourceRef="paid" targetRef="wait"/>
+            <bpmn2:sequenceFlow id="f3" sourceRef="paid" targetRef="ship" name="Yes"/>
+            <bpmn2:sequenceFlow id="f4" sourceRef="ship" targetRef="end"/>
+          </bpmn2:process>
+          <bpmndi:BPMNDiagram id="diagram">
+            <bpmndi:BPMNPlane id="plane" bpmnElement="order">
+              <bpmndi:BPMNShape id="ship_di" bpmnElement="ship">
+                <dc:Bounds x="320" y="40" width="100" height="80"/>
+              </bpmndi:BPMNShape>
+            </bpmndi:BPMNPlane>
+          </bpmndi:BPMNDiagram>
+        </bpmn2:definitions>"#;
+
+        let workflow = from_bpmn_xml(xml).unwrap();
+
+        assert_eq!(workflow.nodes.len(), 5);
+        assert_eq!(node(&workflow, "workflow-submit").name, "Order placed");
+        let ship = node(&workflow, "run");
+        assert_eq!(ship.config["durable_step_name"], "Ship order");
+        assert_eq!((ship.x, ship.y), (320.0, 40.0));
+        assert_eq!(
+            node(&workflow, "compensate").config["target_step"],
+            "Ship order"
+        );
+        assert_eq!(node(&workflow, "sleep").config["duration_ms"], 5_400_000);
+        let gateway = node(&workflow, "condition").id;
+        let port_to = |target: NodeId| {
+            workflow
+                .connections
+                .iter()
+                .find(|c| c.source == gateway && c.target == target)
+                .map(|c| c.source_port.0.as_str())
+        };
+        assert_eq!(port_to(ship.id), Some("true"));
+        assert_eq!(port_to(node(&workflow, "sleep").id), Some("false"));
+        assert_eq!(workflow.connections.len(), 4);
+    }
+
+    #[test]
+    fn given_non_bpmn_input_when_importing_then_errors_explain_why() {
+        assert!(matches!(from_bpmn_xml("<a>"), Err(BpmnError::Xml(_))));
+        assert!(matches!(
+            from_bpmn_xml("<svg/>"),
+            Err(BpmnError::NotBpmn(name)) if name == "svg"
+        ));
+        assert!(matches!(
+            from_bpmn_xml("<definitions/>"),
+            Err(BpmnError::MissingProcess)
+        ));
+    }
+
+    #[test]
+    fn given_secret_in_config_when_exporting_then_xml_carries_only_the_redaction_marker() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("http-request", 0.0, 0.0);
+        workflow.nodes[0].apply_config_update(&json!({
+            "url": "https://api.example.com",
+            "auth_token": "tok-123456",
+        }));
+
+        let xml = to_bpmn_xml(&workflow);
+
+        assert!(!xml.contains("tok-123456"));
+        assert!(xml.contains(crate::graph::redaction::REDACTED));
+        assert!(xml.contains("https://api.example.com"));
+    }
+}
@@ -0,0 +1,4 @@
+//! Exchange formats shared with other modeling tools.
+
+pub mod bpmn;
+mod xml;
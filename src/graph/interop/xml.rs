@@ -0,0 +1,260 @@
+//! Just enough XML for interchange formats: a tree reader that keeps
+//! elements and attributes, and escaping for writers. Namespaces are not
+//! resolved; element names are compared without their prefix.
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Element {
+    /// Qualified name as written, e.g. `bpmn:task`.
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Self>,
+    pub text: String,
+}
+
+impl Element {
+    /// Name without its namespace prefix.
+    #[must_use]
+    pub fn local_name(&self) -> &str {
+        local(&self.name)
+    }
+
+    /// Value of the attribute with exactly this qualified name.
+    #[must_use]
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// First element with this local name, searching depth-first.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<&Self> {
+        self.children.iter().find_map(|child| {
+            if child.local_name() == name {
+                Some(child)
+            } else {
+                child.find(name)
+            }
+        })
+    }
+
+    /// Every element below this one, depth-first.
+    #[must_use]
+    pub fn descendants(&self) -> Vec<&Self> {
+        let mut found = Vec::new();
+        for child in &self.children {
+            found.push(child);
+            found.extend(child.descendants());
+        }
+        found
+    }
+}
+
+fn local(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Escape text for use in element content or a double-quoted attribute.
+#[must_use]
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+fn unescape(text: &str) -> Result<String, String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| format!("unterminated entity in '{text}'"))?;
+        let entity = &rest[start + 1..start + end];
+        let character = match entity {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32)
+                .ok_or_else(|| format!("unknown entity &{entity};"))?,
+        };
+        unescaped.push(character);
+        rest = &rest[start + end + 1..];
+    }
+    unescaped.push_str(rest);
+    Ok(unescaped)
+}
+
+/// Parse a document and return its root element.
+///
+/// # Errors
+/// Returns a description of the first malformed construct.
+pub fn parse(xml: &str) -> Result<Element, String> {
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        if let Some(open) = stack.last_mut() {
+            open.text.push_str(&unescape(&rest[..start])?);
+        }
+        rest = &rest[start..];
+
+        let skip = [("<?", "?>"), ("<!--", "-->"), ("<!DOCTYPE", ">")]
+            .into_iter()
+            .find(|(opening, _)| rest.starts_with(opening));
+        if let Some((_, closing)) = skip {
+            let end = rest
+                .find(closing)
+                .ok_or("unterminated declaration or comment")?;
+            rest = &rest[end + closing.len()..];
+            continue;
+        }
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").ok_or("unterminated CDATA section")?;
+            if let Some(open) = stack.last_mut() {
+                open.text.push_str(&cdata[..end]);
+            }
+            rest = &cdata[end + 3..];
+            continue;
+        }
+
+        let end = tag_end(rest).ok_or("unterminated tag")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let element = stack.pop().ok_or("closing tag without an opening tag")?;
+            if element.name != name.trim() {
+                return Err(format!(
+                    "expected </{}>, found </{}>",
+                    element.name,
+                    name.trim()
+                ));
+            }
+            close(element, &mut stack, &mut root);
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let element = open_tag(tag.trim_end_matches('/'))?;
+        if self_closing {
+            close(element, &mut stack, &mut root);
+        } else {
+            stack.push(element);
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(format!("<{}> is never closed", open.name));
+    }
+    root.ok_or_else(|| "document has no root element".to_string())
+}
+
+/// Index of the `>` that ends the tag at the start of `text`, ignoring any
+/// inside quoted attribute values.
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, character) in text.char_indices() {
+        match (quote, character) {
+            (None, '"' | '\'') => quote = Some(character),
+            (Some(open), _) if open == character => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn open_tag(tag: &str) -> Result<Element, String> {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let mut element = Element {
+        name: tag[..name_end].to_string(),
+        ..Element::default()
+    };
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let equals = rest
+            .find('=')
+            .ok_or_else(|| format!("attribute without value in <{}>", element.name))?;
+        let name = rest[..equals].trim().to_string();
+        let value = rest[equals + 1..].trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|quote| matches!(quote, '"' | '\''))
+            .ok_or_else(|| format!("unquoted attribute {name} in <{}>", element.name))?;
+        let close = value[1..]
+            .find(quote)
+            .ok_or_else(|| format!("unterminated attribute {name}"))?;
+        element
+            .attributes
+            .push((name, unescape(&value[1..=close])?));
+        rest = value[close + 2..].trim_start();
+    }
+    Ok(element)
+}
+
+fn close(element: Element, stack: &mut [Element], root: &mut Option<Element>) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(element),
+        None => *root = Some(element),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_document_with_prefixes_and_entities_when_parsing_then_tree_is_built() {
+        let root = parse(
+            r#"<?xml version="1.0"?>
+            <!-- modeler output -->
+            <a:root xmlns:a="urn:a" note='x &gt; 1'>
+              <a:child id="1" label="Tom &amp; Jerry"/>
+              <a:child id="2"><![CDATA[<raw>]]> &#65;&#x42;</a:child>
+            </a:root>"#,
+        )
+        .unwrap();
+
+        assert_eq!(root.local_name(), "root");
+        assert_eq!(root.attribute("note"), Some("x > 1"));
+        let children = &root.children;
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].attribute("label"), Some("Tom & Jerry"));
+        assert_eq!(children[1].text.trim(), "<raw> AB");
+        assert_eq!(
+            unescape(&escape("a<\"b\"> & 'c'\n")).unwrap(),
+            "a<\"b\"> & 'c'\n"
+        );
+    }
+
+    #[test]
+    fn given_malformed_documents_when_parsing_then_errors_name_the_problem() {
+        assert!(parse("<a><b></a>").unwrap_err().contains("expected </b>"));
+        assert!(parse("<a>").unwrap_err().contains("never closed"));
+        assert!(parse("<a b=c/>").unwrap_err().contains("unquoted"));
+        assert!(parse("just text").is_err());
+    }
+}
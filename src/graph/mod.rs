@@ -5,13 +5,20 @@
 #![warn(clippy::nursery)]
 #![forbid(unsafe_code)]
 
+pub mod behavior_coverage;
 pub mod calc;
+pub mod collab;
+pub mod compile;
 pub mod connectivity;
+pub mod contract;
 pub mod core;
 mod core_types;
+pub mod cron;
 #[cfg(test)]
 mod cycle_detection_tests;
+pub mod diff;
 mod domain_types;
+pub mod events;
 mod execution;
 pub mod execution_errors;
 pub mod execution_record;
@@ -19,19 +26,29 @@ pub mod execution_record_types;
 pub mod execution_runtime;
 pub mod execution_state;
 pub mod execution_types;
+pub mod frames;
 pub mod graph_ops;
 mod metadata;
 mod primitives;
+pub mod profile;
+pub mod redaction;
 mod view;
 
 pub mod connection_errors;
 pub mod expressions;
+pub mod flow_import;
+pub mod grammar;
+pub mod interop;
 pub mod layout;
 pub mod node_icon;
+pub mod node_index;
 pub mod node_ui_state;
 pub mod port_types;
 pub mod restate_types;
 pub mod service_kinds;
+pub mod subgraph;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod validation;
 mod validation_checks;
 pub mod value_objects;
@@ -61,6 +78,7 @@ pub use execution_state::{
     can_transition, try_transition, CompletedState, ExecutionState, FailedState, IdleState,
     InvalidTransition, QueuedState, RunningState, SkippedState, StateTransition, TerminalState,
 };
+pub use node_index::{NodeChanges, NodeIndex, NodeMap};
 pub use primitives::{Connection, NodeCategory, NodeId, PortName};
 pub use validation::{
     validate_unique_node_ids, validate_workflow, ValidationIssue, ValidationResult,
@@ -0,0 +1,171 @@
+//! Nodes keyed by id, kept up to date incrementally.
+//!
+//! The editor looks nodes up by id on every interaction. Rebuilding a
+//! `HashMap<NodeId, Node>` for that cloned every node each time one moved.
+//! [`NodeIndex`] is a persistent map instead: [`NodeIndex::sync`] rewrites
+//! only the entries whose node changed, and clones share everything else,
+//! so dragging one node costs one node.
+
+use std::collections::HashSet;
+
+use super::{Node, NodeId};
+
+/// Persistent map of nodes by id; clones are O(1) and share structure.
+pub type NodeMap = im::HashMap<NodeId, Node>;
+
+/// What a [`NodeIndex::sync`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeChanges {
+    pub added: Vec<NodeId>,
+    pub updated: Vec<NodeId>,
+    pub removed: Vec<NodeId>,
+}
+
+impl NodeChanges {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+
+    /// Whether nodes were added or removed, not just edited.
+    #[must_use]
+    pub const fn changes_membership(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NodeIndex {
+    nodes: NodeMap,
+}
+
+impl NodeIndex {
+    #[must_use]
+    pub fn from_nodes(nodes: &[Node]) -> Self {
+        Self {
+            nodes: nodes.iter().map(|node| (node.id, node.clone())).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &NodeId) -> Option<&Node> {
+        self.nodes.get(id)
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &NodeId) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The underlying map, for callers that want the whole thing.
+    #[must_use]
+    pub const fn as_map(&self) -> &NodeMap {
+        &self.nodes
+    }
+
+    /// Bring the index in line with `nodes`, touching only entries that
+    /// differ. Unchanged nodes are compared but never cloned.
+    pub fn sync(&mut self, nodes: &[Node]) -> NodeChanges {
+        let mut changes = NodeChanges::default();
+        for node in nodes {
+            match self.nodes.get(&node.id) {
+                Some(indexed) if indexed == node => {}
+                Some(_) => {
+                    self.nodes.insert(node.id, node.clone());
+                    changes.updated.push(node.id);
+                }
+                None => {
+                    self.nodes.insert(node.id, node.clone());
+                    changes.added.push(node.id);
+                }
+            }
+        }
+        // Every node is now indexed, so extra entries mean removals.
+        if self.nodes.len() > nodes.len() {
+            let live: HashSet<NodeId> = nodes.iter().map(|node| node.id).collect();
+            changes.removed = self
+                .nodes
+                .keys()
+                .filter(|id| !live.contains(id))
+                .copied()
+                .collect();
+            for id in &changes.removed {
+                self.nodes.remove(id);
+            }
+        }
+        changes
+    }
+}
+
+/// Indexes sharing structure are equal without comparing their nodes, so an
+/// unchanged index is cheap to compare against its previous value.
+impl PartialEq for NodeIndex {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes.ptr_eq(&other.nodes) || self.nodes == other.nodes
+    }
+}
+
+impl FromIterator<Node> for NodeIndex {
+    fn from_iter<I: IntoIterator<Item = Node>>(nodes: I) -> Self {
+        Self {
+            nodes: nodes.into_iter().map(|node| (node.id, node)).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::synthetic::large_workflow;
+
+    #[test]
+    fn given_moved_added_and_removed_nodes_when_syncing_then_only_those_entries_change() {
+        let mut workflow = large_workflow(50);
+        let mut index = NodeIndex::from_nodes(&workflow.nodes);
+        let before = index.clone();
+
+        let moved = workflow.nodes[3].id;
+        workflow.nodes[3].x += 40.0;
+        let removed = workflow.nodes.remove(7).id;
+        let added = workflow.add_node("run", 9_000.0, 0.0);
+        let changes = index.sync(&workflow.nodes);
+
+        assert_eq!(changes.updated, vec![moved]);
+        assert_eq!(changes.added, vec![added]);
+        assert_eq!(changes.removed, vec![removed]);
+        assert!(changes.changes_membership());
+        assert_eq!(index.len(), workflow.nodes.len());
+        assert_eq!(index.get(&moved).unwrap().x, workflow.nodes[3].x);
+        assert!(!index.contains(&removed));
+        assert_eq!(before.len(), 50);
+        assert!(before.contains(&removed));
+    }
+
+    #[test]
+    fn given_unchanged_nodes_when_syncing_then_index_still_shares_structure() {
+        let workflow = large_workflow(20);
+        let mut index = NodeIndex::from_nodes(&workflow.nodes);
+        let before = index.clone();
+
+        assert!(index.sync(&workflow.nodes).is_empty());
+        assert!(index.as_map().ptr_eq(before.as_map()));
+        assert_eq!(index, before);
+        assert_eq!(index, workflow.nodes.iter().cloned().collect());
+    }
+}
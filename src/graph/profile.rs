@@ -0,0 +1,172 @@
+//! Run timing profile: where a run spent its time.
+//!
+//! Each [`RunRecord`] keeps the wall-clock duration of every node that ran.
+//! The critical path is the chain of connected nodes whose durations add up
+//! to the most time; speeding up anything off that chain cannot shorten a
+//! run that executes branches in parallel.
+
+use std::collections::{HashMap, HashSet};
+
+use super::graph_ops;
+use super::{NodeId, RunRecord, Workflow};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RunProfile {
+    pub durations: HashMap<NodeId, u64>,
+    /// Nodes on the critical path, from entry to exit.
+    pub critical_path: Vec<NodeId>,
+    /// Sum of the durations along the critical path.
+    pub critical_ms: u64,
+}
+
+impl RunProfile {
+    /// Profile of a recorded run, or `None` if it has no timings.
+    #[must_use]
+    pub fn from_run(workflow: &Workflow, run: &RunRecord) -> Option<Self> {
+        if run.durations.is_empty() {
+            return None;
+        }
+        let (critical_path, critical_ms) = workflow.critical_path(&run.durations)?;
+        Some(Self {
+            durations: run.durations.clone(),
+            critical_path,
+            critical_ms,
+        })
+    }
+
+    #[must_use]
+    pub fn max_ms(&self) -> u64 {
+        self.durations.values().copied().max().unwrap_or(0)
+    }
+
+    /// Duration relative to the slowest node, from 0.0 to 1.0.
+    #[must_use]
+    pub fn heat(&self, node_id: NodeId) -> Option<f32> {
+        let duration = *self.durations.get(&node_id)?;
+        let max = self.max_ms();
+        if max == 0 {
+            return Some(0.0);
+        }
+        #[allow(clippy::cast_precision_loss)]
+        Some(duration as f32 / max as f32)
+    }
+
+    #[must_use]
+    pub fn is_critical(&self, node_id: NodeId) -> bool {
+        self.critical_path.contains(&node_id)
+    }
+
+    /// Whether the edge `source -> target` lies on the critical path.
+    #[must_use]
+    pub fn is_critical_edge(&self, source: NodeId, target: NodeId) -> bool {
+        self.critical_path
+            .windows(2)
+            .any(|pair| pair[0] == source && pair[1] == target)
+    }
+}
+
+impl Workflow {
+    /// The heaviest path through the DAG, weighting each node by its
+    /// duration. Nodes without a duration weigh nothing. Returns `None` for
+    /// an empty or cyclic graph.
+    #[must_use]
+    pub fn critical_path(&self, durations: &HashMap<NodeId, u64>) -> Option<(Vec<NodeId>, u64)> {
+        let node_ids: HashSet<NodeId> = graph_ops::collect_node_ids(&self.nodes);
+        let (adjacency, in_degree) =
+            graph_ops::build_adjacency_with_in_degree(&self.connections, &node_ids);
+        let order =
+            graph_ops::topological_sort(&node_ids, &adjacency, &in_degree, |a, b| a.0.cmp(&b.0))
+                .ok()?;
+
+        // Heaviest path ending at each node, and the node before it.
+        let mut best: HashMap<NodeId, (u64, Option<NodeId>)> = HashMap::new();
+        for id in &order {
+            let own = durations.get(id).copied().unwrap_or(0);
+            let entry = best.entry(*id).or_insert((0, None));
+            entry.0 += own;
+            let total = entry.0;
+            for target in adjacency.get(id).into_iter().flatten() {
+                let candidate = best.entry(*target).or_insert((0, None));
+                if candidate.1.is_none() || total > candidate.0 {
+                    *candidate = (total, Some(*id));
+                }
+            }
+        }
+
+        let (mut cursor, total) = order
+            .iter()
+            .map(|id| (*id, best.get(id).map_or(0, |entry| entry.0)))
+            .max_by_key(|(_, total)| *total)?;
+        let mut path = vec![cursor];
+        while let Some(previous) = best.get(&cursor).and_then(|entry| entry.1) {
+            path.push(previous);
+            cursor = previous;
+        }
+        path.reverse();
+        Some((path, total))
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::PortName;
+
+    #[test]
+    fn given_parallel_branches_when_computing_critical_path_then_slowest_chain_wins() {
+        let mut workflow = Workflow::new();
+        let start = workflow.add_node("http-handler", 0.0, 0.0);
+        let fast = workflow.add_node("run", 300.0, 0.0);
+        let slow = workflow.add_node("run", 300.0, 200.0);
+        let end = workflow.add_node("run", 600.0, 100.0);
+        let main = PortName("main".to_string());
+        for (source, target) in [(start, fast), (start, slow), (fast, end), (slow, end)] {
+            workflow
+                .add_connection(source, target, &main, &main)
+                .unwrap();
+        }
+        let durations = HashMap::from([(start, 10), (fast, 5), (slow, 200), (end, 20)]);
+
+        let (path, total) = workflow.critical_path(&durations).unwrap();
+
+        assert_eq!(path, vec![start, slow, end]);
+        assert_eq!(total, 230);
+    }
+
+    #[test]
+    fn given_recorded_run_when_profiling_then_heat_and_critical_edges_follow_durations() {
+        let mut workflow = Workflow::new();
+        let start = workflow.add_node("http-handler", 0.0, 0.0);
+        let next = workflow.add_node("run", 300.0, 0.0);
+        let main = PortName("main".to_string());
+        workflow.add_connection(start, next, &main, &main).unwrap();
+        let run = RunRecord {
+            id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            results: HashMap::new(),
+            success: true,
+            restate_invocation_id: None,
+            durations: HashMap::from([(start, 50), (next, 100)]),
+        };
+
+        let profile = RunProfile::from_run(&workflow, &run).unwrap();
+
+        assert_eq!(profile.heat(start), Some(0.5));
+        assert_eq!(profile.heat(next), Some(1.0));
+        assert!(profile.is_critical_edge(start, next));
+        assert!(!profile.is_critical_edge(next, start));
+        assert_eq!(profile.critical_ms, 150);
+
+        let untimed = RunRecord {
+            durations: HashMap::new(),
+            ..run
+        };
+        assert!(RunProfile::from_run(&workflow, &untimed).is_none());
+    }
+}
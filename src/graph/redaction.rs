@@ -0,0 +1,309 @@
+//! Secret redaction at serialization boundaries.
+//!
+//! A config value is secret when its key matches the policy's patterns
+//! (`password`, `*_token`, `api_key`, …; camelCase keys are compared as
+//! `snake_case`) or is listed in the node's `secret_keys` config. Secret
+//! values are replaced with [`REDACTED`] wherever the workflow leaves
+//! memory: localStorage, exports and run history. Any other string that
+//! contains a secret value, such as an `Authorization` header echoed into a
+//! node output, has the secret replaced in place. `{{ … }}` expressions are
+//! kept, since they reference a secret rather than hold one.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use super::execution_record_types::StepOutput;
+use super::{Node, NodeId, Workflow};
+
+/// Placeholder written in place of a secret.
+pub const REDACTED: &str = "[redacted]";
+/// Config key listing further config keys of a node to treat as secret.
+pub const SECRET_KEYS_CONFIG_KEY: &str = "secret_keys";
+
+const DEFAULT_PATTERNS: &[&str] = &[
+    "password",
+    "*_password",
+    "passwd",
+    "secret",
+    "*_secret",
+    "token",
+    "*_token",
+    "api_key",
+    "authorization",
+    "private_key",
+];
+
+/// Shortest secret value scrubbed from other strings; shorter values would
+/// mangle unrelated text.
+const MIN_SCRUBBED_LEN: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum KeyPattern {
+    Exact(String),
+    Suffix(String),
+}
+
+impl KeyPattern {
+    fn parse(pattern: &str) -> Self {
+        let pattern = snake_case(pattern);
+        pattern.strip_prefix('*').map_or_else(
+            || Self::Exact(pattern.clone()),
+            |suffix| Self::Suffix(suffix.to_string()),
+        )
+    }
+
+    fn matches(&self, key: &str) -> bool {
+        match self {
+            Self::Exact(exact) => key == exact,
+            Self::Suffix(suffix) => key.ends_with(suffix.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionPolicy {
+    patterns: Vec<KeyPattern>,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_PATTERNS)
+    }
+}
+
+impl RedactionPolicy {
+    /// A policy from key patterns: exact keys, or `*suffix` for any key
+    /// ending in `suffix`.
+    #[must_use]
+    pub fn new(patterns: &[&str]) -> Self {
+        Self {
+            patterns: patterns.iter().map(|p| KeyPattern::parse(p)).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_secret_key(&self, key: &str) -> bool {
+        let key = snake_case(key);
+        self.patterns.iter().any(|pattern| pattern.matches(&key))
+    }
+
+    /// A copy of `workflow` safe to persist or hand to someone else.
+    #[must_use]
+    pub fn redact_workflow(&self, workflow: &Workflow) -> Workflow {
+        let secrets = self.secret_values(workflow.nodes.iter().map(|node| &node.config));
+        let mut redacted = workflow.clone();
+        for node in &mut redacted.nodes {
+            let marked = marked_keys(&node.config);
+            self.scrub(&mut node.config, &marked, &secrets);
+            if let Some(output) = node.last_output.as_mut() {
+                self.scrub(output, &[], &secrets);
+            }
+        }
+        for run in &mut redacted.history {
+            for output in run.results.values_mut() {
+                self.scrub(output, &[], &secrets);
+            }
+        }
+        for record in &mut redacted.execution_records {
+            for (_, step) in &mut record.steps {
+                if let Some(input) = step.input.as_mut() {
+                    self.scrub(input, &[], &secrets);
+                }
+                if let StepOutput::Success(output) = &mut step.output {
+                    self.scrub(output, &[], &secrets);
+                }
+            }
+        }
+        redacted
+    }
+
+    /// Scrubs the node outputs of a run before they enter history.
+    pub fn redact_results(&self, nodes: &[Node], results: &mut HashMap<NodeId, Value>) {
+        let secrets = self.secret_values(nodes.iter().map(|node| &node.config));
+        for output in results.values_mut() {
+            self.scrub(output, &[], &secrets);
+        }
+    }
+
+    /// A copy of one node's config safe to send to other editors.
+    #[must_use]
+    pub fn redact_config(&self, config: &Value) -> Value {
+        let secrets = self.secret_values(std::slice::from_ref(config));
+        let mut redacted = config.clone();
+        self.scrub(&mut redacted, &marked_keys(config), &secrets);
+        redacted
+    }
+
+    /// Plain-text secret values in `configs`, longest first so a secret
+    /// containing another is replaced whole.
+    fn secret_values<'a>(&self, configs: impl IntoIterator<Item = &'a Value>) -> Vec<String> {
+        let mut values: Vec<String> = configs
+            .into_iter()
+            .flat_map(|config| {
+                let marked = marked_keys(config);
+                config
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter(move |(key, _)| marked.contains(key) || self.is_secret_key(key))
+                    .filter_map(|(_, value)| value.as_str())
+                    .filter(|value| value.len() >= MIN_SCRUBBED_LEN && !is_expression(value))
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+        values.dedup();
+        values
+    }
+
+    fn scrub(&self, value: &mut Value, marked: &[String], secrets: &[String]) {
+        match value {
+            Value::Object(map) => {
+                for (key, entry) in map.iter_mut() {
+                    if marked.contains(key) || self.is_secret_key(key) {
+                        if is_present_secret(entry) {
+                            *entry = Value::String(REDACTED.to_string());
+                        }
+                    } else {
+                        self.scrub(entry, &[], secrets);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.scrub(item, &[], secrets);
+                }
+            }
+            Value::String(text) => {
+                for secret in secrets {
+                    if text.contains(secret.as_str()) {
+                        *text = text.replace(secret.as_str(), REDACTED);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Workflow {
+    /// This workflow with secrets redacted by the default policy.
+    #[must_use]
+    pub fn redacted(&self) -> Self {
+        RedactionPolicy::default().redact_workflow(self)
+    }
+}
+
+fn marked_keys(config: &Value) -> Vec<String> {
+    config
+        .get(SECRET_KEYS_CONFIG_KEY)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_present_secret(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::String(text) => !text.is_empty() && !is_expression(text) && text != REDACTED,
+        _ => true,
+    }
+}
+
+fn is_expression(text: &str) -> bool {
+    let text = text.trim();
+    text.starts_with("{{") && text.ends_with("}}")
+}
+
+/// `apiKey`, `api-key` and `API_KEY` all become `api_key`.
+fn snake_case(key: &str) -> String {
+    let mut snake = String::with_capacity(key.len() + 4);
+    let mut previous_lower = false;
+    for c in key.chars() {
+        if c == '-' || c == ' ' {
+            snake.push('_');
+            previous_lower = false;
+        } else if c.is_ascii_uppercase() {
+            if previous_lower {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+            previous_lower = false;
+        } else {
+            snake.push(c);
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        }
+    }
+    snake
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn given_key_patterns_when_matching_then_case_styles_are_normalized() {
+        let policy = RedactionPolicy::default();
+
+        assert!(policy.is_secret_key("password"));
+        assert!(policy.is_secret_key("accessToken"));
+        assert!(policy.is_secret_key("GITHUB_TOKEN"));
+        assert!(policy.is_secret_key("api-key"));
+        assert!(!policy.is_secret_key("stateKey"));
+        assert!(!policy.is_secret_key("idempotencyKey"));
+        assert!(!policy.is_secret_key(SECRET_KEYS_CONFIG_KEY));
+    }
+
+    #[test]
+    fn given_secrets_in_config_and_outputs_when_redacting_then_every_copy_is_replaced() {
+        let mut workflow = Workflow::new();
+        let id = workflow.add_node("http-request", 0.0, 0.0);
+        let node = workflow
+            .nodes
+            .iter_mut()
+            .find(|node| node.id == id)
+            .unwrap();
+        node.config = json!({
+            "url": "https://api.example.com",
+            "auth_token": "tok-123456",
+            "signing": "hunter22",
+            "fallback_token": "{{ $env.TOKEN }}",
+            "secret_keys": ["signing"],
+        });
+        node.last_output = Some(json!({ "headers": ["Bearer tok-123456"], "sig": "hunter22" }));
+        workflow.history.push(crate::graph::RunRecord {
+            id: uuid::Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            results: HashMap::from([(id, json!({ "echo": "key=tok-123456" }))]),
+            success: true,
+            restate_invocation_id: None,
+            durations: HashMap::new(),
+        });
+
+        let redacted = workflow.redacted();
+
+        let node = &redacted.nodes[0];
+        assert_eq!(node.config["url"], "https://api.example.com");
+        assert_eq!(node.config["auth_token"], REDACTED);
+        assert_eq!(node.config["signing"], REDACTED);
+        assert_eq!(node.config["fallback_token"], "{{ $env.TOKEN }}");
+        assert_eq!(
+            node.last_output,
+            Some(json!({ "headers": ["Bearer [redacted]"], "sig": "[redacted]" }))
+        );
+        assert_eq!(redacted.history[0].results[&id]["echo"], "key=[redacted]");
+        assert_eq!(workflow.nodes[0].config["auth_token"], "tok-123456");
+    }
+}
@@ -0,0 +1,329 @@
+//! Partial workflow export and import.
+//!
+//! [`Workflow::export_subgraph`] cuts the selected nodes and the connections
+//! between them out of a workflow. The result serializes as a standalone
+//! workflow JSON document; edges that crossed the selection boundary are kept
+//! as [`DanglingEdgeStub`]s so the cut is visible to whoever imports it.
+//! [`Workflow::import_subgraph`] merges such a document back into a canvas
+//! with fresh ids, anchored at a given canvas position.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::events::{self, WorkflowEvent};
+use super::{Connection, ExecutionState, Node, NodeId, PortName, Workflow, WorkflowNode};
+
+/// Which side of the selection the cut edge's external endpoint was on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StubDirection {
+    /// The edge entered the selection from an external node.
+    Incoming,
+    /// The edge left the selection towards an external node.
+    Outgoing,
+}
+
+/// A connection that was cut because only one endpoint was selected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DanglingEdgeStub {
+    pub direction: StubDirection,
+    pub node_id: NodeId,
+    pub port: PortName,
+    pub external_node_id: NodeId,
+    pub external_node_name: String,
+    pub external_port: PortName,
+}
+
+/// A selected subgraph, serialized as a standalone workflow document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubgraphExport {
+    #[serde(flatten)]
+    pub workflow: Workflow,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dangling_edges: Vec<DanglingEdgeStub>,
+}
+
+impl SubgraphExport {
+    /// Parses a subgraph document, rebuilding each node's typed config.
+    ///
+    /// Any full workflow JSON is also accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if the text is not a workflow document, has no nodes,
+    /// or contains a node of unknown type.
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let mut export = serde_json::from_str::<Self>(text)
+            .map_err(|err| format!("Invalid subgraph JSON: {err}"))?;
+        if export.workflow.nodes.is_empty() {
+            return Err("Subgraph contains no nodes".to_string());
+        }
+        for node in &mut export.workflow.nodes {
+            node.node = node.node_type.parse::<WorkflowNode>().map_err(|_| {
+                format!("Node '{}' has unknown type '{}'", node.name, node.node_type)
+            })?;
+            let config = node.config.clone();
+            node.apply_config_update(&config);
+        }
+        Ok(export)
+    }
+}
+
+fn reset_runtime_state(node: &mut Node) {
+    node.selected = false;
+    node.executing = false;
+    node.skipped = false;
+    node.error = None;
+    node.last_output = None;
+    node.execution_state = ExecutionState::Idle;
+    node.execution_data = serde_json::Value::Null;
+}
+
+impl Workflow {
+    /// Extracts the given nodes and their internal connections.
+    ///
+    /// Run state, history, and suppressions are not carried over.
+    ///
+    /// # Errors
+    ///
+    /// Returns `String` if `node_ids` is empty or names a node that does not exist.
+    pub fn export_subgraph(&self, node_ids: &[NodeId]) -> Result<SubgraphExport, String> {
+        if node_ids.is_empty() {
+            return Err("Select at least one node to export".to_string());
+        }
+        let selected = node_ids.iter().copied().collect::<HashSet<_>>();
+        if let Some(missing) = selected
+            .iter()
+            .find(|id| !self.nodes.iter().any(|node| node.id == **id))
+        {
+            return Err(format!("Node {missing} not found"));
+        }
+
+        let names = self
+            .nodes
+            .iter()
+            .map(|node| (node.id, node.name.as_str()))
+            .collect::<HashMap<_, _>>();
+        let external_name = |id: NodeId| names.get(&id).copied().unwrap_or_default().to_string();
+
+        let mut subgraph = Self::new();
+        subgraph.nodes = self
+            .nodes
+            .iter()
+            .filter(|node| selected.contains(&node.id))
+            .cloned()
+            .map(|mut node| {
+                reset_runtime_state(&mut node);
+                node
+            })
+            .collect();
+
+        let mut dangling_edges = Vec::new();
+        for connection in &self.connections {
+            match (
+                selected.contains(&connection.source),
+                selected.contains(&connection.target),
+            ) {
+                (true, true) => subgraph.connections.push(connection.clone()),
+                (true, false) => dangling_edges.push(DanglingEdgeStub {
+                    direction: StubDirection::Outgoing,
+                    node_id: connection.source,
+                    port: connection.source_port.clone(),
+                    external_node_id: connection.target,
+                    external_node_name: external_name(connection.target),
+                    external_port: connection.target_port.clone(),
+                }),
+                (false, true) => dangling_edges.push(DanglingEdgeStub {
+                    direction: StubDirection::Incoming,
+                    node_id: connection.target,
+                    port: connection.target_port.clone(),
+                    external_node_id: connection.source,
+                    external_node_name: external_name(connection.source),
+                    external_port: connection.source_port.clone(),
+                }),
+                (false, false) => {}
+            }
+        }
+
+        Ok(SubgraphExport {
+            workflow: subgraph,
+            dangling_edges,
+        })
+    }
+
+    /// Merges `subgraph` into this workflow with its top-left node at `(x, y)`.
+    ///
+    /// Every node and connection gets a fresh id, so the same subgraph can be
+    /// imported repeatedly. Returns the ids of the inserted nodes.
+    pub fn import_subgraph(&mut self, subgraph: &Self, x: f32, y: f32) -> Vec<NodeId> {
+        let min_x = subgraph
+            .nodes
+            .iter()
+            .map(|node| node.x)
+            .fold(f32::INFINITY, f32::min);
+        let min_y = subgraph
+            .nodes
+            .iter()
+            .map(|node| node.y)
+            .fold(f32::INFINITY, f32::min);
+        let (dx, dy) = if min_x.is_finite() && min_y.is_finite() {
+            (x - min_x, y - min_y)
+        } else {
+            (0.0, 0.0)
+        };
+
+        let id_map = subgraph
+            .nodes
+            .iter()
+            .map(|node| (node.id, NodeId::new()))
+            .collect::<HashMap<_, _>>();
+
+        let mut inserted = Vec::with_capacity(subgraph.nodes.len());
+        for node in &subgraph.nodes {
+            let Some(new_id) = id_map.get(&node.id).copied() else {
+                continue;
+            };
+            let mut node = node.clone();
+            node.id = new_id;
+            node.x += dx;
+            node.y += dy;
+            reset_runtime_state(&mut node);
+            inserted.push(new_id);
+            let node_type = node.node_type.clone();
+            self.nodes.push(node);
+            events::emit(&WorkflowEvent::NodeAdded {
+                node_id: new_id,
+                node_type,
+            });
+        }
+
+        self.connections
+            .extend(subgraph.connections.iter().filter_map(|connection| {
+                Some(Connection {
+                    id: Uuid::new_v4(),
+                    source: *id_map.get(&connection.source)?,
+                    target: *id_map.get(&connection.target)?,
+                    source_port: connection.source_port.clone(),
+                    target_port: connection.target_port.clone(),
+                })
+            }));
+
+        inserted
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::{StubDirection, SubgraphExport};
+    use crate::graph::{NodeId, PortName, Workflow, WorkflowNode};
+    use serde_json::json;
+
+    fn main_port() -> PortName {
+        PortName::from("main")
+    }
+
+    fn chain() -> (Workflow, [NodeId; 3]) {
+        let mut workflow = Workflow::new();
+        let entry = workflow.add_node("http-handler", 0.0, 0.0);
+        let run = workflow.add_node("run", 200.0, 40.0);
+        let state = workflow.add_node("set-state", 400.0, 80.0);
+        let _ = workflow.add_connection_checked(entry, run, &main_port(), &main_port());
+        let _ = workflow.add_connection_checked(run, state, &main_port(), &main_port());
+        (workflow, [entry, run, state])
+    }
+
+    #[test]
+    fn given_middle_node_selected_when_exporting_then_boundary_edges_become_stubs() {
+        let (workflow, [entry, run, state]) = chain();
+
+        let export = workflow.export_subgraph(&[run]).unwrap();
+
+        assert_eq!(export.workflow.nodes.len(), 1);
+        assert!(export.workflow.connections.is_empty());
+        assert_eq!(export.dangling_edges.len(), 2);
+        let incoming = export
+            .dangling_edges
+            .iter()
+            .find(|stub| stub.direction == StubDirection::Incoming)
+            .unwrap();
+        assert_eq!(incoming.node_id, run);
+        assert_eq!(incoming.external_node_id, entry);
+        let outgoing = export
+            .dangling_edges
+            .iter()
+            .find(|stub| stub.direction == StubDirection::Outgoing)
+            .unwrap();
+        assert_eq!(outgoing.external_node_id, state);
+    }
+
+    #[test]
+    fn given_exported_json_when_parsed_as_workflow_then_it_is_standalone() {
+        let (mut workflow, [_, run, state]) = chain();
+        workflow.nodes[1].last_output = Some(json!({"done": true}));
+
+        let export = workflow.export_subgraph(&[run, state]).unwrap();
+        let text = serde_json::to_string(&export).unwrap();
+        let standalone = serde_json::from_str::<Workflow>(&text).unwrap();
+
+        assert_eq!(standalone.nodes.len(), 2);
+        assert_eq!(standalone.connections.len(), 1);
+        assert!(standalone.history.is_empty());
+        assert!(standalone
+            .nodes
+            .iter()
+            .all(|node| node.last_output.is_none()));
+    }
+
+    #[test]
+    fn given_subgraph_when_imported_twice_then_ids_are_fresh_and_anchored() {
+        let (workflow, [_, run, state]) = chain();
+        let text =
+            serde_json::to_string(&workflow.export_subgraph(&[run, state]).unwrap()).unwrap();
+        let parsed = SubgraphExport::from_json(&text).unwrap();
+        let mut target = Workflow::new();
+
+        let first = target.import_subgraph(&parsed.workflow, 1000.0, 500.0);
+        let second = target.import_subgraph(&parsed.workflow, 0.0, 0.0);
+
+        assert_eq!(target.nodes.len(), 4);
+        assert_eq!(target.connections.len(), 2);
+        assert!(first.iter().all(|id| !second.contains(id)));
+        assert!(!first.contains(&run));
+        let anchored = target
+            .nodes
+            .iter()
+            .find(|node| node.id == first[0])
+            .unwrap();
+        assert_eq!((anchored.x, anchored.y), (1000.0, 500.0));
+        let shifted = target
+            .nodes
+            .iter()
+            .find(|node| node.id == first[1])
+            .unwrap();
+        assert_eq!((shifted.x, shifted.y), (1200.0, 540.0));
+        assert!(matches!(shifted.node, WorkflowNode::SetState(_)));
+        assert!(target
+            .connections
+            .iter()
+            .any(|connection| { connection.source == first[0] && connection.target == first[1] }));
+    }
+
+    #[test]
+    fn given_invalid_selection_when_exporting_then_error_is_returned() {
+        let (workflow, _) = chain();
+
+        assert!(workflow.export_subgraph(&[]).is_err());
+        assert!(workflow.export_subgraph(&[NodeId::new()]).is_err());
+        let empty = serde_json::to_string(&Workflow::new()).unwrap();
+        assert!(SubgraphExport::from_json(&empty).is_err());
+        assert!(SubgraphExport::from_json("not json").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! Helpers for building workflows in tests, here and in downstream crates
+//! (enable the `testing` feature).
+//!
+//! A fluent [`WorkflowBuilder`], proptest [`strategies`] that generate
+//! random graphs and [`synthetic`] workflows large enough to benchmark.
+
+pub mod strategies;
+pub mod synthetic;
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{Connection, NodeId, PortName, Workflow};
+
+/// Horizontal spacing of nodes placed without an explicit position.
+const COLUMN_WIDTH: f32 = 260.0;
+
+/// Builds a [`Workflow`] from nodes named by test-local keys.
+///
+/// ```
+/// use oya_frontend::graph::testing::WorkflowBuilder;
+///
+/// let workflow = WorkflowBuilder::new()
+///     .node("entry", "http-handler")
+///     .node("check", "condition")
+///     .connect("entry", "check")
+///     .connect_port("check", "true", "charge")
+///     .build();
+/// assert_eq!(workflow.nodes.len(), 3);
+/// ```
+///
+/// Connecting a key that was never declared declares it as a `run` node.
+/// Connections are added as given, without the editor's validation, so
+/// tests can also build graphs the editor would refuse.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowBuilder {
+    workflow: Workflow,
+    ids: HashMap<String, NodeId>,
+}
+
+impl WorkflowBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node, placed to the right of the previous one.
+    #[must_use]
+    pub fn node(self, key: &str, node_type: &str) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let x = self.workflow.nodes.len() as f32 * COLUMN_WIDTH;
+        self.node_at(key, node_type, x, 0.0)
+    }
+
+    /// Add a node at an exact position. Re-using a key replaces the node's
+    /// type but keeps its id and connections.
+    #[must_use]
+    pub fn node_at(mut self, key: &str, node_type: &str, x: f32, y: f32) -> Self {
+        let previous = self.id(key);
+        if let Some(previous) = previous {
+            self.workflow.nodes.retain(|node| node.id != previous);
+        }
+        let added = self.workflow.add_node(node_type, x, y);
+        if let Some(node) = self.workflow.nodes.iter_mut().find(|node| node.id == added) {
+            // `add_node` nudges nodes off occupied spots; keep the exact one.
+            node.id = previous.unwrap_or(added);
+            node.name = key.to_string();
+            node.x = x;
+            node.y = y;
+            self.ids.insert(key.to_string(), node.id);
+        }
+        self
+    }
+
+    /// Merge `config` into the node's configuration.
+    #[must_use]
+    pub fn config(mut self, key: &str, config: &Value) -> Self {
+        let id = self.id_or_declare(key);
+        if let Some(node) = self.workflow.nodes.iter_mut().find(|node| node.id == id) {
+            node.apply_config_update(config);
+        }
+        self
+    }
+
+    /// Connect `from`'s main output to `to`.
+    #[must_use]
+    pub fn connect(self, from: &str, to: &str) -> Self {
+        self.connect_port(from, "main", to)
+    }
+
+    /// Connect a named output port of `from` to `to`.
+    #[must_use]
+    pub fn connect_port(mut self, from: &str, port: &str, to: &str) -> Self {
+        let source = self.id_or_declare(from);
+        let target = self.id_or_declare(to);
+        self.workflow.connections.push(Connection {
+            id: Uuid::new_v4(),
+            source,
+            target,
+            source_port: PortName::from(port),
+            target_port: PortName::from("main"),
+        });
+        self
+    }
+
+    /// The id of a node added so far.
+    #[must_use]
+    pub fn id(&self, key: &str) -> Option<NodeId> {
+        self.ids.get(key).copied()
+    }
+
+    #[must_use]
+    pub fn build(self) -> Workflow {
+        self.workflow
+    }
+
+    /// The workflow together with the id of every key.
+    #[must_use]
+    pub fn build_with_ids(self) -> (Workflow, HashMap<String, NodeId>) {
+        (self.workflow, self.ids)
+    }
+
+    fn id_or_declare(&mut self, key: &str) -> NodeId {
+        if let Some(id) = self.id(key) {
+            return id;
+        }
+        let builder = std::mem::take(self).node(key, "run");
+        *self = builder;
+        self.ids[key]
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::strategies::{arb_runnable_workflow, arb_workflow};
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn given_keys_when_building_then_nodes_and_ports_are_wired() {
+        let (workflow, ids) = WorkflowBuilder::new()
+            .node("entry", "http-handler")
+            .node_at("check", "condition", 300.0, 40.0)
+            .connect("entry", "check")
+            .connect_port("check", "false", "reject")
+            .config(
+                "reject",
+                &serde_json::json!({ "durable_step_name": "reject" }),
+            )
+            .node("check", "switch")
+            .build_with_ids();
+
+        assert_eq!(workflow.nodes.len(), 3);
+        let check = workflow
+            .nodes
+            .iter()
+            .find(|node| node.id == ids["check"])
+            .unwrap();
+        assert_eq!(check.node_type, "switch");
+        assert_eq!(check.name, "check");
+        let reject = workflow
+            .nodes
+            .iter()
+            .find(|node| node.id == ids["reject"])
+            .unwrap();
+        assert_eq!(reject.node_type, "run");
+        assert_eq!(reject.config["durable_step_name"], "reject");
+        assert_eq!(workflow.connections[1].source, ids["check"]);
+        assert_eq!(workflow.connections[1].source_port.0, "false");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn given_runnable_workflow_when_preparing_run_then_queue_is_topological(
+            mut workflow in arb_runnable_workflow(12),
+        ) {
+            prop_assert!(workflow.prepare_run().is_ok());
+            prop_assert_eq!(workflow.execution_queue.len(), workflow.nodes.len());
+            let position = |id: NodeId| workflow.execution_queue.iter().position(|queued| *queued == id);
+            for connection in &workflow.connections {
+                prop_assert!(position(connection.source) < position(connection.target));
+            }
+        }
+
+        #[test]
+        fn given_any_workflow_when_removing_a_node_then_no_connection_dangles(
+            mut workflow in arb_workflow(12),
+            pick in any::<prop::sample::Index>(),
+        ) {
+            let removed = workflow.nodes[pick.index(workflow.nodes.len())].id;
+            workflow.remove_node(removed);
+
+            prop_assert!(workflow.nodes.iter().all(|node| node.id != removed));
+            for connection in &workflow.connections {
+                prop_assert!(workflow.nodes.iter().any(|node| node.id == connection.source));
+                prop_assert!(workflow.nodes.iter().any(|node| node.id == connection.target));
+            }
+        }
+
+        #[test]
+        fn given_any_workflow_when_laying_out_twice_then_positions_are_unchanged(
+            mut workflow in arb_workflow(12),
+        ) {
+            workflow.apply_layout();
+            let once = workflow.nodes.iter().map(|node| (node.x, node.y)).collect::<Vec<_>>();
+            workflow.apply_layout();
+            let twice = workflow.nodes.iter().map(|node| (node.x, node.y)).collect::<Vec<_>>();
+            prop_assert_eq!(once, twice);
+        }
+    }
+}
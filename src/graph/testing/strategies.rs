@@ -0,0 +1,81 @@
+//! Proptest strategies for random workflows.
+
+use std::collections::BTreeSet;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::Index;
+
+use super::WorkflowBuilder;
+use crate::graph::Workflow;
+
+/// Step types that accept and produce a plain `main` flow.
+pub const STEP_TYPES: &[&str] = &["run", "service-call", "http-call", "sleep", "set-state"];
+
+pub fn arb_step_type() -> impl Strategy<Value = &'static str> {
+    proptest::sample::select(STEP_TYPES)
+}
+
+/// Workflows that pass `prepare_run`: an `http-handler` entry followed by
+/// up to `max_nodes - 1` steps, each reached from an earlier node, plus
+/// extra forward edges. Always acyclic and connected.
+pub fn arb_runnable_workflow(max_nodes: usize) -> impl Strategy<Value = Workflow> {
+    (1..=max_nodes.max(1)).prop_flat_map(|count| {
+        (
+            vec(arb_step_type(), count - 1),
+            vec(any::<Index>(), count - 1),
+            vec((any::<Index>(), any::<Index>()), 0..=count),
+        )
+            .prop_map(move |(steps, parents, extra)| {
+                let mut edges = BTreeSet::new();
+                for (offset, parent) in parents.iter().enumerate() {
+                    edges.insert((parent.index(offset + 1), offset + 1));
+                }
+                for (a, b) in extra {
+                    let (a, b) = (a.index(count), b.index(count));
+                    if a != b {
+                        edges.insert((a.min(b), a.max(b)));
+                    }
+                }
+                let builder = steps.into_iter().enumerate().fold(
+                    WorkflowBuilder::new().node("n0", "http-handler"),
+                    |builder, (offset, step)| builder.node(&format!("n{}", offset + 1), step),
+                );
+                connect_all(builder, edges)
+            })
+    })
+}
+
+/// Arbitrary graphs of up to `max_nodes` steps with random edges,
+/// possibly disconnected or cyclic. Never empty.
+pub fn arb_workflow(max_nodes: usize) -> impl Strategy<Value = Workflow> {
+    (1..=max_nodes.max(1)).prop_flat_map(|count| {
+        (
+            vec(arb_step_type(), count),
+            vec((any::<Index>(), any::<Index>()), 0..=count * 2),
+        )
+            .prop_map(move |(steps, edges)| {
+                let builder = steps
+                    .into_iter()
+                    .enumerate()
+                    .fold(WorkflowBuilder::new(), |builder, (index, step)| {
+                        builder.node(&format!("n{index}"), step)
+                    });
+                let edges = edges
+                    .into_iter()
+                    .map(|(a, b)| (a.index(count), b.index(count)))
+                    .filter(|(a, b)| a != b)
+                    .collect();
+                connect_all(builder, edges)
+            })
+    })
+}
+
+fn connect_all(builder: WorkflowBuilder, edges: BTreeSet<(usize, usize)>) -> Workflow {
+    edges
+        .into_iter()
+        .fold(builder, |builder, (a, b)| {
+            builder.connect(&format!("n{a}"), &format!("n{b}"))
+        })
+        .build()
+}
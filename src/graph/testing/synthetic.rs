@@ -0,0 +1,163 @@
+//! Large deterministic workflows for benchmarks and performance budgets.
+//!
+//! [`WorkflowBuilder`](super::WorkflowBuilder) goes through `add_node`,
+//! which nudges every node off its neighbours and so grows quadratically;
+//! these generators place nodes directly and scale to tens of thousands.
+
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::strategies::STEP_TYPES;
+use crate::graph::workflow_node::WorkflowNode;
+use crate::graph::{Connection, Node, PortName, Workflow};
+
+/// Nodes per layer of [`large_workflow`].
+pub const LAYER_WIDTH: usize = 25;
+const COLUMN_SPACING: f32 = 260.0;
+const ROW_SPACING: f32 = 120.0;
+
+/// A runnable workflow of `node_count` nodes.
+///
+/// An `http-handler` entry is followed by layers of [`LAYER_WIDTH`] steps
+/// on a grid. Every step is fed by the step in its column one layer up,
+/// and every other step also by its left neighbour's column, so the graph
+/// is acyclic, connected and has merge points. Node names are `n0`, `n1`, ... in creation order.
+#[must_use]
+pub fn large_workflow(node_count: usize) -> Workflow {
+    let mut workflow = Workflow::new();
+    if node_count == 0 {
+        return workflow;
+    }
+    workflow.nodes.reserve(node_count);
+    workflow.nodes.push(node(0, "http-handler", 0.0, 0.0));
+    for index in 1..node_count {
+        let (layer, column) = ((index - 1) / LAYER_WIDTH, (index - 1) % LAYER_WIDTH);
+        #[allow(clippy::cast_precision_loss)]
+        let (x, y) = (
+            (layer + 1) as f32 * COLUMN_SPACING,
+            column as f32 * ROW_SPACING,
+        );
+        let step_type = STEP_TYPES[index % STEP_TYPES.len()];
+        workflow.nodes.push(node(index, step_type, x, y));
+
+        let parents = if layer == 0 {
+            vec![0]
+        } else {
+            let above = index - LAYER_WIDTH;
+            if column % 2 == 1 {
+                vec![above, above - 1]
+            } else {
+                vec![above]
+            }
+        };
+        for parent in parents {
+            let (source, target) = (workflow.nodes[parent].id, workflow.nodes[index].id);
+            workflow.connections.push(Connection {
+                id: Uuid::new_v4(),
+                source,
+                target,
+                source_port: PortName::from("main"),
+                target_port: PortName::from("main"),
+            });
+        }
+    }
+    workflow
+}
+
+/// A node config nested `depth` objects deep. Each level holds a
+/// `{{...}}` expression reading `node_name`'s output, an array mixing
+/// another with a plain value, and the next level under `next`.
+#[must_use]
+pub fn deep_config(depth: usize, node_name: &str) -> Value {
+    let expression = format!("{{{{$node[\"{node_name}\"].json.value}}}}");
+    (0..depth).fold(json!({ "leaf": expression }), |next, level| {
+        json!({
+            "level": level,
+            "value": expression,
+            "items": [expression, "plain"],
+            "next": next,
+        })
+    })
+}
+
+fn node(index: usize, node_type: &str, x: f32, y: f32) -> Node {
+    let workflow_node = WorkflowNode::from_str(node_type)
+        .unwrap_or_else(|_| WorkflowNode::Run(crate::graph::RunConfig::default()));
+    Node::from_workflow_node(format!("n{index}"), workflow_node, x, y)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::ui::edges::edge_paths;
+    use crate::ui::editor_interactions::nodes_in_rect;
+
+    /// Budgets are loose enough for unoptimised test builds on a busy CI
+    /// machine; they catch accidental quadratic blow-ups, not small drifts.
+    /// `cargo bench --features testing --bench graph_hot_paths` measures
+    /// the real numbers.
+    fn assert_within(budget: Duration, label: &str, work: impl FnOnce()) {
+        let started = Instant::now();
+        work();
+        let elapsed = started.elapsed();
+        assert!(
+            elapsed <= budget,
+            "{label} took {elapsed:?}, over its {budget:?} budget"
+        );
+    }
+
+    #[test]
+    fn given_node_count_when_generating_then_workflow_is_runnable_and_connected() {
+        let mut workflow = large_workflow(1 + LAYER_WIDTH * 3);
+
+        assert_eq!(workflow.nodes.len(), 76);
+        assert_eq!(workflow.connections.len(), 25 + 2 * (25 + 12));
+        assert!(workflow.prepare_run().is_ok());
+        assert_eq!(workflow.execution_queue.len(), 76);
+        assert_eq!(workflow.nodes[0].node_type, "http-handler");
+    }
+
+    #[test]
+    fn given_deep_config_when_resolving_then_every_level_reads_the_node_output() {
+        let mut workflow = large_workflow(2);
+        workflow.nodes[1].last_output = Some(json!({ "value": 41 }));
+        let resolved = workflow.resolve_expressions(&deep_config(3, "n1"));
+
+        assert_eq!(resolved["value"], 41);
+        assert_eq!(resolved["next"]["next"]["items"][0], 41);
+        assert_eq!(resolved["next"]["next"]["items"][1], "plain");
+        assert_eq!(resolved["next"]["next"]["next"]["leaf"], 41);
+    }
+
+    #[test]
+    fn given_ten_thousand_nodes_when_preparing_run_then_it_stays_within_budget() {
+        let mut workflow = large_workflow(10_000);
+        assert_within(Duration::from_secs(5), "prepare_run(10k)", || {
+            assert!(workflow.prepare_run().is_ok());
+        });
+    }
+
+    #[test]
+    fn given_large_canvas_when_hit_testing_and_drawing_edges_then_they_stay_within_budget() {
+        let workflow = large_workflow(10_000);
+        assert_within(Duration::from_millis(500), "marquee(10k)", || {
+            assert!(!nodes_in_rect(&workflow.nodes, (0.0, 0.0, 2_000.0, 1_000.0)).is_empty());
+        });
+        assert_within(Duration::from_secs(5), "edge_paths(10k)", || {
+            assert_eq!(
+                edge_paths(&workflow.connections, &workflow.nodes).len(),
+                workflow.connections.len()
+            );
+        });
+    }
+}
@@ -3,8 +3,10 @@
 use std::collections::HashSet;
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
 /// Severity level for validation issues.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ValidationSeverity {
     Error,
     Warning,
@@ -20,7 +22,7 @@ impl fmt::Display for ValidationSeverity {
 }
 
 /// A validation issue found during workflow validation.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidationIssue {
     pub message: String,
     pub node_id: Option<super::NodeId>,
@@ -109,7 +111,8 @@ impl ValidationResult {
 
 // Re-export validation functions from validation_checks module
 pub use crate::graph::validation_checks::structural::{
-    validate_entry_points, validate_orphan_nodes, validate_reachability,
+    validate_entry_points, validate_grammar_constraints, validate_orphan_nodes,
+    validate_reachability,
 };
 
 /// Validates that all node IDs in the workflow are unique.
@@ -135,6 +138,9 @@ pub fn validate_unique_node_ids(workflow: &super::Workflow) -> Vec<ValidationIss
 }
 
 // The main validation function
+//
+// Node config checks are not part of the graph lint; `compile::compile_workflow`
+// runs them alongside this.
 #[must_use]
 pub fn validate_workflow(workflow: &super::Workflow) -> ValidationResult {
     let mut issues = Vec::new();
@@ -142,18 +148,9 @@ pub fn validate_workflow(workflow: &super::Workflow) -> ValidationResult {
     validate_entry_points(workflow, &mut issues);
     validate_reachability(workflow, &mut issues);
     validate_orphan_nodes(workflow, &mut issues);
+    validate_grammar_constraints(workflow, &mut issues);
     issues.extend(validate_unique_node_ids(workflow));
-
-    // Config validation would go here
-    // for node in &workflow.nodes {
-    //     match workflow_node_from_persisted(node) {
-    //         Ok(workflow_node) => validate_node_config(&workflow_node, node, &mut issues),
-    //         Err(_) => issues.push(ValidationIssue::error_for_node(
-    //             format!("Unknown node type"),
-    //             node.id,
-    //         )),
-    //     }
-    // }
+    super::contract::validate_contract(workflow, &mut issues);
 
     ValidationResult::from_issues(issues)
 }
@@ -0,0 +1,65 @@
+//! Node configuration validations.
+
+use crate::graph::workflow_node::WorkflowNode;
+use crate::graph::{Node, ValidationIssue, Workflow};
+
+// ===========================================================================
+// Config Validations (Known Types, Config Shape, Required Fields)
+// ===========================================================================
+
+/// Reports nodes whose persisted type or config cannot be compiled.
+///
+/// Unknown node types and non-object configs are errors; empty required
+/// fields are warnings because nodes are routinely placed before they are
+/// configured.
+pub fn validate_node_configs(workflow: &Workflow, issues: &mut Vec<ValidationIssue>) {
+    for node in &workflow.nodes {
+        if node.node_type.parse::<WorkflowNode>().is_err() {
+            issues.push(ValidationIssue::error_for_node(
+                format!("Node '{}' has unknown type '{}'", node.name, node.node_type),
+                node.id,
+            ));
+            continue;
+        }
+
+        if !(node.config.is_object() || node.config.is_null()) {
+            issues.push(ValidationIssue::error_for_node(
+                format!("Node '{}' config must be a JSON object", node.name),
+                node.id,
+            ));
+            continue;
+        }
+
+        if let Some(field) = missing_required_field(node) {
+            issues.push(ValidationIssue::warning_for_node(
+                format!("Node '{}' is missing required field '{field}'", node.name),
+                node.id,
+            ));
+        }
+    }
+}
+
+fn missing_required_field(node: &Node) -> Option<&'static str> {
+    let is_blank = |value: Option<&String>| value.is_none_or(|text| text.trim().is_empty());
+
+    match &node.node {
+        WorkflowNode::HttpCall(config) if is_blank(config.url.as_ref()) => Some("url"),
+        WorkflowNode::ServiceCall(config) if is_blank(config.service.as_ref()) => Some("service"),
+        WorkflowNode::SendMessage(config) if is_blank(config.target.as_ref()) => Some("target"),
+        WorkflowNode::DelayedSend(config) if is_blank(config.target.as_ref()) => Some("target"),
+        WorkflowNode::Condition(config) if is_blank(config.expression.as_ref()) => {
+            Some("expression")
+        }
+        WorkflowNode::Switch(config) if is_blank(config.expression.as_ref()) => Some("expression"),
+        WorkflowNode::GetState(config) if is_blank(config.key.as_ref()) => Some("key"),
+        WorkflowNode::SetState(config) if is_blank(config.key.as_ref()) => Some("key"),
+        WorkflowNode::ClearState(config) if is_blank(config.key.as_ref()) => Some("key"),
+        WorkflowNode::KafkaHandler(config) | WorkflowNode::KafkaConsumer(config)
+            if is_blank(config.topic.as_ref()) =>
+        {
+            Some("topic")
+        }
+        WorkflowNode::CronTrigger(config) if is_blank(config.schedule.as_ref()) => Some("schedule"),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
 //! Validation submodules.
 
+pub mod config;
 pub mod structural;
@@ -1,5 +1,6 @@
 //! Structural validations for workflows.
 
+use crate::graph::grammar::{self, StructuralConstraint};
 use crate::graph::graph_ops;
 use crate::graph::{NodeCategory, NodeId, ValidationIssue, Workflow};
 
@@ -79,3 +80,26 @@ pub fn validate_orphan_nodes(workflow: &Workflow, issues: &mut Vec<ValidationIss
         }
     }
 }
+
+/// Reports every node whose declared structural constraints are not met.
+///
+/// Incoming edges into entry nodes are errors; the remaining rules describe
+/// incomplete wiring and are reported as warnings.
+pub fn validate_grammar_constraints(workflow: &Workflow, issues: &mut Vec<ValidationIssue>) {
+    for violation in grammar::check_graph(&workflow.nodes, &workflow.connections) {
+        let name = workflow
+            .nodes
+            .iter()
+            .find(|node| node.id == violation.node_id)
+            .map_or_else(|| violation.node_type.clone(), |node| node.name.clone());
+        let message = format!("Node '{name}' {}", violation.constraint);
+        issues.push(match violation.constraint {
+            StructuralConstraint::NoIncomingEdges => {
+                ValidationIssue::error_for_node(message, violation.node_id)
+            }
+            StructuralConstraint::ExactlyOneSuccessor | StructuralConstraint::DownstreamOf(_) => {
+                ValidationIssue::warning_for_node(message, violation.node_id)
+            }
+        });
+    }
+}
@@ -1,9 +1,13 @@
 use super::layout::DagLayout;
-use super::Workflow;
+use super::{NodeId, Workflow};
 use crate::graph::calc;
 
 const MIN_ZOOM: f32 = 0.15;
 const MAX_ZOOM: f32 = 3.0;
+/// Zoom used when bringing a single node into focus from further out.
+const FOCUS_ZOOM: f32 = 1.0;
+/// Centre of the node box, matching `ui::constants`.
+const NODE_CENTER: (f32, f32) = (110.0, 34.0);
 
 const fn clamp_zoom(value: f32) -> f32 {
     if value < MIN_ZOOM {
@@ -48,6 +52,24 @@ impl Workflow {
             self.viewport.zoom = clamp_zoom(zoom);
         }
     }
+
+    /// Pans so the node sits in the middle of the viewport, zooming in to
+    /// at least 100%. Returns `false` if the node does not exist.
+    pub fn focus_node(&mut self, id: NodeId, viewport_width: f32, viewport_height: f32) -> bool {
+        let Some(node) = self.nodes.iter().find(|node| node.id == id) else {
+            return false;
+        };
+        let zoom = clamp_zoom(self.viewport.zoom.max(FOCUS_ZOOM));
+        let x = (node.x + NODE_CENTER.0).mul_add(-zoom, viewport_width / 2.0);
+        let y = (node.y + NODE_CENTER.1).mul_add(-zoom, viewport_height / 2.0);
+        if !x.is_finite() || !y.is_finite() {
+            return false;
+        }
+        self.viewport.x = x;
+        self.viewport.y = y;
+        self.viewport.zoom = zoom;
+        true
+    }
 }
 
 #[cfg(test)]
@@ -58,7 +80,7 @@ impl Workflow {
     clippy::float_cmp
 )]
 mod tests {
-    use crate::graph::Workflow;
+    use crate::graph::{NodeId, Workflow};
 
     #[test]
     fn given_zoom_delta_when_zooming_then_viewport_values_change() {
@@ -95,4 +117,19 @@ mod tests {
         assert_eq!(workflow.viewport.y, before.y);
         assert_eq!(workflow.viewport.zoom, before.zoom);
     }
+
+    #[test]
+    fn given_zoomed_out_view_when_focusing_node_then_node_is_centred_at_full_zoom() {
+        let mut workflow = Workflow::new();
+        let id = workflow.add_node("run", 400.0, 300.0);
+        workflow.viewport.zoom = 0.5;
+
+        assert!(workflow.focus_node(id, 1000.0, 600.0));
+
+        let viewport = &workflow.viewport;
+        assert_eq!(viewport.zoom, 1.0);
+        assert_eq!(400.0 + 110.0 + viewport.x, 500.0);
+        assert_eq!(300.0 + 34.0 + viewport.y, 300.0);
+        assert!(!workflow.focus_node(NodeId::new(), 1000.0, 600.0));
+    }
 }
@@ -15,6 +15,7 @@ use std::fmt;
 use std::str::FromStr;
 
 use super::NodeCategory;
+use crate::graph::grammar::StructuralConstraint;
 use crate::graph::{restate_types::PortType, service_kinds::ServiceKind};
 
 pub mod configs;
@@ -278,6 +279,26 @@ impl WorkflowNode {
         }
     }
 
+    /// Structural rules this node type must satisfy within a workflow graph.
+    #[must_use]
+    pub const fn structural_constraints(&self) -> &'static [StructuralConstraint] {
+        match self {
+            Self::CronTrigger(_)
+            | Self::HttpHandler(_)
+            | Self::KafkaConsumer(_)
+            | Self::KafkaHandler(_) => &[StructuralConstraint::NoIncomingEdges],
+            Self::Timeout(_) | Self::TimeoutGuard(_) => {
+                &[StructuralConstraint::ExactlyOneSuccessor]
+            }
+            Self::ResolvePromise(_) => &[StructuralConstraint::DownstreamOf(&[
+                "awakeable",
+                "wait-for-webhook",
+                "durable-promise",
+            ])],
+            _ => &[],
+        }
+    }
+
     #[must_use]
     pub const fn output_port_type(&self) -> PortType {
         match self {
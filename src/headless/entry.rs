@@ -0,0 +1,55 @@
+//! Runs a workflow with an external event as one entry node's output.
+
+use serde_json::Value;
+
+use crate::graph::execution_runtime::adapter::{
+    AdapterError, AdapterFuture, ExecutionAdapter, NodeExecutionRequest,
+};
+use crate::graph::{NodeId, Workflow};
+
+/// Feeds `payload` to the triggered entry node; other entry nodes of the
+/// same type output nothing.
+struct EntryPayload<'a> {
+    node_type: &'a str,
+    node_id: NodeId,
+    payload: Value,
+}
+
+impl ExecutionAdapter for EntryPayload<'_> {
+    fn handles(&self, node_type: &str) -> bool {
+        node_type == self.node_type
+    }
+
+    fn execute<'a>(&'a self, request: &'a NodeExecutionRequest) -> AdapterFuture<'a> {
+        let output = if request.node_id == self.node_id {
+            self.payload.clone()
+        } else {
+            Value::Null
+        };
+        Box::pin(async move { Ok::<_, AdapterError>(output) })
+    }
+}
+
+/// Runs `workflow` in place with `payload` as the output of `node_id`, and
+/// returns whether the run succeeded.
+pub(super) async fn run_with_entry(
+    workflow: &mut Workflow,
+    node_id: NodeId,
+    payload: Value,
+) -> bool {
+    let Some(node_type) = workflow
+        .nodes
+        .iter()
+        .find(|node| node.id == node_id)
+        .map(|node| node.node_type.clone())
+    else {
+        return false;
+    };
+    let adapter = EntryPayload {
+        node_type: &node_type,
+        node_id,
+        payload,
+    };
+    workflow.run_with_adapter(&adapter).await;
+    workflow.history.last().is_some_and(|run| run.success)
+}
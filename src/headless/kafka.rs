@@ -0,0 +1,313 @@
+//! Kafka consumption for `kafka-handler` nodes.
+//!
+//! A handler with `brokers` and `topic` configured binds to that topic as
+//! consumer group `group_id` (default [`DEFAULT_GROUP_ID`]). Each message
+//! runs the workflow with the message as the handler's output, and its
+//! offset is committed only when the run succeeds, so a failed run sees the
+//! message again after a restart or rebalance.
+//!
+//! The broker connection, [`BrokerSource`], needs the `kafka` feature; the
+//! consume loop works over any [`MessageSource`].
+
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use tracing::Instrument;
+
+use super::entry::run_with_entry;
+use crate::graph::{NodeId, Workflow};
+
+pub const DEFAULT_GROUP_ID: &str = "oya";
+const KAFKA_HANDLER: &str = "kafka-handler";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum KafkaError {
+    #[error("Node '{0}' needs both brokers and a topic")]
+    Unbound(String),
+    #[error("No kafka-handler node has brokers and a topic")]
+    NoBindings,
+    #[error("Kafka client error: {0}")]
+    Client(String),
+}
+
+/// Where a `kafka-handler` node consumes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KafkaBinding {
+    pub node_id: NodeId,
+    pub brokers: String,
+    pub topic: String,
+    pub group_id: String,
+}
+
+impl KafkaBinding {
+    /// Every `kafka-handler` in `workflow` with brokers and a topic.
+    ///
+    /// # Errors
+    /// Returns an error if a handler sets only one of brokers and topic.
+    pub fn from_workflow(workflow: &Workflow) -> Result<Vec<Self>, KafkaError> {
+        workflow
+            .nodes
+            .iter()
+            .filter(|node| node.node_type == KAFKA_HANDLER)
+            .filter_map(|node| {
+                let brokers = config_str(&node.config, "brokers");
+                let topic = config_str(&node.config, "topic");
+                match (brokers, topic) {
+                    (Some(brokers), Some(topic)) => Some(Ok(Self {
+                        node_id: node.id,
+                        brokers: brokers.to_string(),
+                        topic: topic.to_string(),
+                        group_id: config_str(&node.config, "group_id")
+                            .unwrap_or(DEFAULT_GROUP_ID)
+                            .to_string(),
+                    })),
+                    (None, None) => None,
+                    _ => Some(Err(KafkaError::Unbound(node.name.clone()))),
+                }
+            })
+            .collect()
+    }
+}
+
+/// One consumed message, handed to the workflow as the handler's output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KafkaMessage {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub key: Option<String>,
+    /// JSON payloads are parsed; anything else arrives as a string.
+    pub payload: Value,
+    pub timestamp_ms: Option<i64>,
+}
+
+/// A stream of messages whose offsets are committed explicitly.
+pub trait MessageSource {
+    fn recv(&mut self) -> impl Future<Output = Result<KafkaMessage, KafkaError>> + Send;
+
+    /// Marks `message` and everything before it in its partition as done.
+    ///
+    /// # Errors
+    /// Returns an error if the commit is rejected.
+    fn commit(&mut self, message: &KafkaMessage) -> Result<(), KafkaError>;
+}
+
+/// Consumes messages for `binding` and runs `workflow` once per message,
+/// calling `on_run` with each message and its run. Stops after `limit`
+/// messages when set.
+///
+/// # Errors
+/// Returns an error if receiving or committing fails.
+pub async fn consume<S: MessageSource>(
+    workflow: &Workflow,
+    binding: &KafkaBinding,
+    source: &mut S,
+    limit: Option<usize>,
+    mut on_run: impl FnMut(&KafkaMessage, &Workflow),
+) -> Result<(), KafkaError> {
+    let mut handled = 0;
+    while limit.is_none_or(|limit| handled < limit) {
+        let message = source.recv().await?;
+        let mut run = workflow.clone();
+        let payload = serde_json::to_value(&message).unwrap_or(Value::Null);
+        let span = tracing::info_span!(
+            "kafka.message",
+            topic = %message.topic,
+            partition = message.partition,
+            offset = message.offset,
+        );
+        if run_with_entry(&mut run, binding.node_id, payload)
+            .instrument(span)
+            .await
+        {
+            source.commit(&message)?;
+        }
+        on_run(&message, &run);
+        handled += 1;
+    }
+    Ok(())
+}
+
+fn config_str<'a>(config: &'a Value, key: &str) -> Option<&'a str> {
+    config
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Payload bytes as JSON when they parse, otherwise as a string.
+#[must_use]
+pub fn decode_payload(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+#[cfg(feature = "kafka")]
+pub use broker::BrokerSource;
+
+#[cfg(feature = "kafka")]
+mod broker {
+    use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+    use rdkafka::{ClientConfig, Message, Offset, TopicPartitionList};
+
+    use super::{decode_payload, KafkaBinding, KafkaError, KafkaMessage, MessageSource};
+
+    /// A consumer-group member reading one topic from a real broker.
+    pub struct BrokerSource {
+        consumer: StreamConsumer,
+    }
+
+    impl BrokerSource {
+        /// # Errors
+        /// Returns an error if the client cannot be created or subscribed.
+        pub fn connect(binding: &KafkaBinding) -> Result<Self, KafkaError> {
+            let consumer: StreamConsumer = ClientConfig::new()
+                .set("bootstrap.servers", &binding.brokers)
+                .set("group.id", &binding.group_id)
+                .set("enable.auto.commit", "false")
+                .set("auto.offset.reset", "earliest")
+                .create()
+                .map_err(client_error)?;
+            consumer
+                .subscribe(&[binding.topic.as_str()])
+                .map_err(client_error)?;
+            Ok(Self { consumer })
+        }
+    }
+
+    impl MessageSource for BrokerSource {
+        async fn recv(&mut self) -> Result<KafkaMessage, KafkaError> {
+            let message = self.consumer.recv().await.map_err(client_error)?;
+            Ok(KafkaMessage {
+                topic: message.topic().to_string(),
+                partition: message.partition(),
+                offset: message.offset(),
+                key: message
+                    .key()
+                    .map(|key| String::from_utf8_lossy(key).into_owned()),
+                payload: message
+                    .payload()
+                    .map_or(serde_json::Value::Null, decode_payload),
+                timestamp_ms: message.timestamp().to_millis(),
+            })
+        }
+
+        fn commit(&mut self, message: &KafkaMessage) -> Result<(), KafkaError> {
+            let mut offsets = TopicPartitionList::new();
+            offsets
+                .add_partition_offset(
+                    &message.topic,
+                    message.partition,
+                    Offset::Offset(message.offset + 1),
+                )
+                .map_err(client_error)?;
+            self.consumer
+                .commit(&offsets, CommitMode::Sync)
+                .map_err(client_error)
+        }
+    }
+
+    fn client_error(error: rdkafka::error::KafkaError) -> KafkaError {
+        KafkaError::Client(error.to_string())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::WorkflowBuilder;
+    use serde_json::json;
+    use std::collections::VecDeque;
+
+    #[derive(Default)]
+    struct QueueSource {
+        pending: VecDeque<KafkaMessage>,
+        committed: Vec<i64>,
+    }
+
+    impl MessageSource for QueueSource {
+        async fn recv(&mut self) -> Result<KafkaMessage, KafkaError> {
+            self.pending
+                .pop_front()
+                .ok_or_else(|| KafkaError::Client("drained".to_string()))
+        }
+
+        fn commit(&mut self, message: &KafkaMessage) -> Result<(), KafkaError> {
+            self.committed.push(message.offset);
+            Ok(())
+        }
+    }
+
+    fn message(offset: i64, payload: &[u8]) -> KafkaMessage {
+        KafkaMessage {
+            topic: "orders".to_string(),
+            partition: 0,
+            offset,
+            key: None,
+            payload: decode_payload(payload),
+            timestamp_ms: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn given_messages_when_consuming_then_only_successful_runs_commit() {
+        let workflow = WorkflowBuilder::new()
+            .node("entry", KAFKA_HANDLER)
+            .node("check", "run")
+            .connect("entry", "check")
+            .config(
+                "entry",
+                &json!({ "brokers": "localhost:9092", "topic": "orders" }),
+            )
+            .config(
+                "check",
+                &json!({ "mapping": { "error": "{{ $node[\"entry\"].json.payload.error }}" } }),
+            )
+            .build();
+        let bindings = KafkaBinding::from_workflow(&workflow).unwrap();
+        assert_eq!(bindings[0].group_id, DEFAULT_GROUP_ID);
+        let mut source = QueueSource {
+            pending: VecDeque::from([
+                message(4, br#"{"error": "out of stock"}"#),
+                message(5, b"not json"),
+            ]),
+            ..QueueSource::default()
+        };
+        let mut payloads = Vec::new();
+
+        consume(
+            &workflow,
+            &bindings[0],
+            &mut source,
+            Some(2),
+            |message, _| {
+                payloads.push(message.payload.clone());
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            payloads,
+            vec![json!({ "error": "out of stock" }), json!("not json")]
+        );
+        assert_eq!(source.committed, vec![5]);
+    }
+
+    #[test]
+    fn given_handler_with_topic_but_no_brokers_when_binding_then_it_is_reported() {
+        let workflow = WorkflowBuilder::new()
+            .node("entry", KAFKA_HANDLER)
+            .config("entry", &json!({ "topic": "orders" }))
+            .build();
+
+        assert_eq!(
+            KafkaBinding::from_workflow(&workflow),
+            Err(KafkaError::Unbound("entry".to_string()))
+        );
+    }
+}
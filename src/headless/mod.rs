@@ -0,0 +1,24 @@
+//! Headless workflow execution: run canvas-authored workflows from other
+//! tools and agents over a local JSON-RPC/HTTP server, without a browser,
+//! on the schedules of their `cron-trigger` nodes, as local endpoints for
+//! their `http-handler` nodes, and from the topics of their `kafka-handler`
+//! nodes.
+
+mod entry;
+pub mod kafka;
+pub mod rpc;
+pub mod scheduler;
+pub mod server;
+pub mod telemetry;
+pub mod webhook;
+
+#[cfg(feature = "kafka")]
+pub use kafka::BrokerSource;
+pub use kafka::{KafkaBinding, KafkaError, KafkaMessage, MessageSource};
+pub use rpc::{ExecutionHost, NodeEvent, RpcError, RpcRequest, RpcResponse, RunStatus, RunSummary};
+pub use scheduler::{ScheduleError, Scheduler};
+pub use server::{serve, ApiAccess};
+pub use telemetry::OtlpConfig;
+#[cfg(feature = "otel")]
+pub use telemetry::{init as init_telemetry, TelemetryError, TelemetryGuard};
+pub use webhook::{serve_webhooks, WebhookError, WebhookRequest, WebhookResponse, WebhookRuntime};
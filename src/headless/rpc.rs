@@ -0,0 +1,491 @@
+//! JSON-RPC 2.0 methods for loading and running workflows.
+//!
+//! | method          | params                 | result                       |
+//! |-----------------|------------------------|------------------------------|
+//! | `workflow.load` | `{workflow}`           | `{workflow_id, nodes}`       |
+//! | `workflow.run`  | `{workflow_id, wait?}` | `{run_id}` or [`RunSummary`] |
+//! | `run.events`    | `{run_id, after?}`     | `{events, finished}`         |
+//! | `run.result`    | `{run_id}`             | [`RunSummary`]               |
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::{Mutex, Notify};
+use uuid::Uuid;
+
+use crate::graph::{ExecutionState, NodeId, Workflow};
+use crate::metrics::{AuditAction, AuditEntry, MetricsStore};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+pub const UNKNOWN_WORKFLOW: i64 = -32001;
+pub const UNKNOWN_RUN: i64 = -32002;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    /// Absent for notifications, which get no response.
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn reply(id: Value, outcome: Result<Value, RpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// One node finishing (or being skipped) during a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeEvent {
+    pub sequence: usize,
+    pub node_id: NodeId,
+    pub name: String,
+    pub node_type: String,
+    pub state: ExecutionState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    pub run_id: Uuid,
+    pub workflow_id: Uuid,
+    pub status: RunStatus,
+    /// Why the run could not start, e.g. a cycle in the graph.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub events: Vec<NodeEvent>,
+    /// Output of every node that produced one.
+    pub results: BTreeMap<NodeId, Value>,
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    workflows: HashMap<Uuid, Workflow>,
+    runs: HashMap<Uuid, RunSummary>,
+}
+
+/// Loaded workflows and their runs, shared by every connection. Runs
+/// execute on their own task, so a slow node never blocks other calls.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionHost {
+    state: Arc<Mutex<HostState>>,
+    changed: Arc<Notify>,
+    audit: Option<Arc<MetricsStore>>,
+}
+
+impl ExecutionHost {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record every load and finished run in `store`'s audit log, under the
+    /// workflow id the host assigned.
+    #[must_use]
+    pub fn with_audit(mut self, store: Arc<MetricsStore>) -> Self {
+        self.audit = Some(store);
+        self
+    }
+
+    /// Handle one JSON-RPC request body. Returns `None` for notifications.
+    pub async fn handle_json(&self, body: &str) -> Option<RpcResponse> {
+        let request = match serde_json::from_str::<Value>(body) {
+            Err(error) => {
+                return Some(RpcResponse::reply(
+                    Value::Null,
+                    Err(RpcError::new(PARSE_ERROR, error.to_string())),
+                ))
+            }
+            Ok(value) => serde_json::from_value::<RpcRequest>(value.clone())
+                .map_err(|error| (value.get("id").cloned(), error)),
+        };
+        match request {
+            Ok(request) => self.call(request).await,
+            Err((id, error)) => Some(RpcResponse::reply(
+                id.unwrap_or(Value::Null),
+                Err(RpcError::new(INVALID_REQUEST, error.to_string())),
+            )),
+        }
+    }
+
+    pub async fn call(&self, request: RpcRequest) -> Option<RpcResponse> {
+        let outcome = if request.jsonrpc == "2.0" {
+            self.dispatch(&request.method, &request.params).await
+        } else {
+            Err(RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""))
+        };
+        request.id.map(|id| RpcResponse::reply(id, outcome))
+    }
+
+    #[tracing::instrument(name = "rpc.call", skip_all, fields(rpc.method = method))]
+    async fn dispatch(&self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "workflow.load" => {
+                let workflow = params
+                    .get("workflow")
+                    .cloned()
+                    .ok_or_else(|| missing("workflow"))?;
+                let workflow = serde_json::from_value::<Workflow>(workflow)
+                    .map_err(|error| RpcError::new(INVALID_PARAMS, error.to_string()))?;
+                let nodes = workflow.nodes.len();
+                let workflow_id = self.load(workflow).await;
+                Ok(json!({ "workflow_id": workflow_id, "nodes": nodes }))
+            }
+            "workflow.run" => {
+                let run_id = self.start_run(uuid_param(params, "workflow_id")?).await?;
+                if params.get("wait").and_then(Value::as_bool) == Some(true) {
+                    to_value(&self.wait(run_id).await?)
+                } else {
+                    Ok(json!({ "run_id": run_id }))
+                }
+            }
+            "run.events" => {
+                let after = params.get("after").and_then(Value::as_u64).unwrap_or(0);
+                let after = usize::try_from(after).unwrap_or(usize::MAX);
+                let (events, finished) = self.events(uuid_param(params, "run_id")?, after).await?;
+                Ok(json!({ "events": events, "finished": finished }))
+            }
+            "run.result" => to_value(&self.summary(uuid_param(params, "run_id")?).await?),
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method: {method}"),
+            )),
+        }
+    }
+
+    pub async fn load(&self, workflow: Workflow) -> Uuid {
+        let workflow_id = Uuid::new_v4();
+        self.state
+            .lock()
+            .await
+            .workflows
+            .insert(workflow_id, workflow);
+        self.audit(AuditEntry::new(
+            AuditAction::Import,
+            &workflow_id.to_string(),
+        ));
+        workflow_id
+    }
+
+    /// Start running a copy of a loaded workflow.
+    ///
+    /// # Errors
+    /// Returns an error if no workflow was loaded under `workflow_id`.
+    pub async fn start_run(&self, workflow_id: Uuid) -> Result<Uuid, RpcError> {
+        let run_id = Uuid::new_v4();
+        let workflow = {
+            let mut state = self.state.lock().await;
+            let workflow = state.workflows.get(&workflow_id).cloned().ok_or_else(|| {
+                RpcError::new(UNKNOWN_WORKFLOW, format!("Unknown workflow: {workflow_id}"))
+            })?;
+            state.runs.insert(
+                run_id,
+                RunSummary {
+                    run_id,
+                    workflow_id,
+                    status: RunStatus::Running,
+                    error: None,
+                    events: Vec::new(),
+                    results: BTreeMap::new(),
+                },
+            );
+            workflow
+        };
+        let host = self.clone();
+        tokio::spawn(async move { host.execute(run_id, workflow).await });
+        Ok(run_id)
+    }
+
+    async fn execute(&self, run_id: Uuid, mut workflow: Workflow) {
+        if let Err(error) = workflow.prepare_run() {
+            self.finish(run_id, RunStatus::Failed, Some(error.to_string()))
+                .await;
+            return;
+        }
+        while !workflow.execution_failed && workflow.step().await {
+            let Some(node) = workflow
+                .execution_queue
+                .get(workflow.current_step.saturating_sub(1))
+                .and_then(|id| workflow.nodes.iter().find(|node| node.id == *id))
+            else {
+                continue;
+            };
+            {
+                let mut state = self.state.lock().await;
+                if let Some(run) = state.runs.get_mut(&run_id) {
+                    if let Some(output) = &node.last_output {
+                        run.results.insert(node.id, output.clone());
+                    }
+                    run.events.push(NodeEvent {
+                        sequence: run.events.len(),
+                        node_id: node.id,
+                        name: node.name.clone(),
+                        node_type: node.node_type.clone(),
+                        state: node.execution_state,
+                        output: node.last_output.clone(),
+                        error: node.error.clone(),
+                    });
+                }
+            }
+            self.changed.notify_waiters();
+        }
+        let succeeded = workflow.nodes.iter().all(|node| {
+            node.error.is_none()
+                && matches!(
+                    node.execution_state,
+                    ExecutionState::Completed | ExecutionState::Skipped
+                )
+        });
+        let status = if succeeded {
+            RunStatus::Succeeded
+        } else {
+            RunStatus::Failed
+        };
+        self.finish(run_id, status, None).await;
+    }
+
+    async fn finish(&self, run_id: Uuid, status: RunStatus, error: Option<String>) {
+        let workflow_id = self.state.lock().await.runs.get_mut(&run_id).map(|run| {
+            run.status = status;
+            run.error = error;
+            run.workflow_id
+        });
+        self.changed.notify_waiters();
+        if let Some(workflow_id) = workflow_id {
+            self.audit(
+                AuditEntry::new(AuditAction::Run, &workflow_id.to_string())
+                    .with_detail(run_id.to_string())
+                    .with_success(status == RunStatus::Succeeded),
+            );
+        }
+    }
+
+    /// Audit failures are logged; they never fail the call being audited.
+    fn audit(&self, entry: AuditEntry) {
+        if let Some(Err(error)) = self.audit.as_ref().map(|store| store.record_audit(entry)) {
+            tracing::warn!(%error, "could not record audit entry");
+        }
+    }
+
+    /// Events after the first `after`, and whether the run has finished.
+    ///
+    /// # Errors
+    /// Returns an error if there is no run `run_id`.
+    pub async fn events(
+        &self,
+        run_id: Uuid,
+        after: usize,
+    ) -> Result<(Vec<NodeEvent>, bool), RpcError> {
+        let state = self.state.lock().await;
+        let run = state.runs.get(&run_id).ok_or_else(|| unknown_run(run_id))?;
+        Ok((
+            run.events.iter().skip(after).cloned().collect(),
+            run.status != RunStatus::Running,
+        ))
+    }
+
+    /// # Errors
+    /// Returns an error if there is no run `run_id`.
+    pub async fn summary(&self, run_id: Uuid) -> Result<RunSummary, RpcError> {
+        self.state
+            .lock()
+            .await
+            .runs
+            .get(&run_id)
+            .cloned()
+            .ok_or_else(|| unknown_run(run_id))
+    }
+
+    /// Wait until the run's events grow past `after` or it finishes.
+    pub async fn changed_since(&self, run_id: Uuid, after: usize) {
+        let notified = self.changed.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        match self.events(run_id, after).await {
+            Ok((events, finished)) if events.is_empty() && !finished => notified.await,
+            _ => {}
+        }
+    }
+
+    /// Wait for the run to finish and return its summary.
+    ///
+    /// # Errors
+    /// Returns an error if there is no run `run_id`.
+    pub async fn wait(&self, run_id: Uuid) -> Result<RunSummary, RpcError> {
+        loop {
+            let summary = self.summary(run_id).await?;
+            if summary.status != RunStatus::Running {
+                return Ok(summary);
+            }
+            self.changed_since(run_id, summary.events.len()).await;
+        }
+    }
+}
+
+fn missing(name: &str) -> RpcError {
+    RpcError::new(INVALID_PARAMS, format!("Missing parameter: {name}"))
+}
+
+fn unknown_run(run_id: Uuid) -> RpcError {
+    RpcError::new(UNKNOWN_RUN, format!("Unknown run: {run_id}"))
+}
+
+fn uuid_param(params: &Value, name: &str) -> Result<Uuid, RpcError> {
+    let text = params
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| missing(name))?;
+    Uuid::parse_str(text)
+        .map_err(|error| RpcError::new(INVALID_PARAMS, format!("Invalid {name}: {error}")))
+}
+
+fn to_value(summary: &RunSummary) -> Result<Value, RpcError> {
+    serde_json::to_value(summary).map_err(|error| RpcError::new(INTERNAL_ERROR, error.to_string()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::WorkflowBuilder;
+
+    async fn call(host: &ExecutionHost, method: &str, params: Value) -> RpcResponse {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        host.handle_json(&body.to_string()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn given_loaded_workflow_when_running_with_wait_then_every_node_reports_an_event() {
+        let host = ExecutionHost::new();
+        let workflow = WorkflowBuilder::new()
+            .node("entry", "http-handler")
+            .node("step", "run")
+            .connect("entry", "step")
+            .build();
+
+        let loaded = call(
+            &host,
+            "workflow.load",
+            json!({ "workflow": serde_json::to_value(&workflow).unwrap() }),
+        )
+        .await;
+        let workflow_id = loaded.result.unwrap()["workflow_id"].clone();
+        let ran = call(
+            &host,
+            "workflow.run",
+            json!({ "workflow_id": workflow_id, "wait": true }),
+        )
+        .await;
+        let summary: RunSummary = serde_json::from_value(ran.result.unwrap()).unwrap();
+
+        assert_eq!(summary.status, RunStatus::Succeeded);
+        let names = summary
+            .events
+            .iter()
+            .map(|event| event.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["entry", "step"]);
+
+        let later = call(
+            &host,
+            "run.events",
+            json!({ "run_id": summary.run_id, "after": 1 }),
+        )
+        .await
+        .result
+        .unwrap();
+        assert_eq!(later["events"].as_array().unwrap().len(), 1);
+        assert_eq!(later["finished"], true);
+    }
+
+    #[tokio::test]
+    async fn given_cyclic_workflow_when_running_then_run_fails_with_reason() {
+        let host = ExecutionHost::new();
+        let workflow_id = host
+            .load(
+                WorkflowBuilder::new()
+                    .node("a", "http-handler")
+                    .connect("a", "b")
+                    .connect("b", "c")
+                    .connect("c", "b")
+                    .build(),
+            )
+            .await;
+
+        let run_id = host.start_run(workflow_id).await.unwrap();
+        let summary = host.wait(run_id).await.unwrap();
+
+        assert_eq!(summary.status, RunStatus::Failed);
+        assert!(summary.error.unwrap().to_lowercase().contains("cycle"));
+    }
+
+    #[tokio::test]
+    async fn given_bad_requests_when_handling_then_json_rpc_error_codes_are_returned() {
+        let host = ExecutionHost::new();
+
+        let parse = host.handle_json("{").await.unwrap();
+        assert_eq!(parse.error.unwrap().code, PARSE_ERROR);
+        let unknown = call(&host, "workflow.delete", json!({})).await;
+        assert_eq!(unknown.error.unwrap().code, METHOD_NOT_FOUND);
+        let missing = call(&host, "run.result", json!({})).await;
+        assert_eq!(missing.error.unwrap().code, INVALID_PARAMS);
+        let run = call(&host, "run.result", json!({ "run_id": Uuid::new_v4() })).await;
+        assert_eq!(run.error.unwrap().code, UNKNOWN_RUN);
+        let notification = json!({ "jsonrpc": "2.0", "method": "run.result", "params": {} });
+        assert!(host.handle_json(&notification.to_string()).await.is_none());
+    }
+}
@@ -0,0 +1,159 @@
+//! Cron scheduling for `cron-trigger` workflows.
+//!
+//! A [`Scheduler`] owns one workflow, parses the schedule of every
+//! `cron-trigger` node in it, and runs the workflow headlessly whenever the
+//! earliest of those schedules comes due. Each run lands in the workflow's
+//! history like a run started from the canvas.
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::graph::cron::{CronError, CronSchedule};
+use crate::graph::{NodeId, RunRecord, Workflow};
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ScheduleError {
+    #[error("Workflow has no cron-trigger node with a schedule")]
+    NoTriggers,
+    #[error("Node '{node}' has an invalid schedule: {source}")]
+    InvalidSchedule { node: String, source: CronError },
+    #[error("No schedule in the workflow will ever fire")]
+    NeverFires,
+}
+
+pub struct Scheduler {
+    workflow: Workflow,
+    triggers: Vec<(NodeId, CronSchedule)>,
+}
+
+impl Scheduler {
+    /// # Errors
+    /// Returns an error if the workflow has no scheduled `cron-trigger` node
+    /// or one of the schedules does not parse.
+    pub fn new(workflow: Workflow) -> Result<Self, ScheduleError> {
+        let triggers = workflow
+            .cron_triggers()
+            .into_iter()
+            .map(|(id, schedule)| {
+                schedule.map(|schedule| (id, schedule)).map_err(|source| {
+                    ScheduleError::InvalidSchedule {
+                        node: workflow
+                            .nodes
+                            .iter()
+                            .find(|node| node.id == id)
+                            .map_or_else(|| id.to_string(), |node| node.name.clone()),
+                        source,
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if triggers.is_empty() {
+            return Err(ScheduleError::NoTriggers);
+        }
+        Ok(Self { workflow, triggers })
+    }
+
+    #[must_use]
+    pub const fn workflow(&self) -> &Workflow {
+        &self.workflow
+    }
+
+    #[must_use]
+    pub fn into_workflow(self) -> Workflow {
+        self.workflow
+    }
+
+    /// The earliest fire time after `after` across all triggers, and the
+    /// trigger that owns it.
+    #[must_use]
+    pub fn next_fire(&self, after: DateTime<Utc>) -> Option<(DateTime<Utc>, NodeId)> {
+        self.triggers
+            .iter()
+            .filter_map(|(id, schedule)| Some((schedule.next_after(after)?, *id)))
+            .min_by_key(|(time, _)| *time)
+    }
+
+    /// Runs the workflow now and returns the recorded run.
+    #[tracing::instrument(name = "schedule.fire", skip_all)]
+    pub async fn fire(&mut self) -> Option<&RunRecord> {
+        self.workflow.run().await;
+        self.workflow.history.last()
+    }
+
+    /// Sleeps until each fire time and runs the workflow, calling `on_run`
+    /// after every run. Returns after the first run when `once` is set.
+    ///
+    /// # Errors
+    /// Returns an error if no schedule can fire again.
+    pub async fn run<F: FnMut(&Workflow)>(
+        &mut self,
+        once: bool,
+        mut on_run: F,
+    ) -> Result<(), ScheduleError> {
+        loop {
+            let now = Utc::now();
+            let (due, _) = self.next_fire(now).ok_or(ScheduleError::NeverFires)?;
+            if let Ok(wait) = (due - now).to_std() {
+                tokio::time::sleep(wait).await;
+            }
+            self.fire().await;
+            on_run(&self.workflow);
+            if once {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::cron::SCHEDULE_CONFIG_KEY;
+    use serde_json::json;
+
+    fn scheduled(schedules: &[&str]) -> Workflow {
+        let mut workflow = Workflow::new();
+        for schedule in schedules {
+            let id = workflow.add_node("cron-trigger", 0.0, 0.0);
+            if let Some(node) = workflow.nodes.iter_mut().find(|node| node.id == id) {
+                node.config = json!({ SCHEDULE_CONFIG_KEY: schedule });
+            }
+        }
+        workflow
+    }
+
+    #[tokio::test]
+    async fn given_two_triggers_when_scheduling_then_earliest_fires_and_run_is_recorded() {
+        let workflow = scheduled(&["0 * * * *", "*/10 * * * *"]);
+        let every_ten = workflow.nodes[1].id;
+        let mut scheduler = Scheduler::new(workflow).unwrap();
+        let now = DateTime::parse_from_rfc3339("2026-10-17T10:07:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let (due, node) = scheduler.next_fire(now).unwrap();
+        assert_eq!(node, every_ten);
+        assert_eq!(due.to_rfc3339(), "2026-10-17T10:10:00+00:00");
+
+        assert!(scheduler.fire().await.is_some());
+        assert_eq!(scheduler.into_workflow().history.len(), 1);
+    }
+
+    #[test]
+    fn given_missing_or_bad_schedules_when_creating_then_scheduler_is_rejected() {
+        assert!(matches!(
+            Scheduler::new(Workflow::new()),
+            Err(ScheduleError::NoTriggers)
+        ));
+        assert!(matches!(
+            Scheduler::new(scheduled(&["every day"])),
+            Err(ScheduleError::InvalidSchedule { .. })
+        ));
+    }
+}
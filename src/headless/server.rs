@@ -0,0 +1,336 @@
+//! HTTP transport for the execution API: JSON-RPC over `POST /rpc` and a
+//! server-sent-event stream of node events per run.
+//!
+//! Every request must carry `Authorization: Bearer <token>`. Browsers are
+//! only answered for origins on the allowlist, so a page the user happens
+//! to visit cannot drive workflows through a localhost server.
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use uuid::Uuid;
+
+use super::rpc::ExecutionHost;
+use crate::http::{read_request, respond, start_event_stream, Request};
+
+/// Who may call the execution API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiAccess {
+    token: String,
+    allowed_origins: Vec<String>,
+}
+
+impl ApiAccess {
+    /// Require `token` as the bearer token. An empty token admits nobody.
+    #[must_use]
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            allowed_origins: Vec::new(),
+        }
+    }
+
+    /// Let browser pages served from `origin` (e.g. `http://localhost:8081`)
+    /// call the API.
+    #[must_use]
+    pub fn with_allowed_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins
+            .push(origin.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    fn admits(&self, request: &Request) -> bool {
+        !self.token.is_empty()
+            && request
+                .bearer_token()
+                .is_some_and(|token| constant_time_eq(token.as_bytes(), self.token.as_bytes()))
+    }
+
+    /// Cross-origin headers for the request's origin: none for non-browser
+    /// clients, `None` for an origin not on the allowlist.
+    fn cors_headers<'a>(&self, request: &'a Request) -> Option<Vec<(&'static str, &'a str)>> {
+        let Some(origin) = request.header("origin") else {
+            return Some(Vec::new());
+        };
+        if !self.allowed_origins.iter().any(|allowed| allowed == origin) {
+            return None;
+        }
+        Some(vec![
+            ("Access-Control-Allow-Origin", origin),
+            ("Access-Control-Allow-Methods", "GET, POST, OPTIONS"),
+            (
+                "Access-Control-Allow-Headers",
+                "Authorization, Content-Type",
+            ),
+            ("Vary", "Origin"),
+        ])
+    }
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    left.len() == right.len()
+        && left
+            .iter()
+            .zip(right)
+            .fold(0, |diff, (left, right)| diff | (left ^ right))
+            == 0
+}
+
+/// Serve `host` on `listener` to callers `access` admits, until accepting a
+/// connection fails.
+///
+/// Routes: `POST /rpc` (one JSON-RPC 2.0 request) and
+/// `GET /runs/<run_id>/events` (a `node` event per finished node, then a
+/// `finished` event carrying the run summary).
+///
+/// # Errors
+/// Returns an error if the listener stops accepting connections.
+pub async fn serve(
+    listener: TcpListener,
+    host: ExecutionHost,
+    access: ApiAccess,
+) -> std::io::Result<()> {
+    let access = std::sync::Arc::new(access);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let (host, access) = (host.clone(), std::sync::Arc::clone(&access));
+        tokio::spawn(async move {
+            // A client hanging up mid-response is not the server's problem.
+            let _ = handle(stream, &host, &access).await;
+        });
+    }
+}
+
+async fn handle(
+    mut stream: TcpStream,
+    host: &ExecutionHost,
+    access: &ApiAccess,
+) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+    let Some(cors) = access.cors_headers(&request) else {
+        return respond(
+            &mut stream,
+            "403 Forbidden",
+            &[],
+            "text/plain",
+            "Origin not allowed",
+        )
+        .await;
+    };
+    if request.method == "OPTIONS" {
+        return respond(&mut stream, "204 No Content", &cors, "text/plain", "").await;
+    }
+    if !access.admits(&request) {
+        let mut headers = cors.clone();
+        headers.push(("WWW-Authenticate", "Bearer"));
+        return respond(
+            &mut stream,
+            "401 Unauthorized",
+            &headers,
+            "text/plain",
+            "Bearer token required",
+        )
+        .await;
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/rpc") => {
+            let body = String::from_utf8_lossy(&request.body);
+            match host.handle_json(&body).await {
+                Some(response) => {
+                    let json = serde_json::to_string(&response).map_err(std::io::Error::other)?;
+                    respond(&mut stream, "200 OK", &cors, "application/json", &json).await
+                }
+                None => respond(&mut stream, "204 No Content", &cors, "text/plain", "").await,
+            }
+        }
+        ("GET", path) => match path
+            .strip_prefix("/runs/")
+            .and_then(|rest| rest.strip_suffix("/events"))
+            .and_then(|id| Uuid::parse_str(id).ok())
+        {
+            Some(run_id) if host.summary(run_id).await.is_ok() => {
+                stream_events(&mut stream, &cors, host, run_id).await
+            }
+            _ => {
+                respond(
+                    &mut stream,
+                    "404 Not Found",
+                    &cors,
+                    "text/plain",
+                    "Not found",
+                )
+                .await
+            }
+        },
+        _ => {
+            respond(
+                &mut stream,
+                "405 Method Not Allowed",
+                &cors,
+                "text/plain",
+                "POST /rpc or GET /runs/<id>/events",
+            )
+            .await
+        }
+    }
+}
+
+/// Replay the run's events so far, follow new ones as they happen, and
+/// close the stream once the run finishes.
+async fn stream_events(
+    stream: &mut TcpStream,
+    cors: &[(&str, &str)],
+    host: &ExecutionHost,
+    run_id: Uuid,
+) -> std::io::Result<()> {
+    start_event_stream(stream, cors).await?;
+    let mut sent = 0;
+    loop {
+        let Ok((events, finished)) = host.events(run_id, sent).await else {
+            break;
+        };
+        for event in &events {
+            let data = serde_json::to_string(event).map_err(std::io::Error::other)?;
+            stream
+                .write_all(format!("event: node\ndata: {data}\n\n").as_bytes())
+                .await?;
+        }
+        sent += events.len();
+        if finished {
+            if let Ok(summary) = host.summary(run_id).await {
+                let data = serde_json::to_string(&summary).map_err(std::io::Error::other)?;
+                stream
+                    .write_all(format!("event: finished\ndata: {data}\n\n").as_bytes())
+                    .await?;
+            }
+            break;
+        }
+        stream.flush().await?;
+        host.changed_since(run_id, sent).await;
+    }
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::WorkflowBuilder;
+    use serde_json::{json, Value};
+    use tokio::io::AsyncReadExt;
+
+    const TOKEN: &str = "test-token";
+
+    async fn start() -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let access = ApiAccess::new(TOKEN).with_allowed_origin("http://localhost:8081/");
+        tokio::spawn(serve(listener, ExecutionHost::new(), access));
+        addr
+    }
+
+    async fn send(addr: std::net::SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    async fn rpc(addr: std::net::SocketAddr, method: &str, params: Value) -> Value {
+        let body =
+            json!({ "jsonrpc": "2.0", "id": 7, "method": method, "params": params }).to_string();
+        let response = send(
+            addr,
+            &format!(
+                "POST /rpc HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {TOKEN}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            ),
+        )
+        .await;
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        serde_json::from_str(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn given_running_server_when_loading_running_and_streaming_then_events_arrive() {
+        let addr = start().await;
+        let workflow = WorkflowBuilder::new()
+            .node("entry", "http-handler")
+            .node("step", "run")
+            .connect("entry", "step")
+            .build();
+
+        let loaded = rpc(addr, "workflow.load", json!({ "workflow": workflow })).await;
+        assert_eq!(loaded["id"], 7);
+        let started = rpc(
+            addr,
+            "workflow.run",
+            json!({ "workflow_id": loaded["result"]["workflow_id"] }),
+        )
+        .await;
+        let run_id = started["result"]["run_id"].as_str().unwrap().to_string();
+
+        let stream = send(
+            addr,
+            &format!(
+                "GET /runs/{run_id}/events HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {TOKEN}\r\n\r\n"
+            ),
+        )
+        .await;
+        assert!(stream.contains("text/event-stream"));
+        assert_eq!(stream.matches("event: node").count(), 2);
+        assert!(stream.contains("event: finished"));
+        assert!(stream.contains("\"status\":\"succeeded\""));
+
+        let missing = send(
+            addr,
+            &format!(
+                "GET /runs/{}/events HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer {TOKEN}\r\n\r\n",
+                Uuid::new_v4()
+            ),
+        )
+        .await;
+        assert!(missing.starts_with("HTTP/1.1 404"));
+    }
+
+    #[tokio::test]
+    async fn given_missing_token_or_foreign_origin_when_calling_then_request_is_refused() {
+        let addr = start().await;
+        let rpc_without = |headers: &str| {
+            format!(
+                "POST /rpc HTTP/1.1\r\nHost: localhost\r\n{headers}Content-Length: 2\r\n\r\n{{}}"
+            )
+        };
+
+        let anonymous = send(addr, &rpc_without("")).await;
+        assert!(anonymous.starts_with("HTTP/1.1 401"), "{anonymous}");
+        let wrong = send(addr, &rpc_without("Authorization: Bearer guess\r\n")).await;
+        assert!(wrong.starts_with("HTTP/1.1 401"), "{wrong}");
+        let foreign = send(
+            addr,
+            &rpc_without(&format!(
+                "Origin: https://evil.example\r\nAuthorization: Bearer {TOKEN}\r\n"
+            )),
+        )
+        .await;
+        assert!(foreign.starts_with("HTTP/1.1 403"), "{foreign}");
+        assert!(!foreign.contains("Access-Control-Allow-Origin"));
+        let preflight = send(
+            addr,
+            "OPTIONS /rpc HTTP/1.1\r\nOrigin: https://evil.example\r\n\r\n",
+        )
+        .await;
+        assert!(preflight.starts_with("HTTP/1.1 403"), "{preflight}");
+
+        let allowed = send(
+            addr,
+            "OPTIONS /rpc HTTP/1.1\r\nOrigin: http://localhost:8081\r\n\r\n",
+        )
+        .await;
+        assert!(allowed.starts_with("HTTP/1.1 204"), "{allowed}");
+        assert!(allowed.contains("Access-Control-Allow-Origin: http://localhost:8081\r\n"));
+    }
+}
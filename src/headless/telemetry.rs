@@ -0,0 +1,170 @@
+//! OTLP export of tracing spans.
+//!
+//! Workflow runs, scenario steps and headless request handlers record
+//! `tracing` spans: one per run, node, scenario, step and request. With the
+//! `otel` feature, [`init`] installs a subscriber that ships those spans to
+//! an OTLP/HTTP collector such as Jaeger or Tempo, so an execution can be
+//! followed into the backend services it called.
+
+pub const DEFAULT_SERVICE_NAME: &str = "oya";
+const ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+const SERVICE_NAME_ENV: &str = "OTEL_SERVICE_NAME";
+
+/// Where spans are exported and what service they are reported under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtlpConfig {
+    /// Collector base URL, e.g. `http://localhost:4318`.
+    pub endpoint: String,
+    pub service_name: String,
+}
+
+impl OtlpConfig {
+    #[must_use]
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            endpoint: endpoint.to_string(),
+            service_name: DEFAULT_SERVICE_NAME.to_string(),
+        }
+    }
+
+    /// Reads the standard `OTEL_EXPORTER_OTLP_ENDPOINT` and
+    /// `OTEL_SERVICE_NAME` variables. Returns `None` when no endpoint is set.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let endpoint = std::env::var(ENDPOINT_ENV)
+            .ok()
+            .filter(|value| !value.trim().is_empty())?;
+        let mut config = Self::new(&endpoint);
+        if let Some(name) = std::env::var(SERVICE_NAME_ENV)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+        {
+            config.service_name = name;
+        }
+        Some(config)
+    }
+
+    /// The OTLP/HTTP traces URL under the collector base URL.
+    #[must_use]
+    pub fn traces_url(&self) -> String {
+        let base = self.endpoint.trim().trim_end_matches('/');
+        if base.ends_with("/v1/traces") {
+            base.to_string()
+        } else {
+            format!("{base}/v1/traces")
+        }
+    }
+}
+
+/// W3C trace-context headers for the current span, to send with outgoing
+/// requests so backends join the same trace. Empty without the `otel`
+/// feature.
+#[must_use]
+pub fn trace_context_headers() -> Vec<(String, String)> {
+    #[cfg(feature = "otel")]
+    {
+        exporter::trace_context_headers()
+    }
+    #[cfg(not(feature = "otel"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(feature = "otel")]
+pub use exporter::{init, TelemetryError, TelemetryGuard};
+
+#[cfg(feature = "otel")]
+mod exporter {
+    use std::collections::HashMap;
+
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+    use thiserror::Error;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    use super::OtlpConfig;
+
+    #[derive(Debug, Error)]
+    pub enum TelemetryError {
+        #[error("Could not build the OTLP exporter: {0}")]
+        Exporter(String),
+        #[error("A tracing subscriber is already installed")]
+        AlreadyInstalled,
+    }
+
+    /// Flushes pending spans when dropped; keep it alive for the process.
+    pub struct TelemetryGuard {
+        provider: SdkTracerProvider,
+    }
+
+    impl Drop for TelemetryGuard {
+        fn drop(&mut self) {
+            let _ = self.provider.shutdown();
+        }
+    }
+
+    /// Installs a global subscriber exporting spans to `config`.
+    ///
+    /// # Errors
+    /// Returns an error if the exporter cannot be built or a subscriber is
+    /// already installed.
+    pub fn init(config: &OtlpConfig) -> Result<TelemetryGuard, TelemetryError> {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(config.traces_url())
+            .build()
+            .map_err(|error| TelemetryError::Exporter(error.to_string()))?;
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(config.service_name.clone())
+                    .build(),
+            )
+            .build();
+        let tracer = provider.tracer(super::DEFAULT_SERVICE_NAME);
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(tracer))
+            .try_init()
+            .map_err(|_| TelemetryError::AlreadyInstalled)?;
+        Ok(TelemetryGuard { provider })
+    }
+
+    pub(super) fn trace_context_headers() -> Vec<(String, String)> {
+        let context = tracing::Span::current().context();
+        let mut headers = HashMap::new();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&context, &mut headers);
+        });
+        headers.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_collector_base_urls_when_building_traces_url_then_path_is_appended_once() {
+        assert_eq!(
+            OtlpConfig::new("http://localhost:4318").traces_url(),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            OtlpConfig::new("http://tempo:4318/").traces_url(),
+            "http://tempo:4318/v1/traces"
+        );
+        assert_eq!(
+            OtlpConfig::new("http://tempo:4318/v1/traces").traces_url(),
+            "http://tempo:4318/v1/traces"
+        );
+    }
+}
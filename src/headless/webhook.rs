@@ -0,0 +1,353 @@
+//! Local webhook runtime: serve `http-handler` nodes as real endpoints.
+//!
+//! Every `http-handler` node registers its `method` and `path`. A matching
+//! request runs the node's workflow with the request as the handler's
+//! output, and the response body is the output of the node named by the
+//! handler's `response_node` config, or of the last node to run when none
+//! is named. A failed run answers `500` with the node errors.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tokio::net::{TcpListener, TcpStream};
+
+use super::entry::run_with_entry;
+use crate::graph::{NodeId, Workflow};
+use crate::http::{read_request, respond};
+
+/// Config key naming the node whose output becomes the HTTP response.
+pub const RESPONSE_NODE_CONFIG_KEY: &str = "response_node";
+const HTTP_HANDLER: &str = "http-handler";
+const DEFAULT_METHOD: &str = "POST";
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WebhookError {
+    #[error("{method} {path} is registered by more than one http-handler")]
+    DuplicateRoute { method: String, path: String },
+    #[error("No http-handler node has a path")]
+    NoRoutes,
+}
+
+/// An incoming request, handed to the workflow as the handler's output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookRequest {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+    /// JSON bodies are parsed; anything else arrives as a string.
+    pub body: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+#[derive(Debug, Clone)]
+struct Route {
+    method: String,
+    path: String,
+    handler: NodeId,
+    workflow: Arc<Workflow>,
+}
+
+/// Routes requests to the workflows that registered them.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookRuntime {
+    routes: Vec<Route>,
+}
+
+impl WebhookRuntime {
+    /// Registers every `http-handler` with a path across `workflows`.
+    ///
+    /// # Errors
+    /// Returns an error if two handlers claim the same method and path, or
+    /// no handler has a path.
+    pub fn new(workflows: impl IntoIterator<Item = Workflow>) -> Result<Self, WebhookError> {
+        let mut routes: Vec<Route> = Vec::new();
+        for workflow in workflows {
+            let workflow = Arc::new(workflow);
+            for node in workflow
+                .nodes
+                .iter()
+                .filter(|n| n.node_type == HTTP_HANDLER)
+            {
+                let Some(path) = config_str(&node.config, "path") else {
+                    continue;
+                };
+                let method = config_str(&node.config, "method")
+                    .unwrap_or(DEFAULT_METHOD)
+                    .to_ascii_uppercase();
+                let path = normalize_path(path);
+                if routes.iter().any(|r| r.method == method && r.path == path) {
+                    return Err(WebhookError::DuplicateRoute { method, path });
+                }
+                routes.push(Route {
+                    method,
+                    path,
+                    handler: node.id,
+                    workflow: Arc::clone(&workflow),
+                });
+            }
+        }
+        if routes.is_empty() {
+            return Err(WebhookError::NoRoutes);
+        }
+        Ok(Self { routes })
+    }
+
+    /// Registered routes as `(method, path)`.
+    #[must_use]
+    pub fn routes(&self) -> Vec<(&str, &str)> {
+        self.routes
+            .iter()
+            .map(|route| (route.method.as_str(), route.path.as_str()))
+            .collect()
+    }
+
+    /// Runs the workflow registered for the request and builds its response.
+    #[tracing::instrument(
+        name = "webhook.request",
+        skip_all,
+        fields(
+            http.method = %request.method,
+            http.path = %request.path,
+            http.status_code = tracing::field::Empty,
+        )
+    )]
+    pub async fn handle(&self, request: WebhookRequest) -> WebhookResponse {
+        let path = normalize_path(&request.path);
+        let matching: Vec<&Route> = self.routes.iter().filter(|r| r.path == path).collect();
+        let Some(route) = matching.iter().find(|r| r.method == request.method) else {
+            let (status, error) = if matching.is_empty() {
+                (404, "No http-handler registered for this path")
+            } else {
+                (405, "Method not allowed")
+            };
+            tracing::Span::current().record("http.status_code", status);
+            return WebhookResponse {
+                status,
+                body: json!({ "error": error }),
+            };
+        };
+
+        let mut workflow = (*route.workflow).clone();
+        let payload = serde_json::to_value(&request).unwrap_or(Value::Null);
+        run_with_entry(&mut workflow, route.handler, payload).await;
+        let response = response_for(&workflow, route.handler);
+        tracing::Span::current().record("http.status_code", response.status);
+        response
+    }
+}
+
+fn response_for(workflow: &Workflow, handler: NodeId) -> WebhookResponse {
+    let errors: Vec<Value> = workflow
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            let error = node.error.as_ref()?;
+            Some(json!({ "node": node.name, "error": error }))
+        })
+        .collect();
+    if !errors.is_empty() {
+        return WebhookResponse {
+            status: 500,
+            body: json!({ "errors": errors }),
+        };
+    }
+
+    let named = workflow
+        .nodes
+        .iter()
+        .find(|node| node.id == handler)
+        .and_then(|node| config_str(&node.config, RESPONSE_NODE_CONFIG_KEY));
+    let output = match named {
+        Some(name) => workflow
+            .nodes
+            .iter()
+            .find(|node| node.name == name)
+            .and_then(|node| node.last_output.clone()),
+        None => workflow
+            .execution_queue
+            .iter()
+            .rev()
+            .filter_map(|id| workflow.nodes.iter().find(|node| node.id == *id))
+            .find_map(|node| node.last_output.clone()),
+    };
+    WebhookResponse {
+        status: 200,
+        body: output.unwrap_or(Value::Null),
+    }
+}
+
+fn config_str<'a>(config: &'a Value, key: &str) -> Option<&'a str> {
+    config
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim().trim_end_matches('/');
+    if trimmed.starts_with('/') {
+        trimmed.to_string()
+    } else {
+        format!("/{trimmed}")
+    }
+}
+
+/// Serve `runtime` on `listener` until accepting a connection fails.
+///
+/// # Errors
+/// Returns an error if the listener stops accepting connections.
+pub async fn serve_webhooks(listener: TcpListener, runtime: WebhookRuntime) -> std::io::Result<()> {
+    let runtime = Arc::new(runtime);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let runtime = Arc::clone(&runtime);
+        tokio::spawn(async move {
+            let _ = handle_connection(stream, &runtime).await;
+        });
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, runtime: &WebhookRuntime) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+    let body = String::from_utf8_lossy(&request.body);
+    let request = WebhookRequest {
+        query: request.query_pairs().into_iter().collect(),
+        body: if body.trim().is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&body).unwrap_or_else(|_| Value::String(body.into_owned()))
+        },
+        method: request.method,
+        path: request.path,
+        headers: request.headers,
+    };
+
+    let response = runtime.handle(request).await;
+    let json = serde_json::to_string(&response.body).map_err(std::io::Error::other)?;
+    respond(
+        &mut stream,
+        status_line(response.status),
+        &[],
+        "application/json",
+        &json,
+    )
+    .await
+}
+
+const fn status_line(status: u16) -> &'static str {
+    match status {
+        200 => "200 OK",
+        404 => "404 Not Found",
+        405 => "405 Method Not Allowed",
+        _ => "500 Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::WorkflowBuilder;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn request(method: &str, path: &str, body: Value) -> WebhookRequest {
+        WebhookRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: HashMap::new(),
+            headers: HashMap::new(),
+            body,
+        }
+    }
+
+    fn orders_workflow() -> Workflow {
+        WorkflowBuilder::new()
+            .node("entry", HTTP_HANDLER)
+            .node("echo", "run")
+            .connect("entry", "echo")
+            .config(
+                "entry",
+                &json!({ "path": "orders/", "method": "post", "response_node": "echo" }),
+            )
+            .config(
+                "echo",
+                &json!({ "mapping": { "order": "{{ $node[\"entry\"].json.body.id }}" } }),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn given_registered_handler_when_request_arrives_then_response_node_output_is_returned() {
+        let runtime = WebhookRuntime::new([orders_workflow()]).unwrap();
+        assert_eq!(runtime.routes(), vec![("POST", "/orders")]);
+
+        let response = runtime
+            .handle(request("POST", "/orders", json!({ "id": "A-1" })))
+            .await;
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, json!({ "order": "A-1" }));
+        let missing = runtime.handle(request("POST", "/nope", Value::Null)).await;
+        assert_eq!(missing.status, 404);
+        let wrong_method = runtime.handle(request("GET", "/orders", Value::Null)).await;
+        assert_eq!(wrong_method.status, 405);
+    }
+
+    #[test]
+    fn given_two_handlers_on_one_route_when_registering_then_runtime_is_rejected() {
+        assert!(matches!(
+            WebhookRuntime::new([orders_workflow(), orders_workflow()]),
+            Err(WebhookError::DuplicateRoute { .. })
+        ));
+        assert_eq!(
+            WebhookRuntime::new([Workflow::new()]).err(),
+            Some(WebhookError::NoRoutes)
+        );
+    }
+
+    #[tokio::test]
+    async fn given_encoded_query_when_served_then_workflow_sees_decoded_values() {
+        let workflow = WorkflowBuilder::new()
+            .node("entry", HTTP_HANDLER)
+            .node("echo", "run")
+            .connect("entry", "echo")
+            .config("entry", &json!({ "path": "greet", "method": "GET" }))
+            .config(
+                "echo",
+                &json!({ "mapping": { "name": "{{ $node[\"entry\"].json.query.name }}" } }),
+            )
+            .build();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve_webhooks(
+            listener,
+            WebhookRuntime::new([workflow]).unwrap(),
+        ));
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /greet?name=a%20b HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        let (_, body) = response.split_once("\r\n\r\n").unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(body).unwrap(),
+            json!({ "name": "a b" })
+        );
+    }
+}
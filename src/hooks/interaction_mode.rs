@@ -2,6 +2,7 @@
 #![deny(clippy::expect_used)]
 #![deny(clippy::panic)]
 
+use crate::graph::frames::FrameId;
 use crate::graph::NodeId;
 
 // ---------------------------------------------------------------------------
@@ -24,6 +25,12 @@ pub enum InteractionMode {
         start: CanvasPoint,
         current: CanvasPoint,
     },
+    MovingFrame {
+        frame_id: FrameId,
+    },
+    ResizingFrame {
+        frame_id: FrameId,
+    },
 }
 
 impl InteractionMode {
@@ -51,6 +58,12 @@ impl InteractionMode {
         matches!(self, Self::Panning)
     }
 
+    /// Returns `true` when a frame is being moved or resized.
+    #[must_use]
+    pub fn is_editing_frame(&self) -> bool {
+        matches!(self, Self::MovingFrame { .. } | Self::ResizingFrame { .. })
+    }
+
     /// Returns `true` when the mode is `Idle`.
     #[must_use]
     pub fn is_idle(&self) -> bool {
@@ -273,7 +286,8 @@ pub fn update_marquee_mode(mode: &InteractionMode, pos: (f32, f32)) -> Interacti
 #[must_use]
 pub fn cursor_class_for(mode: &InteractionMode, cursor_tool: CursorTool) -> &'static str {
     match mode {
-        InteractionMode::Panning => "cursor-grabbing",
+        InteractionMode::Panning | InteractionMode::MovingFrame { .. } => "cursor-grabbing",
+        InteractionMode::ResizingFrame { .. } => "cursor-nwse-resize",
         InteractionMode::Idle if cursor_tool == CursorTool::SpaceHand => "cursor-grab",
         _ => "cursor-default",
     }
@@ -427,6 +441,22 @@ mod tests {
         assert_eq!(class, "cursor-grabbing");
     }
 
+    #[test]
+    fn given_frame_edit_modes_when_getting_cursor_class_then_grab_and_resize_cursors() {
+        let frame_id = crate::graph::frames::FrameId::new();
+        let moving = InteractionMode::MovingFrame { frame_id };
+        let resizing = InteractionMode::ResizingFrame { frame_id };
+        assert!(moving.is_editing_frame() && resizing.is_editing_frame());
+        assert_eq!(
+            cursor_class_for(&moving, CursorTool::Select),
+            "cursor-grabbing"
+        );
+        assert_eq!(
+            cursor_class_for(&resizing, CursorTool::Select),
+            "cursor-nwse-resize"
+        );
+    }
+
     #[test]
     fn given_select_tool_and_idle_when_getting_cursor_class_then_cursor_default() {
         let class = cursor_class_for(&InteractionMode::Idle, CursorTool::Select);
@@ -3,22 +3,27 @@
 #![deny(clippy::pedantic)]
 
 pub mod interaction_mode;
+pub mod use_analysis;
 pub mod use_canvas_events;
 pub mod use_canvas_interaction;
 pub mod use_canvas_mouse;
+pub mod use_collab;
 pub mod use_frozen_mode;
 pub mod use_restate_sync;
 pub mod use_selection;
 pub mod use_sidebar;
 pub mod use_toast;
 pub mod use_ui_panels;
+pub mod use_workflow_persistence;
 pub mod use_workflow_state;
 
 #[cfg(target_arch = "wasm32")]
 pub use use_toast::{provide_toast_context, use_toast, ToastStore};
+pub use use_analysis::{provide_analysis_context, use_analysis, AnalysisHandle};
 pub use use_canvas_interaction::{
     provide_canvas_interaction_context, use_canvas_interaction, InteractionMode,
 };
+pub use use_collab::{provide_collab_context, use_collab, CollabHandle, CollabStatus};
 pub use use_restate_sync::{
     build_restate_config_from_url, poll_sleep_ms, provide_restate_sync_context, use_restate_sync,
     RestateSyncHandle,
@@ -26,4 +31,5 @@ pub use use_restate_sync::{
 pub use use_selection::{provide_selection_context, use_selection};
 pub use use_sidebar::{provide_sidebar_context, use_sidebar};
 pub use use_ui_panels::{provide_ui_panels_context, use_ui_panels};
+pub use use_workflow_persistence::use_workflow_persistence;
 pub use use_workflow_state::{provide_workflow_state_context, use_workflow_state};
@@ -0,0 +1,141 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::nursery)]
+#![forbid(unsafe_code)]
+
+//! Layout, compilation and extension suggestions for large graphs, run in
+//! the analysis worker so the canvas keeps its frame rate.
+//!
+//! Graphs under [`OFFLOAD_NODE_THRESHOLD`] nodes are still analysed inline:
+//! their results are needed immediately and cost less than a round trip.
+
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+
+use super::use_workflow_state::WorkflowState;
+use crate::analysis_worker::bridge::AnalysisWorker;
+use crate::analysis_worker::{AnalysisJob, AnalysisUpdate, JobTracker, OFFLOAD_NODE_THRESHOLD};
+use crate::flow_extender::{suggest_extensions, FlowExtension};
+use crate::graph::compile::{compile_workflow, CompileReport, SeverityGate};
+use crate::graph::Workflow;
+
+/// How long edits must pause before a large graph is re-analysed.
+const ANALYZE_DEBOUNCE_MS: u32 = 150;
+
+#[derive(Clone, Copy)]
+pub struct AnalysisHandle {
+    state: WorkflowState,
+    worker: CopyValue<Rc<AnalysisWorker>>,
+    tracker: Signal<JobTracker>,
+    compile_report: Signal<Option<CompileReport>>,
+    suggestions: Signal<Option<Vec<FlowExtension>>>,
+    generation: CopyValue<u64>,
+}
+
+impl AnalysisHandle {
+    /// Auto-layout; applied when the worker answers on large graphs.
+    pub fn layout(self) {
+        if is_small(&self.state.workflow().read()) {
+            self.state.apply_layout();
+            return;
+        }
+        let workflow = self.state.workflow().read().clone();
+        self.post(AnalysisJob::Layout { workflow });
+    }
+
+    /// Queue compilation and suggestions for the current graph once edits
+    /// pause. Does nothing for small graphs, which are analysed inline.
+    pub fn schedule(mut self, gate: SeverityGate) {
+        if is_small(&self.state.workflow().peek()) {
+            return;
+        }
+        *self.generation.write() += 1;
+        let generation = *self.generation.peek();
+        spawn(async move {
+            gloo_timers::future::TimeoutFuture::new(ANALYZE_DEBOUNCE_MS).await;
+            if *self.generation.peek() != generation {
+                return;
+            }
+            let workflow = self.state.workflow().peek().clone();
+            self.post(AnalysisJob::Analyze { workflow, gate });
+        });
+    }
+
+    /// The compile report for `workflow`: computed inline for small graphs,
+    /// otherwise the worker's latest, or inline until it first answers.
+    #[must_use]
+    pub fn compile_report(&self, workflow: &Workflow, gate: SeverityGate) -> CompileReport {
+        if is_small(workflow) {
+            return compile_workflow(workflow, gate);
+        }
+        self.compile_report.read().as_ref().map_or_else(
+            || compile_workflow(workflow, gate),
+            // Diagnostics do not depend on the gate, only what they block.
+            |report| CompileReport {
+                gate,
+                diagnostics: report.diagnostics.clone(),
+            },
+        )
+    }
+
+    /// Extension suggestions for `workflow`, sourced like
+    /// [`Self::compile_report`].
+    #[must_use]
+    pub fn suggestions(&self, workflow: &Workflow) -> Vec<FlowExtension> {
+        if is_small(workflow) {
+            return suggest_extensions(workflow);
+        }
+        self.suggestions
+            .read()
+            .clone()
+            .unwrap_or_else(|| suggest_extensions(workflow))
+    }
+
+    fn post(mut self, job: AnalysisJob) {
+        let request = self.tracker.write().start(job);
+        self.worker.read().post(request);
+    }
+}
+
+fn is_small(workflow: &Workflow) -> bool {
+    workflow.nodes.len() < OFFLOAD_NODE_THRESHOLD
+}
+
+pub fn provide_analysis_context(state: WorkflowState) -> AnalysisHandle {
+    let tracker = use_signal(JobTracker::default);
+    let compile_report = use_signal(|| None);
+    let suggestions = use_signal(|| None);
+    let worker = use_hook(|| {
+        CopyValue::new(Rc::new(AnalysisWorker::spawn(move |message| {
+            let (mut tracker, mut compile_report, mut suggestions) =
+                (tracker, compile_report, suggestions);
+            if !tracker.write().accept(&message) {
+                return;
+            }
+            match message.update {
+                AnalysisUpdate::Positions(positions) => state.apply_positions(&positions),
+                AnalysisUpdate::Compiled(report) => compile_report.set(Some(report)),
+                AnalysisUpdate::Suggestions(found) => suggestions.set(Some(found)),
+                AnalysisUpdate::Done | AnalysisUpdate::Failed(_) => {}
+            }
+        })))
+    });
+    let handle = AnalysisHandle {
+        state,
+        worker,
+        tracker,
+        compile_report,
+        suggestions,
+        generation: use_hook(|| CopyValue::new(0)),
+    };
+    provide_context(handle);
+    handle
+}
+
+#[must_use]
+pub fn use_analysis() -> AnalysisHandle {
+    use_context::<AnalysisHandle>()
+}
@@ -2,6 +2,7 @@
 #![deny(clippy::expect_used)]
 #![deny(clippy::panic)]
 
+use crate::graph::frames::FrameId;
 use crate::graph::NodeId;
 use crate::hooks::interaction_mode::{
     cursor_class_for, drag_mode_from_selection, update_marquee_mode,
@@ -83,6 +84,14 @@ impl CanvasInteraction {
         });
     }
 
+    pub fn start_frame_move(mut self, frame_id: FrameId) {
+        self.mode.set(InteractionMode::MovingFrame { frame_id });
+    }
+
+    pub fn start_frame_resize(mut self, frame_id: FrameId) {
+        self.mode.set(InteractionMode::ResizingFrame { frame_id });
+    }
+
     pub fn update_marquee(mut self, pos: (f32, f32)) {
         let mode = self.mode.read().clone();
         self.mode.set(update_marquee_mode(&mode, pos));
@@ -164,6 +173,11 @@ impl CanvasInteraction {
         matches!(*self.mode.read(), InteractionMode::Panning)
     }
 
+    #[must_use]
+    pub fn is_editing_frame(&self) -> bool {
+        self.mode.read().is_editing_frame()
+    }
+
     #[must_use]
     pub fn is_idle(&self) -> bool {
         matches!(*self.mode.read(), InteractionMode::Idle)
@@ -189,6 +203,22 @@ impl CanvasInteraction {
         }
     }
 
+    #[must_use]
+    pub fn moving_frame(&self) -> Option<FrameId> {
+        match &*self.mode.read() {
+            InteractionMode::MovingFrame { frame_id } => Some(*frame_id),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn resizing_frame(&self) -> Option<FrameId> {
+        match &*self.mode.read() {
+            InteractionMode::ResizingFrame { frame_id } => Some(*frame_id),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub fn drag_anchor(&self) -> Option<(f32, f32)> {
         self.drag_anchor.read().as_point()
@@ -12,9 +12,10 @@ use crate::hooks::use_selection::SelectionState;
 use crate::hooks::use_sidebar::SidebarState;
 use crate::hooks::use_ui_panels::UiPanels;
 use crate::hooks::use_workflow_state::WorkflowState;
+use crate::ui::capabilities::Capability;
 use crate::ui::constants::{
-    EDGE_AUTO_PAN_MAX, EDGE_AUTO_PAN_ZONE, FALLBACK_CANVAS_HEIGHT, FALLBACK_CANVAS_WIDTH,
-    NODE_CENTER_X_OFFSET, NODE_HANDLE_Y_OFFSET,
+    AUTO_CONNECT_RADIUS_PX, EDGE_AUTO_PAN_MAX, EDGE_AUTO_PAN_ZONE, FALLBACK_CANVAS_HEIGHT,
+    FALLBACK_CANVAS_WIDTH, NODE_CENTER_X_OFFSET, NODE_HANDLE_Y_OFFSET,
 };
 use crate::ui::edges::Position as FlowPosition;
 use dioxus::html::input_data::MouseButton;
@@ -79,14 +80,18 @@ pub fn handle_canvas_wheel_event(
 /// Handle canvas `onmouseleave`.
 ///
 /// Cancels any ongoing interaction unless actively dragging, panning,
-/// marquee-selecting, or connecting -- in those cases we want the
+/// marquee-selecting, connecting, or editing a frame -- in those cases we want the
 /// interaction to continue even if the cursor leaves the canvas element.
 pub fn handle_canvas_mouseleave_event(
     canvas: CanvasInteraction,
     sidebar: SidebarState,
     selection: SelectionState,
 ) {
-    if canvas.is_dragging() || canvas.is_panning() || canvas.is_marquee() || canvas.is_connecting()
+    if canvas.is_dragging()
+        || canvas.is_panning()
+        || canvas.is_marquee()
+        || canvas.is_connecting()
+        || canvas.is_editing_frame()
     {
         return;
     }
@@ -167,6 +172,7 @@ pub fn handle_canvas_mousedown_event(
 /// - **Connecting**: Snaps to handles or shows temp edge
 /// - **Marquee**: Updates selection rectangle
 /// - **Panning**: Pans the viewport
+/// - **Frame move/resize**: Moves a frame with its nodes, or resizes it
 pub fn handle_canvas_mousemove_event(
     evt: &MouseEvent,
     canvas: CanvasInteraction,
@@ -208,7 +214,10 @@ pub fn handle_canvas_mousemove_event(
             let moved = (mx - ax).hypot(my - ay);
             if moved >= DRAG_THRESHOLD_PX {
                 if let Some(node_ids) = selection.take_pending_drag() {
-                    if let Some(primary_id) = node_ids.first().copied() {
+                    let primary_id = node_ids.first().copied();
+                    if let Some(primary_id) =
+                        primary_id.filter(|_| workflow.can(Capability::EditWorkflow))
+                    {
                         canvas.start_drag(primary_id, node_ids);
                     }
                 }
@@ -257,12 +266,15 @@ pub fn handle_canvas_mousemove_event(
         let canvas_y = (my - current_vp.y) / zoom;
 
         if let Some((source_id, source_kind)) = canvas.connecting_from() {
-            let node_list = workflow.nodes().read().clone();
-            let snapped =
-                crate::ui::editor_interactions::snap_handle(&node_list, mx, my, &current_vp)
-                    .filter(|(node_id, handle_kind, _)| {
-                        *node_id != source_id && *handle_kind != source_kind
-                    });
+            let snapped = crate::ui::editor_interactions::snap_handle(
+                &workflow.nodes().read(),
+                mx,
+                my,
+                &current_vp,
+            )
+            .filter(|(node_id, handle_kind, _)| {
+                *node_id != source_id && *handle_kind != source_kind
+            });
 
             if let Some((node_id, handle_kind, snapped_pos)) = snapped {
                 canvas.set_hovered_handle(Some((node_id, handle_kind)));
@@ -290,19 +302,16 @@ pub fn handle_canvas_mousemove_event(
             );
             let end_canvas = ((mx - current_vp.x) / zoom, (my - current_vp.y) / zoom);
             let rect = crate::ui::editor_interactions::normalize_rect(start_canvas, end_canvas);
-            let selected = workflow
-                .nodes()
-                .read()
-                .iter()
-                .filter(|node| {
-                    crate::ui::editor_interactions::node_intersects_rect(node.x, node.y, rect)
-                })
-                .map(|node| node.id)
-                .collect::<Vec<_>>();
+            let selected =
+                crate::ui::editor_interactions::nodes_in_rect(&workflow.nodes().read(), rect);
             selection.set_multiple(selected);
         }
     } else if canvas.is_panning() {
         workflow.pan(dx, dy);
+    } else if let Some(frame_id) = canvas.moving_frame() {
+        workflow.move_frame(frame_id, dx / zoom, dy / zoom);
+    } else if let Some(frame_id) = canvas.resizing_frame() {
+        workflow.resize_frame(frame_id, dx / zoom, dy / zoom);
     }
 }
 
@@ -315,7 +324,8 @@ pub fn handle_canvas_mousemove_event(
 /// Finalizes the current interaction:
 /// - **Connecting**: Creates a connection if snapped to a valid handle
 /// - **Marquee click**: Clears selection if it was a tiny click
-/// - **Sidebar drop**: Places a new node at cursor position
+/// - **Sidebar drop**: Places a new node at cursor position, splicing it into
+///   a nearby edge or attaching it to a nearby unconnected output
 /// - Always ends interaction and clears pending states
 pub fn handle_canvas_mouseup_event(
     evt: &MouseEvent,
@@ -365,9 +375,22 @@ pub fn handle_canvas_mouseup_event(
             if mx.is_finite() && my.is_finite() {
                 let current_vp = workflow.viewport().read().clone();
                 if crate::ui::interaction_guards::is_valid_zoom(current_vp.zoom) {
-                    let canvas_x = (mx - current_vp.x) / current_vp.zoom - NODE_CENTER_X_OFFSET;
-                    let canvas_y = (my - current_vp.y) / current_vp.zoom - NODE_HANDLE_Y_OFFSET;
-                    let _ = workflow.add_node(node_type.as_str(), canvas_x, canvas_y);
+                    let point = (
+                        (mx - current_vp.x) / current_vp.zoom,
+                        (my - current_vp.y) / current_vp.zoom,
+                    );
+                    let auto_connect = crate::ui::editor_interactions::auto_connect_target(
+                        &workflow.nodes().read(),
+                        &workflow.connections().read(),
+                        point,
+                        AUTO_CONNECT_RADIUS_PX / current_vp.zoom,
+                    );
+                    let _ = workflow.drop_node(
+                        node_type.as_str(),
+                        point.0 - NODE_CENTER_X_OFFSET,
+                        point.1 - NODE_HANDLE_Y_OFFSET,
+                        auto_connect,
+                    );
                 }
             }
         }
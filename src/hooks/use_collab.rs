@@ -0,0 +1,279 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! Live collaboration through the relay served by `oya collab-relay`.
+//!
+//! Opening the editor with `?collab=ws://host:8093/<room>&name=<you>` joins
+//! the room. Local edits are diffed into merge-safe operations by
+//! [`WorkflowState::collab_ops`] as they happen, and other peers' operations
+//! are merged with [`WorkflowState::merge_collab_op`], so no peer ever
+//! overwrites the whole workflow. Secrets in node configs are redacted
+//! before anything leaves the editor. Cursor and selection travel as
+//! presence.
+
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{MessageEvent, WebSocket};
+
+use super::use_canvas_interaction::CanvasInteraction;
+use super::use_selection::SelectionState;
+use super::use_workflow_state::WorkflowState;
+use crate::graph::collab::{CollabMessage, Presence, StampedOp};
+use crate::graph::NodeId;
+use crate::ui::deep_link::linked_collab_room;
+
+/// Least time between two cursor updates sent to the relay.
+const CURSOR_THROTTLE_MS: f64 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollabStatus {
+    Offline,
+    Connecting,
+    Live,
+    /// The relay closed the connection or could not be reached.
+    Disconnected,
+}
+
+struct Connection {
+    socket: WebSocket,
+    _on_open: Closure<dyn FnMut(web_sys::Event)>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // The handlers are freed with this connection; the close event must
+        // not reach them.
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        let _ = self.socket.close();
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct CollabHandle {
+    state: WorkflowState,
+    connection: CopyValue<Option<Rc<Connection>>>,
+    status: Signal<CollabStatus>,
+    /// This editor as the relay knows it, once welcomed.
+    me: Signal<Option<Presence>>,
+    peers: Signal<Vec<Presence>>,
+    last_cursor_at: CopyValue<f64>,
+}
+
+impl CollabHandle {
+    #[must_use]
+    pub fn status(&self) -> ReadSignal<CollabStatus> {
+        self.status.into()
+    }
+
+    /// Everyone else in the room.
+    #[must_use]
+    pub fn peers(&self) -> ReadSignal<Vec<Presence>> {
+        self.peers.into()
+    }
+
+    /// Join the room at `url`, a relay WebSocket URL, as `display_name`.
+    /// The current workflow seeds the room if nobody has shaped it yet.
+    pub fn connect(mut self, url: &str, display_name: String) {
+        self.disconnect();
+        let Ok(socket) = WebSocket::new(url) else {
+            self.status.set(CollabStatus::Disconnected);
+            return;
+        };
+        let on_open = {
+            let display_name = display_name.clone();
+            Closure::<dyn FnMut(web_sys::Event)>::new(move |_event: web_sys::Event| {
+                self.send(&CollabMessage::Join {
+                    display_name: display_name.clone(),
+                    seed: Some(Box::new(self.state.workflow().peek().redacted())),
+                });
+            })
+        };
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(message) = event
+                .data()
+                .as_string()
+                .and_then(|json| CollabMessage::from_json(&json).ok())
+            else {
+                return;
+            };
+            self.receive(message, &display_name);
+        });
+        let on_close = Closure::<dyn FnMut(web_sys::Event)>::new(move |_event: web_sys::Event| {
+            self.leave_room(CollabStatus::Disconnected);
+        });
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+        self.connection.set(Some(Rc::new(Connection {
+            socket,
+            _on_open: on_open,
+            _on_message: on_message,
+            _on_close: on_close,
+        })));
+        self.status.set(CollabStatus::Connecting);
+    }
+
+    pub fn disconnect(mut self) {
+        self.connection.set(None);
+        self.leave_room(CollabStatus::Offline);
+    }
+
+    /// Send the operations for local edits made since the last flush.
+    pub fn flush(self) {
+        if *self.status.peek() == CollabStatus::Live {
+            self.send_ops(self.state.collab_ops());
+        }
+    }
+
+    /// Share the pointer position, in canvas coordinates, at most every
+    /// [`CURSOR_THROTTLE_MS`].
+    pub fn share_cursor(mut self, cursor: Option<(f32, f32)>) {
+        let now = js_sys::Date::now();
+        if now - *self.last_cursor_at.peek() < CURSOR_THROTTLE_MS {
+            return;
+        }
+        self.last_cursor_at.set(now);
+        self.share(|me| me.cursor = cursor);
+    }
+
+    pub fn share_selection(self, selection: Vec<NodeId>) {
+        self.share(|me| me.selection = selection);
+    }
+
+    fn share(mut self, change: impl FnOnce(&mut Presence)) {
+        if *self.status.peek() != CollabStatus::Live {
+            return;
+        }
+        let presence = {
+            let mut me = self.me.write();
+            let Some(me) = me.as_mut() else {
+                return;
+            };
+            let before = me.clone();
+            change(me);
+            if *me == before {
+                return;
+            }
+            me.clone()
+        };
+        self.send(&CollabMessage::Presence { presence });
+    }
+
+    fn receive(mut self, message: CollabMessage, display_name: &str) {
+        match message {
+            CollabMessage::Welcome {
+                peer,
+                base,
+                ops,
+                peers,
+                color,
+            } => {
+                self.state.start_collab(peer.clone(), *base, &ops);
+                let mut me = Presence::new(peer, display_name);
+                me.color = color;
+                self.me.set(Some(me));
+                self.peers.set(peers);
+                self.status.set(CollabStatus::Live);
+            }
+            CollabMessage::Op { op } => {
+                let pending = self.state.merge_collab_op(&op);
+                self.send_ops(pending);
+            }
+            CollabMessage::Presence { presence } => {
+                let mut peers = self.peers.write();
+                match peers.iter_mut().find(|peer| peer.peer == presence.peer) {
+                    Some(known) => *known = presence,
+                    None => peers.push(presence),
+                }
+            }
+            CollabMessage::Leave { peer } => {
+                self.peers.write().retain(|presence| presence.peer != peer);
+            }
+            CollabMessage::Join { .. } => {}
+        }
+    }
+
+    fn leave_room(mut self, status: CollabStatus) {
+        self.state.stop_collab();
+        self.me.set(None);
+        self.peers.write().clear();
+        self.status.set(status);
+    }
+
+    /// Send operations with their secrets redacted; each peer keeps its own.
+    fn send_ops(self, ops: Vec<StampedOp>) {
+        for op in ops {
+            self.send(&CollabMessage::Op { op: op.redacted() });
+        }
+    }
+
+    fn send(self, message: &CollabMessage) {
+        let Ok(json) = message.to_json() else {
+            return;
+        };
+        if let Some(connection) = self.connection.peek().as_ref() {
+            let _ = connection.socket.send_with_str(&json);
+        }
+    }
+}
+
+pub fn provide_collab_context(
+    state: WorkflowState,
+    selection: SelectionState,
+    canvas: CanvasInteraction,
+) -> CollabHandle {
+    let handle = CollabHandle {
+        state,
+        connection: use_hook(|| CopyValue::new(None)),
+        status: use_signal(|| CollabStatus::Offline),
+        me: use_signal(|| None),
+        peers: use_signal(Vec::new),
+        last_cursor_at: use_hook(|| CopyValue::new(0.0)),
+    };
+
+    use_hook(move || {
+        let room = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.url().ok())
+            .and_then(|url| linked_collab_room(&url));
+        if let Some((url, display_name)) = room {
+            handle.connect(&url, display_name);
+        }
+    });
+    use_effect(move || {
+        let _ = state.workflow().read();
+        handle.flush();
+    });
+    use_effect(move || {
+        let selected = selection.selected_ids().read().clone();
+        handle.share_selection(selected);
+    });
+    use_effect(move || {
+        let mouse = *canvas.mouse_pos().read();
+        let viewport = state.viewport().read().clone();
+        if viewport.zoom > 0.0 {
+            handle.share_cursor(Some((
+                (mouse.x - viewport.x) / viewport.zoom,
+                (mouse.y - viewport.y) / viewport.zoom,
+            )));
+        }
+    });
+
+    provide_context(handle);
+    handle
+}
+
+#[must_use]
+pub fn use_collab() -> CollabHandle {
+    use_context::<CollabHandle>()
+}
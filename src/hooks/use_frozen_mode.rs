@@ -125,6 +125,7 @@ mod tests {
             results: HashMap::new(),
             success: true,
             restate_invocation_id: None,
+            durations: HashMap::new(),
         }
     }
 
@@ -0,0 +1,187 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::nursery)]
+#![forbid(unsafe_code)]
+
+//! Saves the workflow to local storage once edits pause, rather than on
+//! every change, serializing while the browser is idle. Leaving or hiding
+//! the page writes pending edits at once, since the tab may not come back.
+//! See [`crate::ui::persistence`] for the scheduling and delta encoding.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Document, Storage, Window};
+
+use crate::graph::Workflow;
+use crate::ui::persistence::{
+    DeltaEncoder, PersistScheduler, PersistWrite, DELTA_STORAGE_KEY, SNAPSHOT_STORAGE_KEY,
+};
+
+/// How often pending edits are checked for being due.
+const POLL_INTERVAL_MS: u32 = 100;
+/// Wait before saving where the browser has no `requestIdleCallback`.
+const IDLE_FALLBACK_MS: i32 = 50;
+
+/// Persists `workflow` in the background and returns whether edits are
+/// still waiting to be written.
+pub fn use_workflow_persistence(workflow: ReadSignal<Workflow>) -> Memo<bool> {
+    let scheduler = use_signal(PersistScheduler::default);
+    // The workflow was just loaded from storage; only later changes are
+    // unsaved.
+    let loaded = use_hook(|| Rc::new(Cell::new(false)));
+    let encoder = use_hook(|| {
+        let encoder = storage()
+            .and_then(|storage| storage.get_item(SNAPSHOT_STORAGE_KEY).ok().flatten())
+            .map(|snapshot| DeltaEncoder::resume(&snapshot))
+            .unwrap_or_default();
+        Rc::new(RefCell::new(encoder))
+    });
+
+    use_effect(move || {
+        workflow.read();
+        if loaded.replace(true) {
+            let mut scheduler = scheduler;
+            scheduler.write().mark_changed(js_sys::Date::now());
+        }
+    });
+
+    use_hook({
+        let encoder = Rc::clone(&encoder);
+        move || {
+            Rc::new(FlushOnHide::register(move || {
+                if scheduler.peek().is_dirty() {
+                    save(&encoder, workflow, scheduler);
+                }
+            }))
+        }
+    });
+
+    use_future(move || {
+        let encoder = Rc::clone(&encoder);
+        async move {
+            loop {
+                gloo_timers::future::TimeoutFuture::new(POLL_INTERVAL_MS).await;
+                if !scheduler.peek().is_due(js_sys::Date::now()) {
+                    continue;
+                }
+                idle().await;
+                // A page-hide flush may have saved in the meantime.
+                if scheduler.peek().is_dirty() {
+                    save(&encoder, workflow, scheduler);
+                }
+            }
+        }
+    });
+
+    use_memo(move || scheduler.read().is_dirty())
+}
+
+fn save(
+    encoder: &RefCell<DeltaEncoder>,
+    workflow: ReadSignal<Workflow>,
+    mut scheduler: Signal<PersistScheduler>,
+) {
+    let mut encoder = encoder.borrow_mut();
+    let redacted = workflow.peek().redacted();
+    if encoder.encode(redacted).is_ok_and(|write| store(&write)) {
+        scheduler.write().mark_saved();
+    } else {
+        // The stored base may not match the encoder's any more; start over
+        // from a full snapshot.
+        *encoder = DeltaEncoder::default();
+        scheduler.write().retry_later(js_sys::Date::now());
+    }
+}
+
+/// Resolves once the browser is idle, or after [`IDLE_FALLBACK_MS`] where
+/// `requestIdleCallback` is unavailable.
+async fn idle() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        let has_idle_callback =
+            js_sys::Reflect::has(&window, &JsValue::from_str("requestIdleCallback"))
+                .unwrap_or(false);
+        let scheduled = if has_idle_callback {
+            window.request_idle_callback(&resolve).map(drop)
+        } else {
+            window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, IDLE_FALLBACK_MS)
+                .map(drop)
+        };
+        if scheduled.is_err() {
+            let _ = resolve.call0(&JsValue::UNDEFINED);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Runs a flush on `pagehide`, and on `visibilitychange` when the page is
+/// hidden, until dropped.
+struct FlushOnHide {
+    window: Window,
+    document: Document,
+    callback: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl FlushOnHide {
+    fn register(mut flush: impl FnMut() + 'static) -> Option<Self> {
+        let window = web_sys::window()?;
+        let document = window.document()?;
+        let page = document.clone();
+        let callback = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            if event.type_() == "pagehide" || page.hidden() {
+                flush();
+            }
+        });
+        let listener = Self {
+            window,
+            document,
+            callback,
+        };
+        let callback = listener.callback.as_ref().unchecked_ref();
+        let _ = listener
+            .window
+            .add_event_listener_with_callback("pagehide", callback);
+        let _ = listener
+            .document
+            .add_event_listener_with_callback("visibilitychange", callback);
+        Some(listener)
+    }
+}
+
+impl Drop for FlushOnHide {
+    fn drop(&mut self) {
+        let callback = self.callback.as_ref().unchecked_ref();
+        let _ = self
+            .window
+            .remove_event_listener_with_callback("pagehide", callback);
+        let _ = self
+            .document
+            .remove_event_listener_with_callback("visibilitychange", callback);
+    }
+}
+
+fn storage() -> Option<Storage> {
+    web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+}
+
+fn store(write: &PersistWrite) -> bool {
+    let Some(storage) = storage() else {
+        return false;
+    };
+    match write {
+        PersistWrite::Snapshot(json) => {
+            storage.set_item(SNAPSHOT_STORAGE_KEY, json).is_ok()
+                && storage.remove_item(DELTA_STORAGE_KEY).is_ok()
+        }
+        PersistWrite::Delta(json) => storage.set_item(DELTA_STORAGE_KEY, json).is_ok(),
+    }
+}
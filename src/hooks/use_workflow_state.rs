@@ -2,14 +2,26 @@
 #![deny(clippy::expect_used)]
 #![deny(clippy::panic)]
 
+use crate::analysis_worker::NodePosition;
 use crate::errors::{WorkflowError, WorkflowResult};
+use crate::graph::behavior_coverage::{link_behavior, node_behavior_refs, unlink_behavior};
+use crate::graph::collab::{CollabDocument, PeerId, StampedOp};
+use crate::graph::events::{self, WorkflowEvent};
+use crate::graph::frames::{Frame, FrameId};
 use crate::graph::{
-    Connection, ConnectionResult, ConnectivityConnectionError, Node, NodeId, PortName, Viewport,
-    Workflow,
+    Connection, ConnectionResult, ConnectivityConnectionError, Node, NodeId, NodeIndex, PortName,
+    Viewport, Workflow,
 };
-use crate::ui::constants::{NODE_CENTER_X_OFFSET, NODE_HANDLE_Y_OFFSET};
+use crate::ui::capabilities::{Capabilities, Capability};
+use crate::ui::constants::{
+    AUTO_CONNECT_GAP, FRAME_DEFAULT_HEIGHT, FRAME_DEFAULT_WIDTH, NODE_CENTER_X_OFFSET,
+    NODE_HANDLE_Y_OFFSET, NODE_WIDTH,
+};
+use crate::ui::editor_interactions::AutoConnect;
 use dioxus::prelude::*;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 
 fn push_undo_snapshot(undo_stack: &mut Vec<Workflow>, snapshot: Workflow, cap: usize) {
     undo_stack.push(snapshot);
@@ -87,6 +99,7 @@ fn merge_run_result(mut current: Workflow, completed: Workflow) -> Workflow {
         current_step,
         history,
         execution_records,
+        node_cache,
         ..
     } = completed;
 
@@ -111,6 +124,7 @@ fn merge_run_result(mut current: Workflow, completed: Workflow) -> Workflow {
     current.current_step = current_step;
     current.history = history;
     current.execution_records = execution_records;
+    current.node_cache = node_cache;
     current
 }
 
@@ -174,22 +188,44 @@ pub struct WorkflowState {
     undo_stack: Signal<Vec<Workflow>>,
     redo_stack: Signal<Vec<Workflow>>,
     nodes: Memo<Vec<Node>>,
-    nodes_by_id: Memo<HashMap<NodeId, Node>>,
+    node_ids: Memo<Vec<NodeId>>,
+    nodes_by_id: Memo<NodeIndex>,
     connections: Memo<Vec<Connection>>,
+    frames: Memo<Vec<Frame>>,
     viewport: Memo<Viewport>,
+    snap_grid: Signal<Option<f32>>,
+    capabilities: Signal<Capabilities>,
+    /// Shared replica while collaborating; local edits are diffed against
+    /// it into operations rather than sent as whole workflows.
+    collab: Signal<Option<CollabDocument>>,
 }
 
-async fn run_workflow_detached(mut workflow: Workflow, ingress_url: String) -> Workflow {
+async fn run_workflow_detached(
+    mut workflow: Workflow,
+    ingress_url: String,
+    on_progress: impl FnMut(&Workflow),
+) -> Workflow {
     workflow.restate_ingress_url = ingress_url;
-    workflow.run().await;
+    workflow.run_with_progress(on_progress).await;
     workflow
 }
 
 impl WorkflowState {
-    /// Access to workflow data signal
+    /// Read-only access to the workflow; edits go through the methods
+    /// below, which check the user's capabilities first
     #[must_use]
-    pub fn workflow(&self) -> Signal<Workflow> {
-        self.workflow
+    pub fn workflow(&self) -> ReadSignal<Workflow> {
+        self.workflow.into()
+    }
+
+    /// Apply an edit the dedicated methods do not cover, such as a config
+    /// field change. Records no undo point. Returns `None` without calling
+    /// `edit` if the user may not edit the workflow.
+    pub fn update<R>(mut self, edit: impl FnOnce(&mut Workflow) -> R) -> Option<R> {
+        if !self.can(Capability::EditWorkflow) {
+            return None;
+        }
+        Some(edit(&mut self.workflow.write()))
     }
 
     /// Access to workflow name signal
@@ -213,9 +249,16 @@ impl WorkflowState {
         self.nodes.into()
     }
 
-    /// Read-only access to nodes by ID map (memoized)
+    /// Node ids in workflow order; changes only when nodes are added,
+    /// removed or reordered, not when one is edited or moved
     #[must_use]
-    pub fn nodes_by_id(&self) -> ReadSignal<HashMap<NodeId, Node>> {
+    pub fn node_ids(&self) -> ReadSignal<Vec<NodeId>> {
+        self.node_ids.into()
+    }
+
+    /// Read-only access to nodes by ID, updated incrementally (memoized)
+    #[must_use]
+    pub fn nodes_by_id(&self) -> ReadSignal<NodeIndex> {
         self.nodes_by_id.into()
     }
 
@@ -225,12 +268,29 @@ impl WorkflowState {
         self.connections.into()
     }
 
+    /// Read-only access to canvas frames, bottom to top (memoized)
+    #[must_use]
+    pub fn frames(&self) -> ReadSignal<Vec<Frame>> {
+        self.frames.into()
+    }
+
     /// Read-only access to viewport (memoized)
     #[must_use]
     pub fn viewport(&self) -> ReadSignal<Viewport> {
         self.viewport.into()
     }
 
+    /// What the current user may do, from the page's capability token
+    #[must_use]
+    pub fn capabilities(&self) -> ReadSignal<Capabilities> {
+        self.capabilities.into()
+    }
+
+    #[must_use]
+    pub fn can(&self, capability: Capability) -> bool {
+        self.capabilities.read().can(capability)
+    }
+
     /// Save current state to undo stack before mutation
     pub fn save_undo_point(mut self) {
         let current = self.workflow.read().clone();
@@ -240,14 +300,61 @@ impl WorkflowState {
 
     /// Add a new node at the specified position
     #[must_use]
-    pub fn add_node(mut self, node_type: &str, x: f32, y: f32) -> NodeId {
+    pub fn add_node(mut self, node_type: &str, x: f32, y: f32) -> Option<NodeId> {
+        if !self.can(Capability::EditWorkflow) {
+            return None;
+        }
         self.save_undo_point();
-        self.workflow.write().add_node(node_type, x, y)
+        Some(self.workflow.write().add_node(node_type, x, y))
+    }
+
+    /// Add a node dropped from the sidebar and auto-connect it as a single
+    /// undo step. A node wired to a free output is placed to the right of
+    /// that output's node. Connections the graph rejects are skipped and the
+    /// node is left unconnected.
+    #[must_use]
+    pub fn drop_node(
+        mut self,
+        node_type: &str,
+        x: f32,
+        y: f32,
+        auto_connect: Option<AutoConnect>,
+    ) -> Option<NodeId> {
+        if !self.can(Capability::EditWorkflow) {
+            return None;
+        }
+        self.save_undo_point();
+        let mut workflow = self.workflow.write();
+        let node_id = match auto_connect {
+            Some(AutoConnect::Splice(connection_id)) => {
+                let node_id = workflow.add_node(node_type, x, y);
+                let _ = workflow.splice_node_into_connection(connection_id, node_id);
+                node_id
+            }
+            Some(AutoConnect::FromOutput(source)) => {
+                let (x, y) = workflow
+                    .nodes
+                    .iter()
+                    .find(|node| node.id == source)
+                    .map_or((x, y), |node| {
+                        (node.x + NODE_WIDTH + AUTO_CONNECT_GAP, node.y)
+                    });
+                let node_id = workflow.add_node(node_type, x, y);
+                let main = PortName::from("main");
+                let _ = workflow.add_connection_checked(source, node_id, &main, &main);
+                node_id
+            }
+            None => workflow.add_node(node_type, x, y),
+        };
+        Some(node_id)
     }
 
     /// Duplicate a node at an offset position. Returns the new node's ID.
     #[must_use]
     pub fn duplicate_node(mut self, node_id: NodeId) -> Option<NodeId> {
+        if !self.can(Capability::EditWorkflow) {
+            return None;
+        }
         let wf = self.workflow.read();
         let original = wf.nodes.iter().find(|n| n.id == node_id)?;
         let new_id = NodeId::new();
@@ -261,11 +368,26 @@ impl WorkflowState {
         new_node.error = None;
         drop(wf);
 
+        let node_type = new_node.node_type.clone();
         self.save_undo_point();
         self.workflow.write().nodes.push(new_node);
+        events::emit(&WorkflowEvent::NodeAdded {
+            node_id: new_id,
+            node_type,
+        });
         Some(new_id)
     }
 
+    /// Merge an imported subgraph at the given canvas position. Returns the new node IDs.
+    #[must_use]
+    pub fn import_subgraph(mut self, subgraph: &Workflow, x: f32, y: f32) -> Vec<NodeId> {
+        if !self.can(Capability::EditWorkflow) {
+            return Vec::new();
+        }
+        self.save_undo_point();
+        self.workflow.write().import_subgraph(subgraph, x, y)
+    }
+
     /// Add a node at the viewport center using explicit canvas dimensions
     #[must_use]
     pub fn add_node_at_viewport_center_with_canvas(
@@ -273,14 +395,15 @@ impl WorkflowState {
         node_type: &str,
         canvas_width: f32,
         canvas_height: f32,
-    ) -> NodeId {
+    ) -> Option<NodeId> {
+        if !self.can(Capability::EditWorkflow) {
+            return None;
+        }
         self.save_undo_point();
         let viewport = self.workflow.read().viewport.clone();
-        if let Some((x, y)) = viewport_center_node_origin(&viewport, canvas_width, canvas_height) {
-            self.workflow.write().add_node(node_type, x, y)
-        } else {
-            self.workflow.write().add_node(node_type, 0.0, 0.0)
-        }
+        let (x, y) = viewport_center_node_origin(&viewport, canvas_width, canvas_height)
+            .unwrap_or((0.0, 0.0));
+        Some(self.workflow.write().add_node(node_type, x, y))
     }
 
     /// Remove multiple nodes as a single undo transaction
@@ -288,6 +411,9 @@ impl WorkflowState {
     /// # Errors
     /// Returns `WorkflowError::NodeNotFound` if any of the provided node IDs do not exist in the workflow.
     pub fn remove_nodes(mut self, node_ids: &[NodeId]) -> WorkflowResult<()> {
+        if !self.can(Capability::EditWorkflow) {
+            return Err(WorkflowError::ReadOnly);
+        }
         let mut workflow = self.workflow.write();
         let mut undo_stack = self.undo_stack.write();
         let mut redo_stack = self.redo_stack.write();
@@ -305,6 +431,9 @@ impl WorkflowState {
         source_port: &PortName,
         target_port: &PortName,
     ) -> WorkflowResult<()> {
+        if !self.can(Capability::EditWorkflow) {
+            return Err(WorkflowError::ReadOnly);
+        }
         let mut workflow = self.workflow.write();
         let mut undo_stack = self.undo_stack.write();
         let mut redo_stack = self.redo_stack.write();
@@ -335,15 +464,45 @@ impl WorkflowState {
         self.workflow.write().fit_view(width, height, padding);
     }
 
+    /// Centre the viewport on a node. Returns `false` if it does not exist.
+    pub fn focus_node(mut self, node_id: NodeId, width: f32, height: f32) -> bool {
+        self.workflow.write().focus_node(node_id, width, height)
+    }
+
     /// Apply auto-layout to nodes
     pub fn apply_layout(mut self) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
         self.save_undo_point();
         self.workflow.write().apply_layout();
     }
 
+    /// Move nodes to positions computed elsewhere, such as a layout run in
+    /// the analysis worker. Nodes deleted in the meantime are skipped.
+    pub fn apply_positions(mut self, positions: &[NodePosition]) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
+        self.save_undo_point();
+        let by_id: HashMap<NodeId, &NodePosition> = positions
+            .iter()
+            .map(|position| (position.id, position))
+            .collect();
+        for node in &mut self.workflow.write().nodes {
+            if let Some(position) = by_id.get(&node.id) {
+                node.x = position.x;
+                node.y = position.y;
+            }
+        }
+    }
+
     /// Undo last action - returns true if undo was performed
     #[must_use]
     pub fn undo(mut self) -> bool {
+        if !self.can(Capability::EditWorkflow) {
+            return false;
+        }
         let mut workflow = self.workflow.read().clone();
         let did_undo = apply_undo(
             &mut workflow,
@@ -359,6 +518,9 @@ impl WorkflowState {
     /// Redo last undone action - returns true if redo was performed
     #[must_use]
     pub fn redo(mut self) -> bool {
+        if !self.can(Capability::EditWorkflow) {
+            return false;
+        }
         let mut workflow = self.workflow.read().clone();
         let did_redo = apply_redo(
             &mut workflow,
@@ -385,24 +547,126 @@ impl WorkflowState {
 
     /// Update node position
     pub fn update_node_position(mut self, node_id: NodeId, dx: f32, dy: f32) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
         if !dx.is_finite() || !dy.is_finite() {
             return;
         }
-        self.workflow.write().update_node_position(node_id, dx, dy);
+        let grid = *self.snap_grid.read();
+        self.workflow
+            .write()
+            .update_node_position_on_grid(node_id, dx, dy, grid);
+    }
+
+    /// Set the grid dragged nodes snap to; `None` moves them freely.
+    pub fn set_snap_grid(mut self, grid: Option<f32>) {
+        self.snap_grid.set(grid);
+    }
+
+    /// Wrap the given nodes in a new frame. Returns `None` if no node matched.
+    #[must_use]
+    pub fn frame_nodes(mut self, title: &str, node_ids: &[NodeId]) -> Option<FrameId> {
+        if !self.can(Capability::EditWorkflow) {
+            return None;
+        }
+        let snapshot = self.workflow.read().clone();
+        let id = self.workflow.write().frame_nodes(title, node_ids)?;
+        push_undo_snapshot(&mut self.undo_stack.write(), snapshot, 60);
+        self.redo_stack.write().clear();
+        Some(id)
+    }
+
+    /// Add an empty frame with its top-left corner at a canvas position
+    #[must_use]
+    pub fn add_frame(mut self, title: &str, x: f32, y: f32) -> Option<FrameId> {
+        if !self.can(Capability::EditWorkflow) {
+            return None;
+        }
+        self.save_undo_point();
+        Some(self.workflow.write().add_frame(
+            title,
+            x,
+            y,
+            FRAME_DEFAULT_WIDTH,
+            FRAME_DEFAULT_HEIGHT,
+        ))
+    }
+
+    /// Raise a frame above the others and record an undo point before it
+    /// is moved or resized
+    pub fn begin_frame_edit(mut self, frame_id: FrameId) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
+        self.save_undo_point();
+        self.workflow.write().bring_frame_to_front(frame_id);
+    }
+
+    /// Move a frame and the nodes inside it
+    pub fn move_frame(mut self, frame_id: FrameId, dx: f32, dy: f32) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
+        self.workflow.write().move_frame(frame_id, dx, dy);
+    }
+
+    /// Resize a frame from its bottom-right corner
+    pub fn resize_frame(mut self, frame_id: FrameId, dw: f32, dh: f32) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
+        self.workflow.write().resize_frame(frame_id, dw, dh);
+    }
+
+    /// Rename a frame
+    pub fn rename_frame(mut self, frame_id: FrameId, title: &str) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
+        self.save_undo_point();
+        self.workflow.write().rename_frame(frame_id, title);
+    }
+
+    /// Remove a frame, leaving its nodes in place
+    pub fn remove_frame(mut self, frame_id: FrameId) {
+        if !self.can(Capability::EditWorkflow) {
+            return;
+        }
+        self.save_undo_point();
+        self.workflow.write().remove_frame(frame_id);
     }
 
     /// Run the workflow asynchronously, using `ingress_url` for Restate service calls.
     pub fn run(self, ingress_url: String) {
+        if !self.can(Capability::RunWorkflow) {
+            return;
+        }
         let mut workflow_signal = self.workflow;
         let workflow_snapshot = workflow_signal.read().clone();
 
         spawn(async move {
-            let workflow_result = run_workflow_detached(workflow_snapshot, ingress_url).await;
+            // Mirror node statuses while the run is in flight so the canvas
+            // can animate progress.
+            let on_progress = move |snapshot: &Workflow| {
+                let merged = merge_run_result(workflow_signal.read().clone(), snapshot.clone());
+                workflow_signal.set(merged);
+            };
+            let workflow_result =
+                run_workflow_detached(workflow_snapshot, ingress_url, on_progress).await;
             let merged = merge_run_result(workflow_signal.read().clone(), workflow_result);
             workflow_signal.set(merged);
         });
     }
 
+    /// Forget cached node outputs so the next run executes every node
+    pub fn clear_node_cache(mut self) {
+        if !self.can(Capability::RunWorkflow) {
+            return;
+        }
+        self.workflow.write().clear_node_cache();
+    }
+
     /// Find downstream nodes (nodes connected FROM the given node)
     #[must_use]
     pub fn downstream_nodes(&self, node_id: NodeId) -> Vec<NodeId> {
@@ -435,6 +699,84 @@ impl WorkflowState {
     pub fn first_node_id(&self) -> Option<NodeId> {
         self.nodes.read().first().map(|n| n.id)
     }
+
+    /// Start collaborating on the relay's shared workflow: `base` with
+    /// `ops` replayed. Nodes this editor already has keep their run state;
+    /// undo history is dropped, since it predates the shared workflow.
+    pub fn start_collab(mut self, peer: PeerId, base: Workflow, ops: &[StampedOp]) {
+        let mut document = CollabDocument::new(peer, base);
+        for op in ops {
+            document.apply(op);
+        }
+        document.sync_into(&mut self.workflow.write());
+        self.undo_stack.write().clear();
+        self.redo_stack.write().clear();
+        self.collab.set(Some(document));
+    }
+
+    pub fn stop_collab(mut self) {
+        self.collab.set(None);
+    }
+
+    /// Operations for the local edits made since the last call, to send to
+    /// the relay. Empty when not collaborating or nothing shared changed.
+    #[must_use]
+    pub fn collab_ops(mut self) -> Vec<StampedOp> {
+        let workflow = self.workflow.peek();
+        self.collab
+            .write()
+            .as_mut()
+            .map(|document| document.edit_to(&workflow))
+            .unwrap_or_default()
+    }
+
+    /// Merge another peer's operation into the workflow. Local edits not yet
+    /// sent are turned into operations first so the merge cannot lose them;
+    /// those are returned for sending.
+    #[must_use]
+    pub fn merge_collab_op(mut self, op: &StampedOp) -> Vec<StampedOp> {
+        let mut collab = self.collab.write();
+        let Some(document) = collab.as_mut() else {
+            return Vec::new();
+        };
+        let pending = document.edit_to(&self.workflow.peek());
+        if document.apply(op) {
+            document.sync_into(&mut self.workflow.write());
+        }
+        pending
+    }
+
+    /// Link or unlink a spec behavior on a node, with an undo point.
+    /// Returns `false` if the node is missing or already in that state.
+    pub fn set_behavior_link(mut self, node_id: NodeId, behavior_id: &str, linked: bool) -> bool {
+        if !self.can(Capability::EditWorkflow) {
+            return false;
+        }
+        let is_linked = self
+            .workflow
+            .read()
+            .nodes
+            .iter()
+            .find(|n| n.id == node_id)
+            .map(|n| node_behavior_refs(n).iter().any(|r| r == behavior_id));
+        if is_linked != Some(!linked) {
+            return false;
+        }
+
+        self.save_undo_point();
+        self.workflow
+            .write()
+            .nodes
+            .iter_mut()
+            .find(|n| n.id == node_id)
+            .is_some_and(|node| {
+                if linked {
+                    link_behavior(node, behavior_id)
+                } else {
+                    unlink_behavior(node, behavior_id)
+                }
+            })
+    }
 }
 
 fn map_connection_error(error: &ConnectivityConnectionError) -> WorkflowError {
@@ -444,6 +786,9 @@ fn map_connection_error(error: &ConnectivityConnectionError) -> WorkflowError {
         | ConnectivityConnectionError::MissingTargetNode(node_id) => {
             WorkflowError::NodeNotFound(*node_id)
         }
+        ConnectivityConnectionError::MissingConnection(connection_id) => {
+            WorkflowError::InvalidConnection(format!("Connection not found: {connection_id}"))
+        }
         ConnectivityConnectionError::WouldCreateCycle => WorkflowError::CycleDetected,
         ConnectivityConnectionError::Duplicate => WorkflowError::DuplicateConnection,
         ConnectivityConnectionError::TypeMismatch {
@@ -455,6 +800,9 @@ fn map_connection_error(error: &ConnectivityConnectionError) -> WorkflowError {
         ConnectivityConnectionError::ParseError(_) => {
             WorkflowError::InvalidConnection("Parse error".to_string())
         }
+        ConnectivityConnectionError::GrammarViolation(violation) => {
+            WorkflowError::InvalidConnection(violation.to_string())
+        }
     }
 }
 
@@ -464,18 +812,13 @@ pub fn provide_workflow_state_context() -> WorkflowState {
         {
             use web_sys::window;
             let storage = window().and_then(|w| w.local_storage().ok()).flatten();
+            use crate::ui::persistence::{restore, DELTA_STORAGE_KEY, SNAPSHOT_STORAGE_KEY};
             if let Some(s) = storage {
-                match s.get_item("flow-wasm-v1-workflow") {
-                    Ok(Some(json)) => {
-                        if let Ok(mut parsed) = serde_json::from_str::<Workflow>(&json) {
-                            parsed.nodes.iter_mut().for_each(|node| {
-                                let config = node.config.clone();
-                                node.apply_config_update(&config);
-                            });
-                            return parsed;
-                        }
+                if let Ok(Some(json)) = s.get_item(SNAPSHOT_STORAGE_KEY) {
+                    let delta = s.get_item(DELTA_STORAGE_KEY).ok().flatten();
+                    if let Some(parsed) = restore(&json, delta.as_deref()) {
+                        return parsed;
                     }
-                    _ => {}
                 }
             }
         }
@@ -488,16 +831,21 @@ pub fn provide_workflow_state_context() -> WorkflowState {
 
     // Derived memos for performance
     let nodes = use_memo(move || workflow.read().nodes.clone());
+    let node_ids = use_memo(move || workflow.read().nodes.iter().map(|n| n.id).collect());
+    // The index outlives each memo run so a change only rewrites the nodes
+    // it touched; the memo hands out cheap structural clones of it.
+    let node_index = use_hook(|| Rc::new(RefCell::new(NodeIndex::default())));
     let nodes_by_id = use_memo(move || {
-        workflow
-            .read()
-            .nodes
-            .iter()
-            .map(|n| (n.id, n.clone()))
-            .collect()
+        let mut index = node_index.borrow_mut();
+        index.sync(&workflow.read().nodes);
+        index.clone()
     });
     let connections = use_memo(move || workflow.read().connections.clone());
+    let frames = use_memo(move || workflow.read().frames.clone());
     let viewport = use_memo(move || workflow.read().viewport.clone());
+    let snap_grid = use_signal(|| Some(crate::graph::calc::DEFAULT_SNAP_GRID));
+    let capabilities = use_signal(Capabilities::for_current_page);
+    let collab = use_signal(|| None);
 
     let state = WorkflowState {
         workflow,
@@ -505,9 +853,14 @@ pub fn provide_workflow_state_context() -> WorkflowState {
         undo_stack,
         redo_stack,
         nodes,
+        node_ids,
         nodes_by_id,
         connections,
+        frames,
         viewport,
+        snap_grid,
+        capabilities,
+        collab,
     };
     provide_context(state)
 }
@@ -517,6 +870,14 @@ pub fn use_workflow_state() -> WorkflowState {
     use_context::<WorkflowState>()
 }
 
+/// One node as its own memo. It only notifies when that node changes, so a
+/// component showing it is left alone while other nodes are dragged.
+#[must_use]
+pub fn use_node(node_id: NodeId) -> Memo<Option<Node>> {
+    let workflow = use_workflow_state();
+    use_memo(move || workflow.nodes_by_id.read().get(&node_id).cloned())
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -540,7 +901,8 @@ mod tests {
         let mut workflow = Workflow::new();
         workflow.add_node("http-handler", 0.0, 0.0);
 
-        let updated = run_workflow_detached(workflow, "http://localhost:8080".to_string()).await;
+        let updated =
+            run_workflow_detached(workflow, "http://localhost:8080".to_string(), |_| {}).await;
 
         assert_eq!(updated.history.len(), 1);
         assert!(updated.history[0].success);
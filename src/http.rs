@@ -0,0 +1,273 @@
+//! Minimal HTTP/1.1 plumbing shared by the local servers: reading one
+//! request per connection within fixed bounds, and writing a response
+//! without any cross-origin headers unless a server adds them.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::ui::deep_link::decode;
+
+/// Largest request body accepted, to bound memory per connection.
+pub const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+/// Largest request line plus headers accepted.
+pub const MAX_HEAD_BYTES: usize = 64 * 1024;
+/// Time a client gets to send the request line and headers.
+pub const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One parsed request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Request {
+    /// Upper-cased.
+    pub method: String,
+    pub path: String,
+    /// Raw query string, without the `?`.
+    pub query: String,
+    /// Keyed by lower-cased header name.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Query parameters, percent-decoded. A component that does not decode
+    /// is kept as sent.
+    #[must_use]
+    pub fn query_pairs(&self) -> Vec<(String, String)> {
+        let decode = |component: &str| decode(component).unwrap_or_else(|| component.to_string());
+        self.query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode(key), decode(value))
+            })
+            .collect()
+    }
+
+    /// Token of an `Authorization: Bearer <token>` header.
+    #[must_use]
+    pub fn bearer_token(&self) -> Option<&str> {
+        let (scheme, token) = self.header("authorization")?.split_once(' ')?;
+        scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+    }
+}
+
+/// Read the request on `stream`. Requests over [`MAX_HEAD_BYTES`] of
+/// headers, slower than [`HEAD_TIMEOUT`] or over [`MAX_BODY_BYTES`] of body
+/// are answered here and yield `None`.
+///
+/// # Errors
+/// Returns an error if reading from or answering the client fails.
+pub async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut reader = BufReader::new(&mut *stream);
+    let head = tokio::time::timeout(HEAD_TIMEOUT, read_head(&mut reader)).await;
+    let mut request = match head {
+        Ok(Ok(Some(request))) => request,
+        Ok(Ok(None)) => {
+            return reject(stream, "431 Request Header Fields Too Large").await;
+        }
+        Ok(Err(error)) => return Err(error),
+        Err(_) => return reject(stream, "408 Request Timeout").await,
+    };
+    let content_length: usize = request
+        .header("content-length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return reject(stream, "413 Payload Too Large").await;
+    }
+    request.body = vec![0; content_length];
+    reader.read_exact(&mut request.body).await?;
+    Ok(Some(request))
+}
+
+/// Request line and headers, or `None` once they pass [`MAX_HEAD_BYTES`].
+async fn read_head(reader: &mut BufReader<&mut TcpStream>) -> std::io::Result<Option<Request>> {
+    let mut budget = MAX_HEAD_BYTES as u64;
+    let mut line = String::new();
+    if !read_line(reader, &mut budget, &mut line).await? {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_ascii_uppercase();
+    let target = parts.next().unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = Request {
+        method,
+        path: path.to_string(),
+        query: query.to_string(),
+        ..Request::default()
+    };
+    loop {
+        if !read_line(reader, &mut budget, &mut line).await? {
+            return Ok(None);
+        }
+        if line.trim_end().is_empty() {
+            return Ok(Some(request));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            request
+                .headers
+                .insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+}
+
+/// Read one line into `line` out of the remaining `budget`; `false` once
+/// the budget runs out before the line ends.
+async fn read_line(
+    reader: &mut BufReader<&mut TcpStream>,
+    budget: &mut u64,
+    line: &mut String,
+) -> std::io::Result<bool> {
+    line.clear();
+    let read = (&mut *reader).take(*budget).read_line(line).await?;
+    *budget -= read as u64;
+    if read == 0 && *budget > 0 {
+        return Err(std::io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(line.ends_with('\n') || *budget > 0)
+}
+
+async fn reject(stream: &mut TcpStream, status: &str) -> std::io::Result<Option<Request>> {
+    respond(stream, status, &[], "text/plain", status).await?;
+    Ok(None)
+}
+
+/// Write a complete response with `headers` added and close the
+/// connection.
+///
+/// # Errors
+/// Returns an error if writing to the client fails.
+pub async fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    headers: &[(&str, &str)],
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        header_lines(headers),
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Start a server-sent-event stream with `headers` added.
+///
+/// # Errors
+/// Returns an error if writing to the client fails.
+pub async fn start_event_stream(
+    stream: &mut TcpStream,
+    headers: &[(&str, &str)],
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n{}Connection: keep-alive\r\n\r\n",
+        header_lines(headers)
+    );
+    stream.write_all(head.as_bytes()).await
+}
+
+fn header_lines(headers: &[(&str, &str)]) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| format!("{name}: {value}\r\n"))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    /// Send `raw` to a server that reads one request, and return what the
+    /// server parsed plus whatever it answered.
+    async fn exchange(raw: Vec<u8>) -> (Option<Request>, String) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut stream).await.unwrap();
+            drop(stream);
+            request
+        });
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        // The server may answer and hang up before every byte is sent.
+        let _ = client.write_all(&raw).await;
+        let mut answer = String::new();
+        let _ = client.read_to_string(&mut answer).await;
+        (server.await.unwrap(), answer)
+    }
+
+    #[tokio::test]
+    async fn given_request_when_reading_then_method_path_query_headers_and_body_are_parsed() {
+        let (request, _) = exchange(
+            b"post /rpc?x=1 HTTP/1.1\r\nAuthorization: Bearer s3cret\r\nContent-Length: 2\r\n\r\n{}"
+                .to_vec(),
+        )
+        .await;
+
+        let request = request.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/rpc");
+        assert_eq!(request.query_pairs(), [("x".to_string(), "1".to_string())]);
+        assert_eq!(request.bearer_token(), Some("s3cret"));
+        assert_eq!(request.body, b"{}");
+    }
+
+    #[tokio::test]
+    async fn given_oversized_head_or_body_when_reading_then_request_is_rejected() {
+        let mut endless_header = b"GET / HTTP/1.1\r\nX-Padding: ".to_vec();
+        endless_header.resize(MAX_HEAD_BYTES + 16, b'a');
+        let (request, answer) = exchange(endless_header).await;
+        assert_eq!(request, None);
+        assert!(answer.starts_with("HTTP/1.1 431"), "{answer}");
+
+        let (request, answer) = exchange(
+            format!(
+                "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+                MAX_BODY_BYTES + 1
+            )
+            .into_bytes(),
+        )
+        .await;
+        assert_eq!(request, None);
+        assert!(answer.starts_with("HTTP/1.1 413"), "{answer}");
+    }
+
+    #[test]
+    fn given_encoded_query_when_splitting_then_components_are_decoded() {
+        let request = Request {
+            query: "name=a%20b&tag=x+y&bad=%zz&flag".to_string(),
+            ..Request::default()
+        };
+
+        let pairs = request.query_pairs();
+
+        let pairs = pairs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            [
+                ("name", "a b"),
+                ("tag", "x y"),
+                ("bad", "%zz"),
+                ("flag", "")
+            ]
+        );
+    }
+}
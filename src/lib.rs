@@ -2,6 +2,7 @@
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod agent_feedback;
+pub mod analysis_worker;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod connectivity;
 #[cfg(not(target_arch = "wasm32"))]
@@ -15,13 +16,21 @@ pub mod feedback;
 pub mod flow_extender;
 pub mod graph;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod headless;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod http;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod linter;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod metrics;
+pub mod prelude;
 pub mod restate_client;
 pub mod restate_sync;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod scenario_runner;
+pub mod twin_client;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod wizard;
 
 #[cfg(target_arch = "wasm32")]
 pub mod hooks;
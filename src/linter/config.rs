@@ -0,0 +1,156 @@
+//! Workspace lint configuration (`.oya-lint.yaml`).
+//!
+//! ```yaml
+//! severity:
+//!   SPEC-021: error
+//! disabled: [SPEC-040]
+//! allowed_phrases: ["just"]
+//! specs:
+//!   legacy-import:
+//!     disabled: [SPEC-011]
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::model::LintError;
+
+/// Names looked up by [`LintConfig::discover`], in order.
+pub const LINT_CONFIG_FILES: [&str; 2] = [".oya-lint.yaml", ".oya-lint.yml"];
+
+/// Overrides for one spec id, layered over the workspace-wide ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecLintOverrides {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_phrases: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintConfig {
+    /// Rule id to `error` or `warning`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub severity: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled: Vec<String>,
+    /// Banned phrases (SPEC-010) this workspace accepts anyway.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_phrases: Vec<String>,
+    /// Keyed by spec id.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub specs: BTreeMap<String, SpecLintOverrides>,
+    /// File the configuration was loaded from.
+    #[serde(skip)]
+    pub source: Option<PathBuf>,
+}
+
+/// The configuration one spec was linted with, after per-spec overrides.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectiveLintConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PathBuf>,
+    #[serde(default)]
+    pub severity: BTreeMap<String, String>,
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    #[serde(default)]
+    pub allowed_phrases: Vec<String>,
+}
+
+impl LintConfig {
+    /// Load a configuration file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LintError` if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self, LintError> {
+        let content = fs::read_to_string(path)?;
+        let config: Self = serde_yaml::from_str(&content)?;
+        Ok(Self {
+            source: Some(path.to_path_buf()),
+            ..config
+        })
+    }
+
+    /// Load the nearest configuration file in `start` or its ancestors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LintError` if a configuration file exists but cannot be
+    /// read or parsed.
+    pub fn discover(start: &Path) -> Result<Option<Self>, LintError> {
+        start
+            .ancestors()
+            .flat_map(|dir| LINT_CONFIG_FILES.iter().map(move |name| dir.join(name)))
+            .find(|path| path.is_file())
+            .map(|path| Self::load(&path))
+            .transpose()
+    }
+
+    /// Rule ids the configuration mentions, for validation.
+    pub(super) fn rule_ids(&self) -> impl Iterator<Item = &str> {
+        let overrides = self.specs.values().flat_map(|spec| {
+            spec.severity
+                .keys()
+                .chain(spec.disabled.iter())
+                .map(String::as_str)
+        });
+        self.severity
+            .keys()
+            .chain(self.disabled.iter())
+            .map(String::as_str)
+            .chain(overrides)
+    }
+
+    /// Severities the configuration sets, as `(rule id, severity)`.
+    pub(super) fn severities(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.severity
+            .iter()
+            .chain(self.specs.values().flat_map(|spec| spec.severity.iter()))
+            .map(|(rule_id, severity)| (rule_id.as_str(), severity.as_str()))
+    }
+
+    #[must_use]
+    pub fn effective_for(&self, spec_id: &str) -> EffectiveLintConfig {
+        let overrides = self.specs.get(spec_id);
+        let mut severity = self.severity.clone();
+        let mut disabled = self.disabled.clone();
+        let mut allowed_phrases = self.allowed_phrases.clone();
+        if let Some(overrides) = overrides {
+            severity.extend(overrides.severity.clone());
+            disabled.extend(overrides.disabled.iter().cloned());
+            allowed_phrases.extend(overrides.allowed_phrases.iter().cloned());
+        }
+        disabled.sort();
+        disabled.dedup();
+        allowed_phrases.sort();
+        allowed_phrases.dedup();
+
+        EffectiveLintConfig {
+            source: self.source.clone(),
+            severity,
+            disabled,
+            allowed_phrases,
+        }
+    }
+}
+
+impl EffectiveLintConfig {
+    #[must_use]
+    pub fn is_disabled(&self, rule_id: &str) -> bool {
+        self.disabled.iter().any(|disabled| disabled == rule_id)
+    }
+
+    #[must_use]
+    pub fn is_allowed_phrase(&self, phrase: &str) -> bool {
+        self.allowed_phrases
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(phrase))
+    }
+}
@@ -0,0 +1,211 @@
+//! Consistency between `api_contract` and the behaviors that exercise it.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use super::model::{ApiEndpoint, Behavior, Specification};
+
+const HTTP_METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// One contract inconsistency, ready to become a lint issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ContractFinding {
+    pub rule_id: &'static str,
+    pub message: String,
+    pub location: String,
+    /// Spec text that would resolve the finding.
+    pub suggestion: String,
+}
+
+/// Findings per rule id, in `SPEC-013`, `SPEC-014`, `SPEC-015` order.
+/// `None` when the spec has no API contract.
+pub(super) fn analyze(
+    specification: &Specification,
+) -> Option<Vec<(&'static str, Vec<ContractFinding>)>> {
+    let contract = specification.api_contract.as_ref()?;
+    let endpoints = contract.endpoints.as_deref().unwrap_or_default();
+    let texts = behavior_texts(&specification.behaviors);
+    let mentions = texts
+        .iter()
+        .flat_map(|(behavior_id, text)| {
+            path_mentions(text).map(move |(method, path)| (*behavior_id, method, path))
+        })
+        .collect::<Vec<_>>();
+
+    let unreferenced = endpoints
+        .iter()
+        .filter(|endpoint| {
+            !mentions.iter().any(|(_, method, path)| {
+                path_matches(&endpoint.path, path)
+                    && method.is_none_or(|method| method.eq_ignore_ascii_case(&endpoint.method))
+            })
+        })
+        .map(|endpoint| ContractFinding {
+            rule_id: "SPEC-013",
+            message: format!(
+                "Endpoint {} is not exercised by any behavior",
+                endpoint_label(endpoint)
+            ),
+            location: format!("api_contract.endpoints.{}", endpoint_label(endpoint)),
+            suggestion: format!(
+                "when: \"Client sends {}\"\nthen:\n  - \"HTTP <status> response returned\"",
+                endpoint_label(endpoint)
+            ),
+        })
+        .collect();
+
+    let mut undeclared: Vec<ContractFinding> = Vec::new();
+    let mut reported: HashSet<(&str, &str)> = HashSet::new();
+    for (behavior_id, method, path) in &mentions {
+        if endpoints
+            .iter()
+            .any(|endpoint| path_matches(&endpoint.path, path))
+            || !reported.insert((behavior_id, path.as_str()))
+        {
+            continue;
+        }
+        let method = method.unwrap_or("GET");
+        undeclared.push(ContractFinding {
+            rule_id: "SPEC-014",
+            message: format!(
+                "Behavior '{behavior_id}' mentions {path}, which api_contract.endpoints does not declare"
+            ),
+            location: format!("behaviors.{behavior_id}"),
+            suggestion: format!(
+                "- method: {method}\n  path: {path}\n  authentication: <scheme or \"none\">"
+            ),
+        });
+    }
+
+    let emitted = contract
+        .events_emitted
+        .iter()
+        .flatten()
+        .filter_map(event_name)
+        .map(|name| (name, "emitted"));
+    let consumed = contract
+        .events_consumed
+        .iter()
+        .flatten()
+        .filter_map(event_name)
+        .map(|name| (name, "consumed"));
+    let orphan_events = emitted
+        .chain(consumed)
+        .filter(|(name, _)| {
+            let name = name.to_lowercase();
+            !texts
+                .iter()
+                .any(|(_, text)| text.to_lowercase().contains(&name))
+        })
+        .map(|(name, direction)| {
+            let (clause, suggestion) = if direction == "emitted" {
+                ("emits", format!("then:\n  - \"{name} event is emitted\""))
+            } else {
+                ("handles", format!("when: \"{name} event is received\""))
+            };
+            ContractFinding {
+                rule_id: "SPEC-015",
+                message: format!("Event '{name}' is {direction} but no behavior {clause} it"),
+                location: format!("api_contract.events_{direction}.{name}"),
+                suggestion,
+            }
+        })
+        .collect();
+
+    Some(vec![
+        ("SPEC-013", unreferenced),
+        ("SPEC-014", undeclared),
+        ("SPEC-015", orphan_events),
+    ])
+}
+
+fn behavior_texts(behaviors: &[Behavior]) -> Vec<(&str, &str)> {
+    behaviors
+        .iter()
+        .flat_map(|behavior| {
+            let edge_cases = behavior.edge_cases.iter().flatten().flat_map(|edge_case| {
+                std::iter::once(edge_case.r#when.as_str())
+                    .chain(edge_case.then.iter().map(String::as_str))
+            });
+            std::iter::once(behavior.description.as_str())
+                .chain(behavior.given.iter().flatten().map(String::as_str))
+                .chain(behavior.r#when.as_deref())
+                .chain(behavior.then.iter().map(String::as_str))
+                .chain(edge_cases)
+                .map(|text| (behavior.id.as_str(), text))
+        })
+        .collect()
+}
+
+/// HTTP paths written in prose, with the method when one precedes them.
+fn path_mentions(text: &str) -> impl Iterator<Item = (Option<&'static str>, String)> + '_ {
+    let words = text.split_whitespace().collect::<Vec<_>>();
+    (0..words.len()).filter_map(move |index| {
+        let word = words[index].trim_start_matches(['"', '\'', '(', '`']);
+        let path = word
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(['"', '\'', ')', '`', '.', ',', ';', ':']);
+        let is_path = path.starts_with('/')
+            && path.len() > 1
+            && path
+                .chars()
+                .nth(1)
+                .is_some_and(|c| c.is_alphanumeric() || c == '{' || c == ':');
+        if !is_path {
+            return None;
+        }
+        let method = index
+            .checked_sub(1)
+            .and_then(|previous| {
+                HTTP_METHODS
+                    .iter()
+                    .find(|method| **method == words[previous])
+            })
+            .copied();
+        Some((method, path.to_string()))
+    })
+}
+
+/// Whether a concrete or templated `mentioned` path fits the contract's
+/// `declared` path; `{id}` and `:id` segments match any segment.
+fn path_matches(declared: &str, mentioned: &str) -> bool {
+    let is_param = |segment: &str| {
+        segment.starts_with(':') || (segment.starts_with('{') && segment.ends_with('}'))
+    };
+    let declared = declared
+        .trim_end_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    let mentioned = mentioned
+        .trim_end_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    declared.len() == mentioned.len()
+        && declared
+            .iter()
+            .zip(&mentioned)
+            .all(|(declared, mentioned)| {
+                is_param(declared)
+                    || is_param(mentioned)
+                    || declared.eq_ignore_ascii_case(mentioned)
+            })
+}
+
+fn event_name(event: &Value) -> Option<String> {
+    let name = match event {
+        Value::String(name) => Some(name.as_str()),
+        Value::Object(fields) => ["name", "event", "type"]
+            .iter()
+            .find_map(|key| fields.get(*key).and_then(Value::as_str)),
+        _ => None,
+    }?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+fn endpoint_label(endpoint: &ApiEndpoint) -> String {
+    format!("{} {}", endpoint.method, endpoint.path)
+}
@@ -0,0 +1,250 @@
+//! Rules declared in the rules YAML instead of in Rust.
+//!
+//! A rule with a `check` block selects items from the spec, optionally
+//! narrows them with `where`, and reports every item that fails `assert`:
+//!
+//! ```yaml
+//! - id: TEAM-001
+//!   name: write-endpoints-declare-auth
+//!   severity: error
+//!   category: Security
+//!   description: Write endpoints must say how callers authenticate.
+//!   check:
+//!     select: api_contract.endpoints
+//!     where:
+//!       one_of: { field: method, values: [POST, PUT, PATCH, DELETE] }
+//!     assert:
+//!       present: authentication
+//!     message: "Endpoint {method} {path} does not declare authentication"
+//! ```
+//!
+//! `select` is a dotted path below `specification`; lists along the path
+//! are flattened, so `behaviors.then` yields every `then` clause of every
+//! behavior. Message placeholders name fields of the selected item,
+//! `{value}` is the item itself and `{parent.<field>}` reads the object that
+//! holds it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleCheck {
+    pub select: String,
+    #[serde(default, rename = "where", skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Predicate>,
+    #[serde(rename = "assert")]
+    pub predicate: Predicate,
+    pub message: String,
+}
+
+/// Conditions on one selected item; every condition that is set must hold.
+/// `field` is a dotted path inside the item and defaults to the item itself.
+/// Text comparisons ignore case unless a terms check sets `case_sensitive`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Predicate {
+    /// The field is set and is not an empty string or list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub present: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains_any: Option<TermsPredicate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains_none: Option<TermsPredicate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<ValuesPredicate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all: Vec<Self>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub any: Vec<Self>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<Self>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermsPredicate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub terms: Vec<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub case_sensitive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValuesPredicate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub values: Vec<String>,
+}
+
+/// One selected item and the object that holds it.
+#[derive(Debug, Clone, Copy)]
+struct Selected<'a> {
+    item: &'a Value,
+    parent: Option<&'a Value>,
+}
+
+impl RuleCheck {
+    /// Messages for every selected item that passes `where` but fails
+    /// `assert`, in document order.
+    #[must_use]
+    pub fn violations(&self, specification: &Value) -> Vec<String> {
+        select(specification, &self.select)
+            .into_iter()
+            .filter(|selected| {
+                self.filter
+                    .as_ref()
+                    .is_none_or(|filter| filter.holds(selected.item))
+            })
+            .filter(|selected| !self.predicate.holds(selected.item))
+            .map(|selected| render_message(&self.message, selected))
+            .collect()
+    }
+}
+
+impl Predicate {
+    fn holds(&self, item: &Value) -> bool {
+        self.present
+            .as_deref()
+            .is_none_or(|field| field_value(item, Some(field)).is_some_and(is_present))
+            && self
+                .contains_any
+                .as_ref()
+                .is_none_or(|terms| terms.matches(item))
+            && self
+                .contains_none
+                .as_ref()
+                .is_none_or(|terms| !terms.matches(item))
+            && self.one_of.as_ref().is_none_or(|values| {
+                let text = field_text(item, values.field.as_deref());
+                values
+                    .values
+                    .iter()
+                    .any(|value| value.to_lowercase() == text)
+            })
+            && self.all.iter().all(|predicate| predicate.holds(item))
+            && (self.any.is_empty() || self.any.iter().any(|predicate| predicate.holds(item)))
+            && self
+                .not
+                .as_ref()
+                .is_none_or(|predicate| !predicate.holds(item))
+    }
+}
+
+impl TermsPredicate {
+    fn matches(&self, item: &Value) -> bool {
+        if self.case_sensitive {
+            let text = field_value(item, self.field.as_deref())
+                .map(display)
+                .unwrap_or_default();
+            return self.terms.iter().any(|term| text.contains(term.as_str()));
+        }
+        let text = field_text(item, self.field.as_deref());
+        self.terms
+            .iter()
+            .any(|term| text.contains(&term.to_lowercase()))
+    }
+}
+
+fn select<'a>(root: &'a Value, path: &str) -> Vec<Selected<'a>> {
+    path.split('.')
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .fold(
+            vec![Selected {
+                item: root,
+                parent: None,
+            }],
+            |current, segment| {
+                current
+                    .into_iter()
+                    .filter_map(|selected| {
+                        selected
+                            .item
+                            .get(segment)
+                            .map(|child| (selected.item, child))
+                    })
+                    .flat_map(|(parent, child)| match child {
+                        Value::Array(items) => items
+                            .iter()
+                            .map(|item| Selected {
+                                item,
+                                parent: Some(parent),
+                            })
+                            .collect::<Vec<_>>(),
+                        Value::Null => Vec::new(),
+                        item => vec![Selected {
+                            item,
+                            parent: Some(parent),
+                        }],
+                    })
+                    .collect()
+            },
+        )
+}
+
+fn field_value<'a>(item: &'a Value, field: Option<&str>) -> Option<&'a Value> {
+    field.map_or(Some(item), |field| {
+        field
+            .split('.')
+            .try_fold(item, |value, segment| value.get(segment.trim()))
+    })
+}
+
+fn field_text(item: &Value, field: Option<&str>) -> String {
+    field_value(item, field)
+        .map(display)
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn is_present(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::String(text) => !text.trim().is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        _ => true,
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(display).collect::<Vec<_>>().join(" "),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Fill `{placeholder}`s; unknown ones are left as written.
+fn render_message(template: &str, selected: Selected<'_>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let placeholder = &rest[start + 1..start + len];
+        rendered.push_str(&rest[..start]);
+        match resolve(placeholder, selected) {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+fn resolve(placeholder: &str, selected: Selected<'_>) -> Option<String> {
+    let placeholder = placeholder.trim();
+    if placeholder == "value" {
+        return Some(display(selected.item));
+    }
+    let (scope, field) = placeholder
+        .strip_prefix("parent.")
+        .map_or((Some(selected.item), placeholder), |field| {
+            (selected.parent, field)
+        });
+    scope
+        .and_then(|scope| field_value(scope, Some(field)))
+        .map(display)
+}
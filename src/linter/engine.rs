@@ -1,10 +1,27 @@
 use std::fs;
 use std::path::Path;
 
+use super::config::{EffectiveLintConfig, LintConfig};
+use super::contract;
+use super::glossary::GlossaryUsage;
 use super::model::{CategoryScore, LintError, LintIssue, LintReport, LintRules, Spec};
 
+/// Rules implemented in Rust because they compare items across the spec or
+/// feed a category score of their own. Any other id needs a declarative
+/// `check`, either in the rules file or from [`DEFAULT_RULES`].
+const BUILTIN_RULE_IDS: [&str; 14] = [
+    "SPEC-001", "SPEC-002", "SPEC-004", "SPEC-005", "SPEC-006", "SPEC-010", "SPEC-012", "SPEC-013",
+    "SPEC-014", "SPEC-015", "SPEC-020", "SPEC-021", "SPEC-030", "SPEC-040",
+];
+
+/// Default declarative checks for the remaining built-in ids.
+const DEFAULT_RULES: &str = include_str!("default_rules.yaml");
+
+const DEFAULT_DECLARATIVE_CATEGORY: &str = "Custom";
+
 pub struct SpecLinter {
     rules: LintRules,
+    config: LintConfig,
 }
 
 impl SpecLinter {
@@ -15,17 +32,109 @@ impl SpecLinter {
     /// Returns `LintError` if the file cannot be read or parsed.
     pub fn new(rules_path: &Path) -> Result<Self, LintError> {
         let rules_content = fs::read_to_string(rules_path)?;
-        let rules: LintRules = serde_yaml::from_str(&rules_content)?;
+        let mut rules: LintRules = serde_yaml::from_str(&rules_content)?;
+        Self::apply_default_checks(&mut rules)?;
         Self::validate_rules(&rules)?;
-        Ok(Self { rules })
+        Ok(Self {
+            rules,
+            config: LintConfig::default(),
+        })
     }
 
-    fn validate_rules(rules: &LintRules) -> Result<(), LintError> {
-        const ALLOWED_RULE_IDS: [&str; 10] = [
-            "SPEC-001", "SPEC-002", "SPEC-003", "SPEC-004", "SPEC-010", "SPEC-011", "SPEC-020",
-            "SPEC-021", "SPEC-030", "SPEC-040",
-        ];
+    /// Apply severity overrides, disabled rules and allowed phrases from a
+    /// workspace configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LintError` if the configuration names a rule the linter does
+    /// not know or sets an invalid severity.
+    pub fn with_config(self, config: LintConfig) -> Result<Self, LintError> {
+        for rule_id in config.rule_ids() {
+            let known = BUILTIN_RULE_IDS.contains(&rule_id)
+                || Self::default_rules()?
+                    .rules
+                    .iter()
+                    .any(|rule| rule.id == rule_id)
+                || self.rules.rules.iter().any(|rule| rule.id == rule_id);
+            if !known {
+                return Err(LintError::UnknownRuleId {
+                    rule_id: rule_id.to_string(),
+                });
+            }
+        }
+        if let Some((rule_id, severity)) = config
+            .severities()
+            .find(|(_, severity)| *severity != "error" && *severity != "warning")
+        {
+            return Err(LintError::InvalidSeverity {
+                rule_id: rule_id.to_string(),
+                severity: severity.to_string(),
+            });
+        }
 
+        Ok(Self { config, ..self })
+    }
+
+    pub(super) fn default_rules() -> Result<LintRules, LintError> {
+        Ok(serde_yaml::from_str(DEFAULT_RULES)?)
+    }
+
+    /// Give listed rules without a `check` the default check for their id.
+    fn apply_default_checks(rules: &mut LintRules) -> Result<(), LintError> {
+        let defaults = Self::default_rules()?;
+        for rule in rules.rules.iter_mut().filter(|rule| rule.check.is_none()) {
+            if let Some(default) = defaults
+                .rules
+                .iter()
+                .find(|default| default.id == rule.id.trim())
+            {
+                rule.check.clone_from(&default.check);
+                if rule.category.is_none() {
+                    rule.category.clone_from(&default.category);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The rules file minus disabled rules and allowed phrases.
+    fn configured_rules(rules: &LintRules, config: &EffectiveLintConfig) -> LintRules {
+        LintRules {
+            rules: rules
+                .rules
+                .iter()
+                .filter(|rule| !config.is_disabled(&rule.id))
+                .cloned()
+                .map(|mut rule| {
+                    if let Some(phrases) = rule.banned_phrases.as_mut() {
+                        phrases.retain(|phrase| !config.is_allowed_phrase(phrase));
+                    }
+                    rule
+                })
+                .collect(),
+        }
+    }
+
+    fn apply_severity_overrides(config: &EffectiveLintConfig, report: &mut LintReport) {
+        if config.severity.is_empty() {
+            return;
+        }
+        let issues = std::mem::take(&mut report.errors)
+            .into_iter()
+            .chain(std::mem::take(&mut report.warnings));
+        for mut issue in issues {
+            if let Some(severity) = config.severity.get(&issue.rule_id) {
+                issue.severity.clone_from(severity);
+            }
+            if issue.severity == "error" {
+                report.errors.push(issue);
+            } else {
+                report.warnings.push(issue);
+            }
+        }
+    }
+
+    pub(super) fn validate_rules(rules: &LintRules) -> Result<(), LintError> {
         for rule in &rules.rules {
             let rule_id = rule.id.trim();
             if rule_id.is_empty() {
@@ -35,10 +144,31 @@ impl SpecLinter {
                 });
             }
 
-            if !ALLOWED_RULE_IDS.contains(&rule_id) {
-                return Err(LintError::UnknownRuleId {
-                    rule_id: rule_id.to_string(),
-                });
+            let is_builtin = BUILTIN_RULE_IDS.contains(&rule_id);
+            match &rule.check {
+                None if !is_builtin => {
+                    return Err(LintError::UnknownRuleId {
+                        rule_id: rule_id.to_string(),
+                    });
+                }
+                Some(_) if is_builtin => {
+                    return Err(LintError::BuiltinRuleCheck {
+                        rule_id: rule_id.to_string(),
+                    });
+                }
+                Some(check) if check.select.trim().is_empty() => {
+                    return Err(LintError::MissingRequiredField {
+                        rule_id: rule.id.clone(),
+                        field: "check.select".to_string(),
+                    });
+                }
+                Some(check) if check.message.trim().is_empty() => {
+                    return Err(LintError::MissingRequiredField {
+                        rule_id: rule.id.clone(),
+                        field: "check.message".to_string(),
+                    });
+                }
+                _ => {}
             }
 
             if rule.name.trim().is_empty() {
@@ -80,39 +210,49 @@ impl SpecLinter {
     ///
     /// Returns `LintError` if the file cannot be read or parsed.
     pub fn lint(&self, spec_path: &Path) -> Result<LintReport, LintError> {
+        self.lint_spec(&Self::read_spec(spec_path)?)
+    }
+
+    pub(super) fn read_spec(spec_path: &Path) -> Result<Spec, LintError> {
         let spec_content = fs::read_to_string(spec_path)?;
-        let spec: Spec = serde_yaml::from_str(&spec_content)?;
+        Ok(serde_yaml::from_str(&spec_content)?)
+    }
 
+    /// Lint an already parsed specification.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LintError` if a declarative rule cannot inspect the spec.
+    pub fn lint_spec(&self, spec: &Spec) -> Result<LintReport, LintError> {
         let mut report = LintReport::new(
             spec.specification.identity.id.clone(),
             spec.specification.identity.version.clone(),
         );
 
-        Self::check_completeness(&self.rules, &spec, &mut report);
-        Self::check_clarity(&self.rules, &spec, &mut report);
-        Self::check_security(&self.rules, &spec, &mut report);
-        Self::check_testability(&self.rules, &spec, &mut report);
-        Self::check_data_model(&self.rules, &spec, &mut report);
-
+        let config = self.config.effective_for(&spec.specification.identity.id);
+        let rules = Self::configured_rules(&self.rules, &config);
+
+        Self::check_completeness(&rules, spec, &mut report);
+        Self::check_clarity(&rules, spec, &mut report);
+        Self::check_glossary(&rules, &config, spec, &mut report);
+        Self::check_security(&rules, &config, spec, &mut report);
+        Self::check_testability(&rules, &config, spec, &mut report);
+        Self::check_data_model(&rules, &config, spec, &mut report);
+        Self::check_traceability(&rules, &config, spec, &mut report);
+        Self::check_contract(&rules, &config, spec, &mut report);
+        Self::check_declarative(&rules, spec, &mut report)?;
+        Self::apply_severity_overrides(&config, &mut report);
+
+        report.effective_config = Some(config);
         report.calculate_score();
         Ok(report)
     }
 
-    #[allow(clippy::too_many_lines)]
     fn check_completeness(rules: &LintRules, spec: &Spec, report: &mut LintReport) {
         let spec_001_rule = rules.rules.iter().find(|r| r.id == "SPEC-001");
-        let spec_003_rule = rules.rules.iter().find(|r| r.id == "SPEC-003");
-        let spec_004_rule = rules.rules.iter().find(|r| r.id == "SPEC-004");
-        let spec_011_rule = rules.rules.iter().find(|r| r.id == "SPEC-011");
 
         let spec_001_severity =
             spec_001_rule.map_or_else(|| "error".to_string(), |r| r.severity.clone());
-        let spec_003_severity =
-            spec_003_rule.map_or_else(|| "error".to_string(), |r| r.severity.clone());
-        let spec_004_severity =
-            spec_004_rule.map_or_else(|| "warning".to_string(), |r| r.severity.clone());
-        let spec_011_severity =
-            spec_011_rule.map_or_else(|| "error".to_string(), |r| r.severity.clone());
 
         let mut error_count = 0;
         let mut warning_count = 0;
@@ -151,6 +291,8 @@ impl SpecLinter {
                                 dep.service
                             ),
                             line: None,
+                            location: None,
+                            suggestion: None,
                         });
                         error_count += 1;
                     } else {
@@ -163,129 +305,8 @@ impl SpecLinter {
                                 dep.service
                             ),
                             line: None,
-                        });
-                        warning_count += 1;
-                    }
-                }
-            }
-        }
-
-        if let Some(rule) = spec_003_rule {
-            if let Some(contract) = &spec.specification.api_contract {
-                if let Some(endpoints) = &contract.endpoints {
-                    for endpoint in endpoints.iter().filter(|e| e.authentication.is_none()) {
-                        if spec_003_severity == "error" {
-                            report.errors.push(LintIssue {
-                                rule_id: rule.id.clone(),
-                                rule_name: rule.name.clone(),
-                                severity: spec_003_severity.clone(),
-                                message: format!(
-                                    "Endpoint {} {} missing authentication specification",
-                                    endpoint.method, endpoint.path
-                                ),
-                                line: None,
-                            });
-                            error_count += 1;
-                        } else {
-                            report.warnings.push(LintIssue {
-                                rule_id: rule.id.clone(),
-                                rule_name: rule.name.clone(),
-                                severity: spec_003_severity.clone(),
-                                message: format!(
-                                    "Endpoint {} {} missing authentication specification",
-                                    endpoint.method, endpoint.path
-                                ),
-                                line: None,
-                            });
-                            warning_count += 1;
-                        }
-                    }
-                }
-            }
-        }
-
-        if let Some(rule) = spec_004_rule {
-            for behavior in &spec.specification.behaviors {
-                let has_criterion = spec.specification.acceptance_criteria.iter().any(|ac| {
-                    ac.behavior_ref
-                        .as_ref()
-                        .is_some_and(|ref_id| ref_id == &behavior.id)
-                });
-
-                if !has_criterion {
-                    if spec_004_severity == "error" {
-                        report.errors.push(LintIssue {
-                            rule_id: rule.id.clone(),
-                            rule_name: rule.name.clone(),
-                            severity: spec_004_severity.clone(),
-                            message: format!(
-                                "Behavior '{}' has no acceptance criterion",
-                                behavior.id
-                            ),
-                            line: None,
-                        });
-                        error_count += 1;
-                    } else {
-                        report.warnings.push(LintIssue {
-                            rule_id: rule.id.clone(),
-                            rule_name: rule.name.clone(),
-                            severity: spec_004_severity.clone(),
-                            message: format!(
-                                "Behavior '{}' has no acceptance criterion",
-                                behavior.id
-                            ),
-                            line: None,
-                        });
-                        warning_count += 1;
-                    }
-                }
-            }
-        }
-
-        if let Some(rule) = spec_011_rule {
-            let error_terms = [
-                "error",
-                "fail",
-                "exception",
-                "invalid",
-                "unauthorized",
-                "not found",
-            ];
-            for behavior in &spec.specification.behaviors {
-                let mentions_error = behavior.then.iter().any(|t| {
-                    error_terms
-                        .iter()
-                        .any(|term| t.to_lowercase().contains(term))
-                });
-
-                let has_concrete_response = behavior.then.iter().any(|t| {
-                    t.contains("HTTP")
-                        || t.contains("status")
-                        || t.contains("code")
-                        || t.contains("response")
-                        || t.contains("400")
-                        || t.contains("500")
-                        || t.contains("401")
-                        || t.contains("404")
-                });
-
-                if mentions_error && !has_concrete_response {
-                    if spec_011_severity == "error" {
-                        report.errors.push(LintIssue {
-                            rule_id: rule.id.clone(),
-                            rule_name: rule.name.clone(),
-                            severity: spec_011_severity.clone(),
-                            message: format!("Behavior '{}' mentions error but doesn't specify concrete HTTP status code", behavior.id),
-                            line: None,
-                        });
-                        error_count += 1;
-                    } else {
-                        report.warnings.push(LintIssue {
-                            rule_id: rule.id.clone(),
-                            rule_name: rule.name.clone(),
-                            severity: spec_011_severity.clone(),
-                            message: format!("Behavior '{}' mentions error but doesn't specify concrete HTTP status code", behavior.id),
-                            line: None,
+                            location: None,
+                            suggestion: None,
                         });
                         warning_count += 1;
                     }
@@ -333,6 +354,8 @@ impl SpecLinter {
                                     behavior.id
                                 ),
                                 line: None,
+                                location: None,
+                                suggestion: None,
                             })
                         } else {
                             None
@@ -360,8 +383,84 @@ impl SpecLinter {
         );
     }
 
+    /// Clarity sub-check: behaviors and criteria stick to glossary terms.
+    fn check_glossary(
+        rules: &LintRules,
+        config: &EffectiveLintConfig,
+        spec: &Spec,
+        report: &mut LintReport,
+    ) {
+        if config.is_disabled("SPEC-012") {
+            return;
+        }
+        let rule = rules.rules.iter().find(|r| r.id == "SPEC-012");
+        let synonyms = rule.and_then(|r| r.synonyms.clone()).unwrap_or_default();
+        let Some(usage) = GlossaryUsage::analyze(&spec.specification, &synonyms) else {
+            return;
+        };
+
+        let rule_name = rule.map_or_else(
+            || "glossary-term-consistency".to_string(),
+            |r| r.name.clone(),
+        );
+        let severity = rule.map_or_else(|| "warning".to_string(), |r| r.severity.clone());
+        let synonym_messages = usage.synonyms.iter().map(|found| {
+            format!(
+                "Use glossary term '{}' instead of '{}' ({} uses in {})",
+                found.term,
+                found.synonym,
+                found.count,
+                found.found_in.join(", ")
+            )
+        });
+        let undefined_messages = usage.undefined.iter().map(|found| {
+            format!(
+                "Term '{}' is not defined in the glossary ({} uses in {})",
+                found.term,
+                found.count,
+                found.found_in.join(", ")
+            )
+        });
+        let issues = synonym_messages
+            .chain(undefined_messages)
+            .map(|message| LintIssue {
+                rule_id: "SPEC-012".to_string(),
+                rule_name: rule_name.clone(),
+                severity: severity.clone(),
+                message,
+                line: None,
+                location: Some("context.glossary".to_string()),
+                suggestion: None,
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(clarity) = report.categories.get_mut("Clarity") {
+            clarity.details = format!(
+                "{}; {} inconsistent glossary terms",
+                clarity.details,
+                issues.len()
+            );
+            if !issues.is_empty() {
+                clarity.score = clarity.score.saturating_sub(10);
+            }
+        }
+        for issue in issues {
+            if issue.severity == "error" {
+                report.errors.push(issue);
+            } else {
+                report.warnings.push(issue);
+            }
+        }
+        report.glossary = Some(usage);
+    }
+
     #[allow(clippy::too_many_lines)]
-    fn check_security(rules: &LintRules, spec: &Spec, report: &mut LintReport) {
+    fn check_security(
+        rules: &LintRules,
+        config: &EffectiveLintConfig,
+        spec: &Spec,
+        report: &mut LintReport,
+    ) {
         let spec_020_rule = rules.rules.iter().find(|r| r.id == "SPEC-020");
         let spec_021_rule = rules.rules.iter().find(|r| r.id == "SPEC-021");
         let spec_040_rule = rules.rules.iter().find(|r| r.id == "SPEC-040");
@@ -395,7 +494,7 @@ impl SpecLinter {
                         })
                     });
 
-                    if !has_enumeration_check {
+                    if !has_enumeration_check && !config.is_disabled("SPEC-020") {
                         let issue = LintIssue {
                             rule_id: "SPEC-020".to_string(),
                             rule_name: "enumeration-prevention".to_string(),
@@ -405,6 +504,8 @@ impl SpecLinter {
                                 endpoint.path
                             ),
                             line: None,
+                            location: None,
+                            suggestion: None,
                         };
                         if issue.severity == "error" {
                             report.errors.push(issue);
@@ -418,7 +519,7 @@ impl SpecLinter {
                 let has_write_endpoints = endpoints
                     .iter()
                     .any(|e| write_methods.contains(&e.method.as_str()));
-                if has_write_endpoints {
+                if has_write_endpoints && !config.is_disabled("SPEC-021") {
                     let has_rate_limit = spec.specification.behaviors.iter().any(|b| {
                         b.then.iter().any(|t| {
                             t.to_lowercase().contains("rate")
@@ -444,6 +545,8 @@ impl SpecLinter {
                                 "Write endpoints found but no rate limiting behavior specified"
                                     .to_string(),
                             line: None,
+                            location: None,
+                            suggestion: None,
                         };
                         if issue.severity == "error" {
                             report.errors.push(issue);
@@ -461,7 +564,7 @@ impl SpecLinter {
                 || b.then.iter().any(|t| t.to_lowercase().contains("canvas"))
         });
 
-        if has_canvas_behavior {
+        if has_canvas_behavior && !config.is_disabled("SPEC-040") {
             let has_visual_feedback = spec.specification.behaviors.iter().any(|b| {
                 b.then.iter().any(|t| {
                     t.to_lowercase().contains("display")
@@ -481,6 +584,8 @@ impl SpecLinter {
                     message: "Canvas behaviors should specify visual feedback for user experience"
                         .to_string(),
                     line: None,
+                    location: None,
+                    suggestion: None,
                 };
                 if issue.severity == "error" {
                     report.errors.push(issue);
@@ -499,7 +604,12 @@ impl SpecLinter {
         );
     }
 
-    fn check_testability(rules: &LintRules, spec: &Spec, report: &mut LintReport) {
+    fn check_testability(
+        rules: &LintRules,
+        config: &EffectiveLintConfig,
+        spec: &Spec,
+        report: &mut LintReport,
+    ) {
         let spec_030_rule = rules.rules.iter().find(|r| r.id == "SPEC-030");
         let severity = spec_030_rule.map_or_else(|| "error".to_string(), |r| r.severity.clone());
 
@@ -517,6 +627,11 @@ impl SpecLinter {
             })
             .count();
 
+        let non_observable_count = if config.is_disabled("SPEC-030") {
+            0
+        } else {
+            non_observable_count
+        };
         let score = if non_observable_count > 0 { 90 } else { 100 };
         if non_observable_count > 0 {
             let issue = LintIssue {
@@ -527,6 +642,8 @@ impl SpecLinter {
                     "{non_observable_count} behaviors may not have observable outcomes"
                 ),
                 line: None,
+                location: None,
+                suggestion: None,
             };
             if issue.severity == "error" {
                 report.errors.push(issue);
@@ -544,7 +661,12 @@ impl SpecLinter {
         );
     }
 
-    fn check_data_model(rules: &LintRules, spec: &Spec, report: &mut LintReport) {
+    fn check_data_model(
+        rules: &LintRules,
+        config: &EffectiveLintConfig,
+        spec: &Spec,
+        report: &mut LintReport,
+    ) {
         let spec_002_rule = rules.rules.iter().find(|r| r.id == "SPEC-002");
         let severity = spec_002_rule.map_or_else(|| "error".to_string(), |r| r.severity.clone());
 
@@ -552,7 +674,10 @@ impl SpecLinter {
 
         if let Some(data_model) = &spec.specification.data_model {
             if let Some(transitions) = &data_model.state_transitions {
-                if !transitions.is_empty() && spec.specification.context.invariants.is_empty() {
+                if !transitions.is_empty()
+                    && spec.specification.context.invariants.is_empty()
+                    && !config.is_disabled("SPEC-002")
+                {
                     score = 88;
                     let issue = LintIssue {
                         rule_id: "SPEC-002".to_string(),
@@ -560,6 +685,8 @@ impl SpecLinter {
                         severity: severity.clone(),
                         message: "State transitions found but no invariants defined".to_string(),
                         line: None,
+                        location: None,
+                        suggestion: None,
                     };
                     if issue.severity == "error" {
                         report.errors.push(issue);
@@ -578,4 +705,272 @@ impl SpecLinter {
             },
         );
     }
+
+    /// Links between behaviors, edge cases and acceptance criteria. Each
+    /// enabled check counts towards the `Traceability` score.
+    fn check_traceability(
+        rules: &LintRules,
+        config: &EffectiveLintConfig,
+        spec: &Spec,
+        report: &mut LintReport,
+    ) {
+        let specification = &spec.specification;
+        let rule_for = |rule_id: &str, name: &str, default_severity: &str| {
+            rules.rules.iter().find(|r| r.id == rule_id).map_or_else(
+                || (name.to_string(), default_severity.to_string()),
+                |r| (r.name.clone(), r.severity.clone()),
+            )
+        };
+        let mut checks: Vec<(&str, Vec<(String, String)>)> = Vec::new();
+
+        // SPEC-004 keeps its opt-in behaviour: it only runs when listed.
+        if rules.rules.iter().any(|r| r.id == "SPEC-004") {
+            let uncovered = specification
+                .behaviors
+                .iter()
+                .filter(|behavior| {
+                    !specification.acceptance_criteria.iter().any(|ac| {
+                        ac.behavior_ref
+                            .as_ref()
+                            .is_some_and(|ref_id| ref_id == &behavior.id)
+                    })
+                })
+                .map(|behavior| {
+                    (
+                        format!("Behavior '{}' has no acceptance criterion", behavior.id),
+                        format!("behaviors.{}", behavior.id),
+                    )
+                })
+                .collect();
+            checks.push(("SPEC-004", uncovered));
+        }
+
+        if !config.is_disabled("SPEC-005") {
+            let dangling = specification
+                .acceptance_criteria
+                .iter()
+                .filter_map(|ac| {
+                    ac.behavior_ref
+                        .as_ref()
+                        .filter(|ref_id| {
+                            !specification
+                                .behaviors
+                                .iter()
+                                .any(|behavior| &behavior.id == *ref_id)
+                        })
+                        .map(|ref_id| {
+                            (
+                                format!(
+                                    "Acceptance criterion '{}' references unknown behavior '{ref_id}'",
+                                    ac.id
+                                ),
+                                format!("acceptance_criteria.{}.behavior_ref", ac.id),
+                            )
+                        })
+                })
+                .collect();
+            checks.push(("SPEC-005", dangling));
+        }
+
+        if !config.is_disabled("SPEC-006") {
+            let mut seen: Vec<&str> = Vec::new();
+            let duplicates = specification
+                .behaviors
+                .iter()
+                .flat_map(|behavior| {
+                    behavior
+                        .edge_cases
+                        .iter()
+                        .flatten()
+                        .map(move |edge_case| (behavior, edge_case))
+                })
+                .filter_map(|(behavior, edge_case)| {
+                    if seen.contains(&edge_case.id.as_str()) {
+                        Some((
+                            format!(
+                                "Edge case id '{}' in behavior '{}' is already used",
+                                edge_case.id, behavior.id
+                            ),
+                            format!("behaviors.{}.edge_cases.{}", behavior.id, edge_case.id),
+                        ))
+                    } else {
+                        seen.push(&edge_case.id);
+                        None
+                    }
+                })
+                .collect();
+            checks.push(("SPEC-006", duplicates));
+        }
+
+        let total = checks.len();
+        let passed = checks
+            .iter()
+            .filter(|(_, issues)| issues.is_empty())
+            .count();
+        for (rule_id, issues) in checks {
+            let (rule_name, severity) = match rule_id {
+                "SPEC-004" => rule_for(
+                    rule_id,
+                    "every-behavior-has-acceptance-criterion",
+                    "warning",
+                ),
+                "SPEC-005" => rule_for(rule_id, "acceptance-criteria-reference-behaviors", "error"),
+                _ => rule_for(rule_id, "edge-case-ids-are-unique", "error"),
+            };
+            for (message, location) in issues {
+                let issue = LintIssue {
+                    rule_id: rule_id.to_string(),
+                    rule_name: rule_name.clone(),
+                    severity: severity.clone(),
+                    message,
+                    line: None,
+                    location: Some(location),
+                    suggestion: None,
+                };
+                if issue.severity == "error" {
+                    report.errors.push(issue);
+                } else {
+                    report.warnings.push(issue);
+                }
+            }
+        }
+
+        let score = passed * 100 / total.max(1);
+        report.categories.insert(
+            "Traceability".to_string(),
+            CategoryScore {
+                score: score.try_into().map_or(100, |score| score),
+                details: format!("{passed}/{total} traceability checks passed"),
+            },
+        );
+    }
+
+    /// `api_contract` against the behaviors: unexercised endpoints,
+    /// undeclared paths and events nothing emits or handles.
+    fn check_contract(
+        rules: &LintRules,
+        config: &EffectiveLintConfig,
+        spec: &Spec,
+        report: &mut LintReport,
+    ) {
+        let Some(findings) = contract::analyze(&spec.specification) else {
+            return;
+        };
+        let checks = findings
+            .into_iter()
+            .filter(|(rule_id, _)| !config.is_disabled(rule_id))
+            .collect::<Vec<_>>();
+        let total = checks.len();
+        let passed = checks.iter().filter(|(_, found)| found.is_empty()).count();
+
+        for (rule_id, found) in checks {
+            let (default_name, default_severity) = match rule_id {
+                "SPEC-013" => ("endpoints-are-exercised-by-behaviors", "warning"),
+                "SPEC-014" => ("behavior-paths-are-declared", "warning"),
+                _ => ("events-have-behaviors", "warning"),
+            };
+            let rule = rules.rules.iter().find(|r| r.id == rule_id);
+            let rule_name = rule.map_or_else(|| default_name.to_string(), |r| r.name.clone());
+            let severity =
+                rule.map_or_else(|| default_severity.to_string(), |r| r.severity.clone());
+            for finding in found {
+                let issue = LintIssue {
+                    rule_id: finding.rule_id.to_string(),
+                    rule_name: rule_name.clone(),
+                    severity: severity.clone(),
+                    message: finding.message,
+                    line: None,
+                    location: Some(finding.location),
+                    suggestion: Some(finding.suggestion),
+                };
+                if issue.severity == "error" {
+                    report.errors.push(issue);
+                } else {
+                    report.warnings.push(issue);
+                }
+            }
+        }
+
+        if let Some(score) = (passed * 100).checked_div(total) {
+            report.categories.insert(
+                "Contract".to_string(),
+                CategoryScore {
+                    score: score.try_into().map_or(100, |score| score),
+                    details: format!("{passed}/{total} contract checks passed"),
+                },
+            );
+        }
+    }
+
+    /// Run rules that carry a `check`. Each category scores the share of its
+    /// declarative rules without violations; a category shared with a
+    /// built-in check keeps the lower score.
+    fn check_declarative(
+        rules: &LintRules,
+        spec: &Spec,
+        report: &mut LintReport,
+    ) -> Result<(), LintError> {
+        let declarative = rules
+            .rules
+            .iter()
+            .filter_map(|rule| rule.check.as_ref().map(|check| (rule, check)))
+            .collect::<Vec<_>>();
+        if declarative.is_empty() {
+            return Ok(());
+        }
+
+        let specification = serde_json::to_value(&spec.specification)?;
+        let mut tallies: Vec<(String, u32, u32)> = Vec::new();
+
+        for (rule, check) in declarative {
+            let violations = check.violations(&specification);
+            let category = rule
+                .category
+                .as_deref()
+                .map(str::trim)
+                .filter(|category| !category.is_empty())
+                .unwrap_or(DEFAULT_DECLARATIVE_CATEGORY);
+            let passed = u32::from(violations.is_empty());
+            match tallies.iter_mut().find(|(name, _, _)| name == category) {
+                Some((_, passed_count, total)) => {
+                    *passed_count += passed;
+                    *total += 1;
+                }
+                None => tallies.push((category.to_string(), passed, 1)),
+            }
+
+            let severity = rule.severity.trim().to_string();
+            for message in violations {
+                let issue = LintIssue {
+                    rule_id: rule.id.clone(),
+                    rule_name: rule.name.clone(),
+                    severity: severity.clone(),
+                    message,
+                    line: None,
+                    location: None,
+                    suggestion: None,
+                };
+                if issue.severity == "error" {
+                    report.errors.push(issue);
+                } else {
+                    report.warnings.push(issue);
+                }
+            }
+        }
+
+        for (category, passed, total) in tallies {
+            let score = passed * 100 / total.max(1);
+            let details = format!("{passed}/{total} declarative rules passed");
+            report
+                .categories
+                .entry(category)
+                .and_modify(|existing| {
+                    existing.score = existing.score.min(score);
+                    existing.details = format!("{}; {details}", existing.details);
+                })
+                .or_insert(CategoryScore { score, details });
+        }
+
+        Ok(())
+    }
 }
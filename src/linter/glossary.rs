@@ -0,0 +1,208 @@
+//! Glossary consistency: domain terms in behaviors and acceptance criteria
+//! should be the ones `context.glossary` defines.
+//!
+//! Two kinds of drift are reported: a known synonym used in place of a
+//! glossary term ("client" where the glossary defines "customer"), and a
+//! term marked up as `domain term` in backticks that the glossary lacks.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::model::Specification;
+
+/// Synonyms checked when the rule does not list its own.
+const DEFAULT_SYNONYMS: [(&str, &[&str]); 6] = [
+    ("customer", &["client", "buyer", "shopper"]),
+    ("user", &["member", "account holder"]),
+    ("order", &["purchase"]),
+    ("workflow", &["pipeline"]),
+    ("node", &["vertex"]),
+    ("connection", &["link"]),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SynonymUse {
+    pub synonym: String,
+    /// The glossary term to use instead.
+    pub term: String,
+    pub count: usize,
+    /// Behavior ids and acceptance criterion ids it appears in.
+    pub found_in: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UndefinedTerm {
+    pub term: String,
+    pub count: usize,
+    pub found_in: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryUsage {
+    /// Occurrences of every glossary term, including unused ones.
+    pub term_counts: BTreeMap<String, usize>,
+    pub synonyms: Vec<SynonymUse>,
+    pub undefined: Vec<UndefinedTerm>,
+}
+
+impl GlossaryUsage {
+    /// Measure glossary usage, or `None` when the spec has no glossary.
+    /// `synonyms` maps a glossary term to words that should not replace it;
+    /// when empty, a small built-in list is used.
+    #[must_use]
+    pub fn analyze(
+        specification: &Specification,
+        synonyms: &HashMap<String, Vec<String>>,
+    ) -> Option<Self> {
+        let glossary = specification
+            .context
+            .glossary
+            .as_ref()
+            .filter(|glossary| !glossary.is_empty())?;
+        let terms = glossary
+            .keys()
+            .map(|term| normalize(term))
+            .collect::<Vec<_>>();
+        let texts = spec_texts(specification);
+
+        let term_counts = terms
+            .iter()
+            .map(|term| {
+                let count = texts.iter().map(|(_, text)| count_word(text, term)).sum();
+                (term.clone(), count)
+            })
+            .collect();
+
+        let synonym_pairs = if synonyms.is_empty() {
+            DEFAULT_SYNONYMS
+                .iter()
+                .flat_map(|(term, words)| {
+                    words
+                        .iter()
+                        .map(move |word| ((*term).to_string(), (*word).to_string()))
+                })
+                .collect::<Vec<_>>()
+        } else {
+            synonyms
+                .iter()
+                .flat_map(|(term, words)| {
+                    words.iter().map(move |word| (term.clone(), word.clone()))
+                })
+                .collect()
+        };
+        let mut synonyms = synonym_pairs
+            .into_iter()
+            .map(|(term, synonym)| (normalize(&term), normalize(&synonym)))
+            .filter(|(term, synonym)| terms.contains(term) && !terms.contains(synonym))
+            .filter_map(|(term, synonym)| {
+                let (count, found_in) = occurrences(&texts, &synonym);
+                (count > 0).then_some(SynonymUse {
+                    synonym,
+                    term,
+                    count,
+                    found_in,
+                })
+            })
+            .collect::<Vec<_>>();
+        synonyms.sort_by(|a, b| a.synonym.cmp(&b.synonym));
+
+        let mut undefined: BTreeMap<String, UndefinedTerm> = BTreeMap::new();
+        for (source, text) in &texts {
+            for term in backticked(text).filter(|term| !terms.contains(term)) {
+                let entry = undefined.entry(term.clone()).or_insert(UndefinedTerm {
+                    term,
+                    count: 0,
+                    found_in: Vec::new(),
+                });
+                entry.count += 1;
+                if !entry.found_in.contains(source) {
+                    entry.found_in.push(source.clone());
+                }
+            }
+        }
+
+        Some(Self {
+            term_counts,
+            synonyms,
+            undefined: undefined.into_values().collect(),
+        })
+    }
+
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.synonyms.is_empty() && self.undefined.is_empty()
+    }
+}
+
+/// `(behavior or criterion id, lowercase text)` for every checked sentence.
+fn spec_texts(specification: &Specification) -> Vec<(String, String)> {
+    let behaviors = specification.behaviors.iter().flat_map(|behavior| {
+        let edge_cases =
+            behavior.edge_cases.iter().flatten().flat_map(|edge_case| {
+                std::iter::once(&edge_case.r#when).chain(edge_case.then.iter())
+            });
+        std::iter::once(&behavior.description)
+            .chain(behavior.given.iter().flatten())
+            .chain(behavior.r#when.iter())
+            .chain(behavior.then.iter())
+            .chain(edge_cases)
+            .map(|text| (behavior.id.clone(), text.to_lowercase()))
+    });
+    let criteria = specification
+        .acceptance_criteria
+        .iter()
+        .map(|ac| (ac.id.clone(), ac.criterion.to_lowercase()));
+    behaviors.chain(criteria).collect()
+}
+
+fn occurrences(texts: &[(String, String)], word: &str) -> (usize, Vec<String>) {
+    texts
+        .iter()
+        .fold((0, Vec::new()), |(count, mut found_in), (source, text)| {
+            let hits = count_word(text, word);
+            if hits > 0 && !found_in.contains(source) {
+                found_in.push(source.clone());
+            }
+            (count + hits, found_in)
+        })
+}
+
+/// Glossary keys are often snake_case; prose uses spaces.
+fn normalize(term: &str) -> String {
+    term.trim().to_lowercase().replace(['_', '-'], " ")
+}
+
+/// Whole-word matches of `word` in `text`, allowing a plural `s`/`es`.
+fn count_word(text: &str, word: &str) -> usize {
+    if word.is_empty() {
+        return 0;
+    }
+    let text = normalize(text);
+    let is_word_char = |c: char| c.is_alphanumeric();
+    text.match_indices(word)
+        .filter(|(start, _)| {
+            text[..*start]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !is_word_char(c))
+        })
+        .filter(|(start, _)| {
+            let rest = &text[start + word.len()..];
+            let rest = rest
+                .strip_prefix("es")
+                .or_else(|| rest.strip_prefix('s'))
+                .filter(|after| after.chars().next().is_none_or(|c| !is_word_char(c)))
+                .unwrap_or(rest);
+            rest.chars().next().is_none_or(|c| !is_word_char(c))
+        })
+        .count()
+}
+
+fn backticked(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split('`')
+        .skip(1)
+        .step_by(2)
+        .map(normalize)
+        .filter(|term| !term.is_empty())
+}
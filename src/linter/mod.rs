@@ -1,8 +1,20 @@
+mod config;
+mod contract;
+mod declarative;
 mod engine;
+mod glossary;
 mod model;
+mod project;
 
 #[cfg(test)]
 mod tests;
 
+pub use config::{EffectiveLintConfig, LintConfig, SpecLintOverrides, LINT_CONFIG_FILES};
+pub use declarative::{Predicate, RuleCheck, TermsPredicate, ValuesPredicate};
 pub use engine::SpecLinter;
-pub use model::{CategoryScore, LintError, LintIssue, LintReport, LintRule, LintRules, Spec};
+pub use glossary::{GlossaryUsage, SynonymUse, UndefinedTerm};
+pub use model::{
+    AcceptanceCriterion, Behavior, CategoryScore, LintError, LintIssue, LintReport, LintRule,
+    LintRules, Spec, SpecContext, SpecIdentity, SpecIntent, Specification, SystemDependency,
+};
+pub use project::{ProjectLintReport, ScoreDistribution, SpecLintEntry, SpecLintFailure};
@@ -1,3 +1,7 @@
+use super::config::EffectiveLintConfig;
+use super::declarative::RuleCheck;
+use super::glossary::GlossaryUsage;
+use crate::flow_extender::archetype::SpecArchetype;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
@@ -16,6 +20,10 @@ pub enum LintError {
     InvalidSeverity { rule_id: String, severity: String },
     #[error("Missing required field '{field}' for rule {rule_id}")]
     MissingRequiredField { rule_id: String, field: String },
+    #[error("Rule {rule_id} is built in and cannot declare a check")]
+    BuiltinRuleCheck { rule_id: String },
+    #[error(transparent)]
+    Discovery(#[from] crate::coverage::CoverageError),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -114,6 +122,9 @@ pub struct AcceptanceCriterion {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Specification {
     pub identity: SpecIdentity,
+    /// Drives preset recommendations and the scaffold for new workflows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archetype: Option<SpecArchetype>,
     pub intent: SpecIntent,
     pub context: SpecContext,
     pub behaviors: Vec<Behavior>,
@@ -138,6 +149,16 @@ pub struct LintRule {
     pub description: String,
     #[serde(rename = "banned_phrases")]
     pub banned_phrases: Option<Vec<String>>,
+    /// Glossary term to words that should not stand in for it (SPEC-012).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synonyms: Option<HashMap<String, Vec<String>>>,
+    /// Report category for declarative rules; defaults to `Custom`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Declarative check. Required for ids the linter has no built-in or
+    /// default check for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub check: Option<RuleCheck>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -152,6 +173,12 @@ pub struct LintIssue {
     pub severity: String,
     pub message: String,
     pub line: Option<usize>,
+    /// Where in the spec the issue is, e.g. `behaviors.<id>.edge_cases.<id>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Spec text that would resolve the issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -164,6 +191,12 @@ pub struct LintReport {
     pub errors: Vec<LintIssue>,
     pub warnings: Vec<LintIssue>,
     pub suggestions: Vec<String>,
+    /// Glossary term usage, when the spec defines a glossary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub glossary: Option<GlossaryUsage>,
+    /// Configuration the spec was linted with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_config: Option<EffectiveLintConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -184,6 +217,8 @@ impl LintReport {
             errors: Vec::new(),
             warnings: Vec::new(),
             suggestions: Vec::new(),
+            glossary: None,
+            effective_config: None,
         }
     }
 
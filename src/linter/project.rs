@@ -0,0 +1,270 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use super::engine::SpecLinter;
+use super::model::{LintError, LintIssue, LintReport, Spec};
+use crate::coverage::collect_yaml_files;
+
+/// Score bands used by [`ScoreDistribution::buckets`], highest first.
+const SCORE_BANDS: [(&str, u32); 4] = [("90-100", 90), ("80-89", 80), ("60-79", 60), ("0-59", 0)];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecLintEntry {
+    pub path: PathBuf,
+    pub supersedes: Option<String>,
+    pub report: LintReport,
+}
+
+/// A file under the linted directory that could not be read as a spec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecLintFailure {
+    pub path: PathBuf,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreDistribution {
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    /// Spec count per score band, e.g. `"80-89"`.
+    pub buckets: BTreeMap<String, usize>,
+}
+
+impl ScoreDistribution {
+    fn from_scores(scores: &[u32]) -> Self {
+        let mut buckets = SCORE_BANDS
+            .iter()
+            .map(|(band, _)| ((*band).to_string(), 0))
+            .collect::<BTreeMap<_, _>>();
+        for score in scores {
+            if let Some((band, _)) = SCORE_BANDS.iter().find(|(_, floor)| score >= floor) {
+                *buckets.entry((*band).to_string()).or_default() += 1;
+            }
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let mean = match scores.len() {
+            0 => 0.0,
+            count => f64::from(scores.iter().sum::<u32>()) / count as f64,
+        };
+
+        Self {
+            min: scores.iter().copied().min().unwrap_or(0),
+            max: scores.iter().copied().max().unwrap_or(0),
+            mean,
+            buckets,
+        }
+    }
+}
+
+/// Lint results for every spec under a directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectLintReport {
+    /// Sorted by path.
+    pub specs: Vec<SpecLintEntry>,
+    pub failures: Vec<SpecLintFailure>,
+    pub score_distribution: ScoreDistribution,
+    /// Issues that only show up when specs are looked at together.
+    pub cross_spec_issues: Vec<LintIssue>,
+    pub passed: bool,
+}
+
+impl ProjectLintReport {
+    fn new(specs: Vec<SpecLintEntry>, failures: Vec<SpecLintFailure>) -> Self {
+        let scores = specs
+            .iter()
+            .map(|entry| entry.report.overall_score)
+            .collect::<Vec<_>>();
+        let cross_spec_issues = cross_spec_issues(&specs);
+        let passed = failures.is_empty()
+            && specs.iter().all(|entry| entry.report.passed)
+            && cross_spec_issues
+                .iter()
+                .all(|issue| issue.severity != "error");
+
+        Self {
+            score_distribution: ScoreDistribution::from_scores(&scores),
+            specs,
+            failures,
+            cross_spec_issues,
+            passed,
+        }
+    }
+}
+
+impl SpecLinter {
+    /// Lint every spec below `specs_dir`, discovered the same way coverage
+    /// discovers them. Files that do not parse are recorded as failures
+    /// instead of aborting the run.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LintError` if the directory cannot be walked.
+    pub fn lint_dir(&self, specs_dir: &Path) -> Result<ProjectLintReport, LintError> {
+        let mut paths = collect_yaml_files(specs_dir)?;
+        paths.sort();
+
+        let workers = thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        let chunk_size = paths.len().div_ceil(workers).max(1);
+        let outcomes = thread::scope(|scope| {
+            let handles = paths
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let handle = scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|path| {
+                                let outcome = self.lint_file(path).map_err(|e| e.to_string());
+                                (path.clone(), outcome)
+                            })
+                            .collect::<Vec<_>>()
+                    });
+                    (chunk, handle)
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .flat_map(|(chunk, handle)| {
+                    // A panicking worker loses its whole chunk; report each of
+                    // those specs as failed rather than leaving them out.
+                    handle.join().unwrap_or_else(|_| {
+                        chunk
+                            .iter()
+                            .map(|path| {
+                                (
+                                    path.clone(),
+                                    Err("Lint worker panicked before finishing this spec"
+                                        .to_string()),
+                                )
+                            })
+                            .collect()
+                    })
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let (specs, failures) = outcomes.into_iter().fold(
+            (Vec::new(), Vec::new()),
+            |(mut specs, mut failures), (path, outcome)| {
+                match outcome {
+                    Ok((supersedes, report)) => specs.push(SpecLintEntry {
+                        path,
+                        supersedes,
+                        report,
+                    }),
+                    Err(error) => failures.push(SpecLintFailure { path, error }),
+                }
+                (specs, failures)
+            },
+        );
+
+        Ok(ProjectLintReport::new(specs, failures))
+    }
+
+    fn lint_file(&self, path: &Path) -> Result<(Option<String>, LintReport), LintError> {
+        let spec: Spec = Self::read_spec(path)?;
+        let report = self.lint_spec(&spec)?;
+        Ok((spec.specification.identity.supersedes, report))
+    }
+}
+
+fn cross_spec_issue(rule_id: &str, rule_name: &str, severity: &str, message: String) -> LintIssue {
+    LintIssue {
+        rule_id: rule_id.to_string(),
+        rule_name: rule_name.to_string(),
+        severity: severity.to_string(),
+        message,
+        line: None,
+        location: None,
+        suggestion: None,
+    }
+}
+
+fn cross_spec_issues(specs: &[SpecLintEntry]) -> Vec<LintIssue> {
+    let mut by_id: BTreeMap<&str, Vec<&SpecLintEntry>> = BTreeMap::new();
+    for entry in specs {
+        by_id.entry(&entry.report.spec_id).or_default().push(entry);
+    }
+
+    let duplicates =
+        by_id
+            .iter()
+            .filter(|(_, entries)| entries.len() > 1)
+            .map(|(spec_id, entries)| {
+                let paths = entries
+                    .iter()
+                    .map(|entry| entry.path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                cross_spec_issue(
+                    "PROJ-001",
+                    "unique-spec-ids",
+                    "error",
+                    format!("Spec id '{spec_id}' is declared by {paths}"),
+                )
+            });
+
+    let supersedes = specs
+        .iter()
+        .filter_map(|entry| {
+            entry
+                .supersedes
+                .as_deref()
+                .map(str::trim)
+                .filter(|target| !target.is_empty())
+                .map(|target| (entry.report.spec_id.as_str(), target))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let dangling = specs.iter().filter_map(|entry| {
+        let spec_id = entry.report.spec_id.as_str();
+        entry
+            .supersedes
+            .as_deref()
+            .map(str::trim)
+            .filter(|target| !target.is_empty() && !by_id.contains_key(target))
+            .map(|target| {
+                cross_spec_issue(
+                    "PROJ-002",
+                    "supersedes-resolves",
+                    "warning",
+                    format!(
+                        "Spec '{spec_id}' ({}) supersedes unknown spec '{target}'",
+                        entry.path.display()
+                    ),
+                )
+            })
+    });
+
+    let cycles = by_id.keys().filter_map(|spec_id| {
+        let mut chain = vec![*spec_id];
+        let mut current = *spec_id;
+        while let Some(next) = supersedes.get(current).copied() {
+            if next == *spec_id {
+                // Report each cycle once, from its smallest id.
+                return chain.iter().all(|member| member >= spec_id).then(|| {
+                    cross_spec_issue(
+                        "PROJ-003",
+                        "supersedes-is-acyclic",
+                        "error",
+                        format!(
+                            "Supersedes chain loops: {} -> {spec_id}",
+                            chain.join(" -> ")
+                        ),
+                    )
+                });
+            }
+            if chain.contains(&next) {
+                return None;
+            }
+            chain.push(next);
+            current = next;
+        }
+        None
+    });
+
+    duplicates.chain(dangling).chain(cycles).collect()
+}
@@ -504,10 +504,11 @@ fn given_api_endpoint_without_auth_when_linting_then_spec_003_error_is_reported(
     let linter = SpecLinter::new(rules_file.path())?;
     let report = linter.lint(spec_file.path())?;
 
-    assert!(report
-        .errors
-        .iter()
-        .any(|issue| issue.rule_id == "SPEC-003"));
+    assert!(report.errors.iter().any(|issue| issue.rule_id == "SPEC-003"
+        && issue
+            .message
+            .contains("missing authentication specification")));
+    assert!(report.categories["Completeness"].score < 100);
     Ok(())
 }
 
@@ -650,6 +651,63 @@ fn given_error_without_concrete_http_status_when_linting_then_spec_011_error_is_
     Ok(())
 }
 
+#[test]
+fn given_status_wording_in_other_case_when_linting_then_spec_011_matches_it_exactly(
+) -> anyhow::Result<()> {
+    let rules_file = create_test_rules()?;
+    let mut spec_file = NamedTempFile::new()?;
+    writeln!(
+        spec_file,
+        "{}",
+        r#"
+specification:
+  identity:
+    id: spec-011-case
+    version: 1.0.0
+    status: draft
+    author: test
+    created: "2026-01-01T00:00:00Z"
+  intent:
+    problem_statement: "Test problem"
+    success_criteria:
+      - "Test criteria"
+  context:
+    system_dependencies: []
+    invariants: []
+  behaviors:
+    - id: lower-http
+      description: "Lowercase protocol name"
+      then:
+        - "Returns an http error"
+    - id: upper-status
+      description: "Capitalised status"
+      then:
+        - "Fails with Status unknown"
+    - id: upper-http
+      description: "Protocol name as written before"
+      then:
+        - "FAILS with HTTP 503"
+  acceptance_criteria:
+    - id: ac-01
+      criterion: "Test criterion"
+"#
+    )?;
+
+    let linter = SpecLinter::new(rules_file.path())?;
+    let report = linter.lint(spec_file.path())?;
+
+    let flagged = report
+        .errors
+        .iter()
+        .filter(|issue| issue.rule_id == "SPEC-011")
+        .map(|issue| issue.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(flagged.len(), 2, "{flagged:?}");
+    assert!(flagged[0].contains("'lower-http'"));
+    assert!(flagged[1].contains("'upper-status'"));
+    Ok(())
+}
+
 fn create_spec_with_write_endpoint_no_rate_limit() -> anyhow::Result<NamedTempFile> {
     let mut file = NamedTempFile::new()?;
     writeln!(
@@ -804,3 +862,572 @@ fn given_spec_030_with_error_severity_when_linting_then_issue_is_reported_as_err
         .any(|issue| issue.rule_id == "SPEC-030" && issue.severity == "error"));
     Ok(())
 }
+
+#[test]
+fn given_spec_with_archetype_when_parsing_then_archetype_is_declared() -> anyhow::Result<()> {
+    let yaml = r#"
+specification:
+  identity:
+    id: spec-test
+    version: 1.0.0
+    status: draft
+    author: test
+    created: "2026-01-01T00:00:00Z"
+  archetype: webhook-service
+  intent:
+    problem_statement: "Test problem"
+    success_criteria:
+      - "Test criteria"
+  context:
+    system_dependencies: []
+    invariants: []
+  behaviors: []
+  acceptance_criteria: []
+"#;
+
+    let spec: Spec = serde_yaml::from_str(yaml)?;
+
+    assert_eq!(
+        spec.specification.archetype,
+        Some(crate::flow_extender::archetype::SpecArchetype::WebhookService)
+    );
+    Ok(())
+}
+
+fn create_rules_with_declarative_checks() -> anyhow::Result<NamedTempFile> {
+    create_invalid_rules(
+        r#"
+rules:
+  - id: TEAM-001
+    name: write-endpoints-use-bearer-auth
+    severity: error
+    category: Security
+    description: "Write endpoints must use bearer auth"
+    check:
+      select: api_contract.endpoints
+      where:
+        one_of: { field: method, values: [post, put, patch, delete] }
+      assert:
+        one_of: { field: authentication, values: [bearer] }
+      message: "Endpoint {method} {path} uses {authentication} auth"
+  - id: TEAM-002
+    name: then-clauses-avoid-internal-state
+    severity: warning
+    description: "Then clauses describe outcomes, not internals"
+    check:
+      select: behaviors.then
+      assert:
+        contains_none: { terms: ["database row", "cache"] }
+      message: "Behavior '{parent.id}' asserts internal state: {value}"
+  - id: TEAM-003
+    name: behaviors-have-descriptions
+    severity: warning
+    description: "Behaviors need descriptions"
+    check:
+      select: behaviors
+      assert:
+        present: description
+      message: "Behavior '{id}' has no description"
+"#,
+    )
+}
+
+#[test]
+fn given_declarative_rules_when_linting_then_violations_use_rendered_messages() -> anyhow::Result<()>
+{
+    let rules_file = create_rules_with_declarative_checks()?;
+    let spec_file = create_invalid_rules(
+        r#"
+specification:
+  identity:
+    id: spec-custom
+    version: 1.0.0
+    status: draft
+    author: test
+    created: "2026-01-01T00:00:00Z"
+  intent:
+    problem_statement: "Test problem"
+    success_criteria:
+      - "Test criteria"
+  context:
+    system_dependencies: []
+    invariants: []
+  behaviors:
+    - id: create-user
+      description: "Create user"
+      then:
+        - "HTTP 201 response returned"
+        - "Database row is inserted"
+  api_contract:
+    endpoints:
+      - method: GET
+        path: /users
+        authentication: none
+      - method: POST
+        path: /users
+        authentication: api-key
+  acceptance_criteria:
+    - id: ac-01
+      behavior_ref: create-user
+      criterion: "Test criterion"
+"#,
+    )?;
+
+    let linter = SpecLinter::new(rules_file.path())?;
+    let report = linter.lint(spec_file.path())?;
+
+    let messages = |issues: &[LintIssue]| {
+        issues
+            .iter()
+            .filter(|issue| issue.rule_id.starts_with("TEAM-"))
+            .map(|issue| format!("{}: {}", issue.rule_id, issue.message))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        messages(&report.errors),
+        vec!["TEAM-001: Endpoint POST /users uses api-key auth"]
+    );
+    assert!(messages(&report.warnings).contains(
+        &"TEAM-002: Behavior 'create-user' asserts internal state: Database row is inserted"
+            .to_string()
+    ));
+    assert!(!report
+        .warnings
+        .iter()
+        .any(|issue| issue.rule_id == "TEAM-003"));
+    assert_eq!(report.categories["Security"].score, 0);
+    assert_eq!(report.categories["Custom"].score, 50);
+    Ok(())
+}
+
+#[test]
+fn given_builtin_rule_with_check_when_loading_rules_then_linter_returns_explicit_error(
+) -> anyhow::Result<()> {
+    let rules_file = create_invalid_rules(
+        r#"
+rules:
+  - id: SPEC-001
+    name: every-dependency-has-error-handling
+    severity: error
+    description: "Redefined built-in"
+    check:
+      select: context.system_dependencies
+      assert:
+        present: service
+      message: "Dependency has no service"
+"#,
+    )?;
+
+    let result = SpecLinter::new(rules_file.path());
+
+    assert!(matches!(result, Err(LintError::BuiltinRuleCheck { .. })));
+    Ok(())
+}
+
+#[test]
+fn given_default_declarative_rules_when_parsing_then_they_validate() -> anyhow::Result<()> {
+    let defaults = SpecLinter::default_rules()?;
+
+    SpecLinter::validate_rules(&defaults)?;
+    assert!(defaults.rules.iter().all(|rule| rule.check.is_some()));
+    Ok(())
+}
+
+#[test]
+fn given_spec_003_with_own_check_when_linting_then_it_replaces_the_default() -> anyhow::Result<()> {
+    let rules_file = create_invalid_rules(
+        r#"
+rules:
+  - id: SPEC-003
+    name: every-endpoint-specifies-auth
+    severity: warning
+    category: Security
+    description: "Team variant of the built-in"
+    check:
+      select: api_contract.endpoints
+      where:
+        one_of: { field: method, values: [POST] }
+      assert:
+        present: authentication
+      message: "Write endpoint {path} has no auth"
+"#,
+    )?;
+    let spec_file = create_spec_with_missing_auth_endpoint()?;
+
+    let linter = SpecLinter::new(rules_file.path())?;
+    let report = linter.lint(spec_file.path())?;
+
+    assert!(report
+        .errors
+        .iter()
+        .all(|issue| issue.rule_id != "SPEC-003"));
+    assert!(report.categories["Security"]
+        .details
+        .contains("1/1 declarative rules passed"));
+    Ok(())
+}
+
+fn project_spec(id: &str, supersedes: Option<&str>) -> String {
+    let supersedes =
+        supersedes.map_or_else(String::new, |target| format!("\n    supersedes: {target}"));
+    format!(
+        r#"
+specification:
+  identity:
+    id: {id}
+    version: 1.0.0
+    status: draft
+    author: test
+    created: "2026-01-01T00:00:00Z"{supersedes}
+  intent:
+    problem_statement: "Test problem"
+    success_criteria:
+      - "Test criteria"
+  context:
+    system_dependencies: []
+    invariants: []
+  behaviors:
+    - id: test-behavior
+      description: "Test"
+      then:
+        - "HTTP response returned"
+  acceptance_criteria:
+    - id: ac-01
+      behavior_ref: test-behavior
+      criterion: "Test criterion"
+"#
+    )
+}
+
+#[test]
+fn given_spec_directory_when_linting_dir_then_specs_are_aggregated_with_cross_spec_issues(
+) -> anyhow::Result<()> {
+    let rules_file = create_test_rules()?;
+    let dir = tempfile::tempdir()?;
+    std::fs::create_dir_all(dir.path().join("nested/deeper"))?;
+    std::fs::write(dir.path().join("a.yaml"), project_spec("orders", None))?;
+    std::fs::write(
+        dir.path().join("nested/b.yml"),
+        project_spec("orders", Some("legacy-orders")),
+    )?;
+    std::fs::write(
+        dir.path().join("nested/deeper/c.yaml"),
+        project_spec("refunds", Some("returns")),
+    )?;
+    std::fs::write(
+        dir.path().join("nested/deeper/d.yaml"),
+        project_spec("returns", Some("refunds")),
+    )?;
+    std::fs::write(dir.path().join("broken.yaml"), "specification: [")?;
+    std::fs::write(dir.path().join("notes.txt"), "not a spec")?;
+
+    let linter = SpecLinter::new(rules_file.path())?;
+    let report = linter.lint_dir(dir.path())?;
+
+    assert_eq!(report.specs.len(), 4);
+    assert_eq!(report.failures.len(), 1);
+    assert!(report.failures[0].path.ends_with("broken.yaml"));
+    assert_eq!(report.score_distribution.buckets.values().sum::<usize>(), 4);
+    assert!(!report.passed);
+
+    let rule_ids = report
+        .cross_spec_issues
+        .iter()
+        .map(|issue| issue.rule_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(rule_ids, vec!["PROJ-001", "PROJ-002", "PROJ-003"]);
+    assert!(report.cross_spec_issues[1]
+        .message
+        .contains("legacy-orders"));
+    assert!(report.cross_spec_issues[2]
+        .message
+        .contains("refunds -> returns -> refunds"));
+    Ok(())
+}
+
+#[test]
+fn given_lint_config_when_linting_then_overrides_apply_and_are_recorded() -> anyhow::Result<()> {
+    let rules_file = create_test_rules()?;
+    let spec_file = create_spec_with_ambiguous_language()?;
+    let dir = tempfile::tempdir()?;
+    let config_path = dir.path().join(".oya-lint.yaml");
+    std::fs::write(
+        &config_path,
+        r"
+severity:
+  SPEC-030: error
+allowed_phrases: [OBVIOUSLY]
+specs:
+  spec-clarity:
+    disabled: [SPEC-010]
+",
+    )?;
+    std::fs::create_dir_all(dir.path().join("specs/nested"))?;
+    let config = LintConfig::discover(&dir.path().join("specs/nested"))?
+        .ok_or_else(|| anyhow::anyhow!("config not discovered"))?;
+
+    let baseline = SpecLinter::new(rules_file.path())?.lint(spec_file.path())?;
+    let report = SpecLinter::new(rules_file.path())?
+        .with_config(config)?
+        .lint(spec_file.path())?;
+
+    assert!(baseline
+        .warnings
+        .iter()
+        .any(|issue| issue.rule_id == "SPEC-010"));
+    assert!(!report
+        .errors
+        .iter()
+        .chain(&report.warnings)
+        .any(|issue| issue.rule_id == "SPEC-010"));
+    assert!(report
+        .errors
+        .iter()
+        .any(|issue| issue.rule_id == "SPEC-030" && issue.severity == "error"));
+    assert!(!report
+        .warnings
+        .iter()
+        .any(|issue| issue.rule_id == "SPEC-030"));
+    let effective = report
+        .effective_config
+        .ok_or_else(|| anyhow::anyhow!("missing effective config"))?;
+    assert_eq!(effective.source.as_deref(), Some(config_path.as_path()));
+    assert_eq!(effective.disabled, vec!["SPEC-010".to_string()]);
+    assert_eq!(effective.allowed_phrases, vec!["OBVIOUSLY".to_string()]);
+    Ok(())
+}
+
+#[test]
+fn given_config_with_unknown_rule_when_applying_then_linter_returns_explicit_error(
+) -> anyhow::Result<()> {
+    let rules_file = create_test_rules()?;
+    let config: LintConfig = serde_yaml::from_str("disabled: [SPEC-999]")?;
+
+    let result = SpecLinter::new(rules_file.path())?.with_config(config);
+
+    assert!(matches!(result, Err(LintError::UnknownRuleId { .. })));
+    Ok(())
+}
+
+#[test]
+fn given_broken_cross_references_when_linting_then_traceability_issues_are_reported(
+) -> anyhow::Result<()> {
+    let rules_file = create_test_rules()?;
+    let spec_file = create_invalid_rules(
+        r#"
+specification:
+  identity:
+    id: spec-traceability
+    version: 1.0.0
+    status: draft
+    author: test
+    created: "2026-01-01T00:00:00Z"
+  intent:
+    problem_statement: "Test problem"
+    success_criteria:
+      - "Test criteria"
+  context:
+    system_dependencies: []
+    invariants: []
+  behaviors:
+    - id: place-order
+      description: "Place order"
+      then:
+        - "HTTP 201 response returned"
+      edge_cases:
+        - id: out-of-stock
+          when: "Item is out of stock"
+          then:
+            - "HTTP 409 response returned"
+    - id: cancel-order
+      description: "Cancel order"
+      then:
+        - "HTTP 200 response returned"
+      edge_cases:
+        - id: out-of-stock
+          when: "Item is out of stock"
+          then:
+            - "HTTP 409 response returned"
+  acceptance_criteria:
+    - id: ac-01
+      behavior_ref: place-order
+      criterion: "Order is placed"
+    - id: ac-02
+      behavior_ref: refund-order
+      criterion: "Order is refunded"
+"#,
+    )?;
+
+    let linter = SpecLinter::new(rules_file.path())?;
+    let report = linter.lint(spec_file.path())?;
+
+    let locations = |rule_id: &str| {
+        report
+            .errors
+            .iter()
+            .chain(&report.warnings)
+            .filter(|issue| issue.rule_id == rule_id)
+            .filter_map(|issue| issue.location.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(locations("SPEC-004"), vec!["behaviors.cancel-order"]);
+    assert_eq!(
+        locations("SPEC-005"),
+        vec!["acceptance_criteria.ac-02.behavior_ref"]
+    );
+    assert_eq!(
+        locations("SPEC-006"),
+        vec!["behaviors.cancel-order.edge_cases.out-of-stock"]
+    );
+    let traceability = &report.categories["Traceability"];
+    assert_eq!(traceability.score, 0);
+    assert_eq!(traceability.details, "0/3 traceability checks passed");
+    Ok(())
+}
+
+#[test]
+fn given_glossary_when_behaviors_use_synonyms_and_undefined_terms_then_clarity_sub_check_reports_them(
+) -> anyhow::Result<()> {
+    let rules_file = create_test_rules()?;
+    let spec_file = create_invalid_rules(
+        r#"
+specification:
+  identity:
+    id: spec-glossary
+    version: 1.0.0
+    status: draft
+    author: test
+    created: "2026-01-01T00:00:00Z"
+  intent:
+    problem_statement: "Test problem"
+    success_criteria:
+      - "Test criteria"
+  context:
+    system_dependencies: []
+    invariants: []
+    glossary:
+      customer: "A person who places orders"
+      order_line: "One product and quantity in an order"
+  behaviors:
+    - id: place-order
+      description: "A customer places an order with order lines"
+      then:
+        - "HTTP 201 response returned to the client"
+        - "The `loyalty tier` is shown in the response"
+  acceptance_criteria:
+    - id: ac-01
+      behavior_ref: place-order
+      criterion: "Clients and customers see the same HTTP response"
+"#,
+    )?;
+
+    let linter = SpecLinter::new(rules_file.path())?;
+    let report = linter.lint(spec_file.path())?;
+
+    let usage = report
+        .glossary
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("missing glossary usage"))?;
+    assert_eq!(usage.term_counts["customer"], 2);
+    assert_eq!(usage.term_counts["order line"], 1);
+    assert_eq!(usage.synonyms.len(), 1);
+    assert_eq!(usage.synonyms[0].synonym, "client");
+    assert_eq!(usage.synonyms[0].count, 2);
+    assert_eq!(usage.synonyms[0].found_in, vec!["place-order", "ac-01"]);
+    assert_eq!(usage.undefined.len(), 1);
+    assert_eq!(usage.undefined[0].term, "loyalty tier");
+
+    let glossary_warnings = report
+        .warnings
+        .iter()
+        .filter(|issue| issue.rule_id == "SPEC-012")
+        .count();
+    assert_eq!(glossary_warnings, 2);
+    assert_eq!(report.categories["Clarity"].score, 90);
+    Ok(())
+}
+
+#[test]
+fn given_contract_out_of_sync_with_behaviors_when_linting_then_contract_issues_carry_suggestions(
+) -> anyhow::Result<()> {
+    let rules_file = create_test_rules()?;
+    let spec_file = create_invalid_rules(
+        r#"
+specification:
+  identity:
+    id: spec-contract
+    version: 1.0.0
+    status: draft
+    author: test
+    created: "2026-01-01T00:00:00Z"
+  intent:
+    problem_statement: "Test problem"
+    success_criteria:
+      - "Test criteria"
+  context:
+    system_dependencies: []
+    invariants: []
+  behaviors:
+    - id: fetch-order
+      description: "Fetch an order"
+      when: "Client sends GET /orders/42"
+      then:
+        - "HTTP 200 response returned"
+        - "OrderViewed event is emitted"
+    - id: cancel-order
+      description: "Cancel an order"
+      when: "Client sends POST /orders/42/cancel"
+      then:
+        - "HTTP 202 response returned"
+  api_contract:
+    endpoints:
+      - method: GET
+        path: /orders/{id}
+        authentication: bearer
+      - method: DELETE
+        path: /orders/{id}
+        authentication: bearer
+    events_emitted:
+      - name: OrderViewed
+      - name: OrderCancelled
+  acceptance_criteria:
+    - id: ac-01
+      behavior_ref: fetch-order
+      criterion: "Order is returned"
+"#,
+    )?;
+
+    let linter = SpecLinter::new(rules_file.path())?;
+    let report = linter.lint(spec_file.path())?;
+
+    let contract_issues = report
+        .warnings
+        .iter()
+        .filter(|issue| ["SPEC-013", "SPEC-014", "SPEC-015"].contains(&issue.rule_id.as_str()))
+        .map(|issue| (issue.rule_id.as_str(), issue.location.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        contract_issues,
+        vec![
+            (
+                "SPEC-013",
+                Some("api_contract.endpoints.DELETE /orders/{id}")
+            ),
+            ("SPEC-014", Some("behaviors.cancel-order")),
+            (
+                "SPEC-015",
+                Some("api_contract.events_emitted.OrderCancelled")
+            ),
+        ]
+    );
+    let undeclared = report
+        .warnings
+        .iter()
+        .find(|issue| issue.rule_id == "SPEC-014")
+        .and_then(|issue| issue.suggestion.clone())
+        .unwrap_or_default();
+    assert!(undeclared.contains("method: POST"));
+    assert!(undeclared.contains("path: /orders/42/cancel"));
+    assert_eq!(report.categories["Contract"].score, 0);
+    Ok(())
+}
@@ -90,6 +90,8 @@ mod wasm_app {
         let _sidebar = hooks::provide_sidebar_context();
         let _restate = hooks::provide_restate_sync_context();
         let _toast = hooks::provide_toast_context();
+        let _analysis = hooks::provide_analysis_context(workflow);
+        let _collab = hooks::provide_collab_context(workflow, selection, canvas);
 
         let _global_mouseup_listener =
             use_hook(move || register_global_mouseup_listener(canvas, selection));
@@ -121,6 +123,10 @@ mod wasm_app {
 
 #[cfg(target_arch = "wasm32")]
 fn main() {
+    // The analysis worker loads this same build; there is no page to mount.
+    if web_sys::window().is_none() {
+        return;
+    }
     wasm_app::App();
     launch(wasm_app::App);
 }
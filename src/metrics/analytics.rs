@@ -0,0 +1,184 @@
+//! Per-scenario and per-behavior failure analysis over recorded scenario
+//! runs.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::health::{mean, percentage};
+use super::model::{MetricsStore, ScenarioOutcome};
+
+/// Runs of one scenario across every recorded validation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioStats {
+    pub spec_id: String,
+    pub scenario_id: String,
+    pub runs: usize,
+    pub failures: usize,
+    /// Share of consecutive runs whose outcome flipped, as a percentage.
+    /// `None` with fewer than two runs.
+    pub flip_rate: Option<f64>,
+    pub avg_duration_ms: f64,
+    pub max_duration_ms: u64,
+}
+
+/// How often scenarios asserting on one behavior failed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorFailures {
+    pub spec_id: String,
+    pub behavior_ref: String,
+    pub runs: usize,
+    pub failures: usize,
+    pub failure_rate: f64,
+    /// Failed assertion types, most frequent first.
+    pub assertion_categories: Vec<(String, usize)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FailureAnalytics {
+    /// Scenarios that both passed and failed, most intermittent first.
+    pub flaky_scenarios: Vec<ScenarioStats>,
+    /// Scenarios by average duration, slowest first.
+    pub slowest_scenarios: Vec<ScenarioStats>,
+    /// Behaviors with at least one failure, most failures first.
+    pub failing_behaviors: Vec<BehaviorFailures>,
+}
+
+impl MetricsStore {
+    /// Flakiest and slowest scenarios and most failing behaviors, each list
+    /// cut to `limit` entries. Only runs recorded with per-scenario outcomes
+    /// contribute.
+    #[must_use]
+    pub fn failure_analytics(&self, limit: usize) -> FailureAnalytics {
+        let Ok(data) = self.data.read() else {
+            return FailureAnalytics::default();
+        };
+
+        let mut runs = data.scenario_validations.iter().collect::<Vec<_>>();
+        runs.sort_by_key(|run| run.timestamp);
+
+        let mut by_scenario: BTreeMap<(&str, &str), Vec<&ScenarioOutcome>> = BTreeMap::new();
+        let mut by_behavior: BTreeMap<(&str, &str), Vec<&ScenarioOutcome>> = BTreeMap::new();
+        for run in runs {
+            for outcome in &run.scenarios {
+                let spec_id = run.spec_id.as_str();
+                by_scenario
+                    .entry((spec_id, outcome.scenario_id.as_str()))
+                    .or_default()
+                    .push(outcome);
+                for behavior in &outcome.behavior_refs {
+                    by_behavior
+                        .entry((spec_id, behavior.as_str()))
+                        .or_default()
+                        .push(outcome);
+                }
+            }
+        }
+
+        let scenarios = by_scenario
+            .into_iter()
+            .map(|((spec_id, scenario_id), outcomes)| {
+                scenario_stats(spec_id, scenario_id, &outcomes)
+            })
+            .collect::<Vec<_>>();
+
+        let mut flaky_scenarios = scenarios
+            .iter()
+            .filter(|stats| stats.failures > 0 && stats.failures < stats.runs)
+            .cloned()
+            .collect::<Vec<_>>();
+        flaky_scenarios.sort_by(|a, b| {
+            b.flip_rate
+                .partial_cmp(&a.flip_rate)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(b.runs.cmp(&a.runs))
+        });
+        flaky_scenarios.truncate(limit);
+
+        let mut slowest_scenarios = scenarios;
+        slowest_scenarios.sort_by(|a, b| b.avg_duration_ms.total_cmp(&a.avg_duration_ms));
+        slowest_scenarios.truncate(limit);
+
+        let mut failing_behaviors = by_behavior
+            .into_iter()
+            .map(|((spec_id, behavior_ref), outcomes)| {
+                behavior_failures(spec_id, behavior_ref, &outcomes)
+            })
+            .filter(|behavior| behavior.failures > 0)
+            .collect::<Vec<_>>();
+        failing_behaviors.sort_by(|a, b| {
+            b.failures
+                .cmp(&a.failures)
+                .then(b.failure_rate.total_cmp(&a.failure_rate))
+        });
+        failing_behaviors.truncate(limit);
+
+        FailureAnalytics {
+            flaky_scenarios,
+            slowest_scenarios,
+            failing_behaviors,
+        }
+    }
+}
+
+/// `outcomes` are oldest first.
+fn scenario_stats(
+    spec_id: &str,
+    scenario_id: &str,
+    outcomes: &[&ScenarioOutcome],
+) -> ScenarioStats {
+    #[allow(clippy::cast_precision_loss)]
+    let durations = outcomes
+        .iter()
+        .map(|outcome| outcome.duration_ms as f64)
+        .collect::<Vec<_>>();
+    let flips = outcomes
+        .windows(2)
+        .filter(|pair| pair[0].passed != pair[1].passed)
+        .count();
+    ScenarioStats {
+        spec_id: spec_id.to_string(),
+        scenario_id: scenario_id.to_string(),
+        runs: outcomes.len(),
+        failures: outcomes.iter().filter(|outcome| !outcome.passed).count(),
+        flip_rate: percentage(flips, outcomes.len().saturating_sub(1)),
+        avg_duration_ms: mean(&durations).unwrap_or_default(),
+        max_duration_ms: outcomes
+            .iter()
+            .map(|outcome| outcome.duration_ms)
+            .max()
+            .unwrap_or_default(),
+    }
+}
+
+fn behavior_failures(
+    spec_id: &str,
+    behavior_ref: &str,
+    outcomes: &[&ScenarioOutcome],
+) -> BehaviorFailures {
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| !outcome.passed)
+        .collect::<Vec<_>>();
+    let mut categories: BTreeMap<&str, usize> = BTreeMap::new();
+    for category in failed
+        .iter()
+        .flat_map(|outcome| &outcome.failed_assertion_categories)
+    {
+        *categories.entry(category.as_str()).or_default() += 1;
+    }
+    let mut assertion_categories = categories
+        .into_iter()
+        .map(|(category, count)| (category.to_string(), count))
+        .collect::<Vec<_>>();
+    assertion_categories.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+    BehaviorFailures {
+        spec_id: spec_id.to_string(),
+        behavior_ref: behavior_ref.to_string(),
+        runs: outcomes.len(),
+        failures: failed.len(),
+        failure_rate: percentage(failed.len(), outcomes.len()).unwrap_or_default(),
+        assertion_categories,
+    }
+}
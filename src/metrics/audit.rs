@@ -0,0 +1,205 @@
+//! Append-only audit log of who ran, imported, deployed or extended a
+//! workflow, and when.
+//!
+//! Entries go through the store's backend like every other record, but are
+//! never rewritten: compaction leaves them alone and there is no API to
+//! edit or delete one.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::backend::MetricsRecord;
+use super::errors::MetricsError;
+use super::model::MetricsStore;
+
+/// Variable naming the actor recorded on entries; `USER` is the fallback.
+pub const ACTOR_ENV: &str = "OYA_ACTOR";
+const UNKNOWN_ACTOR: &str = "unknown";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Run,
+    Import,
+    Deployment,
+    ExtensionApplied,
+}
+
+impl AuditAction {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::Import => "import",
+            Self::Deployment => "deployment",
+            Self::ExtensionApplied => "extension_applied",
+        }
+    }
+
+    /// Whether the action changes the workflow rather than executing it.
+    #[must_use]
+    pub const fn is_change(self) -> bool {
+        !matches!(self, Self::Run)
+    }
+}
+
+impl fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "run" => Ok(Self::Run),
+            "import" => Ok(Self::Import),
+            "deployment" => Ok(Self::Deployment),
+            "extension_applied" => Ok(Self::ExtensionApplied),
+            other => Err(format!(
+                "unknown audit action '{other}' (run, import, deployment, extension_applied)"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub actor: String,
+    pub action: AuditAction,
+    /// The workflow acted on, usually its file path.
+    pub workflow: String,
+    /// What was done, e.g. the extension key or run id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Whether the action succeeded, when it can fail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+impl AuditEntry {
+    /// An entry for `action` on `workflow` by the current actor, now.
+    #[must_use]
+    pub fn new(action: AuditAction, workflow: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            actor: current_actor(),
+            action,
+            workflow: workflow.to_string(),
+            detail: None,
+            success: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    #[must_use]
+    pub const fn with_success(mut self, success: bool) -> Self {
+        self.success = Some(success);
+        self
+    }
+}
+
+/// Which entries a query returns. Unset fields place no constraint; the
+/// time range is half-open.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditQuery {
+    pub workflow: Option<String>,
+    pub actor: Option<String>,
+    pub action: Option<AuditAction>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl AuditQuery {
+    #[must_use]
+    pub fn for_workflow(workflow: &str) -> Self {
+        Self {
+            workflow: Some(workflow.to_string()),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.workflow
+            .as_deref()
+            .is_none_or(|workflow| entry.workflow == workflow)
+            && self
+                .actor
+                .as_deref()
+                .is_none_or(|actor| entry.actor == actor)
+            && self.action.is_none_or(|action| entry.action == action)
+            && self.from.is_none_or(|from| entry.timestamp >= from)
+            && self.to.is_none_or(|to| entry.timestamp < to)
+    }
+}
+
+/// `OYA_ACTOR`, else the login user, else `unknown`.
+#[must_use]
+pub fn current_actor() -> String {
+    [ACTOR_ENV, "USER", "USERNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+        .unwrap_or_else(|| UNKNOWN_ACTOR.to_string())
+}
+
+impl MetricsStore {
+    /// Append `entry` to the audit log.
+    ///
+    /// # Errors
+    /// Returns an error if the lock cannot be acquired or the backend cannot
+    /// store the entry.
+    pub fn record_audit(&self, entry: AuditEntry) -> Result<(), MetricsError> {
+        let mut data = self
+            .data
+            .write()
+            .map_err(|_| MetricsError::LockAcquisition)?;
+        data.audit_log.push(entry.clone());
+        self.backend.record(&data, MetricsRecord::Audit(&entry))
+    }
+
+    /// Entries matching `query`, oldest first, as stored by the backend.
+    ///
+    /// # Errors
+    /// Returns an error if the backend cannot read its entries.
+    pub fn audit_log(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, MetricsError> {
+        self.backend.audit_entries(query)
+    }
+
+    /// The most recent run of `workflow`.
+    ///
+    /// # Errors
+    /// Returns an error if the backend cannot read its entries.
+    pub fn last_execution(&self, workflow: &str) -> Result<Option<AuditEntry>, MetricsError> {
+        Ok(self
+            .audit_log(&AuditQuery {
+                action: Some(AuditAction::Run),
+                ..AuditQuery::for_workflow(workflow)
+            })?
+            .pop())
+    }
+
+    /// The most recent import, deployment or applied extension of
+    /// `workflow`.
+    ///
+    /// # Errors
+    /// Returns an error if the backend cannot read its entries.
+    pub fn last_change(&self, workflow: &str) -> Result<Option<AuditEntry>, MetricsError> {
+        Ok(self
+            .audit_log(&AuditQuery::for_workflow(workflow))?
+            .into_iter()
+            .rev()
+            .find(|entry| entry.action.is_change()))
+    }
+}
@@ -0,0 +1,140 @@
+//! Storage backends for [`MetricsStore`](super::MetricsStore).
+//!
+//! The store keeps every record in memory for summaries and digests and
+//! hands each new record to its backend to persist. The JSON file backend
+//! rewrites one `metrics.json`; the SQLite backend, behind the `sqlite`
+//! feature, appends rows and answers session queries from an index on spec
+//! and start time.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use super::audit::{AuditEntry, AuditQuery};
+use super::errors::MetricsError;
+use super::model::{
+    MetricsData, QualityGateSession, ScenarioValidationMetrics, SpecValidationMetrics,
+    SuggestionDecisionMetrics,
+};
+
+/// One record handed to a backend to persist. Sessions are written again
+/// whenever an iteration is added.
+#[derive(Debug, Clone, Copy)]
+pub enum MetricsRecord<'a> {
+    SpecValidation(&'a SpecValidationMetrics),
+    ScenarioValidation(&'a ScenarioValidationMetrics),
+    SuggestionDecision(&'a SuggestionDecisionMetrics),
+    Session(&'a QualityGateSession),
+    Audit(&'a AuditEntry),
+}
+
+/// Which sessions a query returns. Unset fields place no constraint; the
+/// time range applies to `started_at` and is half-open.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionQuery {
+    pub spec_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl SessionQuery {
+    #[must_use]
+    pub fn matches(&self, session: &QualityGateSession) -> bool {
+        self.spec_id
+            .as_deref()
+            .is_none_or(|spec_id| session.spec_id.as_str() == spec_id)
+            && self.from.is_none_or(|from| session.started_at >= from)
+            && self.to.is_none_or(|to| session.started_at < to)
+    }
+}
+
+pub trait MetricsBackend: Send + Sync {
+    /// Every stored record, read once when the store opens.
+    ///
+    /// # Errors
+    /// Returns an error if the stored records cannot be read or parsed.
+    fn load(&self) -> Result<MetricsData, MetricsError>;
+
+    /// Persist `record`, which has already been applied to `data`.
+    ///
+    /// # Errors
+    /// Returns an error if the record cannot be written.
+    fn record(&self, data: &MetricsData, record: MetricsRecord<'_>) -> Result<(), MetricsError>;
+
+    /// Sessions matching `query`, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the stored sessions cannot be read.
+    fn sessions(&self, query: &SessionQuery) -> Result<Vec<QualityGateSession>, MetricsError>;
+
+    /// Audit entries matching `query`, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the stored entries cannot be read.
+    fn audit_entries(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, MetricsError>;
+
+    /// Replace everything stored with `data`, after a compaction pruned it.
+    /// The audit log is append-only and is left as it is.
+    ///
+    /// # Errors
+    /// Returns an error if the records cannot be written.
+    fn replace(&self, data: &MetricsData) -> Result<(), MetricsError>;
+}
+
+/// All records in one pretty-printed JSON file, rewritten on every record.
+#[derive(Debug, Clone)]
+pub struct JsonFileBackend {
+    path: PathBuf,
+}
+
+impl JsonFileBackend {
+    #[must_use]
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+}
+
+impl MetricsBackend for JsonFileBackend {
+    fn load(&self) -> Result<MetricsData, MetricsError> {
+        if !self.path.exists() {
+            return Ok(MetricsData::default());
+        }
+        let content = fs::read_to_string(&self.path)?;
+        serde_json::from_str(&content).map_err(MetricsError::ParseError)
+    }
+
+    fn record(&self, data: &MetricsData, _record: MetricsRecord<'_>) -> Result<(), MetricsError> {
+        let json = serde_json::to_string_pretty(data).map_err(MetricsError::ParseError)?;
+        fs::write(&self.path, json).map_err(MetricsError::WriteError)
+    }
+
+    fn sessions(&self, query: &SessionQuery) -> Result<Vec<QualityGateSession>, MetricsError> {
+        let mut sessions = self
+            .load()?
+            .sessions
+            .into_iter()
+            .filter(|session| query.matches(session))
+            .collect::<Vec<_>>();
+        sessions.sort_by_key(|session| session.started_at);
+        Ok(sessions)
+    }
+
+    fn audit_entries(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, MetricsError> {
+        let mut entries = self
+            .load()?
+            .audit_log
+            .into_iter()
+            .filter(|entry| query.matches(entry))
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| entry.timestamp);
+        Ok(entries)
+    }
+
+    fn replace(&self, data: &MetricsData) -> Result<(), MetricsError> {
+        let json = serde_json::to_string_pretty(data).map_err(MetricsError::ParseError)?;
+        fs::write(&self.path, json).map_err(MetricsError::WriteError)
+    }
+}
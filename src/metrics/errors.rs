@@ -18,4 +18,7 @@ pub enum MetricsError {
     InvalidFeedbackLevel(u8),
     #[error("Unsupported export format: {0}")]
     UnsupportedExportFormat(String),
+    #[cfg(feature = "sqlite")]
+    #[error("Metrics database error: {0}")]
+    Database(#[from] rusqlite::Error),
 }
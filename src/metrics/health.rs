@@ -0,0 +1,448 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use super::model::{MetricsData, MetricsStore, ScenarioValidationMetrics};
+use crate::coverage::CoverageReport;
+
+const MAX_ACTIONS: usize = 3;
+const DESIGN_WARNING_PENALTY: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthSignal {
+    Lint,
+    Coverage,
+    PassRate,
+    Stability,
+    Design,
+}
+
+impl HealthSignal {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Lint => "Spec lint score",
+            Self::Coverage => "Scenario coverage",
+            Self::PassRate => "Scenario pass rate",
+            Self::Stability => "Scenario stability",
+            Self::Design => "Design analysis",
+        }
+    }
+}
+
+/// Relative weight of each signal. Weights are normalized over the signals
+/// that have data, so they need not sum to one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HealthWeights {
+    pub lint: f64,
+    pub coverage: f64,
+    pub pass_rate: f64,
+    pub stability: f64,
+    pub design: f64,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            lint: 0.25,
+            coverage: 0.25,
+            pass_rate: 0.25,
+            stability: 0.15,
+            design: 0.10,
+        }
+    }
+}
+
+impl HealthWeights {
+    const fn weight(&self, signal: HealthSignal) -> f64 {
+        match signal {
+            HealthSignal::Lint => self.lint,
+            HealthSignal::Coverage => self.coverage,
+            HealthSignal::PassRate => self.pass_rate,
+            HealthSignal::Stability => self.stability,
+            HealthSignal::Design => self.design,
+        }
+    }
+}
+
+/// Raw signal values, each on a 0–100 scale. `None` means no data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HealthInputs {
+    pub lint_score: Option<f64>,
+    pub coverage: Option<f64>,
+    pub pass_rate: Option<f64>,
+    /// Share of consecutive reruns whose outcome flipped, 0–100.
+    pub flakiness: Option<f64>,
+    pub design_warnings: usize,
+}
+
+impl HealthInputs {
+    fn signal_score(&self, signal: HealthSignal) -> Option<f64> {
+        match signal {
+            HealthSignal::Lint => self.lint_score,
+            HealthSignal::Coverage => self.coverage,
+            HealthSignal::PassRate => self.pass_rate,
+            HealthSignal::Stability => self.flakiness.map(|flakiness| 100.0 - flakiness),
+            #[allow(clippy::cast_precision_loss)]
+            HealthSignal::Design => {
+                Some((self.design_warnings as f64).mul_add(-DESIGN_WARNING_PENALTY, 100.0))
+            }
+        }
+        .map(|score| score.clamp(0.0, 100.0))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthComponent {
+    pub signal: HealthSignal,
+    pub score: f64,
+    /// Normalized weight actually applied.
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceHealth {
+    pub score: f64,
+    pub components: Vec<HealthComponent>,
+}
+
+impl WorkspaceHealth {
+    #[must_use]
+    pub fn compute(inputs: &HealthInputs, weights: &HealthWeights) -> Self {
+        let scored = [
+            HealthSignal::Lint,
+            HealthSignal::Coverage,
+            HealthSignal::PassRate,
+            HealthSignal::Stability,
+            HealthSignal::Design,
+        ]
+        .into_iter()
+        .filter_map(|signal| {
+            let weight = weights.weight(signal).max(0.0);
+            inputs
+                .signal_score(signal)
+                .filter(|_| weight > 0.0)
+                .map(|score| (signal, score, weight))
+        })
+        .collect::<Vec<_>>();
+
+        let total_weight: f64 = scored.iter().map(|(_, _, weight)| weight).sum();
+        if total_weight <= 0.0 {
+            return Self {
+                score: 0.0,
+                components: Vec::new(),
+            };
+        }
+
+        let components = scored
+            .into_iter()
+            .map(|(signal, score, weight)| HealthComponent {
+                signal,
+                score,
+                weight: weight / total_weight,
+            })
+            .collect::<Vec<_>>();
+        let score = components
+            .iter()
+            .map(|component| component.score * component.weight)
+            .sum();
+
+        Self { score, components }
+    }
+
+    #[must_use]
+    pub fn component(&self, signal: HealthSignal) -> Option<&HealthComponent> {
+        self.components
+            .iter()
+            .find(|component| component.signal == signal)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalTrend {
+    pub signal: HealthSignal,
+    pub current: f64,
+    pub previous: Option<f64>,
+}
+
+impl SignalTrend {
+    #[must_use]
+    pub fn delta(&self) -> Option<f64> {
+        self.previous.map(|previous| self.current - previous)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecommendedAction {
+    pub signal: HealthSignal,
+    /// Health points recoverable by fixing this signal.
+    pub impact: f64,
+    pub action: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeeklyDigest {
+    pub week_start: DateTime<Utc>,
+    pub week_end: DateTime<Utc>,
+    pub health: WorkspaceHealth,
+    pub previous_score: Option<f64>,
+    pub trends: Vec<SignalTrend>,
+    pub actions: Vec<RecommendedAction>,
+}
+
+impl WeeklyDigest {
+    /// Render the digest as JSON.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Workspace health digest\n\n_{} – {}_\n\n",
+            self.week_start.format("%Y-%m-%d"),
+            self.week_end.format("%Y-%m-%d")
+        );
+        let _ = write!(out, "**Health score: {:.1}/100**", self.health.score);
+        if let Some(previous) = self.previous_score {
+            let _ = write!(out, " ({})", format_delta(self.health.score - previous));
+        }
+        out.push_str("\n\n| Signal | Score | Weight | Trend |\n| --- | ---: | ---: | ---: |\n");
+        for trend in &self.trends {
+            let weight = self
+                .health
+                .component(trend.signal)
+                .map_or(0.0, |component| component.weight * 100.0);
+            let _ = writeln!(
+                out,
+                "| {} | {:.1} | {weight:.0}% | {} |",
+                trend.signal.label(),
+                trend.current,
+                trend.delta().map_or_else(|| "—".to_string(), format_delta)
+            );
+        }
+
+        out.push_str("\n## Top actions\n\n");
+        if self.actions.is_empty() {
+            out.push_str("No action needed this week.\n");
+        }
+        for (index, action) in self.actions.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{}. {} _(+{:.1} pts)_",
+                index + 1,
+                action.action,
+                action.impact
+            );
+        }
+        out
+    }
+}
+
+fn format_delta(delta: f64) -> String {
+    if delta.abs() < 0.05 {
+        "±0.0".to_string()
+    } else {
+        format!("{delta:+.1}")
+    }
+}
+
+impl MetricsStore {
+    /// Build the weekly digest for the seven days ending at `now`.
+    ///
+    /// Lint, pass rate and flakiness come from recorded metrics; coverage and
+    /// design warnings describe the workspace as it is now, so they have no
+    /// trend.
+    #[must_use]
+    pub fn weekly_digest(
+        &self,
+        now: DateTime<Utc>,
+        coverage: Option<&CoverageReport>,
+        design_warnings: usize,
+        weights: &HealthWeights,
+    ) -> WeeklyDigest {
+        let week_start = now - Duration::days(7);
+        let (mut current, previous) = self.data.read().map_or_else(
+            |_| (HealthInputs::default(), None),
+            |data| {
+                let current = window_inputs(&data, week_start, now);
+                let previous = window_inputs(&data, week_start - Duration::days(7), week_start);
+                let has_history = previous.lint_score.is_some() || previous.pass_rate.is_some();
+                (current, has_history.then_some(previous))
+            },
+        );
+        current.coverage = coverage.map(|report| report.overall_coverage);
+        current.design_warnings = design_warnings;
+
+        let health = WorkspaceHealth::compute(&current, weights);
+        let previous_health = previous.map(|mut previous| {
+            previous.coverage = current.coverage;
+            previous.design_warnings = design_warnings;
+            WorkspaceHealth::compute(&previous, weights)
+        });
+
+        let trends = health
+            .components
+            .iter()
+            .map(|component| SignalTrend {
+                signal: component.signal,
+                current: component.score,
+                previous: previous_health
+                    .as_ref()
+                    .and_then(|previous| previous.component(component.signal))
+                    .filter(|_| {
+                        !matches!(
+                            component.signal,
+                            HealthSignal::Coverage | HealthSignal::Design
+                        )
+                    })
+                    .map(|previous| previous.score),
+            })
+            .collect();
+        let actions = recommended_actions(&health, &current, coverage);
+
+        WeeklyDigest {
+            week_start,
+            week_end: now,
+            previous_score: previous_health.map(|previous| previous.score),
+            health,
+            trends,
+            actions,
+        }
+    }
+}
+
+fn window_inputs(data: &MetricsData, start: DateTime<Utc>, end: DateTime<Utc>) -> HealthInputs {
+    let in_window = |timestamp: &DateTime<Utc>| *timestamp >= start && *timestamp < end;
+
+    let lint_scores = data
+        .spec_validations
+        .iter()
+        .filter(|metrics| in_window(&metrics.timestamp))
+        .map(|metrics| f64::from(metrics.overall_score))
+        .collect::<Vec<_>>();
+    let scenario_runs = data
+        .scenario_validations
+        .iter()
+        .filter(|metrics| in_window(&metrics.timestamp))
+        .collect::<Vec<_>>();
+
+    let (total, passed) = scenario_runs.iter().fold((0, 0), |(total, passed), run| {
+        (total + run.total_scenarios, passed + run.passed_scenarios)
+    });
+
+    HealthInputs {
+        lint_score: mean(&lint_scores),
+        coverage: None,
+        pass_rate: percentage(passed, total),
+        flakiness: flakiness(&scenario_runs),
+        design_warnings: 0,
+    }
+}
+
+/// Share of consecutive runs of the same spec whose overall outcome flipped.
+fn flakiness(runs: &[&ScenarioValidationMetrics]) -> Option<f64> {
+    let mut by_spec: HashMap<&str, Vec<&ScenarioValidationMetrics>> = HashMap::new();
+    for run in runs {
+        by_spec.entry(run.spec_id.as_str()).or_default().push(run);
+    }
+
+    let (pairs, flips) = by_spec
+        .into_values()
+        .fold((0, 0), |(pairs, flips), mut spec_runs| {
+            spec_runs.sort_by_key(|run| run.timestamp);
+            let spec_flips = spec_runs
+                .windows(2)
+                .filter(|pair| (pair[0].failed_scenarios == 0) != (pair[1].failed_scenarios == 0))
+                .count();
+            (
+                pairs + spec_runs.len().saturating_sub(1),
+                flips + spec_flips,
+            )
+        });
+
+    percentage(flips, pairs)
+}
+
+fn recommended_actions(
+    health: &WorkspaceHealth,
+    inputs: &HealthInputs,
+    coverage: Option<&CoverageReport>,
+) -> Vec<RecommendedAction> {
+    let mut actions = health
+        .components
+        .iter()
+        .filter(|component| component.score < 100.0)
+        .map(|component| RecommendedAction {
+            signal: component.signal,
+            impact: (100.0 - component.score) * component.weight,
+            action: action_text(component, inputs, coverage),
+        })
+        .collect::<Vec<_>>();
+    actions.sort_by(|left, right| right.impact.total_cmp(&left.impact));
+    actions.truncate(MAX_ACTIONS);
+    actions
+}
+
+fn action_text(
+    component: &HealthComponent,
+    inputs: &HealthInputs,
+    coverage: Option<&CoverageReport>,
+) -> String {
+    match component.signal {
+        HealthSignal::Lint => format!(
+            "Fix spec lint findings; the average spec score is {:.0}/100.",
+            component.score
+        ),
+        HealthSignal::Coverage => {
+            let gaps = coverage
+                .map(|report| {
+                    report
+                        .common_gaps
+                        .iter()
+                        .take(3)
+                        .map(|gap| format!("`{gap}`"))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            if gaps.is_empty() {
+                format!(
+                    "Add scenarios for uncovered behaviors; coverage is {:.1}%.",
+                    component.score
+                )
+            } else {
+                format!(
+                    "Add scenarios for uncovered behaviors, starting with {}.",
+                    gaps.join(", ")
+                )
+            }
+        }
+        HealthSignal::PassRate => format!(
+            "Investigate failing scenarios; {:.1}% passed this week.",
+            component.score
+        ),
+        HealthSignal::Stability => format!(
+            "Stabilize flaky scenarios; {:.1}% of reruns changed outcome.",
+            100.0 - component.score
+        ),
+        HealthSignal::Design => format!(
+            "Resolve {} design-analysis warning(s).",
+            inputs.design_warnings
+        ),
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(super) fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+#[allow(clippy::cast_precision_loss)]
+pub(super) fn percentage(part: usize, total: usize) -> Option<f64> {
+    (total > 0).then(|| part as f64 / total as f64 * 100.0)
+}
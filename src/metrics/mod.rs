@@ -1,14 +1,36 @@
+mod analytics;
+mod audit;
+mod backend;
 mod errors;
+mod health;
 mod model;
+mod openmetrics;
 mod report;
+mod retention;
+mod session_export;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod store;
+mod trend;
 
 #[cfg(test)]
 mod tests;
 
+pub use analytics::{BehaviorFailures, FailureAnalytics, ScenarioStats};
+pub use audit::{current_actor, AuditAction, AuditEntry, AuditQuery, ACTOR_ENV};
+pub use backend::{JsonFileBackend, MetricsBackend, MetricsRecord, SessionQuery};
 pub use errors::MetricsError;
+pub use health::{
+    HealthComponent, HealthInputs, HealthSignal, HealthWeights, RecommendedAction, SignalTrend,
+    WeeklyDigest, WorkspaceHealth,
+};
 pub use model::{
-    CategoryStats, MetricsStore, MetricsSummary, QualityGateIteration, QualityGateSession,
-    ScenarioValidationMetrics, SessionStatus, SpecValidationMetrics, SuggestionDecision,
-    SuggestionDecisionMetrics, SuggestionKey,
+    CategoryStats, MetricsData, MetricsStore, MetricsSummary, QualityGateIteration,
+    QualityGateSession, ScenarioOutcome, ScenarioValidationMetrics, SessionStatus,
+    SpecValidationMetrics, SuggestionDecision, SuggestionDecisionMetrics, SuggestionKey,
 };
+pub use retention::{CompactionReport, DailyRollup, RetentionPolicy};
+pub use session_export::{SessionTimeline, TimelineEvent};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteBackend;
+pub use trend::{TrendBucket, TrendPoint};
@@ -4,7 +4,10 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
+use super::audit::AuditEntry;
+use super::backend::MetricsBackend;
 use super::errors::MetricsError;
+use super::retention::DailyRollup;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SpecId(pub String);
@@ -440,6 +443,23 @@ pub struct ScenarioValidationMetrics {
     pub failed_scenarios: usize,
     pub category_breakdown: HashMap<CategoryName, CategoryStats>,
     pub duration_ms: u64,
+    /// Per-scenario results of the run, when the caller recorded them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scenarios: Vec<ScenarioOutcome>,
+}
+
+/// How one scenario fared in a validation run.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScenarioOutcome {
+    pub scenario_id: String,
+    pub passed: bool,
+    pub duration_ms: u64,
+    /// Behaviors the scenario's assertions reference.
+    #[serde(default)]
+    pub behavior_refs: Vec<String>,
+    /// Types of the assertions that failed, such as `status` or `json_path`.
+    #[serde(default)]
+    pub failed_assertion_categories: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -504,6 +524,19 @@ pub enum SessionStatus {
     Escalated,
 }
 
+impl SessionStatus {
+    /// The serialized name, e.g. `in_progress`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Escalated => "escalated",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct MetricsSummary {
     pub total_sessions: usize,
@@ -519,12 +552,28 @@ pub struct MetricsSummary {
 pub struct MetricsStore {
     pub(crate) base_path: PathBuf,
     pub(crate) data: Arc<RwLock<MetricsData>>,
+    pub(crate) backend: Box<dyn MetricsBackend>,
+}
+
+impl std::fmt::Debug for MetricsStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsStore")
+            .field("base_path", &self.base_path)
+            .finish_non_exhaustive()
+    }
 }
 
+/// Every record a store holds, as a backend loads and persists it.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub(crate) struct MetricsData {
-    pub(crate) spec_validations: Vec<SpecValidationMetrics>,
-    pub(crate) scenario_validations: Vec<ScenarioValidationMetrics>,
-    pub(crate) suggestion_decisions: Vec<SuggestionDecisionMetrics>,
-    pub(crate) sessions: Vec<QualityGateSession>,
+pub struct MetricsData {
+    pub spec_validations: Vec<SpecValidationMetrics>,
+    pub scenario_validations: Vec<ScenarioValidationMetrics>,
+    pub suggestion_decisions: Vec<SuggestionDecisionMetrics>,
+    pub sessions: Vec<QualityGateSession>,
+    /// Daily aggregates of records pruned by compaction.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rollups: Vec<DailyRollup>,
+    /// Append-only; compaction never prunes it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audit_log: Vec<AuditEntry>,
 }
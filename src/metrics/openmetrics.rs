@@ -0,0 +1,161 @@
+//! OpenMetrics text exposition of the recorded quality-gate metrics, for
+//! Prometheus-compatible scrapers.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use super::model::{MetricsStore, SessionStatus, SpecValidationMetrics};
+
+const ITERATION_BUCKETS: [f64; 5] = [1.0, 2.0, 3.0, 4.0, 5.0];
+const DURATION_BUCKETS_SECONDS: [f64; 8] = [0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+impl MetricsStore {
+    /// Render sessions by status, iterations per session, validation
+    /// durations and each spec's latest lint score in the OpenMetrics text
+    /// format, terminated by `# EOF`.
+    #[must_use]
+    pub fn to_openmetrics(&self) -> String {
+        let mut out = String::new();
+        let Ok(data) = self.data.read() else {
+            out.push_str("# EOF\n");
+            return out;
+        };
+
+        let _ = writeln!(out, "# TYPE oya_quality_gate_sessions counter");
+        let _ = writeln!(
+            out,
+            "# HELP oya_quality_gate_sessions Quality gate sessions by current status."
+        );
+        for status in [
+            SessionStatus::InProgress,
+            SessionStatus::Passed,
+            SessionStatus::Failed,
+            SessionStatus::Escalated,
+        ] {
+            let count = data
+                .sessions
+                .iter()
+                .filter(|session| session.status == status)
+                .count();
+            let _ = writeln!(
+                out,
+                "oya_quality_gate_sessions_total{{status=\"{}\"}} {count}",
+                status.as_str()
+            );
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let iterations = data
+            .sessions
+            .iter()
+            .map(|session| session.iterations.len() as f64)
+            .collect::<Vec<_>>();
+        write_histogram(
+            &mut out,
+            "oya_quality_gate_session_iterations",
+            "Iterations each quality gate session took.",
+            &[("", &iterations)],
+            &ITERATION_BUCKETS,
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let seconds = |ms: u64| ms as f64 / 1000.0;
+        let spec_durations = data
+            .spec_validations
+            .iter()
+            .map(|validation| seconds(validation.duration_ms))
+            .collect::<Vec<_>>();
+        let scenario_durations = data
+            .scenario_validations
+            .iter()
+            .map(|validation| seconds(validation.duration_ms))
+            .collect::<Vec<_>>();
+        write_histogram(
+            &mut out,
+            "oya_validation_duration_seconds",
+            "Time spent validating specs and scenarios.",
+            &[
+                ("kind=\"spec\"", &spec_durations),
+                ("kind=\"scenario\"", &scenario_durations),
+            ],
+            &DURATION_BUCKETS_SECONDS,
+        );
+
+        // The most recent lint score per spec.
+        let latest =
+            data.spec_validations
+                .iter()
+                .fold(BTreeMap::new(), |mut latest, validation| {
+                    latest
+                        .entry(validation.spec_id.as_str())
+                        .and_modify(|current: &mut &SpecValidationMetrics| {
+                            if validation.timestamp >= current.timestamp {
+                                *current = validation;
+                            }
+                        })
+                        .or_insert(validation);
+                    latest
+                });
+        let _ = writeln!(out, "# TYPE oya_spec_score gauge");
+        let _ = writeln!(
+            out,
+            "# HELP oya_spec_score Latest spec lint score, out of 100."
+        );
+        for (spec_id, validation) in latest {
+            let _ = writeln!(
+                out,
+                "oya_spec_score{{spec=\"{}\"}} {}",
+                escape_label(spec_id),
+                validation.overall_score
+            );
+        }
+
+        out.push_str("# EOF\n");
+        out
+    }
+}
+
+/// One histogram family with a series per label set; `buckets` are the
+/// finite upper bounds, `+Inf` is added.
+fn write_histogram(
+    out: &mut String,
+    name: &str,
+    help: &str,
+    series: &[(&str, &[f64])],
+    buckets: &[f64],
+) {
+    let _ = writeln!(out, "# TYPE {name} histogram");
+    let _ = writeln!(out, "# HELP {name} {help}");
+    for (labels, values) in series {
+        let with = |extra: &str| match (labels.is_empty(), extra.is_empty()) {
+            (true, true) => String::new(),
+            (true, false) => format!("{{{extra}}}"),
+            (false, true) => format!("{{{labels}}}"),
+            (false, false) => format!("{{{labels},{extra}}}"),
+        };
+        for bound in buckets {
+            let count = values.iter().filter(|value| **value <= *bound).count();
+            let _ = writeln!(
+                out,
+                "{name}_bucket{} {count}",
+                with(&format!("le=\"{bound:?}\""))
+            );
+        }
+        let _ = writeln!(out, "{name}_bucket{} {}", with("le=\"+Inf\""), values.len());
+        let _ = writeln!(out, "{name}_count{} {}", with(""), values.len());
+        let _ = writeln!(
+            out,
+            "{name}_sum{} {:?}",
+            with(""),
+            values.iter().sum::<f64>()
+        );
+    }
+}
+
+/// Escapes a label value as OpenMetrics requires.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,98 +1,38 @@
 use std::collections::HashMap;
 
-use super::model::{MetricsStore, MetricsSummary, SessionStatus};
+use chrono::{DateTime, Utc};
+
+use super::model::{MetricsStore, MetricsSummary, QualityGateSession, SessionStatus};
 
 impl MetricsStore {
     #[must_use]
     pub fn get_summary(&self) -> MetricsSummary {
-        let Ok(data_guard) = self.data.read() else {
-            return MetricsSummary::default();
-        };
-        let data = &*data_guard;
-
-        let total_sessions = data.sessions.len();
-        let passed_sessions = data
-            .sessions
-            .iter()
-            .filter(|s| s.status == SessionStatus::Passed)
-            .count();
-        let failed_sessions = data
-            .sessions
-            .iter()
-            .filter(|s| s.status == SessionStatus::Failed)
-            .count();
-        let escalated_sessions = data.sessions.iter().filter(|s| s.escalated).count();
+        self.summary_where(|_| true)
+    }
 
-        let passed_sessions_refs: Vec<_> = data
-            .sessions
-            .iter()
-            .filter(|s| s.status == SessionStatus::Passed)
-            .collect();
+    /// Summary of the sessions started, and spec validations recorded, in
+    /// `[from, to)`.
+    #[must_use]
+    pub fn summary_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> MetricsSummary {
+        self.summary_where(|timestamp| *timestamp >= from && *timestamp < to)
+    }
 
-        let avg_iterations = if passed_sessions_refs.is_empty() {
-            0.0
-        } else {
-            let total_iterations: usize = passed_sessions_refs
-                .iter()
-                .map(|s| s.iterations.len())
-                .sum();
-            #[allow(clippy::cast_precision_loss)]
-            {
-                total_iterations as f64 / passed_sessions_refs.len() as f64
-            }
+    fn summary_where(&self, in_window: impl Fn(&DateTime<Utc>) -> bool) -> MetricsSummary {
+        let Ok(data) = self.data.read() else {
+            return MetricsSummary::default();
         };
-
-        let total_duration_ms: u64 = passed_sessions_refs
+        let sessions = data
+            .sessions
             .iter()
-            .map(|s| s.total_duration_ms)
-            .sum();
-        let avg_duration_minutes = if passed_sessions_refs.is_empty() {
-            0.0
-        } else {
-            #[allow(clippy::cast_precision_loss)]
-            {
-                total_duration_ms as f64 / passed_sessions_refs.len() as f64 / 60000.0
-            }
-        };
-
-        let spec_scores: Vec<f64> = data
+            .filter(|session| in_window(&session.started_at))
+            .collect::<Vec<_>>();
+        let spec_scores = data
             .spec_validations
             .iter()
-            .map(|v| f64::from(v.overall_score))
-            .collect();
-        let avg_spec_score = if spec_scores.is_empty() {
-            0.0
-        } else {
-            #[allow(clippy::cast_precision_loss)]
-            {
-                spec_scores.iter().sum::<f64>() / spec_scores.len() as f64
-            }
-        };
-
-        let mut failure_counts: HashMap<String, usize> = HashMap::new();
-        for session in &data.sessions {
-            for iteration in &session.iterations {
-                if !iteration.overall_passed {
-                    if let Some(category) = &iteration.failure_category {
-                        *failure_counts.entry(category.to_string()).or_insert(0) += 1;
-                    }
-                }
-            }
-        }
-
-        let mut failures: Vec<_> = failure_counts.into_iter().collect();
-        failures.sort_by_key(|b| std::cmp::Reverse(b.1));
-
-        MetricsSummary {
-            total_sessions,
-            passed_sessions,
-            failed_sessions,
-            escalated_sessions,
-            avg_iterations_to_pass: avg_iterations,
-            avg_duration_minutes,
-            most_common_failure_categories: failures,
-            avg_spec_score,
-        }
+            .filter(|validation| in_window(&validation.timestamp))
+            .map(|validation| f64::from(validation.overall_score))
+            .collect::<Vec<_>>();
+        summarize(&sessions, &spec_scores)
     }
 
     /// Export a metrics report.
@@ -105,7 +45,8 @@ impl MetricsStore {
         match format {
             "json" => Ok(serde_json::to_string_pretty(&summary)?),
             "text" => Ok(Self::format_text_report(&summary)),
-            _ => Err("Unsupported format. Use 'json' or 'text'".into()),
+            "openmetrics" => Ok(self.to_openmetrics()),
+            _ => Err("Unsupported format. Use 'json', 'text' or 'openmetrics'".into()),
         }
     }
 
@@ -179,3 +120,81 @@ impl MetricsStore {
         )
     }
 }
+
+fn summarize(sessions: &[&QualityGateSession], spec_scores: &[f64]) -> MetricsSummary {
+    let total_sessions = sessions.len();
+    let passed_sessions = sessions
+        .iter()
+        .filter(|s| s.status == SessionStatus::Passed)
+        .count();
+    let failed_sessions = sessions
+        .iter()
+        .filter(|s| s.status == SessionStatus::Failed)
+        .count();
+    let escalated_sessions = sessions.iter().filter(|s| s.escalated).count();
+
+    let passed_sessions_refs: Vec<_> = sessions
+        .iter()
+        .filter(|s| s.status == SessionStatus::Passed)
+        .collect();
+
+    let avg_iterations = if passed_sessions_refs.is_empty() {
+        0.0
+    } else {
+        let total_iterations: usize = passed_sessions_refs
+            .iter()
+            .map(|s| s.iterations.len())
+            .sum();
+        #[allow(clippy::cast_precision_loss)]
+        {
+            total_iterations as f64 / passed_sessions_refs.len() as f64
+        }
+    };
+
+    let total_duration_ms: u64 = passed_sessions_refs
+        .iter()
+        .map(|s| s.total_duration_ms)
+        .sum();
+    let avg_duration_minutes = if passed_sessions_refs.is_empty() {
+        0.0
+    } else {
+        #[allow(clippy::cast_precision_loss)]
+        {
+            total_duration_ms as f64 / passed_sessions_refs.len() as f64 / 60000.0
+        }
+    };
+
+    let avg_spec_score = if spec_scores.is_empty() {
+        0.0
+    } else {
+        #[allow(clippy::cast_precision_loss)]
+        {
+            spec_scores.iter().sum::<f64>() / spec_scores.len() as f64
+        }
+    };
+
+    let mut failure_counts: HashMap<String, usize> = HashMap::new();
+    for session in sessions {
+        for iteration in &session.iterations {
+            if !iteration.overall_passed {
+                if let Some(category) = &iteration.failure_category {
+                    *failure_counts.entry(category.to_string()).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    let mut failures: Vec<_> = failure_counts.into_iter().collect();
+    failures.sort_by_key(|b| std::cmp::Reverse(b.1));
+
+    MetricsSummary {
+        total_sessions,
+        passed_sessions,
+        failed_sessions,
+        escalated_sessions,
+        avg_iterations_to_pass: avg_iterations,
+        avg_duration_minutes,
+        most_common_failure_categories: failures,
+        avg_spec_score,
+    }
+}
@@ -0,0 +1,216 @@
+//! Retention limits and compaction of raw records into daily rollups.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::errors::MetricsError;
+use super::health::percentage;
+use super::model::{
+    MetricsData, MetricsStore, QualityGateSession, ScenarioValidationMetrics, SessionStatus,
+    SpecId, SpecValidationMetrics,
+};
+use super::trend::TrendBucket;
+
+/// How much raw history a store keeps. Unset limits keep everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Most sessions to keep, newest by start time. In-progress sessions
+    /// are always kept and do not count against the limit.
+    pub max_sessions: Option<usize>,
+    /// Oldest raw record to keep, relative to the time of compaction.
+    pub max_age: Option<Duration>,
+}
+
+/// Aggregates of one spec's pruned records for one UTC day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyRollup {
+    pub day: DateTime<Utc>,
+    pub spec_id: SpecId,
+    pub lint_runs: usize,
+    pub lint_score_sum: u64,
+    pub scenario_runs: usize,
+    pub scenarios_total: usize,
+    pub scenarios_passed: usize,
+    pub sessions: usize,
+    pub passed_sessions: usize,
+    pub failed_sessions: usize,
+    pub escalated_sessions: usize,
+    pub iterations: usize,
+}
+
+impl DailyRollup {
+    fn empty(day: DateTime<Utc>, spec_id: SpecId) -> Self {
+        Self {
+            day,
+            spec_id,
+            lint_runs: 0,
+            lint_score_sum: 0,
+            scenario_runs: 0,
+            scenarios_total: 0,
+            scenarios_passed: 0,
+            sessions: 0,
+            passed_sessions: 0,
+            failed_sessions: 0,
+            escalated_sessions: 0,
+            iterations: 0,
+        }
+    }
+
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn avg_score(&self) -> Option<f64> {
+        (self.lint_runs > 0).then(|| self.lint_score_sum as f64 / self.lint_runs as f64)
+    }
+
+    /// Passed scenarios as a percentage of all scenarios run that day.
+    #[must_use]
+    pub fn pass_rate(&self) -> Option<f64> {
+        percentage(self.scenarios_passed, self.scenarios_total)
+    }
+
+    fn add_spec_validation(&mut self, validation: &SpecValidationMetrics) {
+        self.lint_runs += 1;
+        self.lint_score_sum += u64::from(validation.overall_score);
+    }
+
+    fn add_scenario_validation(&mut self, validation: &ScenarioValidationMetrics) {
+        self.scenario_runs += 1;
+        self.scenarios_total += validation.total_scenarios;
+        self.scenarios_passed += validation.passed_scenarios;
+    }
+
+    fn add_session(&mut self, session: &QualityGateSession) {
+        self.sessions += 1;
+        self.passed_sessions += usize::from(session.status == SessionStatus::Passed);
+        self.failed_sessions += usize::from(session.status == SessionStatus::Failed);
+        self.escalated_sessions += usize::from(session.escalated);
+        self.iterations += session.iterations.len();
+    }
+}
+
+/// What a compaction removed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionReport {
+    pub spec_validations_removed: usize,
+    pub scenario_validations_removed: usize,
+    pub sessions_removed: usize,
+    /// Rollups held after compaction.
+    pub rollups: usize,
+}
+
+impl CompactionReport {
+    #[must_use]
+    pub const fn removed(&self) -> usize {
+        self.spec_validations_removed + self.scenario_validations_removed + self.sessions_removed
+    }
+}
+
+impl MetricsStore {
+    /// Prune raw records outside `policy` as of `now`, folding each pruned
+    /// lint run, scenario run and session into the daily rollup of its spec.
+    /// Suggestion decisions are small and are kept as they are.
+    ///
+    /// # Errors
+    /// Returns an error if the lock cannot be acquired or the backend cannot
+    /// store the compacted records.
+    pub fn compact(
+        &self,
+        policy: &RetentionPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<CompactionReport, MetricsError> {
+        let mut data = self
+            .data
+            .write()
+            .map_err(|_| MetricsError::LockAcquisition)?;
+        let report = compact_data(&mut data, policy, now);
+        if report.removed() > 0 {
+            self.backend.replace(&data)?;
+        }
+        Ok(report)
+    }
+
+    /// Rollups of pruned records, oldest first.
+    #[must_use]
+    pub fn daily_rollups(&self) -> Vec<DailyRollup> {
+        self.data
+            .read()
+            .map_or_else(|_| Vec::new(), |data| data.rollups.clone())
+    }
+}
+
+fn compact_data(
+    data: &mut MetricsData,
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> CompactionReport {
+    let cutoff = policy.max_age.map(|max_age| now - max_age);
+    let expired = |timestamp: DateTime<Utc>| cutoff.is_some_and(|cutoff| timestamp < cutoff);
+
+    let mut rollups = data
+        .rollups
+        .drain(..)
+        .map(|rollup| ((rollup.day, rollup.spec_id.to_string()), rollup))
+        .collect::<Rollups>();
+    let (expired_lint, kept_lint): (Vec<_>, Vec<_>) = data
+        .spec_validations
+        .drain(..)
+        .partition(|validation| expired(validation.timestamp));
+    for validation in &expired_lint {
+        rollup_for(&mut rollups, validation.timestamp, &validation.spec_id)
+            .add_spec_validation(validation);
+    }
+    data.spec_validations = kept_lint;
+
+    let (expired_runs, kept_runs): (Vec<_>, Vec<_>) = data
+        .scenario_validations
+        .drain(..)
+        .partition(|validation| expired(validation.timestamp));
+    for validation in &expired_runs {
+        rollup_for(&mut rollups, validation.timestamp, &validation.spec_id)
+            .add_scenario_validation(validation);
+    }
+    data.scenario_validations = kept_runs;
+
+    // Newest first, so the session limit keeps the most recent.
+    let mut sessions = std::mem::take(&mut data.sessions);
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.started_at));
+    let mut kept_finished = 0;
+    let (kept_sessions, expired_sessions): (Vec<_>, Vec<_>) =
+        sessions.into_iter().partition(|session| {
+            if session.status == SessionStatus::InProgress {
+                return true;
+            }
+            let keep = !expired(session.started_at)
+                && policy.max_sessions.is_none_or(|max| kept_finished < max);
+            kept_finished += usize::from(keep);
+            keep
+        });
+    for session in &expired_sessions {
+        rollup_for(&mut rollups, session.started_at, &session.spec_id).add_session(session);
+    }
+    data.sessions = kept_sessions;
+    data.sessions.sort_by_key(|session| session.started_at);
+
+    data.rollups = rollups.into_values().collect();
+    CompactionReport {
+        spec_validations_removed: expired_lint.len(),
+        scenario_validations_removed: expired_runs.len(),
+        sessions_removed: expired_sessions.len(),
+        rollups: data.rollups.len(),
+    }
+}
+
+type Rollups = BTreeMap<(DateTime<Utc>, String), DailyRollup>;
+
+fn rollup_for<'a>(
+    rollups: &'a mut Rollups,
+    timestamp: DateTime<Utc>,
+    spec_id: &SpecId,
+) -> &'a mut DailyRollup {
+    let day = TrendBucket::Day.start_of(timestamp);
+    rollups
+        .entry((day, spec_id.to_string()))
+        .or_insert_with(|| DailyRollup::empty(day, spec_id.clone()))
+}
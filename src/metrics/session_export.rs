@@ -0,0 +1,246 @@
+//! Per-session timelines and Markdown digests, for pasting into pull
+//! requests and incident docs.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::model::{MetricsStore, QualityGateIteration, QualityGateSession};
+
+/// One iteration of a session, placed relative to the session start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    pub iteration: u32,
+    pub timestamp: DateTime<Utc>,
+    /// Milliseconds between the session start and this iteration.
+    pub elapsed_ms: i64,
+    pub spec_score: u32,
+    pub spec_passed: bool,
+    pub scenarios_passed: usize,
+    pub scenarios_total: usize,
+    pub overall_passed: bool,
+    pub failure_category: Option<String>,
+    pub feedback_level: u8,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionTimeline {
+    pub session_id: String,
+    pub spec_id: String,
+    pub spec_version: String,
+    pub status: String,
+    pub escalated: bool,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub total_duration_ms: u64,
+    pub events: Vec<TimelineEvent>,
+}
+
+impl SessionTimeline {
+    #[must_use]
+    pub fn new(session: &QualityGateSession) -> Self {
+        Self {
+            session_id: session.session_id.to_string(),
+            spec_id: session.spec_id.to_string(),
+            spec_version: session.spec_version.to_string(),
+            status: session.status.as_str().to_string(),
+            escalated: session.escalated,
+            started_at: session.started_at,
+            completed_at: session.completed_at,
+            total_duration_ms: session.total_duration_ms,
+            events: session
+                .iterations
+                .iter()
+                .map(|iteration| event(session.started_at, iteration))
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Quality gate session `{}`\n\n- **Spec:** {} v{}\n- **Status:** {}{}\n- **Started:** {}\n",
+            self.session_id,
+            self.spec_id,
+            self.spec_version,
+            self.status,
+            if self.escalated { " (escalated)" } else { "" },
+            self.started_at.format("%Y-%m-%d %H:%M UTC"),
+        );
+        if let Some(completed_at) = self.completed_at {
+            let _ = writeln!(
+                out,
+                "- **Completed:** {}",
+                completed_at.format("%Y-%m-%d %H:%M UTC")
+            );
+        }
+        let _ = writeln!(out, "- **Iterations:** {}", self.events.len());
+
+        out.push_str("\n## Iterations\n\n");
+        if self.events.is_empty() {
+            out.push_str("No iterations recorded.\n");
+        } else {
+            out.push_str(
+                "| # | Elapsed | Spec score | Scenarios | Result | Failure category | Feedback level | Duration |\n\
+                 | ---: | ---: | ---: | ---: | --- | --- | ---: | ---: |\n",
+            );
+        }
+        for event in &self.events {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {}/{} | {} | {} | {} | {} |",
+                event.iteration,
+                format_ms(event.elapsed_ms),
+                event.spec_score,
+                event.scenarios_passed,
+                event.scenarios_total,
+                if event.overall_passed { "pass" } else { "fail" },
+                event.failure_category.as_deref().unwrap_or("—"),
+                event.feedback_level,
+                format_ms(i64::try_from(event.duration_ms).unwrap_or(i64::MAX)),
+            );
+        }
+
+        let categories = failure_categories(self.events.iter());
+        if !categories.is_empty() {
+            out.push_str("\n## Failure categories\n\n");
+            for (category, count) in categories {
+                let _ = writeln!(out, "- {category}: {count}");
+            }
+        }
+        out
+    }
+}
+
+fn event(started_at: DateTime<Utc>, iteration: &QualityGateIteration) -> TimelineEvent {
+    TimelineEvent {
+        iteration: iteration.iteration.value(),
+        timestamp: iteration.timestamp,
+        elapsed_ms: (iteration.timestamp - started_at).num_milliseconds(),
+        spec_score: iteration.spec_score,
+        spec_passed: iteration.spec_passed,
+        scenarios_passed: iteration.scenarios_passed_count,
+        scenarios_total: iteration.scenarios_total,
+        overall_passed: iteration.overall_passed,
+        failure_category: iteration.failure_category.as_ref().map(ToString::to_string),
+        feedback_level: iteration.feedback_level.value(),
+        duration_ms: iteration.duration_ms,
+    }
+}
+
+/// Failure categories by count, most frequent first.
+fn failure_categories<'a>(events: impl Iterator<Item = &'a TimelineEvent>) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for category in events.filter_map(|event| event.failure_category.as_deref()) {
+        *counts.entry(category).or_default() += 1;
+    }
+    let mut counts = counts
+        .into_iter()
+        .map(|(category, count)| (category.to_string(), count))
+        .collect::<Vec<_>>();
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+}
+
+fn format_ms(ms: i64) -> String {
+    if ms < 1000 {
+        format!("{ms}ms")
+    } else {
+        #[allow(clippy::cast_precision_loss)]
+        let seconds = ms as f64 / 1000.0;
+        format!("{seconds:.1}s")
+    }
+}
+
+impl MetricsStore {
+    /// Export one session as a `json` timeline or a `markdown` digest.
+    ///
+    /// # Errors
+    /// Returns an error if the session does not exist or the format is
+    /// unsupported.
+    pub fn export_session(
+        &self,
+        session_id: &str,
+        format: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let session = self
+            .get_session(session_id)
+            .ok_or_else(|| format!("Session not found: {session_id}"))?;
+        let timeline = SessionTimeline::new(&session);
+        match format {
+            "json" => Ok(serde_json::to_string_pretty(&timeline)?),
+            "markdown" | "md" => Ok(timeline.to_markdown()),
+            _ => Err("Unsupported format. Use 'json' or 'markdown'".into()),
+        }
+    }
+
+    /// Markdown digest of every session started in the seven days ending at
+    /// `now`, one row per session, newest first.
+    #[must_use]
+    pub fn weekly_sessions_digest(&self, now: DateTime<Utc>) -> String {
+        let week_start = now - Duration::days(7);
+        let summary = self.summary_between(week_start, now);
+        let mut timelines = self.data.read().map_or_else(
+            |_| Vec::new(),
+            |data| {
+                data.sessions
+                    .iter()
+                    .filter(|session| session.started_at >= week_start && session.started_at < now)
+                    .map(SessionTimeline::new)
+                    .collect::<Vec<_>>()
+            },
+        );
+        timelines.sort_by_key(|timeline| std::cmp::Reverse(timeline.started_at));
+
+        let mut out = format!(
+            "# Quality gate sessions\n\n_{} – {}_\n\n",
+            week_start.format("%Y-%m-%d"),
+            now.format("%Y-%m-%d")
+        );
+        let _ = writeln!(
+            out,
+            "**{} sessions:** {} passed, {} failed, {} escalated. Average {:.1} iterations to pass.",
+            summary.total_sessions,
+            summary.passed_sessions,
+            summary.failed_sessions,
+            summary.escalated_sessions,
+            summary.avg_iterations_to_pass
+        );
+        if timelines.is_empty() {
+            out.push_str("\nNo sessions this week.\n");
+            return out;
+        }
+
+        out.push_str(
+            "\n| Session | Spec | Status | Iterations | Final score | Duration |\n\
+             | --- | --- | --- | ---: | ---: | ---: |\n",
+        );
+        for timeline in &timelines {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} | {} |",
+                timeline.session_id,
+                timeline.spec_id,
+                timeline.status,
+                timeline.events.len(),
+                timeline
+                    .events
+                    .last()
+                    .map_or_else(|| "—".to_string(), |event| event.spec_score.to_string()),
+                format_ms(i64::try_from(timeline.total_duration_ms).unwrap_or(i64::MAX)),
+            );
+        }
+
+        let categories = failure_categories(timelines.iter().flat_map(|timeline| &timeline.events));
+        if !categories.is_empty() {
+            out.push_str("\n## Failure categories\n\n");
+            for (category, count) in categories {
+                let _ = writeln!(out, "- {category}: {count}");
+            }
+        }
+        out
+    }
+}
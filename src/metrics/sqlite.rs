@@ -0,0 +1,295 @@
+//! SQLite storage for [`MetricsStore`](super::MetricsStore), behind the
+//! `sqlite` feature.
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::audit::{AuditAction, AuditEntry, AuditQuery};
+use super::backend::{MetricsBackend, MetricsRecord, SessionQuery};
+use super::errors::MetricsError;
+use super::model::{MetricsData, QualityGateSession};
+
+const SQLITE_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS spec_validations (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    spec_id TEXT NOT NULL,
+    record TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS scenario_validations (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    spec_id TEXT NOT NULL,
+    record TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS suggestion_decisions (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    record TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS sessions (
+    session_id TEXT PRIMARY KEY,
+    spec_id TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    record TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS daily_rollups (
+    day TEXT NOT NULL,
+    spec_id TEXT NOT NULL,
+    record TEXT NOT NULL,
+    PRIMARY KEY (day, spec_id)
+);
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    workflow TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    action TEXT NOT NULL,
+    record TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS audit_log_by_workflow ON audit_log (workflow, timestamp);
+CREATE INDEX IF NOT EXISTS sessions_by_spec ON sessions (spec_id, started_at);
+CREATE INDEX IF NOT EXISTS sessions_by_start ON sessions (started_at);
+CREATE INDEX IF NOT EXISTS spec_validations_by_spec ON spec_validations (spec_id, timestamp);
+CREATE INDEX IF NOT EXISTS scenario_validations_by_spec ON scenario_validations (spec_id, timestamp);
+";
+
+/// One row per record in an SQLite database. Each record is kept whole as
+/// JSON next to the columns it is queried by.
+pub struct SqliteBackend {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteBackend {
+    /// Open, or create, the database at `path`.
+    ///
+    /// # Errors
+    /// Returns an error if the database cannot be opened or migrated.
+    pub fn open(path: &Path) -> Result<Self, MetricsError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// A database that lives only as long as the backend.
+    ///
+    /// # Errors
+    /// Returns an error if the database cannot be created.
+    pub fn in_memory() -> Result<Self, MetricsError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, MetricsError> {
+        connection.execute_batch(SQLITE_SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> Result<std::sync::MutexGuard<'_, Connection>, MetricsError> {
+        self.connection
+            .lock()
+            .map_err(|_| MetricsError::LockAcquisition)
+    }
+}
+
+/// Fixed-width UTC timestamps, so text order is time order.
+fn sql_time(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn to_json<T: Serialize>(record: &T) -> Result<String, MetricsError> {
+    serde_json::to_string(record).map_err(MetricsError::ParseError)
+}
+
+fn read_table<T: DeserializeOwned>(
+    connection: &Connection,
+    table: &str,
+) -> Result<Vec<T>, MetricsError> {
+    let mut statement = connection.prepare(&format!(
+        "SELECT record FROM {table} ORDER BY timestamp, id"
+    ))?;
+    let records = statement
+        .query_map([], |row| row.get::<_, String>(0))?
+        .map(|json| serde_json::from_str(&json?).map_err(MetricsError::ParseError))
+        .collect();
+    records
+}
+
+fn insert(connection: &Connection, record: MetricsRecord<'_>) -> Result<(), MetricsError> {
+    match record {
+        MetricsRecord::SpecValidation(metrics) => connection.execute(
+            "INSERT INTO spec_validations (timestamp, spec_id, record) VALUES (?1, ?2, ?3)",
+            params![
+                sql_time(metrics.timestamp),
+                metrics.spec_id.as_str(),
+                to_json(metrics)?
+            ],
+        )?,
+        MetricsRecord::ScenarioValidation(metrics) => connection.execute(
+            "INSERT INTO scenario_validations (timestamp, spec_id, record) VALUES (?1, ?2, ?3)",
+            params![
+                sql_time(metrics.timestamp),
+                metrics.spec_id.as_str(),
+                to_json(metrics)?
+            ],
+        )?,
+        MetricsRecord::SuggestionDecision(metrics) => connection.execute(
+            "INSERT INTO suggestion_decisions (timestamp, record) VALUES (?1, ?2)",
+            params![sql_time(metrics.timestamp), to_json(metrics)?],
+        )?,
+        MetricsRecord::Session(session) => connection.execute(
+            "INSERT INTO sessions (session_id, spec_id, started_at, record)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (session_id) DO UPDATE SET record = excluded.record",
+            params![
+                session.session_id.as_str(),
+                session.spec_id.as_str(),
+                sql_time(session.started_at),
+                to_json(session)?
+            ],
+        )?,
+        MetricsRecord::Audit(entry) => connection.execute(
+            "INSERT INTO audit_log (timestamp, workflow, actor, action, record)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                sql_time(entry.timestamp),
+                entry.workflow,
+                entry.actor,
+                entry.action.as_str(),
+                to_json(entry)?
+            ],
+        )?,
+    };
+    Ok(())
+}
+
+impl MetricsBackend for SqliteBackend {
+    fn load(&self) -> Result<MetricsData, MetricsError> {
+        let (spec_validations, scenario_validations, suggestion_decisions) = {
+            let connection = self.connection()?;
+            (
+                read_table(&connection, "spec_validations")?,
+                read_table(&connection, "scenario_validations")?,
+                read_table(&connection, "suggestion_decisions")?,
+            )
+        };
+        let rollups = {
+            let connection = self.connection()?;
+            let mut statement =
+                connection.prepare("SELECT record FROM daily_rollups ORDER BY day, spec_id")?;
+            let rollups = statement
+                .query_map([], |row| row.get::<_, String>(0))?
+                .map(|json| serde_json::from_str(&json?).map_err(MetricsError::ParseError))
+                .collect::<Result<Vec<_>, _>>()?;
+            rollups
+        };
+        Ok(MetricsData {
+            spec_validations,
+            scenario_validations,
+            suggestion_decisions,
+            sessions: self.sessions(&SessionQuery::default())?,
+            rollups,
+            audit_log: self.audit_entries(&AuditQuery::default())?,
+        })
+    }
+
+    fn record(&self, _data: &MetricsData, record: MetricsRecord<'_>) -> Result<(), MetricsError> {
+        insert(&*self.connection()?, record)
+    }
+
+    fn sessions(&self, query: &SessionQuery) -> Result<Vec<QualityGateSession>, MetricsError> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare(
+            "SELECT record FROM sessions
+             WHERE (?1 IS NULL OR spec_id = ?1)
+               AND (?2 IS NULL OR started_at >= ?2)
+               AND (?3 IS NULL OR started_at < ?3)
+             ORDER BY started_at",
+        )?;
+        let sessions = statement
+            .query_map(
+                params![
+                    query.spec_id,
+                    query.from.map(sql_time),
+                    query.to.map(sql_time)
+                ],
+                |row| row.get::<_, String>(0),
+            )?
+            .map(|json| serde_json::from_str(&json?).map_err(MetricsError::ParseError))
+            .collect();
+        sessions
+    }
+
+    fn audit_entries(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, MetricsError> {
+        let connection = self.connection()?;
+        let mut statement = connection.prepare(
+            "SELECT record FROM audit_log
+             WHERE (?1 IS NULL OR workflow = ?1)
+               AND (?2 IS NULL OR actor = ?2)
+               AND (?3 IS NULL OR action = ?3)
+               AND (?4 IS NULL OR timestamp >= ?4)
+               AND (?5 IS NULL OR timestamp < ?5)
+             ORDER BY timestamp, id",
+        )?;
+        let entries = statement
+            .query_map(
+                params![
+                    query.workflow,
+                    query.actor,
+                    query.action.map(AuditAction::as_str),
+                    query.from.map(sql_time),
+                    query.to.map(sql_time)
+                ],
+                |row| row.get::<_, String>(0),
+            )?
+            .map(|json| serde_json::from_str(&json?).map_err(MetricsError::ParseError))
+            .collect();
+        entries
+    }
+
+    fn replace(&self, data: &MetricsData) -> Result<(), MetricsError> {
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(
+            "DELETE FROM spec_validations;
+             DELETE FROM scenario_validations;
+             DELETE FROM suggestion_decisions;
+             DELETE FROM sessions;
+             DELETE FROM daily_rollups;",
+        )?;
+        let records = data
+            .spec_validations
+            .iter()
+            .map(MetricsRecord::SpecValidation)
+            .chain(
+                data.scenario_validations
+                    .iter()
+                    .map(MetricsRecord::ScenarioValidation),
+            )
+            .chain(
+                data.suggestion_decisions
+                    .iter()
+                    .map(MetricsRecord::SuggestionDecision),
+            )
+            .chain(data.sessions.iter().map(MetricsRecord::Session));
+        for record in records {
+            insert(&transaction, record)?;
+        }
+        for rollup in &data.rollups {
+            transaction.execute(
+                "INSERT INTO daily_rollups (day, spec_id, record) VALUES (?1, ?2, ?3)",
+                params![
+                    sql_time(rollup.day),
+                    rollup.spec_id.as_str(),
+                    to_json(rollup)?
+                ],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
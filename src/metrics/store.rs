@@ -1,8 +1,10 @@
 use chrono::Utc;
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use super::backend::{JsonFileBackend, MetricsBackend, MetricsRecord, SessionQuery};
+use super::errors::MetricsError;
 use super::model::{
     MetricsData, MetricsStore, QualityGateIteration, QualityGateSession, ScenarioValidationMetrics,
     SessionId, SessionStatus, SpecId, SpecValidationMetrics, SpecVersion,
@@ -10,22 +12,17 @@ use super::model::{
 };
 
 impl MetricsStore {
-    /// Creates a new `MetricsStore` backed by the given directory.
+    /// Creates a new `MetricsStore` backed by `metrics.json` in the given
+    /// directory.
     ///
     /// If the directory cannot be created, logs the error and continues.
     /// If persisted data cannot be loaded, starts with default empty data.
     #[must_use]
     pub fn new(base_path: &Path) -> Self {
-        let data_path = base_path.join("quality-metrics");
+        let data_path = Self::data_path(base_path);
+        let backend = JsonFileBackend::new(&data_path.join("metrics.json"));
 
-        match std::fs::create_dir_all(&data_path) {
-            Ok(()) => {}
-            Err(e) => {
-                eprintln!("Warning: could not create metrics directory {data_path:?}: {e}");
-            }
-        }
-
-        let data: MetricsData = match Self::load_data(&data_path) {
+        let data: MetricsData = match backend.load() {
             Ok(loaded) => loaded,
             Err(e) => {
                 eprintln!("Warning: could not load metrics data: {e}");
@@ -36,20 +33,85 @@ impl MetricsStore {
         Self {
             base_path: data_path,
             data: std::sync::Arc::new(std::sync::RwLock::new(data)),
+            backend: Box::new(backend),
         }
     }
 
-    fn load_data(path: &Path) -> Result<MetricsData, Box<dyn std::error::Error>> {
-        let metrics_file = path.join("metrics.json");
-        if metrics_file.exists() {
-            let content = std::fs::read_to_string(&metrics_file)?;
-            Ok(serde_json::from_str(&content)?)
-        } else {
-            Ok(MetricsData::default())
+    /// Creates a `MetricsStore` backed by `metrics.db` in the given
+    /// directory.
+    ///
+    /// # Errors
+    /// Returns an error if the database cannot be opened or loaded.
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(base_path: &Path) -> Result<Self, MetricsError> {
+        let data_path = Self::data_path(base_path);
+        let backend = super::SqliteBackend::open(&data_path.join("metrics.db"))?;
+        Self::with_backend(base_path, Box::new(backend))
+    }
+
+    /// Creates a `MetricsStore` that persists through `backend`, starting
+    /// from the records it already holds.
+    ///
+    /// # Errors
+    /// Returns an error if the backend cannot load its records.
+    pub fn with_backend(
+        base_path: &Path,
+        backend: Box<dyn MetricsBackend>,
+    ) -> Result<Self, MetricsError> {
+        let data = backend.load()?;
+        Ok(Self {
+            base_path: Self::data_path(base_path),
+            data: std::sync::Arc::new(std::sync::RwLock::new(data)),
+            backend,
+        })
+    }
+
+    fn data_path(base_path: &Path) -> PathBuf {
+        let data_path = base_path.join("quality-metrics");
+
+        match std::fs::create_dir_all(&data_path) {
+            Ok(()) => {}
+            Err(e) => {
+                eprintln!("Warning: could not create metrics directory {data_path:?}: {e}");
+            }
         }
+        data_path
+    }
+
+    /// Hand a record that was just applied to the in-memory data to the
+    /// backend.
+    fn persist(&self, record: MetricsRecord<'_>) -> Result<(), MetricsError> {
+        let data = self
+            .data
+            .read()
+            .map_err(|_| MetricsError::LockAcquisition)?;
+        self.backend.record(&data, record)
+    }
+
+    /// Sessions matching `query`, oldest first, as stored by the backend.
+    ///
+    /// # Errors
+    /// Returns an error if the backend cannot read its sessions.
+    pub fn sessions(&self, query: &SessionQuery) -> Result<Vec<QualityGateSession>, MetricsError> {
+        self.backend.sessions(query)
+    }
+
+    /// Every session of `spec_id`, oldest first.
+    ///
+    /// # Errors
+    /// Returns an error if the backend cannot read its sessions.
+    pub fn sessions_for_spec(
+        &self,
+        spec_id: &str,
+    ) -> Result<Vec<QualityGateSession>, MetricsError> {
+        self.sessions(&SessionQuery {
+            spec_id: Some(spec_id.to_string()),
+            ..SessionQuery::default()
+        })
     }
 
-    /// Save data to disk.
+    /// Write a full JSON snapshot of the data to `metrics.json`, whatever
+    /// the backend.
     ///
     /// # Errors
     /// Returns an error if the lock cannot be acquired or writing fails.
@@ -87,9 +149,9 @@ impl MetricsStore {
                 .data
                 .write()
                 .map_err(|e| format!("Failed to acquire lock: {e}"))?;
-            data.spec_validations.push(metrics);
+            data.spec_validations.push(metrics.clone());
         }
-        self.save_data()
+        Ok(self.persist(MetricsRecord::SpecValidation(&metrics))?)
     }
 
     /// Record scenario validation metrics.
@@ -105,9 +167,9 @@ impl MetricsStore {
                 .data
                 .write()
                 .map_err(|e| format!("Failed to acquire lock: {e}"))?;
-            data.scenario_validations.push(metrics);
+            data.scenario_validations.push(metrics.clone());
         }
-        self.save_data()
+        Ok(self.persist(MetricsRecord::ScenarioValidation(&metrics))?)
     }
 
     /// Record extension suggestion acceptance/rejection metrics.
@@ -123,9 +185,9 @@ impl MetricsStore {
                 .data
                 .write()
                 .map_err(|e| format!("Failed to acquire lock: {e}"))?;
-            data.suggestion_decisions.push(metrics);
+            data.suggestion_decisions.push(metrics.clone());
         }
-        self.save_data()
+        Ok(self.persist(MetricsRecord::SuggestionDecision(&metrics))?)
     }
 
     /// Start a new quality gate session.
@@ -141,7 +203,7 @@ impl MetricsStore {
         let session_id_str = session_id.to_string();
         let timestamp = Utc::now();
 
-        {
+        let session = {
             let mut data = self
                 .data
                 .write()
@@ -160,9 +222,10 @@ impl MetricsStore {
                 escalated: false,
             };
 
-            data.sessions.push(session);
-        }
-        self.save_data()?;
+            data.sessions.push(session.clone());
+            session
+        };
+        self.persist(MetricsRecord::Session(&session))?;
 
         Ok(session_id_str)
     }
@@ -176,32 +239,35 @@ impl MetricsStore {
         session_id: &str,
         iteration: QualityGateIteration,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        {
+        let updated = {
             let mut data = self
                 .data
                 .write()
                 .map_err(|e| format!("Failed to acquire lock: {e}"))?;
 
-            if let Some(session) = data
-                .sessions
+            data.sessions
                 .iter_mut()
                 .find(|s| s.session_id.as_str() == session_id)
-            {
-                let passed = iteration.overall_passed;
-                session.iterations.push(iteration);
-
-                let now = Utc::now();
-                if passed {
-                    session.status = SessionStatus::Passed;
-                    session.completed_at = Some(now);
-                } else if session.iterations.len() >= 5 {
-                    session.status = SessionStatus::Failed;
-                    session.completed_at = Some(now);
-                    session.escalated = true;
-                }
-            }
+                .map(|session| {
+                    let passed = iteration.overall_passed;
+                    session.iterations.push(iteration);
+
+                    let now = Utc::now();
+                    if passed {
+                        session.status = SessionStatus::Passed;
+                        session.completed_at = Some(now);
+                    } else if session.iterations.len() >= 5 {
+                        session.status = SessionStatus::Failed;
+                        session.completed_at = Some(now);
+                        session.escalated = true;
+                    }
+                    session.clone()
+                })
+        };
+        if let Some(session) = updated {
+            self.persist(MetricsRecord::Session(&session))?;
         }
-        self.save_data()
+        Ok(())
     }
 
     #[must_use]
@@ -89,3 +89,599 @@ fn test_record_suggestion_decision_persists() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn scenario_run(
+    spec: &str,
+    days_ago: i64,
+    total: usize,
+    passed: usize,
+) -> super::ScenarioValidationMetrics {
+    super::ScenarioValidationMetrics {
+        timestamp: Utc::now() - chrono::Duration::days(days_ago),
+        spec_id: SpecId::new(spec).expect("test spec id is valid"),
+        total_scenarios: total,
+        passed_scenarios: passed,
+        failed_scenarios: total - passed,
+        category_breakdown: HashMap::new(),
+        duration_ms: 100,
+        scenarios: Vec::new(),
+    }
+}
+
+fn lint_run(days_ago: i64, score: u32) -> SpecValidationMetrics {
+    SpecValidationMetrics {
+        timestamp: Utc::now() - chrono::Duration::days(days_ago),
+        spec_id: SpecId::new("test-spec").expect("test spec id is valid"),
+        spec_version: SpecVersion::new("1.0.0").expect("test spec version is valid"),
+        overall_score: score,
+        passed: score >= 80,
+        category_scores: HashMap::new(),
+        errors_count: 0,
+        warnings_count: 0,
+        duration_ms: 100,
+    }
+}
+
+#[test]
+fn given_missing_signals_when_computing_health_then_weights_are_renormalized() {
+    let inputs = super::HealthInputs {
+        lint_score: Some(80.0),
+        coverage: Some(40.0),
+        pass_rate: None,
+        flakiness: None,
+        design_warnings: 2,
+    };
+    let weights = super::HealthWeights {
+        lint: 1.0,
+        coverage: 1.0,
+        pass_rate: 1.0,
+        stability: 1.0,
+        design: 2.0,
+    };
+
+    let health = super::WorkspaceHealth::compute(&inputs, &weights);
+
+    assert_eq!(health.components.len(), 3);
+    let design = health
+        .component(super::HealthSignal::Design)
+        .expect("design is scored");
+    assert_eq!(design.score, 80.0);
+    assert!((design.weight - 0.5).abs() < 1e-9);
+    assert!((health.score - 70.0).abs() < 1e-9);
+}
+
+#[test]
+fn given_two_weeks_of_metrics_when_building_digest_then_trends_and_actions_are_reported(
+) -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let store = MetricsStore::new(temp.path());
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    store
+        .record_spec_validation(lint_run(10, 60))
+        .map_err(record)?;
+    store
+        .record_spec_validation(lint_run(2, 90))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("checkout", 9, 10, 10))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("checkout", 3, 10, 8))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("checkout", 2, 10, 10))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("checkout", 1, 10, 7))
+        .map_err(record)?;
+
+    let digest = store.weekly_digest(Utc::now(), None, 1, &super::HealthWeights::default());
+
+    let lint = digest
+        .trends
+        .iter()
+        .find(|trend| trend.signal == super::HealthSignal::Lint)
+        .expect("lint trend");
+    assert_eq!(lint.delta(), Some(30.0));
+    let stability = digest
+        .health
+        .component(super::HealthSignal::Stability)
+        .expect("stability is scored");
+    assert_eq!(stability.score, 0.0);
+    assert!(digest.previous_score.is_some());
+    assert_eq!(digest.actions[0].signal, super::HealthSignal::Stability);
+    assert!(digest.actions.len() <= 3);
+
+    let markdown = digest.to_markdown();
+    assert!(markdown.contains("# Workspace health digest"));
+    assert!(markdown.contains("| Spec lint score | 90.0 |"));
+    assert!(markdown.contains("+30.0"));
+    let json: serde_json::Value = serde_json::from_str(&digest.to_json()?)?;
+    assert_eq!(json["trends"][0]["signal"], "lint");
+
+    Ok(())
+}
+
+#[test]
+fn given_empty_store_when_building_digest_then_only_present_signals_are_scored(
+) -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let store = MetricsStore::new(temp.path());
+
+    let digest = store.weekly_digest(Utc::now(), None, 0, &super::HealthWeights::default());
+
+    assert_eq!(digest.health.components.len(), 1);
+    assert_eq!(digest.health.score, 100.0);
+    assert!(digest.previous_score.is_none());
+    assert!(digest.actions.is_empty());
+    assert!(digest.to_markdown().contains("No action needed this week."));
+    Ok(())
+}
+
+fn session(spec: &str, days_ago: i64) -> super::QualityGateSession {
+    super::QualityGateSession {
+        session_id: super::model::SessionId::new(),
+        spec_id: SpecId::new(spec).expect("test spec id is valid"),
+        spec_version: SpecVersion::new("1.0.0").expect("test spec version is valid"),
+        started_at: Utc::now() - chrono::Duration::days(days_ago),
+        completed_at: None,
+        iterations: Vec::new(),
+        total_duration_ms: 0,
+        status: super::SessionStatus::InProgress,
+        escalated: false,
+    }
+}
+
+/// One store per backend under `root`: JSON always, SQLite with the
+/// `sqlite` feature. Calling it again reopens the same stores.
+fn stores_by_backend(root: &Path) -> anyhow::Result<Vec<MetricsStore>> {
+    let json = MetricsStore::new(&root.join("json"));
+    #[cfg(feature = "sqlite")]
+    return Ok(vec![json, MetricsStore::sqlite(&root.join("sqlite"))?]);
+    #[cfg(not(feature = "sqlite"))]
+    Ok(vec![json])
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn given_sqlite_store_when_reopened_then_records_and_session_updates_survive() -> anyhow::Result<()>
+{
+    let temp = tempfile::tempdir()?;
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    let session_id = {
+        let store = MetricsStore::sqlite(temp.path())?;
+        store
+            .record_spec_validation(lint_run(1, 85))
+            .map_err(record)?;
+        let session_id = store.start_session("checkout", "1.0.0").map_err(record)?;
+        store
+            .record_iteration(
+                &session_id,
+                super::QualityGateIteration {
+                    iteration: super::model::IterationNumber::new(1),
+                    timestamp: Utc::now(),
+                    spec_passed: true,
+                    spec_score: 85,
+                    scenarios_passed: true,
+                    scenarios_total: 3,
+                    scenarios_passed_count: 3,
+                    overall_passed: true,
+                    failure_category: None,
+                    feedback_level: super::model::FeedbackLevel::default(),
+                    duration_ms: 10,
+                },
+            )
+            .map_err(record)?;
+        session_id
+    };
+
+    let store = MetricsStore::sqlite(temp.path())?;
+
+    assert!(temp.path().join("quality-metrics/metrics.db").exists());
+    assert!(!temp.path().join("quality-metrics/metrics.json").exists());
+    let session = store
+        .get_session(&session_id)
+        .expect("session was reloaded");
+    assert_eq!(session.status, super::SessionStatus::Passed);
+    assert_eq!(session.iterations.len(), 1);
+    assert_eq!(store.get_summary().avg_spec_score, 85.0);
+    Ok(())
+}
+
+#[test]
+fn given_sessions_when_querying_by_spec_and_time_range_then_backends_agree() -> anyhow::Result<()> {
+    use super::{JsonFileBackend, MetricsBackend, MetricsRecord, SessionQuery};
+
+    let temp = tempfile::tempdir()?;
+    let backends: Vec<Box<dyn MetricsBackend>> = vec![
+        Box::new(JsonFileBackend::new(&temp.path().join("metrics.json"))),
+        #[cfg(feature = "sqlite")]
+        Box::new(super::SqliteBackend::in_memory()?),
+    ];
+    let sessions = [
+        session("checkout", 10),
+        session("checkout", 3),
+        session("checkout", 1),
+        session("refunds", 2),
+    ];
+
+    for backend in &backends {
+        let mut data = super::MetricsData::default();
+        for session in &sessions {
+            data.sessions.push(session.clone());
+            backend.record(&data, MetricsRecord::Session(session))?;
+        }
+
+        let recent_checkouts = backend.sessions(&SessionQuery {
+            spec_id: Some("checkout".to_string()),
+            from: Some(Utc::now() - chrono::Duration::days(7)),
+            to: Some(Utc::now() - chrono::Duration::hours(36)),
+        })?;
+        let everything = backend.sessions(&SessionQuery::default())?;
+
+        assert_eq!(recent_checkouts, vec![sessions[1].clone()]);
+        assert_eq!(everything.len(), 4);
+        assert_eq!(everything[0], sessions[0]);
+        assert_eq!(everything[1], sessions[1]);
+        assert_eq!(everything[2], sessions[3]);
+    }
+    Ok(())
+}
+
+#[test]
+fn given_bucket_when_aligning_then_days_and_monday_weeks_start_at_midnight() {
+    use super::TrendBucket;
+    use chrono::TimeZone;
+
+    let thursday = Utc.with_ymd_and_hms(2026, 3, 12, 15, 30, 0).unwrap();
+
+    assert_eq!(
+        TrendBucket::Day.start_of(thursday),
+        Utc.with_ymd_and_hms(2026, 3, 12, 0, 0, 0).unwrap()
+    );
+    assert_eq!(
+        TrendBucket::Week.start_of(thursday),
+        Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap()
+    );
+    assert_eq!("week".parse::<TrendBucket>(), Ok(TrendBucket::Week));
+    assert!("month".parse::<TrendBucket>().is_err());
+}
+
+#[test]
+fn given_runs_across_days_when_trending_a_spec_then_buckets_average_scores_and_pass_rates(
+) -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let store = MetricsStore::new(temp.path());
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    store
+        .record_spec_validation(lint_run(3, 60))
+        .map_err(record)?;
+    store
+        .record_spec_validation(lint_run(3, 80))
+        .map_err(record)?;
+    store
+        .record_spec_validation(lint_run(1, 90))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("test-spec", 1, 10, 8))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("test-spec", 1, 10, 6))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("other-spec", 2, 10, 0))
+        .map_err(record)?;
+
+    let trend = store.score_trend("test-spec", super::TrendBucket::Day);
+
+    assert_eq!(trend.len(), 2);
+    assert_eq!(trend[0].lint_runs, 2);
+    assert_eq!(trend[0].avg_score, Some(70.0));
+    assert_eq!(trend[0].scenario_runs, 0);
+    assert_eq!(trend[0].pass_rate, None);
+    assert_eq!(trend[1].avg_score, Some(90.0));
+    assert_eq!(trend[1].scenario_runs, 2);
+    assert_eq!(trend[1].pass_rate, Some(70.0));
+    assert!(trend[0].start < trend[1].start);
+    Ok(())
+}
+
+#[test]
+fn given_sessions_over_time_when_summarizing_a_window_then_only_it_is_counted() -> anyhow::Result<()>
+{
+    let temp = tempfile::tempdir()?;
+    let store = MetricsStore::new(temp.path());
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    store
+        .record_spec_validation(lint_run(10, 50))
+        .map_err(record)?;
+    store
+        .record_spec_validation(lint_run(1, 90))
+        .map_err(record)?;
+    let checkout = store.start_session("checkout", "1.0.0").map_err(record)?;
+    store.start_session("refunds", "1.0.0").map_err(record)?;
+
+    let now = Utc::now();
+    let last_week = store.summary_between(now - chrono::Duration::days(7), now);
+    let last_month = store.summary_between(
+        now - chrono::Duration::days(30),
+        now - chrono::Duration::days(7),
+    );
+
+    assert_eq!(last_week.total_sessions, 2);
+    assert_eq!(last_week.avg_spec_score, 90.0);
+    assert_eq!(last_month.total_sessions, 0);
+    assert_eq!(last_month.avg_spec_score, 50.0);
+    assert_eq!(store.get_summary().avg_spec_score, 70.0);
+    let checkouts = store.sessions_for_spec("checkout")?;
+    assert_eq!(checkouts.len(), 1);
+    assert_eq!(checkouts[0].session_id.as_str(), checkout);
+    Ok(())
+}
+
+#[test]
+fn given_recorded_metrics_when_exporting_openmetrics_then_families_are_exposed(
+) -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let store = MetricsStore::new(temp.path());
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    store
+        .record_spec_validation(lint_run(3, 60))
+        .map_err(record)?;
+    store
+        .record_spec_validation(lint_run(1, 85))
+        .map_err(record)?;
+    store
+        .record_scenario_validation(scenario_run("test-spec", 1, 4, 3))
+        .map_err(record)?;
+    store.start_session("checkout", "1.0.0").map_err(record)?;
+
+    let exposition = store.to_openmetrics();
+    let lines = exposition.lines().collect::<Vec<_>>();
+
+    assert!(lines.contains(&"# TYPE oya_quality_gate_sessions counter"));
+    assert!(lines.contains(&"oya_quality_gate_sessions_total{status=\"in_progress\"} 1"));
+    assert!(lines.contains(&"oya_quality_gate_sessions_total{status=\"passed\"} 0"));
+    assert!(lines.contains(&"oya_quality_gate_session_iterations_bucket{le=\"1.0\"} 1"));
+    assert!(lines.contains(&"oya_quality_gate_session_iterations_count 1"));
+    assert!(lines.contains(&"oya_validation_duration_seconds_bucket{kind=\"spec\",le=\"0.1\"} 2"));
+    assert!(lines.contains(&"oya_validation_duration_seconds_count{kind=\"scenario\"} 1"));
+    assert!(lines.contains(&"oya_validation_duration_seconds_sum{kind=\"spec\"} 0.2"));
+    assert!(lines.contains(&"oya_spec_score{spec=\"test-spec\"} 85"));
+    assert_eq!(lines.last(), Some(&"# EOF"));
+    assert_eq!(
+        store.export_report("openmetrics").map_err(record)?,
+        exposition
+    );
+    Ok(())
+}
+
+fn outcome(scenario: &str, passed: bool, duration_ms: u64) -> super::ScenarioOutcome {
+    super::ScenarioOutcome {
+        scenario_id: scenario.to_string(),
+        passed,
+        duration_ms,
+        behavior_refs: vec![format!("{scenario}-behavior")],
+        failed_assertion_categories: if passed {
+            Vec::new()
+        } else {
+            vec!["status".to_string()]
+        },
+    }
+}
+
+#[test]
+fn given_per_scenario_outcomes_when_analyzing_failures_then_flaky_slow_and_failing_are_ranked(
+) -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let store = MetricsStore::new(temp.path());
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    for (days_ago, flaky_passed) in [(3, true), (2, false), (1, true)] {
+        let mut run = scenario_run("checkout", days_ago, 3, 1);
+        run.scenarios = vec![
+            outcome("pay", flaky_passed, 100),
+            outcome("refund", false, 900),
+            outcome("browse", true, 50),
+        ];
+        store.record_scenario_validation(run).map_err(record)?;
+    }
+    store
+        .record_scenario_validation(scenario_run("checkout", 0, 1, 1))
+        .map_err(record)?;
+
+    let analytics = store.failure_analytics(2);
+
+    assert_eq!(analytics.flaky_scenarios.len(), 1);
+    let pay = &analytics.flaky_scenarios[0];
+    assert_eq!(pay.scenario_id, "pay");
+    assert_eq!((pay.runs, pay.failures), (3, 1));
+    assert_eq!(pay.flip_rate, Some(100.0));
+    let slowest = analytics
+        .slowest_scenarios
+        .iter()
+        .map(|stats| stats.scenario_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(slowest, vec!["refund", "pay"]);
+    let refund = &analytics.failing_behaviors[0];
+    assert_eq!(refund.behavior_ref, "refund-behavior");
+    assert_eq!(refund.failure_rate, 100.0);
+    assert_eq!(refund.assertion_categories, vec![("status".to_string(), 3)]);
+    assert_eq!(analytics.failing_behaviors[1].behavior_ref, "pay-behavior");
+    Ok(())
+}
+
+fn iteration(number: u32, score: u32, category: Option<&str>) -> super::QualityGateIteration {
+    super::QualityGateIteration {
+        iteration: super::model::IterationNumber::new(number),
+        timestamp: Utc::now(),
+        spec_passed: score >= 80,
+        spec_score: score,
+        scenarios_passed: category.is_none(),
+        scenarios_total: 4,
+        scenarios_passed_count: if category.is_none() { 4 } else { 2 },
+        overall_passed: category.is_none(),
+        failure_category: category.map(super::model::FailureCategoryName::new),
+        feedback_level: super::model::FeedbackLevel::default(),
+        duration_ms: 1500,
+    }
+}
+
+#[test]
+fn given_session_with_iterations_when_exporting_then_timeline_and_markdown_describe_it(
+) -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let store = MetricsStore::new(temp.path());
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    let session_id = store.start_session("checkout", "1.0.0").map_err(record)?;
+    store
+        .record_iteration(&session_id, iteration(1, 70, Some("timeout")))
+        .map_err(record)?;
+    store
+        .record_iteration(&session_id, iteration(2, 90, None))
+        .map_err(record)?;
+
+    let timeline: super::SessionTimeline =
+        serde_json::from_str(&store.export_session(&session_id, "json").map_err(record)?)?;
+    let markdown = store
+        .export_session(&session_id, "markdown")
+        .map_err(record)?;
+    let digest = store.weekly_sessions_digest(Utc::now() + chrono::Duration::seconds(1));
+
+    assert_eq!(timeline.status, "passed");
+    assert_eq!(timeline.events.len(), 2);
+    assert_eq!(
+        timeline.events[0].failure_category.as_deref(),
+        Some("timeout")
+    );
+    assert!(timeline.events[1].elapsed_ms >= 0);
+    assert!(markdown.contains("- **Spec:** checkout v1.0.0"));
+    assert!(markdown.contains("| 1 | "));
+    assert!(markdown.contains("| 70 | 2/4 | fail | timeout | 3 | 1.5s |"));
+    assert!(markdown.contains("- timeout: 1"));
+    assert!(digest.contains("**1 sessions:** 1 passed, 0 failed, 0 escalated."));
+    assert!(digest.contains(&format!("| `{session_id}` | checkout | passed | 2 | 90 |")));
+    assert!(store.export_session("missing", "json").is_err());
+    assert!(store.export_session(&session_id, "pdf").is_err());
+    Ok(())
+}
+
+#[test]
+fn given_retention_policy_when_compacting_then_old_records_become_daily_rollups(
+) -> anyhow::Result<()> {
+    let temp = tempfile::tempdir()?;
+    let record = |err: Box<dyn std::error::Error>| anyhow::anyhow!(err.to_string());
+    let finished = |days_ago: i64| super::QualityGateSession {
+        status: super::SessionStatus::Passed,
+        ..session("test-spec", days_ago)
+    };
+    let policy = super::RetentionPolicy {
+        max_sessions: Some(1),
+        max_age: Some(chrono::Duration::days(30)),
+    };
+    let now = Utc::now();
+
+    for store in stores_by_backend(temp.path())? {
+        store
+            .record_spec_validation(lint_run(40, 60))
+            .map_err(record)?;
+        store
+            .record_spec_validation(lint_run(40, 80))
+            .map_err(record)?;
+        store
+            .record_spec_validation(lint_run(1, 90))
+            .map_err(record)?;
+        store
+            .record_scenario_validation(scenario_run("test-spec", 40, 4, 3))
+            .map_err(record)?;
+        let data = {
+            let mut data = store
+                .data
+                .write()
+                .map_err(|_| anyhow::anyhow!("lock poisoned"))?;
+            data.sessions = vec![finished(2), finished(1), session("test-spec", 50)];
+            data.clone()
+        };
+        store.backend.replace(&data)?;
+
+        let report = store.compact(&policy, now)?;
+
+        assert_eq!(report.spec_validations_removed, 2);
+        assert_eq!(report.scenario_validations_removed, 1);
+        assert_eq!(report.sessions_removed, 1);
+        let rollups = store.daily_rollups();
+        assert_eq!(rollups.len(), 2);
+        let old = &rollups[0];
+        assert_eq!((old.lint_runs, old.avg_score()), (2, Some(70.0)));
+        assert_eq!(old.pass_rate(), Some(75.0));
+        assert_eq!((rollups[1].sessions, rollups[1].passed_sessions), (1, 1));
+        assert_eq!(store.get_summary().total_sessions, 2);
+        assert_eq!(store.compact(&policy, now)?.removed(), 0);
+    }
+
+    for reopened in stores_by_backend(temp.path())? {
+        assert_eq!(reopened.daily_rollups().len(), 2);
+        assert_eq!(reopened.sessions(&super::SessionQuery::default())?.len(), 2);
+    }
+    Ok(())
+}
+
+#[test]
+fn given_audit_entries_when_compacting_and_reopening_then_log_is_kept_and_queryable(
+) -> anyhow::Result<()> {
+    use super::{AuditAction, AuditEntry, AuditQuery};
+
+    let temp = tempfile::tempdir()?;
+    let at = |days_ago: i64, entry: AuditEntry| AuditEntry {
+        timestamp: Utc::now() - chrono::Duration::days(days_ago),
+        actor: "ana".to_string(),
+        ..entry
+    };
+    let policy = super::RetentionPolicy {
+        max_sessions: None,
+        max_age: Some(chrono::Duration::days(1)),
+    };
+    for store in stores_by_backend(temp.path())? {
+        store.record_audit(at(
+            9,
+            AuditEntry::new(AuditAction::Import, "flows/checkout.json"),
+        ))?;
+        store.record_audit(at(
+            5,
+            AuditEntry::new(AuditAction::ExtensionApplied, "flows/checkout.json")
+                .with_detail("add-retry"),
+        ))?;
+        store.record_audit(at(
+            2,
+            AuditEntry::new(AuditAction::Run, "flows/checkout.json").with_success(false),
+        ))?;
+        store.record_audit(at(
+            1,
+            AuditEntry::new(AuditAction::Run, "flows/refunds.json"),
+        ))?;
+        store
+            .record_spec_validation(lint_run(40, 60))
+            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+        assert!(store.compact(&policy, Utc::now())?.removed() > 0);
+    }
+
+    for store in stores_by_backend(temp.path())? {
+        assert_eq!(store.audit_log(&AuditQuery::default())?.len(), 4);
+        let last_run = store
+            .last_execution("flows/checkout.json")?
+            .expect("checkout ran");
+        assert_eq!(last_run.success, Some(false));
+        let last_change = store
+            .last_change("flows/checkout.json")?
+            .expect("checkout was changed");
+        assert_eq!(last_change.action, AuditAction::ExtensionApplied);
+        assert_eq!(last_change.detail.as_deref(), Some("add-retry"));
+        let recent = store.audit_log(&AuditQuery {
+            actor: Some("ana".to_string()),
+            from: Some(Utc::now() - chrono::Duration::days(3)),
+            ..AuditQuery::default()
+        })?;
+        assert_eq!(recent.len(), 2);
+        assert!(store.last_change("flows/refunds.json")?.is_none());
+    }
+    Ok(())
+}
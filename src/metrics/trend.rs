@@ -0,0 +1,116 @@
+//! Time-bucketed score and pass-rate series for one spec.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::health::{mean, percentage};
+use super::model::MetricsStore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendBucket {
+    /// Calendar days, UTC.
+    Day,
+    /// Weeks starting on Monday, UTC.
+    Week,
+}
+
+impl TrendBucket {
+    /// Start of the bucket `timestamp` falls in.
+    #[must_use]
+    pub fn start_of(self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let day = timestamp.date_naive();
+        let day = match self {
+            Self::Day => day,
+            Self::Week => day - Duration::days(i64::from(day.weekday().num_days_from_monday())),
+        };
+        day.and_time(chrono::NaiveTime::MIN).and_utc()
+    }
+}
+
+impl FromStr for TrendBucket {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            _ => Err(format!(
+                "Unknown trend bucket: {value}. Use 'day' or 'week'"
+            )),
+        }
+    }
+}
+
+/// One bucket of a spec's trend. Averages are `None` when the bucket has no
+/// runs of that kind.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub start: DateTime<Utc>,
+    pub lint_runs: usize,
+    pub avg_score: Option<f64>,
+    pub scenario_runs: usize,
+    /// Passed scenarios as a percentage of all scenarios run in the bucket.
+    pub pass_rate: Option<f64>,
+}
+
+impl MetricsStore {
+    /// Lint score and scenario pass rate of `spec_id` per bucket, oldest
+    /// first. Buckets without any runs are left out.
+    #[must_use]
+    pub fn score_trend(&self, spec_id: &str, bucket: TrendBucket) -> Vec<TrendPoint> {
+        let Ok(data) = self.data.read() else {
+            return Vec::new();
+        };
+
+        let mut scores: BTreeMap<DateTime<Utc>, Vec<f64>> = BTreeMap::new();
+        for validation in data
+            .spec_validations
+            .iter()
+            .filter(|validation| validation.spec_id.as_str() == spec_id)
+        {
+            scores
+                .entry(bucket.start_of(validation.timestamp))
+                .or_default()
+                .push(f64::from(validation.overall_score));
+        }
+
+        // (runs, scenarios passed, scenarios total) per bucket.
+        let mut runs: BTreeMap<DateTime<Utc>, (usize, usize, usize)> = BTreeMap::new();
+        for validation in data
+            .scenario_validations
+            .iter()
+            .filter(|validation| validation.spec_id.as_str() == spec_id)
+        {
+            let entry = runs
+                .entry(bucket.start_of(validation.timestamp))
+                .or_default();
+            entry.0 += 1;
+            entry.1 += validation.passed_scenarios;
+            entry.2 += validation.total_scenarios;
+        }
+
+        let starts = scores
+            .keys()
+            .chain(runs.keys())
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>();
+        starts
+            .into_iter()
+            .map(|start| {
+                let scores = scores.get(&start).map_or(&[][..], Vec::as_slice);
+                let (scenario_runs, passed, total) = runs.get(&start).copied().unwrap_or_default();
+                TrendPoint {
+                    start,
+                    lint_runs: scores.len(),
+                    avg_score: mean(scores),
+                    scenario_runs,
+                    pass_rate: percentage(passed, total),
+                }
+            })
+            .collect()
+    }
+}
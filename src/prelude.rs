@@ -0,0 +1,57 @@
+//! Stable API for crates that automate workflows, specs and scenarios.
+//!
+//! ```
+//! use oya_frontend::prelude::*;
+//! ```
+//!
+//! Everything re-exported here follows semver: a name is only removed,
+//! renamed or given an incompatible signature in a major release. Internal
+//! module paths (`oya_frontend::graph::core`, `oya_frontend::flow_extender::…`)
+//! are not covered and may move between minor releases, so depend on the
+//! prelude instead.
+//!
+//! The facade groups six capabilities:
+//!
+//! - **Workflow building**: [`Workflow`], [`Node`], [`Connection`],
+//!   [`validate_workflow`] and [`compile_workflow`].
+//! - **Linting**: [`SpecLinter`] and its [`LintReport`].
+//! - **Coverage**: [`CoverageAnalyzer`], [`CoverageReport`] and
+//!   [`CoverageThresholds`].
+//! - **Scenario running**: [`ScenarioRunner`], [`run_validation`] and
+//!   [`run_validation_with`].
+//! - **Extension planning**: [`suggest_extensions`], [`preview_extension`],
+//!   [`apply_extension`] and compound plans.
+//! - **Event hooks**: [`subscribe`] to [`WorkflowEvent`]s for analytics or
+//!   side effects in a host application.
+//!
+//! Linting, coverage and scenario running need the filesystem or network, so
+//! they are not available on `wasm32`.
+
+pub use crate::graph::compile::{compile_workflow, CompileReport, CompileStatus, SeverityGate};
+pub use crate::graph::events::{subscribe, Subscription, WorkflowEvent};
+pub use crate::graph::{
+    validate_workflow, Connection, ConnectionResult, GraphConnectionError, Node, NodeCategory,
+    NodeId, PortName, ValidationIssue, ValidationResult, ValidationSeverity, Workflow,
+    WorkflowNode,
+};
+
+pub use crate::flow_extender::{
+    apply_compound_plan, apply_extension, generate_compound_plan, preview_extension,
+    revert_extension, suggest_extensions, AppliedCompoundPlan, AppliedExtension,
+    CompoundApplyError, CompoundExtensionPlan, ExtensionPatchPreview, ExtensionPriority,
+    FlowExtension,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::coverage::{
+    CoverageAnalyzer, CoverageError, CoverageReport, CoverageThresholds, PolicyOutcome,
+    ReferenceIssues, ReportFormat, SpecCoverage, ThresholdViolation,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::linter::{LintError, LintIssue, LintReport, Spec, SpecLinter};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::scenario_runner::{
+    run_validation, run_validation_with, scaffold_scenarios, RunOptions, RunnerConfig, Scenario,
+    ScenarioError, ScenarioFilter, ScenarioResult, ScenarioRunner, ValidationReport,
+    ValidationReportFormat,
+};
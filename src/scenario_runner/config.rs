@@ -0,0 +1,238 @@
+//! HTTP client settings for scenario runs.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use super::types::ScenarioError;
+
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_CONNECT_RETRY_DELAY: Duration = Duration::from_millis(200);
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// How scenario runners talk HTTP to the application and its twins.
+///
+/// Runners built from equal configs share one pooled client, so repeated
+/// runs in the same process reuse open connections.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RunnerConfig {
+    connect_timeout: Duration,
+    request_timeout: Option<Duration>,
+    proxy: Option<String>,
+    ca_certificates: Vec<PathBuf>,
+    accept_invalid_certs: bool,
+    connect_retries: u32,
+    connect_retry_delay: Duration,
+    pool_idle_timeout: Duration,
+    pool_max_idle_per_host: Option<usize>,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+            proxy: None,
+            ca_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            connect_retries: 0,
+            connect_retry_delay: DEFAULT_CONNECT_RETRY_DELAY,
+            pool_idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+            pool_max_idle_per_host: None,
+        }
+    }
+}
+
+impl RunnerConfig {
+    #[must_use]
+    pub const fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Budget for each request, from connecting to reading the body;
+    /// `None` waits indefinitely.
+    #[must_use]
+    pub const fn with_request_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Send every request through this proxy URL.
+    #[must_use]
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Also trust the PEM certificate at `path`, e.g. the CA that signs a
+    /// twin's TLS certificate.
+    #[must_use]
+    pub fn with_ca_certificate(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_certificates.push(path.into());
+        self
+    }
+
+    /// Skip TLS certificate verification. Only for local twins.
+    #[must_use]
+    pub const fn with_invalid_certs_accepted(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Resend a request up to `retries` more times when connecting fails.
+    /// Requests that reached the server are never resent.
+    #[must_use]
+    pub const fn with_connect_retries(mut self, retries: u32) -> Self {
+        self.connect_retries = retries;
+        self
+    }
+
+    /// Pause between connection attempts.
+    #[must_use]
+    pub const fn with_connect_retry_delay(mut self, delay: Duration) -> Self {
+        self.connect_retry_delay = delay;
+        self
+    }
+
+    /// How long idle pooled connections stay open, and how many to keep
+    /// per host; `None` keeps as many as were opened.
+    #[must_use]
+    pub const fn with_pool(
+        mut self,
+        idle_timeout: Duration,
+        max_idle_per_host: Option<usize>,
+    ) -> Self {
+        self.pool_idle_timeout = idle_timeout;
+        self.pool_max_idle_per_host = max_idle_per_host;
+        self
+    }
+
+    /// A new client with these settings.
+    ///
+    /// # Errors
+    /// Returns an error if a CA certificate cannot be read or parsed, the
+    /// proxy URL is invalid, or the TLS backend fails to initialise.
+    pub fn build_client(&self) -> Result<reqwest::Client, ScenarioError> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if let Some(timeout) = self.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(proxy) = &self.proxy {
+            builder =
+                builder.proxy(reqwest::Proxy::all(proxy).map_err(ScenarioError::ClientConfig)?);
+        }
+        for path in &self.ca_certificates {
+            builder = builder.add_root_certificate(read_certificate(path)?);
+        }
+        builder.build().map_err(ScenarioError::ClientConfig)
+    }
+
+    /// The process-wide client for these settings, built on first use.
+    ///
+    /// # Errors
+    /// Returns an error when the client has to be built and that fails; see
+    /// [`Self::build_client`].
+    pub fn shared_client(&self) -> Result<reqwest::Client, ScenarioError> {
+        let clients = shared_clients();
+        if let Some(client) = clients.lock().ok().and_then(|c| c.get(self).cloned()) {
+            return Ok(client);
+        }
+        let client = self.build_client()?;
+        if let Ok(mut clients) = clients.lock() {
+            return Ok(clients.entry(self.clone()).or_insert(client).clone());
+        }
+        Ok(client)
+    }
+}
+
+fn shared_clients() -> &'static Mutex<HashMap<RunnerConfig, reqwest::Client>> {
+    static CLIENTS: OnceLock<Mutex<HashMap<RunnerConfig, reqwest::Client>>> = OnceLock::new();
+    CLIENTS.get_or_init(Mutex::default)
+}
+
+fn read_certificate(path: &Path) -> Result<reqwest::Certificate, ScenarioError> {
+    let pem = std::fs::read(path).map_err(|source| ScenarioError::ReadCertificate {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    reqwest::Certificate::from_pem(&pem).map_err(ScenarioError::ClientConfig)
+}
+
+/// Sends `request`, resending it while connecting fails and `config` allows
+/// more attempts.
+pub(super) async fn send_with_retry(
+    client: &reqwest::Client,
+    request: reqwest::Request,
+    config: &RunnerConfig,
+) -> reqwest::Result<reqwest::Response> {
+    let mut retries_left = config.connect_retries;
+    loop {
+        // Streaming bodies cannot be cloned; those are sent once.
+        let Some(retry) = request.try_clone().filter(|_| retries_left > 0) else {
+            return client.execute(request).await;
+        };
+        match client.execute(retry).await {
+            Err(error) if error.is_connect() => {
+                retries_left -= 1;
+                tokio::time::sleep(config.connect_retry_delay).await;
+            }
+            outcome => return outcome,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_equal_configs_when_sharing_clients_then_the_client_is_cached_once() {
+        let config = RunnerConfig::default().with_pool(Duration::from_secs(7), Some(3));
+
+        config.shared_client().unwrap();
+        config.clone().shared_client().unwrap();
+
+        let clients = shared_clients().lock().unwrap();
+        assert!(clients.contains_key(&config));
+        assert!(!clients.contains_key(&config.clone().with_pool(Duration::from_secs(7), None)));
+    }
+
+    #[test]
+    fn given_unreadable_ca_certificate_when_building_then_it_returns_typed_error() {
+        let config = RunnerConfig::default().with_ca_certificate("/nonexistent/twin-ca.pem");
+
+        let error = config.build_client().unwrap_err();
+
+        assert!(matches!(error, ScenarioError::ReadCertificate { .. }));
+    }
+
+    #[tokio::test]
+    async fn given_refused_connection_when_sending_then_it_retries_then_fails() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+        let config = RunnerConfig::default()
+            .with_connect_retries(2)
+            .with_connect_retry_delay(Duration::from_millis(20));
+        let client = config.build_client().unwrap();
+
+        let started = std::time::Instant::now();
+        let request = client.get(&url).build().unwrap();
+        let error = send_with_retry(&client, request, &config)
+            .await
+            .unwrap_err();
+
+        assert!(error.is_connect());
+        assert!(started.elapsed() >= Duration::from_millis(40));
+    }
+}
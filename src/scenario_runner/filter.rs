@@ -0,0 +1,179 @@
+//! Selecting which scenarios a run executes.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::types::ScenarioIdentity;
+
+/// Which scenarios a run executes. Empty lists place no constraint;
+/// categories, priorities and tags compare case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priorities: Vec<String>,
+    /// A scenario must carry at least one of these tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_tags: Vec<String>,
+    /// Scenarios carrying any of these tags are skipped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_tags: Vec<String>,
+    /// Glob over scenario ids; `*` matches any run of characters, `?` one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_glob: Option<String>,
+}
+
+impl ScenarioFilter {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+            && self.priorities.is_empty()
+            && self.include_tags.is_empty()
+            && self.exclude_tags.is_empty()
+            && self.id_glob.is_none()
+    }
+
+    #[must_use]
+    pub fn matches(&self, identity: &ScenarioIdentity) -> bool {
+        let listed = |list: &[String], value: &str| {
+            list.is_empty() || list.iter().any(|item| item.eq_ignore_ascii_case(value))
+        };
+        let tagged = |tags: &[String]| {
+            identity
+                .tags
+                .iter()
+                .any(|tag| tags.iter().any(|wanted| wanted.eq_ignore_ascii_case(tag)))
+        };
+
+        listed(&self.categories, &identity.category)
+            && listed(&self.priorities, &identity.priority)
+            && (self.include_tags.is_empty() || tagged(&self.include_tags))
+            && !tagged(&self.exclude_tags)
+            && self
+                .id_glob
+                .as_deref()
+                .is_none_or(|glob| glob_matches(glob, &identity.id))
+    }
+}
+
+impl fmt::Display for ScenarioFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lists = [
+            ("category", &self.categories),
+            ("priority", &self.priorities),
+            ("tag", &self.include_tags),
+            ("not tag", &self.exclude_tags),
+        ];
+        let mut parts = lists
+            .iter()
+            .filter(|(_, values)| !values.is_empty())
+            .map(|(label, values)| format!("{label} in [{}]", values.join(", ")))
+            .collect::<Vec<_>>();
+        if let Some(glob) = &self.id_glob {
+            parts.push(format!("id ~ {glob}"));
+        }
+        if parts.is_empty() {
+            write!(f, "all scenarios")
+        } else {
+            write!(f, "{}", parts.join("; "))
+        }
+    }
+}
+
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let glob = glob.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    // Position after the last `*` and the text index it was retried from.
+    let mut backtrack: Option<(usize, usize)> = None;
+    let (mut g, mut t) = (0, 0);
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g + 1, t));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((after_star, from)) => {
+                    g = after_star;
+                    t = from + 1;
+                    backtrack = Some((after_star, from + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    fn identity(id: &str, category: &str, priority: &str, tags: &[&str]) -> ScenarioIdentity {
+        ScenarioIdentity {
+            id: id.to_string(),
+            spec_ref: "spec".to_string(),
+            spec_version: "1.0.0".to_string(),
+            category: category.to_string(),
+            visibility: "open".to_string(),
+            priority: priority.to_string(),
+            description: String::new(),
+            rationale: String::new(),
+            tags: tags.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn given_globs_when_matching_ids_then_star_and_question_mark_expand() {
+        assert!(glob_matches("checkout-*", "checkout-happy-path"));
+        assert!(glob_matches("*-refund-?", "order-refund-2"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("checkout-*", "cart-checkout"));
+        assert!(!glob_matches("order-?", "order-10"));
+    }
+
+    #[test]
+    fn given_filter_when_matching_then_every_constraint_must_hold() {
+        let filter = ScenarioFilter {
+            categories: vec!["Security".to_string()],
+            include_tags: vec!["smoke".to_string(), "auth".to_string()],
+            exclude_tags: vec!["slow".to_string()],
+            id_glob: Some("login-*".to_string()),
+            ..ScenarioFilter::default()
+        };
+
+        assert!(filter.matches(&identity("login-lockout", "security", "high", &["auth"])));
+        assert!(!filter.matches(&identity("login-lockout", "happy-path", "high", &["auth"])));
+        assert!(!filter.matches(&identity("login-lockout", "security", "high", &[])));
+        assert!(!filter.matches(&identity(
+            "login-lockout",
+            "security",
+            "high",
+            &["auth", "slow"]
+        )));
+        assert!(!filter.matches(&identity("signup", "security", "high", &["auth"])));
+        assert!(ScenarioFilter::default().matches(&identity("any", "any", "low", &[])));
+    }
+
+    #[test]
+    fn given_filter_when_displaying_then_only_set_constraints_are_listed() {
+        let filter = ScenarioFilter {
+            categories: vec!["security".to_string()],
+            exclude_tags: vec!["slow".to_string()],
+            ..ScenarioFilter::default()
+        };
+
+        assert_eq!(
+            filter.to_string(),
+            "category in [security]; not tag in [slow]"
+        );
+        assert_eq!(ScenarioFilter::default().to_string(), "all scenarios");
+    }
+}
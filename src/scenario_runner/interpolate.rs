@@ -0,0 +1,274 @@
+//! `${...}` placeholders in step actions and assertions.
+//!
+//! - `${application.endpoint}` is the application under test.
+//! - `${extracted.<name>}` is a value an earlier step extracted.
+//! - `${twins.<name>.endpoint}` is a digital twin the run was given.
+//!
+//! A JSON string that is exactly one placeholder becomes the variable's JSON
+//! value, so `"${extracted.order}"` can stand for an object or a number.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+
+use serde_json::Value;
+
+use super::types::{Assertion, ScenarioError, StepAction};
+
+pub(super) struct Variables<'a, S = RandomState> {
+    pub application_endpoint: &'a str,
+    pub extracted: &'a HashMap<String, Value>,
+    pub twins: &'a HashMap<String, String, S>,
+}
+
+impl<S: BuildHasher> Variables<'_, S> {
+    fn lookup(&self, variable: &str) -> Result<Value, ScenarioError> {
+        let missing = || ScenarioError::MissingVariable(variable.to_string());
+        match variable.split_once('.') {
+            Some(("application", "endpoint")) => {
+                Ok(Value::String(self.application_endpoint.to_string()))
+            }
+            Some(("extracted", name)) => self.extracted.get(name).cloned().ok_or_else(missing),
+            Some(("twins", twin)) => match twin.rsplit_once('.') {
+                Some((name, "endpoint")) => self
+                    .twins
+                    .get(name)
+                    .map(|endpoint| Value::String(endpoint.clone()))
+                    .ok_or_else(missing),
+                _ => Err(missing()),
+            },
+            _ => Err(missing()),
+        }
+    }
+
+    /// Substitute every placeholder in `text`.
+    ///
+    /// # Errors
+    /// Returns `ScenarioError::MissingVariable` for an unknown or unset
+    /// variable, or `ScenarioError::InvalidPlaceholder` for an unclosed `${`.
+    pub fn text(&self, text: &str) -> Result<String, ScenarioError> {
+        let mut rendered = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            let Some(len) = rest[start..].find('}') else {
+                return Err(ScenarioError::InvalidPlaceholder(text.to_string()));
+            };
+            rendered.push_str(&rest[..start]);
+            match self.lookup(rest[start + 2..start + len].trim())? {
+                Value::String(value) => rendered.push_str(&value),
+                value => rendered.push_str(&value.to_string()),
+            }
+            rest = &rest[start + len + 1..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+
+    /// Substitute placeholders in every string inside `value`.
+    ///
+    /// # Errors
+    /// See [`Self::text`].
+    pub fn value(&self, value: &Value) -> Result<Value, ScenarioError> {
+        match value {
+            Value::String(text) => match whole_placeholder(text) {
+                Some(variable) => self.lookup(variable),
+                None => self.text(text).map(Value::String),
+            },
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.value(item))
+                .collect::<Result<_, _>>()
+                .map(Value::Array),
+            Value::Object(fields) => fields
+                .iter()
+                .map(|(key, item)| Ok((self.text(key)?, self.value(item)?)))
+                .collect::<Result<_, ScenarioError>>()
+                .map(Value::Object),
+            other => Ok(other.clone()),
+        }
+    }
+
+    fn map(
+        &self,
+        map: Option<&HashMap<String, String>>,
+    ) -> Result<Option<HashMap<String, String>>, ScenarioError> {
+        map.map(|map| {
+            map.iter()
+                .map(|(key, value)| Ok((key.clone(), self.text(value)?)))
+                .collect()
+        })
+        .transpose()
+    }
+
+    /// The action with its URL, headers, params, body, key and workflow
+    /// path resolved.
+    ///
+    /// # Errors
+    /// See [`Self::text`].
+    pub fn action(&self, action: &StepAction) -> Result<StepAction, ScenarioError> {
+        Ok(StepAction {
+            url: action
+                .url
+                .as_deref()
+                .map(|url| self.text(url))
+                .transpose()?,
+            headers: self.map(action.headers.as_ref())?,
+            body: action
+                .body
+                .as_ref()
+                .map(|body| self.value(body))
+                .transpose()?,
+            params: self.map(action.params.as_ref())?,
+            key: action
+                .key
+                .as_deref()
+                .map(|key| self.text(key))
+                .transpose()?,
+            workflow: action
+                .workflow
+                .as_deref()
+                .map(|workflow| self.text(workflow))
+                .transpose()?,
+            ..action.clone()
+        })
+    }
+
+    /// The assertion with its expected value and twin match resolved.
+    ///
+    /// # Errors
+    /// See [`Self::text`].
+    pub fn assertion(&self, assertion: &Assertion) -> Result<Assertion, ScenarioError> {
+        Ok(Assertion {
+            expected: assertion
+                .expected
+                .as_ref()
+                .map(|expected| self.value(expected))
+                .transpose()?,
+            matches: assertion
+                .matches
+                .as_ref()
+                .map(|matches| self.value(matches))
+                .transpose()?,
+            ..assertion.clone()
+        })
+    }
+}
+
+fn whole_placeholder(text: &str) -> Option<&str> {
+    text.strip_prefix("${")
+        .and_then(|inner| inner.strip_suffix('}'))
+        .filter(|inner| !inner.contains('}'))
+        .map(str::trim)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extracted() -> HashMap<String, Value> {
+        HashMap::from([
+            ("order_id".to_string(), json!(42)),
+            ("token".to_string(), json!("abc")),
+            ("order".to_string(), json!({ "id": 42, "total": 9.5 })),
+        ])
+    }
+
+    #[test]
+    fn given_extracted_values_when_interpolating_action_then_url_headers_params_and_body_resolve() {
+        let extracted = extracted();
+        let variables = Variables {
+            application_endpoint: "http://app",
+            extracted: &extracted,
+            twins: &HashMap::new(),
+        };
+        let action: StepAction = serde_yaml::from_str(
+            r#"
+type: http
+method: GET
+url: "${application.endpoint}/orders/${extracted.order_id}"
+headers:
+  Authorization: "Bearer ${extracted.token}"
+params:
+  id: "${extracted.order_id}"
+body:
+  order: "${extracted.order}"
+  note: "order ${extracted.order_id}"
+"#,
+        )
+        .unwrap();
+
+        let resolved = variables.action(&action).unwrap();
+
+        assert_eq!(resolved.url.as_deref(), Some("http://app/orders/42"));
+        assert_eq!(resolved.headers.unwrap()["Authorization"], "Bearer abc");
+        assert_eq!(resolved.params.unwrap()["id"], "42");
+        assert_eq!(
+            resolved.body,
+            Some(json!({ "order": { "id": 42, "total": 9.5 }, "note": "order 42" }))
+        );
+    }
+
+    #[test]
+    fn given_assertion_with_placeholder_when_interpolating_then_expected_keeps_json_type() {
+        let extracted = extracted();
+        let variables = Variables {
+            application_endpoint: "http://app",
+            extracted: &extracted,
+            twins: &HashMap::new(),
+        };
+        let assertion: Assertion = serde_yaml::from_str(
+            r#"
+type: body_json
+path: /id
+expected: "${extracted.order_id}"
+"#,
+        )
+        .unwrap();
+
+        let resolved = variables.assertion(&assertion).unwrap();
+
+        assert_eq!(resolved.expected, Some(json!(42)));
+    }
+
+    #[test]
+    fn given_missing_variable_when_interpolating_then_error_names_it() {
+        let extracted = extracted();
+        let variables = Variables {
+            application_endpoint: "http://app",
+            extracted: &extracted,
+            twins: &HashMap::new(),
+        };
+
+        let missing = variables
+            .text("/orders/${extracted.refund_id}")
+            .unwrap_err();
+        let unclosed = variables.text("/orders/${extracted.order_id").unwrap_err();
+
+        assert_eq!(
+            missing.to_string(),
+            "Unknown scenario variable: ${extracted.refund_id}"
+        );
+        assert!(matches!(unclosed, ScenarioError::InvalidPlaceholder(_)));
+    }
+
+    #[test]
+    fn given_twins_when_interpolating_then_twin_endpoints_resolve_by_name() {
+        let extracted = extracted();
+        let twins = HashMap::from([("stripe".to_string(), "http://twin:9901".to_string())]);
+        let variables = Variables {
+            application_endpoint: "http://app",
+            extracted: &extracted,
+            twins: &twins,
+        };
+
+        let url = variables.text("${twins.stripe.endpoint}/charges").unwrap();
+        let unknown = variables.text("${twins.paypal.endpoint}").unwrap_err();
+
+        assert_eq!(url, "http://twin:9901/charges");
+        assert_eq!(
+            unknown.to_string(),
+            "Unknown scenario variable: ${twins.paypal.endpoint}"
+        );
+    }
+}
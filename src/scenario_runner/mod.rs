@@ -1,9 +1,19 @@
+mod config;
+mod filter;
+mod interpolate;
+mod render;
 mod runner;
+mod scaffold;
 mod types;
 
-pub use runner::{run_validation, ScenarioRunner};
+pub use config::RunnerConfig;
+pub use filter::ScenarioFilter;
+pub use render::ValidationReportFormat;
+pub use runner::{run_validation, run_validation_with, ScenarioRunner};
+pub use scaffold::{scaffold_scenarios, skeleton_scenarios};
 pub use types::{
-    ActionResult, Assertion, CategoryResult, Extraction, Precondition, Scenario, ScenarioError,
-    ScenarioIdentity, ScenarioResult, ScenarioSetup, ScenarioStep, ScenarioTeardown, StepAction,
-    StepResult, ValidationReport,
+    ActionResult, Assertion, AssertionFailure, CategoryResult, Extraction, HttpExchange,
+    Precondition, RecordedRequest, RecordedResponse, RetryPolicy, RunOptions, Scenario,
+    ScenarioError, ScenarioIdentity, ScenarioResult, ScenarioSetup, ScenarioStep, ScenarioTeardown,
+    StepAction, StepResult, ValidationReport,
 };
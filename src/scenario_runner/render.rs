@@ -0,0 +1,331 @@
+//! Renderers that turn a [`ValidationReport`] into files test-reporting
+//! pipelines understand.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::types::{ScenarioError, ScenarioResult, StepResult, ValidationReport};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationReportFormat {
+    Json,
+    /// JUnit XML, one `<testsuite>` per scenario category.
+    JUnit,
+    /// A single page with inline styles and no external assets.
+    Html,
+}
+
+impl ValidationReportFormat {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::JUnit => "junit",
+            Self::Html => "html",
+        }
+    }
+
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::JUnit => "xml",
+            Self::Html => "html",
+        }
+    }
+}
+
+impl FromStr for ValidationReportFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(Self::Json),
+            "junit" | "xml" => Ok(Self::JUnit),
+            "html" => Ok(Self::Html),
+            _ => Err(format!("Unknown report format: {value}")),
+        }
+    }
+}
+
+impl ValidationReport {
+    /// Render the report in the given format.
+    ///
+    /// # Errors
+    /// Returns an error if JSON serialization fails.
+    pub fn render(&self, format: ValidationReportFormat) -> Result<String, ScenarioError> {
+        match format {
+            ValidationReportFormat::Json => serde_json::to_string_pretty(self)
+                .map(|json| json + "\n")
+                .map_err(ScenarioError::RenderError),
+            ValidationReportFormat::JUnit => Ok(render_junit(self)),
+            ValidationReportFormat::Html => Ok(render_html(self)),
+        }
+    }
+
+    /// Render the report and write it to `path`.
+    ///
+    /// # Errors
+    /// Returns an error if rendering or writing the file fails.
+    pub fn write_to(
+        &self,
+        format: ValidationReportFormat,
+        path: &Path,
+    ) -> Result<(), ScenarioError> {
+        let rendered = self.render(format)?;
+        fs::write(path, rendered).map_err(|source| ScenarioError::WriteError {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// Results grouped by category, categories sorted, results in run order.
+fn by_category(report: &ValidationReport) -> BTreeMap<&str, Vec<&ScenarioResult>> {
+    report
+        .results
+        .iter()
+        .fold(BTreeMap::new(), |mut groups, result| {
+            groups
+                .entry(result.category.as_str())
+                .or_insert_with(Vec::new)
+                .push(result);
+            groups
+        })
+}
+
+/// Milliseconds as the seconds JUnit expects, e.g. `1.250`.
+fn seconds(ms: u64) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
+
+fn failed_step(result: &ScenarioResult) -> Option<&StepResult> {
+    result.steps.iter().find(|step| !step.passed)
+}
+
+/// Why a scenario failed, in one line.
+fn failure_message(result: &ScenarioResult) -> String {
+    result
+        .error
+        .clone()
+        .or_else(|| {
+            failed_step(result).map(|step| {
+                format!(
+                    "Step {} failed: {}",
+                    step.step_id,
+                    step.error.as_deref().unwrap_or("assertion failed")
+                )
+            })
+        })
+        .unwrap_or_else(|| "Scenario failed".to_string())
+}
+
+fn step_line(step: &StepResult) -> String {
+    format!(
+        "{} {} ({}ms, {}/{} assertions){}",
+        if step.passed { "PASS" } else { "FAIL" },
+        step.step_id,
+        step.duration_ms,
+        step.assertions_passed,
+        step.assertions_passed + step.assertions_failed,
+        step.error
+            .as_deref()
+            .map(|error| format!(": {error}"))
+            .unwrap_or_default()
+    )
+}
+
+fn render_junit(report: &ValidationReport) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let total_ms = report
+        .results
+        .iter()
+        .map(|result| result.total_duration_ms)
+        .sum::<u64>();
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{}\">",
+        escape_xml(&report.spec_id),
+        report.total_scenarios,
+        report.failed_scenarios,
+        seconds(total_ms)
+    );
+
+    for (category, results) in by_category(report) {
+        let failures = results.iter().filter(|result| !result.passed).count();
+        let suite_ms = results
+            .iter()
+            .map(|result| result.total_duration_ms)
+            .sum::<u64>();
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" time=\"{}\">",
+            escape_xml(category),
+            results.len(),
+            seconds(suite_ms)
+        );
+        if let Some(filter) = &report.filter {
+            let _ = writeln!(
+                out,
+                "    <properties>\n      <property name=\"filter\" value=\"{}\"/>\n      <property name=\"skipped_scenarios\" value=\"{}\"/>\n    </properties>",
+                escape_xml(&filter.to_string()),
+                report.skipped_scenarios
+            );
+        }
+        for result in results {
+            let _ = write!(
+                out,
+                "    <testcase name=\"{}\" classname=\"{}.{}\" time=\"{}\"",
+                escape_xml(&result.scenario_id),
+                escape_xml(&result.spec_ref),
+                escape_xml(category),
+                seconds(result.total_duration_ms)
+            );
+            if result.passed {
+                out.push_str("/>\n");
+                continue;
+            }
+            out.push_str(">\n");
+            let details = result
+                .steps
+                .iter()
+                .map(step_line)
+                .collect::<Vec<_>>()
+                .join("\n");
+            let _ = writeln!(
+                out,
+                "      <failure message=\"{}\">{}</failure>",
+                escape_xml(&failure_message(result)),
+                escape_xml(&details)
+            );
+            out.push_str("    </testcase>\n");
+        }
+        out.push_str("  </testsuite>\n");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#0f172a}\
+table{border-collapse:collapse;width:100%;margin-bottom:1.5rem}\
+th,td{border:1px solid #e2e8f0;padding:.4rem .6rem;text-align:left}\
+th{background:#f8fafc}td.num{text-align:right;font-variant-numeric:tabular-nums}\
+tr.failed{background:#fef2f2}.error{color:#b91c1c}\
+.bar{height:.6rem;background:#38bdf8;min-width:2px}tr.failed .bar{background:#f87171}\
+.summary{font-size:1.1rem;margin-bottom:1rem}";
+
+fn render_html(report: &ValidationReport) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n");
+    let _ = writeln!(
+        out,
+        "<meta charset=\"utf-8\">\n<title>Scenario Validation: {}</title>",
+        escape_xml(&report.spec_id)
+    );
+    let _ = writeln!(out, "<style>{HTML_STYLE}</style>");
+    let _ = writeln!(
+        out,
+        "</head>\n<body>\n<h1>Scenario Validation: {}</h1>",
+        escape_xml(&report.spec_id)
+    );
+    let _ = writeln!(
+        out,
+        "<p class=\"summary\"><strong>{}/{}</strong> scenarios passed &middot; {} failed</p>",
+        report.passed_scenarios, report.total_scenarios, report.failed_scenarios
+    );
+    if let Some(filter) = &report.filter {
+        let _ = writeln!(
+            out,
+            "<p>Filter: {} &middot; {} skipped</p>",
+            escape_xml(&filter.to_string()),
+            report.skipped_scenarios
+        );
+    }
+
+    out.push_str("<h2>Categories</h2>\n<table>\n<thead><tr><th>Category</th><th>Passed</th><th>Failed</th><th>Total</th></tr></thead>\n<tbody>\n");
+    let categories = report.category_breakdown.iter().collect::<BTreeMap<_, _>>();
+    for (category, result) in categories {
+        let _ = writeln!(
+            out,
+            "<tr{}><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            if result.failed > 0 { " class=\"failed\"" } else { "" },
+            escape_xml(category),
+            result.passed,
+            result.failed,
+            result.total
+        );
+    }
+    out.push_str("</tbody>\n</table>\n");
+
+    for result in &report.results {
+        let _ = writeln!(
+            out,
+            "<h2>{} {}</h2>",
+            if result.passed { "✅" } else { "❌" },
+            escape_xml(&result.scenario_id)
+        );
+        let _ = writeln!(
+            out,
+            "<p>{} &middot; {} &middot; {}ms</p>",
+            escape_xml(&result.spec_ref),
+            escape_xml(&result.category),
+            result.total_duration_ms
+        );
+        if let Some(error) = &result.error {
+            let _ = writeln!(out, "<p class=\"error\">{}</p>", escape_xml(error));
+        }
+        if result.steps.is_empty() {
+            continue;
+        }
+
+        // Each bar starts where the previous step ended.
+        let total_ms = result
+            .steps
+            .iter()
+            .map(|step| step.duration_ms)
+            .sum::<u64>()
+            .max(1);
+        let mut offset_ms = 0_u64;
+        out.push_str("<table>\n<thead><tr><th>Step</th><th>Timeline</th><th>Duration</th><th>Assertions</th><th>Failure</th></tr></thead>\n<tbody>\n");
+        for step in &result.steps {
+            let _ = writeln!(
+                out,
+                "<tr{}><td><code>{}</code></td><td><div class=\"bar\" style=\"margin-left:{}%;width:{}%\"></div></td><td class=\"num\">{}ms</td><td class=\"num\">{}/{}</td><td class=\"error\">{}</td></tr>",
+                if step.passed { "" } else { " class=\"failed\"" },
+                escape_xml(&step.step_id),
+                offset_ms.saturating_mul(100) / total_ms,
+                step.duration_ms.saturating_mul(100) / total_ms,
+                step.duration_ms,
+                step.assertions_passed,
+                step.assertions_passed + step.assertions_failed,
+                escape_xml(step.error.as_deref().unwrap_or_default())
+            );
+            offset_ms = offset_ms.saturating_add(step.duration_ms);
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Escapes text for XML attributes and content, which also makes it safe
+/// in HTML.
+fn escape_xml(value: &str) -> String {
+    value
+        .chars()
+        .fold(String::with_capacity(value.len()), |mut out, ch| {
+            match ch {
+                '&' => out.push_str("&amp;"),
+                '<' => out.push_str("&lt;"),
+                '>' => out.push_str("&gt;"),
+                '"' => out.push_str("&quot;"),
+                '\'' => out.push_str("&apos;"),
+                _ => out.push(ch),
+            }
+            out
+        })
+}
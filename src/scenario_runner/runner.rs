@@ -1,31 +1,76 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+use tracing::Instrument;
+
+use crate::graph::Workflow;
+use crate::headless::telemetry::trace_context_headers;
+
+use super::config::{send_with_retry, RunnerConfig};
+use super::filter::ScenarioFilter;
+use super::interpolate::Variables;
 use super::types::{
-    ActionResult, Assertion, CategoryResult, Extraction, Scenario, ScenarioError, ScenarioResult,
+    ActionResult, Assertion, AssertionFailure, CategoryResult, Extraction, HttpExchange,
+    RecordedRequest, RecordedResponse, RunOptions, Scenario, ScenarioError, ScenarioResult,
     ScenarioStep, StepAction, StepResult, ValidationReport,
 };
 
+const DEFAULT_POLL_INTERVAL_MS: u64 = 500;
+const DEFAULT_POLL_TIMEOUT_MS: u64 = 10_000;
+
 pub struct ScenarioRunner<S = std::hash::RandomState> {
     http_client: reqwest::Client,
+    config: RunnerConfig,
     application_endpoint: String,
-    #[allow(dead_code)]
     twin_endpoints: HashMap<String, String, S>,
     extracted_values: HashMap<String, serde_json::Value>,
+    record_transcripts: bool,
 }
 
 impl<S: std::hash::BuildHasher + Send + Sync> ScenarioRunner<S> {
+    /// A runner using the shared client for [`RunnerConfig::default`].
     #[must_use]
     pub fn new(application_endpoint: &str, twins: HashMap<String, String, S>) -> Self {
+        let config = RunnerConfig::default();
         Self {
-            http_client: reqwest::Client::new(),
+            http_client: config.shared_client().unwrap_or_default(),
+            config,
             application_endpoint: application_endpoint.to_string(),
             twin_endpoints: twins,
             extracted_values: HashMap::new(),
+            record_transcripts: false,
         }
     }
 
+    /// Talk HTTP with `config`, through the pooled client shared by every
+    /// runner with the same settings.
+    ///
+    /// # Errors
+    /// Returns an error if the client cannot be built; see
+    /// [`RunnerConfig::build_client`].
+    pub fn with_config(mut self, config: RunnerConfig) -> Result<Self, ScenarioError> {
+        self.http_client = config.shared_client()?;
+        self.config = config;
+        Ok(self)
+    }
+
+    /// Record every HTTP exchange into the step results' `transcript`.
+    #[must_use]
+    pub fn with_transcripts(mut self, record: bool) -> Self {
+        self.record_transcripts = record;
+        self
+    }
+
+    #[tracing::instrument(
+        name = "scenario.run",
+        skip_all,
+        fields(scenario.id = %scenario.scenario.id, passed = tracing::field::Empty)
+    )]
     pub async fn run_scenario(&mut self, scenario: &Scenario) -> ScenarioResult {
         let start = std::time::Instant::now();
         let mut step_results = Vec::new();
@@ -41,31 +86,102 @@ impl<S: std::hash::BuildHasher + Send + Sync> ScenarioRunner<S> {
             step_results.push(step_result);
         }
 
-        let duration = u64::try_from(start.elapsed().as_millis()).map_or(u64::MAX, |value| value);
+        tracing::Span::current().record("passed", passed);
         ScenarioResult {
             scenario_id: scenario.scenario.id.clone(),
             spec_ref: scenario.scenario.spec_ref.clone(),
             category: scenario.scenario.category.clone(),
             passed,
             steps: step_results,
-            total_duration_ms: duration,
+            total_duration_ms: elapsed_ms(start),
             error: None,
         }
     }
 
+    #[tracing::instrument(
+        name = "scenario.step",
+        skip_all,
+        fields(step.id = %step.id, passed = tracing::field::Empty)
+    )]
     async fn execute_step(&mut self, step: &ScenarioStep) -> StepResult {
+        let start = std::time::Instant::now();
+        let attempts = step.retry.map_or(1, |retry| retry.attempts.max(1));
+        let mut attempt = 1;
+        let mut earlier_exchanges = Vec::new();
+        loop {
+            let mut result = self.attempt_step(step).await;
+            if result.passed || attempt >= attempts {
+                if let Some(transcript) = &mut result.transcript {
+                    earlier_exchanges.append(transcript);
+                    *transcript = earlier_exchanges;
+                }
+                if attempt > 1 {
+                    result.error = result
+                        .error
+                        .map(|e| format!("{e} (after {attempt} attempts)"));
+                }
+                result.duration_ms = elapsed_ms(start);
+                tracing::Span::current().record("passed", result.passed);
+                return result;
+            }
+            earlier_exchanges.extend(result.transcript.unwrap_or_default());
+            attempt += 1;
+            if let Some(retry) = step.retry {
+                tokio::time::sleep(Duration::from_millis(retry.delay_ms)).await;
+            }
+        }
+    }
+
+    async fn attempt_step(&mut self, step: &ScenarioStep) -> StepResult {
         let start = std::time::Instant::now();
         let mut assertions_passed = 0;
         let mut assertions_failed = 0;
         let mut error = None;
 
-        let action_result = self.execute_action(&step.action).await;
+        let variables = Variables {
+            application_endpoint: &self.application_endpoint,
+            extracted: &self.extracted_values,
+            twins: &self.twin_endpoints,
+        };
+        let action = match variables.action(&step.action) {
+            Ok(action) => action,
+            Err(e) => {
+                return StepResult {
+                    step_id: step.id.clone(),
+                    passed: false,
+                    duration_ms: elapsed_ms(start),
+                    assertions_passed,
+                    assertions_failed: step.assertions.len(),
+                    error: Some(e.to_string()),
+                    transcript: self.record_transcripts.then(Vec::new),
+                    failed_assertions: Vec::new(),
+                };
+            }
+        };
+        let assertions = step
+            .assertions
+            .iter()
+            .map(|assertion| variables.assertion(assertion))
+            .collect::<Vec<_>>();
+
+        let mut transcript = Vec::new();
+        let (action_result, outcomes) = if action.action_type == "poll" {
+            self.poll(&action, &assertions, &mut transcript).await
+        } else {
+            let action_result = self.execute_action(&action, &mut transcript).await;
+            let outcomes = self.check_assertions(&action_result, &assertions).await;
+            (action_result, outcomes)
+        };
 
-        for assertion in &step.assertions {
-            match Self::check_assertion(&action_result, assertion) {
+        let mut failed_assertions = Vec::new();
+        for (assertion, outcome) in assertions.iter().zip(outcomes) {
+            match outcome {
                 Ok(()) => assertions_passed += 1,
                 Err(e) => {
                     assertions_failed += 1;
+                    if let Ok(assertion) = assertion {
+                        failed_assertions.push(assertion_failure(&action_result, assertion, &e));
+                    }
                     error = Some(e);
                 }
             }
@@ -75,82 +191,292 @@ impl<S: std::hash::BuildHasher + Send + Sync> ScenarioRunner<S> {
             self.extract_value(&action_result, extraction);
         }
 
-        let duration = u64::try_from(start.elapsed().as_millis()).map_or(u64::MAX, |value| value);
         StepResult {
             step_id: step.id.clone(),
             passed: assertions_failed == 0,
-            duration_ms: duration,
+            duration_ms: elapsed_ms(start),
             assertions_passed,
             assertions_failed,
             error,
+            transcript: self.record_transcripts.then_some(transcript),
+            failed_assertions,
         }
     }
 
-    async fn execute_action(&self, action: &StepAction) -> ActionResult {
-        match action.action_type.as_str() {
-            "http" => {
-                let client = &self.http_client;
-                let url = action.url.as_ref().map_or_else(String::new, |value| {
-                    value.replace("${application.endpoint}", &self.application_endpoint)
-                });
+    /// Repeat the request until every assertion holds or the action's
+    /// `timeout_ms` elapses.
+    async fn poll(
+        &self,
+        action: &StepAction,
+        assertions: &[Result<Assertion, ScenarioError>],
+        transcript: &mut Vec<HttpExchange>,
+    ) -> (ActionResult, Vec<Result<(), String>>) {
+        let interval =
+            Duration::from_millis(action.interval_ms.unwrap_or(DEFAULT_POLL_INTERVAL_MS));
+        let timeout_ms = action.timeout_ms.unwrap_or(DEFAULT_POLL_TIMEOUT_MS);
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+        let mut requests = 0;
+        loop {
+            requests += 1;
+            let action_result = self.send_http(action, transcript).await;
+            let outcomes = self.check_assertions(&action_result, assertions).await;
+            // An assertion that failed to interpolate will never pass.
+            if outcomes.iter().all(Result::is_ok) || assertions.iter().any(Result::is_err) {
+                return (action_result, outcomes);
+            }
+            if Instant::now() + interval > deadline {
+                let outcomes = outcomes
+                    .into_iter()
+                    .map(|outcome| {
+                        outcome.map_err(|e| {
+                            format!("{e} (gave up after {requests} polls in {timeout_ms}ms)")
+                        })
+                    })
+                    .collect();
+                return (action_result, outcomes);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
 
-                if url.is_empty() {
-                    return ActionResult {
+    async fn execute_action(
+        &self,
+        action: &StepAction,
+        transcript: &mut Vec<HttpExchange>,
+    ) -> ActionResult {
+        match action.action_type.as_str() {
+            "http" | "poll" => self.send_http(action, transcript).await,
+            "grpc" => match grpc_request(action) {
+                Ok(request) => self.send_http(&request, transcript).await,
+                Err(message) => ActionResult {
+                    status: 0,
+                    body: message,
+                    response_time_ms: 0,
+                },
+            },
+            "kafka-publish" => match kafka_publish_request(action) {
+                Ok(request) => self.send_http(&request, transcript).await,
+                Err(message) => ActionResult {
+                    status: 0,
+                    body: message,
+                    response_time_ms: 0,
+                },
+            },
+            "workflow" => run_workflow(action).await,
+            "wait" => match action.duration_ms {
+                Some(duration_ms) => {
+                    tokio::time::sleep(Duration::from_millis(duration_ms)).await;
+                    ActionResult {
                         status: 0,
-                        body: "Missing URL for http action".to_string(),
-                        response_time_ms: 0,
-                    };
+                        body: String::new(),
+                        response_time_ms: duration_ms,
+                    }
                 }
+                None => ActionResult {
+                    status: 0,
+                    body: "Missing duration_ms for wait action".to_string(),
+                    response_time_ms: 0,
+                },
+            },
+            _ => ActionResult {
+                status: 0,
+                body: format!("Unknown action type: {}", action.action_type),
+                response_time_ms: 0,
+            },
+        }
+    }
 
-                let method = action.method.as_deref().map_or("GET", |value| value);
+    /// Send the action's request, appending the exchange to `transcript`
+    /// when the runner records transcripts.
+    async fn send_http(
+        &self,
+        action: &StepAction,
+        transcript: &mut Vec<HttpExchange>,
+    ) -> ActionResult {
+        let client = &self.http_client;
+        let url = action.url.clone().unwrap_or_default();
 
-                let mut req = match method {
-                    "POST" => client.post(&url),
-                    "PUT" => client.put(&url),
-                    "DELETE" => client.delete(&url),
-                    _ => client.get(&url),
-                };
+        if url.is_empty() {
+            return ActionResult {
+                status: 0,
+                body: "Missing URL for http action".to_string(),
+                response_time_ms: 0,
+            };
+        }
 
-                if let Some(headers) = &action.headers {
-                    for (key, value) in headers {
-                        req = req.header(key, value);
-                    }
-                }
+        let method = action.method.as_deref().map_or("GET", |value| value);
+
+        let mut req = match method {
+            "POST" => client.post(&url),
+            "PUT" => client.put(&url),
+            "DELETE" => client.delete(&url),
+            _ => client.get(&url),
+        };
+
+        for (key, value) in trace_context_headers() {
+            req = req.header(key, value);
+        }
+        if let Some(headers) = &action.headers {
+            for (key, value) in headers {
+                req = req.header(key, value);
+            }
+        }
+
+        if let Some(params) = &action.params {
+            req = req.query(params);
+        }
+
+        if let Some(body) = &action.body {
+            req = req.json(body);
+        }
 
-                if let Some(body) = &action.body {
-                    req = req.json(body);
+        let request = match req.build() {
+            Ok(request) => request,
+            Err(e) => {
+                return ActionResult {
+                    status: 0,
+                    body: e.to_string(),
+                    response_time_ms: 0,
                 }
+            }
+        };
+        let recorded = self.record_transcripts.then(|| RecordedRequest {
+            method: request.method().to_string(),
+            url: redact_url(request.url()),
+            headers: redact_headers(request.headers()),
+            body: action.body.as_ref().map(redact_json),
+        });
 
-                match req.send().await {
-                    Ok(response) => {
-                        let status = response.status().as_u16();
-                        let body = match response.text().await {
-                            Ok(text) => text,
-                            Err(e) => format!("<failed to read response body: {e}>"),
-                        };
-                        ActionResult {
-                            status,
-                            body,
-                            response_time_ms: 0,
+        let start = std::time::Instant::now();
+        let (result, response) = match send_with_retry(client, request, &self.config).await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let headers = redact_headers(response.headers());
+                let body = match response.text().await {
+                    Ok(text) => text,
+                    Err(e) => format!("<failed to read response body: {e}>"),
+                };
+                let latency_ms = elapsed_ms(start);
+                let recorded = RecordedResponse {
+                    status,
+                    headers,
+                    body: Some(redact_body(&body)),
+                    latency_ms,
+                };
+                let result = ActionResult {
+                    status,
+                    body,
+                    response_time_ms: latency_ms,
+                };
+                (result, Ok(recorded))
+            }
+            Err(e) => {
+                let result = ActionResult {
+                    status: 0,
+                    body: e.to_string(),
+                    response_time_ms: elapsed_ms(start),
+                };
+                (result, Err(e.to_string()))
+            }
+        };
+
+        if let Some(request) = recorded {
+            let (response, error) = match response {
+                Ok(response) => (Some(response), None),
+                Err(error) => (None, Some(error)),
+            };
+            transcript.push(HttpExchange {
+                request,
+                response,
+                error,
+            });
+        }
+        result
+    }
+
+    async fn check_assertions(
+        &self,
+        result: &ActionResult,
+        assertions: &[Result<Assertion, ScenarioError>],
+    ) -> Vec<Result<(), String>> {
+        let mut outcomes = Vec::with_capacity(assertions.len());
+        for assertion in assertions {
+            outcomes.push(match assertion {
+                Err(e) => Err(e.to_string()),
+                Ok(assertion) if assertion.assertion_type == "twin_state" => {
+                    match (assertion.twin.as_deref(), assertion.collection.as_deref()) {
+                        (Some(twin), Some(collection)) => {
+                            self.check_twin_state(twin, collection, assertion).await
                         }
+                        _ => Err("twin_state assertion needs a twin and a collection".to_string()),
                     }
-                    Err(e) => ActionResult {
-                        status: 0,
-                        body: e.to_string(),
-                        response_time_ms: 0,
-                    },
                 }
+                Ok(assertion) if assertion.assertion_type == "event_consumed" => {
+                    match (assertion.twin.as_deref(), assertion.topic.as_deref()) {
+                        (Some(twin), Some(topic)) => {
+                            let collection = format!("events/{topic}");
+                            self.check_twin_state(twin, &collection, assertion).await
+                        }
+                        _ => Err("event_consumed assertion needs a twin and a topic".to_string()),
+                    }
+                }
+                Ok(assertion) => Self::check_assertion(result, assertion),
+            });
+        }
+        outcomes
+    }
+
+    /// Check a twin collection, read from `GET <twin>/__twin/<collection>`,
+    /// against the assertion's `count` and `matches`. Events a twin consumed
+    /// from a topic are listed as the `events/<topic>` collection.
+    async fn check_twin_state(
+        &self,
+        twin: &str,
+        collection: &str,
+        assertion: &Assertion,
+    ) -> Result<(), String> {
+        let endpoint = self
+            .twin_endpoints
+            .get(twin)
+            .ok_or_else(|| format!("Unknown twin: {twin}"))?;
+        let url = format!("{}/__twin/{collection}", endpoint.trim_end_matches('/'));
+
+        let response = async {
+            let request = self.http_client.get(&url).build()?;
+            send_with_retry(&self.http_client, request, &self.config).await
+        }
+        .await
+        .map_err(|e| format!("Twin {twin} inspection failed: {e}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Twin {twin} returned {status} for {collection}"));
+        }
+        let items = response
+            .json::<Vec<serde_json::Value>>()
+            .await
+            .map_err(|e| format!("Twin {twin} returned an invalid {collection} listing: {e}"))?;
+
+        if let Some(count) = assertion.count {
+            if items.len() != count {
+                return Err(format!(
+                    "Twin {twin} {collection}: expected {count} items, got {}",
+                    items.len()
+                ));
             }
-            _ => ActionResult {
-                status: 0,
-                body: format!("Unknown action type: {}", action.action_type),
-                response_time_ms: 0,
-            },
         }
+        if let Some(pattern) = &assertion.matches {
+            if !items.iter().any(|item| is_subset(pattern, item)) {
+                return Err(format!(
+                    "Twin {twin} {collection}: no item matches {pattern}"
+                ));
+            }
+        }
+        Ok(())
     }
 
     fn check_assertion(result: &ActionResult, assertion: &Assertion) -> Result<(), String> {
         match assertion.assertion_type.as_str() {
+            "node_status" | "node_output" => return check_node(result, assertion),
             "status" => {
                 let Some(expected) = assertion.expected.as_ref() else {
                     return Err("Missing expected value for status assertion".to_string());
@@ -198,7 +524,8 @@ impl<S: std::hash::BuildHasher + Send + Sync> ScenarioRunner<S> {
     }
 }
 
-/// Run validation on a directory of scenarios.
+/// Run validation on a directory of scenarios, one at a time. Values a
+/// scenario extracts stay visible to the scenarios after it.
 ///
 /// # Errors
 /// Returns an error if reading directory or files fails.
@@ -207,20 +534,136 @@ pub async fn run_validation<S: std::hash::BuildHasher + Send + Sync>(
     application_endpoint: &str,
     twins: HashMap<String, String, S>,
 ) -> Result<ValidationReport, ScenarioError> {
-    let mut results = Vec::new();
+    let scenarios = load_scenarios(scenario_dir)?;
     let mut runner = ScenarioRunner::new(application_endpoint, twins);
+    let mut results = Vec::with_capacity(scenarios.len());
+    for scenario in &scenarios {
+        results.push(runner.run_scenario(scenario).await);
+    }
+    Ok(validation_report(results, None, 0))
+}
 
-    let entries = fs::read_dir(scenario_dir)?;
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().is_some_and(|ext| ext == "yaml") {
-            let content = fs::read_to_string(&path)?;
-            let scenario: Scenario = serde_yaml::from_str(&content)?;
-            let result = runner.run_scenario(&scenario).await;
-            results.push(result);
+/// Run validation on the scenarios in a directory that `options.filter`
+/// selects, scheduled by `options`.
+///
+/// With a concurrency of 1 the scenarios run in file-name order on one
+/// runner, so values a scenario extracts stay visible to the scenarios after
+/// it, as in [`run_validation`]. Above that every scenario gets its own
+/// runner and never sees another's values. Results are ordered by file name
+/// whatever order the scenarios finish in.
+///
+/// # Errors
+/// Returns an error if reading directory or files fails.
+#[tracing::instrument(name = "scenario.validation", skip_all)]
+pub async fn run_validation_with<S>(
+    scenario_dir: &Path,
+    application_endpoint: &str,
+    twins: HashMap<String, String, S>,
+    options: RunOptions,
+) -> Result<ValidationReport, ScenarioError>
+where
+    S: std::hash::BuildHasher + Clone + Send + Sync + 'static,
+{
+    let (scenarios, skipped): (Vec<_>, Vec<_>) = load_scenarios(scenario_dir)?
+        .into_iter()
+        .partition(|scenario| options.filter.matches(&scenario.scenario));
+    let permits = Arc::new(Semaphore::new(options.concurrency.max(1)));
+    let deadline = options
+        .timeout
+        .map(|timeout| (Instant::now() + timeout, timeout));
+    let http_client = options.runner.shared_client()?;
+    let new_runner = || {
+        let mut runner = ScenarioRunner::new(application_endpoint, twins.clone())
+            .with_transcripts(options.transcripts);
+        runner.http_client = http_client.clone();
+        runner.config = options.runner.clone();
+        runner
+    };
+    let filter = (!options.filter.is_empty()).then(|| options.filter.clone());
+
+    if options.concurrency <= 1 {
+        let mut runner = new_runner();
+        let mut results = Vec::with_capacity(scenarios.len());
+        for scenario in &scenarios {
+            let run = runner.run_scenario(scenario);
+            results.push(match deadline {
+                Some((deadline, timeout)) => tokio::time::timeout_at(deadline, run)
+                    .await
+                    .unwrap_or_else(|_| {
+                        aborted(
+                            scenario,
+                            format!("Run timed out after {}ms", timeout.as_millis()),
+                        )
+                    }),
+                None => run.await,
+            });
         }
+        return Ok(validation_report(results, filter, skipped.len()));
+    }
+
+    let handles = scenarios
+        .iter()
+        .map(|scenario| {
+            let scenario = scenario.clone();
+            let permits = Arc::clone(&permits);
+            let mut runner = new_runner();
+            tokio::spawn(
+                async move {
+                    let run = async {
+                        let _permit = permits.acquire().await;
+                        runner.run_scenario(&scenario).await
+                    };
+                    match deadline {
+                        Some((deadline, timeout)) => tokio::time::timeout_at(deadline, run)
+                            .await
+                            .unwrap_or_else(|_| {
+                                aborted(
+                                    &scenario,
+                                    format!("Run timed out after {}ms", timeout.as_millis()),
+                                )
+                            }),
+                        None => run.await,
+                    }
+                }
+                .in_current_span(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for (scenario, handle) in scenarios.iter().zip(handles) {
+        results.push(
+            handle
+                .await
+                .unwrap_or_else(|e| aborted(scenario, format!("Scenario task failed: {e}"))),
+        );
     }
 
+    Ok(validation_report(results, filter, skipped.len()))
+}
+
+/// Every `*.yaml` scenario in `scenario_dir`, sorted by file name.
+fn load_scenarios(scenario_dir: &Path) -> Result<Vec<Scenario>, ScenarioError> {
+    let mut paths = fs::read_dir(scenario_dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let content = fs::read_to_string(path)?;
+            Ok(serde_yaml::from_str(&content)?)
+        })
+        .collect()
+}
+
+fn validation_report(
+    results: Vec<ScenarioResult>,
+    filter: Option<ScenarioFilter>,
+    skipped_scenarios: usize,
+) -> ValidationReport {
     let (passed, failed) = results.iter().fold((0, 0), |(passed, failed), result| {
         if result.passed {
             (passed + 1, failed)
@@ -248,12 +691,306 @@ pub async fn run_validation<S: std::hash::BuildHasher + Send + Sync>(
             acc
         });
 
-    Ok(ValidationReport {
+    ValidationReport {
         spec_id: "flow-wasm-v1".to_string(),
         total_scenarios: total,
         passed_scenarios: passed,
         failed_scenarios: failed,
         results,
         category_breakdown,
+        filter,
+        skipped_scenarios,
+    }
+}
+
+/// A scenario that never produced step results.
+fn aborted(scenario: &Scenario, error: String) -> ScenarioResult {
+    ScenarioResult {
+        scenario_id: scenario.scenario.id.clone(),
+        spec_ref: scenario.scenario.spec_ref.clone(),
+        category: scenario.scenario.category.clone(),
+        passed: false,
+        steps: Vec::new(),
+        total_duration_ms: 0,
+        error: Some(error),
+    }
+}
+
+/// A `grpc` action as the unary JSON call gRPC servers with JSON
+/// transcoding (Connect, grpc-gateway style) accept:
+/// `POST <url>/<service>/<rpc>` with the request message as the body.
+fn grpc_request(action: &StepAction) -> Result<StepAction, String> {
+    let (Some(service), Some(rpc)) = (action.service.as_deref(), action.rpc.as_deref()) else {
+        return Err("Missing service or rpc for grpc action".to_string());
+    };
+    let url = action
+        .url
+        .as_deref()
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    Ok(StepAction {
+        method: Some("POST".to_string()),
+        url: Some(format!("{url}/{service}/{rpc}")),
+        body: Some(action.body.clone().unwrap_or_else(|| serde_json::json!({}))),
+        ..action.clone()
+    })
+}
+
+/// A `kafka-publish` action as a Kafka REST proxy (v2) produce request to
+/// `<url>/topics/<topic>`, with the body as the record value.
+fn kafka_publish_request(action: &StepAction) -> Result<StepAction, String> {
+    let Some(topic) = action.topic.as_deref() else {
+        return Err("Missing topic for kafka-publish action".to_string());
+    };
+    let url = action
+        .url
+        .as_deref()
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    let mut headers = action.headers.clone().unwrap_or_default();
+    headers.insert(
+        "Content-Type".to_string(),
+        "application/vnd.kafka.json.v2+json".to_string(),
+    );
+    Ok(StepAction {
+        method: Some("POST".to_string()),
+        url: Some(format!("{url}/topics/{topic}")),
+        headers: Some(headers),
+        body: Some(serde_json::json!({
+            "records": [{ "key": action.key, "value": action.body }]
+        })),
+        ..action.clone()
     })
 }
+
+/// Run the saved canvas workflow at the action's `workflow` path through
+/// the graph runtime, with `url`, when set, as its Restate ingress. The body
+/// lists every node by name with its status, output and error; the status
+/// is 200 when every node completed or was skipped and 500 otherwise.
+async fn run_workflow(action: &StepAction) -> ActionResult {
+    let not_run = |body: String| ActionResult {
+        status: 0,
+        body,
+        response_time_ms: 0,
+    };
+    let Some(path) = action.workflow.as_deref() else {
+        return not_run("Missing workflow for workflow action".to_string());
+    };
+    let loaded = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<Workflow>(&content).map_err(|e| e.to_string()));
+    let mut workflow = match loaded {
+        Ok(workflow) => workflow,
+        Err(e) => return not_run(format!("Failed to load workflow {path}: {e}")),
+    };
+    if let Some(url) = &action.url {
+        workflow.restate_ingress_url.clone_from(url);
+    }
+
+    // A saved canvas may carry the queue of its last run; `run` swallows
+    // planning errors, so plan once here to surface them.
+    workflow.execution_queue.clear();
+    workflow.current_step = 0;
+    workflow
+        .nodes
+        .iter_mut()
+        .for_each(|node| node.executing = false);
+    if let Err(e) = workflow.prepare_run() {
+        return not_run(format!("Workflow {path} cannot run: {e}"));
+    }
+    workflow.execution_queue.clear();
+
+    let start = std::time::Instant::now();
+    workflow.run().await;
+    let success = workflow.history.last().is_some_and(|run| run.success);
+    let nodes = workflow
+        .nodes
+        .iter()
+        .map(|node| {
+            (
+                node.name.clone(),
+                serde_json::json!({
+                    "status": node.execution_state.to_string(),
+                    "output": node.last_output,
+                    "error": node.error,
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    ActionResult {
+        status: if success { 200 } else { 500 },
+        body: serde_json::json!({ "success": success, "nodes": nodes }).to_string(),
+        response_time_ms: elapsed_ms(start),
+    }
+}
+
+/// Check a node of a `workflow` step's run: its status for `node_status`,
+/// or the value at `path` in its output for `node_output`.
+fn check_node(result: &ActionResult, assertion: &Assertion) -> Result<(), String> {
+    let kind = assertion.assertion_type.as_str();
+    let Some(name) = assertion.node.as_deref() else {
+        return Err(format!("{kind} assertion needs a node"));
+    };
+    let Some(expected) = &assertion.expected else {
+        return Err(format!("Missing expected value for {kind} assertion"));
+    };
+    let run = serde_json::from_str::<serde_json::Value>(&result.body)
+        .map_err(|_| format!("{kind} assertion needs a workflow step"))?;
+    let node = run
+        .get("nodes")
+        .and_then(|nodes| nodes.get(name))
+        .ok_or_else(|| format!("Unknown workflow node: {name}"))?;
+
+    let path = assertion.path.as_deref().unwrap_or_default();
+    let (actual, what) = if kind == "node_status" {
+        (node.get("status"), "status".to_string())
+    } else {
+        (
+            node.get("output").and_then(|output| output.pointer(path)),
+            format!("output {path}"),
+        )
+    };
+    match actual {
+        Some(actual) if actual == expected => Ok(()),
+        Some(actual) => Err(format!(
+            "Node {name} {what}: expected {expected}, got {actual}"
+        )),
+        None => Err(format!("Node {name} has no {}", what.trim_end())),
+    }
+}
+
+/// What a failed assertion expected next to the value the step produced,
+/// for the status, body and node assertions whose value the result holds.
+fn assertion_failure(
+    result: &ActionResult,
+    assertion: &Assertion,
+    message: &str,
+) -> AssertionFailure {
+    let body = || serde_json::from_str::<serde_json::Value>(&result.body).ok();
+    let path = assertion.path.as_deref().unwrap_or_default();
+    let node = |field: &str| {
+        let name = assertion.node.as_deref()?;
+        body()?.get("nodes")?.get(name)?.get(field).cloned()
+    };
+    let actual = match assertion.assertion_type.as_str() {
+        "status" => Some(serde_json::json!(result.status)),
+        "body_json" => body().and_then(|body| body.pointer(path).cloned()),
+        "node_status" => node("status"),
+        "node_output" => node("output").and_then(|output| output.pointer(path).cloned()),
+        _ => None,
+    };
+    AssertionFailure {
+        assertion_type: assertion.assertion_type.clone(),
+        path: assertion.path.clone(),
+        node: assertion.node.clone(),
+        expected: assertion.expected.clone(),
+        actual,
+        message: message.to_string(),
+    }
+}
+
+/// Whether `actual` has every field of `pattern`, recursively. Arrays and
+/// scalars must be equal.
+fn is_subset(pattern: &serde_json::Value, actual: &serde_json::Value) -> bool {
+    match (pattern, actual) {
+        (serde_json::Value::Object(pattern), serde_json::Value::Object(actual)) => {
+            pattern.iter().all(|(key, expected)| {
+                actual
+                    .get(key)
+                    .is_some_and(|value| is_subset(expected, value))
+            })
+        }
+        _ => pattern == actual,
+    }
+}
+
+/// Header, query parameter and field names whose values never reach a
+/// transcript.
+const SECRET_NAMES: [&str; 7] = [
+    "authorization",
+    "cookie",
+    "api-key",
+    "api_key",
+    "token",
+    "secret",
+    "password",
+];
+
+const REDACTED: &str = "[REDACTED]";
+
+fn is_secret(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_NAMES.iter().any(|secret| name.contains(secret))
+}
+
+fn redact_headers(headers: &reqwest::header::HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_secret(name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+fn redact_json(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .map(|(name, value)| {
+                let value = if is_secret(name) {
+                    serde_json::Value::String(REDACTED.to_string())
+                } else {
+                    redact_json(value)
+                };
+                (name.clone(), value)
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items.iter().map(redact_json).collect(),
+        other => other.clone(),
+    }
+}
+
+/// JSON bodies are redacted field by field; anything else is kept as is.
+fn redact_body(body: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(json) => {
+            let redacted = redact_json(&json);
+            if redacted == json {
+                body.to_string()
+            } else {
+                redacted.to_string()
+            }
+        }
+        Err(_) => body.to_string(),
+    }
+}
+
+fn redact_url(url: &reqwest::Url) -> String {
+    if !url.query_pairs().any(|(name, _)| is_secret(&name)) {
+        return url.to_string();
+    }
+    let pairs = url
+        .query_pairs()
+        .map(|(name, value)| {
+            let value = if is_secret(&name) {
+                REDACTED.into()
+            } else {
+                value
+            };
+            (name.into_owned(), value.into_owned())
+        })
+        .collect::<Vec<_>>();
+    let mut redacted = url.clone();
+    redacted.query_pairs_mut().clear().extend_pairs(pairs);
+    redacted.to_string()
+}
+
+fn elapsed_ms(start: std::time::Instant) -> u64 {
+    u64::try_from(start.elapsed().as_millis()).map_or(u64::MAX, |value| value)
+}
@@ -0,0 +1,262 @@
+//! Starter scenarios generated from a spec.
+//!
+//! Each behavior gets one scenario file: a step for the behavior itself and
+//! one per edge case, every step carrying a placeholder assertion already
+//! linked through `behavior_ref` or `edge_case_ref` so coverage counts it
+//! while the real request and checks are filled in.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::types::{
+    Assertion, Scenario, ScenarioError, ScenarioIdentity, ScenarioSetup, ScenarioStep,
+    ScenarioTeardown, StepAction,
+};
+use crate::flow_extender::twin::DEFAULT_UNIVERSE;
+use crate::linter::{Behavior, Spec};
+
+/// Write a skeleton scenario for every behavior of the spec at `spec_path`
+/// into `out_dir`, named `<behavior id>.yaml`.
+///
+/// Files that already exist are left alone, so the scaffold can be re-run
+/// after new behaviors are added to close just the new gaps.
+///
+/// # Errors
+/// Returns an error if the spec cannot be read or parsed, or a scenario
+/// file cannot be written.
+pub fn scaffold_scenarios(spec_path: &Path, out_dir: &Path) -> Result<Vec<PathBuf>, ScenarioError> {
+    let spec: Spec = serde_yaml::from_str(&fs::read_to_string(spec_path)?)?;
+    let write_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| ScenarioError::ScaffoldError { path, source }
+    };
+    fs::create_dir_all(out_dir).map_err(write_error(out_dir))?;
+
+    let mut written = Vec::new();
+    for scenario in skeleton_scenarios(&spec) {
+        let Some(behavior_id) = scenario.steps.first().map(|step| step.id.clone()) else {
+            continue;
+        };
+        let path = out_dir.join(format!("{behavior_id}.yaml"));
+        if path.exists() {
+            continue;
+        }
+        fs::write(&path, serde_yaml::to_string(&scenario)?).map_err(write_error(&path))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// One skeleton scenario per behavior, in spec order.
+#[must_use]
+pub fn skeleton_scenarios(spec: &Spec) -> Vec<Scenario> {
+    let identity = &spec.specification.identity;
+    spec.specification
+        .behaviors
+        .iter()
+        .map(|behavior| Scenario {
+            scenario: ScenarioIdentity {
+                id: format!("{}-{}", identity.id, behavior.id),
+                spec_ref: identity.id.clone(),
+                spec_version: identity.version.clone(),
+                category: "happy-path".to_string(),
+                visibility: "open".to_string(),
+                priority: "medium".to_string(),
+                description: behavior.description.clone(),
+                rationale: format!("Scaffolded from behavior {}.", behavior.id),
+                tags: vec!["scaffold".to_string()],
+            },
+            setup: ScenarioSetup {
+                universe: DEFAULT_UNIVERSE.to_string(),
+                initial_state: "empty".to_string(),
+                preconditions: Vec::new(),
+            },
+            steps: skeleton_steps(behavior),
+            teardown: ScenarioTeardown {
+                reset_universe: true,
+                custom_cleanup: None,
+            },
+        })
+        .collect()
+}
+
+fn skeleton_steps(behavior: &Behavior) -> Vec<ScenarioStep> {
+    let main = skeleton_step(
+        &behavior.id,
+        &behavior.description,
+        &behavior.then,
+        Some(&behavior.id),
+        None,
+    );
+    let edge_cases = behavior.edge_cases.iter().flatten().map(|edge_case| {
+        skeleton_step(
+            &format!("{}-{}", behavior.id, edge_case.id),
+            &edge_case.r#when,
+            &edge_case.then,
+            None,
+            Some(&edge_case.id),
+        )
+    });
+    std::iter::once(main).chain(edge_cases).collect()
+}
+
+fn skeleton_step(
+    id: &str,
+    description: &str,
+    then: &[String],
+    behavior_ref: Option<&str>,
+    edge_case_ref: Option<&str>,
+) -> ScenarioStep {
+    ScenarioStep {
+        id: id.to_string(),
+        description: description.to_string(),
+        action: StepAction {
+            action_type: "noop".to_string(),
+            method: None,
+            url: None,
+            headers: None,
+            body: None,
+            params: None,
+            duration_ms: None,
+            interval_ms: None,
+            timeout_ms: None,
+            service: None,
+            rpc: None,
+            topic: None,
+            key: None,
+            workflow: None,
+        },
+        assertions: vec![Assertion {
+            assertion_type: "status".to_string(),
+            path: None,
+            expected: Some(serde_json::json!(0)),
+            operator: None,
+            message: Some(if then.is_empty() {
+                "Replace this stub with a real assertion.".to_string()
+            } else {
+                format!(
+                    "Replace this stub with assertions that: {}",
+                    then.join("; ")
+                )
+            }),
+            behavior_ref: behavior_ref.map(ToString::to_string),
+            edge_case_ref: edge_case_ref.map(ToString::to_string),
+            twin: None,
+            collection: None,
+            count: None,
+            matches: None,
+            topic: None,
+            node: None,
+        }],
+        extractions: Vec::new(),
+        retry: None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r"
+specification:
+  identity:
+    id: checkout
+    version: 1.2.0
+    status: draft
+    author: team
+    created: 2026-01-01
+  intent:
+    problem_statement: Customers cannot pay.
+    success_criteria: []
+  context:
+    system_dependencies: []
+    invariants: []
+  behaviors:
+    - id: pay
+      description: Customer pays for the cart
+      when: the customer submits payment
+      then:
+        - the order is confirmed
+      edge_cases:
+        - id: card-declined
+          when: the card is declined
+          then:
+            - the order stays pending
+    - id: refund
+      description: Customer is refunded
+      then: []
+  acceptance_criteria: []
+";
+
+    fn write_spec(dir: &Path) -> PathBuf {
+        let path = dir.join("checkout.yaml");
+        fs::write(&path, SPEC).unwrap();
+        path
+    }
+
+    #[test]
+    fn given_spec_when_scaffolding_then_each_behavior_gets_linked_stub_steps() {
+        let dir = tempfile::tempdir().unwrap();
+        let out_dir = dir.path().join("scenarios");
+
+        let written = scaffold_scenarios(&write_spec(dir.path()), &out_dir).unwrap();
+
+        assert_eq!(
+            written,
+            vec![out_dir.join("pay.yaml"), out_dir.join("refund.yaml")]
+        );
+        let pay: Scenario =
+            serde_yaml::from_str(&fs::read_to_string(&written[0]).unwrap()).unwrap();
+        assert_eq!(pay.scenario.id, "checkout-pay");
+        assert_eq!(pay.scenario.spec_version, "1.2.0");
+        let refs = pay
+            .steps
+            .iter()
+            .map(|step| {
+                let assertion = &step.assertions[0];
+                (
+                    step.id.as_str(),
+                    assertion.behavior_ref.as_deref(),
+                    assertion.edge_case_ref.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            refs,
+            vec![
+                ("pay", Some("pay"), None),
+                ("pay-card-declined", None, Some("card-declined")),
+            ]
+        );
+        assert_eq!(
+            pay.steps[1].assertions[0].message.as_deref(),
+            Some("Replace this stub with assertions that: the order stays pending")
+        );
+    }
+
+    #[test]
+    fn given_existing_scenario_when_scaffolding_again_then_it_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let out_dir = dir.path().join("scenarios");
+        fs::create_dir_all(&out_dir).unwrap();
+        fs::write(out_dir.join("pay.yaml"), "hand written").unwrap();
+
+        let written = scaffold_scenarios(&write_spec(dir.path()), &out_dir).unwrap();
+
+        assert_eq!(written, vec![out_dir.join("refund.yaml")]);
+        assert_eq!(
+            fs::read_to_string(out_dir.join("pay.yaml")).unwrap(),
+            "hand written"
+        );
+    }
+
+    #[test]
+    fn given_missing_spec_when_scaffolding_then_read_error_is_returned() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let result = scaffold_scenarios(&dir.path().join("missing.yaml"), dir.path());
+
+        assert!(matches!(result, Err(ScenarioError::ReadError(_))));
+    }
+}
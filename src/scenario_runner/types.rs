@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
 use thiserror::Error;
 
+use super::config::RunnerConfig;
+use super::filter::ScenarioFilter;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ScenarioIdentity {
     pub id: String,
@@ -14,6 +18,9 @@ pub struct ScenarioIdentity {
     pub priority: String,
     pub description: String,
     pub rationale: String,
+    /// Free-form labels such as `smoke` or `slow`, used to select scenarios.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -39,6 +46,30 @@ pub struct StepAction {
     pub headers: Option<HashMap<String, String>>,
     pub body: Option<serde_json::Value>,
     pub params: Option<HashMap<String, String>>,
+    /// How long a `wait` action sleeps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Delay between `poll` requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_ms: Option<u64>,
+    /// How long a `poll` action keeps trying before the step fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// `grpc`: fully qualified service, e.g. `orders.v1.OrderService`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    /// `grpc`: the unary method to call on `service`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rpc: Option<String>,
+    /// `kafka-publish`: the topic the body is published to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// `kafka-publish`: the record key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// `workflow`: path to the saved canvas workflow JSON to run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -49,6 +80,29 @@ pub struct Assertion {
     pub expected: Option<serde_json::Value>,
     pub operator: Option<String>,
     pub message: Option<String>,
+    /// Spec behavior this assertion covers, read by the coverage analyzer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behavior_ref: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edge_case_ref: Option<String>,
+    /// `twin_state`: the twin to inspect, by its name in the run's twins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub twin: Option<String>,
+    /// `twin_state`: the collection read from `/__twin/<collection>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    /// `twin_state`: exact number of items in the collection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    /// `twin_state`: fields at least one item must have, compared recursively.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches: Option<serde_json::Value>,
+    /// `event_consumed`: the topic whose consumed events the twin lists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    /// `node_status` and `node_output`: the workflow node, by name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -68,6 +122,17 @@ pub struct ScenarioStep {
     pub action: StepAction,
     pub assertions: Vec<Assertion>,
     pub extractions: Vec<Extraction>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+}
+
+/// Re-run a failed step, action and assertions together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total tries, including the first.
+    pub attempts: u32,
+    #[serde(default)]
+    pub delay_ms: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -94,6 +159,60 @@ pub struct StepResult {
     pub assertions_passed: usize,
     pub assertions_failed: usize,
     pub error: Option<String>,
+    /// Every HTTP exchange the step made, when the run records transcripts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<Vec<HttpExchange>>,
+    /// The assertions that did not hold, in scenario order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failed_assertions: Vec<AssertionFailure>,
+}
+
+/// An assertion that did not hold, with what it expected and what the step
+/// produced where the runner can tell.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssertionFailure {
+    pub assertion_type: String,
+    /// JSON pointer into the body or node output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual: Option<serde_json::Value>,
+    pub message: String,
+}
+
+/// One request and what came back, with secrets redacted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpExchange {
+    pub request: RecordedRequest,
+    /// `None` when no response arrived; see `error`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<RecordedResponse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub latency_ms: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -115,6 +234,41 @@ pub struct ValidationReport {
     pub failed_scenarios: usize,
     pub results: Vec<ScenarioResult>,
     pub category_breakdown: HashMap<String, CategoryResult>,
+    /// The filter the run applied, when it did not run every scenario.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<ScenarioFilter>,
+    /// Scenarios the filter left out.
+    #[serde(default)]
+    pub skipped_scenarios: usize,
+}
+
+/// How [`run_validation_with`](super::run_validation_with) selects and
+/// schedules scenarios.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Scenarios in flight at once; `0` behaves like `1`. At `1`, values a
+    /// scenario extracts stay visible to the scenarios after it.
+    pub concurrency: usize,
+    /// Budget for the whole run. Scenarios unfinished when it runs out are
+    /// reported as failed.
+    pub timeout: Option<Duration>,
+    pub filter: ScenarioFilter,
+    /// Record every request and response into each step's `transcript`.
+    pub transcripts: bool,
+    /// HTTP client settings shared by every scenario in the run.
+    pub runner: RunnerConfig,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 1,
+            timeout: None,
+            filter: ScenarioFilter::default(),
+            transcripts: false,
+            runner: RunnerConfig::default(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -139,10 +293,36 @@ pub enum ScenarioError {
     ParseError(#[from] serde_yaml::Error),
     #[error("HTTP request failed: {0}")]
     HttpError(#[from] reqwest::Error),
+    #[error("Invalid HTTP client configuration: {0}")]
+    ClientConfig(#[source] reqwest::Error),
+    #[error("Failed to read CA certificate at {path}: {source}")]
+    ReadCertificate {
+        path: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
     #[error("Assertion failed: {0}")]
     AssertionFailed(String),
     #[error("Setup failed: {0}")]
     SetupFailed(String),
+    #[error("Unknown scenario variable: ${{{0}}}")]
+    MissingVariable(String),
+    #[error("Unclosed placeholder in: {0}")]
+    InvalidPlaceholder(String),
+    #[error("Failed to render report: {0}")]
+    RenderError(#[source] serde_json::Error),
+    #[error("Failed to write report to {path}: {source}")]
+    WriteError {
+        path: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Failed to write scaffolded scenario to {path}: {source}")]
+    ScaffoldError {
+        path: std::path::PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -0,0 +1,335 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! Twin inspection client and pinned-sample syncing.
+//!
+//! Each twin exposes an inspection API under its endpoint:
+//! - `GET {endpoint}/collections` returns a JSON array of collection names
+//! - `GET {endpoint}/collections/{name}/records` returns a JSON array of records
+//!
+//! A record picked from a collection can be pinned as a node's
+//! `pinnedOutputSample`. The pin remembers where it came from, so it can be
+//! re-synced when the twin's fixtures change.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+pub const PINNED_OUTPUT_KEY: &str = "pinnedOutputSample";
+pub const PINNED_SOURCE_KEY: &str = "pinnedSampleSource";
+
+#[derive(Error, Debug)]
+pub enum TwinClientError {
+    #[error("Connection failed: {0}")]
+    ConnectionFailed(String),
+
+    #[error("HTTP {status}: {message}")]
+    HttpError { status: u16, message: String },
+
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Invalid twin endpoint: {0}")]
+    InvalidEndpoint(String),
+
+    #[error("HTTP request error: {0}")]
+    RequestError(#[from] reqwest::Error),
+}
+
+/// One fixture record in a twin collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwinRecord {
+    pub id: String,
+    pub data: Value,
+}
+
+/// Where a pinned sample was taken from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PinnedSampleSource {
+    pub endpoint: String,
+    pub collection: String,
+    pub record_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PinSyncOutcome {
+    /// The pinned sample already matches the twin record.
+    Unchanged,
+    /// The pinned sample was replaced with the twin's current record.
+    Updated,
+    /// The source record no longer exists; the pin is left as is.
+    RecordMissing,
+    /// The node's pin did not come from a twin.
+    NotLinked,
+}
+
+/// Client for a single twin's inspection API.
+#[derive(Debug, Clone)]
+pub struct TwinInspectionClient {
+    http_client: reqwest::Client,
+    endpoint: String,
+}
+
+impl TwinInspectionClient {
+    #[must_use]
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// List the collections the twin exposes.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails or the body is not a list of names.
+    pub async fn list_collections(&self) -> Result<Vec<String>, TwinClientError> {
+        let body = self.get_json(self.url(&["collections"])?).await?;
+        parse_collections(&body)
+    }
+
+    /// List the records in one collection.
+    ///
+    /// # Errors
+    /// Returns an error if the request fails or a record has no id.
+    pub async fn list_records(&self, collection: &str) -> Result<Vec<TwinRecord>, TwinClientError> {
+        let body = self
+            .get_json(self.url(&["collections", collection, "records"])?)
+            .await?;
+        parse_records(&body)
+    }
+
+    /// The endpoint with `segments` appended, each percent-encoded so a
+    /// collection name cannot change the path or add a query.
+    fn url(&self, segments: &[&str]) -> Result<reqwest::Url, TwinClientError> {
+        let invalid = || TwinClientError::InvalidEndpoint(self.endpoint.clone());
+        let mut url = reqwest::Url::parse(&self.endpoint).map_err(|_| invalid())?;
+        url.path_segments_mut()
+            .map_err(|()| invalid())?
+            .pop_if_empty()
+            .extend(segments);
+        Ok(url)
+    }
+
+    #[tracing::instrument(name = "twin.request", skip(self), fields(url = %url))]
+    async fn get_json(&self, url: reqwest::Url) -> Result<Value, TwinClientError> {
+        let response = self
+            .http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(|error| TwinClientError::ConnectionFailed(error.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_else(|_| {
+                format!("<failed to read response body, HTTP {}>", status.as_u16())
+            });
+            return Err(TwinClientError::HttpError {
+                status: status.as_u16(),
+                message,
+            });
+        }
+
+        Ok(response.json().await?)
+    }
+}
+
+/// Parse a collection listing.
+///
+/// # Errors
+/// Returns an error if `body` is not an array of strings.
+pub fn parse_collections(body: &Value) -> Result<Vec<String>, TwinClientError> {
+    body.as_array()
+        .ok_or_else(|| TwinClientError::InvalidResponse("expected an array of collections".into()))?
+        .iter()
+        .map(|item| {
+            item.as_str().map(str::to_string).ok_or_else(|| {
+                TwinClientError::InvalidResponse("collection names must be strings".into())
+            })
+        })
+        .collect()
+}
+
+/// Parse a record listing.
+///
+/// Records are objects with an `id`. When a record carries a `data` field
+/// that field is the sample; otherwise the whole object is.
+///
+/// # Errors
+/// Returns an error if `body` is not an array or a record has no id.
+pub fn parse_records(body: &Value) -> Result<Vec<TwinRecord>, TwinClientError> {
+    body.as_array()
+        .ok_or_else(|| TwinClientError::InvalidResponse("expected an array of records".into()))?
+        .iter()
+        .map(|item| {
+            let id = match item.get("id") {
+                Some(Value::String(id)) => id.clone(),
+                Some(Value::Number(id)) => id.to_string(),
+                _ => {
+                    return Err(TwinClientError::InvalidResponse(
+                        "each record must have a string or numeric id".into(),
+                    ))
+                }
+            };
+            let data = item.get("data").cloned().unwrap_or_else(|| item.clone());
+            Ok(TwinRecord { id, data })
+        })
+        .collect()
+}
+
+/// Pin a twin record as the node's output sample.
+pub fn pin_twin_record(config: &mut Value, source: PinnedSampleSource, record: &TwinRecord) {
+    if !config.is_object() {
+        *config = Value::Object(serde_json::Map::new());
+    }
+    if let Some(obj) = config.as_object_mut() {
+        obj.insert(PINNED_OUTPUT_KEY.to_owned(), record.data.clone());
+        if let Ok(source) = serde_json::to_value(source) {
+            obj.insert(PINNED_SOURCE_KEY.to_owned(), source);
+        }
+    }
+}
+
+/// The twin a node's pinned sample was taken from, if any.
+#[must_use]
+pub fn pinned_sample_source(config: &Value) -> Option<PinnedSampleSource> {
+    config
+        .get(PINNED_SOURCE_KEY)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// Refresh a twin-linked pin from the collection's current records.
+pub fn sync_pinned_sample(config: &mut Value, records: &[TwinRecord]) -> PinSyncOutcome {
+    let Some(source) = pinned_sample_source(config) else {
+        return PinSyncOutcome::NotLinked;
+    };
+    let Some(record) = records.iter().find(|record| record.id == source.record_id) else {
+        return PinSyncOutcome::RecordMissing;
+    };
+    if config.get(PINNED_OUTPUT_KEY) == Some(&record.data) {
+        return PinSyncOutcome::Unchanged;
+    }
+    pin_twin_record(config, source, record);
+    PinSyncOutcome::Updated
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::{
+        parse_collections, parse_records, pin_twin_record, pinned_sample_source,
+        sync_pinned_sample, PinSyncOutcome, PinnedSampleSource, TwinInspectionClient, TwinRecord,
+        PINNED_OUTPUT_KEY,
+    };
+    use serde_json::json;
+
+    fn source() -> PinnedSampleSource {
+        PinnedSampleSource {
+            endpoint: "http://127.0.0.1:9900/twins/payments".to_string(),
+            collection: "charges".to_string(),
+            record_id: "ch_1".to_string(),
+        }
+    }
+
+    fn record(amount: u64) -> TwinRecord {
+        TwinRecord {
+            id: "ch_1".to_string(),
+            data: json!({ "amount": amount }),
+        }
+    }
+
+    #[test]
+    fn given_record_listing_when_parsing_then_data_field_or_whole_object_is_the_sample() {
+        let records = parse_records(&json!([
+            { "id": "a", "data": { "ok": true } },
+            { "id": 7, "status": "paid" }
+        ]))
+        .unwrap();
+
+        assert_eq!(records[0].data, json!({ "ok": true }));
+        assert_eq!(records[1].id, "7");
+        assert_eq!(records[1].data, json!({ "id": 7, "status": "paid" }));
+    }
+
+    #[test]
+    fn given_malformed_listings_when_parsing_then_errors_are_returned() {
+        assert!(parse_records(&json!([{ "status": "paid" }])).is_err());
+        assert!(parse_records(&json!({ "records": [] })).is_err());
+        assert!(parse_collections(&json!(["orders", 1])).is_err());
+        assert_eq!(
+            parse_collections(&json!(["orders"])).unwrap(),
+            vec!["orders".to_string()]
+        );
+    }
+
+    #[test]
+    fn given_twin_record_when_pinning_then_sample_and_source_are_stored() {
+        let mut config = json!({ "service": "payments" });
+
+        pin_twin_record(&mut config, source(), &record(10));
+
+        assert_eq!(config[PINNED_OUTPUT_KEY], json!({ "amount": 10 }));
+        assert_eq!(config["pinnedSampleSource"]["recordId"], "ch_1");
+        assert_eq!(pinned_sample_source(&config), Some(source()));
+        assert_eq!(config["service"], "payments");
+    }
+
+    #[test]
+    fn given_twin_fixture_changed_when_syncing_then_pin_is_updated_once() {
+        let mut config = json!({});
+        pin_twin_record(&mut config, source(), &record(10));
+
+        assert_eq!(
+            sync_pinned_sample(&mut config, &[record(25)]),
+            PinSyncOutcome::Updated
+        );
+        assert_eq!(config[PINNED_OUTPUT_KEY], json!({ "amount": 25 }));
+        assert_eq!(
+            sync_pinned_sample(&mut config, &[record(25)]),
+            PinSyncOutcome::Unchanged
+        );
+    }
+
+    #[test]
+    fn given_unlinked_or_missing_record_when_syncing_then_pin_is_left_alone() {
+        let mut manual = json!({ PINNED_OUTPUT_KEY: { "manual": true } });
+        let mut linked = json!({});
+        pin_twin_record(&mut linked, source(), &record(10));
+
+        assert_eq!(
+            sync_pinned_sample(&mut manual, &[record(25)]),
+            PinSyncOutcome::NotLinked
+        );
+        assert_eq!(
+            sync_pinned_sample(&mut linked, &[]),
+            PinSyncOutcome::RecordMissing
+        );
+        assert_eq!(linked[PINNED_OUTPUT_KEY], json!({ "amount": 10 }));
+    }
+
+    #[test]
+    fn given_collection_name_with_reserved_characters_when_building_url_then_it_stays_one_segment()
+    {
+        let client = TwinInspectionClient::new("http://127.0.0.1:9900/twins/payments/");
+
+        let url = client
+            .url(&["collections", "refunds/2024?x=1#top", "records"])
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "http://127.0.0.1:9900/twins/payments/collections/refunds%2F2024%3Fx=1%23top/records"
+        );
+        assert!(url.query().is_none());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::graph::contract::WorkflowContract;
+use crate::graph::execution_runtime::cache::NodeCache;
 use crate::graph::{execution_types::ExecutionConfig, Node, Viewport, Workflow};
 use crate::graph::{ConditionConfig, HttpHandlerConfig, RunConfig, WorkflowNode};
 
@@ -42,12 +44,16 @@ pub fn default_workflow() -> Workflow {
         current_step: 0,
         history: vec![],
         execution_records: vec![],
+        suppressed_extensions: vec![],
         restate_ingress_url: "http://localhost:8080".to_string(),
         current_memory_bytes: 0,
         execution_config: ExecutionConfig::default(),
         execution_failed: false,
         last_checkpoint_step: None,
         rollback_stack: vec![],
+        node_cache: NodeCache::default(),
+        contract: WorkflowContract::default(),
+        frames: Vec::new(),
     }
 }
 
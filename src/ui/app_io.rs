@@ -48,7 +48,7 @@ pub fn download_workflow_json(name: &str, workflow: &Workflow) {
     use wasm_bindgen::{JsCast, JsValue};
     use web_sys::{window, Blob, HtmlAnchorElement, Url};
 
-    let json = match serde_json::to_string_pretty(workflow) {
+    let json = match serde_json::to_string_pretty(&workflow.redacted()) {
         Ok(value) => value,
         Err(_) => return,
     };
@@ -169,7 +169,16 @@ where
 
                 match serde_json::from_str::<crate::graph::Workflow>(&text) {
                     Ok(workflow) => on_result(ImportResult::Success(workflow)),
-                    Err(e) => on_result(ImportResult::Error(format!("Invalid workflow JSON: {e}"))),
+                    // Not one of ours; try it as an n8n or Node-RED flow.
+                    Err(e) => match crate::graph::flow_import::import_flow(&text, None) {
+                        Ok(imported) => on_result(ImportResult::Success(imported.workflow)),
+                        Err(crate::graph::flow_import::FlowImportError::Unrecognized) => {
+                            on_result(ImportResult::Error(format!("Invalid workflow JSON: {e}")));
+                        }
+                        Err(import_error) => {
+                            on_result(ImportResult::Error(import_error.to_string()))
+                        }
+                    },
                 }
             });
 
@@ -261,6 +270,53 @@ pub fn export_restate_history<T: serde::Serialize>(invocations: &[T]) {
     Url::revoke_object_url(&url);
 }
 
+#[cfg(target_arch = "wasm32")]
+/// Writes `text` to the system clipboard. The callback receives the outcome.
+pub fn copy_text_to_clipboard<F>(text: String, on_result: F)
+where
+    F: FnOnce(Result<(), String>) + 'static,
+{
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::window;
+
+    let Some(window) = window() else {
+        on_result(Err("Clipboard is unavailable".to_string()));
+        return;
+    };
+    let promise = window.navigator().clipboard().write_text(&text);
+    wasm_bindgen_futures::spawn_local(async move {
+        match JsFuture::from(promise).await {
+            Ok(_) => on_result(Ok(())),
+            Err(_) => on_result(Err("Clipboard write was rejected".to_string())),
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+/// Reads text from the system clipboard. The callback receives the outcome.
+pub fn read_clipboard_text<F>(on_result: F)
+where
+    F: FnOnce(Result<String, String>) + 'static,
+{
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::window;
+
+    let Some(window) = window() else {
+        on_result(Err("Clipboard is unavailable".to_string()));
+        return;
+    };
+    let promise = window.navigator().clipboard().read_text();
+    wasm_bindgen_futures::spawn_local(async move {
+        match JsFuture::from(promise).await {
+            Ok(value) => match value.as_string() {
+                Some(text) => on_result(Ok(text)),
+                None => on_result(Err("Clipboard does not contain text".to_string())),
+            },
+            Err(_) => on_result(Err("Clipboard read was rejected".to_string())),
+        }
+    });
+}
+
 #[cfg(all(test, not(target_arch = "wasm32")))]
 #[allow(
     clippy::unwrap_used,
@@ -5,15 +5,21 @@
 #![forbid(unsafe_code)]
 
 use crate::flow_extender::ExtensionPatchPreview;
-use crate::graph::{validate_workflow, ValidationResult};
+use crate::graph::compile::{CompileReport, SeverityGate};
+use crate::graph::profile::RunProfile;
+use crate::graph::{ValidationResult, Workflow};
+use crate::ui::canvas_settings::CanvasSettings;
 use crate::ui::constants::{
-    DEFAULT_CANVAS_HEIGHT, DEFAULT_CANVAS_WIDTH, FIT_VIEW_PADDING, NODE_HANDLE_Y_OFFSET,
-    NODE_WIDTH, ZOOM_CENTER_X, ZOOM_CENTER_Y, ZOOM_DELTA,
+    DEFAULT_CANVAS_HEIGHT, DEFAULT_CANVAS_WIDTH, FIT_VIEW_PADDING, FRAME_DEFAULT_HEIGHT,
+    FRAME_DEFAULT_WIDTH, NODE_HANDLE_Y_OFFSET, NODE_WIDTH, ZOOM_CENTER_X, ZOOM_CENTER_Y,
+    ZOOM_DELTA,
 };
+use crate::ui::run_errors_panel::collect_run_failures;
 use crate::ui::{
-    CanvasArea, CanvasContextMenu, EmptyCanvas, FlowPosition, FlowToolbar, InspectorPanel,
-    NodeCommandPalette, NodeTemplateId, PayloadPreviewPanel, PrototypePalette, RightPanel,
-    RunStatusBar, SelectedNodePanel, SettingsOverlay, ShortcutsOverlay, ToastContainer,
+    CanvasArea, CanvasContextMenu, EditorTab, EmptyCanvas, FlowPosition, FlowToolbar,
+    InspectorPanel, NodeCommandPalette, NodeTemplateId, PayloadPreviewPanel, PrototypePalette,
+    RightPanel, RunErrorsPanel, RunStatusBar, SelectedNodePanel, SettingsOverlay, ShortcutsOverlay,
+    ToastContainer, WorkflowCompareOverlay,
 };
 use dioxus::prelude::*;
 use std::fmt::Write;
@@ -28,24 +34,32 @@ pub fn AppShell() -> Element {
     let sidebar = crate::hooks::use_sidebar();
     let restate = crate::hooks::use_restate_sync();
     let toast = crate::hooks::use_toast();
-
-    // Persist workflow to localStorage
-    use_effect(move || {
-        let wf_signal = workflow.workflow();
-        let wf = wf_signal.read();
-        if let Ok(_json) = serde_json::to_string(&*wf) {
-            #[cfg(target_arch = "wasm32")]
-            {
-                use web_sys::window;
-                let storage = window().and_then(|w| match w.local_storage() {
-                    Ok(s) => s,
-                    Err(_) => None,
-                });
-                if let Some(s) = storage {
-                    let _ = s.set_item("flow-wasm-v1-workflow", &_json);
+    let analysis = crate::hooks::use_analysis();
+
+    // Persist workflow to localStorage once edits pause
+    let unsaved = crate::hooks::use_workflow_persistence(workflow.workflow());
+
+    // Open the workflow named by a `?workflow=<url>` deep link
+    use_hook(move || {
+        spawn(async move {
+            let (mut workflow, mut toast) = (workflow, toast);
+            match crate::ui::deep_link::fetch_linked_workflow().await {
+                Some(Ok(linked)) => {
+                    workflow.load_workflow(linked);
+                    toast.push(
+                        "Workflow opened from link".to_string(),
+                        crate::ui::toast::ToastSeverity::Success,
+                    );
+                }
+                Some(Err(msg)) => {
+                    toast.push(
+                        format!("Could not open linked workflow: {msg}"),
+                        crate::ui::toast::ToastSeverity::Error,
+                    );
                 }
+                None => {}
             }
-        }
+        });
     });
 
     // Derived computations
@@ -61,12 +75,70 @@ pub fn AppShell() -> Element {
     });
     let can_undo = use_memo(move || workflow.can_undo());
     let can_redo = use_memo(move || workflow.can_redo());
+    let cached_count = use_memo(move || workflow.workflow().read().node_cache.len());
     let mut extension_previews = use_signal(Vec::<ExtensionPatchPreview>::new);
     let mut validation_collapsed = use_signal(|| false);
-    let validation_result: Memo<ValidationResult> = use_memo(move || {
+    let mut compile_gate = use_signal(load_compile_gate);
+    let mut canvas_settings = use_signal(load_canvas_settings);
+    let mut editor_tab = use_signal(EditorTab::default);
+    let run_failures = use_memo(move || collect_run_failures(&workflow.workflow().read()));
+    let latest_run_id =
+        use_memo(move || workflow.workflow().read().history.last().map(|run| run.id));
+    let mut dismissed_run_id = use_signal(|| None::<uuid::Uuid>);
+    let mut profile_mode = use_signal(|| false);
+    let latest_profile = use_memo(move || {
+        let binding = workflow.workflow();
+        let wf = binding.read();
+        wf.history
+            .last()
+            .and_then(|run| RunProfile::from_run(&wf, run))
+    });
+    let can_profile = use_memo(move || latest_profile.read().is_some());
+    let canvas_profile = use_memo(move || {
+        if *profile_mode.read() {
+            latest_profile.read().clone()
+        } else {
+            None
+        }
+    });
+    // Large graphs are compiled in the analysis worker once edits pause.
+    use_effect(move || {
+        workflow.workflow().read();
+        analysis.schedule(*compile_gate.read());
+    });
+    let compile_report: Memo<CompileReport> = use_memo(move || {
         let binding = workflow.workflow();
         let wf = binding.read();
-        validate_workflow(&wf)
+        analysis.compile_report(&wf, *compile_gate.read())
+    });
+    let validation_result: Memo<ValidationResult> =
+        use_memo(move || compile_report.read().to_validation_result());
+
+    // Persist the severity gate for this workspace
+    use_effect(move || {
+        let gate = compile_gate.read().as_str();
+        #[cfg(target_arch = "wasm32")]
+        {
+            use web_sys::window;
+            let storage = window().and_then(|w| w.local_storage().ok()).flatten();
+            if let Some(s) = storage {
+                let _ = s.set_item(COMPILE_GATE_STORAGE_KEY, gate);
+            }
+        }
+    });
+
+    // Persist canvas preferences and keep drag snapping in step with them
+    use_effect(move || {
+        let settings = canvas_settings.read().clone();
+        workflow.set_snap_grid(settings.snap_grid());
+        #[cfg(target_arch = "wasm32")]
+        {
+            use web_sys::window;
+            let storage = window().and_then(|w| w.local_storage().ok()).flatten();
+            if let (Some(s), Ok(json)) = (storage, serde_json::to_string(&settings)) {
+                let _ = s.set_item(CANVAS_SETTINGS_STORAGE_KEY, &json);
+            }
+        }
     });
 
     // RunStatusBar signals
@@ -175,6 +247,9 @@ pub fn AppShell() -> Element {
     // PrototypePalette signal
     let mut prototype_open = use_signal(|| false);
 
+    // Imported document shown side by side with the current one
+    let mut compare_target = use_signal(|| None::<Workflow>);
+
     let vp = workflow.viewport();
     let _vx = vp.read().x;
     let _vy = vp.read().y;
@@ -221,6 +296,22 @@ pub fn AppShell() -> Element {
         )
     });
 
+    let removed_preview_nodes = use_memo(move || {
+        let existing_nodes = nodes_by_id.read().clone();
+        crate::flow_extender::preview_calc::compute_removed_preview_nodes(
+            &extension_previews.read(),
+            &existing_nodes,
+        )
+    });
+
+    let removed_preview_edges = use_memo(move || {
+        let existing_nodes = nodes_by_id.read().clone();
+        crate::flow_extender::preview_calc::compute_removed_preview_edges(
+            &extension_previews.read(),
+            &existing_nodes,
+        )
+    });
+
     rsx! {
         document::Stylesheet { href: asset!("/assets/tailwind.css") }
         document::Stylesheet { href: asset!("/style.css") }
@@ -249,16 +340,26 @@ pub fn AppShell() -> Element {
                 on_zoom_in: move |_| workflow.zoom(ZOOM_DELTA, ZOOM_CENTER_X, ZOOM_CENTER_Y),
                 on_zoom_out: move |_| workflow.zoom(-ZOOM_DELTA, ZOOM_CENTER_X, ZOOM_CENTER_Y),
                 on_fit_view: move |_| workflow.fit_view(DEFAULT_CANVAS_WIDTH, DEFAULT_CANVAS_HEIGHT, FIT_VIEW_PADDING),
-                on_layout: move |_| workflow.apply_layout(),
+                on_layout: move |_| analysis.layout(),
                 on_execute: move |_| {
-                    let result = validation_result.read();
-                    if result.has_errors() {
+                    if compile_report.read().is_blocked() {
                         validation_collapsed.set(false);
+                        toast.push("Run blocked by compile diagnostics".to_string(), crate::ui::toast::ToastSeverity::Error);
                     } else {
                         let ingress = restate.ingress_url.read().clone();
                         workflow.run(ingress);
                     }
                 },
+                cached_count: cached_count,
+                can_profile: can_profile,
+                profile_mode: profile_mode,
+                on_toggle_profile: move |_| profile_mode.toggle(),
+                capabilities: workflow.capabilities(),
+                unsaved: unsaved,
+                on_clear_cache: move |_| {
+                    workflow.clear_node_cache();
+                    toast.push("Node cache cleared".to_string(), crate::ui::toast::ToastSeverity::Success);
+                },
                 on_undo: move |_| {
                     let _ = workflow.undo();
                     extension_previews.set(Vec::new());
@@ -270,6 +371,11 @@ pub fn AppShell() -> Element {
                     selection.clear();
                 },
                 on_save: move |_| {
+                    if compile_report.read().is_blocked() {
+                        validation_collapsed.set(false);
+                        toast.push("Export blocked by compile diagnostics".to_string(), crate::ui::toast::ToastSeverity::Error);
+                        return;
+                    }
                     #[cfg(target_arch = "wasm32")]
                     {
                         crate::ui::app_io::download_workflow_json(
@@ -296,7 +402,25 @@ pub fn AppShell() -> Element {
                         });
                     }
                 },
-                on_settings: move |_| panels.toggle_settings()
+                on_compare: move |_| {
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        let toast_clone = toast;
+                        crate::ui::app_io::trigger_import(move |result| {
+                            match result {
+                                crate::ui::app_io::ImportResult::Success(imported) => {
+                                    compare_target.set(Some(imported));
+                                }
+                                crate::ui::app_io::ImportResult::Error(msg) => {
+                                    toast_clone.push(format!("Compare failed: {msg}"), crate::ui::toast::ToastSeverity::Error);
+                                }
+                            }
+                        });
+                    }
+                },
+                on_settings: move |_| panels.toggle_settings(),
+                compile_report: compile_report,
+                on_compile_status: move |_| validation_collapsed.set(false),
             }
 
             RunStatusBar {
@@ -309,7 +433,26 @@ pub fn AppShell() -> Element {
                 on_exit_frozen: move |()| { frozen_run_id.set(None); }
             }
 
-            SettingsOverlay { panels: panels }
+            SettingsOverlay {
+                panels: panels,
+                workflow_state: workflow,
+                compile_gate: *compile_gate.read(),
+                on_compile_gate_change: move |gate| compile_gate.set(gate),
+                canvas_settings: canvas_settings.read().clone(),
+                on_canvas_settings_change: move |settings: CanvasSettings| {
+                    canvas_settings.set(settings.sanitized());
+                },
+            }
+
+            if let Some(other) = compare_target.read().clone() {
+                WorkflowCompareOverlay {
+                    base: workflow.workflow().read().clone(),
+                    other: other,
+                    base_label: "Current".to_string(),
+                    other_label: "Imported".to_string(),
+                    on_close: move |_| compare_target.set(None),
+                }
+            }
 
             if panels.shortcuts_open() {
                 ShortcutsOverlay {
@@ -356,7 +499,56 @@ pub fn AppShell() -> Element {
                 },
                 on_layout: move |_| {
                     panels.close_context_menu();
-                    workflow.apply_layout();
+                    analysis.layout();
+                },
+                on_add_frame: move |_| {
+                    panels.close_context_menu();
+                    let node_ids = selection.selected_ids().read().clone();
+                    if workflow.frame_nodes("Group", &node_ids).is_none() {
+                        let viewport = workflow.viewport().read().clone();
+                        let x = (ZOOM_CENTER_X - viewport.x) / viewport.zoom - FRAME_DEFAULT_WIDTH / 2.0;
+                        let y = (ZOOM_CENTER_Y - viewport.y) / viewport.zoom - FRAME_DEFAULT_HEIGHT / 2.0;
+                        let _ = workflow.add_frame("Group", x, y);
+                    }
+                },
+                can_export_selection: ReadSignal::from(use_memo(move || selection.has_selection())),
+                on_export_selection: move |_| {
+                    panels.close_context_menu();
+                    let node_ids = selection.selected_ids().read().clone();
+                    let export = workflow.workflow().read().redacted().export_subgraph(&node_ids);
+                    match export.and_then(|subgraph| {
+                        serde_json::to_string_pretty(&subgraph).map_err(|err| err.to_string())
+                    }) {
+                        Ok(json) => {
+                            let mut toast_clone = toast;
+                            crate::ui::app_io::copy_text_to_clipboard(json, move |result| match result {
+                                Ok(()) => toast_clone.push("Selection copied as JSON".to_string(), crate::ui::toast::ToastSeverity::Success),
+                                Err(msg) => toast_clone.push(format!("Export failed: {msg}"), crate::ui::toast::ToastSeverity::Error),
+                            });
+                        }
+                        Err(msg) => toast.push(format!("Export failed: {msg}"), crate::ui::toast::ToastSeverity::Error),
+                    }
+                },
+                on_import_subgraph: move |_| {
+                    let anchor = panels.context_menu().read().position().map(|p| (p.x, p.y));
+                    panels.close_context_menu();
+                    let mut toast_clone = toast;
+                    crate::ui::app_io::read_clipboard_text(move |result| {
+                        match result.and_then(|text| crate::graph::subgraph::SubgraphExport::from_json(&text)) {
+                            Ok(subgraph) => {
+                                let viewport = workflow.viewport().read().clone();
+                                let origin = crate::ui::app_io::canvas_origin().unwrap_or((0.0, 0.0));
+                                let (x, y) = anchor
+                                    .and_then(|page| crate::ui::interaction_guards::safe_canvas_from_viewport(page, origin, &viewport))
+                                    .unwrap_or((0.0, 0.0));
+                                let inserted = workflow.import_subgraph(&subgraph.workflow, x, y);
+                                let count = inserted.len();
+                                selection.set_multiple(inserted);
+                                toast_clone.push(format!("Imported {count} nodes"), crate::ui::toast::ToastSeverity::Success);
+                            }
+                            Err(msg) => toast_clone.push(format!("Import failed: {msg}"), crate::ui::toast::ToastSeverity::Error),
+                        }
+                    });
                 }
             }
 
@@ -449,7 +641,26 @@ pub fn AppShell() -> Element {
                         temp_edge: temp_edge,
                         preview_nodes: preview_nodes,
                         preview_edges: preview_edges,
+                        removed_preview_nodes: removed_preview_nodes,
+                        removed_preview_edges: removed_preview_edges,
                         show_inspector: show_inspector,
+                        canvas_settings: canvas_settings.read().clone(),
+                        profile: canvas_profile,
+                    }
+
+                    if *dismissed_run_id.read() != *latest_run_id.read() {
+                        RunErrorsPanel {
+                            failures: ReadSignal::from(run_failures),
+                            on_focus_node: move |node_id| {
+                                let (canvas_w, canvas_h) = crate::ui::app_io::canvas_rect_size()
+                                    .map_or((DEFAULT_CANVAS_WIDTH, DEFAULT_CANVAS_HEIGHT), std::convert::identity);
+                                if workflow.focus_node(node_id, canvas_w, canvas_h) {
+                                    selection.select_single(node_id);
+                                    editor_tab.set(EditorTab::Execution);
+                                }
+                            },
+                            on_dismiss: move |()| dismissed_run_id.set(*latest_run_id.read()),
+                        }
                     }
 
                     if *node_count.read() == 0 {
@@ -481,6 +692,7 @@ pub fn AppShell() -> Element {
                     validation_result: validation_result,
                     validation_collapsed: validation_collapsed,
                     frozen_run_id: frozen_run_id,
+                    selected_node: selection.selected_id(),
                     on_select_node: move |node_id| {
                         selection.select_single(node_id);
                     },
@@ -492,6 +704,7 @@ pub fn AppShell() -> Element {
                     nodes_by_id,
                     workflow_state: workflow,
                     preview_patches: extension_previews,
+                    editor_tab: editor_tab,
                 }
 
                 PayloadPreviewPanel {
@@ -516,3 +729,39 @@ pub fn AppShell() -> Element {
         }
     }
 }
+
+#[cfg(target_arch = "wasm32")]
+const COMPILE_GATE_STORAGE_KEY: &str = "flow-wasm-v1-compile-gate";
+
+fn load_compile_gate() -> SeverityGate {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use web_sys::window;
+        let storage = window().and_then(|w| w.local_storage().ok()).flatten();
+        if let Some(gate) = storage
+            .and_then(|s| s.get_item(COMPILE_GATE_STORAGE_KEY).ok().flatten())
+            .and_then(|value| value.parse().ok())
+        {
+            return gate;
+        }
+    }
+    SeverityGate::default()
+}
+
+#[cfg(target_arch = "wasm32")]
+const CANVAS_SETTINGS_STORAGE_KEY: &str = "flow-wasm-v1-canvas-settings";
+
+fn load_canvas_settings() -> CanvasSettings {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use web_sys::window;
+        let storage = window().and_then(|w| w.local_storage().ok()).flatten();
+        if let Some(settings) = storage
+            .and_then(|s| s.get_item(CANVAS_SETTINGS_STORAGE_KEY).ok().flatten())
+            .and_then(|json| serde_json::from_str::<CanvasSettings>(&json).ok())
+        {
+            return settings.sanitized();
+        }
+    }
+    CanvasSettings::default()
+}
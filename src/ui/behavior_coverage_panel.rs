@@ -0,0 +1,236 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::nursery)]
+#![forbid(unsafe_code)]
+
+use crate::graph::behavior_coverage::{BehaviorCoverage, SpecBehavior, SpecBehaviors};
+use crate::graph::{NodeId, Workflow};
+use crate::ui::icons::{
+    AlertTriangleIcon, CheckCircleIcon, ChevronDownIcon, ChevronRightIcon, XIcon,
+};
+use dioxus::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+const COVERAGE_SPEC_STORAGE_KEY: &str = "flow-wasm-v1-coverage-spec";
+
+/// Spec behaviors the canvas represents, with uncovered ones as a to-do list.
+///
+/// Opening the panel turns on coverage mode. The spec is pasted as YAML and
+/// remembered across reloads.
+#[component]
+pub fn BehaviorCoveragePanel(
+    workflow: ReadSignal<Workflow>,
+    selected_node: ReadSignal<Option<NodeId>>,
+    collapsed: Signal<bool>,
+    on_select_node: EventHandler<NodeId>,
+    on_link: EventHandler<(NodeId, String)>,
+    on_unlink: EventHandler<(NodeId, String)>,
+) -> Element {
+    let mut spec =
+        use_signal(|| load_spec_yaml().and_then(|yaml| SpecBehaviors::from_yaml(&yaml).ok()));
+    let mut draft = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let coverage = use_memo(move || {
+        spec.read()
+            .as_ref()
+            .map(|spec| BehaviorCoverage::compute(&workflow.read(), spec))
+    });
+    let is_collapsed = *collapsed.read();
+    let node_name = move |node_id: NodeId| {
+        workflow
+            .read()
+            .nodes
+            .iter()
+            .find(|node| node.id == node_id)
+            .map_or_else(|| node_id.to_string(), |node| node.name.clone())
+    };
+
+    let badge = coverage
+        .read()
+        .as_ref()
+        .map(|coverage| format!("{}/{}", coverage.covered.len(), coverage.total()));
+
+    rsx! {
+        div { class: "border-b border-slate-200",
+            button {
+                class: "flex w-full items-center justify-between px-3 py-2 text-left transition-colors hover:bg-slate-50",
+                onclick: move |_| collapsed.toggle(),
+                div { class: "flex items-center gap-2",
+                    if is_collapsed {
+                        ChevronRightIcon { class: "h-3.5 w-3.5 text-slate-400" }
+                    } else {
+                        ChevronDownIcon { class: "h-3.5 w-3.5 text-slate-400" }
+                    }
+                    span { class: "text-[12px] font-semibold text-slate-700", "Behavior coverage" }
+                }
+                if let Some(badge) = badge {
+                    span { class: "rounded-full bg-slate-100 px-1.5 py-0.5 text-[10px] font-medium text-slate-600", "{badge}" }
+                }
+            }
+
+            if !is_collapsed {
+                div { class: "max-h-[260px] overflow-y-auto border-t border-slate-100 bg-slate-50/50 px-3 py-2 space-y-2",
+                    match (spec.read().clone(), coverage.read().clone()) {
+                        (Some(loaded), Some(coverage)) => rsx! {
+                            div { class: "flex items-center justify-between",
+                                span { class: "font-mono text-[11px] text-slate-600", "{loaded.spec_id}" }
+                                span { class: "text-[11px] text-slate-500", "{coverage.percentage():.0}% covered" }
+                                button {
+                                    class: "rounded p-0.5 text-slate-400 hover:bg-white hover:text-slate-600",
+                                    title: "Unload spec",
+                                    onclick: move |_| {
+                                        spec.set(None);
+                                        save_spec_yaml(None);
+                                    },
+                                    XIcon { class: "h-3 w-3" }
+                                }
+                            }
+
+                            if coverage.uncovered.is_empty() {
+                                div { class: "flex items-center gap-2 text-[11px] text-emerald-600",
+                                    CheckCircleIcon { class: "h-3.5 w-3.5" }
+                                    "Every behavior is represented"
+                                }
+                            } else {
+                                p { class: "text-[10px] uppercase tracking-wide text-slate-500", "To do" }
+                                for behavior in coverage.uncovered.iter().cloned() {
+                                    TodoRow {
+                                        key: "{behavior.id}",
+                                        behavior,
+                                        selected_node: selected_node(),
+                                        on_link,
+                                    }
+                                }
+                            }
+
+                            if !coverage.covered.is_empty() {
+                                p { class: "text-[10px] uppercase tracking-wide text-slate-500", "Covered" }
+                                for covered in coverage.covered.iter().cloned() {
+                                    div { key: "{covered.behavior.id}", class: "rounded border border-slate-200 bg-white px-2 py-1",
+                                        span { class: "font-mono text-[11px] text-slate-700", "{covered.behavior.id}" }
+                                        div { class: "mt-1 flex flex-wrap gap-1",
+                                            for node_id in covered.nodes.iter().copied() {
+                                                span { class: "flex items-center gap-0.5 rounded bg-emerald-50 px-1.5 py-0.5 text-[10px] text-emerald-700",
+                                                    button {
+                                                        class: "hover:underline",
+                                                        onclick: move |_| on_select_node.call(node_id),
+                                                        "{node_name(node_id)}"
+                                                    }
+                                                    button {
+                                                        class: "text-emerald-500 hover:text-rose-500",
+                                                        title: "Unlink",
+                                                        onclick: {
+                                                            let behavior_id = covered.behavior.id.clone();
+                                                            move |_| on_unlink.call((node_id, behavior_id.clone()))
+                                                        },
+                                                        XIcon { class: "h-2.5 w-2.5" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            for unknown in coverage.unknown_refs.iter().cloned() {
+                                button {
+                                    class: "flex w-full items-start gap-2 border-l-2 border-l-amber-400 bg-amber-50/50 px-2 py-1 text-left",
+                                    onclick: move |_| on_select_node.call(unknown.node_id),
+                                    AlertTriangleIcon { class: "h-3.5 w-3.5 shrink-0 text-amber-500" }
+                                    span { class: "text-[11px] text-slate-600",
+                                        "{node_name(unknown.node_id)} references unknown behavior {unknown.behavior_ref}"
+                                    }
+                                }
+                            }
+                        },
+                        _ => rsx! {
+                            p { class: "text-[11px] text-slate-500", "Paste a spec to see which behaviors this workflow represents." }
+                            textarea {
+                                class: "h-24 w-full rounded border border-slate-200 bg-white p-2 font-mono text-[11px] text-slate-700",
+                                placeholder: "specification:\n  identity:\n    id: ...",
+                                value: "{draft}",
+                                oninput: move |evt| draft.set(evt.value()),
+                            }
+                            if let Some(message) = error() {
+                                p { class: "text-[11px] text-rose-600", "{message}" }
+                            }
+                            button {
+                                class: "rounded bg-slate-800 px-2 py-1 text-[11px] text-white hover:bg-slate-700",
+                                onclick: move |_| {
+                                    let yaml = draft.read().clone();
+                                    match SpecBehaviors::from_yaml(&yaml) {
+                                        Ok(parsed) => {
+                                            save_spec_yaml(Some(&yaml));
+                                            spec.set(Some(parsed));
+                                            draft.set(String::new());
+                                            error.set(None);
+                                        }
+                                        Err(message) => error.set(Some(message)),
+                                    }
+                                },
+                                "Load spec"
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn TodoRow(
+    behavior: SpecBehavior,
+    selected_node: Option<NodeId>,
+    on_link: EventHandler<(NodeId, String)>,
+) -> Element {
+    let behavior_id = behavior.id.clone();
+    rsx! {
+        div { class: "flex items-start gap-2 rounded border-l-2 border-l-amber-400 bg-amber-50/50 px-2 py-1",
+            div { class: "flex-1",
+                span { class: "font-mono text-[11px] text-slate-700", "{behavior.id}" }
+                if !behavior.description.is_empty() {
+                    p { class: "text-[10px] leading-relaxed text-slate-500", "{behavior.description}" }
+                }
+            }
+            if let Some(node_id) = selected_node {
+                button {
+                    class: "shrink-0 rounded border border-slate-200 bg-white px-1.5 py-0.5 text-[10px] text-slate-600 hover:bg-slate-100",
+                    title: "Mark the selected node as implementing this behavior",
+                    onclick: move |_| on_link.call((node_id, behavior_id.clone())),
+                    "Link selected"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_spec_yaml() -> Option<String> {
+    use web_sys::window;
+    window()
+        .and_then(|w| w.local_storage().ok())
+        .flatten()
+        .and_then(|s| s.get_item(COVERAGE_SPEC_STORAGE_KEY).ok().flatten())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const fn load_spec_yaml() -> Option<String> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_spec_yaml(yaml: Option<&str>) {
+    use web_sys::window;
+    if let Some(storage) = window().and_then(|w| w.local_storage().ok()).flatten() {
+        let _ = match yaml {
+            Some(yaml) => storage.set_item(COVERAGE_SPEC_STORAGE_KEY, yaml),
+            None => storage.remove_item(COVERAGE_SPEC_STORAGE_KEY),
+        };
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+const fn save_spec_yaml(_yaml: Option<&str>) {}
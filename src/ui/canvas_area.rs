@@ -4,17 +4,99 @@
 #![warn(clippy::pedantic)]
 #![forbid(unsafe_code)]
 
+use crate::graph::frames::FrameId;
+use crate::graph::profile::RunProfile;
+use crate::graph::NodeId;
 use crate::hooks::use_canvas_interaction::CanvasInteraction;
 use crate::hooks::use_selection::SelectionState;
 use crate::hooks::use_ui_panels::UiPanels;
-use crate::hooks::use_workflow_state::WorkflowState;
+use crate::hooks::use_workflow_state::{use_node, WorkflowState};
+use crate::ui::canvas_settings::CanvasSettings;
+use crate::ui::capabilities::Capability;
+use crate::ui::collab_presence::{CollabPeers, CollabPresence};
 use crate::ui::constants::{
     DEFAULT_CANVAS_HEIGHT, DEFAULT_CANVAS_WIDTH, FIT_VIEW_PADDING, ZOOM_CENTER_X, ZOOM_CENTER_Y,
     ZOOM_DELTA,
 };
-use crate::ui::{FlowEdges, FlowMinimap, FlowNodeComponent, FlowPosition, ParallelGroupOverlay};
+use crate::ui::node::NodeProfile;
+use crate::ui::{
+    CanvasFrames, FlowEdges, FlowMinimap, FlowNodeComponent, FlowPosition, ParallelGroupOverlay,
+};
 use dioxus::html::input_data::MouseButton;
 use dioxus::prelude::*;
+
+/// A node on the canvas, subscribed to that node alone so moving one node
+/// re-renders one node.
+#[component]
+fn CanvasNode(
+    node_id: NodeId,
+    selected: bool,
+    inline_open: bool,
+    on_mouse_down: EventHandler<MouseEvent>,
+    on_click: EventHandler<MouseEvent>,
+    on_double_click: EventHandler<MouseEvent>,
+    on_handle_mouse_down: EventHandler<(MouseEvent, String)>,
+    on_handle_mouse_enter: EventHandler<String>,
+    on_handle_mouse_leave: EventHandler<()>,
+    on_inline_change: EventHandler<serde_json::Value>,
+    on_inline_close: EventHandler<()>,
+    profile: Option<NodeProfile>,
+) -> Element {
+    let node = use_node(node_id);
+    let Some(node) = node.read().clone() else {
+        return rsx! {};
+    };
+    rsx! {
+        FlowNodeComponent {
+            node,
+            selected,
+            inline_open,
+            on_mouse_down,
+            on_click,
+            on_double_click,
+            on_handle_mouse_down,
+            on_handle_mouse_enter,
+            on_handle_mouse_leave,
+            on_inline_change,
+            on_inline_close,
+            profile,
+        }
+    }
+}
+
+/// Starts a frame move or resize from a mouse-down on its title bar or grip.
+fn begin_frame_edit(
+    evt: &MouseEvent,
+    frame_id: FrameId,
+    resize: bool,
+    workflow: WorkflowState,
+    canvas: CanvasInteraction,
+) {
+    if evt.trigger_button() != Some(MouseButton::Primary)
+        || canvas.is_space_hand_active()
+        || !workflow.can(Capability::EditWorkflow)
+    {
+        return;
+    }
+    evt.stop_propagation();
+    let page = evt.page_coordinates();
+    let origin = *canvas.canvas_origin().read();
+    #[allow(clippy::cast_possible_truncation)]
+    let page_point = (page.x as f32, page.y as f32);
+    let Some(mouse_pos) =
+        crate::ui::interaction_guards::safe_canvas_point(page_point, (origin.x, origin.y))
+    else {
+        return;
+    };
+    canvas.update_mouse(mouse_pos);
+    workflow.begin_frame_edit(frame_id);
+    if resize {
+        canvas.start_frame_resize(frame_id);
+    } else {
+        canvas.start_frame_move(frame_id);
+    }
+}
+
 #[component]
 pub fn CanvasArea(
     workflow: WorkflowState,
@@ -24,14 +106,20 @@ pub fn CanvasArea(
     temp_edge: Memo<Option<(FlowPosition, FlowPosition)>>,
     preview_nodes: Memo<Vec<(String, String, f32, f32)>>,
     preview_edges: Memo<Vec<(String, String)>>,
+    removed_preview_nodes: Memo<Vec<(String, String, f32, f32)>>,
+    removed_preview_edges: Memo<Vec<(String, String)>>,
     show_inspector: Signal<bool>,
+    canvas_settings: CanvasSettings,
+    profile: Memo<Option<RunProfile>>,
 ) -> Element {
     let nodes = workflow.nodes();
+    let node_ids = workflow.node_ids();
     let connections = workflow.connections();
     let viewport_state = workflow.viewport();
     let vx = viewport_state.read().x;
     let vy = viewport_state.read().y;
     let vz = viewport_state.read().zoom;
+    let grid_style = canvas_settings.background_style(vx, vy, vz);
 
     let running_node_ids = use_memo(move || {
         nodes
@@ -43,12 +131,25 @@ pub fn CanvasArea(
     });
 
     let zoom = use_memo(move || viewport_state.read().zoom);
+    let critical_edges = use_memo(move || {
+        profile
+            .read()
+            .as_ref()
+            .map(|profile| {
+                profile
+                    .critical_path
+                    .windows(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .collect()
+            })
+            .unwrap_or_default()
+    });
 
     rsx! {
-        // Dot grid background
+        // Grid background
         div {
             class: "absolute inset-0 pointer-events-none",
-            style: "background-image: radial-gradient(circle, rgba(100, 116, 139, 0.33) 1px, transparent 1px); background-size: calc(22px * {vz}) calc(22px * {vz}); background-position: {vx}px {vy}px;"
+            style: "{grid_style}"
         }
 
         // Animated gradient shimmer
@@ -61,12 +162,27 @@ pub fn CanvasArea(
         div {
             class: "absolute origin-top-left",
             style: "transform: translate({vx}px, {vy}px) scale({vz}); will-change: transform;",
+            CanvasFrames {
+                frames: workflow.frames(),
+                on_move_start: move |(evt, frame_id): (MouseEvent, FrameId)| {
+                    begin_frame_edit(&evt, frame_id, false, workflow, canvas);
+                },
+                on_resize_start: move |(evt, frame_id): (MouseEvent, FrameId)| {
+                    begin_frame_edit(&evt, frame_id, true, workflow, canvas);
+                },
+                on_rename: move |(frame_id, title): (FrameId, String)| {
+                    workflow.rename_frame(frame_id, &title);
+                },
+                on_remove: move |frame_id| workflow.remove_frame(frame_id),
+            }
+
             FlowEdges {
                 edges: connections,
                 nodes: nodes,
                 temp_edge: temp_edge,
                 running_node_ids: running_node_ids,
                 zoom: zoom,
+                critical_edges: critical_edges,
             }
 
             ParallelGroupOverlay {
@@ -74,6 +190,8 @@ pub fn CanvasArea(
                 connections: connections,
             }
 
+            CollabPresence { nodes_by_id: workflow.nodes_by_id() }
+
             if !preview_edges.read().is_empty() {
                 svg {
                     class: "absolute inset-0 overflow-visible pointer-events-none w-full h-full z-0",
@@ -90,6 +208,32 @@ pub fn CanvasArea(
                 }
             }
 
+            if !removed_preview_edges.read().is_empty() {
+                svg {
+                    class: "absolute inset-0 overflow-visible pointer-events-none w-full h-full z-20",
+                    for (removed_edge_id, removed_path) in removed_preview_edges.read().iter() {
+                        path {
+                            key: "{removed_edge_id}",
+                            d: "{removed_path}",
+                            fill: "none",
+                            stroke: "rgba(244, 63, 94, 0.8)",
+                            stroke_width: "2.5",
+                            stroke_dasharray: "3 5"
+                        }
+                    }
+                }
+            }
+
+            for (removed_node_id, removed_node_type, removed_x, removed_y) in removed_preview_nodes.read().iter() {
+                div {
+                    key: "{removed_node_id}",
+                    class: "pointer-events-none absolute w-[220px] z-20 rounded-xl border-2 border-dashed border-rose-400/80 bg-rose-500/10 px-3 py-2",
+                    style: "left: {removed_x}px; top: {removed_y}px;",
+                    div { class: "text-[11px] font-semibold text-rose-700", "Remove" }
+                    div { class: "text-[10px] font-mono text-rose-600", "{removed_node_type}" }
+                }
+            }
+
             for (preview_node_id, preview_node_type, preview_x, preview_y) in preview_nodes.read().iter() {
                 div {
                     key: "{preview_node_id}",
@@ -100,9 +244,8 @@ pub fn CanvasArea(
                 }
             }
 
-            for node in nodes.read().iter().cloned() {
+            for node_id in node_ids.read().iter().copied() {
                  {
-                     let node_id = node.id;
                      let is_selected = selection.is_selected(node_id);
                      let is_inline_open = panels.is_inline_panel_open(node_id);
                      let workflow_clone = workflow;
@@ -110,11 +253,18 @@ pub fn CanvasArea(
                      let canvas_clone = canvas;
                      let panels_clone = panels;
                      let mut show_inspector_clone = show_inspector;
+                     let node_profile = profile.read().as_ref().and_then(|profile| {
+                         Some(NodeProfile {
+                             duration_ms: *profile.durations.get(&node_id)?,
+                             heat: profile.heat(node_id)?,
+                             critical: profile.is_critical(node_id),
+                         })
+                     });
 
                      rsx! {
-                         FlowNodeComponent {
+                         CanvasNode {
                              key: "{node_id}",
-                             node,
+                             node_id,
                              selected: is_selected,
                              inline_open: is_inline_open,
                              on_mouse_down: move |evt: MouseEvent| {
@@ -168,6 +318,9 @@ pub fn CanvasArea(
                              },
                              on_handle_mouse_down: move |args: (MouseEvent, String)| {
                                  let (evt, handle_type) = args;
+                                 if !workflow_clone.can(Capability::EditWorkflow) {
+                                     return;
+                                 }
                                  selection_clone.clear_pending_drag();
                                  canvas_clone.clear_drag_anchor();
                                  let page = evt.page_coordinates();
@@ -209,15 +362,16 @@ pub fn CanvasArea(
                              on_handle_mouse_enter: move |handle_type| canvas_clone.set_hovered_handle(Some((node_id, handle_type))),
                              on_handle_mouse_leave: move |()| canvas_clone.set_hovered_handle(None),
                               on_inline_change: move |new_config| {
-                                  let mut binding = workflow_clone.workflow();
-                                  let mut wf = binding.write();
-                                  if let Some(n) = wf.nodes.iter_mut().find(|n| n.id == node_id) {
-                                      n.apply_config_update(&new_config);
-                                  }
+                                  workflow_clone.update(|wf| {
+                                      if let Some(n) = wf.nodes.iter_mut().find(|n| n.id == node_id) {
+                                          n.apply_config_update(&new_config);
+                                      }
+                                  });
                               },
                              on_inline_close: move |()| {
                                  panels_clone.close_inline_panel();
-                             }
+                             },
+                             profile: node_profile,
                          }
                      }
                  }
@@ -262,5 +416,7 @@ pub fn CanvasArea(
                 workflow.fit_view(DEFAULT_CANVAS_WIDTH, DEFAULT_CANVAS_HEIGHT, FIT_VIEW_PADDING);
             }
         }
+
+        CollabPeers {}
     }
 }
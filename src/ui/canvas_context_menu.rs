@@ -2,9 +2,9 @@ use dioxus::prelude::*;
 use std::fmt::Write;
 use web_sys::window;
 
-/// Menu dimensions (width: 224px = w-56, estimated height ~180px)
+/// Menu dimensions (width: 224px = w-56, estimated height ~260px)
 const MENU_WIDTH: f32 = 224.0;
-const MENU_HEIGHT: f32 = 180.0;
+const MENU_HEIGHT: f32 = 260.0;
 const PADDING: f32 = 8.0;
 
 /// Shared Tailwind classes for context menu action buttons.
@@ -45,6 +45,10 @@ pub fn CanvasContextMenu(
     on_add_node: EventHandler<MouseEvent>,
     on_fit_view: EventHandler<MouseEvent>,
     on_layout: EventHandler<MouseEvent>,
+    on_add_frame: EventHandler<MouseEvent>,
+    can_export_selection: ReadSignal<bool>,
+    on_export_selection: EventHandler<MouseEvent>,
+    on_import_subgraph: EventHandler<MouseEvent>,
 ) -> Element {
     if !open() {
         return rsx! {};
@@ -104,6 +108,33 @@ pub fn CanvasContextMenu(
                     "Auto Layout"
                 }
 
+                button {
+                    r#type: "button",
+                    role: "menuitem",
+                    class: "{MENU_BUTTON_CLASSES}",
+                    onclick: move |evt| on_add_frame.call(evt),
+                    if can_export_selection() { "Frame Selection" } else { "Add Frame" }
+                }
+
+                div { class: "border-t border-slate-700/80" }
+
+                button {
+                    r#type: "button",
+                    role: "menuitem",
+                    class: "{MENU_BUTTON_CLASSES} disabled:cursor-not-allowed disabled:text-slate-500 disabled:hover:bg-transparent",
+                    disabled: !can_export_selection(),
+                    onclick: move |evt| on_export_selection.call(evt),
+                    "Export Selection"
+                }
+
+                button {
+                    r#type: "button",
+                    role: "menuitem",
+                    class: "{MENU_BUTTON_CLASSES}",
+                    onclick: move |evt| on_import_subgraph.call(evt),
+                    "Import as Subgraph"
+                }
+
                 div {
                     class: "border-t border-slate-700 px-3 py-2 text-xs text-slate-400",
                     "Hint: Press Esc or click outside to close"
@@ -126,7 +157,7 @@ mod tests {
     const VIEWPORT_W: f32 = 1280.0;
     const VIEWPORT_H: f32 = 720.0;
     const MENU_W: f32 = 224.0;
-    const MENU_H: f32 = 180.0;
+    const MENU_H: f32 = 260.0;
     const PADDING: f32 = 8.0;
 
     #[test]
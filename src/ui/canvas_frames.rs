@@ -0,0 +1,66 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+use crate::graph::frames::{Frame, FrameId, FRAME_RESIZE_HANDLE, FRAME_TITLE_HEIGHT};
+use dioxus::prelude::*;
+
+/// Titled background rectangles drawn beneath edges and nodes.
+///
+/// Only the title bar and the resize grip take pointer events; the body
+/// lets clicks through so marquee selection still works inside a frame.
+#[component]
+pub fn CanvasFrames(
+    frames: ReadSignal<Vec<Frame>>,
+    on_move_start: EventHandler<(MouseEvent, FrameId)>,
+    on_resize_start: EventHandler<(MouseEvent, FrameId)>,
+    on_rename: EventHandler<(FrameId, String)>,
+    on_remove: EventHandler<FrameId>,
+) -> Element {
+    rsx! {
+        for frame in frames.read().iter().cloned() {
+            {
+                let frame_id = frame.id;
+                let x = frame.x;
+                let y = frame.y;
+                let width = frame.width;
+                let height = frame.height;
+
+                rsx! {
+                    div {
+                        key: "{frame_id}",
+                        class: "pointer-events-none absolute rounded-xl border border-slate-600/70 bg-slate-800/25",
+                        style: "left: {x}px; top: {y}px; width: {width}px; height: {height}px;",
+                        div {
+                            class: "pointer-events-auto flex cursor-grab items-center gap-2 rounded-t-xl border-b border-slate-600/50 bg-slate-800/60 px-2",
+                            style: "height: {FRAME_TITLE_HEIGHT}px;",
+                            onmousedown: move |evt| on_move_start.call((evt, frame_id)),
+                            input {
+                                class: "min-w-0 flex-1 bg-transparent text-[12px] font-semibold text-slate-200 outline-none",
+                                value: "{frame.title}",
+                                aria_label: "Frame title",
+                                onmousedown: move |evt| evt.stop_propagation(),
+                                onchange: move |evt| on_rename.call((frame_id, evt.value())),
+                            }
+                            button {
+                                r#type: "button",
+                                class: "text-[12px] leading-none text-slate-400 hover:text-rose-300",
+                                title: "Remove frame",
+                                onmousedown: move |evt| evt.stop_propagation(),
+                                onclick: move |_| on_remove.call(frame_id),
+                                "×"
+                            }
+                        }
+                        div {
+                            class: "pointer-events-auto absolute bottom-0 right-0 cursor-nwse-resize rounded-br-xl border-b-2 border-r-2 border-slate-500/80",
+                            style: "width: {FRAME_RESIZE_HANDLE}px; height: {FRAME_RESIZE_HANDLE}px;",
+                            onmousedown: move |evt| on_resize_start.call((evt, frame_id)),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,150 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! Per-workspace canvas preferences: grid spacing, snapping, grid style and
+//! background colour.
+
+use serde::{Deserialize, Serialize};
+
+pub const MIN_GRID_SIZE: f32 = 4.0;
+pub const MAX_GRID_SIZE: f32 = 80.0;
+const DEFAULT_BACKGROUND: &str = "#f2f7fa";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GridStyle {
+    #[default]
+    Dots,
+    Lines,
+}
+
+impl GridStyle {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Dots => "dots",
+            Self::Lines => "lines",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "dots" => Some(Self::Dots),
+            "lines" => Some(Self::Lines),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CanvasSettings {
+    /// Grid spacing in canvas units, used both for drawing and snapping.
+    pub grid_size: f32,
+    pub snap_to_grid: bool,
+    pub grid_style: GridStyle,
+    /// Background colour as `#rrggbb`.
+    pub background: String,
+}
+
+impl Default for CanvasSettings {
+    fn default() -> Self {
+        Self {
+            grid_size: crate::graph::calc::DEFAULT_SNAP_GRID,
+            snap_to_grid: true,
+            grid_style: GridStyle::Dots,
+            background: DEFAULT_BACKGROUND.to_string(),
+        }
+    }
+}
+
+impl CanvasSettings {
+    /// Clamps the grid size and falls back to the default colour when the
+    /// stored one is not a `#rrggbb` hex value.
+    #[must_use]
+    pub fn sanitized(mut self) -> Self {
+        self.grid_size = if self.grid_size.is_finite() {
+            self.grid_size.clamp(MIN_GRID_SIZE, MAX_GRID_SIZE)
+        } else {
+            crate::graph::calc::DEFAULT_SNAP_GRID
+        };
+        if !is_hex_color(&self.background) {
+            self.background = DEFAULT_BACKGROUND.to_string();
+        }
+        self
+    }
+
+    /// The grid nodes snap to while dragging, or `None` when snapping is off.
+    #[must_use]
+    pub fn snap_grid(&self) -> Option<f32> {
+        self.snap_to_grid.then_some(self.grid_size)
+    }
+
+    /// Inline CSS for the canvas background layer at the given viewport.
+    #[must_use]
+    pub fn background_style(&self, vx: f32, vy: f32, zoom: f32) -> String {
+        // The drawn grid doubles the snap spacing so dots stay legible.
+        let cell = self.grid_size * 2.0 * zoom;
+        let image = match self.grid_style {
+            GridStyle::Dots => {
+                "radial-gradient(circle, rgba(100, 116, 139, 0.33) 1px, transparent 1px)".to_string()
+            }
+            GridStyle::Lines => "linear-gradient(to right, rgba(100, 116, 139, 0.18) 1px, transparent 1px), linear-gradient(to bottom, rgba(100, 116, 139, 0.18) 1px, transparent 1px)".to_string(),
+        };
+        format!(
+            "background-color: {}; background-image: {image}; background-size: {cell}px {cell}px; background-position: {vx}px {vy}px;",
+            self.background
+        )
+    }
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_stored_settings_when_sanitized_then_invalid_values_fall_back() {
+        let settings: CanvasSettings =
+            serde_json::from_str(r#"{"grid_size": 500, "background": "red"}"#).unwrap();
+        let settings = settings.sanitized();
+
+        assert_eq!(settings.grid_size, MAX_GRID_SIZE);
+        assert_eq!(settings.background, DEFAULT_BACKGROUND);
+        assert_eq!(settings.grid_style, GridStyle::Dots);
+        assert_eq!(settings.snap_grid(), Some(MAX_GRID_SIZE));
+    }
+
+    #[test]
+    fn given_line_grid_without_snap_when_rendering_then_style_reflects_settings() {
+        let settings = CanvasSettings {
+            grid_size: 16.0,
+            snap_to_grid: false,
+            grid_style: GridStyle::Lines,
+            background: "#101820".to_string(),
+        };
+
+        let style = settings.background_style(5.0, -3.0, 0.5);
+
+        assert_eq!(settings.snap_grid(), None);
+        assert!(style.starts_with("background-color: #101820;"));
+        assert!(style.contains("linear-gradient(to right"));
+        assert!(style.contains("background-size: 16px 16px;"));
+        assert!(style.contains("background-position: 5px -3px;"));
+    }
+}
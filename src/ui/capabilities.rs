@@ -0,0 +1,340 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! What the person using an embedded editor may do.
+//!
+//! A host page embeds the editor with `?capabilities=<token>`, where the
+//! token is an `EdDSA` (Ed25519) JWT whose claims carry
+//! `{"role": "...", "exp": ...}` (`exp` optional, in seconds since the Unix
+//! epoch). The host signs with its private key; the editor is built with
+//! only the public key, so nothing in the shipped bundle can mint a token.
+//! Anything short of a valid, unexpired signature grants the viewer role,
+//! so a forged or mangled embed fails closed. Only a build configured as
+//! standalone, with no host page, grants every capability.
+//!
+//! Every web build must choose: `OYA_STANDALONE=1`, or
+//! `OYA_CAPABILITY_PUBLIC_KEY=<base64url Ed25519 public key>`. A build with
+//! neither fails to compile rather than shipping an editor nobody can use.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Deserialize;
+
+use crate::ui::deep_link::decode;
+
+pub const CAPABILITIES_PARAM: &str = "capabilities";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Viewer,
+    Editor,
+    Admin,
+}
+
+impl Role {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Viewer => "viewer",
+            Self::Editor => "editor",
+            Self::Admin => "admin",
+        }
+    }
+
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "viewer" => Some(Self::Viewer),
+            "editor" => Some(Self::Editor),
+            "admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// Change nodes, connections, frames, config or the workflow contract.
+    EditWorkflow,
+    /// Execute the workflow and clear its cached outputs.
+    RunWorkflow,
+    /// Browse and manage Restate deployments.
+    ManageDeployments,
+}
+
+const BUILD_STANDALONE: Option<&str> = option_env!("OYA_STANDALONE");
+const BUILD_PUBLIC_KEY: Option<&str> = option_env!("OYA_CAPABILITY_PUBLIC_KEY");
+
+const fn is_standalone_build() -> bool {
+    matches!(BUILD_STANDALONE, Some(flag) if flag.len() == 1 && flag.as_bytes()[0] == b'1')
+}
+
+#[cfg(target_arch = "wasm32")]
+const _: () = assert!(
+    is_standalone_build() || BUILD_PUBLIC_KEY.is_some(),
+    "set OYA_STANDALONE=1 or OYA_CAPABILITY_PUBLIC_KEY when building the editor"
+);
+
+/// How the editor is deployed, fixed at build time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CapabilityMode {
+    /// Runs on its own with no host page; every capability is granted and
+    /// tokens are ignored.
+    Standalone,
+    /// Embedded by a host that signs capability tokens with the private half
+    /// of this key. `None` when the configured key is not a valid Ed25519
+    /// public key, which leaves every page read-only.
+    Embedded { public_key: Option<VerifyingKey> },
+}
+
+impl CapabilityMode {
+    /// Mode from the build environment: `OYA_STANDALONE=1` selects
+    /// standalone, otherwise `OYA_CAPABILITY_PUBLIC_KEY` verifies tokens.
+    #[must_use]
+    pub fn from_build_env() -> Self {
+        if is_standalone_build() {
+            return Self::Standalone;
+        }
+        Self::embedded(BUILD_PUBLIC_KEY.unwrap_or_default())
+    }
+
+    /// Embedded mode verifying tokens with a base64url-encoded Ed25519
+    /// public key.
+    #[must_use]
+    pub fn embedded(public_key: &str) -> Self {
+        let public_key = URL_SAFE_NO_PAD
+            .decode(public_key.trim().trim_end_matches('='))
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok());
+        Self::Embedded { public_key }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    role: Role,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::for_role(Role::Viewer)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenHeader {
+    alg: String,
+}
+
+#[derive(Deserialize)]
+struct TokenClaims {
+    role: Role,
+    #[serde(default)]
+    exp: Option<u64>,
+}
+
+impl Capabilities {
+    #[must_use]
+    pub const fn for_role(role: Role) -> Self {
+        Self { role }
+    }
+
+    /// Capabilities granted by a host-signed token, checked against
+    /// `public_key` at `now` (seconds since the Unix epoch). Viewer unless
+    /// the token verifies.
+    #[must_use]
+    pub fn from_token(token: &str, public_key: &VerifyingKey, now: u64) -> Self {
+        Self::for_role(verified_role(token, public_key, now).unwrap_or(Role::Viewer))
+    }
+
+    /// Capabilities for a page opened at `page_url`.
+    #[must_use]
+    pub fn from_page_url(page_url: &str, mode: &CapabilityMode, now: u64) -> Self {
+        let public_key = match mode {
+            CapabilityMode::Standalone => return Self::for_role(Role::Admin),
+            CapabilityMode::Embedded { public_key: None } => return Self::default(),
+            CapabilityMode::Embedded {
+                public_key: Some(public_key),
+            } => public_key,
+        };
+        page_url
+            .split_once('?')
+            .map(|(_, query)| query.split('#').next().unwrap_or(query))
+            .and_then(|query| {
+                query
+                    .split('&')
+                    .filter_map(|pair| pair.split_once('='))
+                    .find(|(name, _)| *name == CAPABILITIES_PARAM)
+            })
+            .and_then(|(_, value)| decode(value))
+            .map_or_else(Self::default, |token| {
+                Self::from_token(&token, public_key, now)
+            })
+    }
+
+    /// Capabilities for the page the editor is running in.
+    #[cfg(target_arch = "wasm32")]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn for_current_page() -> Self {
+        let now = (js_sys::Date::now() / 1000.0) as u64;
+        web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.url().ok())
+            .map_or_else(Self::default, |url| {
+                Self::from_page_url(&url, &CapabilityMode::from_build_env(), now)
+            })
+    }
+
+    #[must_use]
+    pub const fn role(self) -> Role {
+        self.role
+    }
+
+    #[must_use]
+    pub const fn can(self, capability: Capability) -> bool {
+        match capability {
+            Capability::EditWorkflow | Capability::RunWorkflow => {
+                matches!(self.role, Role::Editor | Role::Admin)
+            }
+            Capability::ManageDeployments => matches!(self.role, Role::Admin),
+        }
+    }
+}
+
+fn verified_role(token: &str, public_key: &VerifyingKey, now: u64) -> Option<Role> {
+    let (signed, signature) = token.rsplit_once('.')?;
+    let (header, claims) = signed.split_once('.')?;
+    let header: TokenHeader = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).ok()?).ok()?;
+    if header.alg != "EdDSA" {
+        return None;
+    }
+    let signature = Signature::from_slice(&URL_SAFE_NO_PAD.decode(signature).ok()?).ok()?;
+    public_key
+        .verify_strict(signed.as_bytes(), &signature)
+        .ok()?;
+    let claims: TokenClaims = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).ok()?).ok()?;
+    claims
+        .exp
+        .is_none_or(|exp| now < exp)
+        .then_some(claims.role)
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_roles_when_checking_capabilities_then_each_role_adds_to_the_last() {
+        let viewer = Capabilities::for_role(Role::Viewer);
+        let editor = Capabilities::for_role(Role::Editor);
+        let admin = Capabilities::for_role(Role::Admin);
+
+        assert!(!viewer.can(Capability::EditWorkflow));
+        assert!(!viewer.can(Capability::RunWorkflow));
+        assert!(editor.can(Capability::EditWorkflow));
+        assert!(editor.can(Capability::RunWorkflow));
+        assert!(!editor.can(Capability::ManageDeployments));
+        assert!(admin.can(Capability::ManageDeployments));
+    }
+
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const NOW: u64 = 1_800_000_000;
+
+    fn host_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    fn sign(claims: &str, key: &SigningKey) -> String {
+        let signed = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(r#"{"alg":"EdDSA","typ":"JWT"}"#),
+            URL_SAFE_NO_PAD.encode(claims)
+        );
+        let signature = key.sign(signed.as_bytes());
+        format!("{signed}.{}", URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+    }
+
+    #[test]
+    fn given_signed_tokens_when_verifying_then_only_valid_unexpired_ones_grant_their_role() {
+        let (key, public_key) = (host_key(), host_key().verifying_key());
+        let role = |token: &str| Capabilities::from_token(token, &public_key, NOW).role();
+        let other_key = SigningKey::from_bytes(&[9; 32]);
+
+        assert_eq!(role(&sign(r#"{"role":"editor"}"#, &key)), Role::Editor);
+        assert_eq!(
+            role(&sign(
+                &format!(r#"{{"role":"admin","exp":{}}}"#, NOW + 60),
+                &key
+            )),
+            Role::Admin
+        );
+        assert_eq!(
+            role(&sign(&format!(r#"{{"role":"admin","exp":{NOW}}}"#), &key)),
+            Role::Viewer
+        );
+        assert_eq!(role(&sign(r#"{"role":"admin"}"#, &other_key)), Role::Viewer);
+        assert_eq!(role("admin"), Role::Viewer);
+        assert_eq!(role(r#"{"role":"admin"}"#), Role::Viewer);
+    }
+
+    #[test]
+    fn given_tampered_claims_when_verifying_then_token_grants_viewer() {
+        let token = sign(r#"{"role":"viewer"}"#, &host_key());
+        let mut parts = token.split('.').collect::<Vec<_>>();
+        let forged = URL_SAFE_NO_PAD.encode(r#"{"role":"admin"}"#);
+        parts[1] = &forged;
+
+        let capabilities =
+            Capabilities::from_token(&parts.join("."), &host_key().verifying_key(), NOW);
+
+        assert_eq!(capabilities.role(), Role::Viewer);
+    }
+
+    #[test]
+    fn given_page_urls_when_reading_token_then_only_standalone_defaults_to_admin() {
+        let embedded = CapabilityMode::embedded(
+            &URL_SAFE_NO_PAD.encode(host_key().verifying_key().as_bytes()),
+        );
+        let role =
+            |url: &str, mode: &CapabilityMode| Capabilities::from_page_url(url, mode, NOW).role();
+        let token = sign(r#"{"role":"editor"}"#, &host_key());
+
+        assert_eq!(role("https://app.example/", &embedded), Role::Viewer);
+        assert_eq!(
+            role(
+                &format!("https://app.example/?workflow=x&capabilities={token}#top"),
+                &embedded
+            ),
+            Role::Editor
+        );
+        assert_eq!(
+            role("https://app.example/?capabilities=admin", &embedded),
+            Role::Viewer
+        );
+        assert_eq!(
+            role(
+                &format!("https://app.example/?capabilities={token}"),
+                &CapabilityMode::embedded("not-a-key")
+            ),
+            Role::Viewer
+        );
+        assert_eq!(
+            role("https://app.example/", &CapabilityMode::Standalone),
+            Role::Admin
+        );
+    }
+}
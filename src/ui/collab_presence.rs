@@ -0,0 +1,78 @@
+//! Other collaborators on the canvas: their pointers, what they have
+//! selected, and who is in the room.
+
+use dioxus::prelude::*;
+
+use crate::graph::NodeIndex;
+use crate::hooks::use_collab::use_collab;
+use crate::ui::constants::{NODE_HEIGHT, NODE_WIDTH};
+
+/// Remote cursors and selection outlines, drawn in canvas coordinates
+/// inside the transformed layer.
+#[component]
+pub fn CollabPresence(nodes_by_id: ReadSignal<NodeIndex>) -> Element {
+    let peers = use_collab().peers();
+
+    rsx! {
+        for presence in peers.read().iter() {
+            for node in presence.selection.iter().filter_map(|id| nodes_by_id.read().get(id).cloned()) {
+                div {
+                    key: "{presence.peer}-{node.id}",
+                    class: "pointer-events-none absolute rounded-xl border-2 z-20",
+                    style: "left: {node.x - 4.0}px; top: {node.y - 4.0}px; width: {NODE_WIDTH + 8.0}px; height: {NODE_HEIGHT + 8.0}px; border-color: {presence.color};",
+                }
+            }
+            if let Some((x, y)) = presence.cursor {
+                div {
+                    key: "{presence.peer}-cursor",
+                    class: "pointer-events-none absolute z-50",
+                    style: "left: {x}px; top: {y}px;",
+                    svg {
+                        width: "16",
+                        height: "16",
+                        view_box: "0 0 16 16",
+                        path { d: "M1 1 L1 13 L5 9 L9 15 L11 14 L7 8 L13 8 Z", fill: "{presence.color}" }
+                    }
+                    span {
+                        class: "ml-3 rounded px-1.5 py-0.5 text-[10px] font-medium text-white whitespace-nowrap",
+                        style: "background-color: {presence.color};",
+                        "{presence.display_name}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Initials of everyone else in the room, for the corner of the canvas.
+#[component]
+pub fn CollabPeers() -> Element {
+    let peers = use_collab().peers();
+    if peers.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "pointer-events-none absolute right-4 top-4 z-30 flex -space-x-2",
+            for presence in peers.read().iter() {
+                div {
+                    key: "{presence.peer}",
+                    class: "flex h-7 w-7 items-center justify-center rounded-full border-2 border-white text-[11px] font-semibold text-white",
+                    style: "background-color: {presence.color};",
+                    title: "{presence.display_name}",
+                    "{initials(&presence.display_name)}"
+                }
+            }
+        }
+    }
+}
+
+fn initials(display_name: &str) -> String {
+    display_name
+        .split_whitespace()
+        .filter_map(|word| word.chars().next())
+        .take(2)
+        .flat_map(char::to_uppercase)
+        .collect()
+}
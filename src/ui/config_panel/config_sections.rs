@@ -1,4 +1,5 @@
 use super::{get_str_val, get_u64_val};
+use crate::graph::cron::{CronSchedule, SCHEDULE_CONFIG_KEY};
 use crate::graph::WorkflowNode;
 use crate::ui::panel_types::HttpMethod;
 use dioxus::prelude::*;
@@ -22,6 +23,12 @@ pub(super) fn EntryConfig(
                         value: get_str_val(&config, "path"),
                         on_change: move |value: String| update_str.call(("path".to_owned(), value))
                     }
+                    FieldInput {
+                        input_cls: input_cls,
+                        label: "Response Node",
+                        value: get_str_val(&config, "response_node"),
+                        on_change: move |value: String| update_str.call(("response_node".to_owned(), value))
+                    }
                     div { class: "flex flex-col gap-1.5",
                         label { class: "text-[11px] font-medium uppercase tracking-wide text-slate-500", "HTTP Method" }
                         select {
@@ -38,14 +45,22 @@ pub(super) fn EntryConfig(
                     }
                 }
             }
-            WorkflowNode::CronTrigger(_) => rsx! {
-                FieldInput {
-                    input_cls: input_cls,
-                    label: "Schedule",
-                    value: get_str_val(&config, "schedule"),
-                    on_change: move |value: String| update_str.call(("schedule".to_owned(), value))
+            WorkflowNode::CronTrigger(_) => {
+                let schedule = get_str_val(&config, SCHEDULE_CONFIG_KEY);
+                let next_run = next_run_label(&schedule);
+                rsx! {
+                    FieldInput {
+                        input_cls: input_cls,
+                        label: "Schedule",
+                        value: schedule,
+                        on_change: move |value: String| update_str.call((SCHEDULE_CONFIG_KEY.to_owned(), value))
+                    }
+                    match next_run {
+                        Ok(label) => rsx! { p { class: "text-[11px] text-slate-500", "{label}" } },
+                        Err(message) => rsx! { p { class: "text-[11px] text-red-600", "{message}" } },
+                    }
                 }
-            },
+            }
             WorkflowNode::KafkaHandler(_) => rsx! {
                 FieldInput {
                     input_cls: input_cls,
@@ -53,6 +68,18 @@ pub(super) fn EntryConfig(
                     value: get_str_val(&config, "topic"),
                     on_change: move |value: String| update_str.call(("topic".to_owned(), value))
                 }
+                FieldInput {
+                    input_cls: input_cls,
+                    label: "Brokers",
+                    value: get_str_val(&config, "brokers"),
+                    on_change: move |value: String| update_str.call(("brokers".to_owned(), value))
+                }
+                FieldInput {
+                    input_cls: input_cls,
+                    label: "Consumer Group",
+                    value: get_str_val(&config, "group_id"),
+                    on_change: move |value: String| update_str.call(("group_id".to_owned(), value))
+                }
             },
             WorkflowNode::WorkflowSubmit(_) => rsx! {
                 FieldInput {
@@ -67,6 +94,18 @@ pub(super) fn EntryConfig(
     }
 }
 
+/// When a schedule next fires, for display under the expression.
+fn next_run_label(schedule: &str) -> Result<String, String> {
+    if schedule.trim().is_empty() {
+        return Ok("Not scheduled".to_string());
+    }
+    let schedule = CronSchedule::parse(schedule).map_err(|error| error.to_string())?;
+    Ok(schedule.next_after(chrono::Utc::now()).map_or_else(
+        || "Never fires".to_string(),
+        |next| format!("Next run: {} UTC", next.format("%Y-%m-%d %H:%M")),
+    ))
+}
+
 #[component]
 fn FieldInput(
     input_cls: &'static str,
@@ -5,6 +5,10 @@
 
 use super::get_str_val;
 use crate::graph::ExecutionState;
+use crate::twin_client::{
+    pin_twin_record, pinned_sample_source, sync_pinned_sample, PinSyncOutcome, PinnedSampleSource,
+    TwinInspectionClient, TwinRecord, PINNED_OUTPUT_KEY,
+};
 use crate::ui::icons::{icon_by_name, CopyIcon};
 use crate::ui::panel_types::{
     invocation_badge_style, ExecutionEventCategory, InvocationStatus, OutputOrigin, PayloadShape,
@@ -14,7 +18,6 @@ use serde_json::Value;
 use wasm_bindgen::JsCast;
 use web_sys::window;
 
-const PINNED_OUTPUT_KEY: &str = "pinnedOutputSample";
 const DEFAULT_PREVIEW_LINES: usize = 10;
 
 fn copy_to_clipboard(text: &str) -> bool {
@@ -130,6 +133,7 @@ pub(super) fn ExecutionTab(
     last_output: Option<Value>,
     input_payloads: Vec<Value>,
     on_pin_sample: EventHandler<Option<Value>>,
+    on_config_change: EventHandler<Value>,
 ) -> Element {
     let invocation_status = resolve_invocation_status(execution_state, &execution_data, &config);
     let journal_idx =
@@ -235,6 +239,8 @@ pub(super) fn ExecutionTab(
                         }
                     }
                 }
+
+                TwinSamplePicker { config: config.clone(), on_config_change }
             }
 
             div { class: "h-px bg-slate-800" }
@@ -262,6 +268,161 @@ pub(super) fn ExecutionTab(
     }
 }
 
+#[derive(Clone, PartialEq)]
+enum TwinRecordsState {
+    Idle,
+    Loading,
+    Loaded(Vec<TwinRecord>),
+    Error(String),
+}
+
+/// Pick a record from a twin collection and pin it as the output sample.
+#[component]
+fn TwinSamplePicker(config: Value, on_config_change: EventHandler<Value>) -> Element {
+    let source = pinned_sample_source(&config);
+    let mut endpoint = use_signal(|| {
+        source
+            .as_ref()
+            .map(|source| source.endpoint.clone())
+            .unwrap_or_default()
+    });
+    let mut collection = use_signal(|| {
+        source
+            .as_ref()
+            .map(|source| source.collection.clone())
+            .unwrap_or_default()
+    });
+    let mut records = use_signal(|| TwinRecordsState::Idle);
+    let mut sync_note = use_signal(|| None::<String>);
+
+    let can_load = !endpoint.read().trim().is_empty()
+        && !collection.read().trim().is_empty()
+        && *records.read() != TwinRecordsState::Loading;
+    let input_class = "h-7 flex-1 rounded-md border border-slate-700 bg-slate-950 px-2 font-mono text-[10px] text-slate-100 outline-none focus:border-indigo-500/50";
+    let button_class = "h-7 rounded-md border border-slate-600 bg-slate-800/60 px-2.5 text-[10px] font-medium text-slate-300 transition-colors hover:bg-slate-700/60 disabled:cursor-not-allowed disabled:opacity-50";
+
+    rsx! {
+        div { class: "flex flex-col gap-2 rounded-lg border border-slate-700 bg-slate-900/65 p-2",
+            div { class: "flex items-center justify-between",
+                span { class: "text-[10px] font-medium text-slate-300", "Pin from twin" }
+                if let Some(source) = source.as_ref() {
+                    span { class: "rounded bg-slate-800 px-1.5 py-0.5 font-mono text-[9px] text-slate-400",
+                        "{source.collection}#{source.record_id}"
+                    }
+                }
+            }
+            div { class: "flex gap-2",
+                input {
+                    class: "{input_class}",
+                    placeholder: "Twin endpoint",
+                    value: "{endpoint}",
+                    oninput: move |e| endpoint.set(e.value()),
+                }
+                input {
+                    class: "{input_class}",
+                    placeholder: "Collection",
+                    value: "{collection}",
+                    oninput: move |e| collection.set(e.value()),
+                }
+            }
+            div { class: "flex items-center gap-2",
+                button {
+                    class: "{button_class}",
+                    disabled: !can_load,
+                    onclick: move |_| {
+                        let client = TwinInspectionClient::new(endpoint.read().trim());
+                        let name = collection.read().trim().to_string();
+                        records.set(TwinRecordsState::Loading);
+                        spawn(async move {
+                            match client.list_records(&name).await {
+                                Ok(items) => records.set(TwinRecordsState::Loaded(items)),
+                                Err(error) => records.set(TwinRecordsState::Error(error.to_string())),
+                            }
+                        });
+                    },
+                    "Load records"
+                }
+                if let Some(source) = source.clone() {
+                    button {
+                        class: "{button_class}",
+                        onclick: {
+                            let config = config.clone();
+                            move |_| {
+                                let client = TwinInspectionClient::new(&source.endpoint);
+                                let name = source.collection.clone();
+                                let mut next = config.clone();
+                                spawn(async move {
+                                    match client.list_records(&name).await {
+                                        Ok(items) => {
+                                            let outcome = sync_pinned_sample(&mut next, &items);
+                                            if outcome == PinSyncOutcome::Updated {
+                                                on_config_change.call(next);
+                                            }
+                                            sync_note.set(Some(sync_outcome_label(outcome).to_owned()));
+                                        }
+                                        Err(error) => sync_note.set(Some(error.to_string())),
+                                    }
+                                });
+                            }
+                        },
+                        "Sync from twin"
+                    }
+                }
+            }
+            if let Some(note) = sync_note.read().as_ref() {
+                p { class: "text-[10px] text-slate-400", "{note}" }
+            }
+            match records.read().clone() {
+                TwinRecordsState::Idle => rsx! {},
+                TwinRecordsState::Loading => rsx! {
+                    p { class: "text-[10px] text-slate-500", "Loading records…" }
+                },
+                TwinRecordsState::Error(message) => rsx! {
+                    p { class: "text-[10px] text-red-400", "{message}" }
+                },
+                TwinRecordsState::Loaded(items) => rsx! {
+                    if items.is_empty() {
+                        p { class: "text-[10px] text-slate-500", "Collection is empty." }
+                    }
+                    div { class: "flex max-h-40 flex-col gap-1 overflow-y-auto",
+                        for record in items {
+                            {
+                                let mut next = config.clone();
+                                let source = PinnedSampleSource {
+                                    endpoint: endpoint.read().trim().trim_end_matches('/').to_string(),
+                                    collection: collection.read().trim().to_string(),
+                                    record_id: record.id.clone(),
+                                };
+                                rsx! {
+                                    button {
+                                        key: "{record.id}",
+                                        class: "flex items-center justify-between rounded-md border border-slate-700 bg-slate-950 px-2 py-1 text-left font-mono text-[10px] text-slate-300 hover:border-indigo-500/50",
+                                        onclick: move |_| {
+                                            pin_twin_record(&mut next, source.clone(), &record);
+                                            on_config_change.call(next.clone());
+                                        },
+                                        span { "{record.id}" }
+                                        span { class: "text-[9px] text-indigo-300", "Pin" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+const fn sync_outcome_label(outcome: PinSyncOutcome) -> &'static str {
+    match outcome {
+        PinSyncOutcome::Unchanged => "Pinned sample already matches the twin.",
+        PinSyncOutcome::Updated => "Pinned sample refreshed from the twin.",
+        PinSyncOutcome::RecordMissing => "Record no longer exists in the twin collection.",
+        PinSyncOutcome::NotLinked => "Pinned sample is not linked to a twin.",
+    }
+}
+
 #[component]
 fn StatusBadge(status: InvocationStatus) -> Element {
     let style = invocation_badge_style(status);
@@ -4,6 +4,7 @@
 #![warn(clippy::pedantic)]
 
 use crate::graph::{Node, NodeCategory};
+use crate::twin_client::{PINNED_OUTPUT_KEY, PINNED_SOURCE_KEY};
 use dioxus::prelude::*;
 use serde_json::Value;
 
@@ -20,8 +21,11 @@ use execution::ExecutionTab;
 const INPUT_CLASS: &str =
     "h-8 w-full rounded-md border border-slate-700 bg-slate-950 px-3 font-mono text-[12px] text-slate-100 outline-none transition-colors focus:border-indigo-500/50 focus:ring-1 focus:ring-indigo-500/30";
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Tab {
+/// Tab shown by [`NodeConfigEditor`]. The owner keeps it so other panels
+/// can jump straight to a node's execution details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EditorTab {
+    #[default]
     Config,
     Execution,
 }
@@ -31,19 +35,19 @@ pub fn NodeConfigEditor(
     node: Node,
     input_payloads: Vec<Value>,
     on_change: EventHandler<Value>,
+    mut tab: Signal<EditorTab>,
 ) -> Element {
-    let mut tab = use_signal(|| Tab::Config);
     let config = node.config.clone();
 
     let tab_val = *tab.read();
 
-    let config_tab_class = if tab_val == Tab::Config {
+    let config_tab_class = if tab_val == EditorTab::Config {
         "flex-1 border-b-2 border-indigo-500 py-2 text-[11px] font-medium capitalize text-slate-100 transition-colors"
     } else {
         "flex-1 border-b-2 border-transparent py-2 text-[11px] font-medium capitalize text-slate-500 transition-colors hover:text-slate-300"
     };
 
-    let exec_tab_class = if tab_val == Tab::Execution {
+    let exec_tab_class = if tab_val == EditorTab::Execution {
         "flex-1 border-b-2 border-indigo-500 py-2 text-[11px] font-medium capitalize text-slate-100 transition-colors"
     } else {
         "flex-1 border-b-2 border-transparent py-2 text-[11px] font-medium capitalize text-slate-500 transition-colors hover:text-slate-300"
@@ -54,22 +58,22 @@ pub fn NodeConfigEditor(
             div { class: "flex border-b border-slate-800",
                 button {
                     class: "{config_tab_class}",
-                    onclick: move |_| tab.set(Tab::Config),
+                    onclick: move |_| tab.set(EditorTab::Config),
                     "Configuration"
                 }
                 button {
                     class: "{exec_tab_class}",
-                    onclick: move |_| tab.set(Tab::Execution),
+                    onclick: move |_| tab.set(EditorTab::Execution),
                     "Execution"
                 }
             }
 
             div { class: "pt-4",
                 match tab_val {
-                    Tab::Config => rsx! {
+                    EditorTab::Config => rsx! {
                         ConfigTab { node: node.clone(), on_change: on_change }
                     },
-                    Tab::Execution => rsx! {
+                    EditorTab::Execution => rsx! {
                         ExecutionTab {
                             config: config.clone(),
                             execution_state: node.execution_state,
@@ -81,18 +85,20 @@ pub fn NodeConfigEditor(
                                 move |payload: Option<Value>| {
                                     let mut new_config = config.clone();
                                     if let Some(obj) = new_config.as_object_mut() {
+                                        obj.remove(PINNED_SOURCE_KEY);
                                         match payload {
                                             Some(value) => {
-                                                obj.insert("pinnedOutputSample".to_owned(), value);
+                                                obj.insert(PINNED_OUTPUT_KEY.to_owned(), value);
                                             }
                                             None => {
-                                                obj.remove("pinnedOutputSample");
+                                                obj.remove(PINNED_OUTPUT_KEY);
                                             }
                                         }
                                         on_change.call(new_config);
                                     }
                                 }
-                            })
+                            }),
+                            on_config_change: on_change,
                         }
                     },
                 }
@@ -22,6 +22,10 @@ pub const NODE_CENTER_X_OFFSET: f32 = NODE_WIDTH / 2.0;
 /// centre-line.  Equal to `NODE_HEIGHT / 2.0`.
 pub const NODE_HANDLE_Y_OFFSET: f32 = NODE_HEIGHT / 2.0;
 
+/// Size of a frame created from the context menu with nothing selected.
+pub const FRAME_DEFAULT_WIDTH: f32 = 480.0;
+pub const FRAME_DEFAULT_HEIGHT: f32 = 280.0;
+
 /// Corner radius for smooth-step edge paths (pixels).
 pub const EDGE_CORNER_RADIUS: f32 = 8.0;
 
@@ -68,6 +72,13 @@ pub const EDGE_AUTO_PAN_ZONE: f32 = 56.0;
 /// Maximum auto-pan speed (pixels per mouse-move event) inside the edge zone.
 pub const EDGE_AUTO_PAN_MAX: f32 = 18.0;
 
+/// Screen-space distance within which a node dropped from the sidebar is
+/// spliced into an edge or attached to an unconnected output handle.
+pub const AUTO_CONNECT_RADIUS_PX: f32 = 28.0;
+
+/// Horizontal gap between a node and one auto-connected to its output.
+pub const AUTO_CONNECT_GAP: f32 = 80.0;
+
 /// Fallback canvas dimensions when `app_io::canvas_rect_size()` returns
 /// `None` (non-WASM or element not yet mounted).
 pub const FALLBACK_CANVAS_WIDTH: f32 = 960.0;
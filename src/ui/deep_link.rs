@@ -0,0 +1,161 @@
+//! Editor deep links: `?workflow=<url>` opens the editor on the workflow JSON
+//! served at `<url>` instead of the one kept in local storage, and
+//! `?collab=<ws-url>&name=<display name>` joins a collaboration room.
+
+pub const WORKFLOW_PARAM: &str = "workflow";
+pub const COLLAB_PARAM: &str = "collab";
+pub const NAME_PARAM: &str = "name";
+
+/// Link that opens the editor at `editor_url` on the workflow at
+/// `workflow_url`.
+#[must_use]
+pub fn editor_link(editor_url: &str, workflow_url: &str) -> String {
+    format!(
+        "{}/?{WORKFLOW_PARAM}={}",
+        editor_url.trim_end_matches('/'),
+        encode(workflow_url)
+    )
+}
+
+/// The workflow URL a page was opened with, if any.
+#[must_use]
+pub fn linked_workflow_url(page_url: &str) -> Option<String> {
+    query_param(page_url, WORKFLOW_PARAM)
+}
+
+/// The collaboration room (a `ws://` or `wss://` relay URL) and display name
+/// a page was opened with, if any.
+#[must_use]
+pub fn linked_collab_room(page_url: &str) -> Option<(String, String)> {
+    let room = query_param(page_url, COLLAB_PARAM)
+        .filter(|url| url.starts_with("ws://") || url.starts_with("wss://"))?;
+    let name = query_param(page_url, NAME_PARAM).unwrap_or_else(|| "Guest".to_string());
+    Some((room, name))
+}
+
+fn query_param(page_url: &str, param: &str) -> Option<String> {
+    let query = page_url.split_once('?')?.1;
+    let query = query.split('#').next().unwrap_or(query);
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == param)
+        .and_then(|(_, value)| decode(value))
+        .filter(|value| !value.is_empty())
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+/// Percent-decode a query value or path segment.
+#[must_use]
+pub fn decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'%' => {
+                let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                rest = &tail[2..];
+            }
+            b'+' => {
+                bytes.push(b' ');
+                rest = tail;
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Load the deep-linked workflow, if the page was opened with one.
+#[cfg(target_arch = "wasm32")]
+pub async fn fetch_linked_workflow() -> Option<Result<crate::graph::Workflow, String>> {
+    let page_url = web_sys::window()?.document()?.url().ok()?;
+    let workflow_url = linked_workflow_url(&page_url)?;
+    let result = async {
+        reqwest::get(&workflow_url)
+            .await
+            .map_err(|error| error.to_string())?
+            .error_for_status()
+            .map_err(|error| error.to_string())?
+            .json::<crate::graph::Workflow>()
+            .await
+            .map_err(|error| error.to_string())
+    }
+    .await;
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn given_workflow_url_when_building_link_then_page_url_round_trips() {
+        let link = editor_link(
+            "http://localhost:8080/",
+            "http://127.0.0.1:8090/api/workflows/signup flow?raw=1",
+        );
+
+        assert_eq!(
+            link,
+            "http://localhost:8080/?workflow=http%3A%2F%2F127.0.0.1%3A8090%2Fapi%2Fworkflows%2Fsignup%20flow%3Fraw%3D1"
+        );
+        assert_eq!(
+            linked_workflow_url(&link).as_deref(),
+            Some("http://127.0.0.1:8090/api/workflows/signup flow?raw=1")
+        );
+    }
+
+    #[test]
+    fn given_page_without_workflow_param_when_reading_link_then_none_is_returned() {
+        assert_eq!(linked_workflow_url("http://localhost:8080/"), None);
+        assert_eq!(linked_workflow_url("http://localhost:8080/?other=1"), None);
+        assert_eq!(
+            linked_workflow_url("http://localhost:8080/?workflow="),
+            None
+        );
+        assert_eq!(
+            linked_workflow_url("http://localhost:8080/?workflow=%zz"),
+            None
+        );
+    }
+
+    #[test]
+    fn given_collab_params_when_reading_room_then_only_websocket_urls_join() {
+        assert_eq!(
+            linked_collab_room(
+                "http://localhost:8080/?collab=ws%3A%2F%2F127.0.0.1%3A8093%2Fsignup&name=Ada+L"
+            ),
+            Some((
+                "ws://127.0.0.1:8093/signup".to_string(),
+                "Ada L".to_string()
+            ))
+        );
+        assert_eq!(
+            linked_collab_room("http://localhost:8080/?collab=wss://relay.example/signup")
+                .map(|(_, name)| name)
+                .as_deref(),
+            Some("Guest")
+        );
+        assert_eq!(
+            linked_collab_room("http://localhost:8080/?collab=http://relay.example/"),
+            None
+        );
+    }
+}
@@ -1,8 +1,10 @@
 use crate::graph::workflow_node::WorkflowNode;
 use crate::graph::{Connection, Node, NodeId};
 use dioxus::prelude::*;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::rc::Rc;
 
 use crate::ui::constants::EDGE_CORNER_RADIUS;
 use crate::ui::editor_interactions::{NODE_HEIGHT, NODE_WIDTH};
@@ -16,7 +18,7 @@ pub struct Position {
 
 const BEND_CLAMP: f32 = 200.0;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 struct EdgeAnchor {
     from: Position,
     to: Position,
@@ -81,27 +83,12 @@ fn create_smooth_step_path(from: Position, to: Position, bend_y: f32) -> (String
     (path, midpoint)
 }
 
-fn resolve_edge_anchors(edges: &[Connection], nodes: &[Node]) -> HashMap<String, EdgeAnchor> {
-    let node_by_id: HashMap<_, _> = nodes.iter().map(|node| (node.id, node.clone())).collect();
-
-    edges
-        .iter()
-        .filter_map(|edge| {
-            let source = node_by_id.get(&edge.source)?;
-            let target = node_by_id.get(&edge.target)?;
-            let from = get_source_point(source);
-            let to = get_target_point(target);
-            Some((edge.id.to_string(), EdgeAnchor { from, to }))
-        })
-        .collect()
-}
-
 fn resolve_edge_anchors_with_parallel(
     edges: &[Connection],
     nodes: &[Node],
     parallel_groups: &[ParallelGroup],
 ) -> HashMap<String, EdgeAnchor> {
-    let node_by_id: HashMap<_, _> = nodes.iter().map(|node| (node.id, node.clone())).collect();
+    let node_by_id: HashMap<_, _> = nodes.iter().map(|node| (node.id, node)).collect();
 
     edges
         .iter()
@@ -119,7 +106,7 @@ fn resolve_edge_anchors_with_parallel(
                 let branch_nodes: Vec<Node> = g
                     .branch_node_ids
                     .iter()
-                    .filter_map(|id| node_by_id.get(id).cloned())
+                    .filter_map(|id| node_by_id.get(id).map(|node| (*node).clone()))
                     .collect();
                 let offset = calculate_parallel_offset(&edge.target, &branch_nodes, NODE_HEIGHT);
                 Position {
@@ -139,6 +126,97 @@ fn resolve_edge_anchors_with_parallel(
         .collect()
 }
 
+/// The SVG path of every drawable edge, keyed by connection id, as
+/// [`FlowEdges`] draws them before any bend is dragged.
+#[must_use]
+pub fn edge_paths(edges: &[Connection], nodes: &[Node]) -> HashMap<String, String> {
+    let groups = find_parallel_branches(nodes, edges);
+    resolve_edge_anchors_with_parallel(edges, nodes, &groups)
+        .into_iter()
+        .map(|(id, anchor)| (id, create_smooth_step_path(anchor.from, anchor.to, 0.0).0))
+        .collect()
+}
+
+/// A drawn edge: its SVG path and where its bend handle sits.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgePath {
+    pub d: Rc<str>,
+    pub midpoint: Position,
+}
+
+/// What a cached path was built from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct EdgePathKey {
+    anchor: EdgeAnchor,
+    bend: f32,
+}
+
+/// Edge paths by connection id, kept across renders.
+///
+/// [`EdgePathCache::update`] rebuilds only the paths whose endpoints or bend
+/// changed, so dragging a node redraws just its own edges. Clones share
+/// structure, so handing the cache out of a memo is cheap.
+#[derive(Debug, Clone, Default)]
+pub struct EdgePathCache {
+    paths: im::HashMap<String, (EdgePathKey, EdgePath)>,
+}
+
+impl EdgePathCache {
+    /// Brings the cache in line with `anchors` and `bends`, returning how
+    /// many paths were rebuilt. Edges without an anchor are dropped.
+    fn update(
+        &mut self,
+        anchors: &HashMap<String, EdgeAnchor>,
+        bends: &HashMap<String, f32>,
+    ) -> usize {
+        let mut rebuilt = 0;
+        for (id, anchor) in anchors {
+            let key = EdgePathKey {
+                anchor: *anchor,
+                bend: bends.get(id).copied().unwrap_or(0.0),
+            };
+            if self.paths.get(id).is_some_and(|(cached, _)| *cached == key) {
+                continue;
+            }
+            let (d, midpoint) = create_smooth_step_path(key.anchor.from, key.anchor.to, key.bend);
+            let path = EdgePath {
+                d: d.into(),
+                midpoint,
+            };
+            self.paths.insert(id.clone(), (key, path));
+            rebuilt += 1;
+        }
+        // Every anchored edge is now cached, so extra entries are stale.
+        if self.paths.len() > anchors.len() {
+            self.paths.retain(|id, _| anchors.contains_key(id));
+        }
+        rebuilt
+    }
+
+    #[must_use]
+    pub fn get(&self, edge_id: &str) -> Option<&EdgePath> {
+        self.paths.get(edge_id).map(|(_, path)| path)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+/// Caches sharing structure are equal without comparing paths, so an
+/// unchanged cache is cheap to compare against its previous value.
+impl PartialEq for EdgePathCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.paths.ptr_eq(&other.paths) || self.paths == other.paths
+    }
+}
+
 #[allow(clippy::cast_precision_loss)]
 fn calculate_parallel_offset(target_id: &NodeId, targets: &[Node], node_height: f32) -> f32 {
     let mut sorted: Vec<_> = targets.iter().enumerate().collect();
@@ -259,9 +337,12 @@ struct Rect {
 mod tests {
     use super::{
         calculate_parallel_offset, find_parallel_branches, normalize_bend_delta,
-        resolve_edge_anchors_with_parallel, AggregateStatus, BoundingBox, ParallelGroup, Rect,
+        resolve_edge_anchors_with_parallel, AggregateStatus, BoundingBox, EdgePathCache,
+        ParallelGroup, Rect,
     };
+    use crate::graph::testing::synthetic::large_workflow;
     use crate::graph::{Connection, Node, NodeId, PortName, WorkflowNode};
+    use std::collections::HashMap;
     use uuid::Uuid;
 
     // Constants for test data builders
@@ -1083,6 +1164,65 @@ mod tests {
         // Both should have the same target y since there's only one target in each group
         assert_eq!(anchor_a.to.y, anchor_b.to.y);
     }
+
+    // ==================== EdgePathCache Tests ====================
+
+    #[test]
+    fn given_one_moved_node_when_updating_path_cache_then_only_its_edges_are_rebuilt() {
+        let mut workflow = large_workflow(60);
+        let anchors = |workflow: &crate::graph::Workflow| {
+            resolve_edge_anchors_with_parallel(&workflow.connections, &workflow.nodes, &[])
+        };
+        let mut cache = EdgePathCache::default();
+        assert_eq!(
+            cache.update(&anchors(&workflow), &HashMap::new()),
+            workflow.connections.len()
+        );
+        let before = cache.clone();
+
+        assert_eq!(cache.update(&anchors(&workflow), &HashMap::new()), 0);
+        assert_eq!(cache, before);
+
+        let moved = workflow.nodes[30].id;
+        workflow.nodes[30].y += 50.0;
+        let touching = workflow
+            .connections
+            .iter()
+            .filter(|edge| edge.source == moved || edge.target == moved)
+            .count();
+        assert_eq!(cache.update(&anchors(&workflow), &HashMap::new()), touching);
+        assert_eq!(
+            cache.get(&workflow.connections[0].id.to_string()),
+            before.get(&workflow.connections[0].id.to_string())
+        );
+    }
+
+    #[test]
+    fn given_bend_and_removed_edge_when_updating_path_cache_then_bent_edge_is_rebuilt_and_removed_dropped(
+    ) {
+        let mut workflow = large_workflow(10);
+        let mut cache = EdgePathCache::default();
+        let anchors =
+            resolve_edge_anchors_with_parallel(&workflow.connections, &workflow.nodes, &[]);
+        cache.update(&anchors, &HashMap::new());
+
+        let bent = workflow.connections[1].id.to_string();
+        let straight = cache.get(&bent).cloned().unwrap();
+        let bends = HashMap::from([(bent.clone(), 40.0)]);
+        assert_eq!(cache.update(&anchors, &bends), 1);
+        assert_ne!(cache.get(&bent), Some(&straight));
+        assert_eq!(
+            cache.get(&bent).unwrap().midpoint.y,
+            straight.midpoint.y + 40.0
+        );
+
+        let removed = workflow.connections.remove(0).id.to_string();
+        let anchors =
+            resolve_edge_anchors_with_parallel(&workflow.connections, &workflow.nodes, &[]);
+        assert_eq!(cache.update(&anchors, &bends), 0);
+        assert!(cache.get(&removed).is_none());
+        assert_eq!(cache.len(), workflow.connections.len());
+    }
 }
 
 #[component]
@@ -1092,23 +1232,20 @@ pub fn FlowEdges(
     temp_edge: ReadSignal<Option<(Position, Position)>>,
     running_node_ids: ReadSignal<Vec<NodeId>>,
     zoom: ReadSignal<f32>,
+    critical_edges: ReadSignal<Vec<(NodeId, NodeId)>>,
 ) -> Element {
     let mut hovered_edge = use_signal(|| None::<String>);
     let mut bend_offsets = use_signal(HashMap::<String, f32>::new);
     let mut drag_state = use_signal(|| None::<DragState>);
 
-    let _edge_anchors = use_memo(move || {
-        let node_list = nodes.read();
-        let edge_list = edges.read();
-        resolve_edge_anchors(&edge_list, &node_list)
-    });
-
-    let node_by_id = use_memo(move || {
+    let source_statuses = use_memo(move || {
         nodes
             .read()
             .iter()
-            .cloned()
-            .map(|node| (node.id, node))
+            .filter_map(|node| {
+                let status = node.config.get("status")?.as_str()?;
+                Some((node.id, status.to_string()))
+            })
             .collect::<HashMap<_, _>>()
     });
 
@@ -1118,16 +1255,21 @@ pub fn FlowEdges(
         find_parallel_branches(&node_list, &edge_list)
     });
 
-    let temp_path = use_memo(move || {
-        (*temp_edge.read()).map(|(from, to)| create_smooth_step_path(from, to, 0.0).0)
-    });
-
     let edge_anchors_with_parallel = use_memo(move || {
         let node_list = nodes.read();
         let edge_list = edges.read();
         resolve_edge_anchors_with_parallel(&edge_list, &node_list, &parallel_groups.read())
     });
 
+    // The cache outlives each memo run so only moved or re-bent edges are
+    // rebuilt; the memo hands out cheap structural clones of it.
+    let path_cache = use_hook(|| Rc::new(RefCell::new(EdgePathCache::default())));
+    let edge_paths = use_memo(move || {
+        let mut cache = path_cache.borrow_mut();
+        cache.update(&edge_anchors_with_parallel.read(), &bend_offsets.read());
+        cache.clone()
+    });
+
     let svg_pointer_class = if drag_state.read().is_some() {
         "pointer-events-auto"
     } else {
@@ -1257,125 +1399,72 @@ pub fn FlowEdges(
             for edge in edges.read().iter() {
                 {
                     let edge_id = edge.id.to_string();
-                    let anchor = edge_anchors_with_parallel.read().get(&edge_id).copied();
+                    let path = edge_paths.read().get(&edge_id).cloned();
 
-                    if let Some(anchor) = anchor {
-                        let bend = bend_offsets
-                            .read()
-                            .get(&edge_id)
-                            .copied()
-                            .map_or(0.0, |value| value);
-                        let (path, midpoint) = create_smooth_step_path(anchor.from, anchor.to, bend);
+                    if let Some(path) = path {
                         let dragging_this = drag_state
                             .read()
                             .as_ref()
                             .is_some_and(|state| state.edge_id == edge_id);
-                        let hovered_this = hovered_edge
-                            .read()
-                            .as_ref()
-                            .is_some_and(|id| *id == edge_id);
-                        let handle_opacity = if hovered_this || dragging_this { "1" } else { "0" };
-                        let source_status = node_by_id
+                        let highlighted = dragging_this
+                            || hovered_edge
+                                .read()
+                                .as_ref()
+                                .is_some_and(|id| *id == edge_id);
+                        let source_running = source_statuses
                             .read()
                             .get(&edge.source)
-                            .and_then(|node| node.config.get("status"))
-                            .and_then(serde_json::Value::as_str)
-                            .map_or_else(|| "pending".to_string(), std::string::ToString::to_string);
+                            .is_some_and(|status| status == "running");
+                        let stroke_color = if critical_edges.read().contains(&(edge.source, edge.target)) {
+                            EdgeStroke::Critical
+                        } else {
+                            EdgeStroke::from_status(source_statuses.read().get(&edge.source).map(String::as_str))
+                        };
                         let target_is_running = running_node_ids
                             .read()
                             .contains(&edge.target);
-                        let stroke_color = match source_status {
-                            ref status if status == "running" => "url(#edge-running-gradient)",
-                            ref status if status == "completed" => "rgba(16, 185, 129, 0.85)",
-                            ref status if status == "failed" => "rgba(244, 63, 94, 0.85)",
-                            _ => "rgba(148, 163, 184, 0.9)",
-                        };
-                        let marker = if source_status == "running" || target_is_running {
-                            "url(#arrowhead-active)"
-                        } else {
-                            "url(#arrowhead)"
-                        };
-                        let dash = if source_status == "running" || target_is_running { "6 4" } else { "0" };
-                        let animation_class = if target_is_running { "edge-animated" } else { "" };
 
                         rsx! {
-                            g { key: "{edge_id}",
-                                path {
-                                    d: "{path}",
-                                    fill: "none",
-                                    stroke: "transparent",
-                                    stroke_width: "16",
-                                    pointer_events: "stroke",
-                                    class: "pointer-events-auto",
-                                    onmouseenter: {
-                                        let edge_id = edge_id.clone();
-                                        move |_| hovered_edge.set(Some(edge_id.clone()))
-                                    },
-                                    onmouseleave: {
-                                        let edge_id = edge_id.clone();
-                                        move |_| {
-                                            let is_dragging = drag_state
-                                                .read()
-                                                .as_ref()
-                                                .is_some_and(|state| state.edge_id == edge_id);
-                                            if !is_dragging {
-                                                hovered_edge.set(None);
-                                            }
-                                        }
-                                    }
-                                }
-                                path {
-                                    d: "{path}",
-                                    fill: "none",
-                                    stroke: "rgba(14,116,144,0.18)",
-                                    stroke_width: "6",
-                                    opacity: if target_is_running { "1" } else { "0" },
-                                    class: "transition-opacity duration-150",
-                                }
-                                path {
-                                    d: "{path}",
-                                    fill: "none",
-                                    stroke: "{stroke_color}",
-                                    stroke_width: "2",
-                                    marker_end: "{marker}",
-                                    stroke_dasharray: "{dash}",
-                                    class: "transition-all duration-150 {animation_class}",
-                                    style: if target_is_running { Some("animation: flow 0.5s linear infinite") } else { None }
-                                }
-                                circle {
-                                    cx: "{midpoint.x}",
-                                    cy: "{midpoint.y}",
-                                    r: "5",
-                                    fill: "rgba(99, 102, 241, 0.95)",
-                                    stroke: "rgba(226, 232, 240, 0.95)",
-                                    stroke_width: "1.5",
-                                    opacity: "{handle_opacity}",
-                                    class: "pointer-events-auto cursor-ns-resize transition-opacity duration-100",
-                                    onmousedown: {
-                                        let edge_id = edge_id.clone();
-                                        move |evt| {
-                                            evt.stop_propagation();
-                                            let coordinates = evt.page_coordinates();
-                                            #[allow(clippy::cast_possible_truncation)]
-                                            let page_y = coordinates.y as f32;
-                                            if !page_y.is_finite() {
-                                                return;
-                                            }
-                                            let current_bend = bend_offsets
-                                                .read()
-                                                .get(&edge_id)
-                                                .copied()
-                                                .map_or(0.0, |value| value);
-                                            let next_bend = sanitize_bend_input_edge(current_bend, current_bend);
-                                            drag_state.set(Some(DragState {
-                                                edge_id: edge_id.clone(),
-                                                start_page_y: page_y,
-                                                start_bend: next_bend,
-                                            }));
+                            FlowEdge {
+                                key: "{edge_id}",
+                                path: path,
+                                stroke: stroke_color,
+                                active: source_running || target_is_running,
+                                target_is_running: target_is_running,
+                                highlighted: highlighted,
+                                on_hover: {
+                                    let edge_id = edge_id.clone();
+                                    move |entered: bool| {
+                                        if entered {
                                             hovered_edge.set(Some(edge_id.clone()));
+                                            return;
+                                        }
+                                        let is_dragging = drag_state
+                                            .read()
+                                            .as_ref()
+                                            .is_some_and(|state| state.edge_id == edge_id);
+                                        if !is_dragging {
+                                            hovered_edge.set(None);
                                         }
                                     }
-                                }
+                                },
+                                on_bend_start: {
+                                    let edge_id = edge_id.clone();
+                                    move |page_y: f32| {
+                                        let current_bend = bend_offsets
+                                            .read()
+                                            .get(&edge_id)
+                                            .copied()
+                                            .map_or(0.0, |value| value);
+                                        let next_bend = sanitize_bend_input_edge(current_bend, current_bend);
+                                        drag_state.set(Some(DragState {
+                                            edge_id: edge_id.clone(),
+                                            start_page_y: page_y,
+                                            start_bend: next_bend,
+                                        }));
+                                        hovered_edge.set(Some(edge_id.clone()));
+                                    }
+                                },
                             }
                         }
                     } else {
@@ -1384,15 +1473,139 @@ pub fn FlowEdges(
                 }
             }
 
-            if let Some(path) = temp_path.read().as_ref() {
-                path {
-                    d: "{path}",
-                    fill: "none",
-                    stroke: "rgba(99, 102, 241, 0.6)",
-                    stroke_width: "2",
-                    stroke_dasharray: "6 4"
+            TempEdgePath { temp_edge: temp_edge }
+        }
+    }
+}
+
+/// How an edge is stroked, from its source node's run status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EdgeStroke {
+    Critical,
+    Running,
+    Completed,
+    Failed,
+    Idle,
+}
+
+impl EdgeStroke {
+    fn from_status(status: Option<&str>) -> Self {
+        match status {
+            Some("running") => Self::Running,
+            Some("completed") => Self::Completed,
+            Some("failed") => Self::Failed,
+            _ => Self::Idle,
+        }
+    }
+
+    const fn color(self) -> &'static str {
+        match self {
+            Self::Critical => "rgba(234, 88, 12, 0.95)",
+            Self::Running => "url(#edge-running-gradient)",
+            Self::Completed => "rgba(16, 185, 129, 0.85)",
+            Self::Failed => "rgba(244, 63, 94, 0.85)",
+            Self::Idle => "rgba(148, 163, 184, 0.9)",
+        }
+    }
+}
+
+/// One edge. Its props only change when its path or status does, so other
+/// edges are not redrawn while a node is dragged.
+#[component]
+fn FlowEdge(
+    path: EdgePath,
+    stroke: EdgeStroke,
+    /// The source or target is running.
+    active: bool,
+    target_is_running: bool,
+    highlighted: bool,
+    on_hover: EventHandler<bool>,
+    on_bend_start: EventHandler<f32>,
+) -> Element {
+    let d = path.d.clone();
+    let midpoint = path.midpoint;
+    let is_critical = stroke == EdgeStroke::Critical;
+    let stroke_color = stroke.color();
+    let marker = if active {
+        "url(#arrowhead-active)"
+    } else {
+        "url(#arrowhead)"
+    };
+    let dash = if active { "6 4" } else { "0" };
+    let animation_class = if target_is_running {
+        "edge-animated"
+    } else {
+        ""
+    };
+    let handle_opacity = if highlighted { "1" } else { "0" };
+
+    rsx! {
+        g {
+            path {
+                d: "{d}",
+                fill: "none",
+                stroke: "transparent",
+                stroke_width: "16",
+                pointer_events: "stroke",
+                class: "pointer-events-auto",
+                onmouseenter: move |_| on_hover.call(true),
+                onmouseleave: move |_| on_hover.call(false),
+            }
+            path {
+                d: "{d}",
+                fill: "none",
+                stroke: "rgba(14,116,144,0.18)",
+                stroke_width: "6",
+                opacity: if target_is_running { "1" } else { "0" },
+                class: "transition-opacity duration-150",
+            }
+            path {
+                d: "{d}",
+                fill: "none",
+                stroke: "{stroke_color}",
+                stroke_width: if is_critical { "3" } else { "2" },
+                marker_end: "{marker}",
+                stroke_dasharray: "{dash}",
+                class: "transition-all duration-150 {animation_class}",
+                style: if target_is_running { Some("animation: flow 0.5s linear infinite") } else { None }
+            }
+            circle {
+                cx: "{midpoint.x}",
+                cy: "{midpoint.y}",
+                r: "5",
+                fill: "rgba(99, 102, 241, 0.95)",
+                stroke: "rgba(226, 232, 240, 0.95)",
+                stroke_width: "1.5",
+                opacity: "{handle_opacity}",
+                class: "pointer-events-auto cursor-ns-resize transition-opacity duration-100",
+                onmousedown: move |evt| {
+                    evt.stop_propagation();
+                    let coordinates = evt.page_coordinates();
+                    #[allow(clippy::cast_possible_truncation)]
+                    let page_y = coordinates.y as f32;
+                    if page_y.is_finite() {
+                        on_bend_start.call(page_y);
+                    }
                 }
             }
         }
     }
 }
+
+/// The edge being drawn from a handle. Kept in its own component so moving
+/// the pointer redraws only this path, not every edge.
+#[component]
+fn TempEdgePath(temp_edge: ReadSignal<Option<(Position, Position)>>) -> Element {
+    let path = (*temp_edge.read()).map(|(from, to)| create_smooth_step_path(from, to, 0.0).0);
+    rsx! {
+        if let Some(path) = path {
+            path {
+                d: "{path}",
+                fill: "none",
+                stroke: "rgba(99, 102, 241, 0.6)",
+                stroke_width: "2",
+                stroke_dasharray: "6 4"
+            }
+        }
+    }
+}
@@ -32,6 +32,19 @@ pub fn node_intersects_rect(node_x: f32, node_y: f32, rect: SelectionRect) -> bo
     !(node_right < rect.0 || node_left > rect.2 || node_bottom < rect.1 || node_top > rect.3)
 }
 
+/// Nodes a marquee over `rect` selects, in canvas coordinates.
+#[must_use]
+pub fn nodes_in_rect(
+    nodes: &[crate::graph::Node],
+    rect: SelectionRect,
+) -> Vec<crate::graph::NodeId> {
+    nodes
+        .iter()
+        .filter(|node| node_intersects_rect(node.x, node.y, rect))
+        .map(|node| node.id)
+        .collect()
+}
+
 #[must_use]
 pub fn snap_handle(
     nodes: &[crate::graph::Node],
@@ -116,6 +129,101 @@ pub fn snap_handle(
     best.map(|(node_id, handle_kind, position, _)| (node_id, handle_kind, position))
 }
 
+/// What a node dropped from the sidebar should be wired into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoConnect {
+    /// Splice the node into this connection.
+    Splice(uuid::Uuid),
+    /// Connect this node's unconnected output to the dropped node.
+    FromOutput(crate::graph::NodeId),
+}
+
+fn distance_to_segment(point: (f32, f32), from: (f32, f32), to: (f32, f32)) -> f32 {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length_sq = dx.mul_add(dx, dy * dy);
+    let t = if length_sq <= f32::EPSILON {
+        0.0
+    } else {
+        (((point.0 - from.0) * dx + (point.1 - from.1) * dy) / length_sq).clamp(0.0, 1.0)
+    };
+    let (nx, ny) = (t.mul_add(dx, from.0), t.mul_add(dy, from.1));
+    (point.0 - nx).hypot(point.1 - ny)
+}
+
+/// Distance from a canvas point to the smooth-step route of an edge: down
+/// from the source handle to the mid line, across, then down to the target.
+fn distance_to_edge(point: (f32, f32), from: (f32, f32), to: (f32, f32)) -> f32 {
+    let mid_y = f32::midpoint(from.1, to.1);
+    let corners = [from, (from.0, mid_y), (to.0, mid_y), to];
+    corners
+        .windows(2)
+        .map(|segment| distance_to_segment(point, segment[0], segment[1]))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// The connection whose rendered route passes closest to `point`, within
+/// `radius` canvas units.
+#[must_use]
+pub fn nearest_connection(
+    nodes: &[crate::graph::Node],
+    connections: &[crate::graph::Connection],
+    point: (f32, f32),
+    radius: f32,
+) -> Option<uuid::Uuid> {
+    let node_at = |id| nodes.iter().find(|node| node.id == id);
+    connections
+        .iter()
+        .filter_map(|connection| {
+            let source = node_at(connection.source)?;
+            let target = node_at(connection.target)?;
+            let distance = distance_to_edge(
+                point,
+                (source.x + NODE_WIDTH, source.y + NODE_HANDLE_Y_OFFSET),
+                (target.x, target.y + NODE_HANDLE_Y_OFFSET),
+            );
+            (distance <= radius).then_some((connection.id, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(id, _)| id)
+}
+
+/// The node with no outgoing connection whose output handle is closest to
+/// `point`, within `radius` canvas units.
+#[must_use]
+pub fn nearest_free_output(
+    nodes: &[crate::graph::Node],
+    connections: &[crate::graph::Connection],
+    point: (f32, f32),
+    radius: f32,
+) -> Option<crate::graph::NodeId> {
+    nodes
+        .iter()
+        .filter(|node| !connections.iter().any(|c| c.source == node.id))
+        .filter_map(|node| {
+            let handle = (node.x + NODE_WIDTH, node.y + NODE_HANDLE_Y_OFFSET);
+            let distance = (point.0 - handle.0).hypot(point.1 - handle.1);
+            (distance <= radius).then_some((node.id, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(id, _)| id)
+}
+
+/// Picks how a node dropped at `point` should be auto-connected. Edges win
+/// over handles, since dropping onto a line is the more deliberate gesture.
+#[must_use]
+pub fn auto_connect_target(
+    nodes: &[crate::graph::Node],
+    connections: &[crate::graph::Connection],
+    point: (f32, f32),
+    radius: f32,
+) -> Option<AutoConnect> {
+    nearest_connection(nodes, connections, point, radius)
+        .map(AutoConnect::Splice)
+        .or_else(|| {
+            nearest_free_output(nodes, connections, point, radius).map(AutoConnect::FromOutput)
+        })
+}
+
 #[cfg(test)]
 #[allow(
     clippy::unwrap_used,
@@ -124,8 +232,11 @@ pub fn snap_handle(
     clippy::float_cmp
 )]
 mod tests {
-    use super::{node_intersects_rect, normalize_rect, rect_contains, snap_handle};
-    use crate::graph::{Viewport, Workflow};
+    use super::{
+        auto_connect_target, node_intersects_rect, normalize_rect, rect_contains, snap_handle,
+        AutoConnect,
+    };
+    use crate::graph::{PortName, Viewport, Workflow};
 
     #[test]
     fn given_drag_points_when_normalizing_then_rect_bounds_are_ordered() {
@@ -276,4 +387,29 @@ mod tests {
     fn given_nan_zoom_when_validating_then_zoom_is_not_finite() {
         assert!(!f32::NAN.is_finite());
     }
+
+    #[test]
+    fn given_drop_near_edge_or_free_handle_when_picking_auto_connect_then_nearest_target_wins() {
+        let mut workflow = Workflow::new();
+        let source = workflow.add_node("http-handler", 0.0, 0.0);
+        let target = workflow.add_node("run", 600.0, 200.0);
+        let main = PortName::from("main");
+        let _ = workflow.add_connection_checked(source, target, &main, &main);
+        let edge = workflow.connections[0].id;
+
+        // Edge route: (220, 34) -> (220, 134) -> (600, 134) -> (600, 234)
+        assert_eq!(
+            auto_connect_target(&workflow.nodes, &workflow.connections, (400.0, 140.0), 24.0),
+            Some(AutoConnect::Splice(edge))
+        );
+        // The target has no outgoing edge, so its output handle is free.
+        assert_eq!(
+            auto_connect_target(&workflow.nodes, &workflow.connections, (830.0, 230.0), 24.0),
+            Some(AutoConnect::FromOutput(target))
+        );
+        assert_eq!(
+            auto_connect_target(&workflow.nodes, &workflow.connections, (400.0, 400.0), 24.0),
+            None
+        );
+    }
 }
@@ -146,6 +146,7 @@ mod tests {
             results: HashMap::new(),
             success: outcome.is_success(),
             restate_invocation_id: None,
+            durations: HashMap::new(),
         }
     }
 
@@ -231,6 +232,7 @@ mod tests {
             results,
             success: false,
             restate_invocation_id: None,
+            durations: HashMap::new(),
         };
 
         assert_eq!(derive_step_counts(&run), (1, 0));
@@ -249,6 +251,7 @@ mod tests {
             results,
             success: false,
             restate_invocation_id: None,
+            durations: HashMap::new(),
         };
 
         assert_eq!(derive_step_counts(&run), (1, 2));
@@ -3,12 +3,19 @@ pub mod app_bootstrap;
 pub mod app_io;
 #[cfg(target_arch = "wasm32")]
 pub mod app_shell;
+pub mod behavior_coverage_panel;
 #[cfg(target_arch = "wasm32")]
 pub mod canvas_area;
 pub mod canvas_context_menu;
+pub mod canvas_frames;
+pub mod canvas_settings;
+pub mod capabilities;
+#[cfg(target_arch = "wasm32")]
+pub mod collab_presence;
 pub mod command_palette;
 pub mod config_panel;
 pub mod constants;
+pub mod deep_link;
 pub mod domain_types;
 pub mod edges;
 pub mod editor_interactions;
@@ -29,10 +36,12 @@ pub mod panel_types;
 pub mod parallel_group_overlay;
 #[cfg(target_arch = "wasm32")]
 pub mod payload_preview_panel;
+pub mod persistence;
 pub mod prototype_palette;
 pub mod restate;
 #[cfg(target_arch = "wasm32")]
 pub mod right_panel;
+pub mod run_errors_panel;
 pub mod run_status_bar;
 #[cfg(target_arch = "wasm32")]
 pub mod selected_node_panel;
@@ -43,17 +52,20 @@ pub mod sidebar;
 pub mod toast;
 pub mod toolbar;
 pub mod validation_panel;
+pub mod workflow_compare;
 pub mod workflow_nodes;
 
 #[cfg(target_arch = "wasm32")]
 pub use app_io::download_workflow_json;
 #[cfg(target_arch = "wasm32")]
 pub use app_shell::AppShell;
+pub use behavior_coverage_panel::BehaviorCoveragePanel;
 #[cfg(target_arch = "wasm32")]
 pub use canvas_area::CanvasArea;
 pub use canvas_context_menu::CanvasContextMenu;
+pub use canvas_frames::CanvasFrames;
 pub use command_palette::NodeCommandPalette;
-pub use config_panel::NodeConfigEditor;
+pub use config_panel::{EditorTab, NodeConfigEditor};
 pub use domain_types::NodeTemplateId;
 pub use edges::{FlowEdges, Position as FlowPosition};
 #[cfg(target_arch = "wasm32")]
@@ -74,6 +86,7 @@ pub use prototype_palette::PrototypePalette;
 #[cfg(target_arch = "wasm32")]
 #[allow(unused_imports)]
 pub use right_panel::RightPanel;
+pub use run_errors_panel::RunErrorsPanel;
 pub use run_status_bar::RunStatusBar;
 #[cfg(target_arch = "wasm32")]
 pub use selected_node_panel::SelectedNodePanel;
@@ -83,3 +96,4 @@ pub use shortcuts_overlay::ShortcutsOverlay;
 pub use sidebar::NodeSidebar;
 pub use toolbar::FlowToolbar;
 pub use validation_panel::ValidationPanel;
+pub use workflow_compare::WorkflowCompareOverlay;
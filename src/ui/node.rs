@@ -7,6 +7,7 @@
 
 use crate::graph::{ExecutionState, Node, NodeCategory};
 use crate::ui::icons::icon_by_name;
+use crate::ui::inspector_panel::format_duration;
 use crate::ui::InlineConfigPanel;
 use dioxus::prelude::*;
 use serde_json::Value;
@@ -65,6 +66,30 @@ pub const fn status_badge_label(state: ExecutionState) -> &'static str {
     }
 }
 
+/// Timing of a node in the profiled run, shown in profile mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeProfile {
+    pub duration_ms: u64,
+    /// Duration relative to the slowest node, from 0.0 to 1.0.
+    pub heat: f32,
+    pub critical: bool,
+}
+
+/// Left-border colour for a node in profile mode, from green (fast) to red
+/// (the slowest nodes of the run).
+#[must_use]
+pub fn profile_heat_class(heat: f32) -> &'static str {
+    if heat >= 0.75 {
+        "border-l-4 border-red-500"
+    } else if heat >= 0.5 {
+        "border-l-4 border-orange-400"
+    } else if heat >= 0.25 {
+        "border-l-4 border-amber-300"
+    } else {
+        "border-l-4 border-emerald-400"
+    }
+}
+
 /// Returns the first `max_lines` lines of pretty-printed JSON for the given
 /// output value, or `None` when there is no output.  Pure - no side effects.
 #[must_use]
@@ -100,6 +125,7 @@ pub fn FlowNodeComponent(
     on_handle_mouse_leave: EventHandler<()>,
     on_inline_change: EventHandler<Value>,
     on_inline_close: EventHandler<()>,
+    #[props(default)] profile: Option<NodeProfile>,
 ) -> Element {
     let category = node.category;
     let icon = node.icon.clone();
@@ -132,7 +158,18 @@ pub fn FlowNodeComponent(
         NodeCategory::Signal => "bg-blue-500/40",
     };
 
-    let exec_border = node_border_class(exec_state);
+    let exec_border = profile.map_or_else(
+        || node_border_class(exec_state),
+        |profile| profile_heat_class(profile.heat),
+    );
+    let critical_ring = if profile.is_some_and(|profile| profile.critical) {
+        "ring-2 ring-orange-500/70"
+    } else {
+        ""
+    };
+    let profile_label = profile
+        .and_then(|profile| i64::try_from(profile.duration_ms).ok())
+        .map(|ms| format_duration(Some(ms)));
 
     let selected_classes = if selected {
         "ring-2 ring-cyan-500/55 border-cyan-500/40 shadow-xl shadow-cyan-500/20"
@@ -142,13 +179,22 @@ pub fn FlowNodeComponent(
 
     // Running nodes retain the subtle outer glow even when not selected.
     let running_glow = if matches!(exec_state, ExecutionState::Running) {
-        "shadow-[0_0_0_2px_rgba(6,182,212,0.2)]"
+        "shadow-[0_0_0_2px_rgba(6,182,212,0.2)] animate-pulse"
     } else {
         ""
     };
 
     let z_index = if selected || inline_open { 10 } else { 1 };
 
+    let duration_label = node
+        .duration_ms()
+        .and_then(|ms| i64::try_from(ms).ok())
+        .map(|ms| format_duration(Some(ms)));
+    let failure_title = node
+        .error
+        .clone()
+        .unwrap_or_else(|| "Execution failed".to_string());
+
     // Output preview: up to 3 lines of pretty JSON.
     let preview = output_preview(node.last_output.as_ref(), 3);
 
@@ -166,7 +212,7 @@ pub fn FlowNodeComponent(
             style: "left: {node.x}px; top: {node.y}px; z-index: {z_index};",
 
             div {
-                class: "group relative w-[220px] rounded-xl border bg-gradient-to-b from-white to-slate-50/70 transition-all duration-150 cursor-grab active:cursor-grabbing {category_border} {exec_border} {selected_classes} {running_glow}",
+                class: "group relative w-[220px] rounded-xl border bg-gradient-to-b from-white to-slate-50/70 transition-all duration-150 cursor-grab active:cursor-grabbing {category_border} {exec_border} {critical_ring} {selected_classes} {running_glow}",
                 onmousedown: move |e| {
                     on_mouse_down.call(e);
                 },
@@ -189,6 +235,13 @@ pub fn FlowNodeComponent(
                     onmouseleave: move |_| on_handle_mouse_leave.call(())
                 }
 
+                if let Some(label) = profile_label.as_ref() {
+                    span {
+                        class: "absolute -top-2.5 left-3 rounded-full border border-slate-200 bg-white px-1.5 py-px font-mono text-[9px] text-slate-600 shadow-sm",
+                        "{label}"
+                    }
+                }
+
                 // ── Header row ───────────────────────────────────────────
                 div {
                     class: "absolute inset-x-0 top-0 h-[1px] rounded-t-xl opacity-70",
@@ -238,14 +291,20 @@ pub fn FlowNodeComponent(
                                         class: "{status_badge_class(ExecutionState::Completed)}",
                                         {icon_by_name("check-circle", "h-2.5 w-2.5".to_string())}
                                         "{status_badge_label(ExecutionState::Completed)}"
+                                        if let Some(duration) = duration_label.as_ref() {
+                                            span { class: "font-mono opacity-80", "{duration}" }
+                                        }
                                     }
                                 },
                                 ExecutionState::Failed => rsx! {
                                     span {
                                         class: "{status_badge_class(ExecutionState::Failed)}",
-                                        title: "Execution failed",
+                                        title: "{failure_title}",
                                         {icon_by_name("x", "h-2.5 w-2.5".to_string())}
                                         "{status_badge_label(ExecutionState::Failed)}"
+                                        if let Some(duration) = duration_label.as_ref() {
+                                            span { class: "font-mono opacity-80", "{duration}" }
+                                        }
                                     }
                                 },
                                 ExecutionState::Skipped => rsx! {
@@ -458,6 +517,16 @@ mod tests {
         assert_eq!(status_badge_label(ExecutionState::Skipped), "Skipped");
     }
 
+    // -- profile_heat_class --------------------------------------------------
+
+    #[test]
+    fn given_heat_levels_when_profile_class_queried_then_colour_escalates() {
+        assert!(profile_heat_class(0.0).contains("emerald"));
+        assert!(profile_heat_class(0.3).contains("amber"));
+        assert!(profile_heat_class(0.6).contains("orange"));
+        assert!(profile_heat_class(1.0).contains("red"));
+    }
+
     // -- output_preview ------------------------------------------------------
 
     #[test]
@@ -0,0 +1,328 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! Debounced, delta-based saving of the workflow to local storage.
+//!
+//! Writing the whole workflow on every change stalls large graphs while a
+//! node is dragged. [`PersistScheduler`] holds writes back until edits pause
+//! for [`PERSIST_DEBOUNCE_MS`], but never longer than
+//! [`PERSIST_MAX_DELAY_MS`] during a long drag. [`DeltaEncoder`] then writes
+//! only the nodes that changed since the last full snapshot, and falls back
+//! to a new snapshot once the delta grows past half the graph.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::graph::{Connection, Node, NodeId, NodeIndex, Workflow};
+
+pub const SNAPSHOT_STORAGE_KEY: &str = "flow-wasm-v1-workflow";
+pub const DELTA_STORAGE_KEY: &str = "flow-wasm-v1-workflow-delta";
+pub const PERSIST_DEBOUNCE_MS: f64 = 400.0;
+pub const PERSIST_MAX_DELAY_MS: f64 = 3_000.0;
+
+/// Decides when pending edits are written. Times are milliseconds from any
+/// monotonic-enough clock, e.g. `Date.now()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PersistScheduler {
+    dirty_since: Option<f64>,
+    last_change: f64,
+}
+
+impl PersistScheduler {
+    pub fn mark_changed(&mut self, now: f64) {
+        self.dirty_since.get_or_insert(now);
+        self.last_change = now;
+    }
+
+    #[must_use]
+    pub const fn is_dirty(&self) -> bool {
+        self.dirty_since.is_some()
+    }
+
+    /// Whether edits have paused long enough, or waited too long, to save.
+    #[must_use]
+    pub fn is_due(&self, now: f64) -> bool {
+        self.dirty_since.is_some_and(|since| {
+            now - self.last_change >= PERSIST_DEBOUNCE_MS || now - since >= PERSIST_MAX_DELAY_MS
+        })
+    }
+
+    pub const fn mark_saved(&mut self) {
+        self.dirty_since = None;
+    }
+
+    /// Keep the edits pending after a failed write and try again after the
+    /// debounce interval rather than on every tick.
+    pub const fn retry_later(&mut self, now: f64) {
+        self.dirty_since = Some(now);
+        self.last_change = now;
+    }
+}
+
+/// What to write after an edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PersistWrite {
+    /// Replace the snapshot and drop any delta.
+    Snapshot(String),
+    /// Replace the delta; the snapshot stays as it is.
+    Delta(String),
+}
+
+/// Nodes changed since a snapshot, plus the rest of the workflow.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WorkflowDelta {
+    /// Fingerprint of the snapshot this delta applies to.
+    base: u64,
+    upserted: Vec<Node>,
+    removed: Vec<NodeId>,
+    /// Node order, when nodes were added or removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    order: Option<Vec<NodeId>>,
+    /// All connections, when they differ from the snapshot's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    connections: Option<Vec<Connection>>,
+    /// The workflow without its nodes and connections.
+    rest: Value,
+}
+
+/// The snapshot deltas are computed against.
+#[derive(Debug, Clone)]
+struct DeltaBase {
+    fingerprint: u64,
+    nodes: NodeIndex,
+    connections: Vec<Connection>,
+}
+
+impl DeltaBase {
+    fn new(json: &str, workflow: &Workflow) -> Self {
+        Self {
+            fingerprint: fingerprint(json),
+            nodes: NodeIndex::from_nodes(&workflow.nodes),
+            connections: workflow.connections.clone(),
+        }
+    }
+}
+
+/// Turns successive workflow states into snapshot or delta writes.
+#[derive(Debug, Clone, Default)]
+pub struct DeltaEncoder {
+    base: Option<DeltaBase>,
+}
+
+impl DeltaEncoder {
+    /// Continue from a snapshot already in storage, so the first save after
+    /// a reload can be a delta. An unreadable snapshot starts afresh.
+    #[must_use]
+    pub fn resume(snapshot_json: &str) -> Self {
+        Self {
+            base: parse(snapshot_json).map(|snapshot| DeltaBase::new(snapshot_json, &snapshot)),
+        }
+    }
+
+    /// The write that stores `workflow`.
+    ///
+    /// # Errors
+    /// Returns an error if the workflow cannot be serialized.
+    pub fn encode(&mut self, mut workflow: Workflow) -> Result<PersistWrite, serde_json::Error> {
+        let Some(base) = &self.base else {
+            return self.snapshot(&workflow);
+        };
+        let changes = base.nodes.clone().sync(&workflow.nodes);
+        let touched = changes.added.len() + changes.updated.len() + changes.removed.len();
+        if touched * 2 > workflow.nodes.len().max(1) {
+            return self.snapshot(&workflow);
+        }
+        let upserted: HashSet<NodeId> = changes
+            .added
+            .iter()
+            .chain(&changes.updated)
+            .copied()
+            .collect();
+        let nodes = std::mem::take(&mut workflow.nodes);
+        let connections = std::mem::take(&mut workflow.connections);
+        let delta = WorkflowDelta {
+            base: base.fingerprint,
+            order: changes
+                .changes_membership()
+                .then(|| nodes.iter().map(|node| node.id).collect()),
+            upserted: nodes
+                .into_iter()
+                .filter(|node| upserted.contains(&node.id))
+                .collect(),
+            removed: changes.removed,
+            connections: (connections != base.connections).then_some(connections),
+            rest: serde_json::to_value(&workflow)?,
+        };
+        serde_json::to_string(&delta).map(PersistWrite::Delta)
+    }
+
+    fn snapshot(&mut self, workflow: &Workflow) -> Result<PersistWrite, serde_json::Error> {
+        let json = serde_json::to_string(workflow)?;
+        self.base = Some(DeltaBase::new(&json, workflow));
+        Ok(PersistWrite::Snapshot(json))
+    }
+}
+
+/// The stored workflow: the snapshot with its delta applied, if the delta
+/// belongs to that snapshot. A stale or unreadable delta is ignored.
+#[must_use]
+pub fn restore(snapshot_json: &str, delta_json: Option<&str>) -> Option<Workflow> {
+    let snapshot = parse(snapshot_json)?;
+    let Some(delta) = delta_json
+        .and_then(|json| serde_json::from_str::<WorkflowDelta>(json).ok())
+        .filter(|delta| delta.base == fingerprint(snapshot_json))
+    else {
+        return Some(snapshot);
+    };
+    let Ok(mut workflow) = serde_json::from_value::<Workflow>(delta.rest) else {
+        return Some(snapshot);
+    };
+    let removed: HashSet<NodeId> = delta.removed.into_iter().collect();
+    let mut upserted: HashMap<NodeId, Node> = delta
+        .upserted
+        .into_iter()
+        .map(|node| (node.id, node))
+        .collect();
+    upserted.values_mut().for_each(Node::rehydrate);
+    let mut nodes: Vec<Node> = snapshot
+        .nodes
+        .into_iter()
+        .filter(|node| !removed.contains(&node.id))
+        .map(|node| upserted.remove(&node.id).unwrap_or(node))
+        .collect();
+    nodes.extend(upserted.into_values());
+    if let Some(order) = delta.order {
+        let position: HashMap<NodeId, usize> = order
+            .into_iter()
+            .enumerate()
+            .map(|(position, id)| (id, position))
+            .collect();
+        nodes.sort_by_key(|node| position.get(&node.id).copied());
+    }
+    workflow.nodes = nodes;
+    workflow.connections = delta.connections.unwrap_or(snapshot.connections);
+    Some(workflow)
+}
+
+/// A stored workflow with each node's typed config rebuilt from its JSON
+/// config, which is all that is serialized.
+fn parse(json: &str) -> Option<Workflow> {
+    let mut workflow = serde_json::from_str::<Workflow>(json).ok()?;
+    workflow.nodes.iter_mut().for_each(Node::rehydrate);
+    Some(workflow)
+}
+
+/// FNV-1a over the snapshot text; ties a delta to the snapshot it was
+/// computed against.
+fn fingerprint(json: &str) -> u64 {
+    json.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::testing::synthetic::large_workflow;
+
+    #[test]
+    fn given_edits_when_checking_schedule_then_save_waits_for_a_pause_but_not_forever() {
+        let mut scheduler = PersistScheduler::default();
+        assert!(!scheduler.is_due(0.0));
+
+        scheduler.mark_changed(1_000.0);
+        assert!(scheduler.is_dirty());
+        assert!(!scheduler.is_due(1_200.0));
+        assert!(scheduler.is_due(1_400.0));
+
+        // A drag keeps changing the graph every 50ms.
+        let mut now = 1_000.0;
+        while now < 3_950.0 {
+            now += 50.0;
+            scheduler.mark_changed(now);
+            assert!(!scheduler.is_due(now) || now >= 4_000.0);
+        }
+        assert!(scheduler.is_due(4_000.0));
+
+        scheduler.retry_later(4_000.0);
+        assert!(!scheduler.is_due(4_100.0));
+        assert!(scheduler.is_due(4_400.0));
+
+        scheduler.mark_saved();
+        assert!(!scheduler.is_dirty());
+        assert!(!scheduler.is_due(10_000.0));
+    }
+
+    #[test]
+    fn given_small_edits_when_encoding_then_delta_restores_the_workflow() {
+        let mut workflow = large_workflow(40);
+        let mut encoder = DeltaEncoder::default();
+        let PersistWrite::Snapshot(snapshot) = encoder.encode(workflow.clone()).unwrap() else {
+            panic!("first write should be a snapshot");
+        };
+
+        workflow.nodes[5].x += 20.0;
+        workflow.nodes.remove(9);
+        workflow.add_node("run", 9_000.0, 0.0);
+        workflow.viewport.zoom = 1.5;
+        let PersistWrite::Delta(delta) = encoder.encode(workflow.clone()).unwrap() else {
+            panic!("a few edits should be a delta");
+        };
+
+        assert!(delta.len() * 4 < snapshot.len());
+        let restored = restore(&snapshot, Some(&delta)).unwrap();
+        assert_eq!(restored.nodes, workflow.nodes);
+        assert_eq!(restored.viewport, workflow.viewport);
+        assert_eq!(restored.connections, workflow.connections);
+    }
+
+    #[test]
+    fn given_large_edit_or_stale_delta_when_encoding_and_restoring_then_snapshot_wins() {
+        let mut workflow = large_workflow(10);
+        let mut encoder = DeltaEncoder::default();
+        let PersistWrite::Snapshot(first) = encoder.encode(workflow.clone()).unwrap() else {
+            panic!("first write should be a snapshot");
+        };
+        workflow.nodes[1].x += 5.0;
+        let PersistWrite::Delta(delta) = encoder.encode(workflow.clone()).unwrap() else {
+            panic!("one edit should be a delta");
+        };
+
+        workflow.apply_layout();
+        workflow.nodes.iter_mut().for_each(|node| node.y += 1.0);
+        let PersistWrite::Snapshot(second) = encoder.encode(workflow.clone()).unwrap() else {
+            panic!("moving every node should write a snapshot");
+        };
+
+        let restored = restore(&second, Some(&delta)).unwrap();
+        assert_eq!(restored.nodes, workflow.nodes);
+        assert_eq!(restore(&first, Some("not json")).unwrap().nodes.len(), 10);
+    }
+
+    #[test]
+    fn given_resumed_encoder_when_editing_then_first_write_is_a_delta() {
+        let mut workflow = large_workflow(10);
+        let snapshot = serde_json::to_string(&workflow).unwrap();
+        let mut encoder = DeltaEncoder::resume(&snapshot);
+        workflow.nodes[2].name = "renamed".to_string();
+        workflow.connections.pop();
+
+        let PersistWrite::Delta(delta) = encoder.encode(workflow.clone()).unwrap() else {
+            panic!("resumed encoder should write a delta");
+        };
+        let restored = restore(&snapshot, Some(&delta)).unwrap();
+        assert_eq!(restored.nodes[2].name, "renamed");
+        assert_eq!(restored.connections, workflow.connections);
+    }
+}
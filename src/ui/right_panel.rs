@@ -7,8 +7,11 @@
 use crate::graph::{NodeId, ValidationResult};
 use crate::hooks::use_restate_sync::RestateSyncHandle;
 use crate::hooks::use_workflow_state::WorkflowState;
+use crate::ui::capabilities::Capability;
 use crate::ui::restate::{DeploymentBrowserPanel, PromiseBrowserPanel, RestateInvocationsPanel};
-use crate::ui::{ExecutionHistoryPanel, ExecutionPlanPanel, ValidationPanel};
+use crate::ui::{
+    BehaviorCoveragePanel, ExecutionHistoryPanel, ExecutionPlanPanel, ValidationPanel,
+};
 use dioxus::prelude::*;
 
 #[component]
@@ -17,9 +20,11 @@ pub fn RightPanel(
     validation_result: Memo<ValidationResult>,
     validation_collapsed: Signal<bool>,
     frozen_run_id: Signal<Option<uuid::Uuid>>,
+    selected_node: ReadSignal<Option<NodeId>>,
     on_select_node: EventHandler<NodeId>,
     restate: RestateSyncHandle,
 ) -> Element {
+    let coverage_collapsed = use_signal(|| true);
     let plan_collapsed = use_signal(|| false);
     let history_collapsed = use_signal(|| true);
     let history_signal = use_memo(move || workflow.workflow().read().history.clone());
@@ -33,6 +38,20 @@ pub fn RightPanel(
                     on_select_node.call(node_id);
                 },
             }
+            BehaviorCoveragePanel {
+                workflow: workflow.workflow(),
+                selected_node,
+                collapsed: coverage_collapsed,
+                on_select_node: move |node_id| {
+                    on_select_node.call(node_id);
+                },
+                on_link: move |(node_id, behavior_id): (NodeId, String)| {
+                    workflow.set_behavior_link(node_id, &behavior_id, true);
+                },
+                on_unlink: move |(node_id, behavior_id): (NodeId, String)| {
+                    workflow.set_behavior_link(node_id, &behavior_id, false);
+                },
+            }
             ExecutionPlanPanel {
                 on_select_node: move |node_id| {
                     on_select_node.call(node_id);
@@ -59,7 +78,9 @@ pub fn RightPanel(
             }
             RestateInvocationsPanel { handle: restate }
             PromiseBrowserPanel { handle: restate }
-            DeploymentBrowserPanel { handle: restate }
+            if workflow.can(Capability::ManageDeployments) {
+                DeploymentBrowserPanel { handle: restate }
+            }
         }
     }
 }
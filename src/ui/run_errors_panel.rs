@@ -0,0 +1,204 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+use crate::graph::{ExecutionState, NodeId, Workflow};
+use crate::ui::icons::{AlertCircleIcon, XIcon};
+use dioxus::prelude::*;
+
+/// Longest upstream output shown beside a failure, in characters.
+const OUTPUT_PREVIEW_CHARS: usize = 80;
+
+/// A node that failed in the latest run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunFailure {
+    pub node_id: NodeId,
+    pub node_name: String,
+    pub message: String,
+    pub upstream: Vec<UpstreamContext>,
+}
+
+/// A direct parent of a failed node, with what it handed over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpstreamContext {
+    pub node_name: String,
+    pub state: ExecutionState,
+    pub output_preview: Option<String>,
+}
+
+/// Every node carrying an error, in execution order.
+#[must_use]
+pub fn collect_run_failures(workflow: &Workflow) -> Vec<RunFailure> {
+    let queue_position = |id: NodeId| {
+        workflow
+            .execution_queue
+            .iter()
+            .position(|queued| *queued == id)
+            .unwrap_or(usize::MAX)
+    };
+
+    let mut failures: Vec<RunFailure> = workflow
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            let message = node.error.clone()?;
+            let upstream = workflow
+                .connections
+                .iter()
+                .filter(|connection| connection.target == node.id)
+                .filter_map(|connection| {
+                    workflow
+                        .nodes
+                        .iter()
+                        .find(|parent| parent.id == connection.source)
+                })
+                .map(|parent| UpstreamContext {
+                    node_name: parent.name.clone(),
+                    state: parent.execution_state,
+                    output_preview: parent
+                        .last_output
+                        .as_ref()
+                        .map(|output| truncate_chars(&output.to_string(), OUTPUT_PREVIEW_CHARS)),
+                })
+                .collect();
+            Some(RunFailure {
+                node_id: node.id,
+                node_name: node.name.clone(),
+                message,
+                upstream,
+            })
+        })
+        .collect();
+    failures.sort_by_key(|failure| queue_position(failure.node_id));
+    failures
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+/// Bottom panel listing the failures of the latest run.
+#[component]
+pub fn RunErrorsPanel(
+    failures: ReadSignal<Vec<RunFailure>>,
+    on_focus_node: EventHandler<NodeId>,
+    on_dismiss: EventHandler<()>,
+) -> Element {
+    let count = failures.read().len();
+    if count == 0 {
+        return rsx! {};
+    }
+    let heading = if count == 1 {
+        "1 node failed".to_string()
+    } else {
+        format!("{count} nodes failed")
+    };
+
+    rsx! {
+        div {
+            class: "absolute inset-x-4 bottom-4 z-30 flex max-h-[40%] flex-col overflow-hidden rounded-lg border border-red-200 bg-white/95 shadow-xl shadow-red-900/10 backdrop-blur",
+            onmousedown: move |evt| evt.stop_propagation(),
+            onwheel: move |evt| evt.stop_propagation(),
+            div { class: "flex items-center justify-between border-b border-red-100 bg-red-50 px-3 py-2",
+                div { class: "flex items-center gap-2",
+                    AlertCircleIcon { class: "h-4 w-4 text-red-500" }
+                    span { class: "text-[12px] font-semibold text-red-700", "{heading}" }
+                }
+                button {
+                    class: "flex h-6 w-6 items-center justify-center rounded-md text-red-400 transition-colors hover:bg-red-100 hover:text-red-600",
+                    title: "Dismiss",
+                    onclick: move |_| on_dismiss.call(()),
+                    XIcon { class: "h-3.5 w-3.5" }
+                }
+            }
+            div { class: "flex-1 overflow-y-auto",
+                for failure in failures.read().iter().cloned() {
+                    {
+                        let node_id = failure.node_id;
+                        rsx! {
+                            button {
+                                key: "{node_id}",
+                                class: "flex w-full flex-col gap-1 border-b border-slate-100 px-3 py-2 text-left transition-colors hover:bg-red-50/60",
+                                onclick: move |_| on_focus_node.call(node_id),
+                                div { class: "flex items-center gap-2",
+                                    span { class: "text-[12px] font-semibold text-slate-800", "{failure.node_name}" }
+                                    span { class: "truncate font-mono text-[11px] text-red-600", "{failure.message}" }
+                                }
+                                for (index, parent) in failure.upstream.iter().enumerate() {
+                                    div {
+                                        key: "{index}",
+                                        class: "flex items-center gap-2 pl-3 text-[10px] text-slate-500",
+                                        span { "← {parent.node_name}" }
+                                        span { class: "rounded bg-slate-100 px-1 py-px", "{parent.state}" }
+                                        if let Some(preview) = parent.output_preview.as_ref() {
+                                            span { class: "truncate font-mono text-slate-400", "{preview}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::PortName;
+    use serde_json::json;
+
+    #[test]
+    fn given_failed_nodes_when_collecting_then_errors_come_in_queue_order_with_parents() {
+        let mut workflow = Workflow::new();
+        let source = workflow.add_node("http-handler", 0.0, 0.0);
+        let later = workflow.add_node("run", 300.0, 0.0);
+        let first = workflow.add_node("run", 300.0, 200.0);
+        let main = PortName("main".to_string());
+        workflow
+            .add_connection(source, first, &main, &main)
+            .unwrap();
+        workflow.add_connection(first, later, &main, &main).unwrap();
+        workflow.execution_queue = vec![source, first, later];
+        for node in &mut workflow.nodes {
+            if node.id == source {
+                node.execution_state = ExecutionState::Completed;
+                node.last_output = Some(json!({ "body": "x".repeat(200) }));
+            } else {
+                node.error = Some(format!("boom {}", node.id));
+            }
+        }
+
+        let failures = collect_run_failures(&workflow);
+
+        let order: Vec<NodeId> = failures.iter().map(|failure| failure.node_id).collect();
+        assert_eq!(order, vec![first, later]);
+        assert_eq!(failures[0].message, format!("boom {first}"));
+        let parent = &failures[0].upstream[0];
+        assert_eq!(parent.state, ExecutionState::Completed);
+        let preview = parent.output_preview.as_ref().unwrap();
+        assert_eq!(preview.chars().count(), OUTPUT_PREVIEW_CHARS + 1);
+        assert!(preview.ends_with('…'));
+    }
+
+    #[test]
+    fn given_successful_run_when_collecting_then_no_failures() {
+        let mut workflow = Workflow::new();
+        workflow.add_node("run", 0.0, 0.0);
+
+        assert!(collect_run_failures(&workflow).is_empty());
+    }
+}
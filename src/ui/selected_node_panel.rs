@@ -3,30 +3,55 @@
 #![deny(clippy::panic)]
 #![warn(clippy::pedantic)]
 
+use crate::errors::WorkflowError;
+use crate::flow_extender::calibration::{set_active_log, ExtensionAcceptanceLog};
 use crate::flow_extender::{
-    apply_extension, extension_presets, preview_extension, resolve_extension_preset,
-    suggest_extensions, ExtensionPatchPreview, ExtensionPriority,
+    applied_extension_keys, apply_extension, clear_suppressions, dismiss_extension,
+    preview_extension, ExtensionPatchPreview, ExtensionPreset, ExtensionPresetRegistry,
+    ExtensionPriority,
 };
-use crate::graph::{Node, NodeCategory, NodeId, Workflow};
+use crate::graph::{NodeCategory, NodeId, NodeIndex, Workflow};
 use dioxus::prelude::*;
 use itertools::Itertools;
-use std::collections::HashMap;
 
-use crate::ui::NodeConfigEditor;
+use crate::ui::capabilities::Capability;
+use crate::ui::{EditorTab, NodeConfigEditor};
 
 #[component]
 pub fn SelectedNodePanel(
     selection: crate::hooks::use_selection::SelectionState,
-    nodes_by_id: ReadSignal<HashMap<NodeId, Node>>,
+    nodes_by_id: ReadSignal<NodeIndex>,
     workflow_state: crate::hooks::use_workflow_state::WorkflowState,
     preview_patches: Signal<Vec<ExtensionPatchPreview>>,
+    editor_tab: Signal<EditorTab>,
 ) -> Element {
     let selected_node_id = selection.selected_id();
-    let mut workflow = workflow_state.workflow();
+    let workflow = workflow_state.workflow();
+    let can_edit = workflow_state.can(Capability::EditWorkflow);
+    let analysis = crate::hooks::use_analysis();
     let mut selected_extension_keys = use_signal(Vec::<String>::new);
     let mut extension_message = use_signal(|| None::<String>);
     let mut extension_timeline = use_signal(Vec::<ExtensionTimelineEvent>::new);
     let mut extension_snapshots = use_signal(Vec::<ExtensionBatchSnapshot>::new);
+    let mut preset_registry = use_signal(load_preset_registry);
+    let mut new_preset_title = use_signal(String::new);
+
+    let mut acceptance_log = use_signal(load_acceptance_log);
+
+    use_effect(move || save_preset_registry(&preset_registry.read()));
+    use_effect(move || {
+        let log = acceptance_log.read();
+        save_acceptance_log(&log);
+        set_active_log(log.clone());
+    });
+
+    // Keep the acceptance log in step with edits to applied extension nodes
+    use_effect(move || {
+        let mut log = acceptance_log.peek().clone();
+        if log.observe(&workflow.read()) > 0 {
+            acceptance_log.set(log);
+        }
+    });
 
     use_effect(move || {
         let selected = selected_extension_keys.read().clone();
@@ -68,7 +93,7 @@ pub fn SelectedNodePanel(
                         }
                     }
 
-                    div { class: "flex-1 overflow-y-auto p-4",
+                    fieldset { class: "min-w-0 flex-1 overflow-y-auto p-4", disabled: !can_edit,
                         div { class: "mb-4 flex items-center gap-2",
                             span { class: "inline-flex items-center rounded-md border px-2 py-0.5 text-[10px] font-medium capitalize {badge_classes}", "{selected_node.category}" }
                             span { class: "text-[10px] font-mono text-slate-500", "ID: {selected_node.id}" }
@@ -79,10 +104,11 @@ pub fn SelectedNodePanel(
                                 class: "h-8 rounded-md border border-slate-300 bg-white px-3 text-[12px] text-slate-900 outline-none transition-colors focus:border-blue-500/50 focus:ring-1 focus:ring-blue-500/30",
                                 value: "{selected_node.name}",
                                 oninput: move |evt| {
-                                    let mut wf = workflow.write();
-                                    if let Some(node) = wf.nodes.iter_mut().find(|node| node.id == node_id) {
-                                        node.name = evt.value();
-                                    }
+                                    workflow_state.update(|wf| {
+                                        if let Some(node) = wf.nodes.iter_mut().find(|node| node.id == node_id) {
+                                            node.name = evt.value();
+                                        }
+                                    });
                                 }
                             }
                         }
@@ -95,10 +121,11 @@ pub fn SelectedNodePanel(
                                 class: "rounded-md border border-slate-300 bg-white px-3 py-2 text-[12px] text-slate-900 placeholder:text-slate-500/70 outline-none transition-colors focus:border-blue-500/50 focus:ring-1 focus:ring-blue-500/30 resize-none",
                                 value: "{selected_node.description}",
                                 oninput: move |evt| {
-                                    let mut wf = workflow.write();
-                                    if let Some(node) = wf.nodes.iter_mut().find(|node| node.id == node_id) {
-                                        node.description = evt.value();
-                                    }
+                                    workflow_state.update(|wf| {
+                                        if let Some(node) = wf.nodes.iter_mut().find(|node| node.id == node_id) {
+                                            node.description = evt.value();
+                                        }
+                                    });
                                 }
                             }
                         }
@@ -108,18 +135,22 @@ pub fn SelectedNodePanel(
                             NodeConfigEditor {
                                 node: selected_node.clone(),
                                 input_payloads: collect_input_payloads(&workflow.read(), node_id),
+                                tab: editor_tab,
                                 on_change: move |new_config| {
-                                    let mut wf = workflow.write();
-                                    if let Some(node) = wf.nodes.iter_mut().find(|node| node.id == node_id) {
-                                        node.apply_config_update(&new_config);
-                                    }
+                                    workflow_state.update(|wf| {
+                                        if let Some(node) = wf.nodes.iter_mut().find(|node| node.id == node_id) {
+                                            node.apply_config_update(&new_config);
+                                        }
+                                    });
                                 }
                             }
                         }
 
                         {
-                            let suggestions = suggest_extensions(&workflow.read());
-                            let presets = extension_presets();
+                            let suggestions = analysis.suggestions(&workflow.read());
+                            let applied_keys = applied_extension_keys(&workflow.read());
+                            let dismissed_count = workflow.read().suppressed_extensions.len();
+                            let presets = preset_registry.read().presets();
                             let suggestions_for_all = suggestions.clone();
                             let suggestions_for_high = suggestions.clone();
                             let selected_count = selected_extension_keys.read().len();
@@ -190,6 +221,18 @@ pub fn SelectedNodePanel(
                                             },
                                             "Redo"
                                         }
+                                        if dismissed_count > 0 {
+                                            button {
+                                                class: "h-7 rounded-md border border-slate-300 bg-white px-2.5 text-[10px] font-medium text-slate-700 transition-colors hover:bg-slate-100",
+                                                onclick: move |_| {
+                                                    let restored = workflow_state.update(clear_suppressions).unwrap_or(0);
+                                                    extension_message.set(Some(format!(
+                                                        "Restored {restored} dismissed suggestion(s).",
+                                                    )));
+                                                },
+                                                "Restore dismissed ({dismissed_count})"
+                                            }
+                                        }
                                     }
 
                                     if !presets.is_empty() {
@@ -205,6 +248,8 @@ pub fn SelectedNodePanel(
                                                         let preset_key_for_apply = preset.key.clone();
                                                         let preset_title_for_preview = preset.title.clone();
                                                         let preset_title_for_apply = preset.title.clone();
+                                                        let preset_key_for_delete = preset.key.clone();
+                                                        let is_custom = preset_registry.read().is_custom(&preset.key);
                                                         rsx! {
                                                             div { class: "rounded-md border border-slate-200 bg-white px-2.5 py-2",
                                                                 div { class: "mb-1 flex items-start justify-between gap-2",
@@ -223,7 +268,7 @@ pub fn SelectedNodePanel(
                                                                     button {
                                                                         class: "h-6 rounded-md border border-slate-300 bg-white px-2 text-[10px] font-medium text-slate-700 transition-colors hover:bg-slate-100",
                                                                         onclick: move |_| {
-                                                                            match resolve_extension_preset(&workflow.read(), &preset_key_for_preview) {
+                                                                            match preset_registry.read().resolve(&workflow.read(), &preset_key_for_preview) {
                                                                                 Ok(resolved) => {
                                                                                     if resolved.conflicts.is_empty() {
                                                                                         let count = resolved.ordered_keys.len();
@@ -268,7 +313,7 @@ pub fn SelectedNodePanel(
                                                                     button {
                                                                         class: "h-6 rounded-md border border-blue-300 bg-blue-50 px-2 text-[10px] font-medium text-blue-700 transition-colors hover:bg-blue-100",
                                                                         onclick: move |_| {
-                                                                            let resolved = resolve_extension_preset(&workflow.read(), &preset_key_for_apply);
+                                                                            let resolved = preset_registry.read().resolve(&workflow.read(), &preset_key_for_apply);
                                                                             let resolved = match resolved {
                                                                                 Ok(value) => value,
                                                                                  Err(err) => {
@@ -310,10 +355,9 @@ pub fn SelectedNodePanel(
                                                                             let mut total_created = 0usize;
                                                                             let mut applied_count = 0usize;
                                                                             let mut failures = Vec::new();
-                                                                            {
-                                                                                let mut wf = workflow.write();
+                                                                            workflow_state.update(|wf| {
                                                                                 resolved.ordered_keys.iter().for_each(|key| {
-                                                                                    match apply_extension(&mut wf, key) {
+                                                                                    match apply_extension(wf, key) {
                                                                                         Ok(applied) => {
                                                                                             total_created += applied.created_nodes.len();
                                                                                             applied_count += 1;
@@ -326,7 +370,7 @@ pub fn SelectedNodePanel(
                                                                                         Err(err) => failures.push(format!("{key}: {err}")),
                                                                                     }
                                                                                 });
-                                                                            }
+                                                                            });
 
                                                                             let (new_snapshots, metadata) = remember_extension_snapshot(
                                                                                 extension_snapshots.read().clone(),
@@ -386,6 +430,19 @@ pub fn SelectedNodePanel(
                                                                         },
                                                                         "Apply preset"
                                                                     }
+                                                                    if is_custom {
+                                                                        button {
+                                                                            class: "h-6 rounded-md border border-slate-300 bg-white px-2 text-[10px] font-medium text-slate-500 transition-colors hover:bg-rose-50 hover:text-rose-700",
+                                                                            onclick: move |_| {
+                                                                                if preset_registry.write().remove(&preset_key_for_delete) {
+                                                                                    extension_message.set(Some(format!(
+                                                                                        "Deleted preset '{preset_key_for_delete}'.",
+                                                                                    )));
+                                                                                }
+                                                                            },
+                                                                            "Delete"
+                                                                        }
+                                                                    }
                                                                 }
                                                             }
                                                         }
@@ -396,6 +453,35 @@ pub fn SelectedNodePanel(
                                     }
 
                                     if selected_count > 0 {
+                                        div { class: "mb-2 flex items-center gap-1.5",
+                                            input {
+                                                class: "h-7 min-w-0 flex-1 rounded-md border border-slate-300 bg-white px-2 text-[11px] text-slate-700 placeholder:text-slate-400",
+                                                placeholder: "New preset name",
+                                                value: "{new_preset_title}",
+                                                oninput: move |evt| new_preset_title.set(evt.value()),
+                                            }
+                                            button {
+                                                class: "h-7 rounded-md border border-slate-300 bg-white px-2.5 text-[10px] font-medium text-slate-700 transition-colors hover:bg-slate-100",
+                                                onclick: move |_| {
+                                                    let title = new_preset_title.read().trim().to_string();
+                                                    let extension_keys = selected_extension_keys.read().clone();
+                                                    let preset = ExtensionPreset {
+                                                        key: preset_key_from_title(&title),
+                                                        description: format!("Custom preset with {} extension rule(s).", extension_keys.len()),
+                                                        title: title.clone(),
+                                                        extension_keys,
+                                                    };
+                                                    match preset_registry.write().define(preset) {
+                                                        Ok(()) => {
+                                                            new_preset_title.set(String::new());
+                                                            extension_message.set(Some(format!("Saved preset '{title}'.")));
+                                                        }
+                                                        Err(err) => extension_message.set(Some(format!("Could not save preset: {err}"))),
+                                                    }
+                                                },
+                                                "Save as preset"
+                                            }
+                                        }
                                         div { class: "mb-2 flex items-center gap-2",
                                             button {
                                                 class: "h-7 rounded-md border border-blue-300 bg-blue-50 px-2.5 text-[11px] font-medium text-blue-700 transition-colors hover:bg-blue-100",
@@ -412,10 +498,9 @@ pub fn SelectedNodePanel(
                                                     let mut total_created = 0usize;
                                                     let mut applied_count = 0usize;
                                                     let mut failures = Vec::new();
-                                                    {
-                                                        let mut wf = workflow.write();
+                                                    workflow_state.update(|wf| {
                                                         for key in &keys {
-                                                                             match apply_extension(&mut wf, key) {
+                                                                             match apply_extension(wf, key) {
                                                                                  Ok(applied) => {
                                                                                      total_created += applied.created_nodes.len();
                                                                                      applied_count += 1;
@@ -428,7 +513,7 @@ pub fn SelectedNodePanel(
                                                                                  Err(err) => failures.push(format!("{key}: {err}")),
                                                                              }
                                                                          }
-                                                    }
+                                                    });
 
                                                     let (new_snapshots, metadata) = remember_extension_snapshot(
                                                         extension_snapshots.read().clone(),
@@ -523,6 +608,7 @@ pub fn SelectedNodePanel(
                                                     let key_for_card = key.clone();
                                                     let key_for_checkbox = key.clone();
                                                     let key_for_apply = key.clone();
+                                                    let key_for_dismiss = key.clone();
                                                     let title = suggestion.title.clone();
                                                     let is_selected = selected_extension_keys.read().iter().any(|selected| selected == &key);
                                                     let added_nodes = preview.as_ref().map_or(0, |value| value.nodes.len());
@@ -583,10 +669,9 @@ pub fn SelectedNodePanel(
                                                                         let workflow_before = workflow.read().clone();
                                                                         workflow_state.save_undo_point();
 
-                                                                        let result = {
-                                                                            let mut wf = workflow.write();
-                                                                            apply_extension(&mut wf, &key_for_apply)
-                                                                        };
+                                                                        let result = workflow_state
+                                                                            .update(|wf| apply_extension(wf, &key_for_apply))
+                                                                            .unwrap_or_else(|| Err(WorkflowError::ReadOnly.to_string()));
 
                                                                         let created_nodes = result
                                                                             .as_ref()
@@ -656,6 +741,102 @@ pub fn SelectedNodePanel(
                                                                     },
                                                                     "Apply"
                                                                 }
+                                                                button {
+                                                                    class: "h-6 rounded-md border border-slate-300 bg-white px-2 text-[10px] font-medium text-slate-600 transition-colors hover:bg-slate-100",
+                                                                    onclick: move |event| {
+                                                                        event.stop_propagation();
+                                                                        let result = workflow_state
+                                                                            .update(|wf| dismiss_extension(wf, &key_for_dismiss))
+                                                                            .unwrap_or_else(|| Err(WorkflowError::ReadOnly.to_string()));
+                                                                        match result {
+                                                                            Ok(_) => {
+                                                                                record_suggestion_decision(
+                                                                                    &key_for_dismiss,
+                                                                                    false,
+                                                                                    "dismiss",
+                                                                                );
+                                                                                let mut next = selected_extension_keys.read().clone();
+                                                                                next.retain(|selected| selected != &key_for_dismiss);
+                                                                                selected_extension_keys.set(next);
+                                                                                extension_message.set(Some(format!(
+                                                                                    "Dismissed '{key_for_dismiss}' until the workflow changes.",
+                                                                                )));
+                                                                            }
+                                                                            Err(err) => {
+                                                                                extension_message.set(Some(format!(
+                                                                                    "Failed to dismiss '{key_for_dismiss}': {err}",
+                                                                                )));
+                                                                            }
+                                                                        }
+                                                                    },
+                                                                    "Dismiss"
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    if !applied_keys.is_empty() {
+                                        div { class: "mt-3 rounded-lg border border-slate-200 bg-slate-50/80 p-2.5",
+                                            div { class: "mb-2 flex items-center justify-between",
+                                                h5 { class: "text-[10px] font-semibold uppercase tracking-wide text-slate-600", "Applied Extensions" }
+                                                span { class: "rounded bg-white px-1.5 py-0.5 text-[10px] text-slate-500", "{applied_keys.len()}" }
+                                            }
+                                            div { class: "flex flex-col gap-1.5",
+                                                for applied_key in applied_keys {
+                                                    {
+                                                        let key_for_revert = applied_key.clone();
+                                                        rsx! {
+                                                            div {
+                                                                key: "applied-{applied_key}",
+                                                                class: "flex items-center justify-between gap-2 rounded-md border border-slate-200 bg-white px-2 py-1.5",
+                                                                span { class: "font-mono text-[10px] text-slate-600", "{applied_key}" }
+                                                                button {
+                                                                    class: "h-5 rounded border border-rose-300 bg-rose-50 px-1.5 text-[9px] font-medium text-rose-700 transition-colors hover:bg-rose-100",
+                                                                    onclick: move |event| {
+                                                                        event.stop_propagation();
+                                                                        workflow_state.save_undo_point();
+                                                                        let result = workflow_state
+                                                                            .update(|wf| acceptance_log.write().revert(wf, &key_for_revert))
+                                                                            .unwrap_or_else(|| Err(WorkflowError::ReadOnly.to_string()));
+                                                                        let (kind, detail) = match result {
+                                                                            Ok(reverted) if reverted.retained_nodes.is_empty() => (
+                                                                                ExtensionTimelineEventKind::Reverted,
+                                                                                format!(
+                                                                                    "Reverted '{}', removed {} node(s).",
+                                                                                    reverted.key,
+                                                                                    reverted.removed_nodes.len()
+                                                                                ),
+                                                                            ),
+                                                                            Ok(reverted) => (
+                                                                                ExtensionTimelineEventKind::Reverted,
+                                                                                format!(
+                                                                                    "Reverted '{}', removed {} node(s); kept {} manually modified node(s).",
+                                                                                    reverted.key,
+                                                                                    reverted.removed_nodes.len(),
+                                                                                    reverted.retained_nodes.len()
+                                                                                ),
+                                                                            ),
+                                                                            Err(err) => (
+                                                                                ExtensionTimelineEventKind::Failed,
+                                                                                format!("Failed to revert '{key_for_revert}': {err}"),
+                                                                            ),
+                                                                        };
+                                                                        let history = extension_timeline.read().clone();
+                                                                        extension_timeline.set(push_timeline(
+                                                                            history,
+                                                                            kind,
+                                                                            detail.clone(),
+                                                                            None,
+                                                                        ));
+                                                                        extension_message.set(Some(detail));
+                                                                        preview_patches.set(Vec::new());
+                                                                    },
+                                                                    "Revert"
+                                                                }
                                                             }
                                                         }
                                                     }
@@ -706,7 +887,7 @@ pub fn SelectedNodePanel(
                                                                                         meta.snapshot_id,
                                                                                     ) {
                                                                                         workflow_state.save_undo_point();
-                                                                                        workflow.set(snapshot.workflow_before.clone());
+                                                                                        workflow_state.update(|wf| *wf = snapshot.workflow_before.clone());
                                                                                         let detail = format!(
                                                                                             "Rolled back to snapshot #{} from batch #{} ({} keys, {} node(s)).",
                                                                                             snapshot.snapshot_id,
@@ -746,24 +927,11 @@ pub fn SelectedNodePanel(
                         }
                     }
 
-                    div { class: "flex items-center gap-2 border-t border-slate-200 px-4 py-3",
+                    fieldset { class: "flex items-center gap-2 border-t border-slate-200 px-4 py-3", disabled: !can_edit,
                         button {
                             class: "flex h-8 flex-1 items-center justify-center gap-1.5 rounded-md border border-slate-300 text-[12px] text-slate-700 transition-colors hover:bg-slate-100",
                             onclick: move |_| {
-                                workflow_state.save_undo_point();
-
-                                let maybe_clone = workflow
-                                    .read()
-                                    .nodes
-                                    .iter()
-                                    .find(|node| node.id == node_id)
-                                    .cloned();
-                                if let Some(mut clone) = maybe_clone {
-                                    clone.id = NodeId::new();
-                                    clone.x += 40.0;
-                                    clone.y += 40.0;
-                                    let cloned_id = clone.id;
-                                    workflow.write().nodes.push(clone);
+                                if let Some(cloned_id) = workflow_state.duplicate_node(node_id) {
                                     selection.select_single(cloned_id);
                                 }
                             },
@@ -773,9 +941,9 @@ pub fn SelectedNodePanel(
                         button {
                             class: "flex h-8 flex-1 items-center justify-center gap-1.5 rounded-md border border-red-500/30 text-[12px] text-red-400 transition-colors hover:bg-red-500/10",
                             onclick: move |_| {
-                                workflow_state.save_undo_point();
-                                workflow.write().remove_node(node_id);
-                                selection.clear();
+                                if workflow_state.remove_nodes(&[node_id]).is_ok() {
+                                    selection.clear();
+                                }
                             },
                             crate::ui::icons::TrashIcon { class: "h-3.5 w-3.5" }
                             "Delete"
@@ -789,6 +957,48 @@ pub fn SelectedNodePanel(
     rsx! {}
 }
 
+const PRESET_REGISTRY_STORAGE_KEY: &str = "flow-wasm-v1-extension-presets";
+
+fn load_preset_registry() -> ExtensionPresetRegistry {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item(PRESET_REGISTRY_STORAGE_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_preset_registry(registry: &ExtensionPresetRegistry) {
+    let storage = web_sys::window().and_then(|w| w.local_storage().ok().flatten());
+    if let (Some(s), Ok(json)) = (storage, serde_json::to_string(registry)) {
+        let _ = s.set_item(PRESET_REGISTRY_STORAGE_KEY, &json);
+    }
+}
+
+const ACCEPTANCE_LOG_STORAGE_KEY: &str = "flow-wasm-v1-extension-acceptance";
+
+fn load_acceptance_log() -> ExtensionAcceptanceLog {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item(ACCEPTANCE_LOG_STORAGE_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_acceptance_log(log: &ExtensionAcceptanceLog) {
+    let storage = web_sys::window().and_then(|w| w.local_storage().ok().flatten());
+    if let (Some(s), Ok(json)) = (storage, serde_json::to_string(log)) {
+        let _ = s.set_item(ACCEPTANCE_LOG_STORAGE_KEY, &json);
+    }
+}
+
+fn preset_key_from_title(title: &str) -> String {
+    title
+        .to_ascii_lowercase()
+        .split(|ch: char| !ch.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .join("-")
+}
+
 fn collect_previews(workflow: &Workflow, keys: &[String]) -> Vec<ExtensionPatchPreview> {
     keys.iter()
         .unique()
@@ -845,6 +1055,7 @@ enum ExtensionTimelineEventKind {
     Undone,
     Redone,
     RolledBack,
+    Reverted,
 }
 
 #[derive(Clone)]
@@ -917,6 +1128,9 @@ fn event_appearance(
         ExtensionTimelineEventKind::RolledBack => {
             ("bg-cyan-500", "bg-cyan-100 text-cyan-700", "Rollback")
         }
+        ExtensionTimelineEventKind::Reverted => {
+            ("bg-rose-500", "bg-rose-100 text-rose-700", "Revert")
+        }
     }
 }
 
@@ -4,15 +4,70 @@
 #![warn(clippy::pedantic)]
 #![forbid(unsafe_code)]
 
+use crate::graph::compile::SeverityGate;
+use crate::graph::contract::{handler_name, handler_signature, OutputMapping};
+use crate::graph::{NodeId, PortName};
 use crate::hooks::use_ui_panels::UiPanels;
+use crate::hooks::use_workflow_state::WorkflowState;
+use crate::ui::canvas_settings::{CanvasSettings, GridStyle, MAX_GRID_SIZE, MIN_GRID_SIZE};
+use crate::ui::capabilities::Capability;
 use dioxus::prelude::*;
 
 #[component]
-pub fn SettingsOverlay(panels: UiPanels) -> Element {
+pub fn SettingsOverlay(
+    panels: UiPanels,
+    workflow_state: WorkflowState,
+    compile_gate: SeverityGate,
+    on_compile_gate_change: EventHandler<SeverityGate>,
+    canvas_settings: CanvasSettings,
+    on_canvas_settings_change: EventHandler<CanvasSettings>,
+) -> Element {
+    let workflow = workflow_state.workflow();
+    let can_edit = workflow_state.can(Capability::EditWorkflow);
+    let mut schema_error = use_signal(|| None::<String>);
+
     if !*panels.settings_open().read() {
         return rsx! {};
     }
 
+    let contract = workflow.read().contract.clone();
+    let schema_text = contract
+        .input_schema
+        .as_ref()
+        .and_then(|schema| serde_json::to_string_pretty(schema).ok())
+        .unwrap_or_default();
+    let output_node = contract
+        .output
+        .as_ref()
+        .map(|output| output.node_id.to_string())
+        .unwrap_or_default();
+    let output_port = contract
+        .output
+        .as_ref()
+        .map(|output| output.port.0.clone())
+        .unwrap_or_default();
+    let node_options: Vec<(String, String)> = workflow
+        .read()
+        .nodes
+        .iter()
+        .map(|node| (node.id.to_string(), node.name.clone()))
+        .collect();
+    let signature = handler_signature(
+        &workflow.read(),
+        &handler_name(&workflow_state.workflow_name().read()),
+    );
+
+    let grid_size = canvas_settings.grid_size;
+    let snap_to_grid = canvas_settings.snap_to_grid;
+    let grid_style = canvas_settings.grid_style.as_str();
+    let background = canvas_settings.background.clone();
+    let (on_grid_size, on_snap, on_grid_style, on_background) = (
+        canvas_settings.clone(),
+        canvas_settings.clone(),
+        canvas_settings.clone(),
+        canvas_settings,
+    );
+
     rsx! {
         div { class: "absolute right-4 top-14 z-40 w-[280px] rounded-lg border border-slate-700 bg-slate-900/95 p-3 shadow-2xl shadow-slate-950/70 backdrop-blur",
             div { class: "mb-2 flex items-center justify-between",
@@ -24,6 +79,153 @@ pub fn SettingsOverlay(panels: UiPanels) -> Element {
                 }
             }
             p { class: "mb-3 text-[11px] leading-relaxed text-slate-400", "Use Save to export the current workflow as JSON. Undo and Redo track recent graph edits." }
+            label { class: "mb-3 flex items-center justify-between gap-2 text-[11px] text-slate-300",
+                span { "Block run and export on" }
+                select {
+                    class: "h-7 rounded-md border border-slate-700 bg-slate-800 px-2 text-[11px] text-slate-100",
+                    value: compile_gate.as_str(),
+                    onchange: move |evt| {
+                        if let Ok(gate) = evt.value().parse::<SeverityGate>() {
+                            on_compile_gate_change.call(gate);
+                        }
+                    },
+                    option { value: SeverityGate::AllowWarnings.as_str(), "Errors only" }
+                    option { value: SeverityGate::DenyWarnings.as_str(), "Errors and warnings" }
+                }
+            }
+            div { class: "mb-3 flex flex-col gap-2 border-y border-slate-800 py-2",
+                span { class: "text-[11px] font-semibold text-slate-200", "Canvas" }
+                label { class: "flex items-center justify-between gap-2 text-[11px] text-slate-300",
+                    span { "Grid size" }
+                    input {
+                        r#type: "number",
+                        min: "{MIN_GRID_SIZE}",
+                        max: "{MAX_GRID_SIZE}",
+                        class: "h-7 w-[72px] rounded-md border border-slate-700 bg-slate-800 px-2 text-[11px] text-slate-100",
+                        value: "{grid_size}",
+                        onchange: move |evt| {
+                            if let Ok(size) = evt.value().parse::<f32>() {
+                                on_canvas_settings_change.call(CanvasSettings {
+                                    grid_size: size,
+                                    ..on_grid_size.clone()
+                                });
+                            }
+                        }
+                    }
+                }
+                label { class: "flex items-center justify-between gap-2 text-[11px] text-slate-300",
+                    span { "Snap to grid" }
+                    input {
+                        r#type: "checkbox",
+                        checked: snap_to_grid,
+                        onchange: move |evt| {
+                            on_canvas_settings_change.call(CanvasSettings {
+                                snap_to_grid: evt.checked(),
+                                ..on_snap.clone()
+                            });
+                        }
+                    }
+                }
+                label { class: "flex items-center justify-between gap-2 text-[11px] text-slate-300",
+                    span { "Grid style" }
+                    select {
+                        class: "h-7 rounded-md border border-slate-700 bg-slate-800 px-2 text-[11px] text-slate-100",
+                        value: grid_style,
+                        onchange: move |evt| {
+                            if let Some(style) = GridStyle::parse(&evt.value()) {
+                                on_canvas_settings_change.call(CanvasSettings {
+                                    grid_style: style,
+                                    ..on_grid_style.clone()
+                                });
+                            }
+                        },
+                        option { value: GridStyle::Dots.as_str(), "Dots" }
+                        option { value: GridStyle::Lines.as_str(), "Lines" }
+                    }
+                }
+                label { class: "flex items-center justify-between gap-2 text-[11px] text-slate-300",
+                    span { "Background" }
+                    input {
+                        r#type: "color",
+                        class: "h-7 w-[72px] rounded-md border border-slate-700 bg-slate-800",
+                        value: "{background}",
+                        oninput: move |evt| {
+                            on_canvas_settings_change.call(CanvasSettings {
+                                background: evt.value(),
+                                ..on_background.clone()
+                            });
+                        }
+                    }
+                }
+            }
+            div { class: "mb-3 flex flex-col gap-1.5",
+                span { class: "text-[11px] text-slate-300", "Input schema (JSON Schema)" }
+                textarea {
+                    rows: "4",
+                    placeholder: "{{ \"type\": \"object\" }}",
+                    class: "resize-none rounded-md border border-slate-700 bg-slate-800 px-2 py-1.5 font-mono text-[10px] text-slate-100 outline-none",
+                    value: "{schema_text}",
+                    disabled: !can_edit,
+                    onchange: move |evt| {
+                        let text = evt.value();
+                        if text.trim().is_empty() {
+                            workflow_state.update(|wf| wf.contract.input_schema = None);
+                            schema_error.set(None);
+                            return;
+                        }
+                        match serde_json::from_str(&text) {
+                            Ok(schema) => {
+                                workflow_state.update(|wf| wf.contract.input_schema = Some(schema));
+                                schema_error.set(None);
+                            }
+                            Err(error) => schema_error.set(Some(error.to_string())),
+                        }
+                    }
+                }
+                if let Some(error) = schema_error.read().clone() {
+                    p { class: "text-[10px] text-rose-400", "{error}" }
+                }
+            }
+            label { class: "mb-2 flex items-center justify-between gap-2 text-[11px] text-slate-300",
+                span { "Output node" }
+                select {
+                    class: "h-7 max-w-[150px] rounded-md border border-slate-700 bg-slate-800 px-2 text-[11px] text-slate-100",
+                    value: "{output_node}",
+                    disabled: !can_edit,
+                    onchange: move |evt| {
+                        let output = evt
+                            .value()
+                            .parse::<uuid::Uuid>()
+                            .ok()
+                            .map(|id| OutputMapping::new(NodeId(id)));
+                        workflow_state.update(|wf| wf.contract.output = output);
+                    },
+                    option { value: "", "None" }
+                    for (id, name) in node_options {
+                        option { key: "{id}", value: "{id}", "{name}" }
+                    }
+                }
+            }
+            if contract.output.is_some() {
+                label { class: "mb-3 flex items-center justify-between gap-2 text-[11px] text-slate-300",
+                    span { "Output port" }
+                    input {
+                        class: "h-7 w-[150px] rounded-md border border-slate-700 bg-slate-800 px-2 text-[11px] text-slate-100",
+                        value: "{output_port}",
+                        disabled: !can_edit,
+                        oninput: move |evt| {
+                            if let Ok(port) = PortName::new(evt.value()) {
+                                workflow_state.update(|wf| {
+                                    if let Some(output) = wf.contract.output.as_mut() {
+                                        output.port = port;
+                                    }
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            pre { class: "mb-3 whitespace-pre-wrap break-all rounded-md border border-slate-700 bg-slate-950 px-2 py-1.5 font-mono text-[10px] text-cyan-200", "{signature}" }
             div { class: "flex items-center gap-2",
                 button {
                     class: "flex h-8 flex-1 items-center justify-center rounded-md border border-slate-700 text-[12px] text-slate-300 transition-colors hover:bg-slate-800 hover:text-slate-100",
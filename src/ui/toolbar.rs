@@ -1,6 +1,8 @@
+use crate::graph::compile::{CompileReport, CompileStatus};
+use crate::ui::capabilities::{Capabilities, Capability};
 use crate::ui::icons::{
-    LayersIcon, MaximizeIcon, PlayIcon, RedoIcon, SaveIcon, SettingsIcon, UndoIcon, UploadIcon,
-    ZoomInIcon, ZoomOutIcon,
+    CopyIcon, LayersIcon, MaximizeIcon, PlayIcon, RedoIcon, SaveIcon, SettingsIcon, UndoIcon,
+    UploadIcon, XIcon, ZoomInIcon, ZoomOutIcon,
 };
 use dioxus::prelude::*;
 
@@ -53,10 +55,29 @@ pub fn FlowToolbar(
     on_redo: EventHandler<MouseEvent>,
     on_save: EventHandler<MouseEvent>,
     on_import: EventHandler<MouseEvent>,
+    on_compare: EventHandler<MouseEvent>,
     on_settings: EventHandler<MouseEvent>,
     can_undo: ReadSignal<bool>,
     can_redo: ReadSignal<bool>,
+    compile_report: ReadSignal<CompileReport>,
+    on_compile_status: EventHandler<MouseEvent>,
+    cached_count: ReadSignal<usize>,
+    on_clear_cache: EventHandler<MouseEvent>,
+    can_profile: ReadSignal<bool>,
+    profile_mode: ReadSignal<bool>,
+    on_toggle_profile: EventHandler<MouseEvent>,
+    capabilities: ReadSignal<Capabilities>,
+    unsaved: ReadSignal<bool>,
 ) -> Element {
+    let (compile_label, compile_classes, compile_dot) = compile_indicator(&compile_report.read());
+    let can_edit = capabilities.read().can(Capability::EditWorkflow);
+    let can_run = capabilities.read().can(Capability::RunWorkflow);
+    let edit_state = if can_edit {
+        ButtonState::Enabled
+    } else {
+        ButtonState::Disabled
+    };
+
     rsx! {
         header {
             role: "toolbar",
@@ -73,6 +94,7 @@ pub fn FlowToolbar(
                         value: "{workflow_name.read()}",
                         class: "h-8 w-auto min-w-[120px] max-w-[180px] border-none bg-transparent text-[14px] font-semibold text-slate-900 outline-none md:max-w-[320px] md:text-[15px]",
                         spellcheck: false,
+                        readonly: !can_edit,
                         oninput: move |evt| on_workflow_name_change.call(evt.value())
                     }
                 }
@@ -80,7 +102,14 @@ pub fn FlowToolbar(
                     span { class: "rounded-full border border-cyan-200 bg-cyan-50 px-2 py-0.5 text-cyan-700", "Workflow" }
                     span { class: "rounded-full border border-slate-200 bg-white px-2 py-0.5 font-mono", "{node_count.read()} nodes" }
                     span { class: "rounded-full border border-slate-200 bg-white px-2 py-0.5 font-mono", "{edge_count.read()} links" }
-                    span { class: "hidden rounded-full border border-amber-200 bg-amber-50 px-2 py-0.5 text-amber-700 md:inline-flex", "K to add node" }
+                    if can_edit {
+                        span { class: "hidden rounded-full border border-amber-200 bg-amber-50 px-2 py-0.5 text-amber-700 md:inline-flex", "K to add node" }
+                    } else {
+                        span { class: "rounded-full border border-slate-300 bg-slate-100 px-2 py-0.5 text-slate-600", "View only" }
+                    }
+                    if *unsaved.read() {
+                        span { class: "rounded-full border border-amber-200 bg-amber-50 px-2 py-0.5 text-amber-700", title: "Changes are saved to this browser once editing pauses", "Unsaved" }
+                    }
                 }
             }
 
@@ -108,7 +137,7 @@ pub fn FlowToolbar(
                 div { class: "mx-1 h-5 w-px bg-slate-300" }
                 ToolbarButton {
                     label: "Auto Layout",
-                    state: ButtonState::Enabled,
+                    state: edit_state,
                     on_click: move |evt| on_layout.call(evt),
                     LayersIcon { class: "h-4 w-4" }
                 }
@@ -124,7 +153,7 @@ pub fn FlowToolbar(
                 }
                 ToolbarButton {
                     label: "Auto Layout",
-                    state: ButtonState::Enabled,
+                    state: edit_state,
                     on_click: move |evt| on_layout.call(evt),
                     LayersIcon { class: "h-4 w-4" }
                 }
@@ -133,23 +162,38 @@ pub fn FlowToolbar(
             div { class: "flex items-center gap-0.5 md:gap-1",
                 ToolbarButton {
                     label: "Undo",
-                    state: if *can_undo.read() { ButtonState::Enabled } else { ButtonState::Disabled },
+                    state: if can_edit && *can_undo.read() { ButtonState::Enabled } else { ButtonState::Disabled },
                     on_click: move |evt| on_undo.call(evt),
                     UndoIcon { class: "h-4 w-4" }
                 }
                 ToolbarButton {
                     label: "Redo",
-                    state: if *can_redo.read() { ButtonState::Enabled } else { ButtonState::Disabled },
+                    state: if can_edit && *can_redo.read() { ButtonState::Enabled } else { ButtonState::Disabled },
                     on_click: move |evt| on_redo.call(evt),
                     RedoIcon { class: "h-4 w-4" }
                 }
                 div { class: "mx-1 h-5 w-px bg-slate-300" }
+                button {
+                    class: "flex h-7 items-center gap-1.5 rounded-full border px-2 text-[11px] font-medium transition-colors {compile_classes}",
+                    r#type: "button",
+                    aria_label: "Compile status",
+                    title: "Show compile diagnostics",
+                    onclick: move |evt| on_compile_status.call(evt),
+                    span { class: "h-1.5 w-1.5 rounded-full {compile_dot}" }
+                    "{compile_label}"
+                }
                 ToolbarButton {
                     label: "Import Workflow",
-                    state: ButtonState::Enabled,
+                    state: edit_state,
                     on_click: move |evt| on_import.call(evt),
                     UploadIcon { class: "h-4 w-4" }
                 }
+                ToolbarButton {
+                    label: "Compare Workflow",
+                    state: ButtonState::Enabled,
+                    on_click: move |evt| on_compare.call(evt),
+                    CopyIcon { class: "h-4 w-4" }
+                }
                 ToolbarButton {
                     label: "Save Workflow",
                     state: ButtonState::Enabled,
@@ -162,9 +206,36 @@ pub fn FlowToolbar(
                     on_click: move |evt| on_settings.call(evt),
                     SettingsIcon { class: "h-4 w-4" }
                 }
+                if can_run && *cached_count.read() > 0 {
+                    button {
+                        class: "ml-1 flex h-7 items-center gap-1 rounded-full border border-slate-200 bg-white px-2 text-[11px] font-medium text-slate-600 transition-colors hover:bg-slate-100",
+                        r#type: "button",
+                        aria_label: "Clear cached node outputs",
+                        title: "Clear cached outputs so every node runs again",
+                        onclick: move |evt| on_clear_cache.call(evt),
+                        "{cached_count.read()} cached"
+                        XIcon { class: "h-3 w-3" }
+                    }
+                }
+                if *can_profile.read() {
+                    button {
+                        class: if *profile_mode.read() {
+                            "ml-1 flex h-7 items-center gap-1 rounded-full border border-orange-300 bg-orange-50 px-2 text-[11px] font-medium text-orange-700 transition-colors hover:bg-orange-100"
+                        } else {
+                            "ml-1 flex h-7 items-center gap-1 rounded-full border border-slate-200 bg-white px-2 text-[11px] font-medium text-slate-600 transition-colors hover:bg-slate-100"
+                        },
+                        r#type: "button",
+                        aria_label: "Toggle timing profile",
+                        aria_pressed: "{profile_mode.read()}",
+                        title: "Color nodes by duration and highlight the critical path",
+                        onclick: move |evt| on_toggle_profile.call(evt),
+                        "Profile"
+                    }
+                }
                 button {
-                    class: "ml-1 flex h-9 items-center gap-1.5 rounded-lg bg-gradient-to-r from-cyan-600 to-teal-600 px-3 text-[12px] font-semibold text-white transition-all duration-150 hover:-translate-y-px hover:from-cyan-500 hover:to-teal-500 hover:shadow-lg hover:shadow-cyan-500/30",
+                    class: "ml-1 flex h-9 items-center gap-1.5 rounded-lg bg-gradient-to-r from-cyan-600 to-teal-600 px-3 text-[12px] font-semibold text-white transition-all duration-150 hover:-translate-y-px hover:from-cyan-500 hover:to-teal-500 hover:shadow-lg hover:shadow-cyan-500/30 disabled:pointer-events-none disabled:opacity-40",
                     r#type: "button",
+                    disabled: !can_run,
                     aria_label: "Execute workflow",
                     title: "Run this workflow",
                     onclick: move |evt| on_execute.call(evt),
@@ -175,3 +246,23 @@ pub fn FlowToolbar(
         }
     }
 }
+
+fn compile_indicator(report: &CompileReport) -> (String, &'static str, &'static str) {
+    match report.status() {
+        CompileStatus::Clean => (
+            "Compiled".to_string(),
+            "border-emerald-200 bg-emerald-50 text-emerald-700 hover:bg-emerald-100",
+            "bg-emerald-500",
+        ),
+        CompileStatus::Warnings => (
+            format!("{} warn", report.warning_count()),
+            "border-amber-200 bg-amber-50 text-amber-700 hover:bg-amber-100",
+            "bg-amber-500",
+        ),
+        CompileStatus::Blocked => (
+            format!("Blocked ({})", report.blocking().count()),
+            "border-red-200 bg-red-50 text-red-700 hover:bg-red-100",
+            "bg-red-500",
+        ),
+    }
+}
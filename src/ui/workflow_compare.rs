@@ -0,0 +1,414 @@
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::panic)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::nursery)]
+#![forbid(unsafe_code)]
+
+use crate::graph::diff::{diff, DiffStatus, WorkflowDiff};
+use crate::graph::{Connection, Node, NodeId, Workflow};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+use crate::ui::constants::{NODE_HEIGHT, NODE_WIDTH};
+
+// ── Constants ─────────────────────────────────────────────────────────────────
+
+/// Zoom applied when the comparison opens.
+const INITIAL_ZOOM: f32 = 0.6;
+const MIN_ZOOM: f32 = 0.15;
+const MAX_ZOOM: f32 = 2.0;
+/// Screen-space margin between the pane edge and the leftmost/topmost node.
+const PANE_PAD: f32 = 32.0;
+
+// ── Data ──────────────────────────────────────────────────────────────────────
+
+/// Which document a pane renders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Base,
+    Other,
+}
+
+/// Fill / stroke pair for a comparison node rectangle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct StatusColors {
+    fill: &'static str,
+    stroke: &'static str,
+}
+
+// ── Calculations ──────────────────────────────────────────────────────────────
+
+const fn status_colors(status: DiffStatus) -> StatusColors {
+    match status {
+        DiffStatus::Unchanged => StatusColors {
+            fill: "rgba(100,116,139,0.18)",
+            stroke: "rgba(148,163,184,0.55)",
+        },
+        DiffStatus::Added => StatusColors {
+            fill: "rgba(74,222,128,0.22)",
+            stroke: "rgba(74,222,128,0.90)",
+        },
+        DiffStatus::Removed => StatusColors {
+            fill: "rgba(251,113,133,0.22)",
+            stroke: "rgba(251,113,133,0.90)",
+        },
+        DiffStatus::Modified => StatusColors {
+            fill: "rgba(251,191,36,0.22)",
+            stroke: "rgba(251,191,36,0.90)",
+        },
+        DiffStatus::Moved => StatusColors {
+            fill: "rgba(96,165,250,0.18)",
+            stroke: "rgba(96,165,250,0.85)",
+        },
+    }
+}
+
+/// Pan that puts the top-left of both documents just inside the pane, so the
+/// two panes start aligned on the same scene origin.
+fn initial_pan(base: &[Node], other: &[Node], zoom: f32) -> (f32, f32) {
+    let (min_x, min_y) = base
+        .iter()
+        .chain(other)
+        .fold(None::<(f32, f32)>, |acc, node| {
+            Some(acc.map_or((node.x, node.y), |(x, y)| (x.min(node.x), y.min(node.y))))
+        })
+        .unwrap_or((0.0, 0.0));
+    (
+        min_x.mul_add(-zoom, PANE_PAD),
+        min_y.mul_add(-zoom, PANE_PAD),
+    )
+}
+
+const fn clamp_zoom(zoom: f32) -> f32 {
+    if zoom.is_finite() {
+        zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+    } else {
+        INITIAL_ZOOM
+    }
+}
+
+/// One line per change, in the order added, removed, modified, moved,
+/// connections.
+fn summary_rows(
+    base: &Workflow,
+    other: &Workflow,
+    changes: &WorkflowDiff,
+) -> Vec<(DiffStatus, String)> {
+    let names = base
+        .nodes
+        .iter()
+        .chain(&other.nodes)
+        .map(|node| (node.id, node.name.as_str()))
+        .collect::<HashMap<NodeId, &str>>();
+    let name = |id: &NodeId| names.get(id).copied().unwrap_or("unknown").to_string();
+    let edge = |connection: &Connection| {
+        format!(
+            "{} → {}",
+            name(&connection.source),
+            name(&connection.target)
+        )
+    };
+
+    changes
+        .added_nodes
+        .iter()
+        .map(|id| (DiffStatus::Added, format!("Node '{}'", name(id))))
+        .chain(
+            changes
+                .removed_nodes
+                .iter()
+                .map(|id| (DiffStatus::Removed, format!("Node '{}'", name(id)))),
+        )
+        .chain(
+            changes
+                .modified_nodes
+                .iter()
+                .map(|id| (DiffStatus::Modified, format!("Node '{}'", name(id)))),
+        )
+        .chain(
+            changes
+                .moved_nodes
+                .iter()
+                .map(|id| (DiffStatus::Moved, format!("Node '{}'", name(id)))),
+        )
+        .chain(
+            changes
+                .added_connections
+                .iter()
+                .map(|connection| (DiffStatus::Added, edge(connection))),
+        )
+        .chain(
+            changes
+                .removed_connections
+                .iter()
+                .map(|connection| (DiffStatus::Removed, edge(connection))),
+        )
+        .collect()
+}
+
+const fn status_label(status: DiffStatus) -> &'static str {
+    match status {
+        DiffStatus::Unchanged => "same",
+        DiffStatus::Added => "added",
+        DiffStatus::Removed => "removed",
+        DiffStatus::Modified => "changed",
+        DiffStatus::Moved => "moved",
+    }
+}
+
+// ── Components ────────────────────────────────────────────────────────────────
+
+#[component]
+fn ComparePane(
+    side: Side,
+    title: String,
+    workflow: Workflow,
+    changes: WorkflowDiff,
+    pan: (f32, f32),
+    zoom: f32,
+) -> Element {
+    let node_map: HashMap<NodeId, &Node> =
+        workflow.nodes.iter().map(|node| (node.id, node)).collect();
+    let transform = format!("translate({} {}) scale({zoom})", pan.0, pan.1);
+    let accent = match side {
+        Side::Base => "text-rose-300",
+        Side::Other => "text-emerald-300",
+    };
+
+    rsx! {
+        div { class: "relative flex-1 overflow-hidden rounded-lg border border-slate-700/80 bg-slate-950/80",
+            div { class: "absolute left-2 top-2 z-10 rounded-md border border-slate-700 bg-slate-900/90 px-2 py-1 text-[10px] font-semibold uppercase tracking-wide {accent}",
+                "{title}"
+            }
+            svg {
+                class: "h-full w-full",
+                xmlns: "http://www.w3.org/2000/svg",
+                g { transform: "{transform}",
+                    for connection in workflow.connections.clone() {
+                        {
+                            let colors = status_colors(changes.connection_status(&connection));
+                            match (node_map.get(&connection.source), node_map.get(&connection.target)) {
+                                (Some(src), Some(tgt)) => rsx! {
+                                    line {
+                                        key: "e-{connection.id}",
+                                        x1: "{src.x + NODE_WIDTH / 2.0}",
+                                        y1: "{src.y + NODE_HEIGHT}",
+                                        x2: "{tgt.x + NODE_WIDTH / 2.0}",
+                                        y2: "{tgt.y}",
+                                        stroke: "{colors.stroke}",
+                                        stroke_width: "3",
+                                    }
+                                },
+                                _ => rsx! {},
+                            }
+                        }
+                    }
+                    for node in workflow.nodes.clone() {
+                        {
+                            let colors = status_colors(changes.node_status(node.id));
+                            rsx! {
+                                g { key: "n-{node.id}",
+                                    rect {
+                                        x: "{node.x}",
+                                        y: "{node.y}",
+                                        width: "{NODE_WIDTH}",
+                                        height: "{NODE_HEIGHT}",
+                                        rx: "8",
+                                        fill: "{colors.fill}",
+                                        stroke: "{colors.stroke}",
+                                        stroke_width: "2",
+                                    }
+                                    text {
+                                        x: "{node.x + 12.0}",
+                                        y: "{node.y + NODE_HEIGHT / 2.0 + 5.0}",
+                                        fill: "rgba(226,232,240,0.95)",
+                                        font_size: "15",
+                                        "{node.name}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Side-by-side structural comparison of two workflow documents.
+///
+/// Both panes share one pan/zoom state, so dragging or zooming either pane
+/// keeps the two documents aligned.
+#[component]
+pub fn WorkflowCompareOverlay(
+    base: Workflow,
+    other: Workflow,
+    base_label: String,
+    other_label: String,
+    on_close: EventHandler<MouseEvent>,
+) -> Element {
+    let base_nodes = base.nodes.clone();
+    let other_nodes = other.nodes.clone();
+    let mut zoom = use_signal(|| INITIAL_ZOOM);
+    let mut pan = use_signal(move || initial_pan(&base_nodes, &other_nodes, INITIAL_ZOOM));
+    let mut drag_from = use_signal(|| None::<(f64, f64)>);
+
+    let changes = diff(&base, &other);
+    let rows = summary_rows(&base, &other, &changes);
+    let change_count = changes.change_count();
+    let current_pan = *pan.read();
+    let current_zoom = *zoom.read();
+
+    rsx! {
+        div { class: "fixed inset-0 z-50 flex flex-col gap-3 bg-slate-950/90 p-4 backdrop-blur-sm",
+            div { class: "flex items-center justify-between",
+                div { class: "flex items-center gap-2",
+                    h2 { class: "text-sm font-semibold text-slate-100", "Compare workflows" }
+                    span { class: "rounded border border-slate-700 px-1.5 py-px text-[10px] text-slate-300",
+                        "{change_count} changes"
+                    }
+                }
+                button {
+                    r#type: "button",
+                    class: "rounded-md border border-slate-700 bg-slate-900 px-3 py-1 text-xs text-slate-200 transition-colors hover:bg-slate-800",
+                    onclick: move |evt| on_close.call(evt),
+                    "Close"
+                }
+            }
+            div { class: "flex min-h-0 flex-1 gap-3",
+                div {
+                    class: "flex min-w-0 flex-1 cursor-grab gap-3 active:cursor-grabbing",
+                    onmousedown: move |evt| {
+                        let point = evt.client_coordinates();
+                        drag_from.set(Some((point.x, point.y)));
+                    },
+                    onmousemove: move |evt| {
+                        let Some((from_x, from_y)) = *drag_from.read() else {
+                            return;
+                        };
+                        let point = evt.client_coordinates();
+                        #[allow(clippy::cast_possible_truncation)]
+                        let (dx, dy) = ((point.x - from_x) as f32, (point.y - from_y) as f32);
+                        let (x, y) = *pan.read();
+                        pan.set((x + dx, y + dy));
+                        drag_from.set(Some((point.x, point.y)));
+                    },
+                    onmouseup: move |_| drag_from.set(None),
+                    onmouseleave: move |_| drag_from.set(None),
+                    onwheel: move |evt| {
+                        #[allow(clippy::cast_possible_truncation)]
+                        let delta = -evt.delta().strip_units().y as f32 * 0.001;
+                        let next = clamp_zoom(*zoom.read() * (1.0 + delta));
+                        zoom.set(next);
+                    },
+                    ComparePane {
+                        side: Side::Base,
+                        title: base_label,
+                        workflow: base,
+                        changes: changes.clone(),
+                        pan: current_pan,
+                        zoom: current_zoom,
+                    }
+                    ComparePane {
+                        side: Side::Other,
+                        title: other_label,
+                        workflow: other,
+                        changes,
+                        pan: current_pan,
+                        zoom: current_zoom,
+                    }
+                }
+                aside { class: "w-64 shrink-0 overflow-y-auto rounded-lg border border-slate-700/80 bg-slate-900/80 p-3",
+                    h3 { class: "mb-2 text-[11px] font-semibold uppercase tracking-wide text-slate-400", "Differences" }
+                    if rows.is_empty() {
+                        p { class: "text-xs text-slate-400", "The documents are structurally identical." }
+                    }
+                    for (index, (status, text)) in rows.into_iter().enumerate() {
+                        {
+                            let colors = status_colors(status);
+                            let label = status_label(status);
+                            rsx! {
+                                div { key: "{index}", class: "mb-1 flex items-center gap-2 text-xs text-slate-200",
+                                    span {
+                                        class: "rounded px-1.5 py-px text-[9px] font-semibold uppercase",
+                                        style: "background: {colors.fill}; color: {colors.stroke};",
+                                        "{label}"
+                                    }
+                                    span { class: "truncate", "{text}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+#[cfg(test)]
+#[allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+mod tests {
+    use super::*;
+    use crate::graph::PortName;
+
+    #[test]
+    fn given_two_documents_when_computing_initial_pan_then_shared_origin_is_padded() {
+        let mut base = Workflow::new();
+        base.add_node("run", 100.0, 300.0);
+        let mut other = Workflow::new();
+        other.add_node("run", 400.0, 50.0);
+
+        let pan = initial_pan(&base.nodes, &other.nodes, 0.5);
+
+        assert_eq!(pan, (PANE_PAD - 50.0, PANE_PAD - 25.0));
+    }
+
+    #[test]
+    fn given_out_of_range_zoom_when_clamping_then_limits_apply() {
+        assert_eq!(clamp_zoom(10.0), MAX_ZOOM);
+        assert_eq!(clamp_zoom(0.0), MIN_ZOOM);
+        assert_eq!(clamp_zoom(f32::NAN), INITIAL_ZOOM);
+    }
+
+    #[test]
+    fn given_changed_copy_when_summarizing_then_rows_name_each_change() {
+        let mut base = Workflow::new();
+        let entry = base.add_node("http-handler", 0.0, 0.0);
+        let mut other = base.clone();
+        let run = other.add_node("run", 200.0, 0.0);
+        let main = PortName::from("main");
+        let _ = other.add_connection_checked(entry, run, &main, &main);
+        let changes = diff(&base, &other);
+
+        let rows = summary_rows(&base, &other, &changes);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, DiffStatus::Added);
+        assert!(rows[0].1.contains("Node"));
+        assert!(rows[1].1.contains('→'));
+    }
+
+    #[test]
+    fn given_statuses_when_getting_colors_then_each_is_distinct() {
+        let strokes = [
+            DiffStatus::Unchanged,
+            DiffStatus::Added,
+            DiffStatus::Removed,
+            DiffStatus::Modified,
+            DiffStatus::Moved,
+        ]
+        .map(|status| status_colors(status).stroke);
+
+        assert!(strokes
+            .iter()
+            .enumerate()
+            .all(|(index, stroke)| !strokes[index + 1..].contains(stroke)));
+    }
+}
@@ -0,0 +1,100 @@
+//! The workspace manifest: what a workspace has registered and how it
+//! customizes presets and archetypes.
+
+use super::WizardError;
+use crate::flow_extender::archetype::ArchetypeManifest;
+use crate::flow_extender::twin::TwinUniverse;
+use crate::flow_extender::ExtensionPresetRegistry;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE: &str = "oya-workspace.json";
+
+/// Files that make up one registered feature, relative to the workspace root.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeatureEntry {
+    pub id: String,
+    pub spec: PathBuf,
+    pub workflow: PathBuf,
+    #[serde(default)]
+    pub scenarios: Vec<PathBuf>,
+    /// Twin universe the feature's scenarios run in.
+    pub universe: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkspaceManifest {
+    #[serde(default)]
+    pub presets: ExtensionPresetRegistry,
+    #[serde(default)]
+    pub archetypes: ArchetypeManifest,
+    #[serde(default)]
+    pub features: Vec<FeatureEntry>,
+    #[serde(default)]
+    pub universes: Vec<TwinUniverse>,
+}
+
+impl WorkspaceManifest {
+    /// Load the manifest from `root`, or an empty one if it does not exist.
+    ///
+    /// # Errors
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(root: &Path) -> Result<Self, WizardError> {
+        let path = root.join(MANIFEST_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|source| WizardError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        serde_json::from_str(&content).map_err(|source| WizardError::Manifest { path, source })
+    }
+
+    /// Write the manifest to `root`.
+    ///
+    /// # Errors
+    /// Returns an error if the manifest cannot be serialized or written.
+    pub fn save(&self, root: &Path) -> Result<(), WizardError> {
+        let path = root.join(MANIFEST_FILE);
+        let content =
+            serde_json::to_string_pretty(self).map_err(|source| WizardError::Manifest {
+                path: path.clone(),
+                source,
+            })?;
+        fs::write(&path, content).map_err(|source| WizardError::Io { path, source })
+    }
+
+    #[must_use]
+    pub fn feature(&self, id: &str) -> Option<&FeatureEntry> {
+        self.features.iter().find(|feature| feature.id == id)
+    }
+
+    #[must_use]
+    pub fn universe(&self, name: &str) -> Option<&TwinUniverse> {
+        self.universes.iter().find(|universe| universe.name == name)
+    }
+
+    /// Register a feature and its universe.
+    ///
+    /// # Errors
+    /// Returns an error if the feature id or universe name is already taken.
+    pub fn register(
+        &mut self,
+        entry: FeatureEntry,
+        universe: Option<TwinUniverse>,
+    ) -> Result<(), WizardError> {
+        if self.feature(&entry.id).is_some() {
+            return Err(WizardError::AlreadyRegistered(entry.id));
+        }
+        if let Some(universe) = universe {
+            if self.universe(&universe.name).is_some() {
+                return Err(WizardError::AlreadyRegistered(universe.name));
+            }
+            self.universes.push(universe);
+        }
+        self.features.push(entry);
+        Ok(())
+    }
+}
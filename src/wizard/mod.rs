@@ -0,0 +1,594 @@
+//! Guided "new feature" flow.
+//!
+//! One draft drives every subsystem: the spec skeleton, a workflow
+//! scaffolded from the spec's archetype, one scenario stub per behavior, a
+//! twin universe for dependencies that have twins, and an entry in the
+//! workspace manifest tying the files together.
+
+mod manifest;
+
+pub use manifest::{FeatureEntry, WorkspaceManifest, MANIFEST_FILE};
+
+use crate::flow_extender::archetype::SpecArchetype;
+use crate::flow_extender::twin::{
+    TwinDefinition, TwinUniverse, DEFAULT_TWIN_BASE_URL, DEFAULT_UNIVERSE,
+};
+use crate::flow_extender::ExtensionPreset;
+use crate::graph::Workflow;
+use crate::linter::{
+    AcceptanceCriterion, Behavior, Spec, SpecContext, SpecIdentity, SpecIntent, Specification,
+    SystemDependency,
+};
+use crate::scenario_runner::{
+    Assertion, Scenario, ScenarioIdentity, ScenarioSetup, ScenarioStep, ScenarioTeardown,
+    StepAction,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const SKELETON_VERSION: &str = "0.1.0";
+const SKELETON_STATUS: &str = "draft";
+
+#[derive(Debug, Error)]
+pub enum WizardError {
+    #[error("Invalid feature draft: {0}")]
+    InvalidDraft(String),
+    #[error("Failed to scaffold workflow: {0}")]
+    Scaffold(String),
+    #[error("'{0}' is already registered in the workspace")]
+    AlreadyRegistered(String),
+    #[error("Refusing to overwrite existing file at {0}")]
+    FileExists(PathBuf),
+    #[error("I/O error at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Invalid workspace manifest at {path}: {source}")]
+    Manifest {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("Failed to serialize {path}: {detail}")]
+    Serialize { path: PathBuf, detail: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BehaviorDraft {
+    pub id: String,
+    pub description: String,
+}
+
+/// Answers collected by the wizard.
+#[derive(Debug, Clone)]
+pub struct FeatureDraft {
+    pub id: String,
+    pub author: String,
+    pub problem_statement: String,
+    pub archetype: SpecArchetype,
+    pub behaviors: Vec<BehaviorDraft>,
+    pub dependencies: Vec<SystemDependency>,
+}
+
+impl FeatureDraft {
+    /// Check ids before anything is generated.
+    ///
+    /// # Errors
+    /// Returns an error if an id is not kebab-case, the problem statement is
+    /// empty, there are no behaviors, or a behavior id repeats.
+    pub fn validate(&self) -> Result<(), WizardError> {
+        if !is_kebab_case(&self.id) {
+            return Err(WizardError::InvalidDraft(format!(
+                "feature id '{}' must be lowercase kebab-case",
+                self.id
+            )));
+        }
+        if self.problem_statement.trim().is_empty() {
+            return Err(WizardError::InvalidDraft(
+                "problem statement is required".to_string(),
+            ));
+        }
+        if self.behaviors.is_empty() {
+            return Err(WizardError::InvalidDraft(
+                "at least one behavior is required".to_string(),
+            ));
+        }
+
+        let mut seen = HashSet::new();
+        for behavior in &self.behaviors {
+            if !is_kebab_case(&behavior.id) {
+                return Err(WizardError::InvalidDraft(format!(
+                    "behavior id '{}' must be lowercase kebab-case",
+                    behavior.id
+                )));
+            }
+            if !seen.insert(behavior.id.as_str()) {
+                return Err(WizardError::InvalidDraft(format!(
+                    "duplicate behavior id '{}'",
+                    behavior.id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_kebab_case(value: &str) -> bool {
+    !value.is_empty()
+        && !value.starts_with('-')
+        && !value.ends_with('-')
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+}
+
+/// Everything generated for one feature, before it is written to disk.
+#[derive(Debug, Clone)]
+pub struct FeatureBundle {
+    pub spec: Spec,
+    pub workflow: Workflow,
+    pub recommended_presets: Vec<ExtensionPreset>,
+    pub scenarios: Vec<Scenario>,
+    /// `None` when no declared dependency has a twin.
+    pub universe: Option<TwinUniverse>,
+    /// Dependencies without a twin; scenarios cannot isolate them.
+    pub untwinned: Vec<String>,
+}
+
+pub struct FeatureWizard {
+    draft: FeatureDraft,
+    twin_base_url: String,
+}
+
+impl FeatureWizard {
+    /// # Errors
+    /// Returns an error if the draft is invalid.
+    pub fn new(draft: FeatureDraft) -> Result<Self, WizardError> {
+        draft.validate()?;
+        Ok(Self {
+            draft,
+            twin_base_url: DEFAULT_TWIN_BASE_URL.to_string(),
+        })
+    }
+
+    #[must_use]
+    pub fn with_twin_base_url(mut self, base_url: &str) -> Self {
+        self.twin_base_url = base_url.to_string();
+        self
+    }
+
+    #[must_use]
+    pub const fn draft(&self) -> &FeatureDraft {
+        &self.draft
+    }
+
+    /// Step 1: a draft spec with one acceptance criterion per behavior.
+    #[must_use]
+    pub fn spec_skeleton(&self, created: &str) -> Spec {
+        let draft = &self.draft;
+        Spec {
+            specification: Specification {
+                identity: SpecIdentity {
+                    id: draft.id.clone(),
+                    version: SKELETON_VERSION.to_string(),
+                    status: SKELETON_STATUS.to_string(),
+                    author: draft.author.clone(),
+                    created: created.to_string(),
+                    updated: None,
+                    supersedes: None,
+                },
+                archetype: Some(draft.archetype),
+                intent: SpecIntent {
+                    problem_statement: draft.problem_statement.clone(),
+                    success_criteria: draft
+                        .behaviors
+                        .iter()
+                        .map(|behavior| behavior.description.clone())
+                        .collect(),
+                    non_goals: None,
+                },
+                context: SpecContext {
+                    system_dependencies: draft.dependencies.clone(),
+                    existing_behaviors: None,
+                    constraints: None,
+                    invariants: Vec::new(),
+                    glossary: None,
+                },
+                behaviors: draft
+                    .behaviors
+                    .iter()
+                    .map(|behavior| Behavior {
+                        id: behavior.id.clone(),
+                        description: behavior.description.clone(),
+                        given: None,
+                        r#when: None,
+                        then: Vec::new(),
+                        edge_cases: Some(Vec::new()),
+                    })
+                    .collect(),
+                data_model: None,
+                api_contract: None,
+                acceptance_criteria: draft
+                    .behaviors
+                    .iter()
+                    .map(|behavior| AcceptanceCriterion {
+                        id: format!("ac-{}", behavior.id),
+                        behavior_ref: Some(behavior.id.clone()),
+                        criterion: behavior.description.clone(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /// Step 3: a twin universe named after the feature, if any dependency
+    /// has a twin.
+    #[must_use]
+    pub fn twin_universe(&self) -> Option<TwinUniverse> {
+        let mut universe = TwinUniverse::new(&self.draft.id);
+        for dependency in self
+            .draft
+            .dependencies
+            .iter()
+            .filter(|dependency| dependency.twin_available)
+        {
+            universe.add(TwinDefinition::for_dependency(
+                dependency,
+                &self.twin_base_url,
+            ));
+        }
+        (!universe.twins.is_empty()).then_some(universe)
+    }
+
+    /// Step 4: one runnable no-op scenario per behavior, already linked to
+    /// it through `behavior_ref` so coverage counts it.
+    #[must_use]
+    pub fn scenario_stubs(&self, universe: &str) -> Vec<Scenario> {
+        self.draft
+            .behaviors
+            .iter()
+            .map(|behavior| Scenario {
+                scenario: ScenarioIdentity {
+                    id: format!("{}-{}", self.draft.id, behavior.id),
+                    spec_ref: self.draft.id.clone(),
+                    spec_version: SKELETON_VERSION.to_string(),
+                    category: "happy-path".to_string(),
+                    visibility: "open".to_string(),
+                    priority: "medium".to_string(),
+                    description: behavior.description.clone(),
+                    rationale: format!("Stub generated for behavior {}.", behavior.id),
+                    tags: Vec::new(),
+                },
+                setup: ScenarioSetup {
+                    universe: universe.to_string(),
+                    initial_state: "empty".to_string(),
+                    preconditions: Vec::new(),
+                },
+                steps: vec![ScenarioStep {
+                    id: behavior.id.clone(),
+                    description: behavior.description.clone(),
+                    action: StepAction {
+                        action_type: "noop".to_string(),
+                        method: None,
+                        url: None,
+                        headers: None,
+                        body: None,
+                        params: None,
+                        duration_ms: None,
+                        interval_ms: None,
+                        timeout_ms: None,
+                        service: None,
+                        rpc: None,
+                        topic: None,
+                        key: None,
+                        workflow: None,
+                    },
+                    assertions: vec![Assertion {
+                        assertion_type: "status".to_string(),
+                        path: None,
+                        expected: Some(serde_json::json!(0)),
+                        operator: None,
+                        message: Some("Replace this stub with a real assertion.".to_string()),
+                        behavior_ref: Some(behavior.id.clone()),
+                        edge_case_ref: None,
+                        twin: None,
+                        collection: None,
+                        count: None,
+                        matches: None,
+                        topic: None,
+                        node: None,
+                    }],
+                    extractions: Vec::new(),
+                    retry: None,
+                }],
+                teardown: ScenarioTeardown {
+                    reset_universe: true,
+                    custom_cleanup: None,
+                },
+            })
+            .collect()
+    }
+
+    /// Run every generation step against the workspace's presets and
+    /// archetype bindings.
+    ///
+    /// # Errors
+    /// Returns an error if the archetype scaffold cannot be built.
+    pub fn build(
+        &self,
+        manifest: &WorkspaceManifest,
+        created: &str,
+    ) -> Result<FeatureBundle, WizardError> {
+        let spec = self.spec_skeleton(created);
+        let scaffold = manifest
+            .archetypes
+            .scaffold(&manifest.presets, self.draft.archetype)
+            .map_err(WizardError::Scaffold)?;
+        let universe = self.twin_universe();
+        let scenarios = self.scenario_stubs(
+            universe
+                .as_ref()
+                .map_or(DEFAULT_UNIVERSE, |universe| universe.name.as_str()),
+        );
+        let untwinned = self
+            .draft
+            .dependencies
+            .iter()
+            .filter(|dependency| !dependency.twin_available)
+            .map(|dependency| dependency.service.clone())
+            .collect();
+
+        Ok(FeatureBundle {
+            spec,
+            workflow: scaffold.workflow,
+            recommended_presets: scaffold.recommended_presets,
+            scenarios,
+            universe,
+            untwinned,
+        })
+    }
+}
+
+impl FeatureBundle {
+    #[must_use]
+    pub fn feature_id(&self) -> &str {
+        &self.spec.specification.identity.id
+    }
+
+    /// Where each file goes, relative to the workspace root.
+    #[must_use]
+    pub fn entry(&self) -> FeatureEntry {
+        let id = self.feature_id();
+        FeatureEntry {
+            id: id.to_string(),
+            spec: PathBuf::from("specs").join(format!("{id}.yaml")),
+            workflow: PathBuf::from("workflows").join(format!("{id}.json")),
+            scenarios: self
+                .scenarios
+                .iter()
+                .filter_map(|scenario| scenario.steps.first())
+                .map(|step| {
+                    PathBuf::from("specs")
+                        .join("scenarios")
+                        .join(id)
+                        .join(format!("{}.yaml", step.id))
+                })
+                .collect(),
+            universe: self.universe.as_ref().map_or_else(
+                || DEFAULT_UNIVERSE.to_string(),
+                |universe| universe.name.clone(),
+            ),
+        }
+    }
+
+    /// Step 5: write every file under `root` and register the feature in the
+    /// manifest, which is saved last.
+    ///
+    /// Nothing is written if the feature is already registered or one of its
+    /// files already exists.
+    ///
+    /// # Errors
+    /// Returns an error on a registration conflict or a failed write.
+    pub fn write(
+        &self,
+        root: &Path,
+        manifest: &mut WorkspaceManifest,
+    ) -> Result<FeatureEntry, WizardError> {
+        let entry = self.entry();
+        if manifest.feature(&entry.id).is_some() {
+            return Err(WizardError::AlreadyRegistered(entry.id));
+        }
+        if let Some(universe) = &self.universe {
+            if manifest.universe(&universe.name).is_some() {
+                return Err(WizardError::AlreadyRegistered(universe.name.clone()));
+            }
+        }
+        if let Some(existing) = std::iter::once(&entry.spec)
+            .chain(std::iter::once(&entry.workflow))
+            .chain(&entry.scenarios)
+            .map(|path| root.join(path))
+            .find(|path| path.exists())
+        {
+            return Err(WizardError::FileExists(existing));
+        }
+
+        write_file(&root.join(&entry.spec), yaml(&self.spec, &entry.spec)?)?;
+        write_file(
+            &root.join(&entry.workflow),
+            serde_json::to_string_pretty(&self.workflow.redacted()).map_err(|err| {
+                WizardError::Serialize {
+                    path: entry.workflow.clone(),
+                    detail: err.to_string(),
+                }
+            })?,
+        )?;
+        for (scenario, path) in self.scenarios.iter().zip(&entry.scenarios) {
+            write_file(&root.join(path), yaml(scenario, path)?)?;
+        }
+
+        manifest.register(entry.clone(), self.universe.clone())?;
+        manifest.save(root)?;
+        Ok(entry)
+    }
+}
+
+fn yaml<T: serde::Serialize>(value: &T, path: &Path) -> Result<String, WizardError> {
+    serde_yaml::to_string(value).map_err(|err| WizardError::Serialize {
+        path: path.to_path_buf(),
+        detail: err.to_string(),
+    })
+}
+
+fn write_file(path: &Path, content: String) -> Result<(), WizardError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|source| WizardError::Io {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    fs::write(path, content).map_err(|source| WizardError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+mod tests {
+    use super::{
+        BehaviorDraft, FeatureDraft, FeatureWizard, WizardError, WorkspaceManifest, MANIFEST_FILE,
+    };
+    use crate::coverage::CoverageAnalyzer;
+    use crate::flow_extender::archetype::SpecArchetype;
+    use crate::linter::{Spec, SystemDependency};
+
+    fn draft() -> FeatureDraft {
+        FeatureDraft {
+            id: "refund-requests".to_string(),
+            author: "team-payments".to_string(),
+            problem_statement: "Customers cannot request refunds without support.".to_string(),
+            archetype: SpecArchetype::ApprovalWorkflow,
+            behaviors: vec![
+                BehaviorDraft {
+                    id: "submit-refund".to_string(),
+                    description: "A customer submits a refund request.".to_string(),
+                },
+                BehaviorDraft {
+                    id: "approve-refund".to_string(),
+                    description: "An agent approves a pending refund.".to_string(),
+                },
+            ],
+            dependencies: vec![
+                SystemDependency {
+                    service: "payments".to_string(),
+                    purpose: "Issue refunds".to_string(),
+                    twin_available: true,
+                },
+                SystemDependency {
+                    service: "crm".to_string(),
+                    purpose: "Look up customers".to_string(),
+                    twin_available: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn given_draft_when_building_then_every_subsystem_gets_an_artifact() {
+        let wizard = FeatureWizard::new(draft()).unwrap();
+
+        let bundle = wizard
+            .build(&WorkspaceManifest::default(), "2026-01-01")
+            .unwrap();
+
+        assert_eq!(bundle.spec.specification.behaviors.len(), 2);
+        assert_eq!(bundle.spec.specification.acceptance_criteria.len(), 2);
+        assert_eq!(bundle.workflow.nodes.len(), 4);
+        assert_eq!(bundle.recommended_presets[0].key, "approval");
+        let universe = bundle.universe.as_ref().unwrap();
+        assert_eq!(universe.name, "refund-requests");
+        assert_eq!(universe.twins.keys().collect::<Vec<_>>(), vec!["payments"]);
+        assert_eq!(bundle.untwinned, vec!["crm".to_string()]);
+        assert!(bundle
+            .scenarios
+            .iter()
+            .all(|scenario| scenario.setup.universe == "refund-requests"));
+    }
+
+    #[test]
+    fn given_invalid_drafts_when_starting_wizard_then_they_are_rejected() {
+        let mut bad_id = draft();
+        bad_id.id = "Refund Requests".to_string();
+        let mut duplicate = draft();
+        duplicate.behaviors[1].id = "submit-refund".to_string();
+        let mut empty = draft();
+        empty.behaviors.clear();
+
+        for candidate in [bad_id, duplicate, empty] {
+            assert!(matches!(
+                FeatureWizard::new(candidate),
+                Err(WizardError::InvalidDraft(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn given_written_feature_when_analyzing_coverage_then_stubs_cover_every_behavior() {
+        let root = tempfile::tempdir().unwrap();
+        let mut manifest = WorkspaceManifest::load(root.path()).unwrap();
+        let bundle = FeatureWizard::new(draft())
+            .unwrap()
+            .build(&manifest, "2026-01-01")
+            .unwrap();
+
+        let entry = bundle.write(root.path(), &mut manifest).unwrap();
+
+        let spec_yaml = std::fs::read_to_string(root.path().join(&entry.spec)).unwrap();
+        let spec: Spec = serde_yaml::from_str(&spec_yaml).unwrap();
+        assert_eq!(spec.specification.identity.id, "refund-requests");
+        assert!(root.path().join(&entry.workflow).exists());
+
+        let reloaded = WorkspaceManifest::load(root.path()).unwrap();
+        assert!(root.path().join(MANIFEST_FILE).exists());
+        assert_eq!(reloaded.feature("refund-requests"), Some(&entry));
+        assert!(reloaded.universe("refund-requests").is_some());
+
+        let report = CoverageAnalyzer::new(
+            &root.path().join("specs"),
+            &root.path().join("specs").join("scenarios"),
+        )
+        .analyze()
+        .unwrap();
+        let coverage = report
+            .specs
+            .iter()
+            .find(|spec| spec.spec_id == "refund-requests")
+            .unwrap();
+        assert_eq!(coverage.covered_behaviors, 2);
+        assert!(coverage.missing_behaviors.is_empty());
+    }
+
+    #[test]
+    fn given_registered_feature_when_writing_again_then_nothing_is_overwritten() {
+        let root = tempfile::tempdir().unwrap();
+        let mut manifest = WorkspaceManifest::default();
+        let bundle = FeatureWizard::new(draft())
+            .unwrap()
+            .build(&manifest, "2026-01-01")
+            .unwrap();
+        bundle.write(root.path(), &mut manifest).unwrap();
+
+        let again = bundle.write(root.path(), &mut manifest);
+        let mut fresh = WorkspaceManifest::default();
+        let on_disk = bundle.write(root.path(), &mut fresh);
+
+        assert!(matches!(again, Err(WizardError::AlreadyRegistered(_))));
+        assert!(matches!(on_disk, Err(WizardError::FileExists(_))));
+        assert_eq!(manifest.features.len(), 1);
+        assert!(fresh.features.is_empty());
+    }
+}
@@ -80,3 +80,66 @@ scenario:
     fs::remove_dir_all(root)?;
     Ok(())
 }
+
+#[test]
+fn given_coverage_below_min_spec_when_running_coverage_cli_then_it_exits_non_zero(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = temp_dir("threshold")?;
+    let specs = root.join("specs");
+    let scenarios = root.join("scenarios");
+    fs::create_dir_all(&specs)?;
+    fs::create_dir_all(&scenarios)?;
+
+    write_file(
+        &specs.join("spec.yaml"),
+        r"
+specification:
+  identity:
+    id: spec-threshold
+  behaviors:
+    - id: covered
+    - id: uncovered
+",
+    )?;
+    write_file(
+        &scenarios.join("scenario.yaml"),
+        r"
+scenario:
+  spec_ref: spec-threshold
+  steps:
+    - assertions:
+        - behavior_ref: covered
+",
+    )?;
+
+    let run = |min_spec: &str| {
+        Command::new(env!("CARGO_BIN_EXE_coverage"))
+            .args([
+                "--specs-dir",
+                specs.to_str().unwrap_or_default(),
+                "--scenarios-dir",
+                scenarios.to_str().unwrap_or_default(),
+                "--format",
+                "json",
+                "--min-spec",
+                min_spec,
+            ])
+            .output()
+    };
+
+    let failing = run("80")?;
+    let passing = run("50")?;
+
+    assert!(!failing.status.success());
+    let stderr = String::from_utf8(failing.stderr)?;
+    assert!(stderr.contains("spec spec-threshold is 50.0%, below the required 80.0%"));
+    serde_json::from_slice::<serde_json::Value>(&failing.stdout)?;
+    assert!(
+        passing.status.success(),
+        "stderr={}",
+        String::from_utf8_lossy(&passing.stderr)
+    );
+
+    fs::remove_dir_all(root)?;
+    Ok(())
+}
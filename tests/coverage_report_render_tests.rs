@@ -0,0 +1,144 @@
+//! Golden-file tests for coverage report renderers.
+//!
+//! Each format is rendered from the same fixed report and compared against a
+//! reviewed snapshot under `tests/snapshots/`.
+#![allow(
+    clippy::unwrap_used,
+    clippy::expect_used,
+    clippy::panic,
+    clippy::float_cmp
+)]
+
+use oya_frontend::coverage::{
+    CoverageReport, OrphanScenario, ReferenceIssues, ReportFormat, ScenarioRef, SpecCoverage,
+    StaleReference,
+};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+fn sample_report() -> CoverageReport {
+    CoverageReport {
+        specs: vec![
+            SpecCoverage {
+                spec_id: "spec-checkout".to_string(),
+                total_behaviors: 3,
+                covered_behaviors: 3,
+                total_edge_cases: 1,
+                covered_edge_cases: 1,
+                coverage_percentage: 100.0,
+                missing_behaviors: Vec::new(),
+                missing_edge_cases: Vec::new(),
+                behavior_to_scenarios: BTreeMap::from([(
+                    "place-order".to_string(),
+                    vec![ScenarioRef {
+                        id: "checkout-happy-path".to_string(),
+                        path: PathBuf::from("checkout/happy_path.yaml"),
+                    }],
+                )]),
+            },
+            SpecCoverage {
+                spec_id: "spec-<refunds>".to_string(),
+                total_behaviors: 4,
+                covered_behaviors: 2,
+                total_edge_cases: 2,
+                covered_edge_cases: 1,
+                coverage_percentage: 50.0,
+                missing_behaviors: vec!["partial-refund".to_string(), "refund-denied".to_string()],
+                missing_edge_cases: vec!["currency-mismatch & rounding".to_string()],
+                behavior_to_scenarios: BTreeMap::new(),
+            },
+        ],
+        overall_coverage: 70.0,
+        total_behaviors: 7,
+        total_edge_cases: 3,
+        covered_behaviors: 5,
+        covered_edge_cases: 2,
+        common_gaps: vec!["spec-<refunds>: 3 gaps".to_string()],
+        reference_issues: ReferenceIssues {
+            unknown_behavior_refs: vec![StaleReference {
+                spec_id: "spec-checkout".to_string(),
+                reference: "apply-coupon".to_string(),
+                scenario: ScenarioRef {
+                    id: "checkout-coupon".to_string(),
+                    path: PathBuf::from("checkout/coupon.yaml"),
+                },
+            }],
+            unknown_edge_case_refs: Vec::new(),
+            orphan_scenarios: vec![OrphanScenario {
+                spec_ref: "spec-legacy-cart".to_string(),
+                scenario: ScenarioRef {
+                    id: "legacy-cart".to_string(),
+                    path: PathBuf::from("legacy/cart.yaml"),
+                },
+            }],
+        },
+    }
+}
+
+#[test]
+fn given_sample_report_when_rendering_json_then_output_matches_golden_file() {
+    let rendered = sample_report().render(ReportFormat::Json).unwrap();
+
+    insta::assert_snapshot!("coverage_report_json", rendered);
+}
+
+#[test]
+fn given_sample_report_when_rendering_html_then_output_matches_golden_file() {
+    let rendered = sample_report().render(ReportFormat::Html).unwrap();
+
+    insta::assert_snapshot!("coverage_report_html", rendered);
+}
+
+#[test]
+fn given_sample_report_when_rendering_markdown_then_output_matches_golden_file() {
+    let rendered = sample_report().render(ReportFormat::Markdown).unwrap();
+
+    insta::assert_snapshot!("coverage_report_markdown", rendered);
+}
+
+#[test]
+fn given_rendered_json_when_parsing_then_report_round_trips() {
+    let rendered = sample_report().render(ReportFormat::Json).unwrap();
+
+    let parsed = serde_json::from_str::<CoverageReport>(&rendered).unwrap();
+
+    assert_eq!(parsed.specs.len(), 2);
+    assert_eq!(parsed.overall_coverage, 70.0);
+}
+
+#[test]
+fn given_html_report_when_spec_ids_contain_markup_then_they_are_escaped() {
+    let rendered = sample_report().render(ReportFormat::Html).unwrap();
+
+    assert!(rendered.contains("spec-&lt;refunds&gt;"));
+    assert!(!rendered.contains("spec-<refunds>"));
+    assert!(!rendered.contains("<link"));
+    assert!(!rendered.contains("<script"));
+}
+
+#[test]
+fn given_empty_report_when_rendering_markdown_then_no_spec_table_is_emitted() {
+    let report = CoverageReport {
+        specs: Vec::new(),
+        overall_coverage: 0.0,
+        total_behaviors: 0,
+        total_edge_cases: 0,
+        covered_behaviors: 0,
+        covered_edge_cases: 0,
+        common_gaps: Vec::new(),
+        reference_issues: ReferenceIssues::default(),
+    };
+
+    let rendered = report.render(ReportFormat::Markdown).unwrap();
+
+    assert!(rendered.contains("_No specs found._"));
+    assert!(!rendered.contains("| Spec |"));
+}
+
+#[test]
+fn given_format_names_when_parsing_then_known_formats_resolve() {
+    assert_eq!(ReportFormat::from_str("md"), Ok(ReportFormat::Markdown));
+    assert_eq!(ReportFormat::from_str("html"), Ok(ReportFormat::Html));
+    assert!(ReportFormat::from_str("pdf").is_err());
+}
@@ -59,6 +59,34 @@ async fn given_simple_chain_when_running_then_nodes_complete_and_history_is_reco
     }));
 }
 
+#[tokio::test]
+async fn given_progress_observer_when_running_then_each_node_is_seen_running_then_settled() {
+    let mut workflow = Workflow::new();
+    let start = workflow.add_node("http-handler", 20.0, 20.0);
+    let next = workflow.add_node("next-custom", 50.0, 110.0);
+    let main = PortName("main".to_string());
+    let _ = workflow.add_connection(start, next, &main, &main);
+
+    let mut seen = Vec::new();
+    workflow
+        .run_with_progress(|snapshot| {
+            seen.extend(
+                snapshot
+                    .nodes
+                    .iter()
+                    .filter(|node| node.executing)
+                    .map(|node| node.id),
+            );
+        })
+        .await;
+
+    assert_eq!(seen, vec![start, next]);
+    assert!(workflow
+        .nodes
+        .iter()
+        .all(|node| node.duration_ms().is_some()));
+}
+
 #[tokio::test]
 async fn given_true_condition_when_running_then_false_branch_is_marked_skipped() {
     let mut workflow = Workflow::new();
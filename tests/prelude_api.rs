@@ -0,0 +1,59 @@
+//! Guards the `prelude` facade: external crates rely on every name used here
+//! resolving through `oya_frontend::prelude` alone.
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use oya_frontend::prelude::*;
+
+fn chained_workflow() -> (Workflow, NodeId, NodeId) {
+    let mut workflow = Workflow::new();
+    let entry = workflow.add_node("http-handler", 0.0, 0.0);
+    let step = workflow.add_node("run", 200.0, 0.0);
+    (workflow, entry, step)
+}
+
+#[test]
+fn given_prelude_when_building_a_workflow_then_it_validates_and_compiles() {
+    let (mut workflow, entry, step) = chained_workflow();
+
+    let connected: Result<ConnectionResult, GraphConnectionError> =
+        workflow.add_connection_checked(entry, step, &PortName::from("out"), &PortName::from("in"));
+
+    assert!(connected.is_ok());
+    let validation: ValidationResult = validate_workflow(&workflow);
+    assert!(!validation.has_errors());
+    let report: CompileReport = compile_workflow(&workflow, SeverityGate::AllowWarnings);
+    assert_eq!(report.gate, SeverityGate::AllowWarnings);
+}
+
+#[test]
+fn given_prelude_when_planning_extensions_then_suggestions_can_be_previewed_and_applied() {
+    let (mut workflow, entry, step) = chained_workflow();
+    workflow
+        .add_connection_checked(entry, step, &PortName::from("out"), &PortName::from("in"))
+        .unwrap();
+
+    let suggestions: Vec<FlowExtension> = suggest_extensions(&workflow);
+    let Some(first) = suggestions.first() else {
+        return;
+    };
+    let preview: Option<ExtensionPatchPreview> = preview_extension(&workflow, &first.key).unwrap();
+    let applied: AppliedExtension = apply_extension(&mut workflow, &first.key).unwrap();
+
+    assert!(preview.is_some() || applied.created_nodes.is_empty());
+    let plan: CompoundExtensionPlan = generate_compound_plan(&workflow, &[]).unwrap();
+    assert!(plan.conflicts.is_empty());
+}
+
+#[test]
+fn given_prelude_when_analyzing_empty_dirs_then_coverage_report_is_empty() {
+    let root = tempfile::tempdir().unwrap();
+
+    let outcome: PolicyOutcome = CoverageAnalyzer::new(root.path(), root.path())
+        .analyze_with_policy(&CoverageThresholds::default())
+        .unwrap();
+    let report: &CoverageReport = &outcome.report;
+
+    assert!(outcome.passed());
+    assert!(report.reference_issues.is_empty());
+    assert_eq!(ReportFormat::Json.as_str(), "json");
+}
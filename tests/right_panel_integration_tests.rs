@@ -123,6 +123,7 @@ fn given_workflow_with_history_when_accessing_then_records_are_available() {
         results: HashMap::new(),
         success: true,
         restate_invocation_id: None,
+        durations: HashMap::new(),
     };
     let workflow = Workflow {
         nodes: vec![],
@@ -143,6 +144,7 @@ fn given_workflow_with_multiple_runs_when_accessing_history_then_order_is_preser
         results: HashMap::new(),
         success: true,
         restate_invocation_id: None,
+        durations: HashMap::new(),
     };
     let r2 = RunRecord {
         id: Uuid::new_v4(),
@@ -150,6 +152,7 @@ fn given_workflow_with_multiple_runs_when_accessing_history_then_order_is_preser
         results: HashMap::new(),
         success: false,
         restate_invocation_id: Some("inv-123".to_string()),
+        durations: HashMap::new(),
     };
 
     let workflow = Workflow {
@@ -180,6 +183,7 @@ fn given_run_record_with_results_when_accessing_then_node_results_are_present()
         results,
         success: true,
         restate_invocation_id: None,
+        durations: HashMap::new(),
     };
 
     assert!(record.results.contains_key(&node_id));
@@ -57,3 +57,1026 @@ async fn extension_behavior_cases_are_counted() -> Result<()> {
 
     Ok(())
 }
+
+/// Serves `POST /orders` and `GET /orders/7` until the test ends.
+async fn spawn_order_api() -> Result<String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buffer = vec![0_u8; 4096];
+            let Ok(read) = socket.read(&mut buffer).await else {
+                continue;
+            };
+            let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+            let (status, body) = if request.starts_with("POST /orders ") {
+                ("201 Created", r#"{"id":7}"#)
+            } else if request.starts_with("GET /orders/7?expand=items ") {
+                ("200 OK", r#"{"id":7,"status":"open"}"#)
+            } else {
+                ("404 Not Found", "{}")
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    Ok(endpoint)
+}
+
+#[tokio::test]
+async fn extracted_values_are_interpolated_into_later_steps() -> Result<()> {
+    use oya_frontend::scenario_runner::{Scenario, ScenarioRunner};
+
+    let endpoint = spawn_order_api().await?;
+    let scenario: Scenario = serde_yaml::from_str(
+        r#"
+scenario:
+  id: create-then-fetch
+  spec_ref: orders
+  spec_version: 1.0.0
+  category: happy-path
+  visibility: open
+  priority: high
+  description: Create an order and fetch it by id.
+  rationale: Later steps depend on ids returned by earlier ones.
+setup:
+  universe: local
+  initial_state: empty
+  preconditions: []
+steps:
+  - id: create
+    description: Create an order.
+    action:
+      type: http
+      method: POST
+      url: "${application.endpoint}/orders"
+      body:
+        item: widget
+    assertions:
+      - type: status
+        expected: 201
+    extractions:
+      - name: order_id
+        from: body
+        path: /id
+  - id: fetch
+    description: Fetch the created order.
+    action:
+      type: http
+      method: GET
+      url: "${application.endpoint}/orders/${extracted.order_id}"
+      params:
+        expand: items
+    assertions:
+      - type: status
+        expected: 200
+      - type: body_json
+        path: /id
+        expected: "${extracted.order_id}"
+    extractions: []
+  - id: refund
+    description: Uses a value nobody extracted.
+    action:
+      type: http
+      method: POST
+      url: "${application.endpoint}/refunds/${extracted.refund_id}"
+    assertions: []
+    extractions: []
+teardown:
+  reset_universe: true
+  custom_cleanup: []
+"#,
+    )?;
+
+    let mut runner = ScenarioRunner::new(&endpoint, HashMap::new());
+    let result = runner.run_scenario(&scenario).await;
+
+    assert_eq!(result.steps.len(), 3);
+    assert!(result.steps[0].passed);
+    assert!(result.steps[1].passed, "{:?}", result.steps[1].error);
+    assert_eq!(result.steps[1].assertions_passed, 2);
+    assert!(!result.steps[2].passed);
+    assert_eq!(
+        result.steps[2].error.as_deref(),
+        Some("Unknown scenario variable: ${extracted.refund_id}")
+    );
+    Ok(())
+}
+
+/// Serves every request after `delay`, and counts the most requests it
+/// ever had in flight at once.
+async fn spawn_slow_api(
+    delay: std::time::Duration,
+) -> Result<(String, std::sync::Arc<std::sync::atomic::AtomicUsize>)> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let peak_seen = Arc::clone(&peak);
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let in_flight = Arc::clone(&in_flight);
+            let peak = Arc::clone(&peak);
+            tokio::spawn(async move {
+                let mut buffer = vec![0_u8; 4096];
+                if socket.read(&mut buffer).await.is_err() {
+                    return;
+                }
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(delay).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                let body = r#"{"id":7}"#;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok((endpoint, peak_seen))
+}
+
+fn single_step_scenario(id: &str, url: &str, extract: bool) -> String {
+    let extractions = if extract {
+        "\n      - name: order_id\n        from: body\n        path: /id"
+    } else {
+        " []"
+    };
+    format!(
+        r#"
+scenario:
+  id: {id}
+  spec_ref: orders
+  spec_version: 1.0.0
+  category: happy-path
+  visibility: open
+  priority: high
+  description: Fetch an order.
+  rationale: Exercises scheduling.
+setup:
+  universe: local
+  initial_state: empty
+  preconditions: []
+steps:
+  - id: fetch
+    description: Fetch an order.
+    action:
+      type: http
+      method: GET
+      url: "{url}"
+    assertions:
+      - type: status
+        expected: 200
+    extractions:{extractions}
+teardown:
+  reset_universe: true
+  custom_cleanup: []
+"#
+    )
+}
+
+/// Files are named so that file order and scenario id order disagree.
+fn write_order_suite(dir: &Path) -> Result<()> {
+    let files = [
+        (
+            "1-create.yaml",
+            single_step_scenario("zeta-create", "${application.endpoint}/orders", true),
+        ),
+        (
+            "2-reuse.yaml",
+            single_step_scenario(
+                "alpha-reuse",
+                "${application.endpoint}/orders/${extracted.order_id}",
+                false,
+            ),
+        ),
+        (
+            "3-list.yaml",
+            single_step_scenario("mid-list", "${application.endpoint}/orders", false),
+        ),
+        (
+            "4-fetch.yaml",
+            single_step_scenario("beta-fetch", "${application.endpoint}/orders/7", false),
+        ),
+    ];
+    for (name, content) in files {
+        std::fs::write(dir.join(name), content)?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn parallel_validation_isolates_extractions_and_keeps_file_order() -> Result<()> {
+    use oya_frontend::scenario_runner::{run_validation_with, RunOptions};
+
+    let dir = tempfile::tempdir()?;
+    write_order_suite(dir.path())?;
+    let (endpoint, peak) = spawn_slow_api(std::time::Duration::from_millis(100)).await?;
+
+    let report = run_validation_with(
+        dir.path(),
+        &endpoint,
+        HashMap::new(),
+        RunOptions {
+            concurrency: 3,
+            ..RunOptions::default()
+        },
+    )
+    .await?;
+
+    let ids = report
+        .results
+        .iter()
+        .map(|result| result.scenario_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        ["zeta-create", "alpha-reuse", "mid-list", "beta-fetch"]
+    );
+    assert_eq!(report.passed_scenarios, 3);
+    assert_eq!(
+        report.results[1].steps[0].error.as_deref(),
+        Some("Unknown scenario variable: ${extracted.order_id}")
+    );
+    assert!(peak.load(std::sync::atomic::Ordering::SeqCst) > 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn validation_timeout_fails_unfinished_scenarios() -> Result<()> {
+    use oya_frontend::scenario_runner::{run_validation_with, RunOptions};
+
+    let dir = tempfile::tempdir()?;
+    write_order_suite(dir.path())?;
+    let (endpoint, _) = spawn_slow_api(std::time::Duration::from_secs(5)).await?;
+
+    let report = run_validation_with(
+        dir.path(),
+        &endpoint,
+        HashMap::new(),
+        RunOptions {
+            concurrency: 4,
+            timeout: Some(std::time::Duration::from_millis(100)),
+            ..RunOptions::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(report.total_scenarios, 4);
+    assert_eq!(report.failed_scenarios, 4);
+    let timed_out = report
+        .results
+        .iter()
+        .filter(|result| result.error.as_deref() == Some("Run timed out after 100ms"))
+        .map(|result| result.scenario_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(timed_out, ["zeta-create", "mid-list", "beta-fetch"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn sequential_validation_shares_extractions_with_later_scenarios() -> Result<()> {
+    use oya_frontend::scenario_runner::{run_validation_with, RunOptions};
+
+    let dir = tempfile::tempdir()?;
+    write_order_suite(dir.path())?;
+    let (endpoint, peak) = spawn_slow_api(std::time::Duration::from_millis(10)).await?;
+
+    let report = run_validation(dir.path(), &endpoint, HashMap::new()).await?;
+    let with_options =
+        run_validation_with(dir.path(), &endpoint, HashMap::new(), RunOptions::default()).await?;
+
+    for report in [report, with_options] {
+        assert_eq!(report.results[0].scenario_id, "zeta-create");
+        assert_eq!(report.results[1].scenario_id, "alpha-reuse");
+        assert!(report.results[1].passed, "{:?}", report.results[1]);
+        assert_eq!(report.passed_scenarios, 4);
+    }
+    assert_eq!(peak.load(std::sync::atomic::Ordering::SeqCst), 1);
+    Ok(())
+}
+
+/// Answers `503 {"status":"pending"}` to the first `pending` requests and
+/// `200 {"status":"done"}` afterwards.
+async fn spawn_eventual_api(pending: usize) -> Result<String> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    let served = AtomicUsize::new(0);
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buffer = vec![0_u8; 4096];
+            if socket.read(&mut buffer).await.is_err() {
+                continue;
+            }
+            let (status, body) = if served.fetch_add(1, Ordering::SeqCst) < pending {
+                ("503 Service Unavailable", r#"{"status":"pending"}"#)
+            } else {
+                ("200 OK", r#"{"status":"done"}"#)
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    Ok(endpoint)
+}
+
+fn scenario_with_steps(steps: &str) -> Result<oya_frontend::scenario_runner::Scenario> {
+    Ok(serde_yaml::from_str(&format!(
+        r#"
+scenario:
+  id: eventual-job
+  spec_ref: jobs
+  spec_version: 1.0.0
+  category: happy-path
+  visibility: open
+  priority: high
+  description: A job finishes some time after it is started.
+  rationale: Restate workflows settle asynchronously.
+setup:
+  universe: local
+  initial_state: empty
+  preconditions: []
+steps:
+{steps}
+teardown:
+  reset_universe: true
+  custom_cleanup: []
+"#
+    ))?)
+}
+
+#[tokio::test]
+async fn poll_action_repeats_until_assertions_pass() -> Result<()> {
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let endpoint = spawn_eventual_api(3).await?;
+    let scenario = scenario_with_steps(
+        r#"
+  - id: settle
+    description: Give the job a head start.
+    action:
+      type: wait
+      duration_ms: 20
+    assertions: []
+    extractions: []
+  - id: job-done
+    description: Poll until the job reports done.
+    action:
+      type: poll
+      url: "${application.endpoint}/jobs/1"
+      interval_ms: 5
+      timeout_ms: 2000
+    assertions:
+      - type: status
+        expected: 200
+      - type: body_json
+        path: /status
+        expected: done
+    extractions: []
+"#,
+    )?;
+
+    let result = ScenarioRunner::new(&endpoint, HashMap::new())
+        .run_scenario(&scenario)
+        .await;
+
+    assert!(result.passed, "{:?}", result.steps);
+    assert!(result.steps[0].duration_ms >= 20);
+    assert_eq!(result.steps[1].assertions_passed, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn poll_action_fails_once_its_timeout_elapses() -> Result<()> {
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let endpoint = spawn_eventual_api(usize::MAX).await?;
+    let scenario = scenario_with_steps(
+        r#"
+  - id: job-done
+    description: Poll until the job reports done.
+    action:
+      type: poll
+      url: "${application.endpoint}/jobs/1"
+      interval_ms: 10
+      timeout_ms: 50
+    assertions:
+      - type: status
+        expected: 200
+    extractions: []
+"#,
+    )?;
+
+    let result = ScenarioRunner::new(&endpoint, HashMap::new())
+        .run_scenario(&scenario)
+        .await;
+
+    assert!(!result.passed);
+    let error = result.steps[0].error.clone().unwrap_or_default();
+    assert!(error.starts_with("Expected status 200, got 503 (gave up after "));
+    assert!(error.ends_with(" polls in 50ms)"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_policy_reruns_failed_steps() -> Result<()> {
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let step = |attempts: u32| {
+        format!(
+            r#"
+  - id: job-done
+    description: Fetch the finished job.
+    action:
+      type: http
+      url: "${{application.endpoint}}/jobs/1"
+    assertions:
+      - type: status
+        expected: 200
+    extractions: []
+    retry:
+      attempts: {attempts}
+      delay_ms: 5
+"#
+        )
+    };
+
+    let endpoint = spawn_eventual_api(2).await?;
+    let recovered = ScenarioRunner::new(&endpoint, HashMap::new())
+        .run_scenario(&scenario_with_steps(&step(3))?)
+        .await;
+    let endpoint = spawn_eventual_api(2).await?;
+    let exhausted = ScenarioRunner::new(&endpoint, HashMap::new())
+        .run_scenario(&scenario_with_steps(&step(2))?)
+        .await;
+
+    assert!(recovered.passed, "{:?}", recovered.steps);
+    assert!(!exhausted.passed);
+    assert_eq!(
+        exhausted.steps[0].error.as_deref(),
+        Some("Expected status 200, got 503 (after 2 attempts)")
+    );
+    Ok(())
+}
+
+/// A twin whose `charges` collection holds one charge for order 7.
+async fn spawn_payments_twin() -> Result<String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut buffer = vec![0_u8; 4096];
+            let Ok(read) = socket.read(&mut buffer).await else {
+                continue;
+            };
+            let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+            let (status, body) = if request.starts_with("GET /__twin/charges ") {
+                (
+                    "200 OK",
+                    r#"[{"id":"ch_1","amount":500,"metadata":{"order_id":"7"}}]"#,
+                )
+            } else if request.starts_with("GET /health ") {
+                ("200 OK", "{}")
+            } else {
+                ("404 Not Found", "{}")
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    Ok(endpoint)
+}
+
+#[tokio::test]
+async fn twin_endpoints_and_state_assertions_reach_the_twin() -> Result<()> {
+    let twin = spawn_payments_twin().await?;
+    let dir = tempfile::tempdir()?;
+    let step = |id: &str, assertion: &str| {
+        format!(
+            r#"
+  - id: {id}
+    description: Inspect the payments twin.
+    action:
+      type: http
+      url: "${{twins.payments.endpoint}}/health"
+    assertions:
+      - type: status
+        expected: 200
+      - type: twin_state
+        twin: payments
+        collection: charges
+{assertion}
+    extractions: []
+"#
+        )
+    };
+    let scenario = |steps: String| -> Result<String> {
+        Ok(serde_yaml::to_string(&scenario_with_steps(&steps)?)?)
+    };
+    std::fs::write(
+        dir.path().join("1-charged.yaml"),
+        scenario(step(
+            "charged",
+            "        count: 1\n        matches:\n          amount: 500\n          metadata:\n            order_id: \"7\"",
+        ))?,
+    )?;
+    std::fs::write(
+        dir.path().join("2-refunded.yaml"),
+        scenario(step("refunded", "        matches:\n          amount: -500"))?,
+    )?;
+    let twins = HashMap::from([("payments".to_string(), twin)]);
+
+    let report = run_validation(dir.path(), "http://127.0.0.1:9", twins).await?;
+
+    assert!(report.results[0].passed, "{:?}", report.results[0].steps);
+    assert_eq!(report.results[0].steps[0].assertions_passed, 2);
+    assert!(!report.results[1].passed);
+    assert_eq!(
+        report.results[1].steps[0].error.as_deref(),
+        Some(r#"Twin payments charges: no item matches {"amount":-500}"#)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn filtered_validation_runs_only_selected_scenarios_and_records_the_filter() -> Result<()> {
+    use oya_frontend::scenario_runner::{run_validation_with, RunOptions, ScenarioFilter};
+
+    let dir = tempfile::tempdir()?;
+    write_order_suite(dir.path())?;
+    let tagged = std::fs::read_to_string(dir.path().join("3-list.yaml"))?
+        .replace("priority: high", "priority: high\n  tags: [slow, nightly]");
+    std::fs::write(dir.path().join("3-list.yaml"), tagged)?;
+    let (endpoint, _) = spawn_slow_api(std::time::Duration::from_millis(1)).await?;
+    let filter = ScenarioFilter {
+        categories: vec!["happy-path".to_string()],
+        exclude_tags: vec!["slow".to_string()],
+        id_glob: Some("*-*".to_string()),
+        ..ScenarioFilter::default()
+    };
+
+    let report = run_validation_with(
+        dir.path(),
+        &endpoint,
+        HashMap::new(),
+        RunOptions {
+            filter: filter.clone(),
+            ..RunOptions::default()
+        },
+    )
+    .await?;
+
+    let ids = report
+        .results
+        .iter()
+        .map(|result| result.scenario_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, ["zeta-create", "alpha-reuse", "beta-fetch"]);
+    assert_eq!(report.total_scenarios, 3);
+    assert_eq!(report.skipped_scenarios, 1);
+    assert_eq!(report.filter, Some(filter));
+    Ok(())
+}
+
+#[tokio::test]
+async fn transcripts_record_redacted_exchanges_and_respect_feedback_levels() -> Result<()> {
+    use oya_frontend::feedback::FeedbackSanitizer;
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let (endpoint, _) = spawn_slow_api(std::time::Duration::from_millis(1)).await?;
+    let scenario = scenario_with_steps(
+        r#"
+  - id: login
+    description: Sign in and create an order.
+    action:
+      type: http
+      method: POST
+      url: "${application.endpoint}/orders"
+      headers:
+        Authorization: Bearer s3cr3t
+        X-Request-Id: req-1
+      params:
+        api_token: abc
+        page: "2"
+      body:
+        item: widget
+        password: hunter2
+    assertions:
+      - type: status
+        expected: 200
+    extractions: []
+"#,
+    )?;
+
+    let result = ScenarioRunner::new(&endpoint, HashMap::new())
+        .with_transcripts(true)
+        .run_scenario(&scenario)
+        .await;
+
+    let transcript = result.steps[0].transcript.clone().unwrap_or_default();
+    assert_eq!(transcript.len(), 1);
+    let exchange = &transcript[0];
+    assert_eq!(exchange.request.method, "POST");
+    let (path, query) = exchange.request.url.split_once('?').unwrap();
+    assert_eq!(path, format!("{endpoint}/orders"));
+    let mut query = query.split('&').collect::<Vec<_>>();
+    query.sort_unstable();
+    assert_eq!(query, ["api_token=%5BREDACTED%5D", "page=2"]);
+    assert_eq!(exchange.request.headers["authorization"], "[REDACTED]");
+    assert_eq!(exchange.request.headers["x-request-id"], "req-1");
+    assert_eq!(
+        exchange.request.body,
+        Some(serde_json::json!({ "item": "widget", "password": "[REDACTED]" }))
+    );
+    let response = exchange.response.clone().unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.body.as_deref(), Some(r#"{"id":7}"#));
+    assert_eq!(response.headers["content-type"], "application/json");
+
+    let results = [result];
+    let transparent = FeedbackSanitizer::new(5).redact_transcripts(&results);
+    let diagnostic = FeedbackSanitizer::new(4).redact_transcripts(&results);
+    let guided = FeedbackSanitizer::new(3).redact_transcripts(&results);
+
+    assert_eq!(transparent[0].steps[0].transcript, Some(transcript));
+    let stripped = diagnostic[0].steps[0].transcript.clone().unwrap();
+    assert_eq!(stripped[0].request.method, "POST");
+    assert!(stripped[0].request.headers.is_empty());
+    assert_eq!(stripped[0].request.body, None);
+    assert_eq!(stripped[0].response.as_ref().map(|r| r.status), Some(200));
+    assert_eq!(
+        stripped[0].response.as_ref().and_then(|r| r.body.clone()),
+        None
+    );
+    assert_eq!(guided[0].steps[0].transcript, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn transcripts_are_off_unless_requested() -> Result<()> {
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let (endpoint, _) = spawn_slow_api(std::time::Duration::from_millis(1)).await?;
+    let scenario = scenario_with_steps(
+        r#"
+  - id: fetch
+    description: Fetch a job.
+    action:
+      type: http
+      url: "${application.endpoint}/jobs/1"
+    assertions: []
+    extractions: []
+"#,
+    )?;
+
+    let result = ScenarioRunner::new(&endpoint, HashMap::new())
+        .run_scenario(&scenario)
+        .await;
+
+    assert!(result.passed);
+    assert_eq!(result.steps[0].transcript, None);
+    Ok(())
+}
+
+/// Plays a transcoding gRPC server, a Kafka REST proxy and the twin that
+/// consumes the `orders` topic. Published records are served back as the
+/// twin's consumed events.
+async fn spawn_event_driven_system() -> Result<String> {
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let endpoint = format!("http://{}", listener.local_addr()?);
+    let consumed = Arc::new(Mutex::new(Vec::<serde_json::Value>::new()));
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            // Headers and body may arrive in separate reads.
+            let mut request = String::new();
+            let mut buffer = vec![0_u8; 8192];
+            while let Ok(read) = socket.read(&mut buffer).await {
+                request.push_str(&String::from_utf8_lossy(&buffer[..read]));
+                let complete = request.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                    let length = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("content-length: "))
+                        .and_then(|length| length.trim().parse::<usize>().ok())
+                        .unwrap_or(0);
+                    body.len() >= length
+                });
+                if read == 0 || complete {
+                    break;
+                }
+            }
+            let body = request
+                .split_once("\r\n\r\n")
+                .and_then(|(_, body)| serde_json::from_str::<serde_json::Value>(body).ok());
+            let (status, response) = if request
+                .starts_with("POST /orders.v1.OrderService/CreateOrder ")
+                && request.contains("content-type: application/json")
+            {
+                let item = body.as_ref().and_then(|body| body.get("item").cloned());
+                (
+                    "200 OK",
+                    serde_json::json!({ "orderId": "7", "item": item }).to_string(),
+                )
+            } else if request.starts_with("POST /topics/orders ")
+                && request.contains("content-type: application/vnd.kafka.json.v2+json")
+            {
+                let records = body
+                    .as_ref()
+                    .and_then(|body| body.get("records"))
+                    .and_then(serde_json::Value::as_array)
+                    .cloned()
+                    .unwrap_or_default();
+                if let Ok(mut consumed) = consumed.lock() {
+                    consumed.extend(records);
+                }
+                (
+                    "200 OK",
+                    r#"{"offsets":[{"partition":0,"offset":0}]}"#.to_string(),
+                )
+            } else if request.starts_with("GET /__twin/events/orders ") {
+                let events = consumed.lock().map(|c| c.clone()).unwrap_or_default();
+                ("200 OK", serde_json::Value::Array(events).to_string())
+            } else {
+                ("404 Not Found", "{}".to_string())
+            };
+            let reply = format!(
+                "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response}",
+                response.len()
+            );
+            let _ = socket.write_all(reply.as_bytes()).await;
+        }
+    });
+    Ok(endpoint)
+}
+
+#[tokio::test]
+async fn grpc_and_kafka_actions_reach_event_driven_entry_points() -> Result<()> {
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let endpoint = spawn_event_driven_system().await?;
+    let scenario = scenario_with_steps(
+        r#"
+  - id: create-order
+    description: Create an order over gRPC.
+    action:
+      type: grpc
+      url: "${application.endpoint}"
+      service: orders.v1.OrderService
+      rpc: CreateOrder
+      body:
+        item: widget
+    assertions:
+      - type: status
+        expected: 200
+      - type: body_json
+        path: /item
+        expected: widget
+    extractions:
+      - name: order_id
+        from: body
+        path: /orderId
+  - id: publish-paid
+    description: Publish the payment event.
+    action:
+      type: kafka-publish
+      url: "${twins.kafka.endpoint}"
+      topic: orders
+      key: "${extracted.order_id}"
+      body:
+        status: paid
+    assertions:
+      - type: status
+        expected: 200
+      - type: event_consumed
+        twin: kafka
+        topic: orders
+        count: 1
+        matches:
+          key: "${extracted.order_id}"
+          value:
+            status: paid
+      - type: event_consumed
+        twin: kafka
+        topic: refunds
+    extractions: []
+"#,
+    )?;
+    let twins = HashMap::from([("kafka".to_string(), endpoint.clone())]);
+
+    let result = ScenarioRunner::new(&endpoint, twins)
+        .run_scenario(&scenario)
+        .await;
+
+    assert!(result.steps[0].passed, "{:?}", result.steps[0].error);
+    let publish = &result.steps[1];
+    assert_eq!(publish.assertions_passed, 2);
+    assert_eq!(
+        publish.error.as_deref(),
+        Some("Twin kafka returned 404 Not Found for events/refunds")
+    );
+    Ok(())
+}
+
+fn write_approval_workflow(dir: &Path) -> Result<std::path::PathBuf> {
+    use oya_frontend::graph::{Connection, PortName, Workflow};
+
+    let mut workflow = Workflow::new();
+    let trigger = workflow.add_node("http-handler", 0.0, 0.0);
+    let check = workflow.add_node("condition", 0.0, 100.0);
+    let approve = workflow.add_node("run", -100.0, 200.0);
+    let reject = workflow.add_node("run", 100.0, 200.0);
+    for (id, name, config) in [
+        (trigger, "Order placed", serde_json::json!({})),
+        (
+            check,
+            "Auto approve?",
+            serde_json::json!({ "condition": "true" }),
+        ),
+        (
+            approve,
+            "Approve",
+            serde_json::json!({ "mapping": { "approved": true } }),
+        ),
+        (
+            reject,
+            "Reject",
+            serde_json::json!({ "mapping": { "approved": false } }),
+        ),
+    ] {
+        let node = workflow
+            .nodes
+            .iter_mut()
+            .find(|node| node.id == id)
+            .expect("node was just added");
+        node.name = name.to_string();
+        node.config = config;
+    }
+    let main = PortName("main".to_string());
+    let _ = workflow.add_connection(trigger, check, &main, &main);
+    for (port, target) in [("true", approve), ("false", reject)] {
+        workflow.connections.push(Connection {
+            id: uuid::Uuid::new_v4(),
+            source: check,
+            target,
+            source_port: PortName(port.to_string()),
+            target_port: main.clone(),
+        });
+    }
+
+    let path = dir.join("approval.json");
+    std::fs::write(&path, serde_json::to_string(&workflow)?)?;
+    Ok(path)
+}
+
+#[tokio::test]
+async fn workflow_steps_run_canvas_workflows_and_assert_on_nodes() -> Result<()> {
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let dir = tempfile::tempdir()?;
+    let workflow = write_approval_workflow(dir.path())?;
+    let scenario = scenario_with_steps(&format!(
+        r#"
+  - id: approve-order
+    description: Run the approval flow.
+    action:
+      type: workflow
+      workflow: "{}"
+    assertions:
+      - type: status
+        expected: 200
+      - type: node_status
+        node: Approve
+        expected: completed
+      - type: node_status
+        node: Reject
+        expected: skipped
+      - type: node_output
+        node: Approve
+        path: /approved
+        expected: true
+      - type: node_output
+        node: Approve
+        path: /reason
+        expected: auto
+    extractions: []
+"#,
+        workflow.display()
+    ))?;
+    let missing = scenario_with_steps(&format!(
+        r#"
+  - id: missing-flow
+    description: A missing workflow fails the step.
+    action:
+      type: workflow
+      workflow: "{}"
+    assertions:
+      - type: status
+        expected: 200
+    extractions: []
+"#,
+        dir.path().join("missing.json").display()
+    ))?;
+    let mut runner = ScenarioRunner::new("http://127.0.0.1:9", HashMap::new());
+
+    let result = runner.run_scenario(&scenario).await;
+    let missing = runner.run_scenario(&missing).await;
+
+    let run = &result.steps[0];
+    assert_eq!(run.assertions_passed, 4, "{:?}", run.error);
+    assert_eq!(
+        run.error.as_deref(),
+        Some("Node Approve has no output /reason")
+    );
+    assert!(!missing.passed);
+    Ok(())
+}
+
+#[tokio::test]
+async fn transparent_feedback_carries_failed_assertions_and_step_sequence() -> Result<()> {
+    use oya_frontend::feedback::FeedbackSanitizer;
+    use oya_frontend::scenario_runner::ScenarioRunner;
+
+    let endpoint = spawn_order_api().await?;
+    let scenario = scenario_with_steps(
+        r#"
+  - id: create
+    description: Create an order.
+    action:
+      type: http
+      method: POST
+      url: "${application.endpoint}/orders"
+      body:
+        item: widget
+    assertions:
+      - type: status
+        expected: 201
+    extractions: []
+  - id: fetch
+    description: Fetch the order, expecting it closed.
+    action:
+      type: http
+      method: GET
+      url: "${application.endpoint}/orders/7"
+      params:
+        expand: items
+    assertions:
+      - type: status
+        expected: 200
+      - type: body_json
+        path: /status
+        expected: closed
+    extractions: []
+"#,
+    )?;
+
+    let result = ScenarioRunner::new(&endpoint, HashMap::new())
+        .run_scenario(&scenario)
+        .await;
+    let results = [result];
+    let transparent = FeedbackSanitizer::new(5).sanitize(&results, 1);
+    let guided = FeedbackSanitizer::new(3).sanitize(&results, 1);
+
+    let details = transparent.failures[0].details.clone().unwrap();
+    assert_eq!(details.failed_step.as_deref(), Some("fetch"));
+    assert_eq!(
+        details
+            .steps
+            .iter()
+            .map(|step| (step.step_id.as_str(), step.passed))
+            .collect::<Vec<_>>(),
+        vec![("create", true), ("fetch", false)]
+    );
+    assert_eq!(details.assertions.len(), 1);
+    let assertion = &details.assertions[0];
+    assert_eq!(assertion.assertion_type, "body_json");
+    assert_eq!(assertion.path.as_deref(), Some("/status"));
+    assert_eq!(assertion.expected, Some(serde_json::json!("closed")));
+    assert_eq!(assertion.actual, Some(serde_json::json!("open")));
+    assert!(details.steps[1].duration_ms.is_some());
+    assert!(guided.failures[0].details.is_none());
+    Ok(())
+}
@@ -763,7 +763,7 @@ fn workflow_lifecycle_mixed_operations() {
     let mut workflow = Workflow::new();
 
     let a = workflow.add_node("http-trigger", 0.0, 0.0);
-    let b = workflow.add_node("http-handler", 100.0, 0.0);
+    let b = workflow.add_node("run", 100.0, 0.0);
     let _c = workflow.add_node("router", 200.0, 0.0);
     let d = workflow.add_node("run", 300.0, 0.0);
     let e = workflow.add_node("delay", 400.0, 0.0);
@@ -0,0 +1,198 @@
+//! Golden-file tests for scenario validation report renderers.
+//!
+//! Each format is rendered from the same fixed report and compared against a
+//! reviewed snapshot under `tests/snapshots/`.
+#![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
+
+use oya_frontend::scenario_runner::{
+    CategoryResult, ScenarioFilter, ScenarioResult, StepResult, ValidationReport,
+    ValidationReportFormat,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+fn step(step_id: &str, duration_ms: u64, failed: usize, error: Option<&str>) -> StepResult {
+    StepResult {
+        step_id: step_id.to_string(),
+        passed: failed == 0,
+        duration_ms,
+        assertions_passed: 2 - failed,
+        assertions_failed: failed,
+        error: error.map(str::to_string),
+        transcript: None,
+        failed_assertions: Vec::new(),
+    }
+}
+
+fn sample_report() -> ValidationReport {
+    ValidationReport {
+        spec_id: "spec-checkout".to_string(),
+        total_scenarios: 3,
+        passed_scenarios: 1,
+        failed_scenarios: 2,
+        results: vec![
+            ScenarioResult {
+                scenario_id: "checkout-happy-path".to_string(),
+                spec_ref: "spec-checkout".to_string(),
+                category: "happy-path".to_string(),
+                passed: true,
+                steps: vec![
+                    step("create-order", 120, 0, None),
+                    step("pay", 380, 0, None),
+                ],
+                total_duration_ms: 500,
+                error: None,
+            },
+            ScenarioResult {
+                scenario_id: "checkout-<script>".to_string(),
+                spec_ref: "spec-checkout".to_string(),
+                category: "security".to_string(),
+                passed: false,
+                steps: vec![
+                    step("login", 40, 0, None),
+                    step(
+                        "tamper",
+                        1210,
+                        1,
+                        Some("Path /total: expected 10, got \"0\" & more"),
+                    ),
+                ],
+                total_duration_ms: 1250,
+                error: None,
+            },
+            ScenarioResult {
+                scenario_id: "checkout-slow".to_string(),
+                spec_ref: "spec-checkout".to_string(),
+                category: "happy-path".to_string(),
+                passed: false,
+                steps: Vec::new(),
+                total_duration_ms: 0,
+                error: Some("Run timed out after 30000ms".to_string()),
+            },
+        ],
+        category_breakdown: HashMap::from([
+            (
+                "happy-path".to_string(),
+                CategoryResult {
+                    total: 2,
+                    passed: 1,
+                    failed: 1,
+                },
+            ),
+            (
+                "security".to_string(),
+                CategoryResult {
+                    total: 1,
+                    passed: 0,
+                    failed: 1,
+                },
+            ),
+        ]),
+        filter: None,
+        skipped_scenarios: 0,
+    }
+}
+
+#[test]
+fn given_sample_report_when_rendering_junit_then_output_matches_golden_file() {
+    let rendered = sample_report()
+        .render(ValidationReportFormat::JUnit)
+        .unwrap();
+
+    insta::assert_snapshot!("validation_report_junit", rendered);
+}
+
+#[test]
+fn given_sample_report_when_rendering_html_then_output_matches_golden_file() {
+    let rendered = sample_report()
+        .render(ValidationReportFormat::Html)
+        .unwrap();
+
+    insta::assert_snapshot!("validation_report_html", rendered);
+}
+
+#[test]
+fn given_rendered_json_when_parsing_then_report_round_trips() {
+    let report = sample_report();
+    let rendered = report.render(ValidationReportFormat::Json).unwrap();
+
+    let parsed = serde_json::from_str::<ValidationReport>(&rendered).unwrap();
+
+    assert_eq!(parsed, report);
+}
+
+#[test]
+fn given_markup_in_ids_and_errors_when_rendering_then_it_is_escaped() {
+    let junit = sample_report()
+        .render(ValidationReportFormat::JUnit)
+        .unwrap();
+    let html = sample_report()
+        .render(ValidationReportFormat::Html)
+        .unwrap();
+
+    for rendered in [&junit, &html] {
+        assert!(rendered.contains("checkout-&lt;script&gt;"));
+        assert!(!rendered.contains("<script"));
+        assert!(rendered.contains("got &quot;0&quot; &amp; more"));
+    }
+}
+
+#[test]
+fn given_report_written_to_file_when_reading_back_then_contents_match_render() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.xml");
+
+    sample_report()
+        .write_to(ValidationReportFormat::JUnit, &path)
+        .unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        sample_report()
+            .render(ValidationReportFormat::JUnit)
+            .unwrap()
+    );
+}
+
+#[test]
+fn given_format_names_when_parsing_then_known_formats_resolve() {
+    assert_eq!(
+        ValidationReportFormat::from_str("xml"),
+        Ok(ValidationReportFormat::JUnit)
+    );
+    assert_eq!(
+        ValidationReportFormat::from_str("html"),
+        Ok(ValidationReportFormat::Html)
+    );
+    assert!(ValidationReportFormat::from_str("pdf").is_err());
+}
+
+#[test]
+fn given_filtered_run_when_rendering_then_filter_and_skipped_count_are_recorded() {
+    let report = ValidationReport {
+        filter: Some(ScenarioFilter {
+            categories: vec!["security".to_string()],
+            exclude_tags: vec!["slow".to_string()],
+            ..ScenarioFilter::default()
+        }),
+        skipped_scenarios: 4,
+        ..sample_report()
+    };
+
+    let junit = report.render(ValidationReportFormat::JUnit).unwrap();
+    let html = report.render(ValidationReportFormat::Html).unwrap();
+    let json = report.render(ValidationReportFormat::Json).unwrap();
+
+    assert!(junit.contains(
+        "<property name=\"filter\" value=\"category in [security]; not tag in [slow]\"/>"
+    ));
+    assert!(junit.contains("<property name=\"skipped_scenarios\" value=\"4\"/>"));
+    assert!(html
+        .contains("<p>Filter: category in [security]; not tag in [slow] &middot; 4 skipped</p>"));
+    assert_eq!(
+        serde_json::from_str::<ValidationReport>(&json)
+            .unwrap()
+            .filter,
+        report.filter
+    );
+}